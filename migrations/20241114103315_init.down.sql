@@ -0,0 +1,4 @@
+-- Revert base licenses table
+-- WARNING: deletes every license
+
+DROP TABLE IF EXISTS licenses;
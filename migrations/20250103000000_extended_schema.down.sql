@@ -0,0 +1,32 @@
+-- Revert extended schema
+-- Requires SQLite 3.35+ (ALTER TABLE ... DROP COLUMN)
+
+DROP INDEX IF EXISTS idx_binding_history_created_at;
+DROP INDEX IF EXISTS idx_binding_history_license_id;
+DROP TABLE IF EXISTS license_binding_history;
+
+-- Indexes must go before the columns they cover
+DROP INDEX IF EXISTS idx_licenses_tier;
+DROP INDEX IF EXISTS idx_licenses_expires_at;
+DROP INDEX IF EXISTS idx_licenses_status;
+DROP INDEX IF EXISTS idx_licenses_hardware_id;
+DROP INDEX IF EXISTS idx_licenses_license_key;
+DROP INDEX IF EXISTS idx_licenses_org_id;
+
+ALTER TABLE licenses DROP COLUMN metadata;
+ALTER TABLE licenses DROP COLUMN blacklist_reason;
+ALTER TABLE licenses DROP COLUMN blacklisted_at;
+ALTER TABLE licenses DROP COLUMN is_blacklisted;
+ALTER TABLE licenses DROP COLUMN suspension_message;
+ALTER TABLE licenses DROP COLUMN grace_period_ends_at;
+ALTER TABLE licenses DROP COLUMN revoke_reason;
+ALTER TABLE licenses DROP COLUMN revoked_at;
+ALTER TABLE licenses DROP COLUMN suspended_at;
+ALTER TABLE licenses DROP COLUMN last_seen_at;
+ALTER TABLE licenses DROP COLUMN bound_at;
+ALTER TABLE licenses DROP COLUMN device_info;
+ALTER TABLE licenses DROP COLUMN device_name;
+ALTER TABLE licenses DROP COLUMN tier;
+ALTER TABLE licenses DROP COLUMN license_key;
+ALTER TABLE licenses DROP COLUMN org_name;
+ALTER TABLE licenses DROP COLUMN org_id;
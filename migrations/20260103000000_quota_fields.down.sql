@@ -0,0 +1,7 @@
+-- Revert quota tracking fields
+
+DROP INDEX IF EXISTS idx_licenses_quota_exceeded;
+
+ALTER TABLE licenses DROP COLUMN quota_exceeded;
+ALTER TABLE licenses DROP COLUMN bandwidth_limit_bytes;
+ALTER TABLE licenses DROP COLUMN bandwidth_used_bytes;
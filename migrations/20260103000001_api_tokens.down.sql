@@ -0,0 +1,4 @@
+-- Revert API tokens table
+-- WARNING: every API token stops working
+
+DROP TABLE IF EXISTS api_tokens;
@@ -0,0 +1,6 @@
+-- Revert multi-seat licenses
+-- The licenses binding fields keep the most recently bound device
+
+DROP TABLE IF EXISTS license_devices;
+
+ALTER TABLE licenses DROP COLUMN max_devices;
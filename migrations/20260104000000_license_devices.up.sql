@@ -0,0 +1,24 @@
+-- Multi-seat licenses: per-license device limit and one row per bound device
+
+-- Number of devices that may hold a seat at the same time
+ALTER TABLE licenses ADD COLUMN max_devices INTEGER NOT NULL DEFAULT 1;
+
+CREATE TABLE IF NOT EXISTS license_devices (
+    license_id TEXT NOT NULL,
+    hardware_id TEXT NOT NULL,
+    device_name TEXT,
+    device_info TEXT,
+    bound_at TIMESTAMP NOT NULL,
+    last_seen_at TIMESTAMP,
+    PRIMARY KEY (license_id, hardware_id),
+    FOREIGN KEY (license_id) REFERENCES licenses(license_id)
+);
+
+-- Index for the stale device cleanup job
+CREATE INDEX IF NOT EXISTS idx_license_devices_last_seen_at ON license_devices(last_seen_at);
+
+-- Existing single-device bindings become the first seat of their license
+INSERT INTO license_devices (license_id, hardware_id, device_name, device_info, bound_at, last_seen_at)
+SELECT license_id, hardware_id, device_name, device_info, bound_at, last_seen_at
+FROM licenses
+WHERE hardware_id IS NOT NULL AND bound_at IS NOT NULL;
@@ -0,0 +1,16 @@
+-- Floating licenses: per-license concurrency limit and one row per checked-out lease
+
+-- Number of devices that may hold a lease at the same time (NULL = unlimited)
+ALTER TABLE licenses ADD COLUMN max_concurrent INTEGER;
+
+CREATE TABLE IF NOT EXISTS license_leases (
+    license_id TEXT NOT NULL,
+    hardware_id TEXT NOT NULL,
+    checked_out_at TIMESTAMP NOT NULL,
+    expires_at TIMESTAMP NOT NULL,
+    PRIMARY KEY (license_id, hardware_id),
+    FOREIGN KEY (license_id) REFERENCES licenses(license_id)
+);
+
+-- Index for counting active leases and purging expired ones
+CREATE INDEX IF NOT EXISTS idx_license_leases_expires_at ON license_leases(expires_at);
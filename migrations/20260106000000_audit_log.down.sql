@@ -0,0 +1,4 @@
+-- Revert audit log
+-- WARNING: deletes the recorded audit history
+
+DROP TABLE IF EXISTS audit_log;
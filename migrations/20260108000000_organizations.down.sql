@@ -0,0 +1,3 @@
+-- Revert organizations (licenses keep their org_id/org_name copies)
+
+DROP TABLE IF EXISTS organizations;
@@ -0,0 +1,32 @@
+-- Organizations that licenses belong to
+
+CREATE TABLE IF NOT EXISTS organizations (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    contact_name TEXT,
+    contact_email TEXT,
+    -- JSON object of free-form metadata
+    metadata TEXT,
+    -- Tier and JSON array of features given to new licenses that name none
+    default_tier TEXT,
+    default_features TEXT,
+    -- active or suspended
+    status TEXT NOT NULL DEFAULT 'active',
+    suspended_at TIMESTAMP,
+    suspension_reason TEXT,
+    created_at TIMESTAMP NOT NULL,
+    updated_at TIMESTAMP NOT NULL
+);
+
+-- Index for listing organizations by status
+CREATE INDEX IF NOT EXISTS idx_organizations_status ON organizations(status, created_at);
+
+-- Register every organization licenses already refer to
+INSERT INTO organizations (id, name, created_at, updated_at)
+SELECT org_id,
+       COALESCE(MAX(org_name), org_id),
+       COALESCE(MIN(issued_at), CURRENT_TIMESTAMP),
+       COALESCE(MIN(issued_at), CURRENT_TIMESTAMP)
+FROM licenses
+WHERE org_id IS NOT NULL
+GROUP BY org_id;
@@ -0,0 +1,3 @@
+-- Revert license listing indexes
+
+DROP INDEX IF EXISTS idx_licenses_issued_at;
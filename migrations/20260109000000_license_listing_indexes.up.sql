@@ -0,0 +1,4 @@
+-- Indexes for sorting and filtering license listings
+
+-- Default sort order of GET /api/v1/licenses
+CREATE INDEX IF NOT EXISTS idx_licenses_issued_at ON licenses(issued_at);
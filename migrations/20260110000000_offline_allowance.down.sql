@@ -0,0 +1,3 @@
+-- Revert offline allowance
+
+ALTER TABLE licenses DROP COLUMN offline_days;
@@ -0,0 +1,4 @@
+-- Offline allowance: per-license override for how many days a client may run offline
+
+-- Days of offline use after each successful check (NULL = tier or server default)
+ALTER TABLE licenses ADD COLUMN offline_days INTEGER;
@@ -0,0 +1,3 @@
+-- Revert binding history hardware index
+
+DROP INDEX IF EXISTS idx_binding_history_hardware_id;
@@ -0,0 +1,3 @@
+-- Revert trial registrations
+
+DROP TABLE IF EXISTS trial_registrations;
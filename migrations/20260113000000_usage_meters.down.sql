@@ -0,0 +1,4 @@
+-- Revert named usage meters
+
+DROP TABLE IF EXISTS usage_reports;
+DROP TABLE IF EXISTS usage_meters;
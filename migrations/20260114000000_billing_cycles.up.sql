@@ -0,0 +1,19 @@
+-- Billing cycles: automatic usage resets with a history of past periods
+
+-- 'monthly', a cron expression or 'none' (NULL = the tier's cycle)
+ALTER TABLE licenses ADD COLUMN billing_cycle TEXT;
+-- Start of the current usage period (NULL = issued_at)
+ALTER TABLE licenses ADD COLUMN usage_period_start TIMESTAMP;
+
+-- Usage of each meter in each closed period
+CREATE TABLE IF NOT EXISTS usage_periods (
+    license_id TEXT NOT NULL REFERENCES licenses(license_id),
+    meter TEXT NOT NULL,
+    period_start TIMESTAMP NOT NULL,
+    period_end TIMESTAMP NOT NULL,
+    used BIGINT NOT NULL,
+    usage_limit BIGINT,
+    PRIMARY KEY (license_id, meter, period_start)
+);
+
+CREATE INDEX IF NOT EXISTS idx_usage_periods_period_end ON usage_periods(period_end);
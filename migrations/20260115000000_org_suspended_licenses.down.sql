@@ -0,0 +1,3 @@
+-- Revert org suspended licenses
+
+ALTER TABLE licenses DROP COLUMN suspended_by_org;
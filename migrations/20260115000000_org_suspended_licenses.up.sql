@@ -0,0 +1,5 @@
+-- Mark licenses suspended by their organization's suspension
+
+-- Set when suspending the organization suspended the license, so reinstating
+-- the organization only reactivates those
+ALTER TABLE licenses ADD COLUMN suspended_by_org INTEGER;
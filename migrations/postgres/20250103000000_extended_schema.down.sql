@@ -0,0 +1,27 @@
+-- Revert extended schema (PostgreSQL)
+-- Dropping a column also drops the indexes and constraints on it
+
+DROP TABLE IF EXISTS license_binding_history;
+
+-- These cover columns from the base table
+DROP INDEX IF EXISTS idx_licenses_expires_at;
+DROP INDEX IF EXISTS idx_licenses_status;
+DROP INDEX IF EXISTS idx_licenses_hardware_id;
+
+ALTER TABLE licenses DROP COLUMN IF EXISTS metadata;
+ALTER TABLE licenses DROP COLUMN IF EXISTS blacklist_reason;
+ALTER TABLE licenses DROP COLUMN IF EXISTS blacklisted_at;
+ALTER TABLE licenses DROP COLUMN IF EXISTS is_blacklisted;
+ALTER TABLE licenses DROP COLUMN IF EXISTS suspension_message;
+ALTER TABLE licenses DROP COLUMN IF EXISTS grace_period_ends_at;
+ALTER TABLE licenses DROP COLUMN IF EXISTS revoke_reason;
+ALTER TABLE licenses DROP COLUMN IF EXISTS revoked_at;
+ALTER TABLE licenses DROP COLUMN IF EXISTS suspended_at;
+ALTER TABLE licenses DROP COLUMN IF EXISTS last_seen_at;
+ALTER TABLE licenses DROP COLUMN IF EXISTS bound_at;
+ALTER TABLE licenses DROP COLUMN IF EXISTS device_info;
+ALTER TABLE licenses DROP COLUMN IF EXISTS device_name;
+ALTER TABLE licenses DROP COLUMN IF EXISTS tier;
+ALTER TABLE licenses DROP COLUMN IF EXISTS license_key;
+ALTER TABLE licenses DROP COLUMN IF EXISTS org_name;
+ALTER TABLE licenses DROP COLUMN IF EXISTS org_id;
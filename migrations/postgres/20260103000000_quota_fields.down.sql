@@ -0,0 +1,5 @@
+-- Revert quota tracking fields (PostgreSQL)
+
+ALTER TABLE licenses DROP COLUMN IF EXISTS quota_exceeded;
+ALTER TABLE licenses DROP COLUMN IF EXISTS bandwidth_limit_bytes;
+ALTER TABLE licenses DROP COLUMN IF EXISTS bandwidth_used_bytes;
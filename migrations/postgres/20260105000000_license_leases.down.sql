@@ -0,0 +1,5 @@
+-- Revert floating leases
+
+DROP TABLE IF EXISTS license_leases;
+
+ALTER TABLE licenses DROP COLUMN IF EXISTS max_concurrent;
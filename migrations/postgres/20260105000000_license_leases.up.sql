@@ -0,0 +1,15 @@
+-- Floating licenses: per-license concurrency limit and one row per checked-out lease (PostgreSQL version)
+
+-- Number of devices that may hold a lease at the same time (NULL = unlimited)
+ALTER TABLE licenses ADD COLUMN IF NOT EXISTS max_concurrent INTEGER;
+
+CREATE TABLE IF NOT EXISTS license_leases (
+    license_id TEXT NOT NULL REFERENCES licenses(license_id),
+    hardware_id TEXT NOT NULL,
+    checked_out_at TIMESTAMP NOT NULL,
+    expires_at TIMESTAMP NOT NULL,
+    PRIMARY KEY (license_id, hardware_id)
+);
+
+-- Index for counting active leases and purging expired ones
+CREATE INDEX IF NOT EXISTS idx_license_leases_expires_at ON license_leases(expires_at);
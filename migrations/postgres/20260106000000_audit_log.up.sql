@@ -0,0 +1,23 @@
+-- Audit log: one row per admin or token operation (PostgreSQL version)
+
+CREATE TABLE IF NOT EXISTS audit_log (
+    id TEXT PRIMARY KEY,
+    occurred_at TIMESTAMP NOT NULL,
+    -- JWT subject or API token name of the caller
+    actor TEXT NOT NULL,
+    -- API token id when the caller used a talos_... token
+    actor_token_id TEXT,
+    action TEXT NOT NULL,
+    target_type TEXT NOT NULL,
+    target_id TEXT NOT NULL,
+    -- JSON objects holding only the fields that changed
+    before_state TEXT,
+    after_state TEXT,
+    ip_address TEXT,
+    request_id TEXT
+);
+
+-- Indexes for the filters offered by GET /api/v1/audit
+CREATE INDEX IF NOT EXISTS idx_audit_log_occurred_at ON audit_log(occurred_at);
+CREATE INDEX IF NOT EXISTS idx_audit_log_target ON audit_log(target_type, target_id);
+CREATE INDEX IF NOT EXISTS idx_audit_log_actor ON audit_log(actor);
@@ -0,0 +1,3 @@
+-- Revert offline allowance
+
+ALTER TABLE licenses DROP COLUMN IF EXISTS offline_days;
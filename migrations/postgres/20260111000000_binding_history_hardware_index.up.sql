@@ -0,0 +1,4 @@
+-- Index for looking up the binding history of a device
+
+-- Reverse lookup of GET /api/v1/devices/{hardware_id}/history
+CREATE INDEX IF NOT EXISTS idx_binding_history_hardware_id ON license_binding_history(hardware_id);
@@ -0,0 +1,15 @@
+-- Registry of self-service trials (PostgreSQL version)
+
+-- One row per machine and product; '' when trials are not scoped per product
+CREATE TABLE IF NOT EXISTS trial_registrations (
+    hardware_id TEXT NOT NULL,
+    product TEXT NOT NULL DEFAULT '',
+    license_id TEXT NOT NULL REFERENCES licenses(license_id),
+    started_at TIMESTAMP NOT NULL,
+    -- Set when an admin converts the trial into a paid license
+    converted_at TIMESTAMP,
+    PRIMARY KEY (hardware_id, product)
+);
+
+-- Index for looking up the trial behind a license
+CREATE INDEX IF NOT EXISTS idx_trial_registrations_license_id ON trial_registrations(license_id);
@@ -0,0 +1,28 @@
+-- Named usage meters per license (PostgreSQL version)
+
+-- Current usage of each meter; a meter gets a row on its first report
+CREATE TABLE IF NOT EXISTS usage_meters (
+    license_id TEXT NOT NULL REFERENCES licenses(license_id),
+    meter TEXT NOT NULL,
+    used BIGINT NOT NULL DEFAULT 0,
+    -- Per-license limit overriding the tier's; 0 means unlimited
+    usage_limit BIGINT,
+    updated_at TIMESTAMP NOT NULL,
+    PRIMARY KEY (license_id, meter)
+);
+
+-- Usage reports already applied, so a retried report is not counted twice
+CREATE TABLE IF NOT EXISTS usage_reports (
+    license_id TEXT NOT NULL REFERENCES licenses(license_id),
+    report_id TEXT NOT NULL,
+    meter TEXT NOT NULL,
+    amount BIGINT NOT NULL,
+    reported_at TIMESTAMP NOT NULL,
+    PRIMARY KEY (license_id, report_id)
+);
+
+-- Bandwidth becomes the built-in 'bandwidth' meter
+INSERT INTO usage_meters (license_id, meter, used, usage_limit, updated_at)
+SELECT license_id, 'bandwidth', COALESCE(bandwidth_used_bytes, 0), bandwidth_limit_bytes, CURRENT_TIMESTAMP
+FROM licenses
+WHERE COALESCE(bandwidth_used_bytes, 0) <> 0 OR bandwidth_limit_bytes IS NOT NULL;
@@ -0,0 +1,6 @@
+-- Revert billing cycles
+
+DROP INDEX IF EXISTS idx_usage_periods_period_end;
+DROP TABLE IF EXISTS usage_periods;
+ALTER TABLE licenses DROP COLUMN IF EXISTS usage_period_start;
+ALTER TABLE licenses DROP COLUMN IF EXISTS billing_cycle;
@@ -0,0 +1,5 @@
+-- Mark licenses suspended by their organization's suspension (PostgreSQL version)
+
+-- Set when suspending the organization suspended the license, so reinstating
+-- the organization only reactivates those
+ALTER TABLE licenses ADD COLUMN IF NOT EXISTS suspended_by_org BOOLEAN;
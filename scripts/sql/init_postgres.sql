@@ -56,7 +56,23 @@ CREATE TABLE IF NOT EXISTS licenses (
     -- Quota/Usage tracking
     bandwidth_used_bytes BIGINT DEFAULT 0,
     bandwidth_limit_bytes BIGINT,
-    quota_exceeded  BOOLEAN DEFAULT FALSE
+    quota_exceeded  BOOLEAN DEFAULT FALSE,
+
+    -- Multi-seat licensing
+    max_devices     INTEGER NOT NULL DEFAULT 1,
+
+    -- Floating licensing (NULL = unlimited)
+    max_concurrent  INTEGER,
+
+    -- Offline allowance in days (NULL = tier or server default)
+    offline_days    INTEGER,
+
+    -- Billing cycle ('monthly', cron or 'none'; NULL = tier's) and current period start
+    billing_cycle   TEXT,
+    usage_period_start TIMESTAMP WITH TIME ZONE,
+
+    -- Suspended by the organization's suspension
+    suspended_by_org BOOLEAN
 );
 
 -- Indexes for licenses
@@ -67,6 +83,7 @@ CREATE INDEX IF NOT EXISTS idx_licenses_status ON licenses(status);
 CREATE INDEX IF NOT EXISTS idx_licenses_expires_at ON licenses(expires_at);
 CREATE INDEX IF NOT EXISTS idx_licenses_tier ON licenses(tier);
 CREATE INDEX IF NOT EXISTS idx_licenses_quota_exceeded ON licenses(quota_exceeded);
+CREATE INDEX IF NOT EXISTS idx_licenses_issued_at ON licenses(issued_at);
 
 -- =============================================================================
 -- License Binding History Table
@@ -85,6 +102,35 @@ CREATE TABLE IF NOT EXISTS license_binding_history (
 
 CREATE INDEX IF NOT EXISTS idx_binding_history_license_id ON license_binding_history(license_id);
 CREATE INDEX IF NOT EXISTS idx_binding_history_created_at ON license_binding_history(created_at);
+CREATE INDEX IF NOT EXISTS idx_binding_history_hardware_id ON license_binding_history(hardware_id);
+
+-- =============================================================================
+-- License Devices Table (one row per occupied seat)
+-- =============================================================================
+CREATE TABLE IF NOT EXISTS license_devices (
+    license_id      TEXT NOT NULL REFERENCES licenses(license_id),
+    hardware_id     TEXT NOT NULL,
+    device_name     TEXT,
+    device_info     TEXT,
+    bound_at        TIMESTAMP WITH TIME ZONE NOT NULL,
+    last_seen_at    TIMESTAMP WITH TIME ZONE,
+    PRIMARY KEY (license_id, hardware_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_license_devices_last_seen_at ON license_devices(last_seen_at);
+
+-- =============================================================================
+-- License Leases Table (one row per checked-out floating lease)
+-- =============================================================================
+CREATE TABLE IF NOT EXISTS license_leases (
+    license_id      TEXT NOT NULL REFERENCES licenses(license_id),
+    hardware_id     TEXT NOT NULL,
+    checked_out_at  TIMESTAMP WITH TIME ZONE NOT NULL,
+    expires_at      TIMESTAMP WITH TIME ZONE NOT NULL,
+    PRIMARY KEY (license_id, hardware_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_license_leases_expires_at ON license_leases(expires_at);
 
 -- =============================================================================
 -- API Tokens Table
@@ -104,6 +150,133 @@ CREATE TABLE IF NOT EXISTS api_tokens (
 CREATE INDEX IF NOT EXISTS idx_api_tokens_hash ON api_tokens(token_hash);
 CREATE INDEX IF NOT EXISTS idx_api_tokens_revoked ON api_tokens(revoked_at);
 
+-- =============================================================================
+-- Audit Log Table
+-- =============================================================================
+CREATE TABLE IF NOT EXISTS audit_log (
+    id              TEXT PRIMARY KEY,
+    occurred_at     TIMESTAMP WITH TIME ZONE NOT NULL,
+    actor           TEXT NOT NULL,
+    actor_token_id  TEXT,
+    action          TEXT NOT NULL,
+    target_type     TEXT NOT NULL,
+    target_id       TEXT NOT NULL,
+    before_state    TEXT,
+    after_state     TEXT,
+    ip_address      TEXT,
+    request_id      TEXT
+);
+
+CREATE INDEX IF NOT EXISTS idx_audit_log_occurred_at ON audit_log(occurred_at);
+CREATE INDEX IF NOT EXISTS idx_audit_log_target ON audit_log(target_type, target_id);
+CREATE INDEX IF NOT EXISTS idx_audit_log_actor ON audit_log(actor);
+
+-- =============================================================================
+-- Webhooks Tables
+-- =============================================================================
+CREATE TABLE IF NOT EXISTS webhooks (
+    id              TEXT PRIMARY KEY,
+    url             TEXT NOT NULL,
+    secret          TEXT NOT NULL,
+    events          TEXT NOT NULL,
+    description     TEXT,
+    is_active       BOOLEAN NOT NULL DEFAULT TRUE,
+    created_at      TIMESTAMP WITH TIME ZONE NOT NULL,
+    updated_at      TIMESTAMP WITH TIME ZONE NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS webhook_deliveries (
+    id              TEXT PRIMARY KEY,
+    webhook_id      TEXT NOT NULL REFERENCES webhooks(id) ON DELETE CASCADE,
+    event_id        TEXT NOT NULL,
+    event           TEXT NOT NULL,
+    payload         TEXT NOT NULL,
+    status          TEXT NOT NULL DEFAULT 'pending',
+    attempts        INTEGER NOT NULL DEFAULT 0,
+    next_attempt_at TIMESTAMP WITH TIME ZONE,
+    last_attempt_at TIMESTAMP WITH TIME ZONE,
+    response_status INTEGER,
+    last_error      TEXT,
+    created_at      TIMESTAMP WITH TIME ZONE NOT NULL,
+    delivered_at    TIMESTAMP WITH TIME ZONE
+);
+
+CREATE INDEX IF NOT EXISTS idx_webhook_deliveries_due ON webhook_deliveries(status, next_attempt_at);
+CREATE INDEX IF NOT EXISTS idx_webhook_deliveries_webhook ON webhook_deliveries(webhook_id, created_at);
+
+-- =============================================================================
+-- Organizations Table
+-- =============================================================================
+CREATE TABLE IF NOT EXISTS organizations (
+    id                  TEXT PRIMARY KEY,
+    name                TEXT NOT NULL,
+    contact_name        TEXT,
+    contact_email       TEXT,
+    metadata            TEXT,
+    default_tier        TEXT,
+    default_features    TEXT,
+    status              TEXT NOT NULL DEFAULT 'active',
+    suspended_at        TIMESTAMP,
+    suspension_reason   TEXT,
+    created_at          TIMESTAMP NOT NULL,
+    updated_at          TIMESTAMP NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_organizations_status ON organizations(status, created_at);
+
+-- =============================================================================
+-- Trial Registrations Table (one trial per machine and product)
+-- =============================================================================
+CREATE TABLE IF NOT EXISTS trial_registrations (
+    hardware_id     TEXT NOT NULL,
+    product         TEXT NOT NULL DEFAULT '',
+    license_id      TEXT NOT NULL REFERENCES licenses(license_id),
+    started_at      TIMESTAMP WITH TIME ZONE NOT NULL,
+    converted_at    TIMESTAMP WITH TIME ZONE,
+    PRIMARY KEY (hardware_id, product)
+);
+
+CREATE INDEX IF NOT EXISTS idx_trial_registrations_license_id ON trial_registrations(license_id);
+
+-- =============================================================================
+-- Usage Meters Table (current usage per license and meter)
+-- =============================================================================
+CREATE TABLE IF NOT EXISTS usage_meters (
+    license_id      TEXT NOT NULL REFERENCES licenses(license_id),
+    meter           TEXT NOT NULL,
+    used            BIGINT NOT NULL DEFAULT 0,
+    usage_limit     BIGINT,
+    updated_at      TIMESTAMP WITH TIME ZONE NOT NULL,
+    PRIMARY KEY (license_id, meter)
+);
+
+-- =============================================================================
+-- Usage Reports Table (applied reports, for idempotent reporting)
+-- =============================================================================
+CREATE TABLE IF NOT EXISTS usage_reports (
+    license_id      TEXT NOT NULL REFERENCES licenses(license_id),
+    report_id       TEXT NOT NULL,
+    meter           TEXT NOT NULL,
+    amount          BIGINT NOT NULL,
+    reported_at     TIMESTAMP WITH TIME ZONE NOT NULL,
+    PRIMARY KEY (license_id, report_id)
+);
+
+-- =============================================================================
+-- Usage Periods Table (usage of each meter in closed billing periods)
+-- =============================================================================
+CREATE TABLE IF NOT EXISTS usage_periods (
+    license_id      TEXT NOT NULL REFERENCES licenses(license_id),
+    meter           TEXT NOT NULL,
+    period_start    TIMESTAMP WITH TIME ZONE NOT NULL,
+    period_end      TIMESTAMP WITH TIME ZONE NOT NULL,
+    used            BIGINT NOT NULL,
+    usage_limit     BIGINT,
+    PRIMARY KEY (license_id, meter, period_start)
+);
+
+CREATE INDEX IF NOT EXISTS idx_usage_periods_period_end ON usage_periods(period_end);
+
 -- =============================================================================
 -- Grant privileges (for non-superuser connections)
 -- =============================================================================
@@ -0,0 +1,170 @@
+//! Manual offline activation for machines that never reach the license server.
+//!
+//! The exchange uses two files carried across the air gap:
+//!
+//! 1. The target machine writes an [`ActivationRequest`] (license key, hardware
+//!    ID, device info) with `License::activation_request()`.
+//! 2. An admin uploads it to `POST /api/v1/licenses/offline-activation`, which
+//!    binds the license and returns an [`ActivationResponse`] containing a
+//!    signed license document.
+//! 3. The target machine loads the response with `License::import_activation()`,
+//!    which verifies the signature and stores the cached validation.
+//!
+//! ```rust,ignore
+//! use talos::client::activation::{ActivationRequest, ActivationResponse};
+//! use talos::client::License;
+//!
+//! let mut license = License::new(key, String::new()).with_public_key(&PUBLIC_KEY);
+//! license
+//!     .activation_request(Some("Line 3 controller"), None)
+//!     .save("activation-request.json")
+//!     .await?;
+//!
+//! // ...later, after the admin returns the response file
+//! let response = ActivationResponse::load("activation-response.json").await?;
+//! license.import_activation(&response).await?;
+//! ```
+
+use std::path::Path;
+
+use chrono::Utc;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use tokio::fs;
+
+use crate::errors::{LicenseError, LicenseResult};
+use crate::signing::SignedLicense;
+
+#[cfg(feature = "openapi")]
+use utoipa::ToSchema;
+
+/// Current version of the activation file format.
+pub const ACTIVATION_FORMAT_VERSION: u8 = 1;
+
+/// Activation request generated on the air-gapped machine.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct ActivationRequest {
+    /// File format version
+    pub version: u8,
+    /// The human-readable license key
+    pub license_key: String,
+    /// Hardware fingerprint of the target machine
+    pub hardware_id: String,
+    /// Optional device name for display purposes
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub device_name: Option<String>,
+    /// Optional device info (OS, CPU, etc.)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub device_info: Option<String>,
+    /// When the request was generated (RFC 3339)
+    pub created_at: String,
+}
+
+impl ActivationRequest {
+    /// Create a new activation request.
+    pub fn new(
+        license_key: String,
+        hardware_id: String,
+        device_name: Option<String>,
+        device_info: Option<String>,
+    ) -> Self {
+        Self {
+            version: ACTIVATION_FORMAT_VERSION,
+            license_key,
+            hardware_id,
+            device_name,
+            device_info,
+            created_at: Utc::now().to_rfc3339(),
+        }
+    }
+
+    /// Write the request to a JSON file.
+    pub async fn save(&self, path: impl AsRef<Path>) -> LicenseResult<()> {
+        write_json(self, path.as_ref()).await
+    }
+
+    /// Read a request from a JSON file.
+    pub async fn load(path: impl AsRef<Path>) -> LicenseResult<Self> {
+        read_json(path.as_ref()).await
+    }
+}
+
+/// Activation response issued by the server for an [`ActivationRequest`].
+///
+/// All license data lives in the signed document; nothing outside it is trusted.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct ActivationResponse {
+    /// File format version
+    pub version: u8,
+    /// Signed license document bound to the requesting hardware
+    pub signed_license: SignedLicense,
+}
+
+impl ActivationResponse {
+    /// Create a new activation response.
+    pub fn new(signed_license: SignedLicense) -> Self {
+        Self {
+            version: ACTIVATION_FORMAT_VERSION,
+            signed_license,
+        }
+    }
+
+    /// Write the response to a JSON file.
+    pub async fn save(&self, path: impl AsRef<Path>) -> LicenseResult<()> {
+        write_json(self, path.as_ref()).await
+    }
+
+    /// Read a response from a JSON file.
+    pub async fn load(path: impl AsRef<Path>) -> LicenseResult<Self> {
+        read_json(path.as_ref()).await
+    }
+}
+
+async fn write_json<T: Serialize>(value: &T, path: &Path) -> LicenseResult<()> {
+    let json = serde_json::to_vec_pretty(value).map_err(|e| {
+        LicenseError::InvalidLicense(format!("failed to encode activation file: {e}"))
+    })?;
+    fs::write(path, json).await?;
+    Ok(())
+}
+
+async fn read_json<T: DeserializeOwned>(path: &Path) -> LicenseResult<T> {
+    let bytes = fs::read(path).await?;
+    serde_json::from_slice(&bytes)
+        .map_err(|e| LicenseError::InvalidLicense(format!("invalid activation file: {e}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_REQUEST_FILE: &str = "talos_activation_request_test.json";
+
+    #[tokio::test]
+    async fn request_file_roundtrip() {
+        let request = ActivationRequest::new(
+            "LIC-AAAA-BBBB-CCCC".to_string(),
+            "hw-123".to_string(),
+            Some("Controller".to_string()),
+            None,
+        );
+
+        request.save(TEST_REQUEST_FILE).await.unwrap();
+        let loaded = ActivationRequest::load(TEST_REQUEST_FILE).await.unwrap();
+        let _ = fs::remove_file(TEST_REQUEST_FILE).await;
+
+        assert_eq!(loaded, request);
+        assert_eq!(loaded.version, ACTIVATION_FORMAT_VERSION);
+    }
+
+    #[tokio::test]
+    async fn load_rejects_invalid_file() {
+        let path = "talos_activation_invalid_test.json";
+        fs::write(path, b"not json").await.unwrap();
+        let result = ActivationResponse::load(path).await;
+        let _ = fs::remove_file(path).await;
+
+        assert!(matches!(result, Err(LicenseError::InvalidLicense(_))));
+    }
+}
@@ -1,7 +1,9 @@
 //! Secure cached validation state for offline/air-gapped systems.
 //!
 //! This module provides encrypted, hardware-bound storage for license validation
-//! state that can be used for offline validation during grace periods.
+//! state that can be used for offline validation when the server can't be
+//! reached: within the offline allowance of a healthy license, or within the
+//! grace period of a suspended or air-gapped one.
 //!
 //! ## Security Model
 //!
@@ -10,18 +12,23 @@
 //! 1. **AES-256-GCM encryption** - Data is encrypted at rest
 //! 2. **Hardware binding** - Encryption key is derived from hardware fingerprint
 //! 3. **Tamper detection** - GCM authentication tag prevents modification
-//! 4. **Server authority** - Grace period comes from server, cannot be forged
+//! 4. **Server authority** - Offline allowance and grace period come from the
+//!    server, cannot be forged
+//! 5. **Signature** - When the server has a signing key, the cache also holds an
+//!    Ed25519-signed license document that is verified against the public key
+//!    embedded in the application (see [`crate::signing`])
 //!
 //! A user cannot:
 //! - Read the cache contents without the hardware-bound key
 //! - Modify the cache (authentication tag would fail)
 //! - Copy the cache to another machine (different hardware = different key)
-//! - Extend the grace period (server-provided, stored encrypted)
+//! - Extend the offline allowance or grace period (server-provided, stored encrypted)
 
 use crate::client::storage::{clear_from_storage, load_from_storage, save_to_storage, StorageKey};
 use crate::encryption::{decrypt_from_base64, encrypt_to_base64, KEY_SIZE};
 use crate::errors::{LicenseError, LicenseResult};
 use crate::hardware::get_hardware_id;
+use crate::signing::{latest_deadline, SignedLicense};
 
 use chrono::{DateTime, Utc};
 use ring::digest::{digest, SHA256};
@@ -60,8 +67,19 @@ pub struct CachedValidation {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub grace_period_ends_at: Option<String>,
 
+    /// Until when the license may be used offline (ISO 8601)
+    ///
+    /// Granted by the server on every successful validate and heartbeat,
+    /// independently of any suspension grace period.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub offline_valid_until: Option<String>,
+
     /// When this cache was last updated from the server (ISO 8601)
     pub validated_at: String,
+
+    /// Server-signed license document, if the server issued one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signed_license: Option<SignedLicense>,
 }
 
 impl CachedValidation {
@@ -81,32 +99,45 @@ impl CachedValidation {
             tier,
             expires_at,
             grace_period_ends_at,
+            offline_valid_until: None,
             validated_at: Utc::now().to_rfc3339(),
+            signed_license: None,
         }
     }
 
+    /// Attach the signed license document returned by the server.
+    pub fn with_signed_license(mut self, signed_license: Option<SignedLicense>) -> Self {
+        self.signed_license = signed_license;
+        self
+    }
+
+    /// Set the offline allowance returned by the server.
+    pub fn with_offline_valid_until(mut self, offline_valid_until: Option<String>) -> Self {
+        self.offline_valid_until = offline_valid_until;
+        self
+    }
+
+    /// When offline use ends: the later of the offline allowance and the
+    /// grace period.
+    pub fn offline_deadline(&self) -> Option<DateTime<Utc>> {
+        latest_deadline([
+            self.offline_valid_until.as_deref(),
+            self.grace_period_ends_at.as_deref(),
+        ])
+    }
+
     /// Check if this cache is still valid for offline use.
     ///
     /// Returns `true` if:
-    /// - The cache has a grace period that hasn't expired yet, OR
-    /// - The cache has no grace period (normal online license)
+    /// - The offline allowance hasn't run out yet, OR
+    /// - The cache has a grace period that hasn't expired yet
     ///
     /// Returns `false` if:
-    /// - The grace period has expired (must go online)
+    /// - Both have run out (must go online)
+    /// - The server granted neither; unparseable values count as missing
     pub fn is_valid_for_offline(&self) -> bool {
-        match &self.grace_period_ends_at {
-            Some(ends_at) => {
-                // Parse the grace period end time
-                match DateTime::parse_from_rfc3339(ends_at) {
-                    Ok(end_time) => Utc::now() < end_time.with_timezone(&Utc),
-                    // If we can't parse it, assume invalid (fail safe)
-                    Err(_) => false,
-                }
-            }
-            // No grace period means this is a normal license, not air-gapped
-            // For safety, require online validation
-            None => false,
-        }
+        self.offline_deadline()
+            .is_some_and(|deadline| Utc::now() < deadline)
     }
 
     /// Check if the license itself has expired (separate from grace period).
@@ -303,7 +334,9 @@ mod tests {
             tier: Some("pro".to_string()),
             expires_at: Some((Utc::now() + Duration::days(365)).to_rfc3339()),
             grace_period_ends_at,
+            offline_valid_until: None,
             validated_at: Utc::now().to_rfc3339(),
+            signed_license: None,
         }
     }
 
@@ -380,8 +413,23 @@ mod tests {
     #[test]
     fn cache_without_grace_period() {
         let cache = create_test_cache(None);
-        // No grace period = not valid for offline (fail safe)
+        // Neither grace period nor offline allowance = not valid for offline (fail safe)
+        assert!(!cache.is_valid_for_offline());
+    }
+
+    #[test]
+    fn cache_with_offline_allowance() {
+        let mut cache = create_test_cache(None)
+            .with_offline_valid_until(Some((Utc::now() + Duration::days(7)).to_rfc3339()));
+        assert!(cache.is_valid_for_offline());
+        assert!(cache.grace_period_remaining().is_none());
+
+        cache.offline_valid_until = Some((Utc::now() - Duration::hours(1)).to_rfc3339());
         assert!(!cache.is_valid_for_offline());
+
+        // A grace period still open keeps the cache usable on its own
+        cache.grace_period_ends_at = Some((Utc::now() + Duration::hours(1)).to_rfc3339());
+        assert!(cache.is_valid_for_offline());
     }
 
     #[test]
@@ -28,12 +28,17 @@ pub enum ClientErrorCode {
     LicenseInactive,
 
     // === Hardware Binding Errors ===
-    /// License is already bound to a different device
-    AlreadyBound,
+    /// Every seat on the license is held by another device.
+    ///
+    /// Servers before multi-seat support send this as `ALREADY_BOUND`.
+    #[serde(alias = "ALREADY_BOUND")]
+    SeatLimitReached,
     /// License is not bound to any device
     NotBound,
     /// Request hardware ID doesn't match bound device
     HardwareMismatch,
+    /// Every floating lease on the license is checked out
+    LeaseLimitReached,
 
     // === Feature/Quota Errors ===
     /// Requested feature is not included in license tier
@@ -41,6 +46,12 @@ pub enum ClientErrorCode {
     /// Usage quota has been exceeded
     QuotaExceeded,
 
+    // === Trial Errors ===
+    /// The machine has already had a trial
+    TrialAlreadyUsed,
+    /// The server does not issue trial licenses
+    TrialsDisabled,
+
     // === Grace Period Errors (client-side) ===
     /// Cached grace period has expired, must go online
     GracePeriodExpired,
@@ -65,11 +76,16 @@ impl ClientErrorCode {
             ClientErrorCode::LicenseSuspended => "License is suspended",
             ClientErrorCode::LicenseBlacklisted => "License has been blacklisted",
             ClientErrorCode::LicenseInactive => "License is not active",
-            ClientErrorCode::AlreadyBound => "License is already bound to another device",
+            ClientErrorCode::SeatLimitReached => "All seats on this license are in use",
             ClientErrorCode::NotBound => "License is not bound to any device",
             ClientErrorCode::HardwareMismatch => "Hardware ID does not match",
+            ClientErrorCode::LeaseLimitReached => {
+                "All concurrent leases on this license are in use"
+            }
             ClientErrorCode::FeatureNotIncluded => "Feature not included in license",
             ClientErrorCode::QuotaExceeded => "Usage quota exceeded",
+            ClientErrorCode::TrialAlreadyUsed => "A trial has already been used on this machine",
+            ClientErrorCode::TrialsDisabled => "Trial licenses are not available",
             ClientErrorCode::GracePeriodExpired => {
                 "Grace period expired - please connect to license server"
             }
@@ -229,7 +245,25 @@ mod tests {
     }
 
     #[test]
-    fn parse_already_bound_error() {
+    fn parse_seat_limit_reached_error() {
+        let json = r#"{
+            "error": {
+                "code": "SEAT_LIMIT_REACHED",
+                "message": "All 1 seat(s) on this license are in use",
+                "details": {"max_devices": 1, "devices": [{"device_name": "Work Laptop"}]}
+            }
+        }"#;
+
+        let resp: ServerErrorResponse = serde_json::from_str(json).unwrap();
+        let err: ClientApiError = resp.into();
+
+        assert_eq!(err.code, ClientErrorCode::SeatLimitReached);
+        let details = err.details.expect("details should be present");
+        assert_eq!(details["devices"][0]["device_name"], "Work Laptop");
+    }
+
+    #[test]
+    fn parse_legacy_already_bound_error() {
         let json = r#"{
             "error": {
                 "code": "ALREADY_BOUND",
@@ -241,9 +275,8 @@ mod tests {
         let resp: ServerErrorResponse = serde_json::from_str(json).unwrap();
         let err: ClientApiError = resp.into();
 
-        assert_eq!(err.code, ClientErrorCode::AlreadyBound);
+        assert_eq!(err.code, ClientErrorCode::SeatLimitReached);
         assert!(err.message.contains("Work Laptop"));
-        assert!(err.details.is_some());
     }
 
     #[test]
@@ -267,7 +300,7 @@ mod tests {
         assert!(ClientErrorCode::LicenseRevoked.is_license_invalid());
         assert!(ClientErrorCode::GracePeriodExpired.is_license_invalid());
 
-        assert!(!ClientErrorCode::AlreadyBound.is_license_invalid());
+        assert!(!ClientErrorCode::SeatLimitReached.is_license_invalid());
         assert!(!ClientErrorCode::NotBound.is_license_invalid());
         assert!(!ClientErrorCode::LicenseSuspended.is_license_invalid());
     }
@@ -278,7 +311,7 @@ mod tests {
         assert!(ClientErrorCode::LicenseSuspended.requires_online());
 
         assert!(!ClientErrorCode::LicenseExpired.requires_online());
-        assert!(!ClientErrorCode::AlreadyBound.requires_online());
+        assert!(!ClientErrorCode::SeatLimitReached.requires_online());
     }
 
     #[test]
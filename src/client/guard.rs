@@ -0,0 +1,394 @@
+//! Background heartbeats and revalidation for a bound license.
+//!
+//! [`LicenseGuard`] validates a license once, then keeps it alive in a
+//! background task: heartbeats go out at the interval the server advertises,
+//! the license is revalidated periodically to refresh the offline cache, and
+//! every change of state is published on a `tokio::sync::watch` channel.
+//!
+//! ```rust,ignore
+//! use talos::client::{GuardOptions, License, LicenseGuard, LicenseState};
+//!
+//! let license = License::load_from_disk().await?;
+//! let guard = LicenseGuard::start(license, GuardOptions::default()).await?;
+//!
+//! let mut state = guard.subscribe();
+//! tokio::spawn(async move {
+//!     while state.changed().await.is_ok() {
+//!         if let LicenseState::Invalid { message, .. } = &*state.borrow() {
+//!             eprintln!("License no longer valid: {message}");
+//!         }
+//!     }
+//! });
+//!
+//! // ...run the application, checking guard.is_valid() as needed
+//!
+//! // Stop the task and free the seat
+//! guard.release().await?;
+//! ```
+
+use std::time::Duration;
+
+use rand::Rng;
+use tokio::sync::{oneshot, watch};
+use tokio::task::JoinHandle;
+use tokio::time::Instant;
+
+use crate::client::errors::{ClientApiError, ClientErrorCode};
+use crate::client::license::License;
+use crate::errors::{LicenseError, LicenseResult};
+
+/// Heartbeat interval used until the server advertises one.
+const DEFAULT_HEARTBEAT_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Fraction of the interval by which each delay is randomly shifted.
+const JITTER_FRACTION: f64 = 0.1;
+
+/// State of the license watched by a [`LicenseGuard`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LicenseState {
+    /// The server confirmed the license on the last check.
+    Valid,
+    /// The server is unreachable and the license is running on its cache.
+    ///
+    /// `ends_at` (RFC 3339) is when offline use runs out: the end of the
+    /// offline allowance or of the grace period, whichever is later.
+    Grace { ends_at: Option<String> },
+    /// The server rejected the license (e.g. `LicenseRevoked`), or offline
+    /// use ran out while the server was unreachable.
+    ///
+    /// The guard keeps checking, so the state can recover.
+    Invalid {
+        code: ClientErrorCode,
+        message: String,
+    },
+}
+
+impl LicenseState {
+    fn from_api_error(err: ClientApiError) -> Self {
+        LicenseState::Invalid {
+            code: err.code,
+            message: err.message,
+        }
+    }
+}
+
+/// Options for [`LicenseGuard::start`].
+#[derive(Debug, Clone)]
+pub struct GuardOptions {
+    /// Fixed heartbeat interval; `None` uses the interval advertised by the server.
+    pub heartbeat_interval: Option<Duration>,
+    /// How often to revalidate the license and refresh the offline cache.
+    pub revalidate_interval: Duration,
+    /// Upper bound for the retry delay while the server is unreachable.
+    pub max_backoff: Duration,
+    /// Release the license when the guard is dropped.
+    pub release_on_drop: bool,
+}
+
+impl Default for GuardOptions {
+    fn default() -> Self {
+        Self {
+            heartbeat_interval: None,
+            revalidate_interval: Duration::from_secs(15 * 60),
+            max_backoff: Duration::from_secs(10 * 60),
+            release_on_drop: false,
+        }
+    }
+}
+
+/// Keeps a bound license alive by sending heartbeats in the background.
+///
+/// Network failures and server errors (`INTERNAL_ERROR`) back off
+/// exponentially up to `max_backoff` and move the state to
+/// [`LicenseState::Grace`] while the cached validation is still
+/// usable. The first successful check after a failure revalidates the
+/// license, so the cache is fresh again as soon as the server is back.
+///
+/// Dropping the guard stops the task. With `release_on_drop` set, the task
+/// releases the license first; this is best effort and is skipped if the
+/// runtime shuts down before the request completes.
+#[derive(Debug)]
+pub struct LicenseGuard {
+    state: watch::Receiver<LicenseState>,
+    stop: Option<oneshot::Sender<bool>>,
+    task: JoinHandle<License>,
+    release_on_drop: bool,
+}
+
+impl LicenseGuard {
+    /// Validate `license` and start keeping it alive.
+    ///
+    /// If the server is unreachable or failing the guard starts in the grace state,
+    /// provided the cached validation is still usable. Fails if the server
+    /// rejects the license or it cannot be validated at all.
+    pub async fn start(mut license: License, options: GuardOptions) -> LicenseResult<Self> {
+        let (initial, advertised) = match license.validate().await {
+            Ok(result) => (LicenseState::Valid, result.heartbeat_interval_secs),
+            Err(e) if is_transient(&e) => {
+                let cached = license.validate_offline()?;
+                (
+                    LicenseState::Grace {
+                        ends_at: cached.offline_valid_until,
+                    },
+                    None,
+                )
+            }
+            Err(e) => return Err(e),
+        };
+
+        let interval = options
+            .heartbeat_interval
+            .or_else(|| advertised.map(interval_from_secs))
+            .unwrap_or(DEFAULT_HEARTBEAT_INTERVAL);
+
+        let (state_tx, state) = watch::channel(initial);
+        let (stop, stop_rx) = oneshot::channel();
+        let release_on_drop = options.release_on_drop;
+
+        let task = tokio::spawn(guard_license(license, interval, options, state_tx, stop_rx));
+
+        Ok(Self {
+            state,
+            stop: Some(stop),
+            task,
+            release_on_drop,
+        })
+    }
+
+    /// Current state of the license.
+    pub fn state(&self) -> LicenseState {
+        self.state.borrow().clone()
+    }
+
+    /// Whether the license can be used, online or within its grace period.
+    pub fn is_valid(&self) -> bool {
+        !matches!(*self.state.borrow(), LicenseState::Invalid { .. })
+    }
+
+    /// Receiver that is notified whenever the state changes.
+    pub fn subscribe(&self) -> watch::Receiver<LicenseState> {
+        self.state.clone()
+    }
+
+    /// Stop the background task without releasing the license.
+    ///
+    /// Returns the license so it can be used for further calls.
+    pub async fn stop(mut self) -> LicenseResult<License> {
+        self.shutdown().await
+    }
+
+    /// Stop the background task and release the license.
+    pub async fn release(mut self) -> LicenseResult<()> {
+        let mut license = self.shutdown().await?;
+        license.release().await
+    }
+
+    async fn shutdown(&mut self) -> LicenseResult<License> {
+        if let Some(stop) = self.stop.take() {
+            let _ = stop.send(false);
+        }
+
+        (&mut self.task)
+            .await
+            .map_err(|e| LicenseError::ServerError(format!("license guard task failed: {e}")))
+    }
+}
+
+impl Drop for LicenseGuard {
+    fn drop(&mut self) {
+        match self.stop.take() {
+            // Let the task finish on its own so it can release the license
+            Some(stop) if self.release_on_drop => {
+                let _ = stop.send(true);
+            }
+            _ => self.task.abort(),
+        }
+    }
+}
+
+fn interval_from_secs(secs: u64) -> Duration {
+    Duration::from_secs(secs.max(1))
+}
+
+/// Delay before the next check: the interval, doubled for every consecutive
+/// failure up to `max_backoff`.
+fn backoff_delay(interval: Duration, failures: u32, max_backoff: Duration) -> Duration {
+    if failures == 0 {
+        return interval;
+    }
+    let factor = 2u32.saturating_pow(failures.min(16));
+    interval
+        .saturating_mul(factor)
+        .min(max_backoff.max(interval))
+}
+
+/// Shift `delay` by up to ±10% so clients started together spread out.
+fn with_jitter(delay: Duration, rng: &mut impl Rng) -> Duration {
+    delay.mul_f64(1.0 + rng.random_range(-JITTER_FRACTION..=JITTER_FRACTION))
+}
+
+/// Whether an error means the server couldn't answer, rather than that it
+/// rejected the license.
+///
+/// A server-side failure is reported as `INTERNAL_ERROR` (or a code this
+/// client doesn't know), and must not invalidate a license that the cache
+/// still covers.
+fn is_transient(error: &LicenseError) -> bool {
+    match error {
+        LicenseError::NetworkError(_) | LicenseError::ServerError(_) => true,
+        LicenseError::ClientApiError(e) => matches!(
+            e.code,
+            ClientErrorCode::InternalError | ClientErrorCode::Unknown
+        ),
+        _ => false,
+    }
+}
+
+/// State to report while the server cannot be reached.
+fn offline_state(license: &License) -> LicenseState {
+    match license.validate_offline() {
+        Ok(cached) => LicenseState::Grace {
+            ends_at: cached.offline_valid_until,
+        },
+        Err(LicenseError::ClientApiError(e)) => LicenseState::from_api_error(e),
+        Err(_) => LicenseState::from_api_error(ClientApiError::grace_period_expired()),
+    }
+}
+
+async fn guard_license(
+    mut license: License,
+    mut interval: Duration,
+    options: GuardOptions,
+    state: watch::Sender<LicenseState>,
+    mut stop: oneshot::Receiver<bool>,
+) -> License {
+    let mut failures: u32 = 0;
+    let mut last_validated = Instant::now();
+
+    let release = loop {
+        let delay = with_jitter(
+            backoff_delay(interval, failures, options.max_backoff),
+            &mut rand::rng(),
+        );
+
+        tokio::select! {
+            release = &mut stop => break release.unwrap_or(false),
+            _ = tokio::time::sleep(delay) => {}
+        }
+
+        // Revalidate on schedule, and after any failure so the cache is
+        // refreshed as soon as the server accepts the license again
+        let revalidate = *state.borrow() != LicenseState::Valid
+            || last_validated.elapsed() >= options.revalidate_interval;
+
+        let outcome = if revalidate {
+            license.validate().await.map(|result| {
+                last_validated = Instant::now();
+                result.heartbeat_interval_secs
+            })
+        } else {
+            license
+                .heartbeat()
+                .await
+                .map(|result| result.heartbeat_interval_secs)
+        };
+
+        let next = match outcome {
+            Ok(advertised) => {
+                failures = 0;
+                if let (None, Some(secs)) = (options.heartbeat_interval, advertised) {
+                    interval = interval_from_secs(secs);
+                }
+                Some(LicenseState::Valid)
+            }
+            // Server unreachable or failing: fall back to the cache and back off
+            Err(e) if is_transient(&e) => {
+                failures = failures.saturating_add(1);
+                Some(offline_state(&license))
+            }
+            Err(LicenseError::ClientApiError(e)) => {
+                failures = 0;
+                Some(LicenseState::from_api_error(e))
+            }
+            // Local problems (storage, signature) leave the state as it is
+            Err(_) => {
+                failures = failures.saturating_add(1);
+                None
+            }
+        };
+
+        if let Some(next) = next {
+            state.send_if_modified(|current| {
+                if *current == next {
+                    return false;
+                }
+                *current = next;
+                true
+            });
+        }
+    };
+
+    if release {
+        let _ = license.release().await;
+    }
+
+    license
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backoff_doubles_up_to_the_cap() {
+        let interval = Duration::from_secs(60);
+        let cap = Duration::from_secs(600);
+
+        assert_eq!(backoff_delay(interval, 0, cap), interval);
+        assert_eq!(backoff_delay(interval, 1, cap), Duration::from_secs(120));
+        assert_eq!(backoff_delay(interval, 3, cap), Duration::from_secs(480));
+        assert_eq!(backoff_delay(interval, 4, cap), cap);
+        assert_eq!(backoff_delay(interval, u32::MAX, cap), cap);
+
+        // A cap below the interval never shortens the normal delay
+        assert_eq!(
+            backoff_delay(interval, 2, Duration::from_secs(10)),
+            interval
+        );
+    }
+
+    #[test]
+    fn jitter_stays_within_ten_percent() {
+        let mut rng = rand::rng();
+        let delay = Duration::from_secs(100);
+
+        for _ in 0..1000 {
+            let jittered = with_jitter(delay, &mut rng);
+            assert!(jittered >= Duration::from_secs(90));
+            assert!(jittered <= Duration::from_secs(110));
+        }
+    }
+
+    #[test]
+    fn server_failures_are_transient() {
+        let api_error = |code| {
+            LicenseError::ClientApiError(ClientApiError {
+                code,
+                message: String::new(),
+                details: None,
+            })
+        };
+
+        assert!(is_transient(&LicenseError::ServerError("502".into())));
+        assert!(is_transient(&api_error(ClientErrorCode::InternalError)));
+        assert!(is_transient(&api_error(ClientErrorCode::Unknown)));
+        assert!(!is_transient(&api_error(ClientErrorCode::LicenseRevoked)));
+        assert!(!is_transient(&LicenseError::SignatureError("bad".into())));
+    }
+
+    #[test]
+    fn advertised_interval_is_at_least_one_second() {
+        assert_eq!(interval_from_secs(0), Duration::from_secs(1));
+        assert_eq!(interval_from_secs(30), Duration::from_secs(30));
+    }
+}
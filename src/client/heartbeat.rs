@@ -1,8 +1,6 @@
 use crate::client::client::License;
 use crate::errors::{LicenseError, LicenseResult};
-use reqwest::Client;
 use serde::{Deserialize, Serialize};
-use std::time::Duration;
 
 /// Struct for the heartbeat request payload sent to the server.
 #[derive(Debug, Serialize)]
@@ -29,18 +27,15 @@ struct HeartbeatResponse {
 /// - `Err(NetworkError | ServerError)` on transport/protocol errors.
 pub async fn send_heartbeat(license: &License) -> LicenseResult<bool> {
     let server_url = &license.server_url;
-    let client = Client::new();
 
     let payload = HeartbeatRequest {
         license_id: license.license_id.clone(),
         client_id: license.client_id.clone(),
     };
 
-    let resp = client
-        .post(format!("{}/heartbeat", server_url))
-        .json(&payload)
-        .timeout(Duration::from_secs(10))
-        .send()
+    let resp = license
+        .transport()
+        .post_json(format!("{}/heartbeat", server_url), &payload)
         .await?; // → LicenseError::NetworkError via #[from] reqwest::Error
 
     if !resp.status().is_success() {
@@ -0,0 +1,192 @@
+//! Background renewal of floating license leases.
+//!
+//! A floating lease returns to the pool if the server sees no heartbeat for
+//! `lease_ttl_secs`. [`LeaseKeeper`] checks a lease out and sends heartbeats
+//! in a background task so the lease stays held while the application runs.
+//!
+//! ```rust,ignore
+//! use talos::client::{LeaseKeeper, License};
+//!
+//! let license = License::new(key, server_url);
+//! let keeper = LeaseKeeper::start(license, Some("Render node 4"), None).await?;
+//!
+//! // ...run the licensed work, checking keeper.is_held() as needed
+//!
+//! // Stop renewing and hand the lease back
+//! let license = keeper.checkin().await?;
+//! ```
+
+use std::time::Duration;
+
+use tokio::sync::{oneshot, watch};
+use tokio::task::JoinHandle;
+use tokio::time::MissedTickBehavior;
+
+use crate::client::license::License;
+use crate::errors::{LicenseError, LicenseResult};
+
+/// State of the lease held by a [`LeaseKeeper`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LeaseStatus {
+    /// The lease is held until `expires_at` (RFC 3339)
+    Held { expires_at: String },
+    /// The lease expired or was freed and could not be checked out again.
+    ///
+    /// The keeper keeps trying on every renewal tick.
+    Lost,
+}
+
+/// Keeps a floating lease checked out by renewing it in the background.
+///
+/// Heartbeats are sent every third of the lease TTL. If the server reports
+/// that the lease is gone (for example after a long network outage), the
+/// keeper checks out a new one. Network errors leave the status unchanged
+/// and are retried on the next tick.
+///
+/// Dropping the keeper stops renewal without checking the lease in; it then
+/// returns to the pool once the TTL runs out.
+#[derive(Debug)]
+pub struct LeaseKeeper {
+    status: watch::Receiver<LeaseStatus>,
+    stop: Option<oneshot::Sender<()>>,
+    task: JoinHandle<License>,
+}
+
+impl LeaseKeeper {
+    /// Check out a lease for `license` and start renewing it.
+    ///
+    /// Fails if the initial checkout fails, e.g. with `LeaseLimitReached`.
+    pub async fn start(
+        mut license: License,
+        device_name: Option<&str>,
+        device_info: Option<&str>,
+    ) -> LicenseResult<Self> {
+        let lease = license.checkout(device_name, device_info).await?;
+
+        let (status_tx, status) = watch::channel(LeaseStatus::Held {
+            expires_at: lease.lease_expires_at,
+        });
+        let (stop, stop_rx) = oneshot::channel();
+
+        let task = tokio::spawn(keep_lease(
+            license,
+            renew_interval(lease.lease_ttl_secs),
+            device_name.map(str::to_string),
+            device_info.map(str::to_string),
+            status_tx,
+            stop_rx,
+        ));
+
+        Ok(Self {
+            status,
+            stop: Some(stop),
+            task,
+        })
+    }
+
+    /// Current state of the lease.
+    pub fn status(&self) -> LeaseStatus {
+        self.status.borrow().clone()
+    }
+
+    /// Whether the lease is currently held.
+    pub fn is_held(&self) -> bool {
+        matches!(*self.status.borrow(), LeaseStatus::Held { .. })
+    }
+
+    /// Stop renewing and return the lease to the pool.
+    ///
+    /// Returns the license so it can be used for further calls.
+    pub async fn checkin(mut self) -> LicenseResult<License> {
+        if let Some(stop) = self.stop.take() {
+            let _ = stop.send(());
+        }
+
+        let license = (&mut self.task)
+            .await
+            .map_err(|e| LicenseError::ServerError(format!("lease keeper task failed: {e}")))?;
+
+        license.checkin().await?;
+        Ok(license)
+    }
+}
+
+impl Drop for LeaseKeeper {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+/// Renew three times per TTL so one lost heartbeat does not cost the lease.
+fn renew_interval(lease_ttl_secs: u64) -> Duration {
+    Duration::from_secs((lease_ttl_secs / 3).max(1))
+}
+
+async fn keep_lease(
+    mut license: License,
+    interval: Duration,
+    device_name: Option<String>,
+    device_info: Option<String>,
+    status: watch::Sender<LeaseStatus>,
+    mut stop: oneshot::Receiver<()>,
+) -> License {
+    let mut ticker = tokio::time::interval(interval);
+    ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+    // The first tick completes immediately; the lease was just checked out
+    ticker.tick().await;
+
+    loop {
+        tokio::select! {
+            _ = &mut stop => break,
+            _ = ticker.tick() => {}
+        }
+
+        let next = match license.heartbeat().await {
+            Ok(result) => match result.lease_expires_at {
+                Some(expires_at) => Some(LeaseStatus::Held { expires_at }),
+                None => Some(
+                    checkout_again(&mut license, device_name.as_deref(), device_info.as_deref())
+                        .await,
+                ),
+            },
+            // Server unreachable: keep the current status and retry next tick
+            Err(LicenseError::NetworkError(_)) => None,
+            // The seat itself is gone; checkout binds again if a seat is free
+            Err(_) => Some(
+                checkout_again(&mut license, device_name.as_deref(), device_info.as_deref()).await,
+            ),
+        };
+
+        if let Some(next) = next {
+            status.send_replace(next);
+        }
+    }
+
+    license
+}
+
+async fn checkout_again(
+    license: &mut License,
+    device_name: Option<&str>,
+    device_info: Option<&str>,
+) -> LeaseStatus {
+    match license.checkout(device_name, device_info).await {
+        Ok(lease) => LeaseStatus::Held {
+            expires_at: lease.lease_expires_at,
+        },
+        Err(_) => LeaseStatus::Lost,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renews_three_times_per_ttl() {
+        assert_eq!(renew_interval(300), Duration::from_secs(100));
+        assert_eq!(renew_interval(1), Duration::from_secs(1));
+        assert_eq!(renew_interval(0), Duration::from_secs(1));
+    }
+}
@@ -40,11 +40,18 @@
 //!
 //! ## Air-Gapped Usage (Offline)
 //!
+//! Embed the server's public key (printed by `talos_server signing-key generate`)
+//! so offline validation is backed by the server's Ed25519 signature:
+//!
 //! ```rust,ignore
 //! use talos::client::license::License;
+//! use talos::signing::decode_public_key;
+//!
+//! const TALOS_PUBLIC_KEY: &str = "base64-public-key";
 //!
 //! async fn check_license() -> Result<bool, Box<dyn std::error::Error>> {
 //!     let mut license = License::load_from_disk().await?;
+//!     license.set_public_key(&decode_public_key(TALOS_PUBLIC_KEY)?);
 //!
 //!     // Try online first, fall back to cached validation
 //!     match license.validate_with_fallback().await {
@@ -62,6 +69,7 @@
 //! }
 //! ```
 
+use crate::client::activation::{ActivationRequest, ActivationResponse};
 use crate::client::cache::{
     clear_cache_from_disk, load_cache_from_disk, save_cache_to_disk, CachedValidation,
 };
@@ -70,18 +78,17 @@ use crate::client::encrypted_storage::{
 };
 use crate::client::errors::{ClientApiError, ClientErrorCode, ServerErrorResponse};
 use crate::client::responses::{
-    BindResult, FeatureResult, HeartbeatResult, ServerBindResponse, ServerFeatureResponse,
-    ServerHeartbeatResponse, ServerReleaseResponse, ServerValidateResponse, ValidationResult,
+    BindResult, FeatureResult, HeartbeatResult, LeaseResult, ServerBindResponse,
+    ServerCheckinResponse, ServerCheckoutResponse, ServerFeatureResponse, ServerHeartbeatResponse,
+    ServerReleaseResponse, ServerTrialResponse, ServerUsageResponse, ServerValidateResponse,
+    TrialResult, UsageResult, ValidationResult,
 };
+use crate::client::transport::{ClientOptions, Transport};
 use crate::errors::{LicenseError, LicenseResult};
 use crate::hardware::get_hardware_id;
+use crate::signing::{SignedLicense, SignedLicensePayload};
 
-use reqwest::Client;
 use serde::{Deserialize, Serialize};
-use std::time::Duration;
-
-/// HTTP client timeout for license server requests.
-const REQUEST_TIMEOUT_SECS: u64 = 30;
 
 /// Core license representation.
 ///
@@ -128,6 +135,17 @@ pub struct License {
     /// Legacy: Active flag
     #[serde(default)]
     pub is_active: bool,
+
+    /// Server public key for verifying signed license documents.
+    ///
+    /// Never persisted: it must come from the application binary, otherwise a
+    /// forged license file could carry its own key.
+    #[serde(skip)]
+    public_key: Option<Vec<u8>>,
+
+    /// HTTP client used for server requests (see `set_options()`)
+    #[serde(skip)]
+    transport: Transport,
 }
 
 // === Request Types ===
@@ -142,6 +160,17 @@ struct BindRequest {
     device_info: Option<String>,
 }
 
+#[derive(Debug, Serialize)]
+struct TrialRequest {
+    hardware_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    product: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    device_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    device_info: Option<String>,
+}
+
 #[derive(Debug, Serialize)]
 struct ReleaseRequest {
     license_key: String,
@@ -160,6 +189,22 @@ struct HeartbeatRequest {
     hardware_id: String,
 }
 
+#[derive(Debug, Serialize)]
+struct CheckoutRequest {
+    license_key: String,
+    hardware_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    device_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    device_info: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct CheckinRequest {
+    license_key: String,
+    hardware_id: String,
+}
+
 #[derive(Debug, Serialize)]
 struct FeatureRequest {
     license_key: String,
@@ -167,6 +212,15 @@ struct FeatureRequest {
     feature: String,
 }
 
+#[derive(Debug, Serialize)]
+struct UsageRequest {
+    license_key: String,
+    hardware_id: String,
+    meter: String,
+    amount: u64,
+    report_id: String,
+}
+
 // === Legacy Request Types ===
 
 #[derive(Debug, Serialize)]
@@ -197,15 +251,58 @@ impl License {
             features: Vec::new(),
             signature: String::new(),
             is_active: false,
+            public_key: None,
+            transport: Transport::default(),
         }
     }
 
-    /// Create an HTTP client with standard timeout.
-    fn http_client() -> Client {
-        Client::builder()
-            .timeout(Duration::from_secs(REQUEST_TIMEOUT_SECS))
-            .build()
-            .unwrap_or_else(|_| Client::new())
+    /// Set the server public key used to verify signed license documents.
+    ///
+    /// Once set, responses carrying a signed document are verified, and
+    /// `validate_offline()` only trusts the signed document.
+    pub fn with_public_key(mut self, public_key: &[u8]) -> Self {
+        self.set_public_key(public_key);
+        self
+    }
+
+    /// Set the server public key used to verify signed license documents.
+    pub fn set_public_key(&mut self, public_key: &[u8]) {
+        self.public_key = Some(public_key.to_vec());
+    }
+
+    /// Use `options` for requests to the license server.
+    ///
+    /// Fails with `ConfigError` if the proxy URL, a root certificate or a
+    /// pin is invalid.
+    pub fn with_options(mut self, options: &ClientOptions) -> LicenseResult<Self> {
+        self.set_options(options)?;
+        Ok(self)
+    }
+
+    /// Use `options` for requests to the license server.
+    ///
+    /// Options are not persisted; set them again after `load_from_disk()`.
+    pub fn set_options(&mut self, options: &ClientOptions) -> LicenseResult<()> {
+        self.transport = options.build()?;
+        Ok(())
+    }
+
+    /// HTTP client used for server requests.
+    pub(crate) fn transport(&self) -> &Transport {
+        &self.transport
+    }
+
+    /// Verify a signed license document against the embedded public key.
+    ///
+    /// Returns `Ok(None)` if no public key is set or no document was issued.
+    fn verify_signed_license(
+        &self,
+        signed: Option<&SignedLicense>,
+    ) -> LicenseResult<Option<SignedLicensePayload>> {
+        match (&self.public_key, signed) {
+            (Some(public_key), Some(signed)) => signed.verify(public_key).map(Some),
+            _ => Ok(None),
+        }
     }
 
     /// Parse an error response from the server.
@@ -251,10 +348,9 @@ impl License {
             device_info: device_info.map(|s| s.to_string()),
         };
 
-        let resp = Self::http_client()
-            .post(format!("{}/api/v1/client/bind", self.server_url))
-            .json(&request)
-            .send()
+        let resp = self
+            .transport
+            .post_json(format!("{}/api/v1/client/bind", self.server_url), &request)
             .await?;
 
         if !resp.status().is_success() {
@@ -265,9 +361,15 @@ impl License {
             LicenseError::ServerError(format!("Failed to parse bind response: {e}"))
         })?;
 
+        // Reject documents that were not signed by our server
+        self.verify_signed_license(server_resp.signed_license.as_ref())?;
+
         // Update local state
         self.hardware_id = hardware_id.clone();
         self.is_active = true;
+        if let Some(ref signed) = server_resp.signed_license {
+            self.signature = signed.signature.clone();
+        }
 
         // Update legacy fields for backwards compatibility
         self.license_id = server_resp.license_id.clone();
@@ -281,6 +383,80 @@ impl License {
         Ok(server_resp.into())
     }
 
+    /// Start a trial on the current hardware.
+    ///
+    /// Asks the server for a time-limited trial license bound to this
+    /// device's hardware fingerprint. The license key is assigned by the
+    /// server, so the `License` can be created with an empty key:
+    ///
+    /// ```rust,ignore
+    /// let mut license = License::new(String::new(), server_url);
+    /// let trial = license.start_trial(None, Some("My Workstation"), None).await?;
+    /// println!("Trial ends {:?}", trial.expires_at);
+    /// ```
+    ///
+    /// # Arguments
+    ///
+    /// * `product` - Product to start the trial for, when the server scopes
+    ///   trials per product
+    /// * `device_name` - Optional human-readable name for this device
+    /// * `device_info` - Optional device information (OS, version, etc.)
+    ///
+    /// # Errors
+    ///
+    /// Fails with `TRIAL_ALREADY_USED` if this device already had a trial,
+    /// and with `TRIALS_DISABLED` if the server doesn't issue trials.
+    pub async fn start_trial(
+        &mut self,
+        product: Option<&str>,
+        device_name: Option<&str>,
+        device_info: Option<&str>,
+    ) -> LicenseResult<TrialResult> {
+        let hardware_id = get_hardware_id();
+
+        let request = TrialRequest {
+            hardware_id: hardware_id.clone(),
+            product: product.map(|s| s.to_string()),
+            device_name: device_name.map(|s| s.to_string()),
+            device_info: device_info.map(|s| s.to_string()),
+        };
+
+        let resp = self
+            .transport
+            .post_json(format!("{}/api/v1/client/trial", self.server_url), &request)
+            .await?;
+
+        if !resp.status().is_success() {
+            return Err(Self::parse_error_response(resp).await);
+        }
+
+        let server_resp: ServerTrialResponse = resp.json().await.map_err(|e| {
+            LicenseError::ServerError(format!("Failed to parse trial response: {e}"))
+        })?;
+
+        // Reject documents that were not signed by our server
+        self.verify_signed_license(server_resp.signed_license.as_ref())?;
+
+        // Update local state
+        self.license_key = server_resp.license_key.clone();
+        self.hardware_id = hardware_id.clone();
+        self.is_active = true;
+        if let Some(ref signed) = server_resp.signed_license {
+            self.signature = signed.signature.clone();
+        }
+
+        // Update legacy fields for backwards compatibility
+        self.license_id = server_resp.license_id.clone();
+        self.client_id = hardware_id;
+        self.features = server_resp.features.clone();
+        self.expiry_date = server_resp.expires_at.clone().unwrap_or_default();
+
+        // Save to disk
+        self.save_to_disk().await?;
+
+        Ok(server_resp.into())
+    }
+
     /// Release this license from the current hardware.
     ///
     /// This unbinds the license from this device, allowing it to be bound
@@ -297,10 +473,12 @@ impl License {
             hardware_id: self.hardware_id.clone(),
         };
 
-        let resp = Self::http_client()
-            .post(format!("{}/api/v1/client/release", self.server_url))
-            .json(&request)
-            .send()
+        let resp = self
+            .transport
+            .post_json(
+                format!("{}/api/v1/client/release", self.server_url),
+                &request,
+            )
             .await?;
 
         if !resp.status().is_success() {
@@ -341,10 +519,12 @@ impl License {
             hardware_id: self.hardware_id.clone(),
         };
 
-        let resp = Self::http_client()
-            .post(format!("{}/api/v1/client/validate", self.server_url))
-            .json(&request)
-            .send()
+        let resp = self
+            .transport
+            .post_json(
+                format!("{}/api/v1/client/validate", self.server_url),
+                &request,
+            )
             .await?;
 
         if !resp.status().is_success() {
@@ -357,15 +537,25 @@ impl License {
 
         let result: ValidationResult = server_resp.into();
 
+        // Reject documents that were not signed by our server
+        self.verify_signed_license(result.signed_license.as_ref())?;
+        if let Some(ref signed) = result.signed_license {
+            self.signature = signed.signature.clone();
+        }
+
         // Update cache for offline use
-        self.cached = Some(CachedValidation::new(
-            self.license_key.clone(),
-            self.hardware_id.clone(),
-            result.features.clone(),
-            result.tier.clone(),
-            result.expires_at.clone(),
-            result.grace_period_ends_at.clone(),
-        ));
+        self.cached = Some(
+            CachedValidation::new(
+                self.license_key.clone(),
+                self.hardware_id.clone(),
+                result.features.clone(),
+                result.tier.clone(),
+                result.expires_at.clone(),
+                result.grace_period_ends_at.clone(),
+            )
+            .with_offline_valid_until(result.offline_valid_until.clone())
+            .with_signed_license(result.signed_license.clone()),
+        );
 
         // Save updated cache
         if let Some(ref cache) = self.cached {
@@ -379,19 +569,25 @@ impl License {
     /// Validate the license using cached state (offline mode).
     ///
     /// This checks the locally cached validation data without contacting
-    /// the server. Use this for air-gapped systems during the grace period.
+    /// the server. Use this while the server is unreachable, within the
+    /// offline allowance or, for suspended and air-gapped systems, the
+    /// grace period.
     ///
     /// # Returns
     ///
     /// Returns `Ok(ValidationResult)` if:
     /// - There is a valid cache for this license/hardware
-    /// - The grace period has not expired
+    /// - The offline allowance or the grace period has not run out
     /// - The license itself has not expired
     ///
     /// Returns `Err` if:
     /// - No cache exists
-    /// - Grace period has expired (must go online)
+    /// - Offline allowance and grace period have run out (must go online)
     /// - License has expired
+    ///
+    /// When a public key is set (see `set_public_key()`), the license data
+    /// comes from the server-signed document in the cache, and a missing or
+    /// invalid signature is an error.
     pub fn validate_offline(&self) -> LicenseResult<ValidationResult> {
         // Try to get cache from memory first, then disk
         let cache = match &self.cached {
@@ -421,6 +617,10 @@ impl License {
             ));
         }
 
+        if self.public_key.is_some() {
+            return self.validate_signed_offline(&cache);
+        }
+
         // Check if license has expired
         if cache.is_license_expired() {
             return Err(LicenseError::ClientApiError(ClientApiError::new(
@@ -429,7 +629,7 @@ impl License {
             )));
         }
 
-        // Check if grace period is still valid
+        // Check if the offline allowance or grace period is still open
         if !cache.is_valid_for_offline() {
             return Err(LicenseError::ClientApiError(
                 ClientApiError::grace_period_expired(),
@@ -437,7 +637,8 @@ impl License {
         }
 
         // Build result from cache
-        let warning = cache.grace_period_ends_at.as_ref().map(|ends_at| {
+        let offline_valid_until = cache.offline_deadline().map(|d| d.to_rfc3339());
+        let warning = offline_valid_until.as_ref().map(|ends_at| {
             format!(
                 "Offline mode - license must be validated online before {}",
                 ends_at
@@ -449,9 +650,70 @@ impl License {
             tier: cache.tier.clone(),
             expires_at: cache.expires_at.clone(),
             grace_period_ends_at: cache.grace_period_ends_at.clone(),
+            offline_valid_until,
             warning,
             bandwidth_used_bytes: None,
             bandwidth_limit_bytes: None,
+            signed_license: cache.signed_license.clone(),
+            heartbeat_interval_secs: None,
+        })
+    }
+
+    /// Offline validation backed by the signed license document.
+    fn validate_signed_offline(&self, cache: &CachedValidation) -> LicenseResult<ValidationResult> {
+        let payload = self
+            .verify_signed_license(cache.signed_license.as_ref())?
+            .ok_or_else(|| {
+                LicenseError::SignatureError(
+                    "No signed license available. Call validate() while online first.".to_string(),
+                )
+            })?;
+
+        if payload.license_key != self.license_key {
+            return Err(LicenseError::InvalidLicense(
+                "Signed license is for a different license.".to_string(),
+            ));
+        }
+
+        if payload.hardware_id != get_hardware_id() {
+            return Err(LicenseError::ClientApiError(ClientApiError::new(
+                ClientErrorCode::HardwareMismatch,
+                "Signed license is bound to different hardware.",
+            )));
+        }
+
+        if payload.is_license_expired() {
+            return Err(LicenseError::ClientApiError(ClientApiError::new(
+                ClientErrorCode::LicenseExpired,
+                "License has expired.",
+            )));
+        }
+
+        if !payload.is_valid_for_offline() {
+            return Err(LicenseError::ClientApiError(
+                ClientApiError::grace_period_expired(),
+            ));
+        }
+
+        let offline_valid_until = payload.offline_deadline().map(|d| d.to_rfc3339());
+        let warning = offline_valid_until.as_ref().map(|ends_at| {
+            format!(
+                "Offline mode - license must be validated online before {}",
+                ends_at
+            )
+        });
+
+        Ok(ValidationResult {
+            features: payload.features,
+            tier: payload.tier,
+            expires_at: payload.expires_at,
+            grace_period_ends_at: payload.grace_period_ends_at,
+            offline_valid_until,
+            warning,
+            bandwidth_used_bytes: None,
+            bandwidth_limit_bytes: None,
+            signed_license: cache.signed_license.clone(),
+            heartbeat_interval_secs: None,
         })
     }
 
@@ -484,13 +746,12 @@ impl License {
             feature: feature.to_string(),
         };
 
-        let resp = Self::http_client()
-            .post(format!(
-                "{}/api/v1/client/validate-feature",
-                self.server_url
-            ))
-            .json(&request)
-            .send()
+        let resp = self
+            .transport
+            .post_json(
+                format!("{}/api/v1/client/validate-feature", self.server_url),
+                &request,
+            )
             .await?;
 
         if !resp.status().is_success() {
@@ -507,7 +768,7 @@ impl License {
     /// Send a heartbeat to the server.
     ///
     /// This updates the server's `last_seen_at` timestamp for this license
-    /// and refreshes the grace period for air-gapped systems.
+    /// and renews the offline allowance in the cache.
     pub async fn heartbeat(&mut self) -> LicenseResult<HeartbeatResult> {
         self.ensure_bound()?;
 
@@ -516,10 +777,12 @@ impl License {
             hardware_id: self.hardware_id.clone(),
         };
 
-        let resp = Self::http_client()
-            .post(format!("{}/api/v1/client/heartbeat", self.server_url))
-            .json(&request)
-            .send()
+        let resp = self
+            .transport
+            .post_json(
+                format!("{}/api/v1/client/heartbeat", self.server_url),
+                &request,
+            )
             .await?;
 
         if !resp.status().is_success() {
@@ -532,17 +795,235 @@ impl License {
 
         let result: HeartbeatResult = server_resp.into();
 
-        // If server returned a new grace period, update cache
-        if let Some(ref new_grace) = result.grace_period_ends_at {
-            if let Some(ref mut cache) = self.cached {
+        // Renew the offline allowance; if the server granted none (allowance
+        // disabled, license no longer usable), the cached one is dropped
+        if let Some(ref mut cache) = self.cached {
+            if let Some(ref new_grace) = result.grace_period_ends_at {
                 cache.grace_period_ends_at = Some(new_grace.clone());
-                let _ = save_cache_to_disk(cache).await;
             }
+            cache.offline_valid_until = result.offline_valid_until.clone();
+            let _ = save_cache_to_disk(cache).await;
         }
 
         Ok(result)
     }
 
+    // =========================================================================
+    // Floating Leases
+    // =========================================================================
+
+    /// Check out a floating lease for this machine.
+    ///
+    /// Binds the license to this hardware first if needed. The lease returns
+    /// to the pool unless `heartbeat()` renews it within `lease_ttl_secs`;
+    /// use `LeaseKeeper` to renew it in the background.
+    ///
+    /// Fails with `LeaseLimitReached` if every lease on the license is in use.
+    pub async fn checkout(
+        &mut self,
+        device_name: Option<&str>,
+        device_info: Option<&str>,
+    ) -> LicenseResult<LeaseResult> {
+        let hardware_id = get_hardware_id();
+
+        let request = CheckoutRequest {
+            license_key: self.license_key.clone(),
+            hardware_id: hardware_id.clone(),
+            device_name: device_name.map(|s| s.to_string()),
+            device_info: device_info.map(|s| s.to_string()),
+        };
+
+        let resp = self
+            .transport
+            .post_json(
+                format!("{}/api/v1/client/checkout", self.server_url),
+                &request,
+            )
+            .await?;
+
+        if !resp.status().is_success() {
+            return Err(Self::parse_error_response(resp).await);
+        }
+
+        let server_resp: ServerCheckoutResponse = resp.json().await.map_err(|e| {
+            LicenseError::ServerError(format!("Failed to parse checkout response: {e}"))
+        })?;
+
+        // Checkout binds the license, so mirror bind()'s local state
+        self.hardware_id = hardware_id.clone();
+        self.is_active = true;
+        self.license_id = server_resp.license_id.clone();
+        self.client_id = hardware_id;
+
+        self.save_to_disk().await?;
+
+        Ok(server_resp.into())
+    }
+
+    /// Return this machine's floating lease to the pool.
+    ///
+    /// The license stays bound to this hardware; call `release()` to free the
+    /// seat as well.
+    pub async fn checkin(&self) -> LicenseResult<()> {
+        self.ensure_bound()?;
+
+        let request = CheckinRequest {
+            license_key: self.license_key.clone(),
+            hardware_id: self.hardware_id.clone(),
+        };
+
+        let resp = self
+            .transport
+            .post_json(
+                format!("{}/api/v1/client/checkin", self.server_url),
+                &request,
+            )
+            .await?;
+
+        if !resp.status().is_success() {
+            return Err(Self::parse_error_response(resp).await);
+        }
+
+        let _: ServerCheckinResponse = resp.json().await.map_err(|e| {
+            LicenseError::ServerError(format!("Failed to parse checkin response: {e}"))
+        })?;
+
+        Ok(())
+    }
+
+    // =========================================================================
+    // Usage Metering
+    // =========================================================================
+
+    /// Report usage of a meter (e.g. API calls or renders) to the server.
+    ///
+    /// `report_id` identifies this report: the server counts each ID only
+    /// once per license, so a report that failed with a network error can be
+    /// retried with the same ID without being counted twice. A UUID works.
+    ///
+    /// ```rust,ignore
+    /// let report_id = uuid::Uuid::new_v4().to_string();
+    /// let usage = license.report_usage("renders", 1, &report_id).await?;
+    /// if usage.exceeded {
+    ///     println!("Render quota used up ({} of {:?})", usage.used, usage.limit);
+    /// }
+    /// ```
+    pub async fn report_usage(
+        &self,
+        meter: &str,
+        amount: u64,
+        report_id: &str,
+    ) -> LicenseResult<UsageResult> {
+        self.ensure_bound()?;
+
+        let request = UsageRequest {
+            license_key: self.license_key.clone(),
+            hardware_id: self.hardware_id.clone(),
+            meter: meter.to_string(),
+            amount,
+            report_id: report_id.to_string(),
+        };
+
+        let resp = self
+            .transport
+            .post_json(format!("{}/api/v1/client/usage", self.server_url), &request)
+            .await?;
+
+        if !resp.status().is_success() {
+            return Err(Self::parse_error_response(resp).await);
+        }
+
+        let server_resp: ServerUsageResponse = resp.json().await.map_err(|e| {
+            LicenseError::ServerError(format!("Failed to parse usage response: {e}"))
+        })?;
+
+        Ok(server_resp.into())
+    }
+
+    // =========================================================================
+    // Offline Activation (air-gapped machines)
+    // =========================================================================
+
+    /// Build an activation request for this machine.
+    ///
+    /// Save it with `ActivationRequest::save()` and hand the file to an admin,
+    /// who uploads it to `POST /api/v1/licenses/offline-activation`.
+    pub fn activation_request(
+        &self,
+        device_name: Option<&str>,
+        device_info: Option<&str>,
+    ) -> ActivationRequest {
+        ActivationRequest::new(
+            self.license_key.clone(),
+            get_hardware_id(),
+            device_name.map(|s| s.to_string()),
+            device_info.map(|s| s.to_string()),
+        )
+    }
+
+    /// Import an activation response without contacting the server.
+    ///
+    /// Requires a public key (see `set_public_key()`): the signed document is
+    /// the only proof that the server issued the activation. On success the
+    /// license is bound to this machine and the cached validation is saved,
+    /// so `validate_offline()` works until the grace window ends.
+    pub async fn import_activation(
+        &mut self,
+        response: &ActivationResponse,
+    ) -> LicenseResult<ValidationResult> {
+        if self.public_key.is_none() {
+            return Err(LicenseError::SignatureError(
+                "A public key is required to import an offline activation.".to_string(),
+            ));
+        }
+
+        let payload = self
+            .verify_signed_license(Some(&response.signed_license))?
+            .ok_or(LicenseError::UnknownError)?;
+
+        if payload.license_key != self.license_key {
+            return Err(LicenseError::InvalidLicense(
+                "Activation response is for a different license.".to_string(),
+            ));
+        }
+
+        let hardware_id = get_hardware_id();
+        if payload.hardware_id != hardware_id {
+            return Err(LicenseError::ClientApiError(ClientApiError::new(
+                ClientErrorCode::HardwareMismatch,
+                "Activation response is for different hardware.",
+            )));
+        }
+
+        // Update local state
+        self.hardware_id = hardware_id.clone();
+        self.is_active = true;
+        self.signature = response.signed_license.signature.clone();
+
+        // Update legacy fields for backwards compatibility
+        self.license_id = payload.license_id.clone();
+        self.client_id = hardware_id.clone();
+        self.features = payload.features.clone();
+        self.expiry_date = payload.expires_at.clone().unwrap_or_default();
+
+        let cache = CachedValidation::new(
+            self.license_key.clone(),
+            hardware_id,
+            payload.features,
+            payload.tier,
+            payload.expires_at,
+            payload.grace_period_ends_at,
+        )
+        .with_offline_valid_until(payload.offline_valid_until)
+        .with_signed_license(Some(response.signed_license.clone()));
+
+        save_cache_to_disk(&cache).await?;
+        self.cached = Some(cache);
+        self.save_to_disk().await?;
+
+        self.validate_offline()
+    }
+
     // =========================================================================
     // Legacy API Methods (deprecated)
     // =========================================================================
@@ -560,10 +1041,9 @@ impl License {
             client_id: client_id.clone(),
         };
 
-        let resp = Self::http_client()
-            .post(format!("{}/activate", server_url))
-            .json(&payload)
-            .send()
+        let resp = self
+            .transport
+            .post_json(format!("{}/activate", server_url), &payload)
             .await?;
 
         if !resp.status().is_success() {
@@ -605,10 +1085,9 @@ impl License {
             client_id,
         };
 
-        let resp = Self::http_client()
-            .post(format!("{}/deactivate", server_url))
-            .json(&payload)
-            .send()
+        let resp = self
+            .transport
+            .post_json(format!("{}/deactivate", server_url), &payload)
             .await?;
 
         if !resp.status().is_success() {
@@ -760,6 +1239,177 @@ mod tests {
         assert!(result.is_err());
     }
 
+    fn signed_cache(
+        signer: &crate::signing::LicenseSigner,
+        license_key: &str,
+        hardware_id: String,
+    ) -> CachedValidation {
+        use chrono::{Duration, Utc};
+
+        let grace = Some((Utc::now() + Duration::hours(24)).to_rfc3339());
+        let payload = SignedLicensePayload {
+            version: crate::signing::PAYLOAD_VERSION,
+            license_id: "lic-1".to_string(),
+            license_key: license_key.to_string(),
+            hardware_id: hardware_id.clone(),
+            features: vec!["signed_feature".to_string()],
+            tier: Some("pro".to_string()),
+            expires_at: None,
+            grace_period_ends_at: grace.clone(),
+            offline_valid_until: None,
+            issued_at: Utc::now().to_rfc3339(),
+        };
+
+        // Unsigned cache fields deliberately differ from the signed payload
+        CachedValidation::new(
+            license_key.to_string(),
+            hardware_id,
+            vec!["forged_feature".to_string()],
+            None,
+            None,
+            grace,
+        )
+        .with_signed_license(Some(signer.sign(&payload).unwrap()))
+    }
+
+    fn test_signer() -> crate::signing::LicenseSigner {
+        let pkcs8 = crate::signing::LicenseSigner::generate_pkcs8().unwrap();
+        crate::signing::LicenseSigner::from_pkcs8(&pkcs8).unwrap()
+    }
+
+    #[test]
+    fn validate_offline_uses_signed_payload() {
+        let signer = test_signer();
+        let mut license = License::new(
+            "TEST-XXXX-XXXX-XXXX".to_string(),
+            "http://localhost:8080".to_string(),
+        )
+        .with_public_key(signer.public_key());
+        license.cached = Some(signed_cache(
+            &signer,
+            "TEST-XXXX-XXXX-XXXX",
+            get_hardware_id(),
+        ));
+
+        let result = license.validate_offline().unwrap();
+        assert!(result.has_feature("signed_feature"));
+        assert!(!result.has_feature("forged_feature"));
+        assert_eq!(result.tier.as_deref(), Some("pro"));
+    }
+
+    #[test]
+    fn validate_offline_rejects_foreign_signature() {
+        let signer = test_signer();
+        let attacker = test_signer();
+        let mut license = License::new(
+            "TEST-XXXX-XXXX-XXXX".to_string(),
+            "http://localhost:8080".to_string(),
+        )
+        .with_public_key(signer.public_key());
+        license.cached = Some(signed_cache(
+            &attacker,
+            "TEST-XXXX-XXXX-XXXX",
+            get_hardware_id(),
+        ));
+
+        assert!(matches!(
+            license.validate_offline(),
+            Err(LicenseError::SignatureError(_))
+        ));
+    }
+
+    #[test]
+    fn validate_offline_requires_signature_when_key_set() {
+        let signer = test_signer();
+        let mut license = License::new(
+            "TEST-XXXX-XXXX-XXXX".to_string(),
+            "http://localhost:8080".to_string(),
+        )
+        .with_public_key(signer.public_key());
+        let mut cache = signed_cache(&signer, "TEST-XXXX-XXXX-XXXX", get_hardware_id());
+        cache.signed_license = None;
+        license.cached = Some(cache);
+
+        assert!(license.validate_offline().is_err());
+    }
+
+    #[test]
+    fn validate_offline_rejects_signed_license_for_other_hardware() {
+        let signer = test_signer();
+        let mut license = License::new(
+            "TEST-XXXX-XXXX-XXXX".to_string(),
+            "http://localhost:8080".to_string(),
+        )
+        .with_public_key(signer.public_key());
+        let mut cache = signed_cache(&signer, "TEST-XXXX-XXXX-XXXX", "other-hw".to_string());
+        // The encrypted cache itself claims this machine
+        cache.hardware_id = get_hardware_id();
+        license.cached = Some(cache);
+
+        assert!(license.validate_offline().is_err());
+    }
+
+    fn activation_response(
+        signer: &crate::signing::LicenseSigner,
+        license_key: &str,
+        hardware_id: String,
+    ) -> ActivationResponse {
+        let cache = signed_cache(signer, license_key, hardware_id);
+        ActivationResponse::new(cache.signed_license.unwrap())
+    }
+
+    #[tokio::test]
+    async fn import_activation_requires_public_key() {
+        let signer = test_signer();
+        let mut license = License::new("TEST-XXXX-XXXX-XXXX".to_string(), String::new());
+        let response = activation_response(&signer, "TEST-XXXX-XXXX-XXXX", get_hardware_id());
+
+        assert!(matches!(
+            license.import_activation(&response).await,
+            Err(LicenseError::SignatureError(_))
+        ));
+        assert!(!license.is_bound());
+    }
+
+    #[tokio::test]
+    async fn import_activation_rejects_foreign_signature() {
+        let signer = test_signer();
+        let attacker = test_signer();
+        let mut license = License::new("TEST-XXXX-XXXX-XXXX".to_string(), String::new())
+            .with_public_key(signer.public_key());
+        let response = activation_response(&attacker, "TEST-XXXX-XXXX-XXXX", get_hardware_id());
+
+        assert!(matches!(
+            license.import_activation(&response).await,
+            Err(LicenseError::SignatureError(_))
+        ));
+        assert!(!license.is_bound());
+    }
+
+    #[tokio::test]
+    async fn import_activation_rejects_other_license_or_hardware() {
+        let signer = test_signer();
+        let mut license = License::new("TEST-XXXX-XXXX-XXXX".to_string(), String::new())
+            .with_public_key(signer.public_key());
+
+        let other_key = activation_response(&signer, "OTHER-XXXX-XXXX-XXXX", get_hardware_id());
+        assert!(license.import_activation(&other_key).await.is_err());
+
+        let other_hw = activation_response(&signer, "TEST-XXXX-XXXX-XXXX", "other-hw".to_string());
+        assert!(license.import_activation(&other_hw).await.is_err());
+        assert!(!license.is_bound());
+    }
+
+    #[test]
+    fn activation_request_uses_this_machine() {
+        let license = License::new("TEST-XXXX-XXXX-XXXX".to_string(), String::new());
+        let request = license.activation_request(Some("Controller"), None);
+
+        assert_eq!(request.license_key, "TEST-XXXX-XXXX-XXXX");
+        assert_eq!(request.hardware_id, get_hardware_id());
+        assert_eq!(request.device_name.as_deref(), Some("Controller"));
+    }
+
     #[test]
     fn validate_offline_requires_cache() {
         let license = License::new(
@@ -5,6 +5,8 @@
 
 use serde::{Deserialize, Serialize};
 
+use crate::signing::SignedLicense;
+
 /// Result of a successful license validation.
 ///
 /// Returned by `License::validate()` and `License::validate_offline()`.
@@ -29,6 +31,14 @@ pub struct ValidationResult {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub grace_period_ends_at: Option<String>,
 
+    /// Until when the license may be used without reaching the server
+    ///
+    /// Granted on every successful online validation. For offline
+    /// validation, this is when offline use ends, taking the grace period
+    /// into account.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub offline_valid_until: Option<String>,
+
     /// Warning message from the server (e.g., approaching expiration)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub warning: Option<String>,
@@ -40,6 +50,16 @@ pub struct ValidationResult {
     /// Bandwidth limit in bytes (if set, None means unlimited)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub bandwidth_limit_bytes: Option<i64>,
+
+    /// Server-signed license document (if the server has a signing key)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signed_license: Option<SignedLicense>,
+
+    /// Heartbeat interval advertised by the server, in seconds
+    ///
+    /// `None` for offline validation and servers that do not advertise one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub heartbeat_interval_secs: Option<u64>,
 }
 
 impl ValidationResult {
@@ -78,6 +98,10 @@ pub struct BindResult {
     /// License expiration date (ISO 8601 format)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub expires_at: Option<String>,
+
+    /// Server-signed license document (if the server has a signing key)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signed_license: Option<SignedLicense>,
 }
 
 impl BindResult {
@@ -87,6 +111,45 @@ impl BindResult {
     }
 }
 
+/// Result of a successful trial request.
+///
+/// Returned by `License::start_trial()`.
+#[non_exhaustive]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrialResult {
+    /// Server-side license ID (UUID)
+    pub license_id: String,
+
+    /// License key issued for the trial
+    pub license_key: String,
+
+    /// List of features enabled for the trial
+    pub features: Vec<String>,
+
+    /// Trial tier name
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tier: Option<String>,
+
+    /// Trial expiration date (ISO 8601 format)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<String>,
+
+    /// Product the trial was started for
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub product: Option<String>,
+
+    /// Server-signed license document (if the server has a signing key)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signed_license: Option<SignedLicense>,
+}
+
+impl TrialResult {
+    /// Check if a specific feature is enabled.
+    pub fn has_feature(&self, feature: &str) -> bool {
+        self.features.iter().any(|f| f == feature)
+    }
+}
+
 /// Result of a feature validation check.
 ///
 /// Returned by `License::validate_feature()`.
@@ -117,6 +180,62 @@ pub struct HeartbeatResult {
     /// Updated grace period end time (for air-gapped systems)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub grace_period_ends_at: Option<String>,
+
+    /// New expiry of this device's floating lease (RFC 3339)
+    ///
+    /// `None` if the device holds no active lease, e.g. because it expired.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lease_expires_at: Option<String>,
+
+    /// Renewed offline allowance (RFC 3339)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub offline_valid_until: Option<String>,
+
+    /// Heartbeat interval advertised by the server, in seconds
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub heartbeat_interval_secs: Option<u64>,
+}
+
+/// Result of a successful lease checkout.
+///
+/// Returned by `License::checkout()`.
+#[non_exhaustive]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LeaseResult {
+    /// Server-side license ID (UUID)
+    pub license_id: String,
+
+    /// When the lease returns to the pool unless renewed (RFC 3339)
+    pub lease_expires_at: String,
+
+    /// Seconds each heartbeat extends the lease by
+    pub lease_ttl_secs: u64,
+}
+
+/// Result of a usage report.
+///
+/// Returned by `License::report_usage()`.
+#[non_exhaustive]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UsageResult {
+    /// Meter the usage was reported to
+    pub meter: String,
+
+    /// Usage of the meter, including this report
+    pub used: i64,
+
+    /// Limit of the meter (`None` if unlimited)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<i64>,
+
+    /// This meter has reached its limit
+    pub exceeded: bool,
+
+    /// Any meter of the license has reached its limit
+    pub quota_exceeded: bool,
+
+    /// The report had already been counted; this retry changed nothing
+    pub duplicate: bool,
 }
 
 // === Server Response Parsing ===
@@ -133,6 +252,8 @@ pub(crate) struct ServerBindResponse {
     pub features: Vec<String>,
     pub tier: Option<String>,
     pub expires_at: Option<String>,
+    #[serde(default)]
+    pub signed_license: Option<SignedLicense>,
 }
 
 impl From<ServerBindResponse> for BindResult {
@@ -142,6 +263,36 @@ impl From<ServerBindResponse> for BindResult {
             features: resp.features,
             tier: resp.tier,
             expires_at: resp.expires_at,
+            signed_license: resp.signed_license,
+        }
+    }
+}
+
+/// Server response for trial endpoint.
+#[derive(Debug, Deserialize)]
+#[allow(dead_code)]
+pub(crate) struct ServerTrialResponse {
+    pub success: bool,
+    pub license_id: String,
+    pub license_key: String,
+    pub features: Vec<String>,
+    pub tier: Option<String>,
+    pub expires_at: Option<String>,
+    pub product: Option<String>,
+    #[serde(default)]
+    pub signed_license: Option<SignedLicense>,
+}
+
+impl From<ServerTrialResponse> for TrialResult {
+    fn from(resp: ServerTrialResponse) -> Self {
+        Self {
+            license_id: resp.license_id,
+            license_key: resp.license_key,
+            features: resp.features,
+            tier: resp.tier,
+            expires_at: resp.expires_at,
+            product: resp.product,
+            signed_license: resp.signed_license,
         }
     }
 }
@@ -164,9 +315,15 @@ pub(crate) struct ServerValidateResponse {
     pub tier: Option<String>,
     pub expires_at: Option<String>,
     pub grace_period_ends_at: Option<String>,
+    #[serde(default)]
+    pub offline_valid_until: Option<String>,
     pub warning: Option<String>,
     pub bandwidth_used_bytes: Option<i64>,
     pub bandwidth_limit_bytes: Option<i64>,
+    #[serde(default)]
+    pub signed_license: Option<SignedLicense>,
+    #[serde(default)]
+    pub heartbeat_interval_secs: Option<u64>,
 }
 
 impl From<ServerValidateResponse> for ValidationResult {
@@ -176,9 +333,12 @@ impl From<ServerValidateResponse> for ValidationResult {
             tier: resp.tier,
             expires_at: resp.expires_at,
             grace_period_ends_at: resp.grace_period_ends_at,
+            offline_valid_until: resp.offline_valid_until,
             warning: resp.warning,
             bandwidth_used_bytes: resp.bandwidth_used_bytes,
             bandwidth_limit_bytes: resp.bandwidth_limit_bytes,
+            signed_license: resp.signed_license,
+            heartbeat_interval_secs: resp.heartbeat_interval_secs,
         }
     }
 }
@@ -189,6 +349,12 @@ impl From<ServerValidateResponse> for ValidationResult {
 pub(crate) struct ServerHeartbeatResponse {
     pub success: bool,
     pub server_time: String,
+    #[serde(default)]
+    pub lease_expires_at: Option<String>,
+    #[serde(default)]
+    pub offline_valid_until: Option<String>,
+    #[serde(default)]
+    pub heartbeat_interval_secs: Option<u64>,
 }
 
 impl From<ServerHeartbeatResponse> for HeartbeatResult {
@@ -197,10 +363,41 @@ impl From<ServerHeartbeatResponse> for HeartbeatResult {
             server_time: resp.server_time,
             // Server doesn't currently return this, but we'll support it for future
             grace_period_ends_at: None,
+            lease_expires_at: resp.lease_expires_at,
+            offline_valid_until: resp.offline_valid_until,
+            heartbeat_interval_secs: resp.heartbeat_interval_secs,
         }
     }
 }
 
+/// Server response for checkout endpoint.
+#[derive(Debug, Deserialize)]
+#[allow(dead_code)]
+pub(crate) struct ServerCheckoutResponse {
+    pub success: bool,
+    pub license_id: String,
+    pub lease_expires_at: String,
+    pub lease_ttl_secs: u64,
+}
+
+impl From<ServerCheckoutResponse> for LeaseResult {
+    fn from(resp: ServerCheckoutResponse) -> Self {
+        Self {
+            license_id: resp.license_id,
+            lease_expires_at: resp.lease_expires_at,
+            lease_ttl_secs: resp.lease_ttl_secs,
+        }
+    }
+}
+
+/// Server response for checkin endpoint.
+#[derive(Debug, Deserialize)]
+#[allow(dead_code)]
+pub(crate) struct ServerCheckinResponse {
+    pub success: bool,
+    pub message: String,
+}
+
 /// Server response for validate-feature endpoint.
 #[derive(Debug, Deserialize)]
 pub(crate) struct ServerFeatureResponse {
@@ -219,6 +416,33 @@ impl From<ServerFeatureResponse> for FeatureResult {
     }
 }
 
+/// Server response for usage endpoint.
+#[derive(Debug, Deserialize)]
+#[allow(dead_code)]
+pub(crate) struct ServerUsageResponse {
+    pub success: bool,
+    pub meter: String,
+    pub used: i64,
+    #[serde(default)]
+    pub limit: Option<i64>,
+    pub exceeded: bool,
+    pub quota_exceeded: bool,
+    pub duplicate: bool,
+}
+
+impl From<ServerUsageResponse> for UsageResult {
+    fn from(resp: ServerUsageResponse) -> Self {
+        Self {
+            meter: resp.meter,
+            used: resp.used,
+            limit: resp.limit,
+            exceeded: resp.exceeded,
+            quota_exceeded: resp.quota_exceeded,
+            duplicate: resp.duplicate,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -230,9 +454,12 @@ mod tests {
             tier: Some("pro".to_string()),
             expires_at: None,
             grace_period_ends_at: None,
+            offline_valid_until: None,
             warning: None,
             bandwidth_used_bytes: None,
             bandwidth_limit_bytes: None,
+            signed_license: None,
+            heartbeat_interval_secs: None,
         };
 
         assert!(result.has_feature("feature_a"));
@@ -247,9 +474,12 @@ mod tests {
             tier: None,
             expires_at: None,
             grace_period_ends_at: Some("2024-12-31T23:59:59Z".to_string()),
+            offline_valid_until: None,
             warning: Some("Must connect by 2024-12-31".to_string()),
             bandwidth_used_bytes: None,
             bandwidth_limit_bytes: None,
+            signed_license: None,
+            heartbeat_interval_secs: None,
         };
 
         assert!(with_grace.has_grace_period_warning());
@@ -260,9 +490,12 @@ mod tests {
             tier: None,
             expires_at: None,
             grace_period_ends_at: None,
+            offline_valid_until: None,
             warning: None,
             bandwidth_used_bytes: None,
             bandwidth_limit_bytes: None,
+            signed_license: None,
+            heartbeat_interval_secs: None,
         };
 
         assert!(!without_grace.has_grace_period_warning());
@@ -306,4 +539,24 @@ mod tests {
         assert!(result.has_grace_period_warning());
         assert!(result.has_warning());
     }
+
+    #[test]
+    fn parse_server_heartbeat_response() {
+        let json = r#"{
+            "success": true,
+            "server_time": "2026-01-05T12:00:00Z",
+            "offline_valid_until": "2026-01-12T12:00:00+00:00",
+            "heartbeat_interval_secs": 120
+        }"#;
+
+        let resp: ServerHeartbeatResponse = serde_json::from_str(json).unwrap();
+        let result: HeartbeatResult = resp.into();
+
+        assert_eq!(result.heartbeat_interval_secs, Some(120));
+        assert_eq!(
+            result.offline_valid_until.as_deref(),
+            Some("2026-01-12T12:00:00+00:00")
+        );
+        assert!(result.lease_expires_at.is_none());
+    }
 }
@@ -0,0 +1,622 @@
+//! HTTP transport for the license client.
+//!
+//! Every [`License`](crate::client::License) sends its requests through one
+//! shared `reqwest::Client`. [`ClientOptions`] configures that client:
+//! request timeout, retries, proxy, extra root certificates, certificate
+//! pinning and user agent.
+//!
+//! ```rust,ignore
+//! use std::time::Duration;
+//! use talos::client::{ClientOptions, License, RetryPolicy};
+//!
+//! let options = ClientOptions::new()
+//!     .with_timeout(Duration::from_secs(10))
+//!     .with_retry(RetryPolicy::exponential(3))
+//!     .with_proxy("http://proxy.corp.example:3128")
+//!     .with_root_certificate(include_bytes!("corp-root-ca.pem"))
+//!     .with_pinned_spki("GKVrxmESHfXE3LBocmD56dF9rmO2R0Se12yOBweBTgk=")
+//!     .with_user_agent("acme-render/4.2");
+//!
+//! let license = License::new(key, server_url).with_options(&options)?;
+//! ```
+//!
+//! ## Certificate pinning
+//!
+//! A pin is the base64 SHA-256 hash of a certificate's DER-encoded
+//! SubjectPublicKeyInfo. With pins set, the server's certificate chain must
+//! still be valid, and the server's own (leaf) certificate must match a pin;
+//! intermediate and root certificates are not pinned. Pinning the public key
+//! rather than the certificate survives renewals that keep the key. Compute
+//! a pin with:
+//!
+//! ```text
+//! openssl x509 -in server.pem -pubkey -noout \
+//!   | openssl pkey -pubin -outform der \
+//!   | openssl dgst -sha256 -binary | base64
+//! ```
+
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
+
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use reqwest::{Client, RequestBuilder, Response};
+use rustls::client::danger::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier};
+use rustls::client::WebPkiServerVerifier;
+use rustls::pki_types::pem::PemObject;
+use rustls::pki_types::{CertificateDer, ServerName, UnixTime};
+use rustls::{ClientConfig, DigitallySignedStruct, RootCertStore, SignatureScheme};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+
+use crate::errors::{LicenseError, LicenseResult};
+
+/// Default timeout for license server requests.
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Default `User-Agent` header.
+const DEFAULT_USER_AGENT: &str = concat!("talos-client/", env!("CARGO_PKG_VERSION"));
+
+/// When to retry failed requests.
+///
+/// Requests are retried on network errors and `5xx` responses, waiting
+/// `initial_backoff` before the first retry and doubling the wait up to
+/// `max_backoff`. Other responses, including `4xx`, are returned as is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Retries after the first attempt (0 disables retrying)
+    pub max_retries: u32,
+    /// Wait before the first retry
+    pub initial_backoff: Duration,
+    /// Longest wait between retries
+    pub max_backoff: Duration,
+}
+
+impl RetryPolicy {
+    /// Never retry.
+    pub fn none() -> Self {
+        Self {
+            max_retries: 0,
+            initial_backoff: Duration::ZERO,
+            max_backoff: Duration::ZERO,
+        }
+    }
+
+    /// Retry up to `max_retries` times, starting at 500ms and backing off to 10s.
+    pub fn exponential(max_retries: u32) -> Self {
+        Self {
+            max_retries,
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(10),
+        }
+    }
+
+    /// Wait before retry number `retry` (starting at 0).
+    fn backoff(&self, retry: u32) -> Duration {
+        self.initial_backoff
+            .saturating_mul(2u32.saturating_pow(retry.min(16)))
+            .min(self.max_backoff)
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self::none()
+    }
+}
+
+/// HTTP settings for talking to the license server.
+///
+/// Apply them with `License::with_options()` or `License::set_options()`.
+/// The defaults match a plain client: 30 second timeout, no retries, the
+/// proxy from the `HTTPS_PROXY`/`HTTP_PROXY` environment variables and the
+/// bundled Mozilla root certificates.
+#[derive(Debug, Clone)]
+pub struct ClientOptions {
+    timeout: Duration,
+    retry: RetryPolicy,
+    proxy: Option<String>,
+    root_certificates: Vec<Vec<u8>>,
+    pinned_spki: Vec<String>,
+    user_agent: String,
+}
+
+impl Default for ClientOptions {
+    fn default() -> Self {
+        Self {
+            timeout: DEFAULT_TIMEOUT,
+            retry: RetryPolicy::none(),
+            proxy: None,
+            root_certificates: Vec::new(),
+            pinned_spki: Vec::new(),
+            user_agent: DEFAULT_USER_AGENT.to_string(),
+        }
+    }
+}
+
+impl ClientOptions {
+    /// Default options.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Timeout for each request attempt.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Retry failed requests according to `retry`.
+    pub fn with_retry(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    /// Send all requests through the proxy at `url` (e.g. `http://proxy:3128`).
+    pub fn with_proxy(mut self, url: impl Into<String>) -> Self {
+        self.proxy = Some(url.into());
+        self
+    }
+
+    /// Trust an additional root certificate, PEM or DER encoded.
+    ///
+    /// Use this for TLS-intercepting proxies or a private CA. A PEM file may
+    /// hold several certificates.
+    pub fn with_root_certificate(mut self, certificate: &[u8]) -> Self {
+        self.root_certificates.push(certificate.to_vec());
+        self
+    }
+
+    /// Require the server's certificate to have this public key.
+    ///
+    /// `pin` is the base64 SHA-256 hash of the DER SubjectPublicKeyInfo. Add
+    /// a backup pin so a key rotation does not lock clients out.
+    pub fn with_pinned_spki(mut self, pin: impl Into<String>) -> Self {
+        self.pinned_spki.push(pin.into());
+        self
+    }
+
+    /// `User-Agent` header sent with every request.
+    pub fn with_user_agent(mut self, user_agent: impl Into<String>) -> Self {
+        self.user_agent = user_agent.into();
+        self
+    }
+
+    /// Build the transport, validating the proxy URL, certificates and pins.
+    pub(crate) fn build(&self) -> LicenseResult<Transport> {
+        let mut builder = Client::builder()
+            .timeout(self.timeout)
+            .user_agent(self.user_agent.clone());
+
+        if let Some(ref url) = self.proxy {
+            let proxy = reqwest::Proxy::all(url)
+                .map_err(|e| LicenseError::ConfigError(format!("invalid proxy URL: {e}")))?;
+            builder = builder.proxy(proxy);
+        }
+
+        if !self.root_certificates.is_empty() || !self.pinned_spki.is_empty() {
+            builder = builder.use_preconfigured_tls(self.tls_config()?);
+        }
+
+        let client = builder
+            .build()
+            .map_err(|e| LicenseError::ConfigError(format!("failed to build HTTP client: {e}")))?;
+
+        Ok(Transport {
+            client,
+            retry: self.retry,
+        })
+    }
+
+    fn tls_config(&self) -> LicenseResult<ClientConfig> {
+        let provider = Arc::new(rustls::crypto::ring::default_provider());
+
+        let mut roots = RootCertStore {
+            roots: webpki_roots::TLS_SERVER_ROOTS.to_vec(),
+        };
+        for certificate in &self.root_certificates {
+            for der in parse_certificates(certificate)? {
+                roots.add(der).map_err(|e| {
+                    LicenseError::ConfigError(format!("invalid root certificate: {e}"))
+                })?;
+            }
+        }
+        let roots = Arc::new(roots);
+
+        let builder = ClientConfig::builder_with_provider(provider.clone())
+            .with_safe_default_protocol_versions()
+            .map_err(|e| LicenseError::ConfigError(format!("TLS setup failed: {e}")))?;
+
+        if self.pinned_spki.is_empty() {
+            return Ok(builder.with_root_certificates(roots).with_no_client_auth());
+        }
+
+        let pins = self
+            .pinned_spki
+            .iter()
+            .map(|pin| decode_pin(pin))
+            .collect::<LicenseResult<Vec<_>>>()?;
+        let inner = WebPkiServerVerifier::builder_with_provider(roots, provider)
+            .build()
+            .map_err(|e| LicenseError::ConfigError(format!("TLS setup failed: {e}")))?;
+
+        Ok(builder
+            .dangerous()
+            .with_custom_certificate_verifier(Arc::new(PinnedVerifier { inner, pins }))
+            .with_no_client_auth())
+    }
+}
+
+/// The HTTP client and retry policy a `License` sends requests with.
+#[derive(Debug, Clone)]
+pub(crate) struct Transport {
+    client: Client,
+    retry: RetryPolicy,
+}
+
+impl Default for Transport {
+    /// The default options, sharing one client across all licenses.
+    fn default() -> Self {
+        static DEFAULT: OnceLock<Transport> = OnceLock::new();
+        DEFAULT
+            .get_or_init(|| {
+                ClientOptions::default()
+                    .build()
+                    .unwrap_or_else(|_| Transport {
+                        client: Client::new(),
+                        retry: RetryPolicy::none(),
+                    })
+            })
+            .clone()
+    }
+}
+
+impl Transport {
+    /// POST `body` as JSON to `url`.
+    pub(crate) async fn post_json<T: Serialize + ?Sized>(
+        &self,
+        url: String,
+        body: &T,
+    ) -> LicenseResult<Response> {
+        self.send(self.client.post(url).json(body)).await
+    }
+
+    /// Send `request`, retrying network errors and `5xx` responses.
+    ///
+    /// Once retries are used up, the last response or error is returned.
+    async fn send(&self, request: RequestBuilder) -> LicenseResult<Response> {
+        let mut retry = 0;
+        while retry < self.retry.max_retries {
+            // Bodies that cannot be replayed are sent once
+            let Some(attempt) = request.try_clone() else {
+                break;
+            };
+            match attempt.send().await {
+                Ok(resp) if !resp.status().is_server_error() => return Ok(resp),
+                _ => tokio::time::sleep(self.retry.backoff(retry)).await,
+            }
+            retry += 1;
+        }
+
+        Ok(request.send().await?)
+    }
+}
+
+/// Parse one DER certificate, or every certificate in a PEM bundle.
+fn parse_certificates(bytes: &[u8]) -> LicenseResult<Vec<CertificateDer<'static>>> {
+    if !bytes.starts_with(b"-----BEGIN") {
+        return Ok(vec![CertificateDer::from(bytes.to_vec())]);
+    }
+
+    let certificates = CertificateDer::pem_slice_iter(bytes)
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| LicenseError::ConfigError(format!("invalid PEM certificate: {e}")))?;
+    if certificates.is_empty() {
+        return Err(LicenseError::ConfigError(
+            "no certificate found in PEM data".to_string(),
+        ));
+    }
+    Ok(certificates)
+}
+
+fn decode_pin(pin: &str) -> LicenseResult<[u8; 32]> {
+    BASE64
+        .decode(pin)
+        .ok()
+        .and_then(|bytes| <[u8; 32]>::try_from(bytes).ok())
+        .ok_or_else(|| {
+            LicenseError::ConfigError(format!(
+                "invalid SPKI pin '{pin}': expected a base64 SHA-256 hash"
+            ))
+        })
+}
+
+/// SHA-256 of a certificate's SubjectPublicKeyInfo, or `None` if the
+/// certificate cannot be parsed.
+fn spki_sha256(certificate: &[u8]) -> Option<[u8; 32]> {
+    subject_public_key_info(certificate).map(|spki| Sha256::digest(spki).into())
+}
+
+/// Locate the DER SubjectPublicKeyInfo inside an X.509 certificate.
+///
+/// ```text
+/// Certificate ::= SEQUENCE { tbsCertificate, ... }
+/// TBSCertificate ::= SEQUENCE {
+///     version [0] EXPLICIT OPTIONAL, serialNumber, signature,
+///     issuer, validity, subject, subjectPublicKeyInfo, ... }
+/// ```
+fn subject_public_key_info(certificate: &[u8]) -> Option<&[u8]> {
+    const SEQUENCE: u8 = 0x30;
+    const VERSION: u8 = 0xa0;
+
+    let (tag, certificate, _) = read_der(certificate)?;
+    if tag != SEQUENCE {
+        return None;
+    }
+    let (tag, mut tbs, _) = read_der(certificate)?;
+    if tag != SEQUENCE {
+        return None;
+    }
+
+    let (tag, _, rest) = read_der(tbs)?;
+    if tag == VERSION {
+        tbs = rest;
+    }
+    // serialNumber, signature, issuer, validity, subject
+    for _ in 0..5 {
+        tbs = read_der(tbs)?.2;
+    }
+
+    let (tag, _, rest) = read_der(tbs)?;
+    (tag == SEQUENCE).then(|| &tbs[..tbs.len() - rest.len()])
+}
+
+/// Read one DER element: `(tag, contents, rest)`.
+fn read_der(input: &[u8]) -> Option<(u8, &[u8], &[u8])> {
+    let (&tag, input) = input.split_first()?;
+    let (&first, mut input) = input.split_first()?;
+
+    let len = if first < 0x80 {
+        usize::from(first)
+    } else {
+        let count = usize::from(first & 0x7f);
+        if count == 0 || count > 4 || input.len() < count {
+            return None;
+        }
+        let (bytes, rest) = input.split_at(count);
+        input = rest;
+        bytes
+            .iter()
+            .fold(0usize, |len, &b| (len << 8) | usize::from(b))
+    };
+
+    if input.len() < len {
+        return None;
+    }
+    let (contents, rest) = input.split_at(len);
+    Some((tag, contents, rest))
+}
+
+/// Verifies the chain as usual, then requires the server's key to be pinned.
+///
+/// Only the end-entity certificate is checked: `intermediates` is the list
+/// the server sent, not the path that was verified, so a server could add
+/// any pinned certificate to it without it being part of the chain.
+#[derive(Debug)]
+struct PinnedVerifier {
+    inner: Arc<WebPkiServerVerifier>,
+    pins: Vec<[u8; 32]>,
+}
+
+impl PinnedVerifier {
+    fn matches(&self, certificate: &CertificateDer<'_>) -> bool {
+        spki_sha256(certificate).is_some_and(|hash| self.pins.contains(&hash))
+    }
+}
+
+impl ServerCertVerifier for PinnedVerifier {
+    fn verify_server_cert(
+        &self,
+        end_entity: &CertificateDer<'_>,
+        intermediates: &[CertificateDer<'_>],
+        server_name: &ServerName<'_>,
+        ocsp_response: &[u8],
+        now: UnixTime,
+    ) -> Result<ServerCertVerified, rustls::Error> {
+        let verified = self.inner.verify_server_cert(
+            end_entity,
+            intermediates,
+            server_name,
+            ocsp_response,
+            now,
+        )?;
+
+        if self.matches(end_entity) {
+            Ok(verified)
+        } else {
+            Err(rustls::Error::General(
+                "license server certificate does not match any pinned key".to_string(),
+            ))
+        }
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        self.inner.verify_tls12_signature(message, cert, dss)
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        self.inner.verify_tls13_signature(message, cert, dss)
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.inner.supported_verify_schemes()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Self-signed P-256 certificate for `license.test`.
+    const TEST_CERT_PEM: &str = "-----BEGIN CERTIFICATE-----
+MIIBhjCCASugAwIBAgIUPBayKyRU5VWxesRcXHINFzBdKUswCgYIKoZIzj0EAwIw
+FzEVMBMGA1UEAwwMbGljZW5zZS50ZXN0MCAXDTI2MTAxNzAyMTkwN1oYDzIxMjYw
+OTIzMDIxOTA3WjAXMRUwEwYDVQQDDAxsaWNlbnNlLnRlc3QwWTATBgcqhkjOPQIB
+BggqhkjOPQMBBwNCAAS3duZmF1cOxhT2CIl9Anxx21H2qfpp2LCwwmBNQ3HlyNKi
+uLXocjG8axSw5B1Muukd7qHMMVr3ii0I814NPgdqo1MwUTAdBgNVHQ4EFgQUuNMg
+I4FqdG+vfgS9woqhrIW5/gUwHwYDVR0jBBgwFoAUuNMgI4FqdG+vfgS9woqhrIW5
+/gUwDwYDVR0TAQH/BAUwAwEB/zAKBggqhkjOPQQDAgNJADBGAiEAg+aaw4a86gBv
+BGeoVZbDYABQSsNiQHMKMX4iVNt4jKYCIQD4zxz+jipJAqhJgrE1q28Wc8yd0llD
+Pzv+sCI2BZMyuQ==
+-----END CERTIFICATE-----
+";
+
+    /// `openssl x509 -pubkey | openssl pkey -pubin -outform der | sha256 | base64`
+    const TEST_CERT_PIN: &str = "GKVrxmESHfXE3LBocmD56dF9rmO2R0Se12yOBweBTgk=";
+
+    /// P-256 CA unrelated to `TEST_CERT_PEM`.
+    const OTHER_CA_PEM: &str = "-----BEGIN CERTIFICATE-----
+MIIBoDCCAUWgAwIBAgIUIVn5K5vmvFjL6oeRCrZdW+qBMQ0wCgYIKoZIzj0EAwIw
+HDEaMBgGA1UEAwwRVW5yZWxhdGVkIFRlc3QgQ0EwIBcNMjYxMDE3MDM1NzMxWhgP
+MjEyNjA5MjMwMzU3MzFaMBwxGjAYBgNVBAMMEVVucmVsYXRlZCBUZXN0IENBMFkw
+EwYHKoZIzj0CAQYIKoZIzj0DAQcDQgAExLDn7cQmLxWGKLjbavrBNEpLvMBWDwin
+Jd/3jC1tPXByuvRKPz/iKeaymZsfpewkjSiBiSBh1cnjMsyeSqZupaNjMGEwHQYD
+VR0OBBYEFPPd7xCrncoi+nbZcWQlX+qituk6MB8GA1UdIwQYMBaAFPPd7xCrncoi
++nbZcWQlX+qituk6MA8GA1UdEwEB/wQFMAMBAf8wDgYDVR0PAQH/BAQDAgIEMAoG
+CCqGSM49BAMCA0kAMEYCIQCrf1aqPvTmkRVjEaszn06b4uFgopflw8zhebRhgC7a
+sQIhAJnp6k/T0PzyRcVHrUkbIg0ryCx6oXl1IO8KSGZtHha3
+-----END CERTIFICATE-----
+";
+
+    /// `license.test` server certificate issued by `OTHER_CA_PEM`.
+    const OTHER_LEAF_PEM: &str = "-----BEGIN CERTIFICATE-----
+MIIBtDCCAVugAwIBAgIUChtcGEK8NUqxyh58ZTWyJGPeizwwCgYIKoZIzj0EAwIw
+HDEaMBgGA1UEAwwRVW5yZWxhdGVkIFRlc3QgQ0EwIBcNMjYxMDE3MDM1NzMxWhgP
+MjEyNjA5MjMwMzU3MzFaMBcxFTATBgNVBAMMDGxpY2Vuc2UudGVzdDBZMBMGByqG
+SM49AgEGCCqGSM49AwEHA0IABJmHE6BhBMLhPWo7YU9HqkDbDbW9/JU0kGuuydQV
+TN+l29Sn41n2wFkqLfr7ncC08DmG1xttTjs8Z1UcuUxvePajfjB8MBcGA1UdEQQQ
+MA6CDGxpY2Vuc2UudGVzdDAMBgNVHRMBAf8EAjAAMBMGA1UdJQQMMAoGCCsGAQUF
+BwMBMB0GA1UdDgQWBBTS9tgTXz9RntBVT6kQV6cOYzO3GzAfBgNVHSMEGDAWgBTz
+3e8Qq53KIvp22XFkJV/qorbpOjAKBggqhkjOPQQDAgNHADBEAiAY1iAxSUskpXq0
+jAoXYoa+40YEgqkQZzkCX7iuMczFggIgBrliYi7cBS06EhQOfdgeSMgIqFuZQJuH
+vroRKD15q+o=
+-----END CERTIFICATE-----
+";
+
+    fn pinned_verifier(roots: &str, pins: &[&[u8]]) -> PinnedVerifier {
+        let provider = Arc::new(rustls::crypto::ring::default_provider());
+        let mut store = RootCertStore::empty();
+        for der in parse_certificates(roots.as_bytes()).unwrap() {
+            store.add(der).unwrap();
+        }
+        let inner = WebPkiServerVerifier::builder_with_provider(Arc::new(store), provider)
+            .build()
+            .unwrap();
+        PinnedVerifier {
+            inner,
+            pins: pins.iter().map(|der| spki_sha256(der).unwrap()).collect(),
+        }
+    }
+
+    fn verify(
+        verifier: &PinnedVerifier,
+        end_entity: &CertificateDer<'_>,
+        intermediates: &[CertificateDer<'_>],
+    ) -> Result<ServerCertVerified, rustls::Error> {
+        let server_name = ServerName::try_from("license.test").unwrap();
+        verifier.verify_server_cert(
+            end_entity,
+            intermediates,
+            &server_name,
+            &[],
+            UnixTime::now(),
+        )
+    }
+
+    #[test]
+    fn pins_match_the_server_certificate() {
+        let leaf = &parse_certificates(OTHER_LEAF_PEM.as_bytes()).unwrap()[0];
+        let verifier = pinned_verifier(OTHER_CA_PEM, &[leaf]);
+        assert!(verify(&verifier, leaf, &[]).is_ok());
+    }
+
+    #[test]
+    fn pinned_certificate_appended_to_another_chain_is_rejected() {
+        let leaf = &parse_certificates(OTHER_LEAF_PEM.as_bytes()).unwrap()[0];
+        let pinned = &parse_certificates(TEST_CERT_PEM.as_bytes()).unwrap()[0];
+        let verifier = pinned_verifier(OTHER_CA_PEM, &[pinned]);
+
+        // The chain is valid without the extra certificate, which only
+        // carries the pinned key
+        let result = verify(&verifier, leaf, std::slice::from_ref(pinned));
+        assert!(matches!(result, Err(rustls::Error::General(_))));
+    }
+
+    #[test]
+    fn spki_hash_matches_openssl() {
+        let certificates = parse_certificates(TEST_CERT_PEM.as_bytes()).unwrap();
+        assert_eq!(certificates.len(), 1);
+
+        let hash = spki_sha256(&certificates[0]).expect("certificate should parse");
+        assert_eq!(hash, decode_pin(TEST_CERT_PIN).unwrap());
+    }
+
+    #[test]
+    fn spki_rejects_malformed_certificates() {
+        assert!(spki_sha256(&[]).is_none());
+        assert!(spki_sha256(&[0x30, 0x05, 0x30]).is_none());
+        assert!(spki_sha256(&[0x04, 0x01, 0x00]).is_none());
+    }
+
+    #[test]
+    fn retry_backoff_doubles_up_to_the_cap() {
+        let policy = RetryPolicy {
+            max_retries: 5,
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(3),
+        };
+        assert_eq!(policy.backoff(0), Duration::from_millis(500));
+        assert_eq!(policy.backoff(1), Duration::from_secs(1));
+        assert_eq!(policy.backoff(2), Duration::from_secs(2));
+        assert_eq!(policy.backoff(3), Duration::from_secs(3));
+        assert_eq!(policy.backoff(u32::MAX), Duration::from_secs(3));
+    }
+
+    #[test]
+    fn builds_with_certificates_and_pins() {
+        let options = ClientOptions::new()
+            .with_proxy("http://127.0.0.1:3128")
+            .with_root_certificate(TEST_CERT_PEM.as_bytes())
+            .with_pinned_spki(TEST_CERT_PIN);
+        assert!(options.build().is_ok());
+    }
+
+    #[test]
+    fn rejects_invalid_options() {
+        let bad_pin = ClientOptions::new().with_pinned_spki("not-a-pin");
+        assert!(matches!(bad_pin.build(), Err(LicenseError::ConfigError(_))));
+
+        let bad_cert = ClientOptions::new()
+            .with_root_certificate(b"-----BEGIN CERTIFICATE-----\n!!\n-----END CERTIFICATE-----\n");
+        assert!(matches!(
+            bad_cert.build(),
+            Err(LicenseError::ConfigError(_))
+        ));
+
+        let bad_proxy = ClientOptions::new().with_proxy("not a url");
+        assert!(matches!(
+            bad_proxy.build(),
+            Err(LicenseError::ConfigError(_))
+        ));
+    }
+}
@@ -10,7 +10,10 @@
 //! All configuration options can be overridden via environment variables:
 //! - `TALOS_SERVER_HOST` - Server bind address
 //! - `TALOS_SERVER_PORT` - Server port
+//! - `TALOS_LEASE_TTL_SECS` - Seconds a floating lease survives without a heartbeat
+//! - `TALOS_OFFLINE_DAYS` - Default days a client may run offline after a successful check
 //! - `TALOS_DATABASE_URL` - Database connection URL
+//! - `TALOS_DATABASE_RUN_MIGRATIONS` - Apply pending schema migrations on startup
 //! - `TALOS_LICENSE_KEY_PREFIX` - License key prefix
 //! - `TALOS_LOG_LEVEL` - Log level (trace, debug, info, warn, error)
 //! - `TALOS_AUTH_ENABLED` - Enable JWT authentication (requires `jwt-auth` feature)
@@ -18,6 +21,22 @@
 //! - `TALOS_JWT_ISSUER` - JWT issuer claim
 //! - `TALOS_JWT_AUDIENCE` - JWT audience claim
 //! - `TALOS_TOKEN_EXPIRATION_SECS` - Token expiration time in seconds
+//! - `TALOS_RATE_LIMIT_ENABLED` - Enable rate limiting (requires `rate-limiting` feature)
+//! - `TALOS_JOBS_ENABLED` - Run background jobs (requires `background-jobs` feature)
+//! - `TALOS_JOBS_GRACE_PERIOD_CRON` - Cron schedule for the grace period check
+//! - `TALOS_JOBS_LICENSE_EXPIRATION_CRON` - Cron schedule for the license expiration check
+//! - `TALOS_JOBS_STALE_DEVICE_CLEANUP_ENABLED` - Enable stale device cleanup
+//! - `TALOS_JOBS_STALE_DEVICE_CRON` - Cron schedule for stale device cleanup
+//! - `TALOS_JOBS_STALE_DEVICE_DAYS` - Days without contact before a device is released
+//! - `TALOS_JOBS_USAGE_RESET_ENABLED` - Reset usage meters at the end of each billing cycle
+//! - `TALOS_JOBS_USAGE_RESET_CRON` - Cron schedule for the billing cycle check
+//! - `TALOS_WEBHOOKS_ENABLED` - Queue and deliver webhook notifications
+//! - `TALOS_WEBHOOKS_MAX_ATTEMPTS` - Delivery attempts before a webhook delivery is marked failed
+//! - `TALOS_TRIALS_ENABLED` - Issue self-service trial licenses
+//! - `TALOS_TRIALS_TIER` - Tier given to trial licenses
+//! - `TALOS_TRIALS_DURATION_DAYS` - Days a trial license is valid
+//! - `TALOS_SIGNING_KEY` - Base64 PKCS#8 Ed25519 key for signing licenses
+//! - `TALOS_SIGNING_KEY_PATH` - File containing the Base64 signing key
 
 use config::Config;
 use serde::Deserialize;
@@ -49,6 +68,14 @@ pub struct TalosConfig {
     pub rate_limit: RateLimitConfig,
     /// Admin API configuration
     pub admin: AdminConfig,
+    /// License signing configuration
+    pub signing: SigningConfig,
+    /// Background job configuration (requires "background-jobs" feature)
+    pub jobs: JobConfig,
+    /// Webhook delivery configuration
+    pub webhooks: WebhookConfig,
+    /// Self-service trial configuration
+    pub trials: TrialConfig,
     /// Tier configurations (optional, keyed by tier name)
     pub tiers: HashMap<String, TierConfig>,
 }
@@ -63,6 +90,11 @@ pub struct ServerConfig {
     pub port: u16,
     /// Heartbeat interval in seconds
     pub heartbeat_interval: u64,
+    /// Seconds a floating lease stays checked out without a heartbeat
+    pub lease_ttl_secs: u64,
+    /// Days a client may run offline after a successful check (0 = never);
+    /// tiers and individual licenses can override this
+    pub offline_days: u32,
 }
 
 impl Default for ServerConfig {
@@ -71,6 +103,8 @@ impl Default for ServerConfig {
             host: "127.0.0.1".to_string(),
             port: 8080,
             heartbeat_interval: 60,
+            lease_ttl_secs: 300,
+            offline_days: 7,
         }
     }
 }
@@ -107,6 +141,8 @@ pub struct DatabaseConfig {
     pub sqlite_url: String,
     /// PostgreSQL connection URL
     pub postgres_url: String,
+    /// Apply pending schema migrations when the server starts
+    pub run_migrations: bool,
 }
 
 impl Default for DatabaseConfig {
@@ -115,6 +151,7 @@ impl Default for DatabaseConfig {
             db_type: "sqlite".to_string(),
             sqlite_url: "sqlite://talos.db".to_string(),
             postgres_url: "postgres://localhost/talos".to_string(),
+            run_migrations: true,
         }
     }
 }
@@ -180,7 +217,7 @@ pub struct RateLimitConfig {
     pub validate_rpm: u32,
     /// Requests per minute for /heartbeat endpoint
     pub heartbeat_rpm: u32,
-    /// Requests per minute for /bind and /release endpoints
+    /// Requests per minute for /bind, /release, /checkout and /checkin endpoints
     pub bind_rpm: u32,
     /// Burst size (allows short bursts above the limit)
     pub burst_size: u32,
@@ -217,11 +254,139 @@ pub struct AdminConfig {
 
     /// Enable audit logging for admin actions.
     ///
-    /// When enabled, all admin API actions are logged with user/token ID,
-    /// license state changes, and authentication failures.
+    /// When enabled, every admin API change to a license or API token is
+    /// written to the `audit_log` table with the caller, changed fields, IP
+    /// and request ID. Review it with `GET /api/v1/audit`.
     pub audit_logging: bool,
 }
 
+/// License signing configuration.
+///
+/// When a key is configured, the server issues Ed25519-signed license
+/// documents that clients can verify offline. Generate a key with
+/// `talos_server signing-key generate`.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct SigningConfig {
+    /// Base64 PKCS#8 private key (use `env:VAR_NAME` to read from environment)
+    pub private_key: String,
+    /// Path to a file containing the Base64 PKCS#8 private key
+    pub private_key_path: String,
+}
+
+/// Background job configuration.
+///
+/// Used by `talos_server` when the `background-jobs` feature is enabled.
+/// Cron expressions use six fields, starting with seconds.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct JobConfig {
+    /// Run background jobs in the server (default: true)
+    pub enabled: bool,
+    /// Cron expression for grace period expiration check (default: every hour at minute 0)
+    pub grace_period_cron: String,
+    /// Cron expression for license expiration check (default: every hour at minute 15)
+    pub license_expiration_cron: String,
+    /// Whether stale device cleanup is enabled (default: false)
+    pub stale_device_cleanup_enabled: bool,
+    /// Cron expression for stale device cleanup (default: daily at 3 AM)
+    pub stale_device_cron: String,
+    /// Number of days after which a device is considered stale (default: 90)
+    pub stale_device_days: u32,
+    /// Whether usage meters reset at the end of each billing cycle (default: true)
+    pub usage_reset_enabled: bool,
+    /// Cron expression for the billing cycle check (default: every hour at minute 30)
+    pub usage_reset_cron: String,
+}
+
+impl Default for JobConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            // Every hour at minute 0
+            grace_period_cron: "0 0 * * * *".to_string(),
+            // Every hour at minute 15
+            license_expiration_cron: "0 15 * * * *".to_string(),
+            // Disabled by default
+            stale_device_cleanup_enabled: false,
+            // Daily at 3 AM
+            stale_device_cron: "0 0 3 * * *".to_string(),
+            // 90 days
+            stale_device_days: 90,
+            // Only affects licenses with a billing cycle
+            usage_reset_enabled: true,
+            // Every hour at minute 30
+            usage_reset_cron: "0 30 * * * *".to_string(),
+        }
+    }
+}
+
+/// Webhook delivery configuration.
+///
+/// Endpoints and their event subscriptions are managed through the admin API
+/// (`/api/v1/webhooks`); this section controls queueing and retries.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct WebhookConfig {
+    /// Queue webhook deliveries for license events (default: false)
+    pub enabled: bool,
+    /// Attempts per delivery before it is marked failed (default: 8)
+    pub max_attempts: u32,
+    /// Delay before the first retry; doubled after each failure (default: 30)
+    pub retry_base_secs: u64,
+    /// Upper bound for the retry delay (default: 3600)
+    pub max_retry_delay_secs: u64,
+    /// HTTP timeout for a single delivery attempt (default: 10)
+    pub timeout_secs: u64,
+    /// How often `talos_server` checks for due deliveries (default: 5)
+    pub poll_interval_secs: u64,
+    /// Allow webhook URLs on loopback, private and link-local addresses
+    /// (default: false)
+    pub allow_private_targets: bool,
+}
+
+impl Default for WebhookConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_attempts: 8,
+            retry_base_secs: 30,
+            max_retry_delay_secs: 3600,
+            timeout_secs: 10,
+            poll_interval_secs: 5,
+            allow_private_targets: false,
+        }
+    }
+}
+
+/// Self-service trial configuration.
+///
+/// Trials are issued by `POST /api/v1/client/trial`, at most one per machine,
+/// or one per machine and product when `products` is set.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct TrialConfig {
+    /// Issue trial licenses to clients (default: false)
+    pub enabled: bool,
+    /// Tier given to trial licenses, with its features (default: none)
+    pub tier: Option<String>,
+    /// Days a trial license is valid (default: 14)
+    pub duration_days: u32,
+    /// Products a machine may start a separate trial for (default: none)
+    pub products: Vec<String>,
+}
+
+impl Default for TrialConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            tier: None,
+            duration_days: 14,
+            products: Vec::new(),
+        }
+    }
+}
+
 impl TalosConfig {
     /// Load configuration from file and environment.
     ///
@@ -238,6 +403,10 @@ impl TalosConfig {
             .map_err(|e| LicenseError::ConfigError(e.to_string()))?
             .set_default("server.heartbeat_interval", 60)
             .map_err(|e| LicenseError::ConfigError(e.to_string()))?
+            .set_default("server.lease_ttl_secs", 300)
+            .map_err(|e| LicenseError::ConfigError(e.to_string()))?
+            .set_default("server.offline_days", 7)
+            .map_err(|e| LicenseError::ConfigError(e.to_string()))?
             .set_default("license.key_prefix", "LIC")
             .map_err(|e| LicenseError::ConfigError(e.to_string()))?
             .set_default("license.key_segments", 4)
@@ -250,6 +419,8 @@ impl TalosConfig {
             .map_err(|e| LicenseError::ConfigError(e.to_string()))?
             .set_default("database.postgres_url", "postgres://localhost/talos")
             .map_err(|e| LicenseError::ConfigError(e.to_string()))?
+            .set_default("database.run_migrations", true)
+            .map_err(|e| LicenseError::ConfigError(e.to_string()))?
             .set_default("logging.enabled", false)
             .map_err(|e| LicenseError::ConfigError(e.to_string()))?
             .set_default("logging.level", "info")
@@ -278,6 +449,46 @@ impl TalosConfig {
             .map_err(|e| LicenseError::ConfigError(e.to_string()))?
             .set_default("admin.audit_logging", false)
             .map_err(|e| LicenseError::ConfigError(e.to_string()))?
+            .set_default("signing.private_key", "")
+            .map_err(|e| LicenseError::ConfigError(e.to_string()))?
+            .set_default("signing.private_key_path", "")
+            .map_err(|e| LicenseError::ConfigError(e.to_string()))?
+            .set_default("jobs.enabled", true)
+            .map_err(|e| LicenseError::ConfigError(e.to_string()))?
+            .set_default("jobs.grace_period_cron", "0 0 * * * *")
+            .map_err(|e| LicenseError::ConfigError(e.to_string()))?
+            .set_default("jobs.license_expiration_cron", "0 15 * * * *")
+            .map_err(|e| LicenseError::ConfigError(e.to_string()))?
+            .set_default("jobs.stale_device_cleanup_enabled", false)
+            .map_err(|e| LicenseError::ConfigError(e.to_string()))?
+            .set_default("jobs.stale_device_cron", "0 0 3 * * *")
+            .map_err(|e| LicenseError::ConfigError(e.to_string()))?
+            .set_default("jobs.stale_device_days", 90)
+            .map_err(|e| LicenseError::ConfigError(e.to_string()))?
+            .set_default("jobs.usage_reset_enabled", true)
+            .map_err(|e| LicenseError::ConfigError(e.to_string()))?
+            .set_default("jobs.usage_reset_cron", "0 30 * * * *")
+            .map_err(|e| LicenseError::ConfigError(e.to_string()))?
+            .set_default("webhooks.enabled", false)
+            .map_err(|e| LicenseError::ConfigError(e.to_string()))?
+            .set_default("webhooks.max_attempts", 8)
+            .map_err(|e| LicenseError::ConfigError(e.to_string()))?
+            .set_default("webhooks.retry_base_secs", 30)
+            .map_err(|e| LicenseError::ConfigError(e.to_string()))?
+            .set_default("webhooks.max_retry_delay_secs", 3600)
+            .map_err(|e| LicenseError::ConfigError(e.to_string()))?
+            .set_default("webhooks.timeout_secs", 10)
+            .map_err(|e| LicenseError::ConfigError(e.to_string()))?
+            .set_default("webhooks.poll_interval_secs", 5)
+            .map_err(|e| LicenseError::ConfigError(e.to_string()))?
+            .set_default("webhooks.allow_private_targets", false)
+            .map_err(|e| LicenseError::ConfigError(e.to_string()))?
+            .set_default("trials.enabled", false)
+            .map_err(|e| LicenseError::ConfigError(e.to_string()))?
+            .set_default("trials.duration_days", 14)
+            .map_err(|e| LicenseError::ConfigError(e.to_string()))?
+            .set_default("trials.products", Vec::<String>::new())
+            .map_err(|e| LicenseError::ConfigError(e.to_string()))?
             // Load from config.toml (optional)
             .add_source(config::File::with_name("config").required(false))
             // Override with environment variables
@@ -297,6 +508,20 @@ impl TalosConfig {
                     .and_then(|v| v.parse::<i64>().ok()),
             )
             .map_err(|e| LicenseError::ConfigError(e.to_string()))?
+            .set_override_option(
+                "server.lease_ttl_secs",
+                env::var("TALOS_LEASE_TTL_SECS")
+                    .ok()
+                    .and_then(|v| v.parse::<i64>().ok()),
+            )
+            .map_err(|e| LicenseError::ConfigError(e.to_string()))?
+            .set_override_option(
+                "server.offline_days",
+                env::var("TALOS_OFFLINE_DAYS")
+                    .ok()
+                    .and_then(|v| v.parse::<i64>().ok()),
+            )
+            .map_err(|e| LicenseError::ConfigError(e.to_string()))?
             .set_override_option(
                 "license.key_prefix",
                 env::var("TALOS_LICENSE_KEY_PREFIX").ok(),
@@ -318,6 +543,13 @@ impl TalosConfig {
                     .filter(|url| url.starts_with("postgres")),
             )
             .map_err(|e| LicenseError::ConfigError(e.to_string()))?
+            .set_override_option(
+                "database.run_migrations",
+                env::var("TALOS_DATABASE_RUN_MIGRATIONS")
+                    .ok()
+                    .and_then(|v| v.parse::<bool>().ok()),
+            )
+            .map_err(|e| LicenseError::ConfigError(e.to_string()))?
             .set_override_option(
                 "logging.enabled",
                 env::var("TALOS_LOGGING_ENABLED")
@@ -347,6 +579,13 @@ impl TalosConfig {
                     .and_then(|v| v.parse::<i64>().ok()),
             )
             .map_err(|e| LicenseError::ConfigError(e.to_string()))?
+            .set_override_option(
+                "rate_limit.enabled",
+                env::var("TALOS_RATE_LIMIT_ENABLED")
+                    .ok()
+                    .and_then(|v| v.parse::<bool>().ok()),
+            )
+            .map_err(|e| LicenseError::ConfigError(e.to_string()))?
             // Admin API security overrides
             .set_override_option(
                 "admin.ip_whitelist",
@@ -365,6 +604,102 @@ impl TalosConfig {
                     .ok()
                     .and_then(|v| v.parse::<bool>().ok()),
             )
+            .map_err(|e| LicenseError::ConfigError(e.to_string()))?
+            // License signing overrides
+            .set_override_option("signing.private_key", env::var("TALOS_SIGNING_KEY").ok())
+            .map_err(|e| LicenseError::ConfigError(e.to_string()))?
+            .set_override_option(
+                "signing.private_key_path",
+                env::var("TALOS_SIGNING_KEY_PATH").ok(),
+            )
+            .map_err(|e| LicenseError::ConfigError(e.to_string()))?
+            // Background job overrides
+            .set_override_option(
+                "jobs.enabled",
+                env::var("TALOS_JOBS_ENABLED")
+                    .ok()
+                    .and_then(|v| v.parse::<bool>().ok()),
+            )
+            .map_err(|e| LicenseError::ConfigError(e.to_string()))?
+            .set_override_option(
+                "jobs.grace_period_cron",
+                env::var("TALOS_JOBS_GRACE_PERIOD_CRON").ok(),
+            )
+            .map_err(|e| LicenseError::ConfigError(e.to_string()))?
+            .set_override_option(
+                "jobs.license_expiration_cron",
+                env::var("TALOS_JOBS_LICENSE_EXPIRATION_CRON").ok(),
+            )
+            .map_err(|e| LicenseError::ConfigError(e.to_string()))?
+            .set_override_option(
+                "jobs.stale_device_cleanup_enabled",
+                env::var("TALOS_JOBS_STALE_DEVICE_CLEANUP_ENABLED")
+                    .ok()
+                    .and_then(|v| v.parse::<bool>().ok()),
+            )
+            .map_err(|e| LicenseError::ConfigError(e.to_string()))?
+            .set_override_option(
+                "jobs.stale_device_cron",
+                env::var("TALOS_JOBS_STALE_DEVICE_CRON").ok(),
+            )
+            .map_err(|e| LicenseError::ConfigError(e.to_string()))?
+            .set_override_option(
+                "jobs.stale_device_days",
+                env::var("TALOS_JOBS_STALE_DEVICE_DAYS")
+                    .ok()
+                    .and_then(|v| v.parse::<i64>().ok()),
+            )
+            .map_err(|e| LicenseError::ConfigError(e.to_string()))?
+            .set_override_option(
+                "jobs.usage_reset_enabled",
+                env::var("TALOS_JOBS_USAGE_RESET_ENABLED")
+                    .ok()
+                    .and_then(|v| v.parse::<bool>().ok()),
+            )
+            .map_err(|e| LicenseError::ConfigError(e.to_string()))?
+            .set_override_option(
+                "jobs.usage_reset_cron",
+                env::var("TALOS_JOBS_USAGE_RESET_CRON").ok(),
+            )
+            .map_err(|e| LicenseError::ConfigError(e.to_string()))?
+            // Webhook overrides
+            .set_override_option(
+                "webhooks.enabled",
+                env::var("TALOS_WEBHOOKS_ENABLED")
+                    .ok()
+                    .and_then(|v| v.parse::<bool>().ok()),
+            )
+            .map_err(|e| LicenseError::ConfigError(e.to_string()))?
+            .set_override_option(
+                "webhooks.max_attempts",
+                env::var("TALOS_WEBHOOKS_MAX_ATTEMPTS")
+                    .ok()
+                    .and_then(|v| v.parse::<i64>().ok()),
+            )
+            .map_err(|e| LicenseError::ConfigError(e.to_string()))?
+            .set_override_option(
+                "webhooks.allow_private_targets",
+                env::var("TALOS_WEBHOOKS_ALLOW_PRIVATE_TARGETS")
+                    .ok()
+                    .and_then(|v| v.parse::<bool>().ok()),
+            )
+            .map_err(|e| LicenseError::ConfigError(e.to_string()))?
+            // Trial overrides
+            .set_override_option(
+                "trials.enabled",
+                env::var("TALOS_TRIALS_ENABLED")
+                    .ok()
+                    .and_then(|v| v.parse::<bool>().ok()),
+            )
+            .map_err(|e| LicenseError::ConfigError(e.to_string()))?
+            .set_override_option("trials.tier", env::var("TALOS_TRIALS_TIER").ok())
+            .map_err(|e| LicenseError::ConfigError(e.to_string()))?
+            .set_override_option(
+                "trials.duration_days",
+                env::var("TALOS_TRIALS_DURATION_DAYS")
+                    .ok()
+                    .and_then(|v| v.parse::<i64>().ok()),
+            )
             .map_err(|e| LicenseError::ConfigError(e.to_string()))?;
 
         let settings = builder
@@ -385,6 +720,13 @@ impl TalosConfig {
             ));
         }
 
+        // Validate lease TTL
+        if self.server.lease_ttl_secs == 0 {
+            return Err(LicenseError::ConfigError(
+                "server.lease_ttl_secs must be greater than 0".to_string(),
+            ));
+        }
+
         // Validate database type
         match self.database.db_type.as_str() {
             "sqlite" | "postgres" => {}
@@ -429,6 +771,48 @@ impl TalosConfig {
             ));
         }
 
+        // Validate job config (only if stale device cleanup is enabled)
+        if self.jobs.stale_device_cleanup_enabled && self.jobs.stale_device_days == 0 {
+            return Err(LicenseError::ConfigError(
+                "jobs.stale_device_days must be greater than 0 when stale device cleanup is enabled"
+                    .to_string(),
+            ));
+        }
+
+        // Validate webhook config (only if enabled)
+        if self.webhooks.enabled {
+            if self.webhooks.max_attempts == 0 {
+                return Err(LicenseError::ConfigError(
+                    "webhooks.max_attempts must be greater than 0".to_string(),
+                ));
+            }
+            if self.webhooks.timeout_secs == 0 || self.webhooks.poll_interval_secs == 0 {
+                return Err(LicenseError::ConfigError(
+                    "webhooks.timeout_secs and webhooks.poll_interval_secs must be greater than 0"
+                        .to_string(),
+                ));
+            }
+        }
+
+        // Validate trial config (only if enabled)
+        if self.trials.enabled && self.trials.duration_days == 0 {
+            return Err(LicenseError::ConfigError(
+                "trials.duration_days must be greater than 0 when trials are enabled".to_string(),
+            ));
+        }
+
+        // Validate tier billing cycles
+        #[cfg(feature = "server")]
+        for (name, tier) in &self.tiers {
+            if let Some(cycle) = &tier.billing_cycle {
+                cycle
+                    .parse::<crate::server::billing::BillingCycle>()
+                    .map_err(|e| {
+                        LicenseError::ConfigError(format!("tiers.{name}.billing_cycle: {e}"))
+                    })?;
+            }
+        }
+
         Ok(())
     }
 }
@@ -496,11 +880,38 @@ pub fn get_heartbeat_interval() -> u64 {
         .unwrap_or(60)
 }
 
+/// Retrieve the floating lease TTL in seconds.
+pub fn get_lease_ttl_secs() -> u64 {
+    get_config().map(|c| c.server.lease_ttl_secs).unwrap_or(300)
+}
+
+/// Retrieve the default offline allowance in days.
+pub fn get_offline_days() -> u32 {
+    get_config().map(|c| c.server.offline_days).unwrap_or(7)
+}
+
 /// Check whether logging is enabled.
 pub fn is_logging_enabled() -> bool {
     get_config().map(|c| c.logging.enabled).unwrap_or(false)
 }
 
+/// Check whether admin actions are recorded in the audit log.
+pub fn is_audit_logging_enabled() -> bool {
+    get_config().map(|c| c.admin.audit_logging).unwrap_or(false)
+}
+
+/// Check whether license events are queued for webhook delivery.
+pub fn is_webhooks_enabled() -> bool {
+    get_config().map(|c| c.webhooks.enabled).unwrap_or(false)
+}
+
+/// Check whether webhooks may target loopback, private and link-local hosts.
+pub fn is_webhook_private_targets_allowed() -> bool {
+    get_config()
+        .map(|c| c.webhooks.allow_private_targets)
+        .unwrap_or(false)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -524,6 +935,15 @@ mod tests {
         assert!(result.unwrap_err().to_string().contains("port"));
     }
 
+    #[test]
+    fn validates_lease_ttl_not_zero() {
+        let mut config = default_config();
+        config.server.lease_ttl_secs = 0;
+        let result = config.validate();
+        assert!(result.is_err());
+        assert!(result.unwrap_err().to_string().contains("lease_ttl_secs"));
+    }
+
     #[test]
     fn validates_database_type() {
         let mut config = default_config();
@@ -589,4 +1009,65 @@ mod tests {
             .to_string()
             .contains("key_segment_length"));
     }
+
+    #[test]
+    fn validates_stale_device_days_when_cleanup_enabled() {
+        let mut config = default_config();
+        config.jobs.stale_device_days = 0;
+        assert!(config.validate().is_ok());
+
+        config.jobs.stale_device_cleanup_enabled = true;
+        let result = config.validate();
+        assert!(result.is_err());
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("stale_device_days"));
+    }
+
+    #[cfg(feature = "server")]
+    #[test]
+    fn validates_tier_billing_cycles() {
+        let mut config = default_config();
+        let tier = |cycle: &str| TierConfig {
+            billing_cycle: Some(cycle.to_string()),
+            ..Default::default()
+        };
+        config.tiers.insert("pro".to_string(), tier("monthly"));
+        assert!(config.validate().is_ok());
+
+        config
+            .tiers
+            .insert("pro".to_string(), tier("every other tuesday"));
+        let result = config.validate();
+        assert!(result.is_err());
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("tiers.pro.billing_cycle"));
+    }
+
+    #[test]
+    fn validates_webhook_attempts_when_enabled() {
+        let mut config = default_config();
+        config.webhooks.max_attempts = 0;
+        assert!(config.validate().is_ok());
+
+        config.webhooks.enabled = true;
+        let result = config.validate();
+        assert!(result.is_err());
+        assert!(result.unwrap_err().to_string().contains("max_attempts"));
+    }
+
+    #[test]
+    fn validates_trial_duration_when_enabled() {
+        let mut config = default_config();
+        config.trials.duration_days = 0;
+        assert!(config.validate().is_ok());
+
+        config.trials.enabled = true;
+        let result = config.validate();
+        assert!(result.is_err());
+        assert!(result.unwrap_err().to_string().contains("duration_days"));
+    }
 }
@@ -39,6 +39,10 @@ pub enum LicenseError {
     #[error("decryption error: {0}")]
     DecryptionError(String),
 
+    /// Errors when signing or verifying a signed license document.
+    #[error("signature error: {0}")]
+    SignatureError(String),
+
     /// Errors when accessing the OS keyring/credential store.
     #[error("keyring error: {0}")]
     KeyringError(String),
@@ -1,84 +1,187 @@
 //! Hardware fingerprinting for Talos license binding.
 //!
-//! This module unifies platform-specific hardware identification
-//! (CPU ID + motherboard/board ID) into a *hashed* fingerprint.
+//! This module unifies platform-specific hardware identification into a
+//! *hashed* fingerprint. A [`FingerprintProvider`] supplies the identifying
+//! values for the machine; [`get_hardware_id`] hashes them.
 //!
 //! Why hashed?
 //! - avoids storing raw hardware serials (privacy + security)
 //! - normalizes inconsistent values across OSes
 //! - allows stable lifetime binding without revealing device secrets
+//!
+//! ## Platform defaults
+//!
+//! | Platform | Provider | Components |
+//! |----------|----------|------------|
+//! | Linux | `LinuxFingerprint` | `/etc/machine-id` and container/VM detection |
+//! | Windows | `WindowsFingerprint` | Processor ID and baseboard serial |
+//! | macOS | `MacosFingerprint` | CPU brand and platform serial number |
+//!
+//! None of the defaults need root. Replace the provider once at startup,
+//! before any license call, with [`set_fingerprint_provider`]:
+//!
+//! ```rust,ignore
+//! use talos::hardware::{set_fingerprint_provider, FingerprintComponent, LinuxFingerprint};
+//!
+//! set_fingerprint_provider(LinuxFingerprint::new([
+//!     FingerprintComponent::MachineId,
+//!     FingerprintComponent::RootDiskSerial,
+//! ]));
+//! ```
+//!
+//! Changing the provider changes the hardware ID: bound licenses must be
+//! released and bound again, and locally stored license files, which are
+//! encrypted with a key derived from the hardware ID, can no longer be read.
+
+use std::sync::{Arc, RwLock};
 
-use crate::errors::{LicenseError, LicenseResult};
 use ring::digest;
 
+/// Platform-specific hardware ID providers
 #[cfg(target_os = "linux")]
 mod linux;
 #[cfg(target_os = "macos")]
 mod macos;
-/// Platform-specific hardware ID providers
 #[cfg(target_os = "windows")]
 mod windows;
 
-/// Returns a *consistent hashed fingerprint* based on the device's hardware.
-///
-/// Steps:
-/// 1. Try to retrieve CPU ID (best-effort)
-/// 2. Try to retrieve motherboard / board serial (best-effort)
-/// 3. Replace missing values with deterministic fallback labels
-/// 4. Compute SHA-256 hash over "cpu_id|board_id"
+#[cfg(target_os = "linux")]
+pub use linux::{LinuxFingerprint, LinuxLegacyFingerprint};
+#[cfg(target_os = "macos")]
+pub use macos::MacosFingerprint;
+#[cfg(target_os = "windows")]
+pub use windows::WindowsFingerprint;
+
+/// Source of the identifying values a hardware ID is derived from.
+pub trait FingerprintProvider: Send + Sync {
+    /// Identifying values for this machine, in a fixed order.
+    ///
+    /// The hardware ID is the SHA-256 of the values joined with `|`, so the
+    /// same values must come back on every call and every run.
+    fn components(&self) -> Vec<String>;
+}
+
+/// A machine property that can go into a fingerprint.
 ///
-/// Example output:
-///     "0a3e7c8d9921ac4d89f11223f4d447adfeec2d722f974146ecf2917c4e97fcb2"
-pub fn get_hardware_id() -> String {
-    // 1. Best-effort raw identifiers
-    let cpu_raw = get_cpu_id().unwrap_or_else(|_| "cpu_unknown".to_string());
-    let board_raw = get_motherboard_id().unwrap_or_else(|_| "board_unknown".to_string());
+/// Which components a provider can read depends on the platform; see
+/// `LinuxFingerprint` for Linux.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FingerprintComponent {
+    /// systemd/D-Bus machine ID, unique per OS installation
+    MachineId,
+    /// SMBIOS system UUID (usually readable by root only)
+    ProductUuid,
+    /// MAC address of the first physical network interface
+    PrimaryMac,
+    /// Serial number of the disk holding the root filesystem
+    RootDiskSerial,
+    /// CPU model name (shared by every machine with the same CPU)
+    CpuModel,
+    /// Motherboard serial number (usually readable by root only)
+    BoardSerial,
+    /// Container runtime or hypervisor the system runs under, or `none`
+    Virtualization,
+}
 
-    let combined = format!("{}|{}", cpu_raw, board_raw);
+impl FingerprintComponent {
+    /// Label the component's value is prefixed with before hashing.
+    pub fn name(self) -> &'static str {
+        match self {
+            FingerprintComponent::MachineId => "machine-id",
+            FingerprintComponent::ProductUuid => "product-uuid",
+            FingerprintComponent::PrimaryMac => "primary-mac",
+            FingerprintComponent::RootDiskSerial => "root-disk-serial",
+            FingerprintComponent::CpuModel => "cpu-model",
+            FingerprintComponent::BoardSerial => "board-serial",
+            FingerprintComponent::Virtualization => "virtualization",
+        }
+    }
 
-    // 2. Hash the combined ID using SHA-256
-    let digest = digest::digest(&digest::SHA256, combined.as_bytes());
-    hex::encode(digest)
+    /// Whether the component tells machines apart on its own.
+    pub fn is_unique(self) -> bool {
+        !matches!(
+            self,
+            FingerprintComponent::CpuModel | FingerprintComponent::Virtualization
+        )
+    }
 }
 
-/// Retrieve the CPU identifier for the current platform.
-fn get_cpu_id() -> LicenseResult<String> {
-    #[cfg(target_os = "windows")]
+/// Provider set with `set_fingerprint_provider`, if any.
+static PROVIDER: RwLock<Option<Arc<dyn FingerprintProvider>>> = RwLock::new(None);
+
+/// Use `provider` for every hardware ID computed from now on.
+pub fn set_fingerprint_provider(provider: impl FingerprintProvider + 'static) {
+    *PROVIDER.write().unwrap_or_else(|e| e.into_inner()) = Some(Arc::new(provider));
+}
+
+/// The provider used when none has been set for this platform.
+pub fn default_fingerprint_provider() -> Arc<dyn FingerprintProvider> {
+    #[cfg(target_os = "linux")]
     {
-        windows::get_cpu_id()
-            .map_err(|e| LicenseError::ServerError(format!("Win CPU ID error: {e}")))
+        Arc::new(LinuxFingerprint::default())
     }
 
     #[cfg(target_os = "macos")]
     {
-        macos::get_cpu_id()
-            .map_err(|e| LicenseError::ServerError(format!("macOS CPU ID error: {e}")))
+        Arc::new(MacosFingerprint)
     }
 
-    #[cfg(target_os = "linux")]
+    #[cfg(target_os = "windows")]
     {
-        linux::get_cpu_id()
-            .map_err(|e| LicenseError::ServerError(format!("Linux CPU ID error: {e}")))
+        Arc::new(WindowsFingerprint)
     }
 }
 
-/// Retrieve the motherboard / board serial identifier.
-fn get_motherboard_id() -> LicenseResult<String> {
-    #[cfg(target_os = "windows")]
-    {
-        windows::get_motherboard_id()
-            .map_err(|e| LicenseError::ServerError(format!("Win Board ID error: {e}")))
+/// Returns a *consistent hashed fingerprint* based on the device's hardware.
+///
+/// Uses the provider set with [`set_fingerprint_provider`], or the platform
+/// default, and hashes its components with [`fingerprint`].
+///
+/// Example output:
+///     "0a3e7c8d9921ac4d89f11223f4d447adfeec2d722f974146ecf2917c4e97fcb2"
+pub fn get_hardware_id() -> String {
+    let provider = PROVIDER
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone()
+        .unwrap_or_else(default_fingerprint_provider);
+
+    fingerprint(provider.as_ref())
+}
+
+/// Compute SHA-256 over the provider's components joined with `|`.
+pub fn fingerprint(provider: &dyn FingerprintProvider) -> String {
+    let combined = provider.components().join("|");
+    let digest = digest::digest(&digest::SHA256, combined.as_bytes());
+    hex::encode(digest)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Fixed(Vec<&'static str>);
+
+    impl FingerprintProvider for Fixed {
+        fn components(&self) -> Vec<String> {
+            self.0.iter().map(|s| s.to_string()).collect()
+        }
     }
 
-    #[cfg(target_os = "macos")]
-    {
-        macos::get_motherboard_id()
-            .map_err(|e| LicenseError::ServerError(format!("macOS Board ID error: {e}")))
+    #[test]
+    fn fingerprint_hashes_joined_components() {
+        // Same format as the original CPU + board fingerprint
+        let expected = hex::encode(digest::digest(&digest::SHA256, b"cpu|board"));
+        assert_eq!(fingerprint(&Fixed(vec!["cpu", "board"])), expected);
+        assert_ne!(
+            fingerprint(&Fixed(vec!["cpu", "board"])),
+            fingerprint(&Fixed(vec!["cpu", "other"]))
+        );
     }
 
-    #[cfg(target_os = "linux")]
-    {
-        linux::get_motherboard_id()
-            .map_err(|e| LicenseError::ServerError(format!("Linux Board ID error: {e}")))
+    #[test]
+    fn default_hardware_id_is_stable() {
+        assert_eq!(get_hardware_id(), get_hardware_id());
+        assert_eq!(get_hardware_id().len(), 64);
     }
 }
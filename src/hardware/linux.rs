@@ -1,11 +1,289 @@
 use std::error::Error;
 use std::fs;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 
+use super::{FingerprintComponent, FingerprintProvider};
+
+/// Placeholder values DMI tables ship with when the vendor left them empty.
+const DMI_PLACEHOLDERS: &[&str] = &[
+    "",
+    "0",
+    "none",
+    "default string",
+    "not specified",
+    "not applicable",
+    "system serial number",
+    "to be filled by o.e.m.",
+    "00000000-0000-0000-0000-000000000000",
+    "03000200-0400-0500-0006-000700080009",
+    "ffffffff-ffff-ffff-ffff-ffffffffffff",
+];
+
+/// Linux fingerprint built from a configurable set of components.
+///
+/// The default uses `/etc/machine-id` (falling back to
+/// `/var/lib/dbus/machine-id`) plus container/VM detection. Both are
+/// world-readable, so the ID is the same with and without root, and the
+/// machine ID is unique per OS installation, unlike the CPU model.
+///
+/// Components that cannot be read are left out. If none of the configured
+/// components identifies the machine on its own (e.g. a container image
+/// without a machine ID), the primary MAC address and then the root disk
+/// serial are used instead.
+///
+/// Containers started from the same image share its machine ID; mount the
+/// host's `/etc/machine-id` into the container to tell them apart.
+#[derive(Debug, Clone)]
+pub struct LinuxFingerprint {
+    components: Vec<FingerprintComponent>,
+    root: PathBuf,
+}
+
+impl Default for LinuxFingerprint {
+    fn default() -> Self {
+        Self::new([
+            FingerprintComponent::MachineId,
+            FingerprintComponent::Virtualization,
+        ])
+    }
+}
+
+impl LinuxFingerprint {
+    /// Fingerprint from `components`, hashed in the given order.
+    pub fn new(components: impl IntoIterator<Item = FingerprintComponent>) -> Self {
+        Self {
+            components: components.into_iter().collect(),
+            root: PathBuf::from("/"),
+        }
+    }
+
+    /// Read files relative to `root` instead of `/`.
+    #[cfg(test)]
+    fn with_root(mut self, root: impl Into<PathBuf>) -> Self {
+        self.root = root.into();
+        self
+    }
+
+    /// Read one component, or `None` if it is unavailable on this machine.
+    pub fn read(&self, component: FingerprintComponent) -> Option<String> {
+        match component {
+            FingerprintComponent::MachineId => self.machine_id(),
+            FingerprintComponent::ProductUuid => self
+                .dmi("product_uuid")
+                .map(|uuid| uuid.to_ascii_lowercase()),
+            FingerprintComponent::PrimaryMac => self.primary_mac(),
+            FingerprintComponent::RootDiskSerial => self.root_disk_serial(),
+            FingerprintComponent::CpuModel => self.cpu_model(),
+            FingerprintComponent::BoardSerial => self.dmi("board_serial"),
+            FingerprintComponent::Virtualization => Some(self.virtualization()),
+        }
+    }
+
+    fn path(&self, relative: &str) -> PathBuf {
+        self.root.join(relative)
+    }
+
+    fn read_trimmed(&self, relative: impl AsRef<Path>) -> Option<String> {
+        let contents = fs::read_to_string(self.root.join(relative)).ok()?;
+        let value = contents.trim();
+        (!value.is_empty()).then(|| value.to_string())
+    }
+
+    fn machine_id(&self) -> Option<String> {
+        ["etc/machine-id", "var/lib/dbus/machine-id"]
+            .iter()
+            .filter_map(|path| self.read_trimmed(path))
+            .find(|id| id != "uninitialized")
+    }
+
+    /// A DMI field, skipping vendor placeholders.
+    fn dmi(&self, field: &str) -> Option<String> {
+        self.read_trimmed(format!("sys/class/dmi/id/{field}"))
+            .filter(|value| !DMI_PLACEHOLDERS.contains(&value.to_ascii_lowercase().as_str()))
+    }
+
+    /// MAC of the first physical interface by name, skipping random MACs.
+    fn primary_mac(&self) -> Option<String> {
+        let mut interfaces: Vec<PathBuf> = fs::read_dir(self.path("sys/class/net"))
+            .ok()?
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            // Virtual interfaces (lo, bridges, veth) have no backing device
+            .filter(|path| path.join("device").exists())
+            .collect();
+        interfaces.sort();
+
+        interfaces.iter().find_map(|interface| {
+            let mac = fs::read_to_string(interface.join("address")).ok()?;
+            let mac = mac.trim().to_ascii_lowercase();
+            let first = u8::from_str_radix(mac.get(..2)?, 16).ok()?;
+            // Locally administered addresses are assigned by software
+            let usable = first & 0x02 == 0 && mac != "00:00:00:00:00:00";
+            usable.then_some(mac)
+        })
+    }
+
+    /// Serial of the disk the root filesystem is mounted from.
+    fn root_disk_serial(&self) -> Option<String> {
+        let mountinfo = fs::read_to_string(self.path("proc/self/mountinfo")).ok()?;
+        // Fields: id, parent id, major:minor, root, mount point, ...
+        let device = mountinfo
+            .lines()
+            .filter_map(|line| {
+                let mut fields = line.split_whitespace().skip(2);
+                let device = fields.next()?;
+                (fields.nth(1)? == "/").then_some(device)
+            })
+            // The last mount on / is the one in effect
+            .next_back()?;
+
+        let mut disk = fs::canonicalize(self.path(&format!("sys/dev/block/{device}"))).ok()?;
+        if disk.join("partition").exists() {
+            disk = disk.parent()?.to_path_buf();
+        }
+
+        ["device/serial", "serial"]
+            .iter()
+            .find_map(|file| {
+                let serial = fs::read_to_string(disk.join(file)).ok()?;
+                let serial = serial.trim();
+                (!serial.is_empty()).then(|| serial.to_string())
+            })
+            .or_else(|| {
+                // SATA disks only expose their serial through udev
+                let dev = fs::read_to_string(disk.join("dev")).ok()?;
+                let udev = self.read_trimmed(format!("run/udev/data/b{}", dev.trim()))?;
+                let property = |key: &str| {
+                    udev.lines()
+                        .find_map(|line| line.strip_prefix(key))
+                        .filter(|serial| !serial.is_empty())
+                        .map(str::to_string)
+                };
+                property("E:ID_SERIAL_SHORT=").or_else(|| property("E:ID_SERIAL="))
+            })
+    }
+
+    fn cpu_model(&self) -> Option<String> {
+        let cpuinfo = fs::read_to_string(self.path("proc/cpuinfo")).ok()?;
+        cpuinfo
+            .lines()
+            .find(|l| l.to_lowercase().starts_with("model name"))
+            .and_then(|line| line.split(':').nth(1))
+            .map(|value| value.trim().to_string())
+            .filter(|value| !value.is_empty())
+    }
+
+    /// `container:<runtime>`, `vm:<hypervisor>` or `none`.
+    fn virtualization(&self) -> String {
+        if self.path(".dockerenv").exists() {
+            return "container:docker".to_string();
+        }
+        if self.path("run/.containerenv").exists() {
+            return "container:podman".to_string();
+        }
+        if let Ok(cgroup) = fs::read_to_string(self.path("proc/1/cgroup")) {
+            for (marker, runtime) in [
+                ("kubepods", "kubernetes"),
+                ("docker", "docker"),
+                ("lxc", "lxc"),
+            ] {
+                if cgroup.contains(marker) {
+                    return format!("container:{runtime}");
+                }
+            }
+        }
+
+        let vendor = [self.dmi("sys_vendor"), self.dmi("product_name")]
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>()
+            .join(" ")
+            .to_ascii_lowercase();
+        for (marker, hypervisor) in [
+            ("kvm", "kvm"),
+            ("qemu", "qemu"),
+            ("vmware", "vmware"),
+            ("virtualbox", "virtualbox"),
+            ("innotek", "virtualbox"),
+            ("xen", "xen"),
+            ("amazon ec2", "amazon"),
+            ("google compute engine", "google"),
+            ("parallels", "parallels"),
+            ("virtual machine", "hyperv"),
+        ] {
+            if vendor.contains(marker) {
+                return format!("vm:{hypervisor}");
+            }
+        }
+
+        let hypervisor_flag = fs::read_to_string(self.path("proc/cpuinfo"))
+            .map(|cpuinfo| {
+                cpuinfo
+                    .lines()
+                    .filter(|l| l.starts_with("flags"))
+                    .any(|l| l.split_whitespace().any(|flag| flag == "hypervisor"))
+            })
+            .unwrap_or(false);
+        if hypervisor_flag {
+            return "vm:unknown".to_string();
+        }
+
+        "none".to_string()
+    }
+}
+
+impl FingerprintProvider for LinuxFingerprint {
+    fn components(&self) -> Vec<String> {
+        let mut components: Vec<String> = Vec::new();
+        let mut unique = false;
+
+        for &component in &self.components {
+            if let Some(value) = self.read(component) {
+                unique |= component.is_unique();
+                components.push(format!("{}={}", component.name(), value));
+            }
+        }
+
+        if !unique {
+            let fallback = [
+                FingerprintComponent::PrimaryMac,
+                FingerprintComponent::RootDiskSerial,
+            ]
+            .into_iter()
+            .filter(|component| !self.components.contains(component))
+            .find_map(|component| {
+                self.read(component)
+                    .map(|value| format!("{}={}", component.name(), value))
+            });
+            components.extend(fallback);
+        }
+
+        components
+    }
+}
+
+/// The Linux fingerprint used before `LinuxFingerprint`: CPU model name and
+/// motherboard serial.
+///
+/// Many machines share it, since the CPU model is the same across a product
+/// line and the board serial is usually readable by root only. Use it only
+/// to keep the hardware IDs of existing installations.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LinuxLegacyFingerprint;
+
+impl FingerprintProvider for LinuxLegacyFingerprint {
+    fn components(&self) -> Vec<String> {
+        vec![
+            get_cpu_id().unwrap_or_else(|_| "cpu_unknown".to_string()),
+            get_motherboard_id().unwrap_or_else(|_| "board_unknown".to_string()),
+        ]
+    }
+}
+
 /// Try to read CPU model name from /proc/cpuinfo.
 ///
 /// This is usually more reliable and cheaper than spawning `lscpu`.
-pub fn get_cpu_id() -> Result<String, Box<dyn Error>> {
+fn get_cpu_id() -> Result<String, Box<dyn Error>> {
     // Primary path: /proc/cpuinfo
     if let Ok(cpuinfo) = fs::read_to_string("/proc/cpuinfo") {
         if let Some(line) = cpuinfo
@@ -41,7 +319,7 @@ pub fn get_cpu_id() -> Result<String, Box<dyn Error>> {
 ///
 /// Reads /sys/devices/virtual/dmi/id/board_serial if present.
 /// Returns a generic fallback if not available.
-pub fn get_motherboard_id() -> Result<String, Box<dyn Error>> {
+fn get_motherboard_id() -> Result<String, Box<dyn Error>> {
     match fs::read_to_string("/sys/devices/virtual/dmi/id/board_serial") {
         Ok(contents) => {
             let value = contents.trim().to_string();
@@ -54,3 +332,138 @@ pub fn get_motherboard_id() -> Result<String, Box<dyn Error>> {
         Err(_) => Ok("linux_mb_unknown".to_string()),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::os::unix::fs::symlink;
+
+    /// Empty directory standing in for `/`.
+    fn fake_root() -> PathBuf {
+        let root = std::env::temp_dir().join(format!("talos-fingerprint-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&root).unwrap();
+        root
+    }
+
+    fn write(root: &Path, relative: &str, contents: &str) {
+        let path = root.join(relative);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, contents).unwrap();
+    }
+
+    #[test]
+    fn default_uses_machine_id_and_virtualization() {
+        let root = fake_root();
+        write(
+            &root,
+            "etc/machine-id",
+            "4c4c4544004d3510804cb4c04f4d4d32\n",
+        );
+        write(&root, "sys/class/dmi/id/sys_vendor", "QEMU\n");
+
+        let provider = LinuxFingerprint::default().with_root(&root);
+        assert_eq!(
+            provider.components(),
+            vec![
+                "machine-id=4c4c4544004d3510804cb4c04f4d4d32",
+                "virtualization=vm:qemu",
+            ]
+        );
+
+        // A different machine ID gives a different fingerprint
+        let before = super::super::fingerprint(&provider);
+        write(
+            &root,
+            "etc/machine-id",
+            "0f1e2d3c4b5a69788796a5b4c3d2e1f0\n",
+        );
+        assert_ne!(super::super::fingerprint(&provider), before);
+
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn falls_back_to_mac_without_machine_id() {
+        let root = fake_root();
+        write(&root, ".dockerenv", "");
+        // Virtual interfaces and random MACs are skipped
+        write(&root, "sys/class/net/lo/address", "00:00:00:00:00:00\n");
+        write(&root, "sys/class/net/eth0/address", "02:42:ac:11:00:02\n");
+        fs::create_dir_all(root.join("sys/class/net/eth0/device")).unwrap();
+        write(&root, "sys/class/net/eth1/address", "3C:52:82:0A:1B:2C\n");
+        fs::create_dir_all(root.join("sys/class/net/eth1/device")).unwrap();
+
+        let provider = LinuxFingerprint::default().with_root(&root);
+        assert_eq!(
+            provider.components(),
+            vec![
+                "virtualization=container:docker",
+                "primary-mac=3c:52:82:0a:1b:2c",
+            ]
+        );
+
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn skips_dmi_placeholders() {
+        let root = fake_root();
+        write(
+            &root,
+            "sys/class/dmi/id/board_serial",
+            "To be filled by O.E.M.\n",
+        );
+        write(
+            &root,
+            "sys/class/dmi/id/product_uuid",
+            "4C4C4544-004D-3510-804C-B4C04F4D4D32\n",
+        );
+
+        let provider = LinuxFingerprint::default().with_root(&root);
+        assert_eq!(provider.read(FingerprintComponent::BoardSerial), None);
+        assert_eq!(
+            provider.read(FingerprintComponent::ProductUuid).as_deref(),
+            Some("4c4c4544-004d-3510-804c-b4c04f4d4d32")
+        );
+        assert_eq!(
+            provider
+                .read(FingerprintComponent::Virtualization)
+                .as_deref(),
+            Some("none")
+        );
+
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn reads_root_disk_serial() {
+        let root = fake_root();
+        write(
+            &root,
+            "proc/self/mountinfo",
+            "22 1 259:2 / / rw,relatime shared:1 - ext4 /dev/nvme0n1p2 rw\n\
+             23 22 0:21 / /proc rw shared:2 - proc proc rw\n",
+        );
+        write(&root, "sys/block/nvme0n1/nvme0n1p2/partition", "2\n");
+        write(
+            &root,
+            "sys/block/nvme0n1/device/serial",
+            "S4EWNX0R123456  \n",
+        );
+        fs::create_dir_all(root.join("sys/dev/block")).unwrap();
+        symlink(
+            "../../block/nvme0n1/nvme0n1p2",
+            root.join("sys/dev/block/259:2"),
+        )
+        .unwrap();
+
+        let provider =
+            LinuxFingerprint::new([FingerprintComponent::RootDiskSerial]).with_root(&root);
+        assert_eq!(
+            provider.components(),
+            vec!["root-disk-serial=S4EWNX0R123456"]
+        );
+
+        fs::remove_dir_all(root).unwrap();
+    }
+}
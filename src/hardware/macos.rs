@@ -1,10 +1,25 @@
 use std::error::Error;
 use std::process::Command;
 
+use super::FingerprintProvider;
+
+/// macOS fingerprint: CPU brand string and platform serial number.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MacosFingerprint;
+
+impl FingerprintProvider for MacosFingerprint {
+    fn components(&self) -> Vec<String> {
+        vec![
+            get_cpu_id().unwrap_or_else(|_| "cpu_unknown".to_string()),
+            get_motherboard_id().unwrap_or_else(|_| "board_unknown".to_string()),
+        ]
+    }
+}
+
 /// Get a CPU brand string on macOS.
 ///
 /// Uses `sysctl machdep.cpu.brand_string`.
-pub fn get_cpu_id() -> Result<String, Box<dyn Error>> {
+fn get_cpu_id() -> Result<String, Box<dyn Error>> {
     let output = Command::new("sysctl")
         .args(&["-n", "machdep.cpu.brand_string"])
         .output()?;
@@ -22,7 +37,7 @@ pub fn get_cpu_id() -> Result<String, Box<dyn Error>> {
 /// Get the platform serial number on macOS.
 ///
 /// Uses `ioreg -l` and searches for `IOPlatformSerialNumber`.
-pub fn get_motherboard_id() -> Result<String, Box<dyn Error>> {
+fn get_motherboard_id() -> Result<String, Box<dyn Error>> {
     let output = Command::new("ioreg").args(&["-l"]).output()?;
 
     let result = String::from_utf8_lossy(&output.stdout);
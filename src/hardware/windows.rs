@@ -2,6 +2,21 @@ use std::error::Error;
 use std::os::windows::process::CommandExt;
 use std::process::Command;
 
+use super::FingerprintProvider;
+
+/// Windows fingerprint: processor ID and baseboard serial number from WMI.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct WindowsFingerprint;
+
+impl FingerprintProvider for WindowsFingerprint {
+    fn components(&self) -> Vec<String> {
+        vec![
+            get_cpu_id().unwrap_or_else(|_| "cpu_unknown".to_string()),
+            get_motherboard_id().unwrap_or_else(|_| "board_unknown".to_string()),
+        ]
+    }
+}
+
 /// Windows flag to prevent CMD window popups in GUI applications.
 const CREATE_NO_WINDOW: u32 = 0x08000000;
 
@@ -56,7 +71,7 @@ fn try_wmic_list(class: &str, key: &str) -> Option<String> {
 /// 1. WMIC cpu get ProcessorId
 /// 2. WMIC cpu get /format:list
 /// 3. fallback deterministic value
-pub fn get_cpu_id() -> Result<String, Box<dyn Error>> {
+fn get_cpu_id() -> Result<String, Box<dyn Error>> {
     // WMIC normal format
     if let Some(val) = try_wmic(&["cpu", "get", "ProcessorId"]) {
         return Ok(val);
@@ -76,7 +91,7 @@ pub fn get_cpu_id() -> Result<String, Box<dyn Error>> {
 /// 1. WMIC baseboard get SerialNumber
 /// 2. WMIC baseboard list format
 /// 3. fallback deterministic ID
-pub fn get_motherboard_id() -> Result<String, Box<dyn Error>> {
+fn get_motherboard_id() -> Result<String, Box<dyn Error>> {
     if let Some(val) = try_wmic(&["baseboard", "get", "SerialNumber"]) {
         return Ok(val);
     }
@@ -6,7 +6,9 @@
 use chrono::Utc;
 use tracing::{debug, info};
 
-use crate::server::database::Database;
+use crate::server::logging::{log_license_event, LicenseEvent};
+use crate::server::store::LicenseStore;
+use crate::server::webhooks;
 
 use super::JobError;
 
@@ -20,8 +22,10 @@ use super::JobError;
 /// - Sets `status = 'revoked'`
 /// - Sets `revoked_at = NOW()`
 ///
+/// Each revocation is sent to webhooks subscribed to `license.revoked`.
+///
 /// Returns the number of licenses that were revoked.
-pub async fn run_grace_period_check(db: &Database) -> Result<u32, JobError> {
+pub async fn run_grace_period_check(db: &dyn LicenseStore) -> Result<u32, JobError> {
     let now = Utc::now().naive_utc();
 
     debug!("Checking for expired grace periods at {}", now);
@@ -48,6 +52,19 @@ pub async fn run_grace_period_check(db: &Database) -> Result<u32, JobError> {
                 "License {} revoked (grace period expired)",
                 license.license_id
             );
+
+            log_license_event(
+                LicenseEvent::Revoked,
+                &license.license_id,
+                Some("grace period expired"),
+            );
+            webhooks::notify(
+                db,
+                LicenseEvent::Revoked,
+                &license.license_id,
+                Some(serde_json::json!({ "reason": "grace period expired" })),
+            )
+            .await;
         }
     }
 
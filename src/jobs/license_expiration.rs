@@ -6,7 +6,9 @@
 use chrono::Utc;
 use tracing::{debug, info};
 
-use crate::server::database::Database;
+use crate::server::logging::{log_license_event, LicenseEvent};
+use crate::server::store::LicenseStore;
+use crate::server::webhooks;
 
 use super::JobError;
 
@@ -19,8 +21,10 @@ use super::JobError;
 /// Updates matching licenses:
 /// - Sets `status = 'expired'`
 ///
+/// Each expiration is sent to webhooks subscribed to `license.expired`.
+///
 /// Returns the number of licenses that were expired.
-pub async fn run_license_expiration_check(db: &Database) -> Result<u32, JobError> {
+pub async fn run_license_expiration_check(db: &dyn LicenseStore) -> Result<u32, JobError> {
     let now = Utc::now().naive_utc();
 
     debug!("Checking for expired licenses at {}", now);
@@ -43,6 +47,17 @@ pub async fn run_license_expiration_check(db: &Database) -> Result<u32, JobError
         if db.insert_license(updated).await.is_ok() {
             count += 1;
             info!("License {} expired", license.license_id);
+
+            log_license_event(LicenseEvent::Expired, &license.license_id, None);
+            webhooks::notify(
+                db,
+                LicenseEvent::Expired,
+                &license.license_id,
+                Some(serde_json::json!({
+                    "expired_at": license.expires_at.map(|d| d.and_utc().to_rfc3339()),
+                })),
+            )
+            .await;
         }
     }
 
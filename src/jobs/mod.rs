@@ -14,16 +14,24 @@
 //! - **Stale Device Cleanup** (optional): Releases licenses from devices that haven't
 //!   been seen for a configurable period
 //!
+//! - **Usage Reset**: Closes the usage period of licenses whose billing cycle has
+//!   rolled over, keeping the usage in `usage_periods` and resetting the meters
+//!
 //! # Usage
 //!
+//! `talos_server` starts the scheduler automatically using the `[jobs]`
+//! config section. To run it in your own binary:
+//!
 //! ```rust,ignore
 //! use talos::jobs::{JobScheduler, JobConfig};
 //! use talos::server::Database;
 //!
 //! let db = Database::new().await?;
 //! let config = JobConfig::default();
-//! let scheduler = JobScheduler::new(db, config).await?;
+//! let mut scheduler = JobScheduler::new(db, config).await?;
 //! scheduler.start().await?;
+//! // ...
+//! scheduler.shutdown().await?;
 //! ```
 
 use chrono::Utc;
@@ -31,65 +39,40 @@ use std::sync::Arc;
 use tokio_cron_scheduler::{Job, JobScheduler as TokioJobScheduler};
 use tracing::{error, info};
 
-use crate::server::database::Database;
+use crate::server::store::LicenseStore;
 
 mod grace_period;
 mod license_expiration;
 mod stale_devices;
+mod usage_reset;
 
 pub use grace_period::run_grace_period_check;
 pub use license_expiration::run_license_expiration_check;
 pub use stale_devices::run_stale_device_cleanup;
+pub use usage_reset::run_usage_reset;
 
-/// Configuration for background jobs.
-#[derive(Debug, Clone)]
-pub struct JobConfig {
-    /// Cron expression for grace period expiration check (default: every hour at minute 0)
-    pub grace_period_cron: String,
-    /// Cron expression for license expiration check (default: every hour at minute 15)
-    pub license_expiration_cron: String,
-    /// Whether stale device cleanup is enabled (default: false)
-    pub stale_device_cleanup_enabled: bool,
-    /// Cron expression for stale device cleanup (default: daily at 3 AM)
-    pub stale_device_cron: String,
-    /// Number of days after which a device is considered stale (default: 90)
-    pub stale_device_days: u32,
-}
-
-impl Default for JobConfig {
-    fn default() -> Self {
-        Self {
-            // Every hour at minute 0
-            grace_period_cron: "0 0 * * * *".to_string(),
-            // Every hour at minute 15
-            license_expiration_cron: "0 15 * * * *".to_string(),
-            // Disabled by default
-            stale_device_cleanup_enabled: false,
-            // Daily at 3 AM
-            stale_device_cron: "0 0 3 * * *".to_string(),
-            // 90 days
-            stale_device_days: 90,
-        }
-    }
-}
+pub use crate::config::JobConfig;
 
 /// Background job scheduler for Talos.
 pub struct JobScheduler {
     scheduler: TokioJobScheduler,
-    db: Arc<Database>,
+    db: Arc<dyn LicenseStore>,
     config: JobConfig,
 }
 
 impl JobScheduler {
     /// Create a new job scheduler.
-    pub async fn new(db: Database, config: JobConfig) -> Result<Self, JobError> {
+    ///
+    /// Takes the store shared with the server, e.g. the `Arc<Database>`
+    /// returned by `Database::new()`.
+    pub async fn new(db: Arc<dyn LicenseStore>, config: JobConfig) -> Result<Self, JobError> {
         let scheduler = TokioJobScheduler::new()
             .await
             .map_err(|e| JobError::SchedulerError(e.to_string()))?;
 
         Ok(Self {
             scheduler,
-            db: Arc::new(db),
+            db,
             config,
         })
     }
@@ -109,6 +92,11 @@ impl JobScheduler {
             self.add_stale_device_job().await?;
         }
 
+        // Add usage reset job if enabled
+        if self.config.usage_reset_enabled {
+            self.add_usage_reset_job().await?;
+        }
+
         // Start the scheduler
         self.scheduler
             .start()
@@ -140,7 +128,7 @@ impl JobScheduler {
                 let now = Utc::now().naive_utc();
                 info!("Running grace period expiration check at {}", now);
 
-                match run_grace_period_check(&db).await {
+                match run_grace_period_check(&*db).await {
                     Ok(count) => {
                         if count > 0 {
                             info!("Grace period check: {} licenses revoked", count);
@@ -179,7 +167,7 @@ impl JobScheduler {
                     let now = Utc::now().naive_utc();
                     info!("Running license expiration check at {}", now);
 
-                    match run_license_expiration_check(&db).await {
+                    match run_license_expiration_check(&*db).await {
                         Ok(count) => {
                             if count > 0 {
                                 info!("License expiration check: {} licenses expired", count);
@@ -218,7 +206,7 @@ impl JobScheduler {
                 let now = Utc::now().naive_utc();
                 info!("Running stale device cleanup at {}", now);
 
-                match run_stale_device_cleanup(&db, stale_days).await {
+                match run_stale_device_cleanup(&*db, stale_days).await {
                     Ok(count) => {
                         if count > 0 {
                             info!("Stale device cleanup: {} licenses released", count);
@@ -245,19 +233,61 @@ impl JobScheduler {
         Ok(())
     }
 
+    /// Add the usage reset job.
+    async fn add_usage_reset_job(&self) -> Result<(), JobError> {
+        let db = Arc::clone(&self.db);
+
+        let job = Job::new_async(self.config.usage_reset_cron.as_str(), move |_uuid, _l| {
+            let db = Arc::clone(&db);
+            Box::pin(async move {
+                let now = Utc::now().naive_utc();
+                info!("Running usage reset at {}", now);
+
+                match run_usage_reset(&*db).await {
+                    Ok(count) => {
+                        if count > 0 {
+                            info!("Usage reset: {} usage periods closed", count);
+                        }
+                    }
+                    Err(e) => {
+                        error!("Usage reset failed: {}", e);
+                    }
+                }
+            })
+        })
+        .map_err(|e| JobError::SchedulerError(e.to_string()))?;
+
+        self.scheduler
+            .add(job)
+            .await
+            .map_err(|e| JobError::SchedulerError(e.to_string()))?;
+
+        info!(
+            "Added usage reset job (schedule: {})",
+            self.config.usage_reset_cron
+        );
+
+        Ok(())
+    }
+
     /// Run the grace period check immediately (useful for testing or manual triggers).
     pub async fn run_grace_period_check_now(&self) -> Result<u32, JobError> {
-        run_grace_period_check(&self.db).await
+        run_grace_period_check(&*self.db).await
     }
 
     /// Run the license expiration check immediately (useful for testing or manual triggers).
     pub async fn run_license_expiration_check_now(&self) -> Result<u32, JobError> {
-        run_license_expiration_check(&self.db).await
+        run_license_expiration_check(&*self.db).await
     }
 
     /// Run the stale device cleanup immediately (useful for testing or manual triggers).
     pub async fn run_stale_device_cleanup_now(&self) -> Result<u32, JobError> {
-        run_stale_device_cleanup(&self.db, self.config.stale_device_days).await
+        run_stale_device_cleanup(&*self.db, self.config.stale_device_days).await
+    }
+
+    /// Run the usage reset immediately (useful for testing or manual triggers).
+    pub async fn run_usage_reset_now(&self) -> Result<u32, JobError> {
+        run_usage_reset(&*self.db).await
     }
 }
 
@@ -291,5 +321,7 @@ mod tests {
         assert_eq!(config.license_expiration_cron, "0 15 * * * *");
         assert!(!config.stale_device_cleanup_enabled);
         assert_eq!(config.stale_device_days, 90);
+        assert!(config.usage_reset_enabled);
+        assert_eq!(config.usage_reset_cron, "0 30 * * * *");
     }
 }
@@ -1,27 +1,37 @@
 //! Stale device cleanup job.
 //!
-//! This job checks for licenses that are bound to hardware but haven't been seen
-//! for a configurable period, and releases them automatically.
+//! This job checks for devices that hold a seat on a license but haven't been
+//! seen for a configurable period, and frees their seats automatically. It also
+//! deletes floating leases that expired without being checked in.
 
 use chrono::{Duration, Utc};
 use tracing::{debug, info};
 
-use crate::server::database::{BindingAction, Database, PerformedBy};
+use crate::server::database::{BindingAction, PerformedBy};
+use crate::server::logging::{log_license_binding_event, LicenseEvent};
+use crate::server::store::LicenseStore;
+use crate::server::webhooks;
 
 use super::JobError;
 
-/// Check for and release licenses from stale devices.
+/// Check for and release seats held by stale devices.
 ///
-/// Queries for licenses where:
-/// - `hardware_id IS NOT NULL` (bound)
+/// Queries `license_devices` for seats where:
 /// - `last_seen_at < NOW() - stale_days`
 ///
-/// Releases matching licenses:
-/// - Clears hardware binding fields
+/// Releases each matching seat:
+/// - Removes the device from the license (other seats are untouched)
 /// - Records in binding history with `performed_by: "system"`
+/// - Notifies webhooks subscribed to `license.released`
 ///
-/// Returns the number of licenses that were released.
-pub async fn run_stale_device_cleanup(db: &Database, stale_days: u32) -> Result<u32, JobError> {
+/// Expired leases are deleted as well. They already count as free, so this
+/// only keeps `license_leases` tidy.
+///
+/// Returns the number of seats that were released.
+pub async fn run_stale_device_cleanup(
+    db: &dyn LicenseStore,
+    stale_days: u32,
+) -> Result<u32, JobError> {
     let now = Utc::now().naive_utc();
     let threshold = now - Duration::days(stale_days as i64);
 
@@ -30,34 +40,30 @@ pub async fn run_stale_device_cleanup(db: &Database, stale_days: u32) -> Result<
         threshold, now
     );
 
-    // Get all licenses with stale devices
-    let stale_licenses = db.get_stale_device_licenses(threshold).await?;
+    // Get all seats held by stale devices
+    let stale_devices = db.get_stale_devices(threshold).await?;
 
     let mut count = 0;
 
-    for license in stale_licenses {
+    for device in stale_devices {
         debug!(
             "Releasing license {} from stale device {} (last seen {:?})",
-            license.license_id,
-            license.hardware_id.as_deref().unwrap_or("unknown"),
-            license.last_seen_at
+            device.license_id, device.hardware_id, device.last_seen_at
         );
 
-        // Save binding info for audit
-        let hardware_id = license.hardware_id.clone();
-        let device_name = license.device_name.clone();
-        let device_info = license.device_info.clone();
-
-        // Release the license
-        if db.release_license(&license.license_id).await.is_ok() {
+        // Free the seat
+        if let Ok(true) = db
+            .remove_license_device(&device.license_id, &device.hardware_id)
+            .await
+        {
             // Record in binding history
             let _ = db
                 .record_binding_history(
-                    &license.license_id,
+                    &device.license_id,
                     BindingAction::SystemRelease,
-                    hardware_id.as_deref(),
-                    device_name.as_deref(),
-                    device_info.as_deref(),
+                    Some(&device.hardware_id),
+                    device.device_name.as_deref(),
+                    device.device_info.as_deref(),
                     PerformedBy::System,
                     Some(&format!(
                         "Automatic release: device not seen for {} days",
@@ -69,12 +75,34 @@ pub async fn run_stale_device_cleanup(db: &Database, stale_days: u32) -> Result<
             count += 1;
             info!(
                 "License {} released from stale device {}",
-                license.license_id,
-                hardware_id.unwrap_or_default()
+                device.license_id, device.hardware_id
+            );
+
+            log_license_binding_event(
+                LicenseEvent::Released,
+                &device.license_id,
+                &device.hardware_id,
+                device.device_name.as_deref(),
             );
+            webhooks::notify(
+                db,
+                LicenseEvent::Released,
+                &device.license_id,
+                Some(serde_json::json!({
+                    "hardware_id": device.hardware_id,
+                    "device_name": device.device_name,
+                    "reason": format!("device not seen for {stale_days} days"),
+                })),
+            )
+            .await;
         }
     }
 
+    let expired_leases = db.delete_expired_leases(now).await?;
+    if expired_leases > 0 {
+        info!("Deleted {} expired floating lease(s)", expired_leases);
+    }
+
     Ok(count)
 }
 
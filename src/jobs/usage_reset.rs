@@ -0,0 +1,87 @@
+//! Usage reset job.
+//!
+//! This job closes the usage period of licenses whose billing cycle has
+//! passed a boundary. The usage of each meter is kept in `usage_periods` so
+//! it can still be reported and billed, then subtracted from the meter, and
+//! `quota_exceeded` is recomputed from what is left.
+
+use chrono::Utc;
+use tracing::{debug, error, info, warn};
+
+use crate::server::billing::license_billing_cycle;
+use crate::server::store::LicenseStore;
+use crate::tiers::get_all_tiers;
+
+use super::JobError;
+
+/// Close the usage periods of licenses whose billing cycle has rolled over.
+///
+/// Checks every license with its own `billing_cycle`, or in a tier with one:
+/// - Finds the latest cycle boundary since the current period started
+/// - Stores each meter's usage up to that boundary in `usage_periods`
+/// - Subtracts that usage from the meters and recomputes `quota_exceeded`
+///
+/// If the job was not running for several periods, they are closed as one
+/// period ending at the latest boundary. Licenses with an invalid billing
+/// cycle are skipped with a warning.
+///
+/// Returns the number of licenses whose period was closed.
+pub async fn run_usage_reset(db: &dyn LicenseStore) -> Result<u32, JobError> {
+    let now = Utc::now().naive_utc();
+    let tiers: Vec<String> = get_all_tiers()
+        .into_iter()
+        .filter(|(_, tier)| tier.billing_cycle.is_some())
+        .map(|(name, _)| name)
+        .collect();
+
+    debug!("Checking billing cycles at {}", now);
+
+    let licenses = db.list_billed_licenses(&tiers).await?;
+
+    let mut count = 0;
+
+    for license in licenses {
+        let cycle = match license_billing_cycle(&license) {
+            Ok(cycle) => cycle,
+            Err(e) => {
+                warn!(
+                    "Skipping usage reset for license {}: {}",
+                    license.license_id, e
+                );
+                continue;
+            }
+        };
+
+        let period_start = license.usage_period_start.unwrap_or(license.issued_at);
+        let Some(period_end) = cycle.period_end(license.issued_at, period_start, now) else {
+            continue;
+        };
+
+        match db.close_usage_period(&license.license_id, period_end).await {
+            Ok(Some(periods)) => {
+                count += 1;
+                info!(
+                    "Closed usage period {} - {} of license {} ({} meter(s) reset)",
+                    period_start,
+                    period_end,
+                    license.license_id,
+                    periods.len()
+                );
+            }
+            Ok(None) => {}
+            Err(e) => {
+                error!(
+                    "Failed to close usage period of license {}: {}",
+                    license.license_id, e
+                );
+            }
+        }
+    }
+
+    Ok(count)
+}
+
+#[cfg(test)]
+mod tests {
+    // Integration tests are in tests/jobs_tests.rs
+}
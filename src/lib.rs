@@ -27,25 +27,37 @@ pub mod encryption;
 pub mod errors;
 pub mod hardware;
 pub mod license_key;
+pub mod signing;
 pub mod tiers;
 
 // Client-related modules (always available)
 pub mod client {
+    pub mod activation;
     pub mod cache;
     pub mod encrypted_storage;
     pub mod errors;
+    pub mod guard;
     pub mod heartbeat;
     pub mod key_generation;
+    pub mod lease;
     pub mod license;
     pub mod responses;
     pub mod storage;
+    pub mod transport;
 
     // Re-export main types at client module level
+    pub use activation::{ActivationRequest, ActivationResponse};
     pub use cache::CachedValidation;
     pub use errors::{ClientApiError, ClientErrorCode};
+    pub use guard::{GuardOptions, LicenseGuard, LicenseState};
+    pub use lease::{LeaseKeeper, LeaseStatus};
     pub use license::License;
-    pub use responses::{BindResult, FeatureResult, HeartbeatResult, ValidationResult};
+    pub use responses::{
+        BindResult, FeatureResult, HeartbeatResult, LeaseResult, TrialResult, UsageResult,
+        ValidationResult,
+    };
     pub use storage::StorageKey;
+    pub use transport::{ClientOptions, RetryPolicy};
 
     // Re-export for backwards compatibility
     pub use license as client;
@@ -8,14 +8,28 @@
 //! - `POST /api/v1/licenses` - Create a new license
 //! - `POST /api/v1/licenses/batch` - Create multiple licenses
 //! - `GET /api/v1/licenses/{license_id}` - Get a license by ID
-//! - `GET /api/v1/licenses?org_id={id}` - List licenses for an organization
+//! - `GET /api/v1/licenses` - List licenses, filtered and sorted, with cursor pagination
+//!
+//! Organizations themselves are managed in [`crate::server::orgs`]. New
+//! licenses pick up their organization's default tier and features, and
+//! creating one for an unregistered `org_id` registers the organization.
 //! - `PATCH /api/v1/licenses/{license_id}` - Update a license
 //! - `POST /api/v1/licenses/{license_id}/release` - Force release from hardware
+//! - `GET /api/v1/licenses/{license_id}/devices` - List devices holding a seat
+//! - `DELETE /api/v1/licenses/{license_id}/devices/{hardware_id}` - Free a single seat
+//! - `GET /api/v1/licenses/{license_id}/history` - Binding history of a license
+//! - `GET /api/v1/devices/{hardware_id}/history` - Binding history of a device across all licenses
 //! - `POST /api/v1/licenses/{license_id}/revoke` - Revoke a license
 //! - `POST /api/v1/licenses/{license_id}/reinstate` - Reinstate a revoked/suspended license
 //! - `POST /api/v1/licenses/{license_id}/extend` - Extend license expiration
 //! - `PATCH /api/v1/licenses/{license_id}/usage` - Update bandwidth/usage tracking
 //! - `POST /api/v1/licenses/{license_id}/blacklist` - Permanently blacklist a license
+//! - `POST /api/v1/licenses/offline-activation` - Activate an air-gapped machine from a request file
+//!
+//! The lifecycle actions can also be applied to many licenses at once, see
+//! [`crate::server::bulk`]. Licenses are imported and exported as files by
+//! [`crate::server::license_io`]. Usage meters other than bandwidth are
+//! managed in [`crate::server::usage`].
 
 use axum::{
     extract::{Path, Query, State},
@@ -35,14 +49,27 @@ use tracing::info;
 use utoipa::ToSchema;
 use uuid::Uuid;
 
+use crate::client::activation::{ActivationRequest, ActivationResponse};
 use crate::config::get_config;
 use crate::errors::{LicenseError, LicenseResult};
 use crate::license_key::{generate_license_key, LicenseKeyConfig};
 use crate::server::api_error::{ApiError, ErrorCode};
-use crate::server::database::{Database, License};
+use crate::server::audit::{snapshot, AuditAction, AuditContext, AuditTarget};
+use crate::server::billing::BillingCycle;
+use crate::server::client_api::issue_signed_license;
+use crate::server::database::{
+    BindingHistoryFilter, License, LicenseBindingHistory, LicenseDevice, SeatClaim,
+};
 use crate::server::handlers::AppState;
-use crate::server::logging::{log_license_event, LicenseEvent};
-use crate::tiers::get_tier_features;
+use crate::server::license_query::{self, LicenseCursor, LicenseFilter, LicenseSort};
+use crate::server::logging::{log_license_binding_event, log_license_event, LicenseEvent};
+use crate::server::orgs::{resolve_license_org, Organization};
+use crate::server::store::LicenseStore;
+use crate::server::usage::{
+    license_meter_statuses, license_tier, sync_quota_exceeded, MeterStatus, UsageMeter,
+};
+use crate::server::webhooks;
+use crate::tiers::{get_tier_features, BANDWIDTH_METER};
 
 // ============================================================================
 // Request/Response Types
@@ -52,9 +79,9 @@ use crate::tiers::get_tier_features;
 #[derive(Debug, Deserialize)]
 #[cfg_attr(feature = "openapi", derive(ToSchema))]
 pub struct CreateLicenseRequest {
-    /// Organization ID (optional)
+    /// Organization ID (optional, registered on first use)
     pub org_id: Option<String>,
-    /// Organization name (optional)
+    /// Organization name (optional, only used when registering the organization)
     pub org_name: Option<String>,
     /// Tier name - if provided and tiers are configured, features are derived from tier
     pub tier: Option<String>,
@@ -63,6 +90,14 @@ pub struct CreateLicenseRequest {
     pub features: Vec<String>,
     /// Expiration date (ISO 8601 format: "2025-12-31T23:59:59")
     pub expires_at: Option<String>,
+    /// Number of devices that may be bound at the same time (default: 1)
+    pub max_devices: Option<i32>,
+    /// Number of devices that may hold a floating lease at the same time (default: unlimited)
+    pub max_concurrent: Option<i32>,
+    /// Days of offline use after each successful check (default: tier or server setting)
+    pub offline_days: Option<i32>,
+    /// When usage meters reset: "monthly", a cron expression or "none" (default: the tier's)
+    pub billing_cycle: Option<String>,
     /// Additional metadata as JSON
     pub metadata: Option<serde_json::Value>,
 }
@@ -73,9 +108,9 @@ pub struct CreateLicenseRequest {
 pub struct BatchCreateLicenseRequest {
     /// Number of licenses to create
     pub count: u32,
-    /// Organization ID (optional, applied to all)
+    /// Organization ID (optional, applied to all, registered on first use)
     pub org_id: Option<String>,
-    /// Organization name (optional, applied to all)
+    /// Organization name (optional, only used when registering the organization)
     pub org_name: Option<String>,
     /// Tier name (optional, applied to all)
     pub tier: Option<String>,
@@ -84,6 +119,14 @@ pub struct BatchCreateLicenseRequest {
     pub features: Vec<String>,
     /// Expiration date (optional, applied to all)
     pub expires_at: Option<String>,
+    /// Seats per license (optional, applied to all, default: 1)
+    pub max_devices: Option<i32>,
+    /// Floating leases per license (optional, applied to all, default: unlimited)
+    pub max_concurrent: Option<i32>,
+    /// Offline days per license (optional, applied to all, default: tier or server setting)
+    pub offline_days: Option<i32>,
+    /// Billing cycle (optional, applied to all, default: the tier's)
+    pub billing_cycle: Option<String>,
 }
 
 /// Request body for updating a license.
@@ -96,16 +139,51 @@ pub struct UpdateLicenseRequest {
     pub features: Option<Vec<String>>,
     /// New expiration date
     pub expires_at: Option<String>,
+    /// New seat limit (devices already bound keep their seats)
+    pub max_devices: Option<i32>,
+    /// New floating lease limit (leases already checked out are kept until they expire)
+    pub max_concurrent: Option<i32>,
+    /// New offline allowance in days (applies from the client's next check)
+    pub offline_days: Option<i32>,
+    /// New billing cycle; an empty string falls back to the tier's
+    pub billing_cycle: Option<String>,
     /// New metadata
     pub metadata: Option<serde_json::Value>,
 }
 
+/// Maximum page size for `GET /api/v1/licenses`.
+pub const MAX_LICENSE_PAGE_SIZE: u32 = 500;
+
 /// Query parameters for listing licenses.
 #[derive(Debug, Deserialize)]
 pub struct ListLicensesQuery {
     /// Filter by organization ID
     pub org_id: Option<String>,
-    /// Pagination: page number (1-indexed)
+    /// Filter by status
+    pub status: Option<String>,
+    /// Filter by tier
+    pub tier: Option<String>,
+    /// Only bound (`true`) or unbound (`false`) licenses
+    pub bound: Option<bool>,
+    /// Only blacklisted (`true`) or non-blacklisted (`false`) licenses
+    pub blacklisted: Option<bool>,
+    /// Only licenses expiring before this time
+    pub expires_before: Option<String>,
+    /// Only licenses issued at or after this time
+    pub created_after: Option<String>,
+    /// Only licenses issued before this time
+    pub created_before: Option<String>,
+    /// Only licenses whose key starts with this prefix (case-insensitive)
+    pub key_prefix: Option<String>,
+    /// Only licenses with this metadata entry, as `key:value`
+    pub metadata: Option<String>,
+    /// Column to sort on (default: `issued_at`)
+    pub sort: Option<String>,
+    /// `asc` (default) or `desc`
+    pub order: Option<String>,
+    /// Continue after the `next_cursor` of a previous response
+    pub cursor: Option<String>,
+    /// Pagination: page number (1-indexed), ignored with `cursor`
     #[serde(default = "default_page")]
     pub page: u32,
     /// Pagination: items per page
@@ -113,6 +191,48 @@ pub struct ListLicensesQuery {
     pub per_page: u32,
 }
 
+impl ListLicensesQuery {
+    /// The requested sort order.
+    pub(crate) fn sort(&self) -> Result<LicenseSort, AdminError> {
+        Ok(LicenseSort {
+            field: parse_param(self.sort.as_deref())?,
+            order: parse_param(self.order.as_deref())?,
+        })
+    }
+
+    /// The requested filters.
+    pub(crate) fn filter(&self) -> Result<LicenseFilter, AdminError> {
+        Ok(LicenseFilter {
+            org_id: self.org_id.clone(),
+            status: self.status.clone(),
+            tier: self.tier.clone(),
+            bound: self.bound,
+            blacklisted: self.blacklisted,
+            expires_before: self
+                .expires_before
+                .as_deref()
+                .map(parse_datetime)
+                .transpose()?,
+            created_after: self
+                .created_after
+                .as_deref()
+                .map(parse_datetime)
+                .transpose()?,
+            created_before: self
+                .created_before
+                .as_deref()
+                .map(parse_datetime)
+                .transpose()?,
+            key_prefix: self.key_prefix.clone(),
+            metadata: self
+                .metadata
+                .as_deref()
+                .map(parse_metadata_filter)
+                .transpose()?,
+        })
+    }
+}
+
 fn default_page() -> u32 {
     1
 }
@@ -133,6 +253,11 @@ pub struct LicenseResponse {
     pub features: Vec<String>,
     pub issued_at: String,
     pub expires_at: Option<String>,
+    pub max_devices: i32,
+    pub max_concurrent: Option<i32>,
+    pub offline_days: Option<i32>,
+    pub billing_cycle: Option<String>,
+    pub usage_period_start: Option<String>,
     pub is_bound: bool,
     pub hardware_id: Option<String>,
     pub device_name: Option<String>,
@@ -166,6 +291,11 @@ impl From<License> for LicenseResponse {
             features,
             issued_at: license.issued_at.to_string(),
             expires_at: license.expires_at.map(|d| d.to_string()),
+            max_devices: license.max_devices,
+            max_concurrent: license.max_concurrent,
+            offline_days: license.offline_days,
+            billing_cycle: license.billing_cycle,
+            usage_period_start: license.usage_period_start.map(|d| d.to_string()),
             is_bound,
             hardware_id: license.hardware_id,
             device_name: license.device_name,
@@ -197,10 +327,13 @@ pub struct LicenseSummary {
 #[cfg_attr(feature = "openapi", derive(ToSchema))]
 pub struct ListLicensesResponse {
     pub licenses: Vec<LicenseResponse>,
-    pub total: u32,
+    /// Number of licenses matching the filters
+    pub total: u64,
     pub page: u32,
     pub per_page: u32,
-    pub total_pages: u32,
+    pub total_pages: u64,
+    /// Pass as `cursor` to fetch the next page; `None` on the last page
+    pub next_cursor: Option<String>,
 }
 
 /// Admin API error type.
@@ -262,7 +395,7 @@ impl From<LicenseError> for AdminError {
 // ============================================================================
 
 /// Parse an ISO 8601 datetime string into NaiveDateTime.
-fn parse_datetime(s: &str) -> Result<NaiveDateTime, AdminError> {
+pub(crate) fn parse_datetime(s: &str) -> Result<NaiveDateTime, AdminError> {
     // Try parsing with time
     if let Ok(dt) = chrono::DateTime::parse_from_rfc3339(s) {
         return Ok(dt.naive_utc());
@@ -283,8 +416,50 @@ fn parse_datetime(s: &str) -> Result<NaiveDateTime, AdminError> {
     )))
 }
 
+/// Validate a requested seat limit, defaulting to a single seat.
+pub(crate) fn resolve_max_devices(max_devices: Option<i32>) -> Result<i32, AdminError> {
+    match max_devices {
+        None => Ok(1),
+        Some(n) if n >= 1 => Ok(n),
+        Some(n) => Err(AdminError::BadRequest(format!(
+            "max_devices must be at least 1, got {n}"
+        ))),
+    }
+}
+
+/// Validate a requested floating lease limit (None = unlimited).
+fn resolve_max_concurrent(max_concurrent: Option<i32>) -> Result<Option<i32>, AdminError> {
+    match max_concurrent {
+        Some(n) if n < 1 => Err(AdminError::BadRequest(format!(
+            "max_concurrent must be at least 1, got {n}"
+        ))),
+        other => Ok(other),
+    }
+}
+
+/// Validate a requested offline allowance (None = tier or server default).
+fn resolve_offline_days(offline_days: Option<i32>) -> Result<Option<i32>, AdminError> {
+    match offline_days {
+        Some(n) if n < 0 => Err(AdminError::BadRequest(format!(
+            "offline_days must not be negative, got {n}"
+        ))),
+        other => Ok(other),
+    }
+}
+
+/// Validate a requested billing cycle (None = the tier's).
+fn resolve_billing_cycle(billing_cycle: Option<String>) -> Result<Option<String>, AdminError> {
+    let Some(cycle) = billing_cycle else {
+        return Ok(None);
+    };
+    cycle
+        .parse::<BillingCycle>()
+        .map_err(AdminError::BadRequest)?;
+    Ok(Some(cycle.trim().to_string()))
+}
+
 /// Merge tier features with explicit features.
-fn resolve_features(tier: Option<&str>, explicit_features: &[String]) -> Vec<String> {
+pub(crate) fn resolve_features(tier: Option<&str>, explicit_features: &[String]) -> Vec<String> {
     let mut features: Vec<String> = if let Some(tier_name) = tier {
         get_tier_features(tier_name)
     } else {
@@ -301,8 +476,29 @@ fn resolve_features(tier: Option<&str>, explicit_features: &[String]) -> Vec<Str
     features
 }
 
+/// Tier and features for a new license.
+///
+/// The request's tier and features win; the organization's defaults fill in
+/// whatever the request leaves out.
+pub(crate) fn resolve_license_defaults(
+    org: Option<&Organization>,
+    tier: Option<String>,
+    explicit_features: &[String],
+) -> (Option<String>, Vec<String>) {
+    let tier = tier.or_else(|| org.and_then(|o| o.default_tier.clone()));
+
+    let features = match org {
+        Some(org) if explicit_features.is_empty() => {
+            resolve_features(tier.as_deref(), &org.default_feature_names())
+        }
+        _ => resolve_features(tier.as_deref(), explicit_features),
+    };
+
+    (tier, features)
+}
+
 /// Generate a unique license key, checking for collisions.
-async fn generate_unique_license_key(db: &Database) -> LicenseResult<String> {
+async fn generate_unique_license_key(db: &dyn LicenseStore) -> LicenseResult<String> {
     let config = get_config()?;
     let key_config: LicenseKeyConfig = (&config.license).into();
 
@@ -340,13 +536,22 @@ async fn generate_unique_license_key(db: &Database) -> LicenseResult<String> {
 ))]
 pub async fn create_license_handler(
     State(state): State<AppState>,
+    audit: AuditContext,
     Json(payload): Json<CreateLicenseRequest>,
 ) -> Result<(StatusCode, Json<LicenseResponse>), AdminError> {
     info!("Creating new license for org_id={:?}", payload.org_id);
 
     let now = Utc::now().naive_utc();
     let license_id = Uuid::new_v4().to_string();
-    let license_key = generate_unique_license_key(&state.db).await?;
+    let license_key = generate_unique_license_key(&*state.db).await?;
+
+    let org = resolve_license_org(
+        &*state.db,
+        &audit,
+        payload.org_id.as_deref(),
+        payload.org_name.as_deref(),
+    )
+    .await?;
 
     // Parse expiration date if provided
     let expires_at = payload
@@ -355,8 +560,13 @@ pub async fn create_license_handler(
         .map(|s| parse_datetime(s))
         .transpose()?;
 
-    // Resolve features from tier and explicit list
-    let features = resolve_features(payload.tier.as_deref(), &payload.features);
+    let max_devices = resolve_max_devices(payload.max_devices)?;
+    let max_concurrent = resolve_max_concurrent(payload.max_concurrent)?;
+    let offline_days = resolve_offline_days(payload.offline_days)?;
+    let billing_cycle = resolve_billing_cycle(payload.billing_cycle)?;
+
+    // Resolve tier and features from the request and the organization's defaults
+    let (tier, features) = resolve_license_defaults(org.as_ref(), payload.tier, &payload.features);
     let features_json = serde_json::to_string(&features).ok();
 
     // Serialize metadata
@@ -375,10 +585,10 @@ pub async fn create_license_handler(
         hardware_id: None,
         signature: None,
         last_heartbeat: None,
-        org_id: payload.org_id,
-        org_name: payload.org_name,
+        org_id: org.as_ref().map(|o| o.id.clone()),
+        org_name: org.map(|o| o.name),
         license_key: Some(license_key.clone()),
-        tier: payload.tier,
+        tier,
         device_name: None,
         device_info: None,
         bound_at: None,
@@ -395,12 +605,29 @@ pub async fn create_license_handler(
         bandwidth_used_bytes: None,
         bandwidth_limit_bytes: None,
         quota_exceeded: None,
+        max_devices,
+        max_concurrent,
+        offline_days,
+        billing_cycle,
+        usage_period_start: None,
+        suspended_by_org: None,
     };
 
     state.db.insert_license(license.clone()).await?;
 
     // Log structured license creation event
     log_license_event(LicenseEvent::Created, &license_id, Some(&license_key));
+    webhooks::notify(&*state.db, LicenseEvent::Created, &license_id, None).await;
+
+    audit
+        .record(
+            &*state.db,
+            AuditAction::LicenseCreated,
+            AuditTarget::License(license_id),
+            None,
+            snapshot(&license),
+        )
+        .await;
 
     Ok((StatusCode::CREATED, Json(license.into())))
 }
@@ -422,6 +649,7 @@ pub async fn create_license_handler(
 ))]
 pub async fn batch_create_license_handler(
     State(state): State<AppState>,
+    audit: AuditContext,
     Json(payload): Json<BatchCreateLicenseRequest>,
 ) -> Result<(StatusCode, Json<BatchCreateResponse>), AdminError> {
     if payload.count == 0 {
@@ -450,15 +678,28 @@ pub async fn batch_create_license_handler(
         .map(|s| parse_datetime(s))
         .transpose()?;
 
-    // Resolve features
-    let features = resolve_features(payload.tier.as_deref(), &payload.features);
+    let max_devices = resolve_max_devices(payload.max_devices)?;
+    let max_concurrent = resolve_max_concurrent(payload.max_concurrent)?;
+    let offline_days = resolve_offline_days(payload.offline_days)?;
+    let billing_cycle = resolve_billing_cycle(payload.billing_cycle)?;
+
+    let org = resolve_license_org(
+        &*state.db,
+        &audit,
+        payload.org_id.as_deref(),
+        payload.org_name.as_deref(),
+    )
+    .await?;
+
+    // Resolve tier and features from the request and the organization's defaults
+    let (tier, features) = resolve_license_defaults(org.as_ref(), payload.tier, &payload.features);
     let features_json = serde_json::to_string(&features).ok();
 
     let mut licenses = Vec::with_capacity(payload.count as usize);
 
     for _ in 0..payload.count {
         let license_id = Uuid::new_v4().to_string();
-        let license_key = generate_unique_license_key(&state.db).await?;
+        let license_key = generate_unique_license_key(&*state.db).await?;
 
         let license = License {
             license_id: license_id.clone(),
@@ -470,10 +711,10 @@ pub async fn batch_create_license_handler(
             hardware_id: None,
             signature: None,
             last_heartbeat: None,
-            org_id: payload.org_id.clone(),
-            org_name: payload.org_name.clone(),
+            org_id: org.as_ref().map(|o| o.id.clone()),
+            org_name: org.as_ref().map(|o| o.name.clone()),
             license_key: Some(license_key.clone()),
-            tier: payload.tier.clone(),
+            tier: tier.clone(),
             device_name: None,
             device_info: None,
             bound_at: None,
@@ -490,10 +731,30 @@ pub async fn batch_create_license_handler(
             bandwidth_used_bytes: None,
             bandwidth_limit_bytes: None,
             quota_exceeded: None,
+            max_devices,
+            max_concurrent,
+            offline_days,
+            billing_cycle: billing_cycle.clone(),
+            usage_period_start: None,
+            suspended_by_org: None,
         };
 
+        let created = snapshot(&license);
         state.db.insert_license(license).await?;
 
+        log_license_event(LicenseEvent::Created, &license_id, Some(&license_key));
+        webhooks::notify(&*state.db, LicenseEvent::Created, &license_id, None).await;
+
+        audit
+            .record(
+                &*state.db,
+                AuditAction::LicenseCreated,
+                AuditTarget::License(license_id.clone()),
+                None,
+                created,
+            )
+            .await;
+
         licenses.push(LicenseSummary {
             license_id,
             license_key,
@@ -543,21 +804,38 @@ pub async fn get_license_handler(
     Ok(Json(license.into()))
 }
 
-/// List licenses with optional filtering.
+/// List licenses, optionally filtered and sorted.
+///
+/// `GET /api/v1/licenses?org_id=&status=&tier=&bound=&blacklisted=&expires_before=&created_after=&created_before=&key_prefix=&metadata=&sort=&order=&cursor=&page=&per_page=`
 ///
-/// `GET /api/v1/licenses?org_id={id}&page={n}&per_page={n}`
+/// Without filters every license is listed; `org_id`, when given, must name
+/// a registered organization. Large listings should be walked by passing
+/// each response's `next_cursor` back as `cursor` rather than by `page`.
 #[cfg_attr(feature = "openapi", utoipa::path(
     get,
     path = "/api/v1/licenses",
     tag = "admin",
     params(
         ("org_id" = Option<String>, Query, description = "Filter by organization ID"),
-        ("page" = Option<u32>, Query, description = "Page number (1-indexed)"),
-        ("per_page" = Option<u32>, Query, description = "Items per page")
+        ("status" = Option<String>, Query, description = "Filter by status"),
+        ("tier" = Option<String>, Query, description = "Filter by tier"),
+        ("bound" = Option<bool>, Query, description = "Only bound (true) or unbound (false) licenses"),
+        ("blacklisted" = Option<bool>, Query, description = "Only blacklisted (true) or non-blacklisted (false) licenses"),
+        ("expires_before" = Option<String>, Query, description = "Only licenses expiring before this time"),
+        ("created_after" = Option<String>, Query, description = "Only licenses issued at or after this time"),
+        ("created_before" = Option<String>, Query, description = "Only licenses issued before this time"),
+        ("key_prefix" = Option<String>, Query, description = "Only licenses whose key starts with this prefix"),
+        ("metadata" = Option<String>, Query, description = "Only licenses with this metadata entry, as key:value"),
+        ("sort" = Option<String>, Query, description = "Sort column: issued_at (default), expires_at, license_id, license_key, org_id, status or tier"),
+        ("order" = Option<String>, Query, description = "Sort order: asc (default) or desc"),
+        ("cursor" = Option<String>, Query, description = "next_cursor from the previous page"),
+        ("page" = Option<u32>, Query, description = "Page number (1-indexed), not combinable with cursor"),
+        ("per_page" = Option<u32>, Query, description = "Items per page (max 500)")
     ),
     responses(
         (status = 200, description = "List of licenses", body = ListLicensesResponse),
-        (status = 400, description = "Invalid request"),
+        (status = 400, description = "Invalid filter, sort or cursor"),
+        (status = 404, description = "Organization not found"),
         (status = 500, description = "Server error"),
     ),
     security(("bearer_auth" = []))
@@ -567,42 +845,102 @@ pub async fn list_licenses_handler(
     Query(query): Query<ListLicensesQuery>,
 ) -> Result<Json<ListLicensesResponse>, AdminError> {
     info!(
-        "Listing licenses org_id={:?} page={} per_page={}",
-        query.org_id, query.page, query.per_page
+        "Listing licenses org_id={:?} page={} per_page={} cursor={}",
+        query.org_id,
+        query.page,
+        query.per_page,
+        query.cursor.is_some()
     );
 
-    let licenses = if let Some(org_id) = &query.org_id {
-        state.db.list_licenses_by_org(org_id).await?
-    } else {
-        // For now, require org_id filter to prevent unbounded queries
+    if query.page == 0 {
         return Err(AdminError::BadRequest(
-            "org_id query parameter is required".to_string(),
+            "page must be at least 1".to_string(),
         ));
+    }
+    if query.per_page == 0 || query.per_page > MAX_LICENSE_PAGE_SIZE {
+        return Err(AdminError::BadRequest(format!(
+            "per_page must be between 1 and {MAX_LICENSE_PAGE_SIZE}"
+        )));
+    }
+
+    let sort = query.sort()?;
+
+    let after = match query.cursor.as_deref() {
+        Some(encoded) => {
+            let cursor = LicenseCursor::decode(encoded)
+                .ok_or_else(|| AdminError::BadRequest("invalid cursor".to_string()))?;
+            if cursor.sort() != sort {
+                return Err(AdminError::BadRequest(
+                    "cursor belongs to a different sort order".to_string(),
+                ));
+            }
+            if query.page != 1 {
+                return Err(AdminError::BadRequest(
+                    "page cannot be combined with cursor".to_string(),
+                ));
+            }
+            Some(cursor)
+        }
+        None => None,
     };
 
-    let total = licenses.len() as u32;
-    let total_pages = total.div_ceil(query.per_page);
+    if let Some(org_id) = &query.org_id {
+        if state.db.get_org(org_id).await?.is_none() {
+            return Err(AdminError::NotFound(format!(
+                "organization not found: {org_id}"
+            )));
+        }
+    }
 
-    // Apply pagination
-    let start = ((query.page.saturating_sub(1)) * query.per_page) as usize;
-    let end = (start + query.per_page as usize).min(licenses.len());
+    let filter = query.filter()?;
 
-    let page_licenses: Vec<LicenseResponse> = licenses
-        .into_iter()
-        .skip(start)
-        .take(end - start)
-        .map(|l| l.into())
-        .collect();
+    // Fetch one extra license to learn whether another page follows
+    let offset = if after.is_some() {
+        0
+    } else {
+        (query.page - 1).saturating_mul(query.per_page)
+    };
+    let (mut licenses, total) = state
+        .db
+        .list_licenses(&filter, sort, after.as_ref(), query.per_page + 1, offset)
+        .await?;
+
+    let next_cursor = if licenses.len() > query.per_page as usize {
+        licenses.truncate(query.per_page as usize);
+        licenses
+            .last()
+            .map(|last| LicenseCursor::after(last, sort).encode())
+    } else {
+        None
+    };
 
     Ok(Json(ListLicensesResponse {
-        licenses: page_licenses,
+        licenses: licenses.into_iter().map(Into::into).collect(),
         total,
         page: query.page,
         per_page: query.per_page,
-        total_pages,
+        total_pages: total.div_ceil(u64::from(query.per_page)),
+        next_cursor,
     }))
 }
 
+/// Parse an optional query parameter, falling back to its default.
+fn parse_param<T>(value: Option<&str>) -> Result<T, AdminError>
+where
+    T: std::str::FromStr<Err = String> + Default,
+{
+    value
+        .map(str::parse)
+        .transpose()
+        .map(Option::unwrap_or_default)
+        .map_err(AdminError::BadRequest)
+}
+
+/// Parse a `key:value` metadata filter.
+pub(crate) fn parse_metadata_filter(s: &str) -> Result<(String, String), AdminError> {
+    license_query::parse_metadata_filter(s).map_err(AdminError::BadRequest)
+}
+
 /// Update a license.
 ///
 /// `PATCH /api/v1/licenses/{license_id}`
@@ -623,6 +961,7 @@ pub async fn list_licenses_handler(
 ))]
 pub async fn update_license_handler(
     State(state): State<AppState>,
+    audit: AuditContext,
     Path(license_id): Path<String>,
     Json(payload): Json<UpdateLicenseRequest>,
 ) -> Result<Json<LicenseResponse>, AdminError> {
@@ -633,6 +972,7 @@ pub async fn update_license_handler(
         .get_license(&license_id)
         .await?
         .ok_or_else(|| AdminError::NotFound(format!("license not found: {license_id}")))?;
+    let before = snapshot(&license);
 
     // Update tier if provided
     if let Some(tier) = &payload.tier {
@@ -661,6 +1001,30 @@ pub async fn update_license_handler(
         license.expires_at = Some(parse_datetime(expires_at_str)?);
     }
 
+    // Update seat limit if provided
+    if payload.max_devices.is_some() {
+        license.max_devices = resolve_max_devices(payload.max_devices)?;
+    }
+
+    // Update floating lease limit if provided
+    if payload.max_concurrent.is_some() {
+        license.max_concurrent = resolve_max_concurrent(payload.max_concurrent)?;
+    }
+
+    // Update offline allowance if provided
+    if payload.offline_days.is_some() {
+        license.offline_days = resolve_offline_days(payload.offline_days)?;
+    }
+
+    // Update billing cycle if provided; an empty one falls back to the tier's
+    if let Some(cycle) = payload.billing_cycle {
+        license.billing_cycle = if cycle.trim().is_empty() {
+            None
+        } else {
+            resolve_billing_cycle(Some(cycle))?
+        };
+    }
+
     // Update metadata if provided
     if let Some(metadata) = &payload.metadata {
         license.metadata = serde_json::to_string(metadata).ok();
@@ -670,6 +1034,16 @@ pub async fn update_license_handler(
 
     info!("Updated license license_id={}", license_id);
 
+    audit
+        .record(
+            &*state.db,
+            AuditAction::LicenseUpdated,
+            AuditTarget::License(license_id),
+            before,
+            snapshot(&license),
+        )
+        .await;
+
     Ok(Json(license.into()))
 }
 
@@ -689,6 +1063,8 @@ pub struct AdminReleaseResponse {
     pub message: String,
     pub previous_hardware_id: Option<String>,
     pub previous_device_name: Option<String>,
+    /// Number of seats that were freed
+    pub released_devices: u64,
 }
 
 /// Admin force release a license from hardware.
@@ -696,7 +1072,9 @@ pub struct AdminReleaseResponse {
 /// `POST /api/v1/licenses/{license_id}/release`
 ///
 /// This endpoint allows administrators to force-release a license from its
-/// bound hardware, useful when a user loses access to their device.
+/// bound hardware, useful when a user loses access to their device. Every
+/// seat is freed; use `DELETE /api/v1/licenses/{license_id}/devices/{hardware_id}`
+/// to free a single one.
 #[cfg_attr(feature = "openapi", utoipa::path(
     post,
     path = "/api/v1/licenses/{license_id}/release",
@@ -714,6 +1092,7 @@ pub struct AdminReleaseResponse {
 ))]
 pub async fn admin_release_handler(
     State(state): State<AppState>,
+    audit: AuditContext,
     Path(license_id): Path<String>,
     Json(payload): Json<AdminReleaseRequest>,
 ) -> Result<Json<AdminReleaseResponse>, AdminError> {
@@ -738,37 +1117,494 @@ pub async fn admin_release_handler(
     // Save previous binding info for response
     let previous_hardware_id = license.hardware_id.clone();
     let previous_device_name = license.device_name.clone();
+    let devices = state.db.list_license_devices(&license_id).await?;
 
-    // Release the license
-    state.db.release_license(&license_id).await?;
+    // Release every seat
+    let released_devices = state.db.remove_all_license_devices(&license_id).await?;
 
     // Record in binding history
+    for device in &devices {
+        let _ = state
+            .db
+            .record_binding_history(
+                &license_id,
+                BindingAction::AdminRelease,
+                Some(&device.hardware_id),
+                device.device_name.as_deref(),
+                device.device_info.as_deref(),
+                PerformedBy::Admin,
+                payload.reason.as_deref(),
+            )
+            .await;
+
+        log_license_binding_event(
+            LicenseEvent::Released,
+            &license_id,
+            &device.hardware_id,
+            device.device_name.as_deref(),
+        );
+        webhooks::notify(
+            &*state.db,
+            LicenseEvent::Released,
+            &license_id,
+            Some(serde_json::json!({
+                "hardware_id": device.hardware_id,
+                "device_name": device.device_name,
+                "reason": payload.reason,
+            })),
+        )
+        .await;
+    }
+
+    info!(
+        "Admin released license {} from {} device(s)",
+        license_id, released_devices
+    );
+
+    let hardware_ids: Vec<&str> = devices.iter().map(|d| d.hardware_id.as_str()).collect();
+    audit
+        .record(
+            &*state.db,
+            AuditAction::LicenseReleased,
+            AuditTarget::License(license_id),
+            Some(serde_json::json!({ "devices": hardware_ids })),
+            Some(serde_json::json!({ "devices": [] })),
+        )
+        .await;
+
+    Ok(Json(AdminReleaseResponse {
+        success: true,
+        message: "License released successfully".to_string(),
+        previous_hardware_id,
+        previous_device_name,
+        released_devices,
+    }))
+}
+
+/// A device holding a seat on a license.
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct LicenseDeviceResponse {
+    pub hardware_id: String,
+    pub device_name: Option<String>,
+    pub device_info: Option<String>,
+    pub bound_at: String,
+    pub last_seen_at: Option<String>,
+}
+
+impl From<LicenseDevice> for LicenseDeviceResponse {
+    fn from(device: LicenseDevice) -> Self {
+        Self {
+            hardware_id: device.hardware_id,
+            device_name: device.device_name,
+            device_info: device.device_info,
+            bound_at: device.bound_at.to_string(),
+            last_seen_at: device.last_seen_at.map(|d| d.to_string()),
+        }
+    }
+}
+
+/// Response for listing the seats of a license.
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct LicenseDevicesResponse {
+    pub license_id: String,
+    pub max_devices: i32,
+    pub devices: Vec<LicenseDeviceResponse>,
+}
+
+/// List the devices holding a seat on a license.
+///
+/// `GET /api/v1/licenses/{license_id}/devices`
+#[cfg_attr(feature = "openapi", utoipa::path(
+    get,
+    path = "/api/v1/licenses/{license_id}/devices",
+    tag = "admin",
+    params(
+        ("license_id" = String, Path, description = "License ID")
+    ),
+    responses(
+        (status = 200, description = "Devices holding a seat", body = LicenseDevicesResponse),
+        (status = 404, description = "License not found"),
+    ),
+    security(("bearer_auth" = []))
+))]
+pub async fn list_license_devices_handler(
+    State(state): State<AppState>,
+    Path(license_id): Path<String>,
+) -> Result<Json<LicenseDevicesResponse>, AdminError> {
+    info!("Listing devices for license_id={}", license_id);
+
+    let license = state
+        .db
+        .get_license(&license_id)
+        .await?
+        .ok_or_else(|| AdminError::NotFound(format!("License {license_id} not found")))?;
+
+    let devices = state.db.list_license_devices(&license_id).await?;
+
+    Ok(Json(LicenseDevicesResponse {
+        license_id,
+        max_devices: license.max_devices,
+        devices: devices.into_iter().map(Into::into).collect(),
+    }))
+}
+
+/// Free the seat held by a single device.
+///
+/// `DELETE /api/v1/licenses/{license_id}/devices/{hardware_id}`
+///
+/// Other devices keep their seats. The freed device must bind again to use
+/// the license.
+#[cfg_attr(feature = "openapi", utoipa::path(
+    delete,
+    path = "/api/v1/licenses/{license_id}/devices/{hardware_id}",
+    tag = "admin",
+    params(
+        ("license_id" = String, Path, description = "License ID"),
+        ("hardware_id" = String, Path, description = "Hardware ID of the device to remove")
+    ),
+    responses(
+        (status = 200, description = "Seat freed", body = AdminReleaseResponse),
+        (status = 404, description = "License or device not found"),
+    ),
+    security(("bearer_auth" = []))
+))]
+pub async fn remove_license_device_handler(
+    State(state): State<AppState>,
+    audit: AuditContext,
+    Path((license_id, hardware_id)): Path<(String, String)>,
+) -> Result<Json<AdminReleaseResponse>, AdminError> {
+    use crate::server::database::{BindingAction, PerformedBy};
+
+    info!(
+        "Admin seat release for license_id={} hardware_id={}",
+        license_id, hardware_id
+    );
+
+    let device = state
+        .db
+        .get_license_device(&license_id, &hardware_id)
+        .await?
+        .ok_or_else(|| {
+            AdminError::NotFound(format!(
+                "Device {hardware_id} does not hold a seat on license {license_id}"
+            ))
+        })?;
+
+    state
+        .db
+        .remove_license_device(&license_id, &hardware_id)
+        .await?;
+
     let _ = state
         .db
         .record_binding_history(
             &license_id,
             BindingAction::AdminRelease,
-            previous_hardware_id.as_deref(),
-            previous_device_name.as_deref(),
-            license.device_info.as_deref(),
+            Some(&device.hardware_id),
+            device.device_name.as_deref(),
+            device.device_info.as_deref(),
             PerformedBy::Admin,
-            payload.reason.as_deref(),
+            None,
         )
         .await;
 
     info!(
-        "Admin released license {} from hardware {:?}",
-        license_id, previous_hardware_id
+        "Admin released license {} from hardware {}",
+        license_id, hardware_id
+    );
+
+    log_license_binding_event(
+        LicenseEvent::Released,
+        &license_id,
+        &device.hardware_id,
+        device.device_name.as_deref(),
     );
+    webhooks::notify(
+        &*state.db,
+        LicenseEvent::Released,
+        &license_id,
+        Some(serde_json::json!({
+            "hardware_id": device.hardware_id,
+            "device_name": device.device_name,
+        })),
+    )
+    .await;
+
+    audit
+        .record(
+            &*state.db,
+            AuditAction::DeviceRemoved,
+            AuditTarget::License(license_id),
+            snapshot(&device),
+            None,
+        )
+        .await;
 
     Ok(Json(AdminReleaseResponse {
         success: true,
-        message: "License released successfully".to_string(),
-        previous_hardware_id,
-        previous_device_name,
+        message: "Device removed from license".to_string(),
+        previous_hardware_id: Some(device.hardware_id),
+        previous_device_name: device.device_name,
+        released_devices: 1,
     }))
 }
 
+/// Maximum page size for the binding history endpoints.
+pub const MAX_HISTORY_PAGE_SIZE: u32 = 500;
+
+/// Query parameters for listing binding history.
+#[derive(Debug, Deserialize)]
+pub struct BindingHistoryQuery {
+    /// Filter by action (`bind`, `release`, `admin_release` or `system_release`)
+    pub action: Option<String>,
+    /// Filter by who performed the action (`client`, `admin` or `system`)
+    pub performed_by: Option<String>,
+    /// Pagination: page number (1-indexed)
+    #[serde(default = "default_page")]
+    pub page: u32,
+    /// Pagination: items per page
+    #[serde(default = "default_per_page")]
+    pub per_page: u32,
+}
+
+impl BindingHistoryQuery {
+    /// Validate pagination and build the store filter.
+    fn filter(&self) -> Result<BindingHistoryFilter, AdminError> {
+        if self.page == 0 {
+            return Err(AdminError::BadRequest(
+                "page must be at least 1".to_string(),
+            ));
+        }
+        if self.per_page == 0 || self.per_page > MAX_HISTORY_PAGE_SIZE {
+            return Err(AdminError::BadRequest(format!(
+                "per_page must be between 1 and {MAX_HISTORY_PAGE_SIZE}"
+            )));
+        }
+
+        Ok(BindingHistoryFilter {
+            action: self
+                .action
+                .as_deref()
+                .map(str::parse)
+                .transpose()
+                .map_err(AdminError::BadRequest)?,
+            performed_by: self
+                .performed_by
+                .as_deref()
+                .map(str::parse)
+                .transpose()
+                .map_err(AdminError::BadRequest)?,
+            ..Default::default()
+        })
+    }
+
+    fn offset(&self) -> u32 {
+        (self.page - 1).saturating_mul(self.per_page)
+    }
+}
+
+/// A binding history record.
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct BindingHistoryEntryResponse {
+    pub id: i64,
+    pub license_id: String,
+    /// License key (device history only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub license_key: Option<String>,
+    /// Organization owning the license (device history only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub org_id: Option<String>,
+    pub action: String,
+    pub hardware_id: Option<String>,
+    pub device_name: Option<String>,
+    pub device_info: Option<String>,
+    pub performed_by: Option<String>,
+    pub reason: Option<String>,
+    pub created_at: String,
+}
+
+impl From<LicenseBindingHistory> for BindingHistoryEntryResponse {
+    fn from(entry: LicenseBindingHistory) -> Self {
+        Self {
+            id: entry.id,
+            license_id: entry.license_id,
+            license_key: None,
+            org_id: None,
+            action: entry.action,
+            hardware_id: entry.hardware_id,
+            device_name: entry.device_name,
+            device_info: entry.device_info,
+            performed_by: entry.performed_by,
+            reason: entry.reason,
+            created_at: entry.created_at.and_utc().to_rfc3339(),
+        }
+    }
+}
+
+/// Response for the binding history of a license.
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct LicenseHistoryResponse {
+    pub license_id: String,
+    pub entries: Vec<BindingHistoryEntryResponse>,
+    pub total: u64,
+    pub page: u32,
+    pub per_page: u32,
+    pub total_pages: u64,
+}
+
+/// Response for the binding history of a device across all licenses.
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct DeviceHistoryResponse {
+    pub hardware_id: String,
+    pub entries: Vec<BindingHistoryEntryResponse>,
+    pub total: u64,
+    pub page: u32,
+    pub per_page: u32,
+    pub total_pages: u64,
+}
+
+/// List the binding history of a license, newest first.
+///
+/// `GET /api/v1/licenses/{license_id}/history?action=&performed_by=&page=&per_page=`
+///
+/// Covers binds, client releases, admin releases and stale-device releases.
+#[cfg_attr(feature = "openapi", utoipa::path(
+    get,
+    path = "/api/v1/licenses/{license_id}/history",
+    tag = "admin",
+    params(
+        ("license_id" = String, Path, description = "License ID"),
+        ("action" = Option<String>, Query, description = "Filter by action (bind, release, admin_release or system_release)"),
+        ("performed_by" = Option<String>, Query, description = "Filter by performer (client, admin or system)"),
+        ("page" = Option<u32>, Query, description = "Page number (1-indexed)"),
+        ("per_page" = Option<u32>, Query, description = "Items per page (max 500)")
+    ),
+    responses(
+        (status = 200, description = "Binding history", body = LicenseHistoryResponse),
+        (status = 400, description = "Invalid filter"),
+        (status = 404, description = "License not found"),
+    ),
+    security(("bearer_auth" = []))
+))]
+pub async fn license_history_handler(
+    State(state): State<AppState>,
+    Path(license_id): Path<String>,
+    Query(query): Query<BindingHistoryQuery>,
+) -> Result<Json<LicenseHistoryResponse>, AdminError> {
+    info!("Listing binding history for license_id={}", license_id);
+
+    let filter = BindingHistoryFilter {
+        license_id: Some(license_id.clone()),
+        ..query.filter()?
+    };
+
+    if state.db.get_license(&license_id).await?.is_none() {
+        return Err(AdminError::NotFound(format!(
+            "License {license_id} not found"
+        )));
+    }
+
+    let (entries, total) = state
+        .db
+        .list_binding_history(&filter, query.per_page, query.offset())
+        .await?;
+
+    Ok(Json(LicenseHistoryResponse {
+        license_id,
+        entries: entries.into_iter().map(Into::into).collect(),
+        total,
+        page: query.page,
+        per_page: query.per_page,
+        total_pages: total.div_ceil(u64::from(query.per_page)),
+    }))
+}
+
+/// List every binding action of a device across all licenses and
+/// organizations, newest first.
+///
+/// `GET /api/v1/devices/{hardware_id}/history?action=&performed_by=&page=&per_page=`
+///
+/// Entries carry the license key and organization of their license. A device
+/// that never bound a license has an empty history.
+#[cfg_attr(feature = "openapi", utoipa::path(
+    get,
+    path = "/api/v1/devices/{hardware_id}/history",
+    tag = "admin",
+    params(
+        ("hardware_id" = String, Path, description = "Hardware ID of the device"),
+        ("action" = Option<String>, Query, description = "Filter by action (bind, release, admin_release or system_release)"),
+        ("performed_by" = Option<String>, Query, description = "Filter by performer (client, admin or system)"),
+        ("page" = Option<u32>, Query, description = "Page number (1-indexed)"),
+        ("per_page" = Option<u32>, Query, description = "Items per page (max 500)")
+    ),
+    responses(
+        (status = 200, description = "Binding history of the device", body = DeviceHistoryResponse),
+        (status = 400, description = "Invalid filter"),
+    ),
+    security(("bearer_auth" = []))
+))]
+pub async fn device_history_handler(
+    State(state): State<AppState>,
+    Path(hardware_id): Path<String>,
+    Query(query): Query<BindingHistoryQuery>,
+) -> Result<Json<DeviceHistoryResponse>, AdminError> {
+    info!("Listing binding history for hardware_id={}", hardware_id);
+
+    let filter = BindingHistoryFilter {
+        hardware_id: Some(hardware_id.clone()),
+        ..query.filter()?
+    };
+
+    let (entries, total) = state
+        .db
+        .list_binding_history(&filter, query.per_page, query.offset())
+        .await?;
+
+    // Look up each license on the page once
+    let mut licenses: std::collections::HashMap<String, Option<License>> =
+        std::collections::HashMap::new();
+    let mut responses = Vec::with_capacity(entries.len());
+    for entry in entries {
+        if !licenses.contains_key(&entry.license_id) {
+            let license = state.db.get_license(&entry.license_id).await?;
+            licenses.insert(entry.license_id.clone(), license);
+        }
+        let license = licenses[&entry.license_id].as_ref();
+
+        let mut response = BindingHistoryEntryResponse::from(entry);
+        response.license_key = license.and_then(|l| l.license_key.clone());
+        response.org_id = license.and_then(|l| l.org_id.clone());
+        responses.push(response);
+    }
+
+    Ok(Json(DeviceHistoryResponse {
+        hardware_id,
+        entries: responses,
+        total,
+        page: query.page,
+        per_page: query.per_page,
+        total_pages: total.div_ceil(u64::from(query.per_page)),
+    }))
+}
+
+/// Default offline validity window for manual activations.
+pub const DEFAULT_OFFLINE_ACTIVATION_DAYS: u32 = 30;
+
+/// Request for activating an air-gapped machine.
+#[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct OfflineActivationRequest {
+    /// Activation request file generated on the target machine
+    pub request: ActivationRequest,
+    /// How long the machine may validate offline (default 30 days)
+    pub valid_for_days: Option<u32>,
+}
+
 /// Request for revoking a license.
 #[derive(Debug, Deserialize)]
 #[cfg_attr(feature = "openapi", derive(ToSchema))]
@@ -799,7 +1635,7 @@ pub struct RevokeLicenseResponse {
 pub struct ReinstateLicenseRequest {
     /// New expiration date (optional, ISO 8601 format)
     pub new_expires_at: Option<String>,
-    /// Whether to reset bandwidth counters (if applicable)
+    /// Close the current usage period, resetting every usage meter
     #[serde(default)]
     pub reset_bandwidth: bool,
     /// Reason for reinstatement (for audit)
@@ -823,7 +1659,7 @@ pub struct ReinstateLicenseResponse {
 pub struct ExtendLicenseRequest {
     /// New expiration date (required, ISO 8601 format)
     pub new_expires_at: String,
-    /// Whether to reset bandwidth counters (if applicable)
+    /// Close the current usage period, resetting every usage meter
     #[serde(default)]
     pub reset_bandwidth: bool,
     /// Reason for extension (for audit)
@@ -846,7 +1682,7 @@ pub struct ExtendLicenseResponse {
 pub struct UpdateUsageRequest {
     /// Current bandwidth used in bytes
     pub bandwidth_used_bytes: Option<u64>,
-    /// Bandwidth limit in bytes (None = unlimited)
+    /// Per-license bandwidth limit in bytes, overriding the tier's (0 = unlimited)
     pub bandwidth_limit_bytes: Option<u64>,
     /// Whether to reset the usage counter to zero
     #[serde(default)]
@@ -859,7 +1695,9 @@ pub struct UpdateUsageRequest {
 pub struct UpdateUsageResponse {
     pub success: bool,
     pub bandwidth_used_bytes: u64,
+    /// Limit from the license or its tier (None = unlimited)
     pub bandwidth_limit_bytes: Option<u64>,
+    /// Any usage meter of the license has reached its limit
     pub quota_exceeded: bool,
     pub usage_percentage: Option<f64>,
 }
@@ -891,6 +1729,7 @@ pub struct UpdateUsageResponse {
 ))]
 pub async fn revoke_license_handler(
     State(state): State<AppState>,
+    audit: AuditContext,
     Path(license_id): Path<String>,
     Json(payload): Json<RevokeLicenseRequest>,
 ) -> Result<Json<RevokeLicenseResponse>, AdminError> {
@@ -913,7 +1752,10 @@ pub async fn revoke_license_handler(
         ));
     }
 
+    let before = snapshot(&license);
     let now = Utc::now().naive_utc();
+    // Revoked on its own now, so reinstating its organization leaves it be
+    license.suspended_by_org = None;
 
     if payload.grace_period_days == 0 {
         // Immediate revocation
@@ -924,6 +1766,7 @@ pub async fn revoke_license_handler(
         license.grace_period_ends_at = None;
         license.suspension_message = None;
 
+        let after = snapshot(&license);
         state.db.insert_license(license).await?;
 
         // Log structured revoke event
@@ -932,6 +1775,23 @@ pub async fn revoke_license_handler(
             &license_id,
             payload.reason.as_deref(),
         );
+        webhooks::notify(
+            &*state.db,
+            LicenseEvent::Revoked,
+            &license_id,
+            Some(serde_json::json!({ "reason": payload.reason })),
+        )
+        .await;
+
+        audit
+            .record(
+                &*state.db,
+                AuditAction::LicenseRevoked,
+                AuditTarget::License(license_id),
+                before,
+                after,
+            )
+            .await;
 
         Ok(Json(RevokeLicenseResponse {
             success: true,
@@ -950,6 +1810,7 @@ pub async fn revoke_license_handler(
         license.suspension_message = payload.message.clone();
         // Don't set revoked_at yet - that happens when grace period expires
 
+        let after = snapshot(&license);
         state.db.insert_license(license).await?;
 
         // Log structured suspend event
@@ -958,6 +1819,26 @@ pub async fn revoke_license_handler(
             &license_id,
             Some(&format!("grace period until {}", grace_end)),
         );
+        webhooks::notify(
+            &*state.db,
+            LicenseEvent::Suspended,
+            &license_id,
+            Some(serde_json::json!({
+                "reason": payload.reason,
+                "grace_period_ends_at": grace_end.and_utc().to_rfc3339(),
+            })),
+        )
+        .await;
+
+        audit
+            .record(
+                &*state.db,
+                AuditAction::LicenseSuspended,
+                AuditTarget::License(license_id),
+                before,
+                after,
+            )
+            .await;
 
         Ok(Json(RevokeLicenseResponse {
             success: true,
@@ -971,6 +1852,19 @@ pub async fn revoke_license_handler(
     }
 }
 
+/// Close a license's usage period now, keeping its usage in the history.
+async fn reset_usage(db: &dyn LicenseStore, license_id: &str) -> Result<(), AdminError> {
+    let closed = db
+        .close_usage_period(license_id, Utc::now().naive_utc())
+        .await?;
+    info!(
+        "Reset usage of license {} ({} meter(s) closed)",
+        license_id,
+        closed.map_or(0, |periods| periods.len())
+    );
+    Ok(())
+}
+
 /// Reinstate a revoked or suspended license.
 ///
 /// `POST /api/v1/licenses/{license_id}/reinstate`
@@ -981,7 +1875,8 @@ pub async fn revoke_license_handler(
 /// - Sets status back to 'active'
 /// - Clears all suspension/revocation fields
 /// - Optionally sets a new expiration date
-/// - Optionally resets bandwidth counters (if tracked)
+/// - With `reset_bandwidth`, closes the current usage period and resets
+///   every usage meter
 #[cfg_attr(feature = "openapi", utoipa::path(
     post,
     path = "/api/v1/licenses/{license_id}/reinstate",
@@ -999,6 +1894,7 @@ pub async fn revoke_license_handler(
 ))]
 pub async fn reinstate_license_handler(
     State(state): State<AppState>,
+    audit: AuditContext,
     Path(license_id): Path<String>,
     Json(payload): Json<ReinstateLicenseRequest>,
 ) -> Result<Json<ReinstateLicenseResponse>, AdminError> {
@@ -1028,6 +1924,22 @@ pub async fn reinstate_license_handler(
         ));
     }
 
+    // Licenses of a suspended organization come back with the organization
+    if let Some(org_id) = &license.org_id {
+        if state
+            .db
+            .get_org(org_id)
+            .await?
+            .is_some_and(|org| org.is_suspended())
+        {
+            return Err(AdminError::BadRequest(format!(
+                "organization {org_id} is suspended"
+            )));
+        }
+    }
+
+    let before = snapshot(&license);
+
     // Set status back to active
     license.status = "active".to_string();
 
@@ -1037,6 +1949,7 @@ pub async fn reinstate_license_handler(
     license.revoke_reason = None;
     license.grace_period_ends_at = None;
     license.suspension_message = None;
+    license.suspended_by_org = None;
 
     // Update expiration date if provided
     let expires_at_str = if let Some(new_expires_at) = &payload.new_expires_at {
@@ -1047,23 +1960,36 @@ pub async fn reinstate_license_handler(
         license.expires_at.map(|dt| dt.to_string())
     };
 
-    // Note: reset_bandwidth would reset any bandwidth tracking if we had it
-    // For now, this is a no-op but the field is accepted for future compatibility
+    let after = snapshot(&license);
+    state.db.insert_license(license).await?;
+
     if payload.reset_bandwidth {
-        info!(
-            "Bandwidth reset requested for license {} (no-op for now)",
-            license_id
-        );
+        reset_usage(&*state.db, &license_id).await?;
     }
 
-    state.db.insert_license(license).await?;
-
     // Log structured reinstate event
     log_license_event(
         LicenseEvent::Reinstated,
         &license_id,
         payload.reason.as_deref(),
     );
+    webhooks::notify(
+        &*state.db,
+        LicenseEvent::Reinstated,
+        &license_id,
+        Some(serde_json::json!({ "reason": payload.reason })),
+    )
+    .await;
+
+    audit
+        .record(
+            &*state.db,
+            AuditAction::LicenseReinstated,
+            AuditTarget::License(license_id),
+            before,
+            after,
+        )
+        .await;
 
     Ok(Json(ReinstateLicenseResponse {
         success: true,
@@ -1081,7 +2007,8 @@ pub async fn reinstate_license_handler(
 ///
 /// # Behavior
 /// - Updates the `expires_at` field to the new date
-/// - Optionally resets bandwidth counters (when quota tracking is enabled)
+/// - With `reset_bandwidth`, closes the current usage period and resets
+///   every usage meter
 /// - Can be used on active, suspended, or revoked licenses
 #[cfg_attr(feature = "openapi", utoipa::path(
     post,
@@ -1100,6 +2027,7 @@ pub async fn reinstate_license_handler(
 ))]
 pub async fn extend_license_handler(
     State(state): State<AppState>,
+    audit: AuditContext,
     Path(license_id): Path<String>,
     Json(payload): Json<ExtendLicenseRequest>,
 ) -> Result<Json<ExtendLicenseResponse>, AdminError> {
@@ -1120,21 +2048,18 @@ pub async fn extend_license_handler(
 
     // Save previous expiration for response
     let previous_expires_at = license.expires_at.map(|dt| dt.to_string());
+    let before = snapshot(&license);
 
     // Update expiration date
     license.expires_at = Some(new_expires_at);
 
-    // Note: reset_bandwidth would reset any bandwidth tracking if we had it
-    // For now, this is a no-op but the field is accepted for future compatibility
+    let after = snapshot(&license);
+    state.db.insert_license(license).await?;
+
     if payload.reset_bandwidth {
-        info!(
-            "Bandwidth reset requested for license {} (no-op for now)",
-            license_id
-        );
+        reset_usage(&*state.db, &license_id).await?;
     }
 
-    state.db.insert_license(license).await?;
-
     // Log structured extend event
     log_license_event(
         LicenseEvent::Extended,
@@ -1142,6 +2067,16 @@ pub async fn extend_license_handler(
         Some(&format!("extended to {}", new_expires_at)),
     );
 
+    audit
+        .record(
+            &*state.db,
+            AuditAction::LicenseExtended,
+            AuditTarget::License(license_id),
+            before,
+            after,
+        )
+        .await;
+
     Ok(Json(ExtendLicenseResponse {
         success: true,
         message: "License expiration has been extended".to_string(),
@@ -1154,18 +2089,16 @@ pub async fn extend_license_handler(
 ///
 /// `PATCH /api/v1/licenses/{license_id}/usage`
 ///
-/// This endpoint updates the bandwidth usage tracking for a license.
+/// This endpoint updates the built-in `bandwidth` usage meter of a license.
 ///
 /// # Behavior
-/// - Sets `bandwidth_used_bytes` to the provided value (or resets to 0 if `reset: true`)
-/// - Sets `bandwidth_limit_bytes` if provided
-/// - Calculates `quota_exceeded` flag based on usage vs limit
+/// - Sets the meter's usage to `bandwidth_used_bytes` (or resets it to 0 if `reset: true`)
+/// - Sets the meter's per-license limit if `bandwidth_limit_bytes` is provided
+/// - Without a per-license limit, the tier's `bandwidth_gb` applies
+/// - Recalculates the license's `quota_exceeded` flag across all its meters
 /// - Returns usage statistics including percentage used
 ///
-/// # Note
-/// Update usage/quota tracking for a license.
-///
-/// Persists bandwidth usage to the database and calculates quota status.
+/// Other meters are set with `PUT /api/v1/licenses/{license_id}/usage/{meter}`.
 #[cfg_attr(feature = "openapi", utoipa::path(
     patch,
     path = "/api/v1/licenses/{license_id}/usage",
@@ -1183,6 +2116,7 @@ pub async fn extend_license_handler(
 ))]
 pub async fn update_usage_handler(
     State(state): State<AppState>,
+    audit: AuditContext,
     Path(license_id): Path<String>,
     Json(payload): Json<UpdateUsageRequest>,
 ) -> Result<Json<UpdateUsageResponse>, AdminError> {
@@ -1198,52 +2132,80 @@ pub async fn update_usage_handler(
         .await?
         .ok_or_else(|| AdminError::NotFound(format!("License {license_id} not found")))?;
 
-    // Calculate usage values
-    // If reset, set to 0. Otherwise use provided value or keep existing.
-    let bandwidth_used_bytes: u64 = if payload.reset {
-        0
-    } else {
-        payload
-            .bandwidth_used_bytes
-            .unwrap_or_else(|| license.bandwidth_used_bytes.unwrap_or(0) as u64)
+    let to_i64 = |value: u64, field: &str| {
+        i64::try_from(value).map_err(|_| AdminError::BadRequest(format!("{field} is too large")))
     };
+    let now = Utc::now().naive_utc();
+    let mut meter = state
+        .db
+        .list_usage_meters(&license_id)
+        .await?
+        .into_iter()
+        .find(|m| m.meter == BANDWIDTH_METER)
+        .unwrap_or_else(|| UsageMeter::empty(&license_id, BANDWIDTH_METER, now));
 
+    // If reset, set to 0. Otherwise use provided value or keep existing.
+    if payload.reset {
+        meter.used = 0;
+    } else if let Some(used) = payload.bandwidth_used_bytes {
+        meter.used = to_i64(used, "bandwidth_used_bytes")?;
+    }
     // Use provided limit or keep existing
-    let bandwidth_limit_bytes: Option<u64> = payload
-        .bandwidth_limit_bytes
-        .or_else(|| license.bandwidth_limit_bytes.map(|v| v as u64));
-
-    // Calculate quota exceeded
-    let quota_exceeded = match bandwidth_limit_bytes {
-        Some(limit) if limit > 0 => bandwidth_used_bytes >= limit,
-        _ => false,
-    };
-
-    // Calculate usage percentage
-    let usage_percentage = bandwidth_limit_bytes.map(|limit| {
-        if limit > 0 {
-            (bandwidth_used_bytes as f64 / limit as f64) * 100.0
-        } else {
-            0.0
-        }
-    });
+    if let Some(limit) = payload.bandwidth_limit_bytes {
+        meter.usage_limit = Some(to_i64(limit, "bandwidth_limit_bytes")?);
+    }
+    meter.updated_at = now;
 
     // Persist to database
-    state
-        .db
-        .update_usage(
-            &license_id,
-            bandwidth_used_bytes as i64,
-            bandwidth_limit_bytes.map(|v| v as i64),
-            quota_exceeded,
-        )
-        .await?;
+    state.db.set_usage_meter(&meter).await?;
+
+    let status = MeterStatus::new(&meter, license_tier(&license).as_ref());
+    let bandwidth_used_bytes = status.used as u64;
+    let bandwidth_limit_bytes = status.limit.map(|limit| limit as u64);
+
+    // Calculate usage percentage
+    let usage_percentage =
+        bandwidth_limit_bytes.map(|limit| (bandwidth_used_bytes as f64 / limit as f64) * 100.0);
+
+    // Notify only when the license crosses its quota, not on every update above it
+    let meters = license_meter_statuses(&*state.db, &license).await?;
+    let quota_exceeded = sync_quota_exceeded(
+        &*state.db,
+        &license,
+        &meters,
+        serde_json::json!({
+            "meter": BANDWIDTH_METER,
+            "used": bandwidth_used_bytes,
+            "limit": bandwidth_limit_bytes,
+            "bandwidth_used_bytes": bandwidth_used_bytes,
+            "bandwidth_limit_bytes": bandwidth_limit_bytes,
+        }),
+    )
+    .await?;
 
     info!(
         "Usage updated for license {}: used={} limit={:?} exceeded={}",
         license_id, bandwidth_used_bytes, bandwidth_limit_bytes, quota_exceeded
     );
 
+    audit
+        .record(
+            &*state.db,
+            AuditAction::UsageUpdated,
+            AuditTarget::License(license_id),
+            Some(serde_json::json!({
+                "bandwidth_used_bytes": license.bandwidth_used_bytes,
+                "bandwidth_limit_bytes": license.bandwidth_limit_bytes,
+                "quota_exceeded": license.quota_exceeded,
+            })),
+            Some(serde_json::json!({
+                "bandwidth_used_bytes": bandwidth_used_bytes,
+                "bandwidth_limit_bytes": meter.usage_limit,
+                "quota_exceeded": quota_exceeded,
+            })),
+        )
+        .await;
+
     Ok(Json(UpdateUsageResponse {
         success: true,
         bandwidth_used_bytes,
@@ -1304,6 +2266,7 @@ pub struct BlacklistLicenseResponse {
 ))]
 pub async fn blacklist_license_handler(
     State(state): State<AppState>,
+    audit: AuditContext,
     Path(license_id): Path<String>,
     Json(payload): Json<BlacklistLicenseRequest>,
 ) -> Result<Json<BlacklistLicenseResponse>, AdminError> {
@@ -1335,18 +2298,21 @@ pub async fn blacklist_license_handler(
         ));
     }
 
+    let before = snapshot(&license);
     let now = Utc::now().naive_utc();
 
-    // If bound, record the release in history before clearing
-    if license.is_bound() {
+    // Free every seat, recording each release in history
+    let devices = state.db.list_license_devices(&license_id).await?;
+    state.db.remove_all_license_devices(&license_id).await?;
+    for device in &devices {
         let _ = state
             .db
             .record_binding_history(
                 &license_id,
                 BindingAction::AdminRelease,
-                license.hardware_id.as_deref(),
-                license.device_name.as_deref(),
-                license.device_info.as_deref(),
+                Some(&device.hardware_id),
+                device.device_name.as_deref(),
+                device.device_info.as_deref(),
                 PerformedBy::Admin,
                 Some(&format!("Blacklisted: {}", payload.reason)),
             )
@@ -1374,6 +2340,7 @@ pub async fn blacklist_license_handler(
     license.device_info = None;
     license.bound_at = None;
 
+    let after = snapshot(&license);
     state.db.insert_license(license).await?;
 
     // Log structured blacklist event
@@ -1382,6 +2349,23 @@ pub async fn blacklist_license_handler(
         &license_id,
         Some(&payload.reason),
     );
+    webhooks::notify(
+        &*state.db,
+        LicenseEvent::Blacklisted,
+        &license_id,
+        Some(serde_json::json!({ "reason": payload.reason })),
+    )
+    .await;
+
+    audit
+        .record(
+            &*state.db,
+            AuditAction::LicenseBlacklisted,
+            AuditTarget::License(license_id),
+            before,
+            after,
+        )
+        .await;
 
     Ok(Json(BlacklistLicenseResponse {
         success: true,
@@ -1391,6 +2375,166 @@ pub async fn blacklist_license_handler(
     }))
 }
 
+/// Activate a license for an air-gapped machine.
+///
+/// `POST /api/v1/licenses/offline-activation`
+///
+/// Binds the license to the hardware in the uploaded request file and returns
+/// a signed activation response for the admin to carry back to the machine.
+/// Requires a configured signing key.
+#[cfg_attr(feature = "openapi", utoipa::path(
+    post,
+    path = "/api/v1/licenses/offline-activation",
+    tag = "admin",
+    request_body = OfflineActivationRequest,
+    responses(
+        (status = 200, description = "License activated", body = ActivationResponse),
+        (status = 400, description = "License cannot be activated on this hardware"),
+        (status = 404, description = "License not found"),
+        (status = 500, description = "Signing key not configured"),
+    ),
+    security(("bearer_auth" = []))
+))]
+pub async fn offline_activation_handler(
+    State(state): State<AppState>,
+    audit: AuditContext,
+    Json(payload): Json<OfflineActivationRequest>,
+) -> Result<Json<ActivationResponse>, AdminError> {
+    use crate::server::database::{BindingAction, PerformedBy};
+
+    let request = payload.request;
+    info!(
+        "Offline activation request for license_key={} hardware_id={}",
+        request.license_key, request.hardware_id
+    );
+
+    if state.signer.is_none() {
+        return Err(AdminError::ConfigError(
+            "offline activation requires a signing key".to_string(),
+        ));
+    }
+    if request.hardware_id.trim().is_empty() {
+        return Err(AdminError::BadRequest(
+            "hardware_id is required".to_string(),
+        ));
+    }
+
+    // Get the license
+    let license = state
+        .db
+        .get_license_by_key(&request.license_key)
+        .await?
+        .ok_or_else(|| {
+            AdminError::NotFound(format!("License key {} not found", request.license_key))
+        })?;
+
+    // Check license status
+    if license.is_blacklisted == Some(true) {
+        return Err(AdminError::BadRequest("License is blacklisted".to_string()));
+    }
+    if license.status != "active" {
+        return Err(AdminError::BadRequest(format!(
+            "License status is '{}'",
+            license.status
+        )));
+    }
+    if license.is_expired() {
+        return Err(AdminError::BadRequest("License has expired".to_string()));
+    }
+
+    // Claim a seat, unless this hardware already holds one
+    let claim = state
+        .db
+        .claim_license_seat(
+            &license,
+            &request.hardware_id,
+            request.device_name.as_deref(),
+            request.device_info.as_deref(),
+        )
+        .await?;
+
+    match claim {
+        SeatClaim::Existing(_) => {}
+        SeatClaim::Full(_) => {
+            return Err(AdminError::BadRequest(format!(
+                "All {} seat(s) on this license are in use",
+                license.max_devices
+            )));
+        }
+        SeatClaim::Claimed => {
+            let _ = state
+                .db
+                .record_binding_history(
+                    &license.license_id,
+                    BindingAction::Bind,
+                    Some(&request.hardware_id),
+                    request.device_name.as_deref(),
+                    request.device_info.as_deref(),
+                    PerformedBy::Admin,
+                    Some("Offline activation"),
+                )
+                .await;
+
+            log_license_binding_event(
+                LicenseEvent::Bound,
+                &license.license_id,
+                &request.hardware_id,
+                request.device_name.as_deref(),
+            );
+            webhooks::notify(
+                &*state.db,
+                LicenseEvent::Bound,
+                &license.license_id,
+                Some(serde_json::json!({
+                    "hardware_id": request.hardware_id,
+                    "device_name": request.device_name,
+                })),
+            )
+            .await;
+        }
+    }
+
+    // The offline window can never outlast the license itself
+    let days = payload
+        .valid_for_days
+        .unwrap_or(DEFAULT_OFFLINE_ACTIVATION_DAYS);
+    let mut offline_until = Utc::now().naive_utc() + chrono::Duration::days(i64::from(days));
+    if let Some(expires_at) = license.expires_at {
+        offline_until = offline_until.min(expires_at);
+    }
+
+    let signed_license = issue_signed_license(
+        &state,
+        &license,
+        &request.hardware_id,
+        None,
+        Some(offline_until.and_utc().to_rfc3339()),
+    )
+    .await
+    .ok_or_else(|| AdminError::ConfigError("failed to sign activation".to_string()))?;
+
+    info!(
+        "Offline activation issued for license {} on hardware {}",
+        license.license_id, request.hardware_id
+    );
+
+    audit
+        .record(
+            &*state.db,
+            AuditAction::OfflineActivated,
+            AuditTarget::License(license.license_id.clone()),
+            None,
+            Some(serde_json::json!({
+                "hardware_id": request.hardware_id,
+                "device_name": request.device_name,
+                "offline_until": offline_until.and_utc().to_rfc3339(),
+            })),
+        )
+        .await;
+
+    Ok(Json(ActivationResponse::new(signed_license)))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1467,6 +2611,12 @@ mod tests {
             bandwidth_used_bytes: None,
             bandwidth_limit_bytes: None,
             quota_exceeded: None,
+            max_devices: 1,
+            max_concurrent: None,
+            offline_days: None,
+            billing_cycle: None,
+            usage_period_start: None,
+            suspended_by_org: None,
         };
 
         let response: LicenseResponse = license.into();
@@ -55,12 +55,14 @@ pub enum ErrorCode {
     LicenseInactive,
 
     // === Hardware Binding Errors (4xx) ===
-    /// License is already bound to a different device
-    AlreadyBound,
+    /// Every seat on the license is held by another device
+    SeatLimitReached,
     /// License is not bound to any device
     NotBound,
     /// Request hardware ID doesn't match bound device
     HardwareMismatch,
+    /// Every floating lease on the license is checked out
+    LeaseLimitReached,
 
     // === Feature/Quota Errors (4xx) ===
     /// Requested feature is not included in license tier
@@ -68,6 +70,12 @@ pub enum ErrorCode {
     /// Usage quota has been exceeded
     QuotaExceeded,
 
+    // === Trial Errors (4xx) ===
+    /// The machine has already had a trial
+    TrialAlreadyUsed,
+    /// The server does not issue trial licenses
+    TrialsDisabled,
+
     // === Validation Errors (400) ===
     /// Request payload is invalid or malformed
     InvalidRequest,
@@ -133,15 +141,18 @@ impl ErrorCode {
             | ErrorCode::HardwareMismatch
             | ErrorCode::FeatureNotIncluded
             | ErrorCode::QuotaExceeded
+            | ErrorCode::TrialsDisabled
             | ErrorCode::InsufficientScope => StatusCode::FORBIDDEN,
 
             // 404 Not Found
             ErrorCode::LicenseNotFound | ErrorCode::NotFound => StatusCode::NOT_FOUND,
 
             // 409 Conflict
-            ErrorCode::AlreadyBound | ErrorCode::NotBound | ErrorCode::Conflict => {
-                StatusCode::CONFLICT
-            }
+            ErrorCode::SeatLimitReached
+            | ErrorCode::NotBound
+            | ErrorCode::LeaseLimitReached
+            | ErrorCode::TrialAlreadyUsed
+            | ErrorCode::Conflict => StatusCode::CONFLICT,
 
             // 500 Internal Server Error
             ErrorCode::DatabaseError
@@ -166,11 +177,14 @@ impl ErrorCode {
             ErrorCode::LicenseSuspended => "License is temporarily suspended",
             ErrorCode::LicenseBlacklisted => "License has been permanently blacklisted",
             ErrorCode::LicenseInactive => "License is not active",
-            ErrorCode::AlreadyBound => "License is already bound to another device",
+            ErrorCode::SeatLimitReached => "All seats on this license are in use",
             ErrorCode::NotBound => "License is not bound to any device",
             ErrorCode::HardwareMismatch => "Hardware ID does not match the bound device",
+            ErrorCode::LeaseLimitReached => "All concurrent leases on this license are in use",
             ErrorCode::FeatureNotIncluded => "Feature is not included in your license tier",
             ErrorCode::QuotaExceeded => "Usage quota has been exceeded",
+            ErrorCode::TrialAlreadyUsed => "A trial has already been started on this machine",
+            ErrorCode::TrialsDisabled => "Trial licenses are not available",
             ErrorCode::InvalidRequest => "Request payload is invalid",
             ErrorCode::MissingField => "A required field is missing",
             ErrorCode::InvalidField => "A field value is invalid",
@@ -351,6 +365,7 @@ impl From<LicenseError> for ApiError {
             }
             LicenseError::EncryptionError(msg)
             | LicenseError::DecryptionError(msg)
+            | LicenseError::SignatureError(msg)
             | LicenseError::KeyringError(msg) => {
                 ApiError::with_message(ErrorCode::CryptoError, msg)
             }
@@ -366,11 +381,14 @@ impl From<LicenseError> for ApiError {
                     ClientErrorCode::LicenseSuspended => ErrorCode::LicenseSuspended,
                     ClientErrorCode::LicenseBlacklisted => ErrorCode::LicenseBlacklisted,
                     ClientErrorCode::LicenseInactive => ErrorCode::LicenseInactive,
-                    ClientErrorCode::AlreadyBound => ErrorCode::AlreadyBound,
+                    ClientErrorCode::SeatLimitReached => ErrorCode::SeatLimitReached,
                     ClientErrorCode::NotBound => ErrorCode::NotBound,
                     ClientErrorCode::HardwareMismatch => ErrorCode::HardwareMismatch,
+                    ClientErrorCode::LeaseLimitReached => ErrorCode::LeaseLimitReached,
                     ClientErrorCode::FeatureNotIncluded => ErrorCode::FeatureNotIncluded,
                     ClientErrorCode::QuotaExceeded => ErrorCode::QuotaExceeded,
+                    ClientErrorCode::TrialAlreadyUsed => ErrorCode::TrialAlreadyUsed,
+                    ClientErrorCode::TrialsDisabled => ErrorCode::TrialsDisabled,
                     ClientErrorCode::GracePeriodExpired
                     | ClientErrorCode::InternalError
                     | ClientErrorCode::Unknown => ErrorCode::InternalError,
@@ -403,7 +421,14 @@ mod tests {
             ErrorCode::LicenseExpired.status_code(),
             StatusCode::FORBIDDEN
         );
-        assert_eq!(ErrorCode::AlreadyBound.status_code(), StatusCode::CONFLICT);
+        assert_eq!(
+            ErrorCode::SeatLimitReached.status_code(),
+            StatusCode::CONFLICT
+        );
+        assert_eq!(
+            ErrorCode::LeaseLimitReached.status_code(),
+            StatusCode::CONFLICT
+        );
         assert_eq!(
             ErrorCode::DatabaseError.status_code(),
             StatusCode::INTERNAL_SERVER_ERROR
@@ -0,0 +1,712 @@
+//! Persistent audit log for admin actions.
+//!
+//! When `admin.audit_logging` is enabled, every admin API change to a license
+//! and every API token operation is written to the `audit_log` table: who made
+//! it (JWT subject or API token), what changed (the fields that differ before
+//! and after), and where it came from (client IP and request ID).
+//!
+//! Compliance can review the log with `GET /api/v1/audit`, filtered by actor,
+//! action, target and time range.
+//!
+//! # Usage
+//!
+//! ```rust,ignore
+//! use talos::server::audit::{AuditAction, AuditContext, AuditTarget};
+//!
+//! async fn handler(State(state): State<AppState>, audit: AuditContext) {
+//!     // ...change the license...
+//!     audit
+//!         .record(&*state.db, AuditAction::LicenseRevoked, AuditTarget::License(id), before, after)
+//!         .await;
+//! }
+//! ```
+
+use axum::{async_trait, extract::FromRequestParts, http::request::Parts};
+use chrono::{NaiveDateTime, Utc};
+use serde::Serialize;
+use serde_json::{Map, Value};
+use sqlx::FromRow;
+use tracing::warn;
+use uuid::Uuid;
+
+#[cfg(feature = "openapi")]
+use utoipa::ToSchema;
+
+use crate::config::is_audit_logging_enabled;
+use crate::errors::{LicenseError, LicenseResult};
+use crate::server::database::Database;
+use crate::server::ip_whitelist::client_ip;
+use crate::server::logging::RequestId;
+use crate::server::store::{AuditStore, LicenseStore};
+
+#[cfg(feature = "jwt-auth")]
+use crate::server::auth::AuthenticatedUser;
+
+#[cfg(feature = "admin-api")]
+use axum::{
+    extract::{Query, State},
+    Json,
+};
+#[cfg(feature = "admin-api")]
+use serde::Deserialize;
+
+#[cfg(feature = "admin-api")]
+use crate::server::admin::{parse_datetime, AdminError};
+#[cfg(feature = "admin-api")]
+use crate::server::handlers::AppState;
+
+/// Actor recorded when no authenticated user is attached to the request
+/// (auth disabled or not compiled in).
+pub const ANONYMOUS_ACTOR: &str = "anonymous";
+
+/// Actor recorded for token commands run from the server CLI.
+pub const CLI_ACTOR: &str = "cli";
+
+/// Kind of change recorded in the audit log.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AuditAction {
+    /// License was created (single or batch)
+    LicenseCreated,
+    /// License fields were updated
+    LicenseUpdated,
+    /// Every seat on the license was force released
+    LicenseReleased,
+    /// A single device was removed from the license
+    DeviceRemoved,
+    /// License was revoked immediately
+    LicenseRevoked,
+    /// License was suspended with a grace period
+    LicenseSuspended,
+    /// License was reinstated
+    LicenseReinstated,
+    /// License expiration was extended
+    LicenseExtended,
+    /// License usage/quota was updated
+    UsageUpdated,
+    /// License was blacklisted
+    LicenseBlacklisted,
+    /// License was activated for an air-gapped machine
+    OfflineActivated,
+    /// Trial license was converted into a paid license
+    TrialConverted,
+    /// API token was created
+    TokenCreated,
+    /// API token was revoked
+    TokenRevoked,
+    /// Webhook endpoint was registered
+    WebhookCreated,
+    /// Webhook URL, events or active flag changed
+    WebhookUpdated,
+    /// Webhook endpoint was deleted
+    WebhookDeleted,
+    /// Organization was registered
+    OrgCreated,
+    /// Organization name, contact details, metadata or defaults changed
+    OrgUpdated,
+    /// Organization was deleted
+    OrgDeleted,
+    /// Organization and its active licenses were suspended
+    OrgSuspended,
+    /// Organization and the licenses suspended with it were reinstated
+    OrgReinstated,
+}
+
+impl AuditAction {
+    /// Name stored in the `action` column.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AuditAction::LicenseCreated => "license.created",
+            AuditAction::LicenseUpdated => "license.updated",
+            AuditAction::LicenseReleased => "license.released",
+            AuditAction::DeviceRemoved => "license.device_removed",
+            AuditAction::LicenseRevoked => "license.revoked",
+            AuditAction::LicenseSuspended => "license.suspended",
+            AuditAction::LicenseReinstated => "license.reinstated",
+            AuditAction::LicenseExtended => "license.extended",
+            AuditAction::UsageUpdated => "license.usage_updated",
+            AuditAction::LicenseBlacklisted => "license.blacklisted",
+            AuditAction::OfflineActivated => "license.offline_activated",
+            AuditAction::TrialConverted => "license.trial_converted",
+            AuditAction::TokenCreated => "token.created",
+            AuditAction::TokenRevoked => "token.revoked",
+            AuditAction::WebhookCreated => "webhook.created",
+            AuditAction::WebhookUpdated => "webhook.updated",
+            AuditAction::WebhookDeleted => "webhook.deleted",
+            AuditAction::OrgCreated => "org.created",
+            AuditAction::OrgUpdated => "org.updated",
+            AuditAction::OrgDeleted => "org.deleted",
+            AuditAction::OrgSuspended => "org.suspended",
+            AuditAction::OrgReinstated => "org.reinstated",
+        }
+    }
+}
+
+impl std::fmt::Display for AuditAction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Object an audited action was applied to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AuditTarget {
+    /// A license, by license ID
+    License(String),
+    /// An API token, by token ID
+    Token(String),
+    /// A webhook endpoint, by webhook ID
+    Webhook(String),
+    /// An organization, by organization ID
+    Org(String),
+}
+
+impl AuditTarget {
+    /// Name stored in the `target_type` column.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            AuditTarget::License(_) => "license",
+            AuditTarget::Token(_) => "token",
+            AuditTarget::Webhook(_) => "webhook",
+            AuditTarget::Org(_) => "org",
+        }
+    }
+
+    /// ID stored in the `target_id` column.
+    pub fn id(&self) -> &str {
+        match self {
+            AuditTarget::License(id)
+            | AuditTarget::Token(id)
+            | AuditTarget::Webhook(id)
+            | AuditTarget::Org(id) => id,
+        }
+    }
+}
+
+/// A row of the `audit_log` table.
+#[derive(Debug, Clone, Serialize, FromRow)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct AuditEntry {
+    pub id: String,
+    pub occurred_at: NaiveDateTime,
+    /// JWT subject or API token name of the caller
+    pub actor: String,
+    /// API token ID, when the caller authenticated with an API token
+    pub actor_token_id: Option<String>,
+    pub action: String,
+    pub target_type: String,
+    pub target_id: String,
+    /// JSON object with the previous values of the changed fields
+    pub before_state: Option<String>,
+    /// JSON object with the new values of the changed fields
+    pub after_state: Option<String>,
+    pub ip_address: Option<String>,
+    pub request_id: Option<String>,
+}
+
+/// Filters for [`AuditStore::list_audit_entries`]. `None` matches everything.
+#[derive(Debug, Clone, Default)]
+pub struct AuditFilter {
+    pub actor: Option<String>,
+    pub action: Option<String>,
+    pub target_type: Option<String>,
+    pub target_id: Option<String>,
+    /// Only entries at or after this time
+    pub since: Option<NaiveDateTime>,
+    /// Only entries at or before this time
+    pub until: Option<NaiveDateTime>,
+}
+
+/// Who is making a request, captured for the audit log.
+///
+/// Use it as an extractor in admin handlers. It never rejects a request:
+/// without an authenticated user the actor is [`ANONYMOUS_ACTOR`].
+#[derive(Debug, Clone)]
+pub struct AuditContext {
+    pub actor: String,
+    pub actor_token_id: Option<String>,
+    pub ip_address: Option<String>,
+    pub request_id: Option<String>,
+}
+
+impl AuditContext {
+    /// Context for actions that do not come from an HTTP request.
+    pub fn system(actor: &str) -> Self {
+        Self {
+            actor: actor.to_string(),
+            actor_token_id: None,
+            ip_address: None,
+            request_id: None,
+        }
+    }
+
+    /// Record an action in the audit log if audit logging is enabled.
+    ///
+    /// `before` and `after` are snapshots of the target (see [`snapshot`]);
+    /// only the fields that differ are stored. Failures are logged and do
+    /// not affect the caller.
+    pub async fn record(
+        &self,
+        db: &dyn LicenseStore,
+        action: AuditAction,
+        target: AuditTarget,
+        before: Option<Value>,
+        after: Option<Value>,
+    ) {
+        if !is_audit_logging_enabled() {
+            return;
+        }
+
+        let (before_state, after_state) = diff_states(before, after);
+        let entry = AuditEntry {
+            id: Uuid::new_v4().to_string(),
+            occurred_at: Utc::now().naive_utc(),
+            actor: self.actor.clone(),
+            actor_token_id: self.actor_token_id.clone(),
+            action: action.to_string(),
+            target_type: target.kind().to_string(),
+            target_id: target.id().to_string(),
+            before_state: before_state.map(|v| v.to_string()),
+            after_state: after_state.map(|v| v.to_string()),
+            ip_address: self.ip_address.clone(),
+            request_id: self.request_id.clone(),
+        };
+
+        if let Err(e) = db.insert_audit_entry(&entry).await {
+            warn!(
+                action = %action,
+                target_id = %entry.target_id,
+                "Failed to write audit log entry: {e}"
+            );
+        }
+    }
+}
+
+#[async_trait]
+impl<S> FromRequestParts<S> for AuditContext
+where
+    S: Send + Sync,
+{
+    type Rejection = std::convert::Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        #[cfg(feature = "jwt-auth")]
+        let (actor, actor_token_id) = match parts.extensions.get::<AuthenticatedUser>() {
+            Some(user) => (user.subject.clone(), user.api_token_id.clone()),
+            None => (ANONYMOUS_ACTOR.to_string(), None),
+        };
+
+        #[cfg(not(feature = "jwt-auth"))]
+        let (actor, actor_token_id) = (ANONYMOUS_ACTOR.to_string(), None);
+
+        Ok(Self {
+            actor,
+            actor_token_id,
+            ip_address: client_ip(&parts.headers, &parts.extensions).map(|ip| ip.to_string()),
+            request_id: parts.extensions.get::<RequestId>().map(|id| id.0.clone()),
+        })
+    }
+}
+
+/// Serialize a target for use as a `before` or `after` state.
+pub fn snapshot<T: Serialize>(value: &T) -> Option<Value> {
+    serde_json::to_value(value).ok()
+}
+
+/// Reduce two snapshots to the fields that changed.
+///
+/// When both are JSON objects, keys with equal values are dropped from both
+/// sides. Otherwise the snapshots are returned unchanged.
+pub fn diff_states(before: Option<Value>, after: Option<Value>) -> (Option<Value>, Option<Value>) {
+    match (before, after) {
+        (Some(Value::Object(before)), Some(Value::Object(after))) => {
+            let mut changed_before = Map::new();
+            let mut changed_after = Map::new();
+
+            for (key, old) in &before {
+                let new = after.get(key).unwrap_or(&Value::Null);
+                if old != new {
+                    changed_before.insert(key.clone(), old.clone());
+                    changed_after.insert(key.clone(), new.clone());
+                }
+            }
+            for (key, new) in &after {
+                if !before.contains_key(key) && !new.is_null() {
+                    changed_before.insert(key.clone(), Value::Null);
+                    changed_after.insert(key.clone(), new.clone());
+                }
+            }
+
+            (
+                Some(Value::Object(changed_before)),
+                Some(Value::Object(changed_after)),
+            )
+        }
+        other => other,
+    }
+}
+
+const AUDIT_COLUMNS: &str = "id, occurred_at, actor, actor_token_id, action, target_type, \
+     target_id, before_state, after_state, ip_address, request_id";
+
+#[async_trait]
+impl AuditStore for Database {
+    async fn insert_audit_entry(&self, entry: &AuditEntry) -> LicenseResult<()> {
+        match self {
+            #[cfg(feature = "sqlite")]
+            Database::SQLite(pool) => {
+                sqlx::query(&format!(
+                    "INSERT INTO audit_log ({AUDIT_COLUMNS}) \
+                     VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"
+                ))
+                .bind(&entry.id)
+                .bind(entry.occurred_at)
+                .bind(&entry.actor)
+                .bind(&entry.actor_token_id)
+                .bind(&entry.action)
+                .bind(&entry.target_type)
+                .bind(&entry.target_id)
+                .bind(&entry.before_state)
+                .bind(&entry.after_state)
+                .bind(&entry.ip_address)
+                .bind(&entry.request_id)
+                .execute(pool)
+                .await
+                .map_err(|e| {
+                    LicenseError::ServerError(format!("insert audit entry failed: {e}"))
+                })?;
+            }
+            #[cfg(feature = "postgres")]
+            Database::Postgres(pool) => {
+                sqlx::query(&format!(
+                    "INSERT INTO audit_log ({AUDIT_COLUMNS}) \
+                     VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)"
+                ))
+                .bind(&entry.id)
+                .bind(entry.occurred_at)
+                .bind(&entry.actor)
+                .bind(&entry.actor_token_id)
+                .bind(&entry.action)
+                .bind(&entry.target_type)
+                .bind(&entry.target_id)
+                .bind(&entry.before_state)
+                .bind(&entry.after_state)
+                .bind(&entry.ip_address)
+                .bind(&entry.request_id)
+                .execute(pool)
+                .await
+                .map_err(|e| {
+                    LicenseError::ServerError(format!("insert audit entry failed: {e}"))
+                })?;
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn list_audit_entries(
+        &self,
+        filter: &AuditFilter,
+        limit: u32,
+        offset: u32,
+    ) -> LicenseResult<(Vec<AuditEntry>, u64)> {
+        match self {
+            #[cfg(feature = "sqlite")]
+            Database::SQLite(pool) => {
+                const WHERE: &str = "(? IS NULL OR actor = ?) \
+                     AND (? IS NULL OR action = ?) \
+                     AND (? IS NULL OR target_type = ?) \
+                     AND (? IS NULL OR target_id = ?) \
+                     AND (? IS NULL OR occurred_at >= ?) \
+                     AND (? IS NULL OR occurred_at <= ?)";
+
+                let count_sql = format!("SELECT COUNT(*) FROM audit_log WHERE {WHERE}");
+                let total: (i64,) = sqlx::query_as(&count_sql)
+                    .bind(&filter.actor)
+                    .bind(&filter.actor)
+                    .bind(&filter.action)
+                    .bind(&filter.action)
+                    .bind(&filter.target_type)
+                    .bind(&filter.target_type)
+                    .bind(&filter.target_id)
+                    .bind(&filter.target_id)
+                    .bind(filter.since)
+                    .bind(filter.since)
+                    .bind(filter.until)
+                    .bind(filter.until)
+                    .fetch_one(pool)
+                    .await
+                    .map_err(|e| {
+                        LicenseError::ServerError(format!("count audit entries failed: {e}"))
+                    })?;
+
+                let list_sql = format!(
+                    "SELECT {AUDIT_COLUMNS} FROM audit_log WHERE {WHERE} \
+                     ORDER BY occurred_at DESC, id LIMIT ? OFFSET ?"
+                );
+                let entries = sqlx::query_as::<_, AuditEntry>(&list_sql)
+                    .bind(&filter.actor)
+                    .bind(&filter.actor)
+                    .bind(&filter.action)
+                    .bind(&filter.action)
+                    .bind(&filter.target_type)
+                    .bind(&filter.target_type)
+                    .bind(&filter.target_id)
+                    .bind(&filter.target_id)
+                    .bind(filter.since)
+                    .bind(filter.since)
+                    .bind(filter.until)
+                    .bind(filter.until)
+                    .bind(i64::from(limit))
+                    .bind(i64::from(offset))
+                    .fetch_all(pool)
+                    .await
+                    .map_err(|e| {
+                        LicenseError::ServerError(format!("list audit entries failed: {e}"))
+                    })?;
+
+                Ok((entries, total.0 as u64))
+            }
+            #[cfg(feature = "postgres")]
+            Database::Postgres(pool) => {
+                const WHERE: &str = "($1::TEXT IS NULL OR actor = $1) \
+                     AND ($2::TEXT IS NULL OR action = $2) \
+                     AND ($3::TEXT IS NULL OR target_type = $3) \
+                     AND ($4::TEXT IS NULL OR target_id = $4) \
+                     AND ($5::TIMESTAMP IS NULL OR occurred_at >= $5) \
+                     AND ($6::TIMESTAMP IS NULL OR occurred_at <= $6)";
+
+                let count_sql = format!("SELECT COUNT(*) FROM audit_log WHERE {WHERE}");
+                let total: (i64,) = sqlx::query_as(&count_sql)
+                    .bind(&filter.actor)
+                    .bind(&filter.action)
+                    .bind(&filter.target_type)
+                    .bind(&filter.target_id)
+                    .bind(filter.since)
+                    .bind(filter.until)
+                    .fetch_one(pool)
+                    .await
+                    .map_err(|e| {
+                        LicenseError::ServerError(format!("count audit entries failed: {e}"))
+                    })?;
+
+                let list_sql = format!(
+                    "SELECT {AUDIT_COLUMNS} FROM audit_log WHERE {WHERE} \
+                     ORDER BY occurred_at DESC, id LIMIT $7 OFFSET $8"
+                );
+                let entries = sqlx::query_as::<_, AuditEntry>(&list_sql)
+                    .bind(&filter.actor)
+                    .bind(&filter.action)
+                    .bind(&filter.target_type)
+                    .bind(&filter.target_id)
+                    .bind(filter.since)
+                    .bind(filter.until)
+                    .bind(i64::from(limit))
+                    .bind(i64::from(offset))
+                    .fetch_all(pool)
+                    .await
+                    .map_err(|e| {
+                        LicenseError::ServerError(format!("list audit entries failed: {e}"))
+                    })?;
+
+                Ok((entries, total.0 as u64))
+            }
+        }
+    }
+}
+
+// ============================================================================
+// HTTP Handler
+// ============================================================================
+
+/// Maximum page size for `GET /api/v1/audit`.
+#[cfg(feature = "admin-api")]
+pub const MAX_AUDIT_PAGE_SIZE: u32 = 500;
+
+/// Query parameters for listing audit entries.
+#[cfg(feature = "admin-api")]
+#[derive(Debug, Deserialize)]
+pub struct AuditQuery {
+    /// Filter by actor (JWT subject or API token name)
+    pub actor: Option<String>,
+    /// Filter by action, e.g. `license.revoked`
+    pub action: Option<String>,
+    /// Filter by target type (`license`, `token` or `webhook`)
+    pub target_type: Option<String>,
+    /// Filter by target ID
+    pub target_id: Option<String>,
+    /// Only entries at or after this time (ISO 8601)
+    pub since: Option<String>,
+    /// Only entries at or before this time (ISO 8601)
+    pub until: Option<String>,
+    /// Pagination: page number (1-indexed)
+    #[serde(default = "default_page")]
+    pub page: u32,
+    /// Pagination: items per page
+    #[serde(default = "default_per_page")]
+    pub per_page: u32,
+}
+
+#[cfg(feature = "admin-api")]
+fn default_page() -> u32 {
+    1
+}
+#[cfg(feature = "admin-api")]
+fn default_per_page() -> u32 {
+    50
+}
+
+/// An audit entry as returned by the API, with states decoded as JSON.
+#[cfg(feature = "admin-api")]
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct AuditEntryResponse {
+    pub id: String,
+    pub occurred_at: String,
+    pub actor: String,
+    pub actor_token_id: Option<String>,
+    pub action: String,
+    pub target_type: String,
+    pub target_id: String,
+    pub before: Option<Value>,
+    pub after: Option<Value>,
+    pub ip_address: Option<String>,
+    pub request_id: Option<String>,
+}
+
+#[cfg(feature = "admin-api")]
+impl From<AuditEntry> for AuditEntryResponse {
+    fn from(entry: AuditEntry) -> Self {
+        let decode = |state: Option<String>| state.and_then(|s| serde_json::from_str(&s).ok());
+
+        Self {
+            id: entry.id,
+            occurred_at: entry.occurred_at.and_utc().to_rfc3339(),
+            actor: entry.actor,
+            actor_token_id: entry.actor_token_id,
+            action: entry.action,
+            target_type: entry.target_type,
+            target_id: entry.target_id,
+            before: decode(entry.before_state),
+            after: decode(entry.after_state),
+            ip_address: entry.ip_address,
+            request_id: entry.request_id,
+        }
+    }
+}
+
+/// Response for listing audit entries.
+#[cfg(feature = "admin-api")]
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct AuditLogResponse {
+    pub entries: Vec<AuditEntryResponse>,
+    pub total: u64,
+    pub page: u32,
+    pub per_page: u32,
+    pub total_pages: u64,
+}
+
+/// List audit log entries, newest first.
+///
+/// `GET /api/v1/audit?actor=&action=&target_type=&target_id=&since=&until=&page=&per_page=`
+#[cfg(feature = "admin-api")]
+#[cfg_attr(feature = "openapi", utoipa::path(
+    get,
+    path = "/api/v1/audit",
+    tag = "admin",
+    params(
+        ("actor" = Option<String>, Query, description = "Filter by actor"),
+        ("action" = Option<String>, Query, description = "Filter by action, e.g. license.revoked"),
+        ("target_type" = Option<String>, Query, description = "Filter by target type (license, token or webhook)"),
+        ("target_id" = Option<String>, Query, description = "Filter by target ID"),
+        ("since" = Option<String>, Query, description = "Only entries at or after this time"),
+        ("until" = Option<String>, Query, description = "Only entries at or before this time"),
+        ("page" = Option<u32>, Query, description = "Page number (1-indexed)"),
+        ("per_page" = Option<u32>, Query, description = "Items per page (max 500)")
+    ),
+    responses(
+        (status = 200, description = "Audit log entries", body = AuditLogResponse),
+        (status = 400, description = "Invalid filter"),
+        (status = 500, description = "Server error"),
+    ),
+    security(("bearer_auth" = []))
+))]
+pub async fn list_audit_handler(
+    State(state): State<AppState>,
+    Query(query): Query<AuditQuery>,
+) -> Result<Json<AuditLogResponse>, AdminError> {
+    if query.page == 0 {
+        return Err(AdminError::BadRequest(
+            "page must be at least 1".to_string(),
+        ));
+    }
+    if query.per_page == 0 || query.per_page > MAX_AUDIT_PAGE_SIZE {
+        return Err(AdminError::BadRequest(format!(
+            "per_page must be between 1 and {MAX_AUDIT_PAGE_SIZE}"
+        )));
+    }
+
+    let filter = AuditFilter {
+        actor: query.actor,
+        action: query.action,
+        target_type: query.target_type,
+        target_id: query.target_id,
+        since: query.since.as_deref().map(parse_datetime).transpose()?,
+        until: query.until.as_deref().map(parse_datetime).transpose()?,
+    };
+
+    let offset = (query.page - 1).saturating_mul(query.per_page);
+    let (entries, total) = state
+        .db
+        .list_audit_entries(&filter, query.per_page, offset)
+        .await?;
+
+    Ok(Json(AuditLogResponse {
+        entries: entries.into_iter().map(Into::into).collect(),
+        total,
+        page: query.page,
+        per_page: query.per_page,
+        total_pages: total.div_ceil(u64::from(query.per_page)),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn diff_keeps_only_changed_fields() {
+        let before = json!({ "status": "active", "tier": "pro", "revoked_at": null });
+        let after = json!({ "status": "revoked", "tier": "pro", "revoked_at": "2026-01-06" });
+
+        let (before, after) = diff_states(Some(before), Some(after));
+
+        assert_eq!(
+            before,
+            Some(json!({ "status": "active", "revoked_at": null }))
+        );
+        assert_eq!(
+            after,
+            Some(json!({ "status": "revoked", "revoked_at": "2026-01-06" }))
+        );
+    }
+
+    #[test]
+    fn diff_passes_through_one_sided_states() {
+        let created = json!({ "license_id": "lic-1" });
+        let (before, after) = diff_states(None, Some(created.clone()));
+        assert_eq!(before, None);
+        assert_eq!(after, Some(created));
+    }
+
+    #[test]
+    fn action_and_target_names() {
+        assert_eq!(AuditAction::LicenseRevoked.to_string(), "license.revoked");
+        assert_eq!(AuditAction::TokenCreated.as_str(), "token.created");
+
+        let target = AuditTarget::Token("tok-1".to_string());
+        assert_eq!(target.kind(), "token");
+        assert_eq!(target.id(), "tok-1");
+    }
+}
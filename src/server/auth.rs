@@ -1,15 +1,25 @@
 //! JWT authentication middleware for Talos admin API.
 //!
-//! This module provides JWT-based authentication for admin endpoints.
+//! This module provides bearer-token authentication for admin endpoints.
 //! It is only available when the `jwt-auth` feature is enabled.
 //!
+//! Two kinds of bearer token are accepted:
+//! - JWTs signed with the configured secret (see [`JwtValidator`])
+//! - Opaque `talos_...` API tokens stored in the `api_tokens` table
+//!   (see [`crate::server::tokens`]), when a database is attached via
+//!   [`AuthState::with_database`]
+//!
+//! Both produce the same [`AuthenticatedUser`].
+//!
 //! # Usage
 //!
 //! ```rust,ignore
-//! use talos::server::auth::{AuthenticatedUser, JwtLayer};
+//! use talos::server::auth::{scopes, AuthLayer, AuthState, AuthenticatedUser, RequireScopeLayer};
 //!
-//! // Create auth layer for protected routes
-//! let auth_layer = JwtLayer::from_config(&config.auth)?;
+//! // Authenticate every request on the admin router, then require a scope per route
+//! let auth_state = AuthState::from_config(&config.auth)?.with_database(db.clone());
+//! let auth_layer = AuthLayer::new(auth_state);
+//! let route = get(list_handler).layer(RequireScopeLayer::new(scopes::LICENSES_READ));
 //!
 //! // Use in route handler via extractor
 //! async fn admin_handler(user: AuthenticatedUser) -> impl IntoResponse {
@@ -22,7 +32,14 @@
 //! JWT tokens can include scopes to control access:
 //! - `licenses:read` - Read license information
 //! - `licenses:write` - Create and modify licenses
+//! - `licenses:delete` - Revoke and blacklist licenses
 //! - `licenses:*` - Full license access
+//! - `tokens:read` / `tokens:write` / `tokens:delete` - API token management
+//! - `tokens:*` - Full token access
+//!
+//! `AuthLayer` rejects requests without a valid bearer token (401), and
+//! `RequireScopeLayer` rejects authenticated requests that lack the scope
+//! required by a route (403).
 //!
 //! # Configuration
 //!
@@ -33,18 +50,27 @@
 
 use axum::{
     async_trait,
+    body::Body,
     extract::FromRequestParts,
-    http::request::Parts,
+    http::{request::Parts, HeaderMap, Request},
     response::{IntoResponse, Response},
 };
 
 use crate::server::api_error::{ApiError, ErrorCode};
 use jsonwebtoken::{decode, encode, DecodingKey, EncodingKey, Header, TokenData, Validation};
 use serde::{Deserialize, Serialize};
+use std::future::Future;
+use std::pin::Pin;
 use std::sync::Arc;
+use std::task::{Context, Poll};
+use tracing::warn;
 
 use crate::config::AuthConfig;
 use crate::errors::{LicenseError, LicenseResult};
+use crate::server::store::LicenseStore;
+use crate::server::tokens::{ApiToken, API_TOKEN_PREFIX};
+
+pub use crate::server::tokens::scopes;
 
 /// JWT claims structure.
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -89,18 +115,54 @@ impl Claims {
     }
 }
 
-/// Authenticated user information extracted from JWT.
+/// Authenticated user information extracted from a JWT or API token.
 #[derive(Debug, Clone)]
 pub struct AuthenticatedUser {
-    /// The subject from the JWT (user ID or service name)
+    /// The subject from the JWT (user ID or service name), or the API token name
     pub subject: String,
-    /// Scopes from the JWT
+    /// Scopes from the JWT or API token
     pub scopes: Vec<String>,
     /// Full claims for advanced use cases
+    ///
+    /// For API tokens these are synthesized from the token record.
     pub claims: Claims,
+    /// ID of the API token used, if the request was not authenticated by JWT
+    pub api_token_id: Option<String>,
 }
 
 impl AuthenticatedUser {
+    /// Build an authenticated user from validated JWT claims.
+    pub fn from_claims(claims: Claims) -> Self {
+        Self {
+            subject: claims.sub.clone(),
+            scopes: claims.scope.split_whitespace().map(String::from).collect(),
+            claims,
+            api_token_id: None,
+        }
+    }
+
+    /// Build an authenticated user from a validated API token.
+    pub fn from_api_token(token: &ApiToken) -> Self {
+        let claims = Claims {
+            sub: token.name.clone(),
+            iat: token.created_at.and_utc().timestamp().max(0) as u64,
+            exp: token
+                .expires_at
+                .map(|e| e.and_utc().timestamp().max(0) as u64)
+                .unwrap_or(u64::MAX),
+            iss: "talos".to_string(),
+            aud: "talos-api".to_string(),
+            scope: token.scopes.clone(),
+        };
+
+        Self {
+            subject: token.name.clone(),
+            scopes: token.scope_list(),
+            claims,
+            api_token_id: Some(token.id.clone()),
+        }
+    }
+
     /// Check if the user has a specific scope.
     pub fn has_scope(&self, scope: &str) -> bool {
         self.claims.has_scope(scope)
@@ -131,6 +193,8 @@ pub enum AuthError {
     InsufficientScope(String),
     /// Auth is not configured/enabled
     AuthDisabled,
+    /// Token lookup failed due to a server-side error
+    ServerError(String),
 }
 
 impl std::fmt::Display for AuthError {
@@ -144,6 +208,7 @@ impl std::fmt::Display for AuthError {
                 write!(f, "insufficient scope: requires {scope}")
             }
             AuthError::AuthDisabled => write!(f, "authentication is not enabled"),
+            AuthError::ServerError(msg) => write!(f, "authentication failed: {msg}"),
         }
     }
 }
@@ -170,6 +235,7 @@ impl From<AuthError> for ApiError {
                 serde_json::json!({ "required_scope": scope }),
             ),
             AuthError::AuthDisabled => ApiError::new(ErrorCode::AuthDisabled),
+            AuthError::ServerError(_) => ApiError::new(ErrorCode::InternalError),
         }
     }
 }
@@ -268,22 +334,22 @@ pub struct AuthState {
     pub enabled: bool,
     /// JWT validator (None if auth is disabled)
     pub validator: Option<Arc<JwtValidator>>,
+    /// Store used to validate `talos_...` API tokens (None = JWT only)
+    pub db: Option<Arc<dyn LicenseStore>>,
 }
 
 impl AuthState {
     /// Create auth state from configuration.
     pub fn from_config(config: &AuthConfig) -> LicenseResult<Self> {
         if !config.enabled {
-            return Ok(Self {
-                enabled: false,
-                validator: None,
-            });
+            return Ok(Self::disabled());
         }
 
         let validator = JwtValidator::from_config(config)?;
         Ok(Self {
             enabled: true,
             validator: Some(Arc::new(validator)),
+            db: None,
         })
     }
 
@@ -292,14 +358,86 @@ impl AuthState {
         Self {
             enabled: false,
             validator: None,
+            db: None,
         }
     }
+
+    /// Attach a store so `talos_...` API tokens are accepted alongside JWTs.
+    pub fn with_database(mut self, db: Arc<dyn LicenseStore>) -> Self {
+        self.db = Some(db);
+        self
+    }
+
+    /// Authenticate a request from its `Authorization: Bearer <token>` header.
+    ///
+    /// Tokens starting with `talos_` are looked up in the `api_tokens` table;
+    /// anything else is validated as a JWT.
+    pub async fn authenticate(&self, headers: &HeaderMap) -> Result<AuthenticatedUser, AuthError> {
+        if !self.enabled {
+            return Err(AuthError::AuthDisabled);
+        }
+
+        let validator = self.validator.as_ref().ok_or(AuthError::AuthDisabled)?;
+
+        // Extract Authorization header
+        let auth_header = headers
+            .get("Authorization")
+            .ok_or(AuthError::MissingToken)?
+            .to_str()
+            .map_err(|_| AuthError::InvalidHeader)?;
+
+        // Parse Bearer token
+        let token = auth_header
+            .strip_prefix("Bearer ")
+            .ok_or(AuthError::InvalidHeader)?;
+
+        if token.starts_with(API_TOKEN_PREFIX) {
+            return self.authenticate_api_token(token).await;
+        }
+
+        // Validate token
+        let token_data = validator.validate_token(token)?;
+        Ok(AuthenticatedUser::from_claims(token_data.claims))
+    }
+
+    /// Validate an opaque API token against the database.
+    async fn authenticate_api_token(
+        &self,
+        raw_token: &str,
+    ) -> Result<AuthenticatedUser, AuthError> {
+        let db = self.db.as_ref().ok_or_else(|| {
+            AuthError::InvalidToken("API tokens are not accepted by this server".to_string())
+        })?;
+
+        let token = db
+            .validate_api_token(raw_token)
+            .await
+            .map_err(|e| AuthError::ServerError(e.to_string()))?
+            .ok_or_else(|| AuthError::InvalidToken("unknown API token".to_string()))?;
+
+        if token.revoked_at.is_some() {
+            return Err(AuthError::InvalidToken(
+                "API token has been revoked".to_string(),
+            ));
+        }
+        if !token.is_valid() {
+            return Err(AuthError::TokenExpired);
+        }
+
+        let mut user = AuthenticatedUser::from_api_token(&token);
+        if let Some(validator) = &self.validator {
+            user.claims.iss = validator.issuer.clone();
+            user.claims.aud = validator.audience.clone();
+        }
+        Ok(user)
+    }
 }
 
 impl std::fmt::Debug for AuthState {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("AuthState")
             .field("enabled", &self.enabled)
+            .field("api_tokens", &self.db.is_some())
             .finish()
     }
 }
@@ -315,53 +453,30 @@ impl std::fmt::Debug for AuthState {
 ///     format!("Hello, {}!", user.subject)
 /// }
 /// ```
+///
+/// Behind `AuthLayer` the user has already been authenticated and is taken
+/// from the request extensions; otherwise the `AuthState` extension is used
+/// to validate the bearer token directly.
 #[async_trait]
 impl<S> FromRequestParts<S> for AuthenticatedUser
 where
     S: Send + Sync,
-    AuthState: FromRequestParts<S>,
 {
     type Rejection = AuthError;
 
     async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
-        // Get auth state from app state
+        if let Some(user) = parts.extensions.get::<AuthenticatedUser>() {
+            return Ok(user.clone());
+        }
+
+        // Get auth state from request extensions
         let auth_state = parts
             .extensions
             .get::<AuthState>()
             .cloned()
             .ok_or(AuthError::AuthDisabled)?;
 
-        if !auth_state.enabled {
-            return Err(AuthError::AuthDisabled);
-        }
-
-        let validator = auth_state
-            .validator
-            .as_ref()
-            .ok_or(AuthError::AuthDisabled)?;
-
-        // Extract Authorization header
-        let auth_header = parts
-            .headers
-            .get("Authorization")
-            .ok_or(AuthError::MissingToken)?
-            .to_str()
-            .map_err(|_| AuthError::InvalidHeader)?;
-
-        // Parse Bearer token
-        let token = auth_header
-            .strip_prefix("Bearer ")
-            .ok_or(AuthError::InvalidHeader)?;
-
-        // Validate token
-        let token_data = validator.validate_token(token)?;
-        let claims = token_data.claims;
-
-        Ok(AuthenticatedUser {
-            subject: claims.sub.clone(),
-            scopes: claims.scope.split_whitespace().map(String::from).collect(),
-            claims,
-        })
+        auth_state.authenticate(&parts.headers).await
     }
 }
 
@@ -376,7 +491,6 @@ pub struct OptionalUser(pub Option<AuthenticatedUser>);
 impl<S> FromRequestParts<S> for OptionalUser
 where
     S: Send + Sync,
-    AuthState: FromRequestParts<S>,
 {
     type Rejection = std::convert::Infallible;
 
@@ -388,10 +502,13 @@ where
     }
 }
 
-/// Middleware layer that injects `AuthState` into request extensions.
+/// Middleware layer that authenticates every request it wraps.
 ///
-/// This enables the `AuthenticatedUser` extractor to work by making
-/// the auth state available to request handlers.
+/// When auth is enabled, requests without a valid bearer token are rejected
+/// with 401 before reaching the handler. On success the `AuthenticatedUser`
+/// is inserted into the request extensions for `RequireScopeLayer` and the
+/// `AuthenticatedUser` extractor. When auth is disabled in config, requests
+/// pass through untouched.
 #[derive(Clone)]
 pub struct AuthLayer {
     auth_state: AuthState,
@@ -415,34 +532,134 @@ impl<S> tower::Layer<S> for AuthLayer {
     }
 }
 
-/// Middleware service that injects `AuthState` into request extensions.
+/// Middleware service that authenticates requests (see `AuthLayer`).
 #[derive(Clone)]
 pub struct AuthMiddleware<S> {
     inner: S,
     auth_state: AuthState,
 }
 
-impl<S, B> tower::Service<axum::http::Request<B>> for AuthMiddleware<S>
+impl<S> tower::Service<Request<Body>> for AuthMiddleware<S>
 where
-    S: tower::Service<axum::http::Request<B>> + Clone + Send + 'static,
+    S: tower::Service<Request<Body>, Response = Response> + Clone + Send + 'static,
     S::Future: Send,
-    B: Send + 'static,
 {
-    type Response = S::Response;
+    type Response = Response;
     type Error = S::Error;
-    type Future = S::Future;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
 
-    fn poll_ready(
-        &mut self,
-        cx: &mut std::task::Context<'_>,
-    ) -> std::task::Poll<Result<(), Self::Error>> {
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
         self.inner.poll_ready(cx)
     }
 
-    fn call(&mut self, mut req: axum::http::Request<B>) -> Self::Future {
+    fn call(&mut self, mut req: Request<Body>) -> Self::Future {
         // Insert AuthState into request extensions
         req.extensions_mut().insert(self.auth_state.clone());
-        self.inner.call(req)
+
+        // Auth disabled in config: nothing to enforce
+        if !self.auth_state.enabled {
+            return Box::pin(self.inner.call(req));
+        }
+
+        // Take the service that was driven to readiness, leaving a fresh clone behind
+        let clone = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, clone);
+        let auth_state = self.auth_state.clone();
+
+        Box::pin(async move {
+            match auth_state.authenticate(req.headers()).await {
+                Ok(user) => {
+                    req.extensions_mut().insert(user);
+                    inner.call(req).await
+                }
+                Err(err) => {
+                    warn!(
+                        path = %req.uri().path(),
+                        error = %err,
+                        "Admin API access denied: authentication failed"
+                    );
+                    Ok(err.into_response())
+                }
+            }
+        })
+    }
+}
+
+/// Middleware layer that requires the authenticated user to hold a scope.
+///
+/// Must be applied inside an `AuthLayer`. Requests from users lacking the
+/// scope are rejected with 403. When auth is disabled in config, requests
+/// pass through untouched.
+#[derive(Clone)]
+pub struct RequireScopeLayer {
+    scope: &'static str,
+}
+
+impl RequireScopeLayer {
+    /// Create a layer requiring the given scope (see [`scopes`]).
+    pub fn new(scope: &'static str) -> Self {
+        Self { scope }
+    }
+}
+
+impl<S> tower::Layer<S> for RequireScopeLayer {
+    type Service = RequireScopeMiddleware<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RequireScopeMiddleware {
+            inner,
+            scope: self.scope,
+        }
+    }
+}
+
+/// Middleware service that enforces a required scope (see `RequireScopeLayer`).
+#[derive(Clone)]
+pub struct RequireScopeMiddleware<S> {
+    inner: S,
+    scope: &'static str,
+}
+
+impl<S> tower::Service<Request<Body>> for RequireScopeMiddleware<S>
+where
+    S: tower::Service<Request<Body>, Response = Response> + Clone + Send + 'static,
+    S::Future: Send,
+{
+    type Response = Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request<Body>) -> Self::Future {
+        let auth_enabled = req
+            .extensions()
+            .get::<AuthState>()
+            .map(|state| state.enabled)
+            .unwrap_or(false);
+
+        if !auth_enabled {
+            return Box::pin(self.inner.call(req));
+        }
+
+        let result = match req.extensions().get::<AuthenticatedUser>() {
+            Some(user) => user.require_scope(self.scope),
+            None => Err(AuthError::MissingToken),
+        };
+
+        match result {
+            Ok(()) => Box::pin(self.inner.call(req)),
+            Err(err) => {
+                warn!(
+                    path = %req.uri().path(),
+                    required_scope = self.scope,
+                    "Admin API access denied: insufficient scope"
+                );
+                Box::pin(async move { Ok(err.into_response()) })
+            }
+        }
     }
 }
 
@@ -614,11 +831,7 @@ mod tests {
             scope: "licenses:read".to_string(),
         };
 
-        let user = AuthenticatedUser {
-            subject: claims.sub.clone(),
-            scopes: claims.scope.split_whitespace().map(String::from).collect(),
-            claims,
-        };
+        let user = AuthenticatedUser::from_claims(claims);
 
         assert!(user.has_scope("licenses:read"));
         assert!(!user.has_scope("licenses:write"));
@@ -626,6 +839,29 @@ mod tests {
         assert!(user.require_scope("licenses:write").is_err());
     }
 
+    #[test]
+    fn authenticated_user_from_api_token() {
+        let token = ApiToken {
+            id: "token-1".to_string(),
+            name: "ci-service".to_string(),
+            token_hash: "hash".to_string(),
+            scopes: "licenses:* tokens:read".to_string(),
+            created_at: chrono::Utc::now().naive_utc(),
+            expires_at: None,
+            last_used_at: None,
+            revoked_at: None,
+            created_by: None,
+        };
+
+        let user = AuthenticatedUser::from_api_token(&token);
+        assert_eq!(user.subject, "ci-service");
+        assert_eq!(user.scopes, vec!["licenses:*", "tokens:read"]);
+        assert_eq!(user.api_token_id.as_deref(), Some("token-1"));
+        assert_eq!(user.claims.exp, u64::MAX);
+        assert!(user.has_scope("licenses:delete"));
+        assert!(!user.has_scope("tokens:write"));
+    }
+
     #[test]
     fn reject_expired_token() {
         let config = test_config();
@@ -0,0 +1,205 @@
+//! Billing cycles for usage meters.
+//!
+//! A billing cycle decides when the usage meters of a license start over. It
+//! is set per license (`billing_cycle`) or per tier
+//! (`[tiers.<name>] billing_cycle`), and is one of:
+//!
+//! - `monthly` - every month at the day and time the license was issued,
+//!   moved to the last day of shorter months
+//! - a cron expression in UTC with 5 or 6 fields, e.g. `0 0 1 * *` for
+//!   midnight on the first of every month
+//! - `none` - usage never resets on its own, even if the tier has a cycle
+//!
+//! Once a cycle boundary has passed, the usage reset job (`talos::jobs`)
+//! closes the license's usage period: the usage of each meter is kept in
+//! `usage_periods` for reporting and billing, and subtracted from the
+//! meters, so only usage reported after the boundary carries over.
+
+use std::str::FromStr;
+
+use chrono::{DateTime, Datelike, Months, NaiveDateTime, Utc};
+use croner::Cron;
+
+use crate::server::database::License;
+use crate::tiers::get_tier_config;
+
+/// Most cron boundaries walked in one call to [`BillingCycle::period_end`].
+///
+/// Keeps a schedule that fires every second from stalling the reset job;
+/// the remaining boundaries are walked on the job's next run.
+const MAX_CRON_STEPS: usize = 10_000;
+
+/// When the usage meters of a license reset.
+#[derive(Debug, Clone)]
+pub enum BillingCycle {
+    /// Usage never resets on its own
+    None,
+    /// Every month, anchored to the license's issue date
+    Monthly,
+    /// On every match of a cron expression (UTC)
+    Cron(Box<Cron>),
+}
+
+impl FromStr for BillingCycle {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "none" => Ok(BillingCycle::None),
+            "monthly" => Ok(BillingCycle::Monthly),
+            "" => Err("billing cycle cannot be empty".to_string()),
+            expr => Cron::new(expr)
+                .with_seconds_optional()
+                .parse()
+                .map(|cron| BillingCycle::Cron(Box::new(cron)))
+                .map_err(|e| {
+                    format!("billing cycle must be 'monthly', 'none' or a cron expression: {e}")
+                }),
+        }
+    }
+}
+
+impl BillingCycle {
+    /// The latest cycle boundary after `since` and at or before `now`.
+    ///
+    /// `since` is the start of the current period and `anchor` the date
+    /// monthly cycles are anchored to (the license's `issued_at`). Returns
+    /// None while the current period is still running.
+    pub fn period_end(
+        &self,
+        anchor: NaiveDateTime,
+        since: NaiveDateTime,
+        now: NaiveDateTime,
+    ) -> Option<NaiveDateTime> {
+        let end = match self {
+            BillingCycle::None => return None,
+            BillingCycle::Monthly => {
+                let months =
+                    (now.year() - anchor.year()) * 12 + now.month() as i32 - anchor.month() as i32;
+                // Anchoring every boundary to the issue date keeps a cycle
+                // issued on the 31st from drifting to the 28th after February
+                (months - 1..=months)
+                    .rev()
+                    .filter_map(|n| u32::try_from(n).ok())
+                    .filter_map(|n| anchor.checked_add_months(Months::new(n)))
+                    .find(|boundary| *boundary <= now)?
+            }
+            BillingCycle::Cron(cron) => cron
+                .iter_after(DateTime::<Utc>::from_naive_utc_and_offset(since, Utc))
+                .map(|boundary| boundary.naive_utc())
+                .take(MAX_CRON_STEPS)
+                .take_while(|boundary| *boundary <= now)
+                .last()?,
+        };
+
+        (end > since).then_some(end)
+    }
+}
+
+/// The configured billing cycle of a license: its own, else its tier's.
+pub fn configured_billing_cycle(license: &License) -> Option<String> {
+    license.billing_cycle.clone().or_else(|| {
+        license
+            .tier
+            .as_deref()
+            .and_then(get_tier_config)
+            .and_then(|tier| tier.config.billing_cycle)
+    })
+}
+
+/// The billing cycle that applies to a license.
+///
+/// The license's own `billing_cycle` wins over its tier's. Licenses with
+/// neither never reset on their own.
+pub fn license_billing_cycle(license: &License) -> Result<BillingCycle, String> {
+    match configured_billing_cycle(license) {
+        Some(cycle) => cycle.parse(),
+        None => Ok(BillingCycle::None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    fn at(y: i32, m: u32, d: u32, h: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(y, m, d)
+            .unwrap()
+            .and_hms_opt(h, 0, 0)
+            .unwrap()
+    }
+
+    #[test]
+    fn parses_cycles() {
+        assert!(matches!("monthly".parse(), Ok(BillingCycle::Monthly)));
+        assert!(matches!(" none ".parse(), Ok(BillingCycle::None)));
+        assert!(matches!("0 0 1 * *".parse(), Ok(BillingCycle::Cron(_))));
+        assert!(matches!("0 0 0 1 * *".parse(), Ok(BillingCycle::Cron(_))));
+        assert!("".parse::<BillingCycle>().is_err());
+        assert!("weekly-ish".parse::<BillingCycle>().is_err());
+        assert!("0 0 32 * *".parse::<BillingCycle>().is_err());
+    }
+
+    #[test]
+    fn monthly_cycle_is_anchored_to_issue_date() {
+        let issued = at(2026, 1, 15, 10);
+        let cycle = BillingCycle::Monthly;
+
+        // Still in the first period
+        assert_eq!(cycle.period_end(issued, issued, at(2026, 2, 15, 9)), None);
+        assert_eq!(
+            cycle.period_end(issued, issued, at(2026, 2, 15, 10)),
+            Some(at(2026, 2, 15, 10))
+        );
+        // Periods missed while the job was down are closed at the latest boundary
+        assert_eq!(
+            cycle.period_end(issued, issued, at(2026, 5, 1, 0)),
+            Some(at(2026, 4, 15, 10))
+        );
+        // A period that was already closed is not closed again
+        assert_eq!(
+            cycle.period_end(issued, at(2026, 4, 15, 10), at(2026, 5, 1, 0)),
+            None
+        );
+    }
+
+    #[test]
+    fn monthly_cycle_handles_short_months() {
+        let issued = at(2026, 1, 31, 0);
+        let cycle = BillingCycle::Monthly;
+
+        let february = cycle.period_end(issued, issued, at(2026, 3, 1, 0));
+        assert_eq!(february, Some(at(2026, 2, 28, 0)));
+        // March goes back to the 31st
+        assert_eq!(
+            cycle.period_end(issued, february.unwrap(), at(2026, 3, 31, 0)),
+            Some(at(2026, 3, 31, 0))
+        );
+    }
+
+    #[test]
+    fn cron_cycle_uses_latest_match() {
+        let cycle: BillingCycle = "0 0 1 * *".parse().unwrap();
+        let issued = at(2026, 1, 15, 10);
+
+        assert_eq!(cycle.period_end(issued, issued, at(2026, 1, 31, 0)), None);
+        assert_eq!(
+            cycle.period_end(issued, issued, at(2026, 3, 20, 0)),
+            Some(at(2026, 3, 1, 0))
+        );
+        assert_eq!(
+            cycle.period_end(issued, at(2026, 3, 1, 0), at(2026, 3, 20, 0)),
+            None
+        );
+    }
+
+    #[test]
+    fn none_cycle_never_ends() {
+        let issued = at(2020, 1, 1, 0);
+        assert_eq!(
+            BillingCycle::None.period_end(issued, issued, at(2026, 1, 1, 0)),
+            None
+        );
+    }
+}
@@ -10,19 +10,36 @@
 //! 2. **CLI Command**: Run `talos token create --name "Admin" --scopes "*"` to
 //!    create a new token interactively.
 //!
+//! A license signing key can be generated with
+//! `talos_server signing-key generate` (see [`crate::signing`]).
+//!
+//! Schema migrations can be inspected and applied with
+//! `talos_server migrate status|up|down` (see [`crate::server::migrations`]).
+//!
+//! Licenses can be exported and imported as CSV or NDJSON files with
+//! `talos_server licenses export|import` (see [`crate::server::license_io`]).
+//!
 //! # Security
 //!
 //! The bootstrap token should be treated as sensitive. It grants full admin access.
 //! After bootstrapping, consider creating more restricted tokens for specific services.
 
 use std::env;
-use std::sync::Arc;
 
 use chrono::Utc;
 use tracing::{info, warn};
 
-use crate::errors::LicenseResult;
+use base64::engine::general_purpose::STANDARD as B64;
+use base64::Engine;
+
+use crate::errors::{LicenseError, LicenseResult};
+use crate::server::audit::{snapshot, AuditAction, AuditContext, AuditTarget, CLI_ACTOR};
 use crate::server::database::Database;
+use crate::server::license_io::{export_licenses, import_licenses, parse_timestamp, LicenseFormat};
+use crate::server::license_query::{parse_metadata_filter, LicenseFilter, LicenseSort};
+use crate::server::store::LicenseStore;
+use crate::server::tokens::TokenMetadata;
+use crate::signing::LicenseSigner;
 
 /// Environment variable name for bootstrap token.
 pub const BOOTSTRAP_TOKEN_ENV: &str = "TALOS_BOOTSTRAP_TOKEN";
@@ -36,7 +53,7 @@ pub const BOOTSTRAP_TOKEN_ENV: &str = "TALOS_BOOTSTRAP_TOKEN";
 /// - `Ok(Some(token))` - A new token was created, returns the raw token value
 /// - `Ok(None)` - No bootstrap needed (tokens exist or no env var set)
 /// - `Err(e)` - Failed to create the bootstrap token
-pub async fn check_bootstrap_token(db: &Arc<Database>) -> LicenseResult<Option<String>> {
+pub async fn check_bootstrap_token(db: &dyn LicenseStore) -> LicenseResult<Option<String>> {
     // Check if the environment variable is set
     let bootstrap_token = match env::var(BOOTSTRAP_TOKEN_ENV) {
         Ok(token) if !token.is_empty() => token,
@@ -67,6 +84,16 @@ pub async fn check_bootstrap_token(db: &Arc<Database>) -> LicenseResult<Option<S
         )
         .await?;
 
+    AuditContext::system("bootstrap")
+        .record(
+            db,
+            AuditAction::TokenCreated,
+            AuditTarget::Token(token.id.clone()),
+            None,
+            snapshot(&TokenMetadata::from(token.clone())),
+        )
+        .await;
+
     warn!(
         "Bootstrap token created with id={}. Store the raw token securely!",
         token.id
@@ -191,7 +218,10 @@ pub fn parse_token_command(args: &[String]) -> TokenCommand {
 }
 
 /// Execute a token command.
-pub async fn execute_token_command(db: &Database, cmd: TokenCommand) -> LicenseResult<bool> {
+pub async fn execute_token_command(
+    db: &dyn LicenseStore,
+    cmd: TokenCommand,
+) -> LicenseResult<bool> {
     match cmd {
         TokenCommand::Create {
             name,
@@ -209,6 +239,16 @@ pub async fn execute_token_command(db: &Database, cmd: TokenCommand) -> LicenseR
                 .create_api_token(&name, &scope_refs, expires, None)
                 .await?;
 
+            AuditContext::system(CLI_ACTOR)
+                .record(
+                    db,
+                    AuditAction::TokenCreated,
+                    AuditTarget::Token(token.id.clone()),
+                    None,
+                    snapshot(&TokenMetadata::from(token.clone())),
+                )
+                .await;
+
             println!("Token created successfully!");
             println!("───────────────────────────────────────────");
             println!("ID:      {}", token.id);
@@ -253,7 +293,18 @@ pub async fn execute_token_command(db: &Database, cmd: TokenCommand) -> LicenseR
             Ok(true) // Exit after command
         }
         TokenCommand::Revoke { id } => {
+            let before = db.get_api_token(&id).await?.map(TokenMetadata::from);
             if db.revoke_api_token(&id).await? {
+                let after = db.get_api_token(&id).await?.map(TokenMetadata::from);
+                AuditContext::system(CLI_ACTOR)
+                    .record(
+                        db,
+                        AuditAction::TokenRevoked,
+                        AuditTarget::Token(id.clone()),
+                        before.as_ref().and_then(snapshot),
+                        after.as_ref().and_then(snapshot),
+                    )
+                    .await;
                 println!("Token {} revoked successfully.", id);
             } else {
                 println!("Token {} not found or already revoked.", id);
@@ -264,6 +315,361 @@ pub async fn execute_token_command(db: &Database, cmd: TokenCommand) -> LicenseR
     }
 }
 
+/// CLI command for schema migrations.
+#[derive(Debug, PartialEq, Eq)]
+pub enum MigrateCommand {
+    /// List embedded migrations and whether each is applied
+    Status,
+    /// Apply every pending migration
+    Up,
+    /// Revert migrations newer than `target`, or only the latest if `None`
+    Down { target: Option<i64> },
+    /// No command (run server normally)
+    None,
+}
+
+/// Parse CLI arguments for migration commands.
+///
+/// # Supported Commands
+///
+/// ```text
+/// talos migrate status
+/// talos migrate up
+/// talos migrate down             # revert the latest migration
+/// talos migrate down <version>   # revert everything newer than <version>
+/// ```
+pub fn parse_migrate_command(args: &[String]) -> MigrateCommand {
+    if args.len() < 3 || args[1] != "migrate" {
+        return MigrateCommand::None;
+    }
+
+    match args[2].as_str() {
+        "status" => MigrateCommand::Status,
+        "up" => MigrateCommand::Up,
+        "down" => match args.get(3) {
+            None => MigrateCommand::Down { target: None },
+            Some(version) => match version.parse::<i64>() {
+                Ok(target) => MigrateCommand::Down {
+                    target: Some(target),
+                },
+                Err(_) => {
+                    eprintln!("Error: migrate down expects a numeric version, got '{version}'");
+                    MigrateCommand::None
+                }
+            },
+        },
+        _ => MigrateCommand::None,
+    }
+}
+
+/// Execute a migration command.
+///
+/// Returns `Ok(false)` for [`MigrateCommand::None`] so startup continues.
+pub async fn execute_migrate_command(db: &Database, cmd: MigrateCommand) -> LicenseResult<bool> {
+    match cmd {
+        MigrateCommand::Status => {
+            let migrations = db.migration_status().await?;
+
+            println!("Migrations ({}):", db.db_type());
+            println!("───────────────────────────────────────────────────────────");
+            for m in &migrations {
+                let state = if m.checksum_mismatch {
+                    "CHANGED"
+                } else if m.applied {
+                    "APPLIED"
+                } else {
+                    "PENDING"
+                };
+                println!("[{}] {} {}", state, m.version, m.description);
+            }
+
+            let pending = migrations.iter().filter(|m| !m.applied).count();
+            println!("───────────────────────────────────────────────────────────");
+            println!("{} pending", pending);
+
+            Ok(true) // Exit after command
+        }
+        MigrateCommand::Up => {
+            let applied = db.run_migrations().await?;
+
+            if applied.is_empty() {
+                println!("Database schema is up to date.");
+            } else {
+                for m in &applied {
+                    println!("Applied {} {}", m.version, m.description);
+                }
+            }
+
+            Ok(true) // Exit after command
+        }
+        MigrateCommand::Down { target } => {
+            let target = match target {
+                Some(target) => target,
+                None => {
+                    // Roll back to the migration applied before the latest one
+                    let applied: Vec<i64> = db
+                        .migration_status()
+                        .await?
+                        .iter()
+                        .filter(|m| m.applied)
+                        .map(|m| m.version)
+                        .collect();
+                    match applied.len() {
+                        0 => {
+                            println!("No migrations to revert.");
+                            return Ok(true);
+                        }
+                        n if n >= 2 => applied[n - 2],
+                        _ => 0,
+                    }
+                }
+            };
+
+            let reverted = db.revert_migrations(target).await?;
+
+            if reverted.is_empty() {
+                println!("No migrations to revert.");
+            } else {
+                for m in &reverted {
+                    println!("Reverted {} {}", m.version, m.description);
+                }
+            }
+
+            Ok(true) // Exit after command
+        }
+        MigrateCommand::None => Ok(false), // Continue with server startup
+    }
+}
+
+/// CLI command for exporting and importing licenses.
+#[derive(Debug)]
+pub enum LicenseCommand {
+    /// Export licenses matching `filter` to `output`, or to stdout
+    Export {
+        format: LicenseFormat,
+        output: Option<String>,
+        filter: LicenseFilter,
+        sort: LicenseSort,
+    },
+    /// Import licenses from a file, or only validate it with `dry_run`
+    Import {
+        path: String,
+        format: LicenseFormat,
+        dry_run: bool,
+    },
+    /// No command (run server normally)
+    None,
+}
+
+/// Parse CLI arguments for license export and import commands.
+///
+/// Without `--format`, the format follows the file extension (`.ndjson` or
+/// `.jsonl` for NDJSON, anything else CSV).
+///
+/// # Supported Commands
+///
+/// ```text
+/// talos licenses export [--format csv|ndjson] [--output FILE]
+///     [--org-id ID] [--status STATUS] [--tier TIER] [--bound true|false]
+///     [--blacklisted true|false] [--expires-before TIME] [--created-after TIME]
+///     [--created-before TIME] [--key-prefix PREFIX] [--metadata key:value]
+///     [--sort COLUMN] [--order asc|desc]
+/// talos licenses import FILE [--format csv|ndjson] [--dry-run]
+/// ```
+pub fn parse_license_command(args: &[String]) -> LicenseCommand {
+    if args.len() < 3 || args[1] != "licenses" {
+        return LicenseCommand::None;
+    }
+
+    let parsed = match args[2].as_str() {
+        "export" => parse_license_export(&args[3..]),
+        "import" => parse_license_import(&args[3..]),
+        _ => return LicenseCommand::None,
+    };
+
+    parsed.unwrap_or_else(|e| {
+        eprintln!("Error: licenses {}: {e}", args[2]);
+        LicenseCommand::None
+    })
+}
+
+fn parse_license_export(args: &[String]) -> Result<LicenseCommand, String> {
+    let mut format = None;
+    let mut output = None;
+    let mut filter = LicenseFilter::default();
+    let mut sort = LicenseSort::default();
+
+    let mut args = args.iter();
+    while let Some(option) = args.next() {
+        let value = args
+            .next()
+            .ok_or_else(|| format!("{option} requires a value"))?;
+        let time = || parse_timestamp(value).ok_or_else(|| format!("invalid {option}: {value}"));
+        let flag = || {
+            value
+                .parse::<bool>()
+                .map_err(|_| format!("{option} expects true or false, got '{value}'"))
+        };
+
+        match option.as_str() {
+            "--format" | "-f" => format = Some(value.parse()?),
+            "--output" | "-o" => output = Some(value.clone()),
+            "--org-id" => filter.org_id = Some(value.clone()),
+            "--status" => filter.status = Some(value.clone()),
+            "--tier" => filter.tier = Some(value.clone()),
+            "--bound" => filter.bound = Some(flag()?),
+            "--blacklisted" => filter.blacklisted = Some(flag()?),
+            "--expires-before" => filter.expires_before = Some(time()?),
+            "--created-after" => filter.created_after = Some(time()?),
+            "--created-before" => filter.created_before = Some(time()?),
+            "--key-prefix" => filter.key_prefix = Some(value.clone()),
+            "--metadata" => filter.metadata = Some(parse_metadata_filter(value)?),
+            "--sort" => sort.field = value.parse()?,
+            "--order" => sort.order = value.parse()?,
+            _ => return Err(format!("unknown option {option}")),
+        }
+    }
+
+    Ok(LicenseCommand::Export {
+        format: format.unwrap_or_else(|| {
+            output
+                .as_deref()
+                .map(LicenseFormat::from_path)
+                .unwrap_or_default()
+        }),
+        output,
+        filter,
+        sort,
+    })
+}
+
+fn parse_license_import(args: &[String]) -> Result<LicenseCommand, String> {
+    let mut path = None;
+    let mut format = None;
+    let mut dry_run = false;
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--dry-run" => dry_run = true,
+            "--format" | "-f" => {
+                let value = args
+                    .next()
+                    .ok_or_else(|| format!("{arg} requires a value"))?;
+                format = Some(value.parse()?);
+            }
+            _ if arg.starts_with('-') => return Err(format!("unknown option {arg}")),
+            _ if path.is_none() => path = Some(arg.clone()),
+            _ => return Err(format!("unexpected argument {arg}")),
+        }
+    }
+
+    let path = path.ok_or("a file to import is required")?;
+    Ok(LicenseCommand::Import {
+        format: format.unwrap_or_else(|| LicenseFormat::from_path(&path)),
+        path,
+        dry_run,
+    })
+}
+
+/// Execute a license export or import command.
+///
+/// An import with invalid rows prints each of them and fails, whether or
+/// not it was a dry run.
+pub async fn execute_license_command(
+    db: &dyn LicenseStore,
+    cmd: LicenseCommand,
+) -> LicenseResult<bool> {
+    match cmd {
+        LicenseCommand::Export {
+            format,
+            output,
+            filter,
+            sort,
+        } => {
+            let body = export_licenses(db, &filter, sort, format).await?;
+
+            match output {
+                Some(path) => {
+                    std::fs::write(&path, &body)?;
+                    println!("Exported licenses as {format} to {path}");
+                }
+                None => print!("{body}"),
+            }
+
+            Ok(true) // Exit after command
+        }
+        LicenseCommand::Import {
+            path,
+            format,
+            dry_run,
+        } => {
+            let input = std::fs::read_to_string(&path)?;
+            let report = import_licenses(
+                db,
+                &AuditContext::system(CLI_ACTOR),
+                &input,
+                format,
+                dry_run,
+            )
+            .await?;
+
+            for e in &report.errors {
+                match &e.license_id {
+                    Some(id) => println!("line {} ({}): {}", e.row, id, e.error),
+                    None => println!("line {}: {}", e.row, e.error),
+                }
+            }
+            println!(
+                "{} licenses: {} valid, {} invalid",
+                report.total, report.valid, report.failed
+            );
+
+            if !report.errors.is_empty() {
+                return Err(LicenseError::InvalidLicense(format!(
+                    "{path} has invalid rows, nothing was imported"
+                )));
+            }
+            if report.committed {
+                println!("Imported {} licenses from {path}", report.valid);
+            } else if dry_run {
+                println!("Dry run: nothing was imported");
+            } else {
+                println!("No licenses to import");
+            }
+
+            Ok(true) // Exit after command
+        }
+        LicenseCommand::None => Ok(false), // Continue with server startup
+    }
+}
+
+/// Check whether the CLI arguments request a new signing key.
+///
+/// ```text
+/// talos signing-key generate
+/// ```
+pub fn parse_signing_key_command(args: &[String]) -> bool {
+    args.len() >= 3 && args[1] == "signing-key" && args[2] == "generate"
+}
+
+/// Generate a new Ed25519 signing key and print it with its public key.
+pub fn execute_signing_key_command() -> LicenseResult<()> {
+    let pkcs8 = LicenseSigner::generate_pkcs8()?;
+    let signer = LicenseSigner::from_pkcs8(&pkcs8)?;
+
+    println!("Signing key generated successfully!");
+    println!("───────────────────────────────────────────");
+    println!("PRIVATE KEY (set as TALOS_SIGNING_KEY, keep secret):");
+    println!("{}", B64.encode(&pkcs8));
+    println!("───────────────────────────────────────────");
+    println!("PUBLIC KEY (embed in client applications):");
+    println!("{}", signer.public_key_base64());
+    println!("───────────────────────────────────────────");
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -330,6 +736,183 @@ mod tests {
         }
     }
 
+    #[test]
+    fn parse_migrate_commands() {
+        let args = |rest: &[&str]| -> Vec<String> {
+            std::iter::once("talos")
+                .chain(rest.iter().copied())
+                .map(String::from)
+                .collect()
+        };
+
+        assert_eq!(parse_migrate_command(&args(&[])), MigrateCommand::None);
+        assert_eq!(
+            parse_migrate_command(&args(&["migrate"])),
+            MigrateCommand::None
+        );
+        assert_eq!(
+            parse_migrate_command(&args(&["migrate", "status"])),
+            MigrateCommand::Status
+        );
+        assert_eq!(
+            parse_migrate_command(&args(&["migrate", "up"])),
+            MigrateCommand::Up
+        );
+        assert_eq!(
+            parse_migrate_command(&args(&["migrate", "down"])),
+            MigrateCommand::Down { target: None }
+        );
+        assert_eq!(
+            parse_migrate_command(&args(&["migrate", "down", "20260103000000"])),
+            MigrateCommand::Down {
+                target: Some(20260103000000)
+            }
+        );
+        assert_eq!(
+            parse_migrate_command(&args(&["migrate", "down", "latest"])),
+            MigrateCommand::None
+        );
+        assert_eq!(
+            parse_migrate_command(&args(&["token", "list"])),
+            MigrateCommand::None
+        );
+    }
+
+    #[test]
+    fn parse_license_export() {
+        let args = |rest: &[&str]| -> Vec<String> {
+            ["talos", "licenses", "export"]
+                .iter()
+                .chain(rest)
+                .map(|s| s.to_string())
+                .collect()
+        };
+
+        match parse_license_command(&args(&[])) {
+            LicenseCommand::Export { format, output, .. } => {
+                assert_eq!(format, LicenseFormat::Csv);
+                assert_eq!(output, None);
+            }
+            other => panic!("Expected Export command, got {other:?}"),
+        }
+
+        match parse_license_command(&args(&[
+            "--output",
+            "finance.ndjson",
+            "--org-id",
+            "acme",
+            "--bound",
+            "true",
+            "--created-after",
+            "2025-01-01",
+            "--metadata",
+            "region:eu",
+            "--sort",
+            "expires_at",
+            "--order",
+            "desc",
+        ])) {
+            LicenseCommand::Export {
+                format,
+                output,
+                filter,
+                sort,
+            } => {
+                assert_eq!(format, LicenseFormat::Ndjson);
+                assert_eq!(output.as_deref(), Some("finance.ndjson"));
+                assert_eq!(filter.org_id.as_deref(), Some("acme"));
+                assert_eq!(filter.bound, Some(true));
+                assert!(filter.created_after.is_some());
+                assert_eq!(filter.metadata, Some(("region".into(), "eu".into())));
+                assert_eq!(sort.field, crate::server::LicenseSortField::ExpiresAt);
+                assert_eq!(sort.order, crate::server::SortOrder::Desc);
+            }
+            other => panic!("Expected Export command, got {other:?}"),
+        }
+
+        // An explicit format wins over the file extension
+        assert!(matches!(
+            parse_license_command(&args(&["--format", "csv", "--output", "out.ndjson"])),
+            LicenseCommand::Export {
+                format: LicenseFormat::Csv,
+                ..
+            }
+        ));
+
+        for bad in [
+            &["--format", "xml"][..],
+            &["--bound", "yes"],
+            &["--created-after", "last week"],
+            &["--metadata", "no-separator"],
+            &["--status"],
+            &["--colour", "blue"],
+        ] {
+            assert!(
+                matches!(parse_license_command(&args(bad)), LicenseCommand::None),
+                "{bad:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn parse_license_import() {
+        let args = |rest: &[&str]| -> Vec<String> {
+            ["talos", "licenses", "import"]
+                .iter()
+                .chain(rest)
+                .map(|s| s.to_string())
+                .collect()
+        };
+
+        match parse_license_command(&args(&["vendor.jsonl", "--dry-run"])) {
+            LicenseCommand::Import {
+                path,
+                format,
+                dry_run,
+            } => {
+                assert_eq!(path, "vendor.jsonl");
+                assert_eq!(format, LicenseFormat::Ndjson);
+                assert!(dry_run);
+            }
+            other => panic!("Expected Import command, got {other:?}"),
+        }
+
+        assert!(matches!(
+            parse_license_command(&args(&["--format", "csv", "export.txt"])),
+            LicenseCommand::Import {
+                format: LicenseFormat::Csv,
+                dry_run: false,
+                ..
+            }
+        ));
+
+        assert!(matches!(
+            parse_license_command(&args(&[])),
+            LicenseCommand::None
+        ));
+        assert!(matches!(
+            parse_license_command(&args(&["a.csv", "b.csv"])),
+            LicenseCommand::None
+        ));
+        assert!(matches!(
+            parse_license_command(&["talos".to_string(), "licenses".to_string()]),
+            LicenseCommand::None
+        ));
+    }
+
+    #[test]
+    fn parse_signing_key_generate() {
+        let args = vec![
+            "talos".to_string(),
+            "signing-key".to_string(),
+            "generate".to_string(),
+        ];
+        assert!(parse_signing_key_command(&args));
+
+        let args = vec!["talos".to_string(), "signing-key".to_string()];
+        assert!(!parse_signing_key_command(&args));
+    }
+
     #[test]
     fn parse_non_token_command_returns_none() {
         let args = vec!["talos".to_string(), "serve".to_string()];
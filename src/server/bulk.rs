@@ -0,0 +1,739 @@
+//! Bulk license operations.
+//!
+//! Applies one lifecycle action (revoke, reinstate, extend, blacklist,
+//! release or a tier change) to many licenses at once, picked either by ID
+//! or by the same filters the license listing accepts. Each license goes
+//! through the checks of the matching single-license endpoint, and gets the
+//! same binding history, events, webhooks and audit entries.
+//!
+//! In `best_effort` mode every license is written on its own and a failure
+//! does not stop the others. In `transactional` mode all licenses are
+//! written in one transaction, and nothing is written if any of them fails.
+//!
+//! # Endpoints
+//!
+//! - `POST /api/v1/licenses/bulk` - Apply an action to many licenses
+
+use std::collections::HashSet;
+
+use axum::{extract::State, Json};
+use chrono::{NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tracing::info;
+#[cfg(feature = "openapi")]
+use utoipa::ToSchema;
+
+use crate::server::admin::{
+    parse_datetime, parse_metadata_filter, resolve_features, AdminError, AdminReleaseRequest,
+    BlacklistLicenseRequest, ExtendLicenseRequest, ReinstateLicenseRequest, RevokeLicenseRequest,
+};
+use crate::server::api_error::ApiError;
+use crate::server::audit::{snapshot, AuditAction, AuditContext, AuditTarget};
+use crate::server::database::{BindingAction, License, LicenseDevice, PerformedBy};
+use crate::server::handlers::AppState;
+use crate::server::license_query::{LicenseFilter, LicenseSort};
+use crate::server::logging::{log_license_binding_event, log_license_event, LicenseEvent};
+use crate::server::store::LicenseStore;
+use crate::server::tokens::{scopes, CallerScopes};
+use crate::server::webhooks;
+
+/// Most licenses a single bulk request may change, the same as batch creation.
+pub const MAX_BULK_LICENSES: usize = 1000;
+
+// ============================================================================
+// Request/Response Types
+// ============================================================================
+
+/// The action to apply, with the parameters of its single-license endpoint.
+#[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+#[serde(tag = "action", content = "params", rename_all = "snake_case")]
+pub enum BulkAction {
+    /// Revoke, or suspend when `grace_period_days` is set
+    Revoke(RevokeLicenseRequest),
+    /// Reinstate revoked or suspended licenses
+    Reinstate(ReinstateLicenseRequest),
+    /// Move the expiration date
+    Extend(ExtendLicenseRequest),
+    /// Blacklist and free every seat
+    Blacklist(BlacklistLicenseRequest),
+    /// Free every seat
+    Release(AdminReleaseRequest),
+    /// Change the tier, re-deriving features
+    SetTier(SetTierRequest),
+}
+
+impl BulkAction {
+    /// Name of the action, as given in the request.
+    pub fn name(&self) -> &'static str {
+        match self {
+            BulkAction::Revoke(_) => "revoke",
+            BulkAction::Reinstate(_) => "reinstate",
+            BulkAction::Extend(_) => "extend",
+            BulkAction::Blacklist(_) => "blacklist",
+            BulkAction::Release(_) => "release",
+            BulkAction::SetTier(_) => "set_tier",
+        }
+    }
+
+    /// Scope needed beyond `licenses:write`, matching the single-license routes.
+    fn extra_scope(&self) -> Option<&'static str> {
+        match self {
+            BulkAction::Revoke(_) | BulkAction::Blacklist(_) => Some(scopes::LICENSES_DELETE),
+            _ => None,
+        }
+    }
+
+    /// Whether the action frees every seat of the license.
+    fn frees_seats(&self) -> bool {
+        matches!(self, BulkAction::Release(_) | BulkAction::Blacklist(_))
+    }
+}
+
+/// Parameters for the `set_tier` action.
+#[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct SetTierRequest {
+    /// New tier
+    pub tier: String,
+    /// Features merged with the tier's (default: only the tier's features)
+    pub features: Option<Vec<String>>,
+}
+
+/// How failures of individual licenses are handled.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum BulkMode {
+    /// Apply the action to every license it can, reporting the rest
+    #[default]
+    BestEffort,
+    /// Apply the action to every license or to none
+    Transactional,
+}
+
+/// Licenses to act on, using the filters of `GET /api/v1/licenses`.
+#[derive(Debug, Default, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct BulkFilter {
+    /// Filter by organization ID
+    pub org_id: Option<String>,
+    /// Filter by status
+    pub status: Option<String>,
+    /// Filter by tier
+    pub tier: Option<String>,
+    /// Only bound (`true`) or unbound (`false`) licenses
+    pub bound: Option<bool>,
+    /// Only blacklisted (`true`) or non-blacklisted (`false`) licenses
+    pub blacklisted: Option<bool>,
+    /// Only licenses expiring before this time
+    pub expires_before: Option<String>,
+    /// Only licenses issued at or after this time
+    pub created_after: Option<String>,
+    /// Only licenses issued before this time
+    pub created_before: Option<String>,
+    /// Only licenses whose key starts with this prefix (case-insensitive)
+    pub key_prefix: Option<String>,
+    /// Only licenses with this metadata entry, as `key:value`
+    pub metadata: Option<String>,
+}
+
+impl TryFrom<BulkFilter> for LicenseFilter {
+    type Error = AdminError;
+
+    fn try_from(filter: BulkFilter) -> Result<Self, Self::Error> {
+        let datetime = |s: Option<String>| s.as_deref().map(parse_datetime).transpose();
+
+        Ok(LicenseFilter {
+            org_id: filter.org_id,
+            status: filter.status,
+            tier: filter.tier,
+            bound: filter.bound,
+            blacklisted: filter.blacklisted,
+            expires_before: datetime(filter.expires_before)?,
+            created_after: datetime(filter.created_after)?,
+            created_before: datetime(filter.created_before)?,
+            key_prefix: filter.key_prefix,
+            metadata: filter
+                .metadata
+                .as_deref()
+                .map(parse_metadata_filter)
+                .transpose()?,
+        })
+    }
+}
+
+/// Request body for a bulk operation.
+///
+/// Exactly one of `license_ids` and `filter` must be given.
+#[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct BulkLicenseRequest {
+    #[serde(flatten)]
+    pub action: BulkAction,
+    /// Licenses to act on
+    pub license_ids: Option<Vec<String>>,
+    /// Act on every license matching these filters
+    pub filter: Option<BulkFilter>,
+    /// `best_effort` (default) or `transactional`
+    #[serde(default)]
+    pub mode: BulkMode,
+}
+
+/// Outcome for a single license.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum BulkItemStatus {
+    /// The action was applied
+    Ok,
+    /// The action could not be applied to this license
+    Failed,
+    /// Not applied because another license failed in transactional mode
+    Skipped,
+}
+
+/// Result for a single license.
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct BulkItemResult {
+    pub license_id: String,
+    pub status: BulkItemStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Report of a bulk operation, with one result per license in request order.
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct BulkLicenseResponse {
+    pub action: String,
+    pub mode: BulkMode,
+    pub total: usize,
+    pub succeeded: usize,
+    pub failed: usize,
+    pub skipped: usize,
+    pub results: Vec<BulkItemResult>,
+}
+
+/// A license the action can be applied to.
+struct Planned {
+    before: License,
+    after: License,
+    /// Seats held before the action, for actions that free them
+    devices: Vec<LicenseDevice>,
+}
+
+// ============================================================================
+// Handler
+// ============================================================================
+
+/// Apply an action to many licenses.
+///
+/// `POST /api/v1/licenses/bulk`
+///
+/// Licenses are chosen by `license_ids` or by `filter`, up to
+/// [`MAX_BULK_LICENSES`]. Failures of single licenses are reported per item
+/// rather than failing the request; `revoke` and `blacklist` also require
+/// the `licenses:delete` scope.
+#[cfg_attr(feature = "openapi", utoipa::path(
+    post,
+    path = "/api/v1/licenses/bulk",
+    tag = "admin",
+    request_body = BulkLicenseRequest,
+    responses(
+        (status = 200, description = "Per-license results", body = BulkLicenseResponse),
+        (status = 400, description = "Invalid parameters, or too many licenses selected"),
+        (status = 403, description = "Missing licenses:delete for revoke or blacklist"),
+        (status = 404, description = "Filtered organization not found"),
+    ),
+    security(("bearer_auth" = []))
+))]
+pub async fn bulk_license_handler(
+    State(state): State<AppState>,
+    audit: AuditContext,
+    caller: CallerScopes,
+    Json(payload): Json<BulkLicenseRequest>,
+) -> Result<Json<BulkLicenseResponse>, ApiError> {
+    let action = payload.action;
+    info!(
+        "Bulk {} request mode={:?} ids={} filter={}",
+        action.name(),
+        payload.mode,
+        payload.license_ids.as_ref().map_or(0, Vec::len),
+        payload.filter.is_some()
+    );
+
+    if let Some(scope) = action.extra_scope() {
+        caller.require(scope)?;
+    }
+    let new_expires_at = validate_action(&action)?;
+
+    let targets = select_licenses(&*state.db, payload.license_ids, payload.filter).await?;
+
+    // Check every license before writing any of them
+    let now = Utc::now().naive_utc();
+    let mut plans = Vec::with_capacity(targets.len());
+    for (license_id, license) in targets {
+        let plan = match license {
+            Some(license) => plan(&*state.db, &action, new_expires_at, license, now).await,
+            None => Err(format!("License {license_id} not found")),
+        };
+        plans.push((license_id, plan));
+    }
+
+    let mut results = Vec::with_capacity(plans.len());
+    let mut applied = Vec::new();
+
+    match payload.mode {
+        BulkMode::Transactional if plans.iter().any(|(_, plan)| plan.is_err()) => {
+            for (license_id, plan) in plans {
+                results.push(match plan {
+                    Ok(_) => item(license_id, BulkItemStatus::Skipped, None),
+                    Err(e) => item(license_id, BulkItemStatus::Failed, Some(e)),
+                });
+            }
+        }
+        BulkMode::Transactional => {
+            let licenses: Vec<License> = plans
+                .iter()
+                .filter_map(|(_, plan)| plan.as_ref().ok())
+                .map(|planned| planned.after.clone())
+                .collect();
+            let written = state
+                .db
+                .insert_licenses(&licenses, action.frees_seats())
+                .await;
+
+            for (license_id, plan) in plans {
+                let Ok(planned) = plan else { continue };
+                match &written {
+                    Ok(()) => {
+                        results.push(item(license_id, BulkItemStatus::Ok, None));
+                        applied.push(planned);
+                    }
+                    Err(e) => results.push(item(
+                        license_id,
+                        BulkItemStatus::Failed,
+                        Some(e.to_string()),
+                    )),
+                }
+            }
+        }
+        BulkMode::BestEffort => {
+            for (license_id, plan) in plans {
+                let planned = match plan {
+                    Ok(planned) => planned,
+                    Err(e) => {
+                        results.push(item(license_id, BulkItemStatus::Failed, Some(e)));
+                        continue;
+                    }
+                };
+
+                match state
+                    .db
+                    .insert_licenses(std::slice::from_ref(&planned.after), action.frees_seats())
+                    .await
+                {
+                    Ok(()) => {
+                        results.push(item(license_id, BulkItemStatus::Ok, None));
+                        applied.push(planned);
+                    }
+                    Err(e) => results.push(item(
+                        license_id,
+                        BulkItemStatus::Failed,
+                        Some(e.to_string()),
+                    )),
+                }
+            }
+        }
+    }
+
+    for planned in &applied {
+        record_effects(&state, &audit, &action, planned).await;
+    }
+
+    let count = |status| results.iter().filter(|r| r.status == status).count();
+    let response = BulkLicenseResponse {
+        action: action.name().to_string(),
+        mode: payload.mode,
+        total: results.len(),
+        succeeded: count(BulkItemStatus::Ok),
+        failed: count(BulkItemStatus::Failed),
+        skipped: count(BulkItemStatus::Skipped),
+        results,
+    };
+
+    info!(
+        "Bulk {} finished: {} succeeded, {} failed, {} skipped",
+        response.action, response.succeeded, response.failed, response.skipped
+    );
+
+    Ok(Json(response))
+}
+
+// ============================================================================
+// Helper Functions
+// ============================================================================
+
+fn item(license_id: String, status: BulkItemStatus, error: Option<String>) -> BulkItemResult {
+    BulkItemResult {
+        license_id,
+        status,
+        error,
+    }
+}
+
+/// Check the action's parameters, returning the parsed expiration date for
+/// actions that set one.
+fn validate_action(action: &BulkAction) -> Result<Option<NaiveDateTime>, AdminError> {
+    match action {
+        BulkAction::Extend(p) => parse_datetime(&p.new_expires_at).map(Some),
+        BulkAction::Reinstate(p) => p.new_expires_at.as_deref().map(parse_datetime).transpose(),
+        BulkAction::Blacklist(p) if p.reason.trim().is_empty() => Err(AdminError::BadRequest(
+            "reason is required for blacklisting".to_string(),
+        )),
+        BulkAction::SetTier(p) if p.tier.trim().is_empty() => {
+            Err(AdminError::BadRequest("tier must not be empty".to_string()))
+        }
+        _ => Ok(None),
+    }
+}
+
+/// Resolve the request's licenses, in request order for explicit IDs.
+///
+/// Unknown IDs are kept with `None` so they can be reported per item.
+async fn select_licenses(
+    db: &dyn LicenseStore,
+    license_ids: Option<Vec<String>>,
+    filter: Option<BulkFilter>,
+) -> Result<Vec<(String, Option<License>)>, AdminError> {
+    match (license_ids, filter) {
+        (Some(ids), None) => {
+            let mut seen = HashSet::new();
+            let ids: Vec<String> = ids
+                .into_iter()
+                .filter(|id| seen.insert(id.clone()))
+                .collect();
+            if ids.is_empty() {
+                return Err(AdminError::BadRequest(
+                    "license_ids must not be empty".to_string(),
+                ));
+            }
+            if ids.len() > MAX_BULK_LICENSES {
+                return Err(AdminError::BadRequest(format!(
+                    "license_ids must not list more than {MAX_BULK_LICENSES} licenses"
+                )));
+            }
+
+            let mut targets = Vec::with_capacity(ids.len());
+            for id in ids {
+                let license = db.get_license(&id).await?;
+                targets.push((id, license));
+            }
+            Ok(targets)
+        }
+        (None, Some(filter)) => {
+            let filter = LicenseFilter::try_from(filter)?;
+            if is_unfiltered(&filter) {
+                return Err(AdminError::BadRequest(
+                    "filter must set at least one condition".to_string(),
+                ));
+            }
+            if let Some(org_id) = &filter.org_id {
+                if db.get_org(org_id).await?.is_none() {
+                    return Err(AdminError::NotFound(format!(
+                        "organization not found: {org_id}"
+                    )));
+                }
+            }
+
+            let limit = MAX_BULK_LICENSES as u32;
+            let (licenses, total) = db
+                .list_licenses(&filter, LicenseSort::default(), None, limit, 0)
+                .await?;
+            if total > u64::from(limit) {
+                return Err(AdminError::BadRequest(format!(
+                    "filter matches {total} licenses; at most {MAX_BULK_LICENSES} can be changed at once"
+                )));
+            }
+
+            Ok(licenses
+                .into_iter()
+                .map(|license| (license.license_id.clone(), Some(license)))
+                .collect())
+        }
+        _ => Err(AdminError::BadRequest(
+            "exactly one of license_ids and filter is required".to_string(),
+        )),
+    }
+}
+
+fn is_unfiltered(filter: &LicenseFilter) -> bool {
+    filter.org_id.is_none()
+        && filter.status.is_none()
+        && filter.tier.is_none()
+        && filter.bound.is_none()
+        && filter.blacklisted.is_none()
+        && filter.expires_before.is_none()
+        && filter.created_after.is_none()
+        && filter.created_before.is_none()
+        && filter.key_prefix.is_none()
+        && filter.metadata.is_none()
+}
+
+/// Check the action against one license and work out its new state, with
+/// the same rules as the single-license endpoint.
+async fn plan(
+    db: &dyn LicenseStore,
+    action: &BulkAction,
+    new_expires_at: Option<NaiveDateTime>,
+    license: License,
+    now: NaiveDateTime,
+) -> Result<Planned, String> {
+    let mut after = license.clone();
+
+    match action {
+        BulkAction::Revoke(p) => {
+            if license.status == "revoked" {
+                return Err("License is already revoked".to_string());
+            }
+            after.revoke_reason = p.reason.clone();
+            after.suspended_by_org = None;
+            if p.grace_period_days == 0 {
+                after.status = "revoked".to_string();
+                after.revoked_at = Some(now);
+                after.suspended_at = None;
+                after.grace_period_ends_at = None;
+                after.suspension_message = None;
+            } else {
+                after.status = "suspended".to_string();
+                after.suspended_at = Some(now);
+                after.grace_period_ends_at =
+                    Some(now + chrono::Duration::days(p.grace_period_days as i64));
+                after.suspension_message = p.message.clone();
+            }
+        }
+        BulkAction::Reinstate(_) => {
+            if license.is_blacklisted == Some(true) {
+                return Err(
+                    "Cannot reinstate a blacklisted license. Remove from blacklist first."
+                        .to_string(),
+                );
+            }
+            if license.status == "active" {
+                return Err("License is already active".to_string());
+            }
+            if let Some(org_id) = &license.org_id {
+                if db
+                    .get_org(org_id)
+                    .await
+                    .map_err(|e| e.to_string())?
+                    .is_some_and(|org| org.is_suspended())
+                {
+                    return Err(format!("organization {org_id} is suspended"));
+                }
+            }
+            after.status = "active".to_string();
+            after.suspended_by_org = None;
+            after.suspended_at = None;
+            after.revoked_at = None;
+            after.revoke_reason = None;
+            after.grace_period_ends_at = None;
+            after.suspension_message = None;
+            if new_expires_at.is_some() {
+                after.expires_at = new_expires_at;
+            }
+        }
+        BulkAction::Extend(_) => {
+            after.expires_at = new_expires_at;
+        }
+        BulkAction::Blacklist(p) => {
+            if license.is_blacklisted == Some(true) {
+                return Err("License is already blacklisted".to_string());
+            }
+            after.is_blacklisted = Some(true);
+            after.blacklisted_at = Some(now);
+            after.blacklist_reason = Some(p.reason.clone());
+            after.status = "revoked".to_string();
+            after.revoked_at = Some(now);
+            after.revoke_reason = Some(format!("Blacklisted: {}", p.reason));
+            if let Some(msg) = &p.message {
+                after.suspension_message = Some(msg.clone());
+            }
+        }
+        BulkAction::Release(_) => {
+            if !license.is_bound() {
+                return Err("License is not currently bound".to_string());
+            }
+        }
+        BulkAction::SetTier(p) => {
+            let features = resolve_features(Some(&p.tier), p.features.as_deref().unwrap_or(&[]));
+            after.tier = Some(p.tier.clone());
+            after.features = serde_json::to_string(&features).ok();
+        }
+    }
+
+    let devices = if action.frees_seats() {
+        after.hardware_id = None;
+        after.device_name = None;
+        after.device_info = None;
+        after.bound_at = None;
+        db.list_license_devices(&license.license_id)
+            .await
+            .map_err(|e| e.to_string())?
+    } else {
+        Vec::new()
+    };
+
+    Ok(Planned {
+        before: license,
+        after,
+        devices,
+    })
+}
+
+/// Record binding history, events, webhooks and the audit entry for a
+/// license the action was applied to.
+async fn record_effects(
+    state: &AppState,
+    audit: &AuditContext,
+    action: &BulkAction,
+    planned: &Planned,
+) {
+    let db = &*state.db;
+    let license_id = planned.after.license_id.as_str();
+    let mut audit_before = snapshot(&planned.before);
+    let mut audit_after = snapshot(&planned.after);
+
+    let audit_action = match action {
+        BulkAction::Revoke(p) if planned.after.status == "revoked" => {
+            log_license_event(LicenseEvent::Revoked, license_id, p.reason.as_deref());
+            webhooks::notify(
+                db,
+                LicenseEvent::Revoked,
+                license_id,
+                Some(serde_json::json!({ "reason": p.reason })),
+            )
+            .await;
+            AuditAction::LicenseRevoked
+        }
+        BulkAction::Revoke(p) => {
+            let grace_end = planned.after.grace_period_ends_at.unwrap_or_default();
+            log_license_event(
+                LicenseEvent::Suspended,
+                license_id,
+                Some(&format!("grace period until {}", grace_end)),
+            );
+            webhooks::notify(
+                db,
+                LicenseEvent::Suspended,
+                license_id,
+                Some(serde_json::json!({
+                    "reason": p.reason,
+                    "grace_period_ends_at": grace_end.and_utc().to_rfc3339(),
+                })),
+            )
+            .await;
+            AuditAction::LicenseSuspended
+        }
+        BulkAction::Reinstate(p) => {
+            log_license_event(LicenseEvent::Reinstated, license_id, p.reason.as_deref());
+            webhooks::notify(
+                db,
+                LicenseEvent::Reinstated,
+                license_id,
+                Some(serde_json::json!({ "reason": p.reason })),
+            )
+            .await;
+            AuditAction::LicenseReinstated
+        }
+        BulkAction::Extend(_) => {
+            if let Some(expires_at) = planned.after.expires_at {
+                log_license_event(
+                    LicenseEvent::Extended,
+                    license_id,
+                    Some(&format!("extended to {}", expires_at)),
+                );
+            }
+            AuditAction::LicenseExtended
+        }
+        BulkAction::Blacklist(p) => {
+            let reason = format!("Blacklisted: {}", p.reason);
+            record_releases(db, license_id, &planned.devices, Some(&reason)).await;
+            log_license_event(LicenseEvent::Blacklisted, license_id, Some(&p.reason));
+            webhooks::notify(
+                db,
+                LicenseEvent::Blacklisted,
+                license_id,
+                Some(serde_json::json!({ "reason": p.reason })),
+            )
+            .await;
+            AuditAction::LicenseBlacklisted
+        }
+        BulkAction::Release(p) => {
+            record_releases(db, license_id, &planned.devices, p.reason.as_deref()).await;
+            for device in &planned.devices {
+                log_license_binding_event(
+                    LicenseEvent::Released,
+                    license_id,
+                    &device.hardware_id,
+                    device.device_name.as_deref(),
+                );
+                webhooks::notify(
+                    db,
+                    LicenseEvent::Released,
+                    license_id,
+                    Some(serde_json::json!({
+                        "hardware_id": device.hardware_id,
+                        "device_name": device.device_name,
+                        "reason": p.reason,
+                    })),
+                )
+                .await;
+            }
+
+            let hardware_ids: Vec<&str> = planned
+                .devices
+                .iter()
+                .map(|d| d.hardware_id.as_str())
+                .collect();
+            audit_before = Some(serde_json::json!({ "devices": hardware_ids }));
+            audit_after = Some(serde_json::json!({ "devices": [] }));
+            AuditAction::LicenseReleased
+        }
+        BulkAction::SetTier(_) => AuditAction::LicenseUpdated,
+    };
+
+    audit
+        .record(
+            db,
+            audit_action,
+            AuditTarget::License(license_id.to_string()),
+            audit_before,
+            audit_after,
+        )
+        .await;
+}
+
+/// Record an admin release in binding history for each freed seat.
+async fn record_releases(
+    db: &dyn LicenseStore,
+    license_id: &str,
+    devices: &[LicenseDevice],
+    reason: Option<&str>,
+) {
+    for device in devices {
+        let _ = db
+            .record_binding_history(
+                license_id,
+                BindingAction::AdminRelease,
+                Some(&device.hardware_id),
+                device.device_name.as_deref(),
+                device.device_info.as_deref(),
+                PerformedBy::Admin,
+                reason,
+            )
+            .await;
+    }
+}
@@ -11,6 +11,8 @@
 //! - `POST /api/v1/client/validate-or-bind` - Validate or auto-bind a license
 //! - `POST /api/v1/client/heartbeat` - Send heartbeat ping
 //! - `POST /api/v1/client/validate-feature` - Validate a specific feature
+//! - `POST /api/v1/client/checkout` - Check out a floating lease
+//! - `POST /api/v1/client/checkin` - Return a floating lease to the pool
 
 use axum::{
     extract::State,
@@ -18,17 +20,23 @@ use axum::{
     response::{IntoResponse, Response},
     Json,
 };
-use chrono::Utc;
+use chrono::{Duration, Utc};
 use serde::{Deserialize, Serialize};
 use tracing::{info, warn};
 
 #[cfg(feature = "openapi")]
 use utoipa::ToSchema;
 
+use crate::config::{get_heartbeat_interval, get_lease_ttl_secs, get_offline_days};
 use crate::server::api_error::{ApiError, ErrorCode};
-use crate::server::database::{BindingAction, PerformedBy};
+use crate::server::database::{
+    BindingAction, LeaseCheckout, License, LicenseDevice, PerformedBy, SeatClaim,
+};
 use crate::server::handlers::AppState;
 use crate::server::logging::{log_license_binding_event, log_license_event, LicenseEvent};
+use crate::server::usage::{license_tier, meter_statuses};
+use crate::server::webhooks;
+use crate::signing::{SignedLicense, SignedLicensePayload, PAYLOAD_VERSION};
 use crate::tiers::get_tier_config;
 
 /// Error codes for client API responses.
@@ -38,12 +46,14 @@ use crate::tiers::get_tier_config;
 pub enum ClientErrorCode {
     /// License key not found
     LicenseNotFound,
-    /// License is already bound to different hardware
-    AlreadyBound,
+    /// Every seat on the license is held by another device
+    SeatLimitReached,
     /// License is not bound (for release operations)
     NotBound,
-    /// Hardware ID doesn't match the bound hardware
+    /// Device does not hold a seat on the license
     HardwareMismatch,
+    /// Every floating lease on the license is checked out
+    LeaseLimitReached,
     /// License is expired
     LicenseExpired,
     /// License is revoked
@@ -58,6 +68,10 @@ pub enum ClientErrorCode {
     FeatureNotIncluded,
     /// Feature restricted due to quota exceeded
     QuotaExceeded,
+    /// The machine has already had a trial
+    TrialAlreadyUsed,
+    /// The server does not issue trial licenses
+    TrialsDisabled,
     /// Invalid request format
     InvalidRequest,
     /// Internal server error
@@ -71,8 +85,40 @@ pub struct ClientError {
     pub success: bool,
     pub error: ClientErrorCode,
     pub message: String,
+    /// Seat usage, reported with `SEAT_LIMIT_REACHED`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub seats: Option<SeatUsage>,
+}
+
+/// Seat usage of a license whose seats are all taken.
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct SeatUsage {
+    /// Number of devices the license allows at the same time
+    pub max_devices: i32,
+    /// Devices currently holding a seat
+    pub devices: Vec<BoundDevice>,
+}
+
+/// A device holding a seat, as shown to clients.
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct BoundDevice {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub device_name: Option<String>,
+    pub bound_at: String,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub bound_device: Option<String>,
+    pub last_seen_at: Option<String>,
+}
+
+impl From<&LicenseDevice> for BoundDevice {
+    fn from(device: &LicenseDevice) -> Self {
+        Self {
+            device_name: device.device_name.clone(),
+            bound_at: device.bound_at.and_utc().to_rfc3339(),
+            last_seen_at: device.last_seen_at.map(|d| d.and_utc().to_rfc3339()),
+        }
+    }
 }
 
 impl ClientError {
@@ -81,21 +127,30 @@ impl ClientError {
             success: false,
             error: code,
             message: message.into(),
-            bound_device: None,
+            seats: None,
         }
     }
 
-    pub fn with_bound_device(mut self, device: Option<String>) -> Self {
-        self.bound_device = device;
-        self
+    /// Error for a device that cannot get a seat because all are taken.
+    pub fn seat_limit_reached(max_devices: i32, devices: &[LicenseDevice]) -> Self {
+        let mut err = Self::new(
+            ClientErrorCode::SeatLimitReached,
+            format!("All {max_devices} seat(s) on this license are in use"),
+        );
+        err.seats = Some(SeatUsage {
+            max_devices,
+            devices: devices.iter().map(BoundDevice::from).collect(),
+        });
+        err
     }
 
     pub fn status_code(&self) -> StatusCode {
         match self.error {
             ClientErrorCode::LicenseNotFound => StatusCode::NOT_FOUND,
-            ClientErrorCode::AlreadyBound => StatusCode::CONFLICT,
+            ClientErrorCode::SeatLimitReached => StatusCode::CONFLICT,
             ClientErrorCode::NotBound => StatusCode::CONFLICT,
             ClientErrorCode::HardwareMismatch => StatusCode::FORBIDDEN,
+            ClientErrorCode::LeaseLimitReached => StatusCode::CONFLICT,
             ClientErrorCode::LicenseExpired => StatusCode::FORBIDDEN,
             ClientErrorCode::LicenseRevoked => StatusCode::FORBIDDEN,
             ClientErrorCode::LicenseSuspended => StatusCode::FORBIDDEN,
@@ -103,6 +158,8 @@ impl ClientError {
             ClientErrorCode::LicenseInactive => StatusCode::FORBIDDEN,
             ClientErrorCode::FeatureNotIncluded => StatusCode::FORBIDDEN,
             ClientErrorCode::QuotaExceeded => StatusCode::FORBIDDEN,
+            ClientErrorCode::TrialAlreadyUsed => StatusCode::CONFLICT,
+            ClientErrorCode::TrialsDisabled => StatusCode::FORBIDDEN,
             ClientErrorCode::InvalidRequest => StatusCode::BAD_REQUEST,
             ClientErrorCode::InternalError => StatusCode::INTERNAL_SERVER_ERROR,
         }
@@ -120,9 +177,10 @@ impl From<ClientErrorCode> for ErrorCode {
     fn from(code: ClientErrorCode) -> Self {
         match code {
             ClientErrorCode::LicenseNotFound => ErrorCode::LicenseNotFound,
-            ClientErrorCode::AlreadyBound => ErrorCode::AlreadyBound,
+            ClientErrorCode::SeatLimitReached => ErrorCode::SeatLimitReached,
             ClientErrorCode::NotBound => ErrorCode::NotBound,
             ClientErrorCode::HardwareMismatch => ErrorCode::HardwareMismatch,
+            ClientErrorCode::LeaseLimitReached => ErrorCode::LeaseLimitReached,
             ClientErrorCode::LicenseExpired => ErrorCode::LicenseExpired,
             ClientErrorCode::LicenseRevoked => ErrorCode::LicenseRevoked,
             ClientErrorCode::LicenseSuspended => ErrorCode::LicenseSuspended,
@@ -130,6 +188,8 @@ impl From<ClientErrorCode> for ErrorCode {
             ClientErrorCode::LicenseInactive => ErrorCode::LicenseInactive,
             ClientErrorCode::FeatureNotIncluded => ErrorCode::FeatureNotIncluded,
             ClientErrorCode::QuotaExceeded => ErrorCode::QuotaExceeded,
+            ClientErrorCode::TrialAlreadyUsed => ErrorCode::TrialAlreadyUsed,
+            ClientErrorCode::TrialsDisabled => ErrorCode::TrialsDisabled,
             ClientErrorCode::InvalidRequest => ErrorCode::InvalidRequest,
             ClientErrorCode::InternalError => ErrorCode::InternalError,
         }
@@ -139,11 +199,11 @@ impl From<ClientErrorCode> for ErrorCode {
 impl From<ClientError> for ApiError {
     fn from(err: ClientError) -> Self {
         let code: ErrorCode = err.error.into();
-        if let Some(device) = err.bound_device {
+        if let Some(seats) = err.seats {
             ApiError::with_details(
                 code,
                 err.message,
-                serde_json::json!({ "bound_device": device }),
+                serde_json::to_value(seats).unwrap_or_default(),
             )
         } else {
             ApiError::with_message(code, err.message)
@@ -182,6 +242,9 @@ pub struct BindResponse {
     pub tier: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub expires_at: Option<String>,
+    /// Signed license document for offline verification (if signing is configured)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub signed_license: Option<SignedLicense>,
 }
 
 /// Request to release a license from hardware.
@@ -227,6 +290,9 @@ pub struct ValidateResponse {
     pub expires_at: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub grace_period_ends_at: Option<String>,
+    /// Until when the client may keep using the license without reaching the server
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub offline_valid_until: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub warning: Option<String>,
     /// Organization ID (for multi-seat licenses). Falls back to license_id if not set.
@@ -241,6 +307,11 @@ pub struct ValidateResponse {
     /// Bandwidth limit for this license (bytes). None means unlimited.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub bandwidth_limit_bytes: Option<i64>,
+    /// Signed license document for offline verification (if signing is configured)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub signed_license: Option<SignedLicense>,
+    /// How often the client should send heartbeats (seconds)
+    pub heartbeat_interval_secs: u64,
 }
 
 /// Request for validate-or-bind operation.
@@ -275,6 +346,60 @@ pub struct ClientHeartbeatRequest {
 pub struct ClientHeartbeatResponse {
     pub success: bool,
     pub server_time: String,
+    /// New expiry of the device's floating lease, if it holds an active one
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lease_expires_at: Option<String>,
+    /// Until when the client may keep using the license without reaching the server
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub offline_valid_until: Option<String>,
+    /// How often the client should send heartbeats (seconds)
+    pub heartbeat_interval_secs: u64,
+}
+
+/// Request to check out a floating lease.
+#[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct CheckoutRequest {
+    /// The human-readable license key
+    pub license_key: String,
+    /// Hardware fingerprint
+    pub hardware_id: String,
+    /// Optional device name (used if binding)
+    #[serde(default)]
+    pub device_name: Option<String>,
+    /// Optional device info (used if binding)
+    #[serde(default)]
+    pub device_info: Option<String>,
+}
+
+/// Response from a successful checkout.
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct CheckoutResponse {
+    pub success: bool,
+    pub license_id: String,
+    /// When the lease returns to the pool unless renewed by a heartbeat (RFC 3339)
+    pub lease_expires_at: String,
+    /// Seconds each heartbeat extends the lease by
+    pub lease_ttl_secs: u64,
+}
+
+/// Request to check a floating lease back in.
+#[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct CheckinRequest {
+    /// The human-readable license key
+    pub license_key: String,
+    /// Hardware fingerprint holding the lease
+    pub hardware_id: String,
+}
+
+/// Response from a checkin.
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct CheckinResponse {
+    pub success: bool,
+    pub message: String,
 }
 
 /// Request to validate a specific feature.
@@ -311,9 +436,9 @@ pub struct ValidateFeatureResponse {
 ///
 /// # Behavior
 /// - Checks if license exists and is valid
-/// - If unbound, binds to the provided hardware
-/// - If already bound to same hardware, returns success
-/// - If bound to different hardware, returns ALREADY_BOUND error
+/// - If the hardware already holds a seat, returns success
+/// - If a seat is free (fewer than `max_devices` bound), binds the hardware
+/// - If every seat is taken, returns SEAT_LIMIT_REACHED listing the bound devices
 #[cfg_attr(feature = "openapi", utoipa::path(
     post,
     path = "/api/v1/client/bind",
@@ -323,7 +448,7 @@ pub struct ValidateFeatureResponse {
         (status = 200, description = "License bound successfully", body = BindResponse),
         (status = 400, description = "Invalid request", body = ClientError),
         (status = 404, description = "License not found", body = ClientError),
-        (status = 409, description = "License already bound to different device", body = ClientError),
+        (status = 409, description = "All seats are taken by other devices", body = ClientError),
     )
 ))]
 pub async fn bind_handler(
@@ -378,43 +503,51 @@ pub async fn bind_handler(
         ));
     }
 
-    // Check if already bound
-    if license.is_bound() {
-        if license.hardware_id.as_deref() == Some(&req.hardware_id) {
+    // Claim a seat for this hardware
+    let claim = state
+        .db
+        .claim_license_seat(
+            &license,
+            &req.hardware_id,
+            req.device_name.as_deref(),
+            req.device_info.as_deref(),
+        )
+        .await
+        .map_err(|e| {
+            warn!("Failed to bind license: {}", e);
+            ClientError::new(ClientErrorCode::InternalError, "Failed to bind license")
+        })?;
+
+    match claim {
+        SeatClaim::Existing(_) => {
             // Already bound to this hardware - return success
             info!("License {} already bound to this hardware", req.license_key);
+            let signed_license = issue_signed_license(
+                &state,
+                &license,
+                &req.hardware_id,
+                None,
+                offline_valid_until(&license),
+            )
+            .await;
             return Ok(Json(BindResponse {
                 success: true,
                 license_id: license.license_id,
                 features: parse_features(&license.features),
                 tier: license.tier,
                 expires_at: license.expires_at.map(|d| d.to_string()),
+                signed_license,
             }));
-        } else {
-            // Bound to different hardware
-            return Err(ClientError::new(
-                ClientErrorCode::AlreadyBound,
-                "License is already bound to a different device",
-            )
-            .with_bound_device(license.device_name));
         }
+        SeatClaim::Full(devices) => {
+            return Err(ClientError::seat_limit_reached(
+                license.max_devices,
+                &devices,
+            ));
+        }
+        SeatClaim::Claimed => {}
     }
 
-    // Bind the license
-    state
-        .db
-        .bind_license(
-            &license.license_id,
-            &req.hardware_id,
-            req.device_name.as_deref(),
-            req.device_info.as_deref(),
-        )
-        .await
-        .map_err(|e| {
-            warn!("Failed to bind license: {}", e);
-            ClientError::new(ClientErrorCode::InternalError, "Failed to bind license")
-        })?;
-
     // Record binding history
     let _ = state
         .db
@@ -436,6 +569,22 @@ pub async fn bind_handler(
         &req.hardware_id,
         req.device_name.as_deref(),
     );
+    webhooks::notify(
+        &*state.db,
+        LicenseEvent::Bound,
+        &license.license_id,
+        Some(bound_details(&req.hardware_id, req.device_name.as_deref())),
+    )
+    .await;
+
+    let signed_license = issue_signed_license(
+        &state,
+        &license,
+        &req.hardware_id,
+        None,
+        offline_valid_until(&license),
+    )
+    .await;
 
     Ok(Json(BindResponse {
         success: true,
@@ -443,14 +592,15 @@ pub async fn bind_handler(
         features: parse_features(&license.features),
         tier: license.tier,
         expires_at: license.expires_at.map(|d| d.to_string()),
+        signed_license,
     }))
 }
 
 /// Release a license from hardware.
 ///
 /// # Behavior
-/// - Verifies hardware_id matches the bound hardware
-/// - Clears hardware binding fields
+/// - Verifies the hardware holds a seat on the license
+/// - Frees that seat (other devices keep theirs)
 /// - Records release in binding history
 #[cfg_attr(feature = "openapi", utoipa::path(
     post,
@@ -484,26 +634,13 @@ pub async fn release_handler(
             ClientError::new(ClientErrorCode::LicenseNotFound, "License key not found")
         })?;
 
-    // Check if bound
-    if !license.is_bound() {
-        return Err(ClientError::new(
-            ClientErrorCode::NotBound,
-            "License is not currently bound",
-        ));
-    }
-
-    // Verify hardware ID matches
-    if license.hardware_id.as_deref() != Some(&req.hardware_id) {
-        return Err(ClientError::new(
-            ClientErrorCode::HardwareMismatch,
-            "Hardware ID does not match the bound device",
-        ));
-    }
+    // Find this hardware's seat
+    let device = require_seat(&state, &license, &req.hardware_id).await?;
 
-    // Release the license
+    // Free the seat
     state
         .db
-        .release_license(&license.license_id)
+        .remove_license_device(&license.license_id, &req.hardware_id)
         .await
         .map_err(|e| {
             warn!("Failed to release license: {}", e);
@@ -517,8 +654,8 @@ pub async fn release_handler(
             &license.license_id,
             BindingAction::Release,
             Some(&req.hardware_id),
-            license.device_name.as_deref(),
-            license.device_info.as_deref(),
+            device.device_name.as_deref(),
+            device.device_info.as_deref(),
             PerformedBy::Client,
             None,
         )
@@ -529,8 +666,18 @@ pub async fn release_handler(
         LicenseEvent::Released,
         &req.license_key,
         &req.hardware_id,
-        license.device_name.as_deref(),
+        device.device_name.as_deref(),
     );
+    webhooks::notify(
+        &*state.db,
+        LicenseEvent::Released,
+        &license.license_id,
+        Some(bound_details(
+            &req.hardware_id,
+            device.device_name.as_deref(),
+        )),
+    )
+    .await;
 
     Ok(Json(ReleaseResponse {
         success: true,
@@ -543,7 +690,7 @@ pub async fn release_handler(
 /// # Behavior
 /// - Checks license exists
 /// - Checks license is not expired, revoked, suspended, or blacklisted
-/// - Checks license is bound to the provided hardware
+/// - Checks the provided hardware holds a seat on the license
 /// - Updates last_seen_at timestamp
 /// - Returns license details including features and tier
 #[cfg_attr(feature = "openapi", utoipa::path(
@@ -602,24 +749,14 @@ pub async fn validate_handler(
         ));
     }
 
-    // Check if bound
-    if !license.is_bound() {
-        return Err(ClientError::new(
-            ClientErrorCode::NotBound,
-            "License is not bound to any device",
-        ));
-    }
-
-    // Check hardware ID matches
-    if license.hardware_id.as_deref() != Some(&req.hardware_id) {
-        return Err(ClientError::new(
-            ClientErrorCode::HardwareMismatch,
-            "Hardware ID does not match the bound device",
-        ));
-    }
+    // Check this hardware holds a seat
+    require_seat(&state, &license, &req.hardware_id).await?;
 
     // Update last_seen_at
-    let _ = state.db.update_last_seen(&license.license_id).await;
+    let _ = state
+        .db
+        .touch_license_device(&license.license_id, &req.hardware_id)
+        .await;
 
     // Handle suspended status (grace period) - check before building response
     let (grace_period_ends, warning_msg) = if license.status == "suspended" {
@@ -663,6 +800,16 @@ pub async fn validate_handler(
         .clone()
         .unwrap_or_else(|| effective_org_id.clone());
 
+    let offline_valid_until = offline_valid_until(&license);
+    let signed_license = issue_signed_license(
+        &state,
+        &license,
+        &req.hardware_id,
+        grace_period_ends.clone(),
+        offline_valid_until.clone(),
+    )
+    .await;
+
     // Build response
     let response = ValidateResponse {
         valid: true,
@@ -671,11 +818,14 @@ pub async fn validate_handler(
         tier: license.tier,
         expires_at: license.expires_at.map(|d| d.and_utc().to_rfc3339()),
         grace_period_ends_at: grace_period_ends,
+        offline_valid_until,
         warning: warning_msg,
         org_id: Some(effective_org_id),
         org_name: Some(effective_org_name),
         bandwidth_used_bytes: license.bandwidth_used_bytes,
         bandwidth_limit_bytes: license.bandwidth_limit_bytes,
+        signed_license,
+        heartbeat_interval_secs: get_heartbeat_interval(),
     };
 
     // Log structured license validation event
@@ -687,9 +837,9 @@ pub async fn validate_handler(
 /// Validate or bind a license.
 ///
 /// # Behavior
-/// - If bound to this hardware: validate and return
-/// - If unbound: bind first, then validate
-/// - If bound to other hardware: return ALREADY_BOUND error
+/// - If this hardware holds a seat: validate and return
+/// - If a seat is free: bind first, then validate
+/// - If every seat is taken: return SEAT_LIMIT_REACHED error
 #[cfg_attr(feature = "openapi", utoipa::path(
     post,
     path = "/api/v1/client/validate-or-bind",
@@ -699,7 +849,7 @@ pub async fn validate_handler(
         (status = 200, description = "License validated (and bound if needed)", body = ValidateResponse),
         (status = 403, description = "License expired, revoked, or invalid", body = ClientError),
         (status = 404, description = "License not found", body = ClientError),
-        (status = 409, description = "License already bound to different device", body = ClientError),
+        (status = 409, description = "All seats are taken by other devices", body = ClientError),
     )
 ))]
 pub async fn validate_or_bind_handler(
@@ -757,58 +907,68 @@ pub async fn validate_or_bind_handler(
         ));
     }
 
-    // Check binding status
-    if license.is_bound() {
-        if license.hardware_id.as_deref() != Some(&req.hardware_id) {
-            // Bound to different hardware
-            return Err(ClientError::new(
-                ClientErrorCode::AlreadyBound,
-                "License is already bound to a different device",
-            )
-            .with_bound_device(license.device_name));
+    // Claim a seat unless this hardware already holds one
+    let claim = state
+        .db
+        .claim_license_seat(
+            &license,
+            &req.hardware_id,
+            req.device_name.as_deref(),
+            req.device_info.as_deref(),
+        )
+        .await
+        .map_err(|e| {
+            warn!("Failed to bind license: {}", e);
+            ClientError::new(ClientErrorCode::InternalError, "Failed to bind license")
+        })?;
+
+    match claim {
+        SeatClaim::Existing(_) => {
+            // Already bound to this hardware - just validate
         }
-        // Already bound to this hardware - just validate
-    } else {
-        // Not bound - bind it first
-        state
-            .db
-            .bind_license(
-                &license.license_id,
+        SeatClaim::Full(devices) => {
+            return Err(ClientError::seat_limit_reached(
+                license.max_devices,
+                &devices,
+            ));
+        }
+        SeatClaim::Claimed => {
+            // Record binding history
+            let _ = state
+                .db
+                .record_binding_history(
+                    &license.license_id,
+                    BindingAction::Bind,
+                    Some(&req.hardware_id),
+                    req.device_name.as_deref(),
+                    req.device_info.as_deref(),
+                    PerformedBy::Client,
+                    None,
+                )
+                .await;
+
+            // Log structured license binding event
+            log_license_binding_event(
+                LicenseEvent::Bound,
+                &req.license_key,
                 &req.hardware_id,
                 req.device_name.as_deref(),
-                req.device_info.as_deref(),
-            )
-            .await
-            .map_err(|e| {
-                warn!("Failed to bind license: {}", e);
-                ClientError::new(ClientErrorCode::InternalError, "Failed to bind license")
-            })?;
-
-        // Record binding history
-        let _ = state
-            .db
-            .record_binding_history(
+            );
+            webhooks::notify(
+                &*state.db,
+                LicenseEvent::Bound,
                 &license.license_id,
-                BindingAction::Bind,
-                Some(&req.hardware_id),
-                req.device_name.as_deref(),
-                req.device_info.as_deref(),
-                PerformedBy::Client,
-                None,
+                Some(bound_details(&req.hardware_id, req.device_name.as_deref())),
             )
             .await;
-
-        // Log structured license binding event
-        log_license_binding_event(
-            LicenseEvent::Bound,
-            &req.license_key,
-            &req.hardware_id,
-            req.device_name.as_deref(),
-        );
+        }
     }
 
     // Update last_seen_at
-    let _ = state.db.update_last_seen(&license.license_id).await;
+    let _ = state
+        .db
+        .touch_license_device(&license.license_id, &req.hardware_id)
+        .await;
 
     // Handle grace period warning - check before building response
     let (grace_period_ends, warning_msg) =
@@ -838,6 +998,16 @@ pub async fn validate_or_bind_handler(
         .clone()
         .unwrap_or_else(|| effective_org_id.clone());
 
+    let offline_valid_until = offline_valid_until(&license);
+    let signed_license = issue_signed_license(
+        &state,
+        &license,
+        &req.hardware_id,
+        grace_period_ends.clone(),
+        offline_valid_until.clone(),
+    )
+    .await;
+
     // Build response
     let response = ValidateResponse {
         valid: true,
@@ -846,11 +1016,14 @@ pub async fn validate_or_bind_handler(
         tier: license.tier,
         expires_at: license.expires_at.map(|d| d.and_utc().to_rfc3339()),
         grace_period_ends_at: grace_period_ends,
+        offline_valid_until,
         warning: warning_msg,
         org_id: Some(effective_org_id),
         org_name: Some(effective_org_name),
         bandwidth_used_bytes: license.bandwidth_used_bytes,
         bandwidth_limit_bytes: license.bandwidth_limit_bytes,
+        signed_license,
+        heartbeat_interval_secs: get_heartbeat_interval(),
     };
 
     // Log structured validation event
@@ -862,9 +1035,10 @@ pub async fn validate_or_bind_handler(
 /// Client heartbeat endpoint using license key.
 ///
 /// # Behavior
-/// - Verifies license exists and is bound to the provided hardware
-/// - Updates last_seen_at timestamp
-/// - Returns server timestamp
+/// - Verifies license exists and the provided hardware holds a seat
+/// - Updates last_seen_at timestamp for the license and the seat
+/// - Renews the device's floating lease if it still holds an active one
+/// - Returns server timestamp and the new lease expiry
 #[cfg_attr(feature = "openapi", utoipa::path(
     post,
     path = "/api/v1/client/heartbeat",
@@ -897,38 +1071,258 @@ pub async fn client_heartbeat_handler(
             ClientError::new(ClientErrorCode::LicenseNotFound, "License key not found")
         })?;
 
-    // Check if bound
-    if !license.is_bound() {
-        return Err(ClientError::new(
-            ClientErrorCode::NotBound,
-            "License is not bound to any device",
-        ));
-    }
-
-    // Verify hardware ID matches
-    if license.hardware_id.as_deref() != Some(&req.hardware_id) {
-        return Err(ClientError::new(
-            ClientErrorCode::HardwareMismatch,
-            "Hardware ID does not match the bound device",
-        ));
-    }
+    // Verify this hardware holds a seat
+    require_seat(&state, &license, &req.hardware_id).await?;
 
     // Update last_seen_at
     state
         .db
-        .update_last_seen(&license.license_id)
+        .touch_license_device(&license.license_id, &req.hardware_id)
         .await
         .map_err(|e| {
             warn!("Failed to update last_seen: {}", e);
             ClientError::new(ClientErrorCode::InternalError, "Failed to update heartbeat")
         })?;
 
+    // Renew the floating lease; an expired one has already gone back to the pool
+    let lease_expires_at = state
+        .db
+        .renew_license_lease(&license.license_id, &req.hardware_id, lease_ttl())
+        .await
+        .map_err(|e| {
+            warn!("Failed to renew lease: {}", e);
+            ClientError::new(ClientErrorCode::InternalError, "Failed to renew lease")
+        })?;
+
     // Log structured heartbeat event
     log_license_event(LicenseEvent::Heartbeat, &req.license_key, None);
 
     Ok(Json(ClientHeartbeatResponse {
         success: true,
         server_time: Utc::now().to_rfc3339(),
+        lease_expires_at: lease_expires_at.map(|d| d.and_utc().to_rfc3339()),
+        offline_valid_until: offline_valid_until(&license),
+        heartbeat_interval_secs: get_heartbeat_interval(),
+    }))
+}
+
+/// Check out a floating lease.
+///
+/// # Behavior
+/// - Checks the license exists and is usable (same checks as bind)
+/// - Claims a seat for the hardware if it does not hold one yet
+/// - Grants a lease unless `max_concurrent` devices already hold active ones
+/// - A device that already holds a lease has it renewed
+/// - The lease expires `lease_ttl_secs` after the last checkout or heartbeat
+#[cfg_attr(feature = "openapi", utoipa::path(
+    post,
+    path = "/api/v1/client/checkout",
+    tag = "client",
+    request_body = CheckoutRequest,
+    responses(
+        (status = 200, description = "Lease checked out", body = CheckoutResponse),
+        (status = 403, description = "License expired, revoked, or suspended", body = ClientError),
+        (status = 404, description = "License not found", body = ClientError),
+        (status = 409, description = "All seats or all leases are in use", body = ClientError),
+    )
+))]
+pub async fn checkout_handler(
+    State(state): State<AppState>,
+    Json(req): Json<CheckoutRequest>,
+) -> Result<Json<CheckoutResponse>, ClientError> {
+    info!("Checkout request for license_key={}", req.license_key);
+
+    // Find the license
+    let license = state
+        .db
+        .get_license_by_key(&req.license_key)
+        .await
+        .map_err(|e| {
+            warn!("Database error: {}", e);
+            ClientError::new(ClientErrorCode::InternalError, "Database error")
+        })?
+        .ok_or_else(|| {
+            warn!("License not found: {}", req.license_key);
+            ClientError::new(ClientErrorCode::LicenseNotFound, "License key not found")
+        })?;
+
+    // Check license status
+    if license.is_blacklisted == Some(true) {
+        return Err(ClientError::new(
+            ClientErrorCode::LicenseBlacklisted,
+            "License is blacklisted",
+        ));
+    }
+    if license.status == "revoked" {
+        return Err(ClientError::new(
+            ClientErrorCode::LicenseRevoked,
+            "License has been revoked",
+        ));
+    }
+    if license.status == "suspended" && !license.is_in_grace_period() {
+        return Err(ClientError::new(
+            ClientErrorCode::LicenseSuspended,
+            "License is suspended",
+        ));
+    }
+    if license.status != "active" && license.status != "suspended" {
+        return Err(ClientError::new(
+            ClientErrorCode::LicenseInactive,
+            format!("License status is '{}'", license.status),
+        ));
+    }
+    if license.is_expired() {
+        return Err(ClientError::new(
+            ClientErrorCode::LicenseExpired,
+            "License has expired",
+        ));
+    }
+
+    // Claim a seat unless this hardware already holds one
+    let claim = state
+        .db
+        .claim_license_seat(
+            &license,
+            &req.hardware_id,
+            req.device_name.as_deref(),
+            req.device_info.as_deref(),
+        )
+        .await
+        .map_err(|e| {
+            warn!("Failed to bind license: {}", e);
+            ClientError::new(ClientErrorCode::InternalError, "Failed to bind license")
+        })?;
+
+    match claim {
+        SeatClaim::Existing(_) => {}
+        SeatClaim::Full(devices) => {
+            return Err(ClientError::seat_limit_reached(
+                license.max_devices,
+                &devices,
+            ));
+        }
+        SeatClaim::Claimed => {
+            let _ = state
+                .db
+                .record_binding_history(
+                    &license.license_id,
+                    BindingAction::Bind,
+                    Some(&req.hardware_id),
+                    req.device_name.as_deref(),
+                    req.device_info.as_deref(),
+                    PerformedBy::Client,
+                    None,
+                )
+                .await;
+
+            log_license_binding_event(
+                LicenseEvent::Bound,
+                &req.license_key,
+                &req.hardware_id,
+                req.device_name.as_deref(),
+            );
+            webhooks::notify(
+                &*state.db,
+                LicenseEvent::Bound,
+                &license.license_id,
+                Some(bound_details(&req.hardware_id, req.device_name.as_deref())),
+            )
+            .await;
+        }
+    }
+
+    // Take a lease from the pool
+    let checkout = state
+        .db
+        .checkout_license_lease(&license, &req.hardware_id, lease_ttl())
+        .await
+        .map_err(|e| {
+            warn!("Failed to check out lease: {}", e);
+            ClientError::new(ClientErrorCode::InternalError, "Failed to check out lease")
+        })?;
+
+    let lease = match checkout {
+        LeaseCheckout::Granted(lease) => lease,
+        LeaseCheckout::Full(active) => {
+            return Err(ClientError::new(
+                ClientErrorCode::LeaseLimitReached,
+                format!("All {active} concurrent lease(s) on this license are in use"),
+            ));
+        }
+    };
+
+    info!(
+        "Lease checked out for license_key={} until {}",
+        req.license_key, lease.expires_at
+    );
+
+    Ok(Json(CheckoutResponse {
+        success: true,
+        license_id: license.license_id,
+        lease_expires_at: lease.expires_at.and_utc().to_rfc3339(),
+        lease_ttl_secs: get_lease_ttl_secs(),
+    }))
+}
+
+/// Return a floating lease to the pool.
+///
+/// # Behavior
+/// - Verifies the hardware holds a seat on the license
+/// - Deletes the device's lease (the seat itself is kept)
+/// - Succeeds even if the lease had already expired
+#[cfg_attr(feature = "openapi", utoipa::path(
+    post,
+    path = "/api/v1/client/checkin",
+    tag = "client",
+    request_body = CheckinRequest,
+    responses(
+        (status = 200, description = "Lease checked in", body = CheckinResponse),
+        (status = 403, description = "Hardware mismatch", body = ClientError),
+        (status = 404, description = "License not found", body = ClientError),
+        (status = 409, description = "License not bound", body = ClientError),
+    )
+))]
+pub async fn checkin_handler(
+    State(state): State<AppState>,
+    Json(req): Json<CheckinRequest>,
+) -> Result<Json<CheckinResponse>, ClientError> {
+    info!("Checkin request for license_key={}", req.license_key);
+
+    // Find the license
+    let license = state
+        .db
+        .get_license_by_key(&req.license_key)
+        .await
+        .map_err(|e| {
+            warn!("Database error: {}", e);
+            ClientError::new(ClientErrorCode::InternalError, "Database error")
+        })?
+        .ok_or_else(|| {
+            warn!("License not found: {}", req.license_key);
+            ClientError::new(ClientErrorCode::LicenseNotFound, "License key not found")
+        })?;
+
+    // Verify this hardware holds a seat
+    require_seat(&state, &license, &req.hardware_id).await?;
+
+    let checked_in = state
+        .db
+        .checkin_license_lease(&license.license_id, &req.hardware_id)
+        .await
+        .map_err(|e| {
+            warn!("Failed to check in lease: {}", e);
+            ClientError::new(ClientErrorCode::InternalError, "Failed to check in lease")
+        })?;
+
+    let message = if checked_in {
+        "Lease checked in"
+    } else {
+        "No lease was checked out"
+    };
+
+    Ok(Json(CheckinResponse {
+        success: true,
+        message: message.to_string(),
     }))
 }
 
@@ -938,7 +1332,7 @@ pub async fn client_heartbeat_handler(
 /// - Performs full license validation first (same checks as validate)
 /// - Checks if the feature is in the license's features list
 /// - Checks if the feature is in the tier's features (if tier is set)
-/// - Checks if the feature is restricted due to quota exceeded
+/// - Denies the tier's `quota_restricted_features` while any usage meter is over its limit
 /// - Returns allowed: true/false with appropriate message
 #[cfg_attr(feature = "openapi", utoipa::path(
     post,
@@ -1006,21 +1400,8 @@ pub async fn validate_feature_handler(
         ));
     }
 
-    // Check if bound
-    if !license.is_bound() {
-        return Err(ClientError::new(
-            ClientErrorCode::NotBound,
-            "License is not bound to any device",
-        ));
-    }
-
-    // Check hardware ID matches
-    if license.hardware_id.as_deref() != Some(&req.hardware_id) {
-        return Err(ClientError::new(
-            ClientErrorCode::HardwareMismatch,
-            "Hardware ID does not match the bound device",
-        ));
-    }
+    // Check this hardware holds a seat
+    require_seat(&state, &license, &req.hardware_id).await?;
 
     // Check for non-active status
     if license.status != "active" && license.status != "suspended" {
@@ -1031,22 +1412,20 @@ pub async fn validate_feature_handler(
     }
 
     // Update last_seen_at
-    let _ = state.db.update_last_seen(&license.license_id).await;
+    let _ = state
+        .db
+        .touch_license_device(&license.license_id, &req.hardware_id)
+        .await;
 
     // Get license features (from JSON string)
     let license_features = parse_features(&license.features);
 
-    // Get tier features (if tier is set)
-    let tier_features: Vec<String> = license
-        .tier
-        .as_ref()
-        .and_then(|t| get_tier_config(t))
-        .map(|tier| tier.config.features)
-        .unwrap_or_default();
+    // Get the tier (if set)
+    let tier = license_tier(&license);
 
     // Check if feature is in license features or tier features
     let feature_in_license = license_features.iter().any(|f| f == &req.feature);
-    let feature_in_tier = tier_features.iter().any(|f| f == &req.feature);
+    let feature_in_tier = tier.as_ref().is_some_and(|t| t.has_feature(&req.feature));
 
     if !feature_in_license && !feature_in_tier {
         info!(
@@ -1062,10 +1441,37 @@ pub async fn validate_feature_handler(
         ));
     }
 
-    // TODO: Check quota_restricted_features when quota fields are added to database
-    // For now, quota checking is a stub that always passes
-    // When quota fields are added, check if quota_exceeded is true and
-    // if the feature is in quota_restricted_features
+    // Deny quota-restricted features while any meter is over its limit
+    if let Some(tier) = tier
+        .as_ref()
+        .filter(|t| t.is_quota_restricted(&req.feature))
+    {
+        let meters = state
+            .db
+            .list_usage_meters(&license.license_id)
+            .await
+            .map_err(|e| {
+                warn!("Database error: {}", e);
+                ClientError::new(ClientErrorCode::InternalError, "Database error")
+            })?;
+
+        if let Some(over) = meter_statuses(&license.license_id, &meters, Some(tier))
+            .into_iter()
+            .find(|m| m.exceeded)
+        {
+            info!(
+                "Feature '{}' restricted for license {}: meter '{}' over quota",
+                req.feature, req.license_key, over.meter
+            );
+            return Err(ClientError::new(
+                ClientErrorCode::QuotaExceeded,
+                format!(
+                    "Feature '{}' is unavailable while the '{}' quota is exceeded",
+                    req.feature, over.meter
+                ),
+            ));
+        }
+    }
 
     info!(
         "Feature '{}' allowed for license {}",
@@ -1083,8 +1489,145 @@ pub async fn validate_feature_handler(
 // Helpers
 // ============================================================================
 
+/// Issue a signed license document for a license bound to `hardware_id`.
+///
+/// Returns `None` when no signing key is configured. The signature is also
+/// stored on the license record; failures are logged rather than failing the
+/// request, since the unsigned response is still valid for online use.
+pub(crate) async fn issue_signed_license(
+    state: &AppState,
+    license: &License,
+    hardware_id: &str,
+    grace_period_ends_at: Option<String>,
+    offline_valid_until: Option<String>,
+) -> Option<SignedLicense> {
+    let signer = state.signer.as_ref()?;
+
+    let payload = SignedLicensePayload {
+        version: PAYLOAD_VERSION,
+        license_id: license.license_id.clone(),
+        license_key: license.license_key.clone().unwrap_or_default(),
+        hardware_id: hardware_id.to_string(),
+        features: parse_features(&license.features),
+        tier: license.tier.clone(),
+        expires_at: license.expires_at.map(|d| d.and_utc().to_rfc3339()),
+        grace_period_ends_at,
+        offline_valid_until,
+        issued_at: Utc::now().to_rfc3339(),
+    };
+
+    let signed = match signer.sign(&payload) {
+        Ok(signed) => signed,
+        Err(e) => {
+            warn!("Failed to sign license {}: {}", license.license_id, e);
+            return None;
+        }
+    };
+
+    if let Err(e) = state
+        .db
+        .update_license_signature(&license.license_id, &signed.signature)
+        .await
+    {
+        warn!("Failed to store license signature: {}", e);
+    }
+
+    Some(signed)
+}
+
+/// Look up the seat held by `hardware_id` on a license.
+///
+/// Returns `NOT_BOUND` if the license has no seats in use, and
+/// `HARDWARE_MISMATCH` if other devices hold seats but this one does not.
+pub(crate) async fn require_seat(
+    state: &AppState,
+    license: &License,
+    hardware_id: &str,
+) -> Result<LicenseDevice, ClientError> {
+    let device = state
+        .db
+        .get_license_device(&license.license_id, hardware_id)
+        .await
+        .map_err(|e| {
+            warn!("Database error: {}", e);
+            ClientError::new(ClientErrorCode::InternalError, "Database error")
+        })?;
+
+    match device {
+        Some(device) => Ok(device),
+        None if license.is_bound() => Err(ClientError::new(
+            ClientErrorCode::HardwareMismatch,
+            "This device does not hold a seat on the license",
+        )),
+        None => Err(ClientError::new(
+            ClientErrorCode::NotBound,
+            "License is not bound to any device",
+        )),
+    }
+}
+
+/// End of the offline allowance granted by a successful check (RFC 3339).
+///
+/// The number of days comes from the license's `offline_days`, then its
+/// tier's, then `server.offline_days`. The allowance never runs past the
+/// license's expiry or, while suspended, the end of its grace period.
+/// Returns `None` if the allowance is zero or the license can't be used.
+pub(crate) fn offline_valid_until(license: &License) -> Option<String> {
+    if license.is_expired() || license.is_blacklisted == Some(true) {
+        return None;
+    }
+    if license.status != "active" && !license.is_in_grace_period() {
+        return None;
+    }
+
+    let days = license
+        .offline_days
+        .and_then(|days| u32::try_from(days).ok())
+        .or_else(|| {
+            license
+                .tier
+                .as_deref()
+                .and_then(get_tier_config)
+                .and_then(|tier| tier.config.offline_days)
+        })
+        .unwrap_or_else(get_offline_days);
+    if days == 0 {
+        return None;
+    }
+
+    let mut until = Utc::now()
+        .naive_utc()
+        .checked_add_signed(Duration::try_days(i64::from(days))?)?;
+    if let Some(expires_at) = license.expires_at {
+        until = until.min(expires_at);
+    }
+    if license.status == "suspended" {
+        if let Some(grace_end) = license.grace_period_ends_at {
+            until = until.min(grace_end);
+        }
+    }
+
+    Some(until.and_utc().to_rfc3339())
+}
+
+/// How long a floating lease lasts without a heartbeat.
+fn lease_ttl() -> Duration {
+    i64::try_from(get_lease_ttl_secs())
+        .ok()
+        .and_then(Duration::try_seconds)
+        .unwrap_or_else(|| Duration::seconds(300))
+}
+
+/// Webhook details for a bind or release.
+pub(crate) fn bound_details(hardware_id: &str, device_name: Option<&str>) -> serde_json::Value {
+    serde_json::json!({
+        "hardware_id": hardware_id,
+        "device_name": device_name,
+    })
+}
+
 /// Parse features from JSON string to Vec<String>.
-fn parse_features(features: &Option<String>) -> Vec<String> {
+pub(crate) fn parse_features(features: &Option<String>) -> Vec<String> {
     features
         .as_ref()
         .and_then(|f| serde_json::from_str::<Vec<String>>(f).ok())
@@ -1109,7 +1652,7 @@ mod tests {
             StatusCode::NOT_FOUND
         );
         assert_eq!(
-            ClientError::new(ClientErrorCode::AlreadyBound, "").status_code(),
+            ClientError::new(ClientErrorCode::SeatLimitReached, "").status_code(),
             StatusCode::CONFLICT
         );
         assert_eq!(
@@ -1,17 +1,26 @@
-use chrono::{NaiveDateTime, Utc};
+use axum::async_trait;
+use chrono::{Duration, NaiveDateTime, Utc};
 use serde::{Deserialize, Serialize};
-use sqlx::{query, query_as, FromRow};
+use sqlx::{query, query_as, query_scalar, FromRow, QueryBuilder};
 use std::sync::Arc;
 use tracing::error;
 
 #[cfg(feature = "sqlite")]
-use sqlx::SqlitePool;
+use sqlx::sqlite::SqliteConnectOptions;
+#[cfg(feature = "sqlite")]
+use sqlx::{SqliteConnection, SqlitePool};
+#[cfg(feature = "sqlite")]
+use std::str::FromStr;
 
 #[cfg(feature = "postgres")]
-use sqlx::PgPool;
+use sqlx::{PgConnection, PgPool};
 
 use crate::config::get_config;
 use crate::errors::{LicenseError, LicenseResult};
+use crate::server::license_query::{
+    push_conditions, push_order_by, Dialect, LicenseCursor, LicenseFilter, LicenseSort,
+};
+use crate::server::store::LicenseStore;
 
 /// Represents a license record stored in the database.
 ///
@@ -64,10 +73,38 @@ pub struct License {
     pub bandwidth_used_bytes: Option<i64>,
     pub bandwidth_limit_bytes: Option<i64>,
     pub quota_exceeded: Option<bool>,
+
+    // === Multi-seat ===
+    /// Number of devices that may hold a seat at the same time
+    pub max_devices: i32,
+
+    // === Floating leases ===
+    /// Number of devices that may hold a lease at the same time (None = unlimited)
+    pub max_concurrent: Option<i32>,
+
+    // === Offline allowance ===
+    /// Days a client may run offline after each successful check
+    /// (None = tier or server default)
+    pub offline_days: Option<i32>,
+
+    // === Billing cycle ===
+    /// When usage meters reset: "monthly", a cron expression or "none"
+    /// (None = the tier's cycle)
+    pub billing_cycle: Option<String>,
+    /// Start of the current usage period (None = `issued_at`)
+    pub usage_period_start: Option<NaiveDateTime>,
+
+    // === Organization suspension ===
+    /// Set when suspending the license's organization suspended it, so
+    /// reinstating the organization reactivates it
+    pub suspended_by_org: Option<bool>,
 }
 
 impl License {
     /// Check if the license is currently bound to hardware.
+    ///
+    /// For multi-seat licenses the binding fields mirror the most recently
+    /// bound device; see `license_devices` for the full list of seats.
     pub fn is_bound(&self) -> bool {
         self.hardware_id.is_some() && self.bound_at.is_some()
     }
@@ -110,6 +147,49 @@ pub struct LicenseBindingHistory {
     pub created_at: NaiveDateTime,
 }
 
+/// A device holding a seat on a license.
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct LicenseDevice {
+    pub license_id: String,
+    pub hardware_id: String,
+    pub device_name: Option<String>,
+    pub device_info: Option<String>,
+    pub bound_at: NaiveDateTime,
+    pub last_seen_at: Option<NaiveDateTime>,
+}
+
+/// Outcome of claiming a seat on a license for a device.
+#[derive(Debug, Clone)]
+pub enum SeatClaim {
+    /// The device already held a seat
+    Existing(LicenseDevice),
+    /// A free seat was assigned to the device
+    Claimed,
+    /// Every seat is taken; holds the devices occupying them
+    Full(Vec<LicenseDevice>),
+}
+
+/// A floating lease held by a device on a license.
+///
+/// A lease is in use until `expires_at`; heartbeats push the expiry out and
+/// checkin deletes it.
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct LicenseLease {
+    pub license_id: String,
+    pub hardware_id: String,
+    pub checked_out_at: NaiveDateTime,
+    pub expires_at: NaiveDateTime,
+}
+
+/// Outcome of checking out a floating lease.
+#[derive(Debug, Clone)]
+pub enum LeaseCheckout {
+    /// The device holds the lease (newly checked out or renewed)
+    Granted(LicenseLease),
+    /// Every lease is in use; holds the number of active leases
+    Full(usize),
+}
+
 /// Actions for license binding history.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum BindingAction {
@@ -120,6 +200,14 @@ pub enum BindingAction {
 }
 
 impl BindingAction {
+    /// Every action, in the order the binding lifecycle uses them.
+    pub const ALL: [BindingAction; 4] = [
+        BindingAction::Bind,
+        BindingAction::Release,
+        BindingAction::AdminRelease,
+        BindingAction::SystemRelease,
+    ];
+
     pub fn as_str(&self) -> &'static str {
         match self {
             BindingAction::Bind => "bind",
@@ -130,6 +218,23 @@ impl BindingAction {
     }
 }
 
+impl std::str::FromStr for BindingAction {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|action| action.as_str() == s)
+            .ok_or_else(|| {
+                let valid: Vec<&str> = Self::ALL.iter().map(|a| a.as_str()).collect();
+                format!(
+                    "invalid binding action: {s} (expected one of {})",
+                    valid.join(", ")
+                )
+            })
+    }
+}
+
 /// Who performed the binding action.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum PerformedBy {
@@ -148,9 +253,35 @@ impl PerformedBy {
     }
 }
 
+impl std::str::FromStr for PerformedBy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "client" => Ok(PerformedBy::Client),
+            "admin" => Ok(PerformedBy::Admin),
+            "system" => Ok(PerformedBy::System),
+            _ => Err(format!(
+                "invalid performed_by: {s} (expected client, admin or system)"
+            )),
+        }
+    }
+}
+
+/// Filters for [`LicenseStore::list_binding_history`]. `None` matches everything.
+#[derive(Debug, Clone, Default)]
+pub struct BindingHistoryFilter {
+    pub license_id: Option<String>,
+    pub hardware_id: Option<String>,
+    pub action: Option<BindingAction>,
+    pub performed_by: Option<PerformedBy>,
+}
+
 /// Unified database abstraction over SQLite and Postgres.
 ///
-/// Available variants depend on enabled features:
+/// This is the SQL implementation of [`LicenseStore`] and the other storage
+/// traits in [`crate::server::store`]. Available variants depend on enabled
+/// features:
 /// - `sqlite` feature enables `Database::SQLite`
 /// - `postgres` feature enables `Database::Postgres`
 #[derive(Debug, Clone)]
@@ -173,12 +304,14 @@ impl Database {
         match db_config.db_type.as_str() {
             #[cfg(feature = "sqlite")]
             "sqlite" => {
-                let pool = SqlitePool::connect(&db_config.sqlite_url)
-                    .await
-                    .map_err(|e| {
-                        error!("Failed to connect to SQLite: {e}");
-                        LicenseError::ServerError(format!("failed to connect to SQLite: {e}"))
-                    })?;
+                // Create the database file on first start so migrations can run
+                let options = SqliteConnectOptions::from_str(&db_config.sqlite_url)
+                    .map_err(|e| LicenseError::ConfigError(format!("invalid SQLite URL: {e}")))?
+                    .create_if_missing(true);
+                let pool = SqlitePool::connect_with(options).await.map_err(|e| {
+                    error!("Failed to connect to SQLite: {e}");
+                    LicenseError::ServerError(format!("failed to connect to SQLite: {e}"))
+                })?;
 
                 Ok(Arc::new(Database::SQLite(pool)))
             }
@@ -217,184 +350,56 @@ impl Database {
         }
     }
 
-    /// Check database connectivity by running a simple query.
-    ///
-    /// Returns `true` if the database is reachable, `false` otherwise.
-    pub async fn health_check(&self) -> bool {
-        match self {
-            #[cfg(feature = "sqlite")]
-            Database::SQLite(pool) => sqlx::query("SELECT 1").fetch_one(pool).await.is_ok(),
-            #[cfg(feature = "postgres")]
-            Database::Postgres(pool) => sqlx::query("SELECT 1").fetch_one(pool).await.is_ok(),
-        }
-    }
+    /// Point a license's binding fields at its most recently bound device.
+    async fn sync_binding_fields(&self, license_id: &str) -> LicenseResult<()> {
+        let devices = self.list_license_devices(license_id).await?;
+        let Some(device) = devices.iter().max_by_key(|d| d.bound_at) else {
+            self.release_license(license_id).await?;
+            return Ok(());
+        };
 
-    /// Insert a new license or update an existing one.
-    ///
-    /// This acts like an "upsert" keyed on `license_id`:
-    /// - if the license doesn't exist, it is created
-    /// - if it exists, the fields are updated
-    pub async fn insert_license(&self, license: License) -> LicenseResult<()> {
         match self {
             #[cfg(feature = "sqlite")]
             Database::SQLite(pool) => {
                 query(
-                    r#"
-                    INSERT INTO licenses (
-                        license_id, client_id, status, features, issued_at, expires_at,
-                        hardware_id, signature, last_heartbeat, org_id, org_name,
-                        license_key, tier, device_name, device_info, bound_at,
-                        last_seen_at, suspended_at, revoked_at, revoke_reason,
-                        grace_period_ends_at, suspension_message, is_blacklisted,
-                        blacklisted_at, blacklist_reason, metadata,
-                        bandwidth_used_bytes, bandwidth_limit_bytes, quota_exceeded
-                    )
-                    VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
-                    ON CONFLICT(license_id) DO UPDATE SET
-                        client_id            = excluded.client_id,
-                        status               = excluded.status,
-                        features             = excluded.features,
-                        issued_at            = excluded.issued_at,
-                        expires_at           = excluded.expires_at,
-                        hardware_id          = excluded.hardware_id,
-                        signature            = excluded.signature,
-                        last_heartbeat       = excluded.last_heartbeat,
-                        org_id               = excluded.org_id,
-                        org_name             = excluded.org_name,
-                        license_key          = excluded.license_key,
-                        tier                 = excluded.tier,
-                        device_name          = excluded.device_name,
-                        device_info          = excluded.device_info,
-                        bound_at             = excluded.bound_at,
-                        last_seen_at         = excluded.last_seen_at,
-                        suspended_at         = excluded.suspended_at,
-                        revoked_at           = excluded.revoked_at,
-                        revoke_reason        = excluded.revoke_reason,
-                        grace_period_ends_at = excluded.grace_period_ends_at,
-                        suspension_message   = excluded.suspension_message,
-                        is_blacklisted       = excluded.is_blacklisted,
-                        blacklisted_at       = excluded.blacklisted_at,
-                        blacklist_reason     = excluded.blacklist_reason,
-                        metadata             = excluded.metadata,
-                        bandwidth_used_bytes = excluded.bandwidth_used_bytes,
-                        bandwidth_limit_bytes = excluded.bandwidth_limit_bytes,
-                        quota_exceeded       = excluded.quota_exceeded
-                    "#,
+                    "UPDATE licenses SET \
+                         hardware_id = ?, \
+                         device_name = ?, \
+                         device_info = ?, \
+                         bound_at = ? \
+                     WHERE license_id = ?",
                 )
-                .bind(&license.license_id)
-                .bind(&license.client_id)
-                .bind(&license.status)
-                .bind(&license.features)
-                .bind(license.issued_at)
-                .bind(license.expires_at)
-                .bind(&license.hardware_id)
-                .bind(&license.signature)
-                .bind(license.last_heartbeat)
-                .bind(&license.org_id)
-                .bind(&license.org_name)
-                .bind(&license.license_key)
-                .bind(&license.tier)
-                .bind(&license.device_name)
-                .bind(&license.device_info)
-                .bind(license.bound_at)
-                .bind(license.last_seen_at)
-                .bind(license.suspended_at)
-                .bind(license.revoked_at)
-                .bind(&license.revoke_reason)
-                .bind(license.grace_period_ends_at)
-                .bind(&license.suspension_message)
-                .bind(license.is_blacklisted)
-                .bind(license.blacklisted_at)
-                .bind(&license.blacklist_reason)
-                .bind(&license.metadata)
-                .bind(license.bandwidth_used_bytes)
-                .bind(license.bandwidth_limit_bytes)
-                .bind(license.quota_exceeded)
+                .bind(&device.hardware_id)
+                .bind(&device.device_name)
+                .bind(&device.device_info)
+                .bind(device.bound_at)
+                .bind(license_id)
                 .execute(pool)
                 .await
                 .map_err(|e| {
-                    error!("SQLite insert_license failed: {e}");
+                    error!("SQLite sync_binding_fields failed: {e}");
                     LicenseError::ServerError(format!("database error: {e}"))
                 })?;
             }
             #[cfg(feature = "postgres")]
             Database::Postgres(pool) => {
                 query(
-                    r#"
-                    INSERT INTO licenses (
-                        license_id, client_id, status, features, issued_at, expires_at,
-                        hardware_id, signature, last_heartbeat, org_id, org_name,
-                        license_key, tier, device_name, device_info, bound_at,
-                        last_seen_at, suspended_at, revoked_at, revoke_reason,
-                        grace_period_ends_at, suspension_message, is_blacklisted,
-                        blacklisted_at, blacklist_reason, metadata,
-                        bandwidth_used_bytes, bandwidth_limit_bytes, quota_exceeded
-                    )
-                    VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18, $19, $20, $21, $22, $23, $24, $25, $26, $27, $28, $29)
-                    ON CONFLICT (license_id) DO UPDATE SET
-                        client_id            = EXCLUDED.client_id,
-                        status               = EXCLUDED.status,
-                        features             = EXCLUDED.features,
-                        issued_at            = EXCLUDED.issued_at,
-                        expires_at           = EXCLUDED.expires_at,
-                        hardware_id          = EXCLUDED.hardware_id,
-                        signature            = EXCLUDED.signature,
-                        last_heartbeat       = EXCLUDED.last_heartbeat,
-                        org_id               = EXCLUDED.org_id,
-                        org_name             = EXCLUDED.org_name,
-                        license_key          = EXCLUDED.license_key,
-                        tier                 = EXCLUDED.tier,
-                        device_name          = EXCLUDED.device_name,
-                        device_info          = EXCLUDED.device_info,
-                        bound_at             = EXCLUDED.bound_at,
-                        last_seen_at         = EXCLUDED.last_seen_at,
-                        suspended_at         = EXCLUDED.suspended_at,
-                        revoked_at           = EXCLUDED.revoked_at,
-                        revoke_reason        = EXCLUDED.revoke_reason,
-                        grace_period_ends_at = EXCLUDED.grace_period_ends_at,
-                        suspension_message   = EXCLUDED.suspension_message,
-                        is_blacklisted       = EXCLUDED.is_blacklisted,
-                        blacklisted_at       = EXCLUDED.blacklisted_at,
-                        blacklist_reason     = EXCLUDED.blacklist_reason,
-                        metadata             = EXCLUDED.metadata,
-                        bandwidth_used_bytes = EXCLUDED.bandwidth_used_bytes,
-                        bandwidth_limit_bytes = EXCLUDED.bandwidth_limit_bytes,
-                        quota_exceeded       = EXCLUDED.quota_exceeded
-                    "#,
+                    "UPDATE licenses SET \
+                         hardware_id = $1, \
+                         device_name = $2, \
+                         device_info = $3, \
+                         bound_at = $4 \
+                     WHERE license_id = $5",
                 )
-                .bind(&license.license_id)
-                .bind(&license.client_id)
-                .bind(&license.status)
-                .bind(&license.features)
-                .bind(license.issued_at)
-                .bind(license.expires_at)
-                .bind(&license.hardware_id)
-                .bind(&license.signature)
-                .bind(license.last_heartbeat)
-                .bind(&license.org_id)
-                .bind(&license.org_name)
-                .bind(&license.license_key)
-                .bind(&license.tier)
-                .bind(&license.device_name)
-                .bind(&license.device_info)
-                .bind(license.bound_at)
-                .bind(license.last_seen_at)
-                .bind(license.suspended_at)
-                .bind(license.revoked_at)
-                .bind(&license.revoke_reason)
-                .bind(license.grace_period_ends_at)
-                .bind(&license.suspension_message)
-                .bind(license.is_blacklisted)
-                .bind(license.blacklisted_at)
-                .bind(&license.blacklist_reason)
-                .bind(&license.metadata)
-                .bind(license.bandwidth_used_bytes)
-                .bind(license.bandwidth_limit_bytes)
-                .bind(license.quota_exceeded)
+                .bind(&device.hardware_id)
+                .bind(&device.device_name)
+                .bind(&device.device_info)
+                .bind(device.bound_at)
+                .bind(license_id)
                 .execute(pool)
                 .await
                 .map_err(|e| {
-                    error!("Postgres insert_license failed: {e}");
+                    error!("Postgres sync_binding_fields failed: {e}");
                     LicenseError::ServerError(format!("database error: {e}"))
                 })?;
             }
@@ -403,13 +408,123 @@ impl Database {
         Ok(())
     }
 
-    /// Fetch a license by its ID.
-    ///
-    /// Returns:
-    /// - `Ok(Some(License))` if found
-    /// - `Ok(None)` if not found
-    /// - `Err(LicenseError::ServerError)` on DB failure
-    pub async fn get_license(&self, license_id: &str) -> LicenseResult<Option<License>> {
+    /// Return every lease on a license to the pool.
+    async fn remove_all_license_leases(&self, license_id: &str) -> LicenseResult<u64> {
+        let rows_affected = match self {
+            #[cfg(feature = "sqlite")]
+            Database::SQLite(pool) => query("DELETE FROM license_leases WHERE license_id = ?")
+                .bind(license_id)
+                .execute(pool)
+                .await
+                .map_err(|e| {
+                    error!("SQLite remove_all_license_leases failed: {e}");
+                    LicenseError::ServerError(format!("database error: {e}"))
+                })?
+                .rows_affected(),
+            #[cfg(feature = "postgres")]
+            Database::Postgres(pool) => query("DELETE FROM license_leases WHERE license_id = $1")
+                .bind(license_id)
+                .execute(pool)
+                .await
+                .map_err(|e| {
+                    error!("Postgres remove_all_license_leases failed: {e}");
+                    LicenseError::ServerError(format!("database error: {e}"))
+                })?
+                .rows_affected(),
+        };
+
+        Ok(rows_affected)
+    }
+}
+
+#[async_trait]
+impl LicenseStore for Database {
+    fn backend(&self) -> &'static str {
+        self.db_type()
+    }
+
+    async fn health_check(&self) -> bool {
+        match self {
+            #[cfg(feature = "sqlite")]
+            Database::SQLite(pool) => sqlx::query("SELECT 1").fetch_one(pool).await.is_ok(),
+            #[cfg(feature = "postgres")]
+            Database::Postgres(pool) => sqlx::query("SELECT 1").fetch_one(pool).await.is_ok(),
+        }
+    }
+
+    async fn insert_license(&self, license: License) -> LicenseResult<()> {
+        match self {
+            #[cfg(feature = "sqlite")]
+            Database::SQLite(pool) => {
+                let fail = |e: sqlx::Error| {
+                    error!("SQLite insert_license failed: {e}");
+                    LicenseError::ServerError(format!("database error: {e}"))
+                };
+                let mut conn = pool.acquire().await.map_err(fail)?;
+                sqlite_upsert_license(&mut conn, &license)
+                    .await
+                    .map_err(fail)?;
+            }
+            #[cfg(feature = "postgres")]
+            Database::Postgres(pool) => {
+                let fail = |e: sqlx::Error| {
+                    error!("Postgres insert_license failed: {e}");
+                    LicenseError::ServerError(format!("database error: {e}"))
+                };
+                let mut conn = pool.acquire().await.map_err(fail)?;
+                pg_upsert_license(&mut conn, &license).await.map_err(fail)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn insert_licenses(&self, licenses: &[License], free_seats: bool) -> LicenseResult<()> {
+        match self {
+            #[cfg(feature = "sqlite")]
+            Database::SQLite(pool) => {
+                let fail = |e: sqlx::Error| {
+                    error!("SQLite insert_licenses failed: {e}");
+                    LicenseError::ServerError(format!("database error: {e}"))
+                };
+
+                let mut tx = pool.begin_with("BEGIN IMMEDIATE").await.map_err(fail)?;
+                for license in licenses {
+                    sqlite_upsert_license(&mut tx, license)
+                        .await
+                        .map_err(fail)?;
+                    if free_seats {
+                        sqlite_free_seats(&mut tx, &license.license_id)
+                            .await
+                            .map_err(fail)?;
+                    }
+                }
+                tx.commit().await.map_err(fail)?;
+            }
+            #[cfg(feature = "postgres")]
+            Database::Postgres(pool) => {
+                let fail = |e: sqlx::Error| {
+                    error!("Postgres insert_licenses failed: {e}");
+                    LicenseError::ServerError(format!("database error: {e}"))
+                };
+
+                let mut tx = pool.begin().await.map_err(fail)?;
+                for license in licenses {
+                    pg_upsert_license(&mut tx, license).await.map_err(fail)?;
+                    if free_seats {
+                        pg_free_seats(&mut tx, &license.license_id)
+                            .await
+                            .map_err(fail)?;
+                    }
+                }
+                tx.commit().await.map_err(fail)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn get_license(&self, license_id: &str) -> LicenseResult<Option<License>> {
         match self {
             #[cfg(feature = "sqlite")]
             Database::SQLite(pool) => {
@@ -441,13 +556,7 @@ impl Database {
         }
     }
 
-    /// Update the `last_heartbeat` timestamp for a license/client pair.
-    ///
-    /// Returns:
-    /// - `Ok(true)` if a row was updated
-    /// - `Ok(false)` if no matching row was found
-    /// - `Err(LicenseError::ServerError)` on DB failure
-    pub async fn update_last_heartbeat(
+    async fn update_last_heartbeat(
         &self,
         license_id: &str,
         client_id: &str,
@@ -492,13 +601,7 @@ impl Database {
         Ok(rows_affected > 0)
     }
 
-    /// Fetch a license by its human-readable license key.
-    ///
-    /// Returns:
-    /// - `Ok(Some(License))` if found
-    /// - `Ok(None)` if not found
-    /// - `Err(LicenseError::ServerError)` on DB failure
-    pub async fn get_license_by_key(&self, license_key: &str) -> LicenseResult<Option<License>> {
+    async fn get_license_by_key(&self, license_key: &str) -> LicenseResult<Option<License>> {
         match self {
             #[cfg(feature = "sqlite")]
             Database::SQLite(pool) => {
@@ -531,8 +634,7 @@ impl Database {
         }
     }
 
-    /// Check if a license key already exists in the database.
-    pub async fn license_key_exists(&self, license_key: &str) -> LicenseResult<bool> {
+    async fn license_key_exists(&self, license_key: &str) -> LicenseResult<bool> {
         match self {
             #[cfg(feature = "sqlite")]
             Database::SQLite(pool) => {
@@ -565,8 +667,7 @@ impl Database {
         }
     }
 
-    /// List licenses by organization ID.
-    pub async fn list_licenses_by_org(&self, org_id: &str) -> LicenseResult<Vec<License>> {
+    async fn list_licenses_by_org(&self, org_id: &str) -> LicenseResult<Vec<License>> {
         match self {
             #[cfg(feature = "sqlite")]
             Database::SQLite(pool) => {
@@ -597,12 +698,74 @@ impl Database {
         }
     }
 
-    /// Update license status.
-    pub async fn update_license_status(
+    async fn list_licenses(
         &self,
-        license_id: &str,
-        status: &str,
-    ) -> LicenseResult<bool> {
+        filter: &LicenseFilter,
+        sort: LicenseSort,
+        after: Option<&LicenseCursor>,
+        limit: u32,
+        offset: u32,
+    ) -> LicenseResult<(Vec<License>, u64)> {
+        let list_error = |e: sqlx::Error| {
+            error!("list_licenses failed: {e}");
+            LicenseError::ServerError(format!("database error: {e}"))
+        };
+
+        match self {
+            #[cfg(feature = "sqlite")]
+            Database::SQLite(pool) => {
+                let mut count = QueryBuilder::new("SELECT COUNT(*) FROM licenses");
+                push_conditions(&mut count, Dialect::Sqlite, filter, None);
+                let total: i64 = count
+                    .build_query_scalar()
+                    .fetch_one(pool)
+                    .await
+                    .map_err(list_error)?;
+
+                let mut list = QueryBuilder::new("SELECT * FROM licenses");
+                push_conditions(&mut list, Dialect::Sqlite, filter, after);
+                push_order_by(&mut list, sort);
+                list.push(" LIMIT ")
+                    .push_bind(i64::from(limit))
+                    .push(" OFFSET ")
+                    .push_bind(i64::from(offset));
+                let licenses = list
+                    .build_query_as::<License>()
+                    .fetch_all(pool)
+                    .await
+                    .map_err(list_error)?;
+
+                Ok((licenses, total as u64))
+            }
+            #[cfg(feature = "postgres")]
+            Database::Postgres(pool) => {
+                let mut count = QueryBuilder::new("SELECT COUNT(*) FROM licenses");
+                push_conditions(&mut count, Dialect::Postgres, filter, None);
+                let total: i64 = count
+                    .build_query_scalar()
+                    .fetch_one(pool)
+                    .await
+                    .map_err(list_error)?;
+
+                let mut list = QueryBuilder::new("SELECT * FROM licenses");
+                push_conditions(&mut list, Dialect::Postgres, filter, after);
+                push_order_by(&mut list, sort);
+                list.push(" LIMIT ")
+                    .push_bind(i64::from(limit))
+                    .push(" OFFSET ")
+                    .push_bind(i64::from(offset));
+                let licenses = list
+                    .build_query_as::<License>()
+                    .fetch_all(pool)
+                    .await
+                    .map_err(list_error)?;
+
+                Ok((licenses, total as u64))
+            }
+        }
+    }
+
+    async fn update_license_status(&self, license_id: &str, status: &str) -> LicenseResult<bool> {
         let rows_affected = match self {
             #[cfg(feature = "sqlite")]
             Database::SQLite(pool) => query("UPDATE licenses SET status = ? WHERE license_id = ?")
@@ -633,8 +796,7 @@ impl Database {
         Ok(rows_affected > 0)
     }
 
-    /// Bind a license to hardware.
-    pub async fn bind_license(
+    async fn bind_license(
         &self,
         license_id: &str,
         hardware_id: &str,
@@ -695,8 +857,7 @@ impl Database {
         Ok(rows_affected > 0)
     }
 
-    /// Release a license from hardware binding.
-    pub async fn release_license(&self, license_id: &str) -> LicenseResult<bool> {
+    async fn release_license(&self, license_id: &str) -> LicenseResult<bool> {
         let rows_affected = match self {
             #[cfg(feature = "sqlite")]
             Database::SQLite(pool) => query(
@@ -737,9 +898,7 @@ impl Database {
         Ok(rows_affected > 0)
     }
 
-    /// Record a binding action in the history table.
-    #[allow(clippy::too_many_arguments)]
-    pub async fn record_binding_history(
+    async fn record_binding_history(
         &self,
         license_id: &str,
         action: BindingAction,
@@ -797,8 +956,110 @@ impl Database {
         Ok(())
     }
 
-    /// Update last_seen_at timestamp for a license.
-    pub async fn update_last_seen(&self, license_id: &str) -> LicenseResult<bool> {
+    async fn list_binding_history(
+        &self,
+        filter: &BindingHistoryFilter,
+        limit: u32,
+        offset: u32,
+    ) -> LicenseResult<(Vec<LicenseBindingHistory>, u64)> {
+        let action = filter.action.map(|a| a.as_str());
+        let performed_by = filter.performed_by.map(|p| p.as_str());
+
+        match self {
+            #[cfg(feature = "sqlite")]
+            Database::SQLite(pool) => {
+                const WHERE: &str = "(? IS NULL OR license_id = ?) \
+                     AND (? IS NULL OR hardware_id = ?) \
+                     AND (? IS NULL OR action = ?) \
+                     AND (? IS NULL OR performed_by = ?)";
+
+                let count_sql =
+                    format!("SELECT COUNT(*) FROM license_binding_history WHERE {WHERE}");
+                let total: (i64,) = query_as(&count_sql)
+                    .bind(&filter.license_id)
+                    .bind(&filter.license_id)
+                    .bind(&filter.hardware_id)
+                    .bind(&filter.hardware_id)
+                    .bind(action)
+                    .bind(action)
+                    .bind(performed_by)
+                    .bind(performed_by)
+                    .fetch_one(pool)
+                    .await
+                    .map_err(|e| {
+                        error!("SQLite count binding history failed: {e}");
+                        LicenseError::ServerError(format!("database error: {e}"))
+                    })?;
+
+                let list_sql = format!(
+                    "SELECT * FROM license_binding_history WHERE {WHERE} \
+                     ORDER BY created_at DESC, id DESC LIMIT ? OFFSET ?"
+                );
+                let entries: Vec<LicenseBindingHistory> = query_as(&list_sql)
+                    .bind(&filter.license_id)
+                    .bind(&filter.license_id)
+                    .bind(&filter.hardware_id)
+                    .bind(&filter.hardware_id)
+                    .bind(action)
+                    .bind(action)
+                    .bind(performed_by)
+                    .bind(performed_by)
+                    .bind(i64::from(limit))
+                    .bind(i64::from(offset))
+                    .fetch_all(pool)
+                    .await
+                    .map_err(|e| {
+                        error!("SQLite list_binding_history failed: {e}");
+                        LicenseError::ServerError(format!("database error: {e}"))
+                    })?;
+
+                Ok((entries, total.0 as u64))
+            }
+            #[cfg(feature = "postgres")]
+            Database::Postgres(pool) => {
+                const WHERE: &str = "($1::TEXT IS NULL OR license_id = $1) \
+                     AND ($2::TEXT IS NULL OR hardware_id = $2) \
+                     AND ($3::TEXT IS NULL OR action = $3) \
+                     AND ($4::TEXT IS NULL OR performed_by = $4)";
+
+                let count_sql =
+                    format!("SELECT COUNT(*) FROM license_binding_history WHERE {WHERE}");
+                let total: (i64,) = query_as(&count_sql)
+                    .bind(&filter.license_id)
+                    .bind(&filter.hardware_id)
+                    .bind(action)
+                    .bind(performed_by)
+                    .fetch_one(pool)
+                    .await
+                    .map_err(|e| {
+                        error!("Postgres count binding history failed: {e}");
+                        LicenseError::ServerError(format!("database error: {e}"))
+                    })?;
+
+                let list_sql = format!(
+                    "SELECT * FROM license_binding_history WHERE {WHERE} \
+                     ORDER BY created_at DESC, id DESC LIMIT $5 OFFSET $6"
+                );
+                let entries: Vec<LicenseBindingHistory> = query_as(&list_sql)
+                    .bind(&filter.license_id)
+                    .bind(&filter.hardware_id)
+                    .bind(action)
+                    .bind(performed_by)
+                    .bind(i64::from(limit))
+                    .bind(i64::from(offset))
+                    .fetch_all(pool)
+                    .await
+                    .map_err(|e| {
+                        error!("Postgres list_binding_history failed: {e}");
+                        LicenseError::ServerError(format!("database error: {e}"))
+                    })?;
+
+                Ok((entries, total.0 as u64))
+            }
+        }
+    }
+
+    async fn update_last_seen(&self, license_id: &str) -> LicenseResult<bool> {
         let now = Utc::now().naive_utc();
 
         let rows_affected = match self {
@@ -833,171 +1094,1181 @@ impl Database {
         Ok(rows_affected > 0)
     }
 
-    /// Get licenses with expired grace periods (suspended licenses past their grace_period_ends_at).
-    pub async fn get_expired_grace_period_licenses(
-        &self,
-        now: NaiveDateTime,
-    ) -> LicenseResult<Vec<License>> {
+    async fn list_license_devices(&self, license_id: &str) -> LicenseResult<Vec<LicenseDevice>> {
         match self {
             #[cfg(feature = "sqlite")]
             Database::SQLite(pool) => {
-                let licenses: Vec<License> = query_as(
-                    "SELECT * FROM licenses WHERE status = 'suspended' AND grace_period_ends_at IS NOT NULL AND grace_period_ends_at < ?"
+                let devices: Vec<LicenseDevice> = query_as(
+                    "SELECT * FROM license_devices WHERE license_id = ? ORDER BY bound_at, hardware_id",
                 )
-                .bind(now)
+                .bind(license_id)
                 .fetch_all(pool)
                 .await
                 .map_err(|e| {
-                    error!("SQLite get_expired_grace_period_licenses failed: {e}");
+                    error!("SQLite list_license_devices failed: {e}");
                     LicenseError::ServerError(format!("database error: {e}"))
                 })?;
 
-                Ok(licenses)
+                Ok(devices)
             }
             #[cfg(feature = "postgres")]
             Database::Postgres(pool) => {
-                let licenses: Vec<License> = query_as(
-                    "SELECT * FROM licenses WHERE status = 'suspended' AND grace_period_ends_at IS NOT NULL AND grace_period_ends_at < $1"
+                let devices: Vec<LicenseDevice> = query_as(
+                    "SELECT * FROM license_devices WHERE license_id = $1 ORDER BY bound_at, hardware_id",
                 )
-                .bind(now)
+                .bind(license_id)
                 .fetch_all(pool)
                 .await
                 .map_err(|e| {
-                    error!("Postgres get_expired_grace_period_licenses failed: {e}");
+                    error!("Postgres list_license_devices failed: {e}");
                     LicenseError::ServerError(format!("database error: {e}"))
                 })?;
 
-                Ok(licenses)
+                Ok(devices)
             }
         }
     }
 
-    /// Get expired licenses (active licenses past their expires_at).
-    pub async fn get_expired_licenses(&self, now: NaiveDateTime) -> LicenseResult<Vec<License>> {
+    async fn get_license_device(
+        &self,
+        license_id: &str,
+        hardware_id: &str,
+    ) -> LicenseResult<Option<LicenseDevice>> {
         match self {
             #[cfg(feature = "sqlite")]
             Database::SQLite(pool) => {
-                let licenses: Vec<License> = query_as(
-                    "SELECT * FROM licenses WHERE status = 'active' AND expires_at IS NOT NULL AND expires_at < ?",
+                let device = query_as::<_, LicenseDevice>(
+                    "SELECT * FROM license_devices WHERE license_id = ? AND hardware_id = ?",
                 )
-                .bind(now)
-                .fetch_all(pool)
+                .bind(license_id)
+                .bind(hardware_id)
+                .fetch_optional(pool)
                 .await
                 .map_err(|e| {
-                    error!("SQLite get_expired_licenses failed: {e}");
+                    error!("SQLite get_license_device failed: {e}");
                     LicenseError::ServerError(format!("database error: {e}"))
                 })?;
 
-                Ok(licenses)
+                Ok(device)
             }
             #[cfg(feature = "postgres")]
             Database::Postgres(pool) => {
-                let licenses: Vec<License> = query_as(
-                    "SELECT * FROM licenses WHERE status = 'active' AND expires_at IS NOT NULL AND expires_at < $1",
+                let device = query_as::<_, LicenseDevice>(
+                    "SELECT * FROM license_devices WHERE license_id = $1 AND hardware_id = $2",
                 )
-                .bind(now)
-                .fetch_all(pool)
+                .bind(license_id)
+                .bind(hardware_id)
+                .fetch_optional(pool)
                 .await
                 .map_err(|e| {
-                    error!("Postgres get_expired_licenses failed: {e}");
+                    error!("Postgres get_license_device failed: {e}");
                     LicenseError::ServerError(format!("database error: {e}"))
                 })?;
 
-                Ok(licenses)
+                Ok(device)
             }
         }
     }
 
-    /// Get licenses bound to stale devices (not seen since threshold).
-    pub async fn get_stale_device_licenses(
+    async fn add_license_device(
         &self,
-        threshold: NaiveDateTime,
-    ) -> LicenseResult<Vec<License>> {
-        match self {
+        license_id: &str,
+        hardware_id: &str,
+        device_name: Option<&str>,
+        device_info: Option<&str>,
+    ) -> LicenseResult<bool> {
+        let now = Utc::now().naive_utc();
+
+        let added = match self {
             #[cfg(feature = "sqlite")]
             Database::SQLite(pool) => {
-                let licenses: Vec<License> = query_as(
-                    "SELECT * FROM licenses WHERE hardware_id IS NOT NULL AND last_seen_at IS NOT NULL AND last_seen_at < ?",
+                let fail = |e: sqlx::Error| {
+                    error!("SQLite add_license_device failed: {e}");
+                    LicenseError::ServerError(format!("database error: {e}"))
+                };
+
+                let mut tx = pool.begin().await.map_err(fail)?;
+                let added = sqlite_take_seat(
+                    &mut tx,
+                    license_id,
+                    hardware_id,
+                    device_name,
+                    device_info,
+                    now,
                 )
-                .bind(threshold)
-                .fetch_all(pool)
                 .await
-                .map_err(|e| {
-                    error!("SQLite get_stale_device_licenses failed: {e}");
-                    LicenseError::ServerError(format!("database error: {e}"))
-                })?;
-
-                Ok(licenses)
+                .map_err(fail)?;
+                tx.commit().await.map_err(fail)?;
+                added
             }
             #[cfg(feature = "postgres")]
             Database::Postgres(pool) => {
-                let licenses: Vec<License> = query_as(
-                    "SELECT * FROM licenses WHERE hardware_id IS NOT NULL AND last_seen_at IS NOT NULL AND last_seen_at < $1",
+                let fail = |e: sqlx::Error| {
+                    error!("Postgres add_license_device failed: {e}");
+                    LicenseError::ServerError(format!("database error: {e}"))
+                };
+
+                let mut tx = pool.begin().await.map_err(fail)?;
+                let added = pg_take_seat(
+                    &mut tx,
+                    license_id,
+                    hardware_id,
+                    device_name,
+                    device_info,
+                    now,
                 )
-                .bind(threshold)
-                .fetch_all(pool)
                 .await
-                .map_err(|e| {
-                    error!("Postgres get_stale_device_licenses failed: {e}");
-                    LicenseError::ServerError(format!("database error: {e}"))
-                })?;
-
-                Ok(licenses)
+                .map_err(fail)?;
+                tx.commit().await.map_err(fail)?;
+                added
             }
-        }
+        };
+
+        Ok(added)
     }
 
-    /// Update usage/quota fields for a license.
-    ///
-    /// Updates bandwidth_used_bytes, bandwidth_limit_bytes, and quota_exceeded.
-    pub async fn update_usage(
+    async fn claim_license_seat(
         &self,
-        license_id: &str,
-        bandwidth_used_bytes: i64,
-        bandwidth_limit_bytes: Option<i64>,
-        quota_exceeded: bool,
-    ) -> LicenseResult<bool> {
-        let rows_affected = match self {
+        license: &License,
+        hardware_id: &str,
+        device_name: Option<&str>,
+        device_info: Option<&str>,
+    ) -> LicenseResult<SeatClaim> {
+        let license_id = license.license_id.as_str();
+        let now = Utc::now().naive_utc();
+
+        match self {
             #[cfg(feature = "sqlite")]
-            Database::SQLite(pool) => query(
-                "UPDATE licenses SET \
-                         bandwidth_used_bytes = ?, \
-                         bandwidth_limit_bytes = ?, \
-                         quota_exceeded = ? \
-                     WHERE license_id = ?",
+            Database::SQLite(pool) => {
+                let fail = |e: sqlx::Error| {
+                    error!("SQLite claim_license_seat failed: {e}");
+                    LicenseError::ServerError(format!("database error: {e}"))
+                };
+
+                let mut tx = pool.begin_with("BEGIN IMMEDIATE").await.map_err(fail)?;
+
+                let max_devices: Option<i32> =
+                    query_scalar("SELECT max_devices FROM licenses WHERE license_id = ?")
+                        .bind(license_id)
+                        .fetch_optional(&mut *tx)
+                        .await
+                        .map_err(fail)?;
+                let Some(max_devices) = max_devices else {
+                    return Err(LicenseError::InvalidLicense(format!(
+                        "license {license_id} not found"
+                    )));
+                };
+
+                let devices: Vec<LicenseDevice> = query_as(
+                    "SELECT * FROM license_devices WHERE license_id = ? ORDER BY bound_at, hardware_id",
+                )
+                .bind(license_id)
+                .fetch_all(&mut *tx)
+                .await
+                .map_err(fail)?;
+
+                if let Some(device) = devices.iter().find(|d| d.hardware_id == hardware_id) {
+                    return Ok(SeatClaim::Existing(device.clone()));
+                }
+                if devices.len() >= seat_limit(max_devices) {
+                    return Ok(SeatClaim::Full(devices));
+                }
+
+                sqlite_take_seat(
+                    &mut tx,
+                    license_id,
+                    hardware_id,
+                    device_name,
+                    device_info,
+                    now,
+                )
+                .await
+                .map_err(fail)?;
+                tx.commit().await.map_err(fail)?;
+            }
+            #[cfg(feature = "postgres")]
+            Database::Postgres(pool) => {
+                let fail = |e: sqlx::Error| {
+                    error!("Postgres claim_license_seat failed: {e}");
+                    LicenseError::ServerError(format!("database error: {e}"))
+                };
+
+                let mut tx = pool.begin().await.map_err(fail)?;
+
+                let max_devices: Option<i32> = query_scalar(
+                    "SELECT max_devices FROM licenses WHERE license_id = $1 FOR UPDATE",
+                )
+                .bind(license_id)
+                .fetch_optional(&mut *tx)
+                .await
+                .map_err(fail)?;
+                let Some(max_devices) = max_devices else {
+                    return Err(LicenseError::InvalidLicense(format!(
+                        "license {license_id} not found"
+                    )));
+                };
+
+                let devices: Vec<LicenseDevice> = query_as(
+                    "SELECT * FROM license_devices WHERE license_id = $1 ORDER BY bound_at, hardware_id",
+                )
+                .bind(license_id)
+                .fetch_all(&mut *tx)
+                .await
+                .map_err(fail)?;
+
+                if let Some(device) = devices.iter().find(|d| d.hardware_id == hardware_id) {
+                    return Ok(SeatClaim::Existing(device.clone()));
+                }
+                if devices.len() >= seat_limit(max_devices) {
+                    return Ok(SeatClaim::Full(devices));
+                }
+
+                pg_take_seat(
+                    &mut tx,
+                    license_id,
+                    hardware_id,
+                    device_name,
+                    device_info,
+                    now,
+                )
+                .await
+                .map_err(fail)?;
+                tx.commit().await.map_err(fail)?;
+            }
+        }
+
+        Ok(SeatClaim::Claimed)
+    }
+
+    async fn remove_license_device(
+        &self,
+        license_id: &str,
+        hardware_id: &str,
+    ) -> LicenseResult<bool> {
+        let rows_affected = match self {
+            #[cfg(feature = "sqlite")]
+            Database::SQLite(pool) => {
+                query("DELETE FROM license_devices WHERE license_id = ? AND hardware_id = ?")
+                    .bind(license_id)
+                    .bind(hardware_id)
+                    .execute(pool)
+                    .await
+                    .map_err(|e| {
+                        error!("SQLite remove_license_device failed: {e}");
+                        LicenseError::ServerError(format!("database error: {e}"))
+                    })?
+                    .rows_affected()
+            }
+            #[cfg(feature = "postgres")]
+            Database::Postgres(pool) => {
+                query("DELETE FROM license_devices WHERE license_id = $1 AND hardware_id = $2")
+                    .bind(license_id)
+                    .bind(hardware_id)
+                    .execute(pool)
+                    .await
+                    .map_err(|e| {
+                        error!("Postgres remove_license_device failed: {e}");
+                        LicenseError::ServerError(format!("database error: {e}"))
+                    })?
+                    .rows_affected()
+            }
+        };
+
+        if rows_affected > 0 {
+            self.checkin_license_lease(license_id, hardware_id).await?;
+            self.sync_binding_fields(license_id).await?;
+        }
+
+        Ok(rows_affected > 0)
+    }
+
+    async fn remove_all_license_devices(&self, license_id: &str) -> LicenseResult<u64> {
+        let rows_affected = match self {
+            #[cfg(feature = "sqlite")]
+            Database::SQLite(pool) => query("DELETE FROM license_devices WHERE license_id = ?")
+                .bind(license_id)
+                .execute(pool)
+                .await
+                .map_err(|e| {
+                    error!("SQLite remove_all_license_devices failed: {e}");
+                    LicenseError::ServerError(format!("database error: {e}"))
+                })?
+                .rows_affected(),
+            #[cfg(feature = "postgres")]
+            Database::Postgres(pool) => query("DELETE FROM license_devices WHERE license_id = $1")
+                .bind(license_id)
+                .execute(pool)
+                .await
+                .map_err(|e| {
+                    error!("Postgres remove_all_license_devices failed: {e}");
+                    LicenseError::ServerError(format!("database error: {e}"))
+                })?
+                .rows_affected(),
+        };
+
+        self.remove_all_license_leases(license_id).await?;
+        self.release_license(license_id).await?;
+
+        Ok(rows_affected)
+    }
+
+    async fn touch_license_device(
+        &self,
+        license_id: &str,
+        hardware_id: &str,
+    ) -> LicenseResult<bool> {
+        let now = Utc::now().naive_utc();
+
+        let rows_affected = match self {
+            #[cfg(feature = "sqlite")]
+            Database::SQLite(pool) => query(
+                "UPDATE license_devices SET last_seen_at = ? WHERE license_id = ? AND hardware_id = ?",
             )
-            .bind(bandwidth_used_bytes)
-            .bind(bandwidth_limit_bytes)
-            .bind(quota_exceeded)
+            .bind(now)
             .bind(license_id)
+            .bind(hardware_id)
             .execute(pool)
             .await
             .map_err(|e| {
-                error!("SQLite update_usage failed: {e}");
+                error!("SQLite touch_license_device failed: {e}");
                 LicenseError::ServerError(format!("database error: {e}"))
             })?
             .rows_affected(),
             #[cfg(feature = "postgres")]
             Database::Postgres(pool) => query(
-                "UPDATE licenses SET \
-                         bandwidth_used_bytes = $1, \
-                         bandwidth_limit_bytes = $2, \
-                         quota_exceeded = $3 \
-                     WHERE license_id = $4",
+                "UPDATE license_devices SET last_seen_at = $1 WHERE license_id = $2 AND hardware_id = $3",
+            )
+            .bind(now)
+            .bind(license_id)
+            .bind(hardware_id)
+            .execute(pool)
+            .await
+            .map_err(|e| {
+                error!("Postgres touch_license_device failed: {e}");
+                LicenseError::ServerError(format!("database error: {e}"))
+            })?
+            .rows_affected(),
+        };
+
+        self.update_last_seen(license_id).await?;
+
+        Ok(rows_affected > 0)
+    }
+
+    async fn list_active_leases(
+        &self,
+        license_id: &str,
+        now: NaiveDateTime,
+    ) -> LicenseResult<Vec<LicenseLease>> {
+        match self {
+            #[cfg(feature = "sqlite")]
+            Database::SQLite(pool) => {
+                let leases: Vec<LicenseLease> = query_as(
+                    "SELECT * FROM license_leases WHERE license_id = ? AND expires_at > ? \
+                     ORDER BY checked_out_at, hardware_id",
+                )
+                .bind(license_id)
+                .bind(now)
+                .fetch_all(pool)
+                .await
+                .map_err(|e| {
+                    error!("SQLite list_active_leases failed: {e}");
+                    LicenseError::ServerError(format!("database error: {e}"))
+                })?;
+
+                Ok(leases)
+            }
+            #[cfg(feature = "postgres")]
+            Database::Postgres(pool) => {
+                let leases: Vec<LicenseLease> = query_as(
+                    "SELECT * FROM license_leases WHERE license_id = $1 AND expires_at > $2 \
+                     ORDER BY checked_out_at, hardware_id",
+                )
+                .bind(license_id)
+                .bind(now)
+                .fetch_all(pool)
+                .await
+                .map_err(|e| {
+                    error!("Postgres list_active_leases failed: {e}");
+                    LicenseError::ServerError(format!("database error: {e}"))
+                })?;
+
+                Ok(leases)
+            }
+        }
+    }
+
+    async fn checkout_license_lease(
+        &self,
+        license: &License,
+        hardware_id: &str,
+        ttl: Duration,
+    ) -> LicenseResult<LeaseCheckout> {
+        let license_id = license.license_id.as_str();
+        let now = Utc::now().naive_utc();
+        let expires_at = now + ttl;
+
+        match self {
+            #[cfg(feature = "sqlite")]
+            Database::SQLite(pool) => {
+                let fail = |e: sqlx::Error| {
+                    error!("SQLite checkout_license_lease failed: {e}");
+                    LicenseError::ServerError(format!("database error: {e}"))
+                };
+
+                let mut tx = pool.begin_with("BEGIN IMMEDIATE").await.map_err(fail)?;
+
+                let max_concurrent: Option<Option<i32>> =
+                    query_scalar("SELECT max_concurrent FROM licenses WHERE license_id = ?")
+                        .bind(license_id)
+                        .fetch_optional(&mut *tx)
+                        .await
+                        .map_err(fail)?;
+                let Some(max_concurrent) = max_concurrent else {
+                    return Err(LicenseError::InvalidLicense(format!(
+                        "license {license_id} not found"
+                    )));
+                };
+
+                let leases: Vec<LicenseLease> = query_as(
+                    "SELECT * FROM license_leases WHERE license_id = ? AND expires_at > ? \
+                     ORDER BY checked_out_at, hardware_id",
+                )
+                .bind(license_id)
+                .bind(now)
+                .fetch_all(&mut *tx)
+                .await
+                .map_err(fail)?;
+
+                let lease = match leases.iter().find(|l| l.hardware_id == hardware_id) {
+                    Some(lease) => LicenseLease {
+                        expires_at,
+                        ..lease.clone()
+                    },
+                    None if lease_limit_reached(max_concurrent, leases.len()) => {
+                        return Ok(LeaseCheckout::Full(leases.len()));
+                    }
+                    None => LicenseLease {
+                        license_id: license_id.to_string(),
+                        hardware_id: hardware_id.to_string(),
+                        checked_out_at: now,
+                        expires_at,
+                    },
+                };
+
+                query(
+                    "INSERT INTO license_leases (license_id, hardware_id, checked_out_at, expires_at) \
+                     VALUES (?, ?, ?, ?) \
+                     ON CONFLICT (license_id, hardware_id) DO UPDATE SET \
+                         checked_out_at = excluded.checked_out_at, \
+                         expires_at = excluded.expires_at",
+                )
+                .bind(&lease.license_id)
+                .bind(&lease.hardware_id)
+                .bind(lease.checked_out_at)
+                .bind(lease.expires_at)
+                .execute(&mut *tx)
+                .await
+                .map_err(fail)?;
+                tx.commit().await.map_err(fail)?;
+
+                Ok(LeaseCheckout::Granted(lease))
+            }
+            #[cfg(feature = "postgres")]
+            Database::Postgres(pool) => {
+                let fail = |e: sqlx::Error| {
+                    error!("Postgres checkout_license_lease failed: {e}");
+                    LicenseError::ServerError(format!("database error: {e}"))
+                };
+
+                let mut tx = pool.begin().await.map_err(fail)?;
+
+                let max_concurrent: Option<Option<i32>> = query_scalar(
+                    "SELECT max_concurrent FROM licenses WHERE license_id = $1 FOR UPDATE",
+                )
+                .bind(license_id)
+                .fetch_optional(&mut *tx)
+                .await
+                .map_err(fail)?;
+                let Some(max_concurrent) = max_concurrent else {
+                    return Err(LicenseError::InvalidLicense(format!(
+                        "license {license_id} not found"
+                    )));
+                };
+
+                let leases: Vec<LicenseLease> = query_as(
+                    "SELECT * FROM license_leases WHERE license_id = $1 AND expires_at > $2 \
+                     ORDER BY checked_out_at, hardware_id",
+                )
+                .bind(license_id)
+                .bind(now)
+                .fetch_all(&mut *tx)
+                .await
+                .map_err(fail)?;
+
+                let lease = match leases.iter().find(|l| l.hardware_id == hardware_id) {
+                    Some(lease) => LicenseLease {
+                        expires_at,
+                        ..lease.clone()
+                    },
+                    None if lease_limit_reached(max_concurrent, leases.len()) => {
+                        return Ok(LeaseCheckout::Full(leases.len()));
+                    }
+                    None => LicenseLease {
+                        license_id: license_id.to_string(),
+                        hardware_id: hardware_id.to_string(),
+                        checked_out_at: now,
+                        expires_at,
+                    },
+                };
+
+                query(
+                    "INSERT INTO license_leases (license_id, hardware_id, checked_out_at, expires_at) \
+                     VALUES ($1, $2, $3, $4) \
+                     ON CONFLICT (license_id, hardware_id) DO UPDATE SET \
+                         checked_out_at = EXCLUDED.checked_out_at, \
+                         expires_at = EXCLUDED.expires_at",
+                )
+                .bind(&lease.license_id)
+                .bind(&lease.hardware_id)
+                .bind(lease.checked_out_at)
+                .bind(lease.expires_at)
+                .execute(&mut *tx)
+                .await
+                .map_err(fail)?;
+                tx.commit().await.map_err(fail)?;
+
+                Ok(LeaseCheckout::Granted(lease))
+            }
+        }
+    }
+
+    async fn renew_license_lease(
+        &self,
+        license_id: &str,
+        hardware_id: &str,
+        ttl: Duration,
+    ) -> LicenseResult<Option<NaiveDateTime>> {
+        let now = Utc::now().naive_utc();
+        let expires_at = now + ttl;
+
+        let rows_affected = match self {
+            #[cfg(feature = "sqlite")]
+            Database::SQLite(pool) => query(
+                "UPDATE license_leases SET expires_at = ? \
+                 WHERE license_id = ? AND hardware_id = ? AND expires_at > ?",
             )
-            .bind(bandwidth_used_bytes)
-            .bind(bandwidth_limit_bytes)
-            .bind(quota_exceeded)
+            .bind(expires_at)
             .bind(license_id)
+            .bind(hardware_id)
+            .bind(now)
+            .execute(pool)
+            .await
+            .map_err(|e| {
+                error!("SQLite renew_license_lease failed: {e}");
+                LicenseError::ServerError(format!("database error: {e}"))
+            })?
+            .rows_affected(),
+            #[cfg(feature = "postgres")]
+            Database::Postgres(pool) => query(
+                "UPDATE license_leases SET expires_at = $1 \
+                 WHERE license_id = $2 AND hardware_id = $3 AND expires_at > $4",
+            )
+            .bind(expires_at)
+            .bind(license_id)
+            .bind(hardware_id)
+            .bind(now)
             .execute(pool)
             .await
             .map_err(|e| {
-                error!("Postgres update_usage failed: {e}");
+                error!("Postgres renew_license_lease failed: {e}");
                 LicenseError::ServerError(format!("database error: {e}"))
             })?
             .rows_affected(),
         };
 
+        Ok((rows_affected > 0).then_some(expires_at))
+    }
+
+    async fn checkin_license_lease(
+        &self,
+        license_id: &str,
+        hardware_id: &str,
+    ) -> LicenseResult<bool> {
+        let rows_affected = match self {
+            #[cfg(feature = "sqlite")]
+            Database::SQLite(pool) => {
+                query("DELETE FROM license_leases WHERE license_id = ? AND hardware_id = ?")
+                    .bind(license_id)
+                    .bind(hardware_id)
+                    .execute(pool)
+                    .await
+                    .map_err(|e| {
+                        error!("SQLite checkin_license_lease failed: {e}");
+                        LicenseError::ServerError(format!("database error: {e}"))
+                    })?
+                    .rows_affected()
+            }
+            #[cfg(feature = "postgres")]
+            Database::Postgres(pool) => {
+                query("DELETE FROM license_leases WHERE license_id = $1 AND hardware_id = $2")
+                    .bind(license_id)
+                    .bind(hardware_id)
+                    .execute(pool)
+                    .await
+                    .map_err(|e| {
+                        error!("Postgres checkin_license_lease failed: {e}");
+                        LicenseError::ServerError(format!("database error: {e}"))
+                    })?
+                    .rows_affected()
+            }
+        };
+
+        Ok(rows_affected > 0)
+    }
+
+    async fn delete_expired_leases(&self, now: NaiveDateTime) -> LicenseResult<u64> {
+        let rows_affected = match self {
+            #[cfg(feature = "sqlite")]
+            Database::SQLite(pool) => query("DELETE FROM license_leases WHERE expires_at <= ?")
+                .bind(now)
+                .execute(pool)
+                .await
+                .map_err(|e| {
+                    error!("SQLite delete_expired_leases failed: {e}");
+                    LicenseError::ServerError(format!("database error: {e}"))
+                })?
+                .rows_affected(),
+            #[cfg(feature = "postgres")]
+            Database::Postgres(pool) => query("DELETE FROM license_leases WHERE expires_at <= $1")
+                .bind(now)
+                .execute(pool)
+                .await
+                .map_err(|e| {
+                    error!("Postgres delete_expired_leases failed: {e}");
+                    LicenseError::ServerError(format!("database error: {e}"))
+                })?
+                .rows_affected(),
+        };
+
+        Ok(rows_affected)
+    }
+
+    async fn update_license_signature(
+        &self,
+        license_id: &str,
+        signature: &str,
+    ) -> LicenseResult<bool> {
+        let rows_affected = match self {
+            #[cfg(feature = "sqlite")]
+            Database::SQLite(pool) => {
+                query("UPDATE licenses SET signature = ? WHERE license_id = ?")
+                    .bind(signature)
+                    .bind(license_id)
+                    .execute(pool)
+                    .await
+                    .map_err(|e| {
+                        error!("SQLite update_license_signature failed: {e}");
+                        LicenseError::ServerError(format!("database error: {e}"))
+                    })?
+                    .rows_affected()
+            }
+            #[cfg(feature = "postgres")]
+            Database::Postgres(pool) => {
+                query("UPDATE licenses SET signature = $1 WHERE license_id = $2")
+                    .bind(signature)
+                    .bind(license_id)
+                    .execute(pool)
+                    .await
+                    .map_err(|e| {
+                        error!("Postgres update_license_signature failed: {e}");
+                        LicenseError::ServerError(format!("database error: {e}"))
+                    })?
+                    .rows_affected()
+            }
+        };
+
         Ok(rows_affected > 0)
     }
+
+    async fn get_expired_grace_period_licenses(
+        &self,
+        now: NaiveDateTime,
+    ) -> LicenseResult<Vec<License>> {
+        match self {
+            #[cfg(feature = "sqlite")]
+            Database::SQLite(pool) => {
+                let licenses: Vec<License> = query_as(
+                    "SELECT * FROM licenses WHERE status = 'suspended' AND grace_period_ends_at IS NOT NULL AND grace_period_ends_at < ?"
+                )
+                .bind(now)
+                .fetch_all(pool)
+                .await
+                .map_err(|e| {
+                    error!("SQLite get_expired_grace_period_licenses failed: {e}");
+                    LicenseError::ServerError(format!("database error: {e}"))
+                })?;
+
+                Ok(licenses)
+            }
+            #[cfg(feature = "postgres")]
+            Database::Postgres(pool) => {
+                let licenses: Vec<License> = query_as(
+                    "SELECT * FROM licenses WHERE status = 'suspended' AND grace_period_ends_at IS NOT NULL AND grace_period_ends_at < $1"
+                )
+                .bind(now)
+                .fetch_all(pool)
+                .await
+                .map_err(|e| {
+                    error!("Postgres get_expired_grace_period_licenses failed: {e}");
+                    LicenseError::ServerError(format!("database error: {e}"))
+                })?;
+
+                Ok(licenses)
+            }
+        }
+    }
+
+    async fn get_expired_licenses(&self, now: NaiveDateTime) -> LicenseResult<Vec<License>> {
+        match self {
+            #[cfg(feature = "sqlite")]
+            Database::SQLite(pool) => {
+                let licenses: Vec<License> = query_as(
+                    "SELECT * FROM licenses WHERE status = 'active' AND expires_at IS NOT NULL AND expires_at < ?",
+                )
+                .bind(now)
+                .fetch_all(pool)
+                .await
+                .map_err(|e| {
+                    error!("SQLite get_expired_licenses failed: {e}");
+                    LicenseError::ServerError(format!("database error: {e}"))
+                })?;
+
+                Ok(licenses)
+            }
+            #[cfg(feature = "postgres")]
+            Database::Postgres(pool) => {
+                let licenses: Vec<License> = query_as(
+                    "SELECT * FROM licenses WHERE status = 'active' AND expires_at IS NOT NULL AND expires_at < $1",
+                )
+                .bind(now)
+                .fetch_all(pool)
+                .await
+                .map_err(|e| {
+                    error!("Postgres get_expired_licenses failed: {e}");
+                    LicenseError::ServerError(format!("database error: {e}"))
+                })?;
+
+                Ok(licenses)
+            }
+        }
+    }
+
+    async fn get_stale_devices(
+        &self,
+        threshold: NaiveDateTime,
+    ) -> LicenseResult<Vec<LicenseDevice>> {
+        match self {
+            #[cfg(feature = "sqlite")]
+            Database::SQLite(pool) => {
+                let devices: Vec<LicenseDevice> = query_as(
+                    "SELECT * FROM license_devices WHERE last_seen_at IS NOT NULL AND last_seen_at < ?",
+                )
+                .bind(threshold)
+                .fetch_all(pool)
+                .await
+                .map_err(|e| {
+                    error!("SQLite get_stale_devices failed: {e}");
+                    LicenseError::ServerError(format!("database error: {e}"))
+                })?;
+
+                Ok(devices)
+            }
+            #[cfg(feature = "postgres")]
+            Database::Postgres(pool) => {
+                let devices: Vec<LicenseDevice> = query_as(
+                    "SELECT * FROM license_devices WHERE last_seen_at IS NOT NULL AND last_seen_at < $1",
+                )
+                .bind(threshold)
+                .fetch_all(pool)
+                .await
+                .map_err(|e| {
+                    error!("Postgres get_stale_devices failed: {e}");
+                    LicenseError::ServerError(format!("database error: {e}"))
+                })?;
+
+                Ok(devices)
+            }
+        }
+    }
+}
+
+/// Number of seats a license offers; every license has at least one.
+pub(crate) fn seat_limit(max_devices: i32) -> usize {
+    usize::try_from(max_devices).unwrap_or(0).max(1)
+}
+
+/// Whether `active` leases use up a license's `max_concurrent` (None = no limit).
+pub(crate) fn lease_limit_reached(max_concurrent: Option<i32>, active: usize) -> bool {
+    max_concurrent.is_some_and(|max| active >= usize::try_from(max).unwrap_or(0))
+}
+
+/// Insert a seat for a device and point the license's binding fields at it.
+///
+/// Runs on the caller's connection so it can share a transaction with the
+/// seat limit check. Returns `false` if the device already held a seat.
+#[cfg(feature = "sqlite")]
+async fn sqlite_take_seat(
+    conn: &mut SqliteConnection,
+    license_id: &str,
+    hardware_id: &str,
+    device_name: Option<&str>,
+    device_info: Option<&str>,
+    now: NaiveDateTime,
+) -> Result<bool, sqlx::Error> {
+    let rows_affected = query(
+        "INSERT INTO license_devices \
+             (license_id, hardware_id, device_name, device_info, bound_at, last_seen_at) \
+         VALUES (?, ?, ?, ?, ?, ?) \
+         ON CONFLICT (license_id, hardware_id) DO NOTHING",
+    )
+    .bind(license_id)
+    .bind(hardware_id)
+    .bind(device_name)
+    .bind(device_info)
+    .bind(now)
+    .bind(now)
+    .execute(&mut *conn)
+    .await?
+    .rows_affected();
+
+    if rows_affected == 0 {
+        return Ok(false);
+    }
+
+    query(
+        "UPDATE licenses SET \
+             hardware_id = ?, \
+             device_name = ?, \
+             device_info = ?, \
+             bound_at = ?, \
+             last_seen_at = ? \
+         WHERE license_id = ?",
+    )
+    .bind(hardware_id)
+    .bind(device_name)
+    .bind(device_info)
+    .bind(now)
+    .bind(now)
+    .bind(license_id)
+    .execute(&mut *conn)
+    .await?;
+
+    Ok(true)
+}
+
+/// Postgres counterpart of [`sqlite_take_seat`].
+#[cfg(feature = "postgres")]
+async fn pg_take_seat(
+    conn: &mut PgConnection,
+    license_id: &str,
+    hardware_id: &str,
+    device_name: Option<&str>,
+    device_info: Option<&str>,
+    now: NaiveDateTime,
+) -> Result<bool, sqlx::Error> {
+    let rows_affected = query(
+        "INSERT INTO license_devices \
+             (license_id, hardware_id, device_name, device_info, bound_at, last_seen_at) \
+         VALUES ($1, $2, $3, $4, $5, $6) \
+         ON CONFLICT (license_id, hardware_id) DO NOTHING",
+    )
+    .bind(license_id)
+    .bind(hardware_id)
+    .bind(device_name)
+    .bind(device_info)
+    .bind(now)
+    .bind(now)
+    .execute(&mut *conn)
+    .await?
+    .rows_affected();
+
+    if rows_affected == 0 {
+        return Ok(false);
+    }
+
+    query(
+        "UPDATE licenses SET \
+             hardware_id = $1, \
+             device_name = $2, \
+             device_info = $3, \
+             bound_at = $4, \
+             last_seen_at = $5 \
+         WHERE license_id = $6",
+    )
+    .bind(hardware_id)
+    .bind(device_name)
+    .bind(device_info)
+    .bind(now)
+    .bind(now)
+    .bind(license_id)
+    .execute(&mut *conn)
+    .await?;
+
+    Ok(true)
+}
+
+/// Insert a license, or overwrite every field of an existing one.
+///
+/// `usage_period_start` is only written on insert; afterwards it belongs to
+/// [`UsageStore::close_usage_period`](crate::server::store::UsageStore::close_usage_period),
+/// so a handler saving a stale copy can't move the period back.
+///
+/// Runs on the caller's connection so several writes can share a transaction.
+#[cfg(feature = "sqlite")]
+pub(crate) async fn sqlite_upsert_license(
+    conn: &mut SqliteConnection,
+    license: &License,
+) -> Result<(), sqlx::Error> {
+    query(
+        r#"
+        INSERT INTO licenses (
+            license_id, client_id, status, features, issued_at, expires_at,
+            hardware_id, signature, last_heartbeat, org_id, org_name,
+            license_key, tier, device_name, device_info, bound_at,
+            last_seen_at, suspended_at, revoked_at, revoke_reason,
+            grace_period_ends_at, suspension_message, is_blacklisted,
+            blacklisted_at, blacklist_reason, metadata,
+            bandwidth_used_bytes, bandwidth_limit_bytes, quota_exceeded,
+            max_devices, max_concurrent, offline_days,
+            billing_cycle, usage_period_start, suspended_by_org
+        )
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+        ON CONFLICT(license_id) DO UPDATE SET
+            client_id            = excluded.client_id,
+            status               = excluded.status,
+            features             = excluded.features,
+            issued_at            = excluded.issued_at,
+            expires_at           = excluded.expires_at,
+            hardware_id          = excluded.hardware_id,
+            signature            = excluded.signature,
+            last_heartbeat       = excluded.last_heartbeat,
+            org_id               = excluded.org_id,
+            org_name             = excluded.org_name,
+            license_key          = excluded.license_key,
+            tier                 = excluded.tier,
+            device_name          = excluded.device_name,
+            device_info          = excluded.device_info,
+            bound_at             = excluded.bound_at,
+            last_seen_at         = excluded.last_seen_at,
+            suspended_at         = excluded.suspended_at,
+            revoked_at           = excluded.revoked_at,
+            revoke_reason        = excluded.revoke_reason,
+            grace_period_ends_at = excluded.grace_period_ends_at,
+            suspension_message   = excluded.suspension_message,
+            is_blacklisted       = excluded.is_blacklisted,
+            blacklisted_at       = excluded.blacklisted_at,
+            blacklist_reason     = excluded.blacklist_reason,
+            metadata             = excluded.metadata,
+            bandwidth_used_bytes = excluded.bandwidth_used_bytes,
+            bandwidth_limit_bytes = excluded.bandwidth_limit_bytes,
+            quota_exceeded       = excluded.quota_exceeded,
+            max_devices          = excluded.max_devices,
+            max_concurrent       = excluded.max_concurrent,
+            offline_days         = excluded.offline_days,
+            billing_cycle        = excluded.billing_cycle,
+            suspended_by_org     = excluded.suspended_by_org
+        "#,
+    )
+    .bind(&license.license_id)
+    .bind(&license.client_id)
+    .bind(&license.status)
+    .bind(&license.features)
+    .bind(license.issued_at)
+    .bind(license.expires_at)
+    .bind(&license.hardware_id)
+    .bind(&license.signature)
+    .bind(license.last_heartbeat)
+    .bind(&license.org_id)
+    .bind(&license.org_name)
+    .bind(&license.license_key)
+    .bind(&license.tier)
+    .bind(&license.device_name)
+    .bind(&license.device_info)
+    .bind(license.bound_at)
+    .bind(license.last_seen_at)
+    .bind(license.suspended_at)
+    .bind(license.revoked_at)
+    .bind(&license.revoke_reason)
+    .bind(license.grace_period_ends_at)
+    .bind(&license.suspension_message)
+    .bind(license.is_blacklisted)
+    .bind(license.blacklisted_at)
+    .bind(&license.blacklist_reason)
+    .bind(&license.metadata)
+    .bind(license.bandwidth_used_bytes)
+    .bind(license.bandwidth_limit_bytes)
+    .bind(license.quota_exceeded)
+    .bind(license.max_devices)
+    .bind(license.max_concurrent)
+    .bind(license.offline_days)
+    .bind(&license.billing_cycle)
+    .bind(license.usage_period_start)
+    .bind(license.suspended_by_org)
+    .execute(&mut *conn)
+    .await?;
+
+    Ok(())
+}
+
+/// Postgres counterpart of [`sqlite_upsert_license`].
+#[cfg(feature = "postgres")]
+pub(crate) async fn pg_upsert_license(
+    conn: &mut PgConnection,
+    license: &License,
+) -> Result<(), sqlx::Error> {
+    query(
+        r#"
+        INSERT INTO licenses (
+            license_id, client_id, status, features, issued_at, expires_at,
+            hardware_id, signature, last_heartbeat, org_id, org_name,
+            license_key, tier, device_name, device_info, bound_at,
+            last_seen_at, suspended_at, revoked_at, revoke_reason,
+            grace_period_ends_at, suspension_message, is_blacklisted,
+            blacklisted_at, blacklist_reason, metadata,
+            bandwidth_used_bytes, bandwidth_limit_bytes, quota_exceeded,
+            max_devices, max_concurrent, offline_days,
+            billing_cycle, usage_period_start, suspended_by_org
+        )
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18, $19, $20, $21, $22, $23, $24, $25, $26, $27, $28, $29, $30, $31, $32, $33, $34, $35)
+        ON CONFLICT (license_id) DO UPDATE SET
+            client_id            = EXCLUDED.client_id,
+            status               = EXCLUDED.status,
+            features             = EXCLUDED.features,
+            issued_at            = EXCLUDED.issued_at,
+            expires_at           = EXCLUDED.expires_at,
+            hardware_id          = EXCLUDED.hardware_id,
+            signature            = EXCLUDED.signature,
+            last_heartbeat       = EXCLUDED.last_heartbeat,
+            org_id               = EXCLUDED.org_id,
+            org_name             = EXCLUDED.org_name,
+            license_key          = EXCLUDED.license_key,
+            tier                 = EXCLUDED.tier,
+            device_name          = EXCLUDED.device_name,
+            device_info          = EXCLUDED.device_info,
+            bound_at             = EXCLUDED.bound_at,
+            last_seen_at         = EXCLUDED.last_seen_at,
+            suspended_at         = EXCLUDED.suspended_at,
+            revoked_at           = EXCLUDED.revoked_at,
+            revoke_reason        = EXCLUDED.revoke_reason,
+            grace_period_ends_at = EXCLUDED.grace_period_ends_at,
+            suspension_message   = EXCLUDED.suspension_message,
+            is_blacklisted       = EXCLUDED.is_blacklisted,
+            blacklisted_at       = EXCLUDED.blacklisted_at,
+            blacklist_reason     = EXCLUDED.blacklist_reason,
+            metadata             = EXCLUDED.metadata,
+            bandwidth_used_bytes = EXCLUDED.bandwidth_used_bytes,
+            bandwidth_limit_bytes = EXCLUDED.bandwidth_limit_bytes,
+            quota_exceeded       = EXCLUDED.quota_exceeded,
+            max_devices          = EXCLUDED.max_devices,
+            max_concurrent       = EXCLUDED.max_concurrent,
+            offline_days         = EXCLUDED.offline_days,
+            billing_cycle        = EXCLUDED.billing_cycle,
+            suspended_by_org     = EXCLUDED.suspended_by_org
+        "#,
+    )
+    .bind(&license.license_id)
+    .bind(&license.client_id)
+    .bind(&license.status)
+    .bind(&license.features)
+    .bind(license.issued_at)
+    .bind(license.expires_at)
+    .bind(&license.hardware_id)
+    .bind(&license.signature)
+    .bind(license.last_heartbeat)
+    .bind(&license.org_id)
+    .bind(&license.org_name)
+    .bind(&license.license_key)
+    .bind(&license.tier)
+    .bind(&license.device_name)
+    .bind(&license.device_info)
+    .bind(license.bound_at)
+    .bind(license.last_seen_at)
+    .bind(license.suspended_at)
+    .bind(license.revoked_at)
+    .bind(&license.revoke_reason)
+    .bind(license.grace_period_ends_at)
+    .bind(&license.suspension_message)
+    .bind(license.is_blacklisted)
+    .bind(license.blacklisted_at)
+    .bind(&license.blacklist_reason)
+    .bind(&license.metadata)
+    .bind(license.bandwidth_used_bytes)
+    .bind(license.bandwidth_limit_bytes)
+    .bind(license.quota_exceeded)
+    .bind(license.max_devices)
+    .bind(license.max_concurrent)
+    .bind(license.offline_days)
+    .bind(&license.billing_cycle)
+    .bind(license.usage_period_start)
+    .bind(license.suspended_by_org)
+    .execute(&mut *conn)
+    .await?;
+
+    Ok(())
+}
+
+/// Remove every seat and lease of a license and clear its binding fields.
+#[cfg(feature = "sqlite")]
+async fn sqlite_free_seats(
+    conn: &mut SqliteConnection,
+    license_id: &str,
+) -> Result<(), sqlx::Error> {
+    query("DELETE FROM license_devices WHERE license_id = ?")
+        .bind(license_id)
+        .execute(&mut *conn)
+        .await?;
+    query("DELETE FROM license_leases WHERE license_id = ?")
+        .bind(license_id)
+        .execute(&mut *conn)
+        .await?;
+    query(
+        "UPDATE licenses SET \
+             hardware_id = NULL, \
+             device_name = NULL, \
+             device_info = NULL, \
+             bound_at = NULL \
+         WHERE license_id = ?",
+    )
+    .bind(license_id)
+    .execute(&mut *conn)
+    .await?;
+
+    Ok(())
+}
+
+/// Postgres counterpart of [`sqlite_free_seats`].
+#[cfg(feature = "postgres")]
+async fn pg_free_seats(conn: &mut PgConnection, license_id: &str) -> Result<(), sqlx::Error> {
+    query("DELETE FROM license_devices WHERE license_id = $1")
+        .bind(license_id)
+        .execute(&mut *conn)
+        .await?;
+    query("DELETE FROM license_leases WHERE license_id = $1")
+        .bind(license_id)
+        .execute(&mut *conn)
+        .await?;
+    query(
+        "UPDATE licenses SET \
+             hardware_id = NULL, \
+             device_name = NULL, \
+             device_info = NULL, \
+             bound_at = NULL \
+         WHERE license_id = $1",
+    )
+    .bind(license_id)
+    .execute(&mut *conn)
+    .await?;
+
+    Ok(())
 }
@@ -14,21 +14,27 @@ use utoipa::ToSchema;
 
 use crate::errors::{LicenseError, LicenseResult};
 use crate::server::api_error::ApiError;
-use crate::server::database::{Database, License};
+use crate::server::database::License;
+use crate::server::store::LicenseStore;
+use crate::signing::LicenseSigner;
 
 #[cfg(feature = "jwt-auth")]
 use crate::server::auth::AuthState;
 
 /// Shared application state for handlers.
 ///
-/// Right now this only wraps the database, but later you can add:
-/// config, key material, metrics handles, etc.
+/// Wraps the license store, auth state and key material. `db` is usually a
+/// [`Database`](crate::server::database::Database), but any
+/// [`LicenseStore`] works. Later you can add:
+/// config, metrics handles, etc.
 /// without touching every handler signature.
 #[derive(Clone)]
 pub struct AppState {
-    pub db: Arc<Database>,
+    pub db: Arc<dyn LicenseStore>,
     #[cfg(feature = "jwt-auth")]
     pub auth: AuthState,
+    /// Signs license documents for offline verification (None = not configured)
+    pub signer: Option<Arc<LicenseSigner>>,
 }
 
 /// Map internal LicenseError into an HTTP response Axum understands.
@@ -135,6 +141,12 @@ pub async fn activate_license_handler(
         bandwidth_used_bytes: None,
         bandwidth_limit_bytes: None,
         quota_exceeded: None,
+        max_devices: 1,
+        max_concurrent: None,
+        offline_days: None,
+        billing_cycle: None,
+        usage_period_start: None,
+        suspended_by_org: None,
     };
 
     state.db.insert_license(license).await?;
@@ -313,7 +325,7 @@ pub async fn health_handler(
 ) -> Json<crate::server::logging::HealthResponse> {
     // Check database connectivity
     let db_connected = state.db.health_check().await;
-    let db_type = state.db.db_type();
+    let db_type = state.db.backend();
 
     Json(crate::server::logging::HealthResponse::healthy(
         db_connected,
@@ -24,7 +24,7 @@
 
 use axum::{
     body::Body,
-    http::{Request, Response, StatusCode},
+    http::{Extensions, HeaderMap, Request, Response, StatusCode},
     response::IntoResponse,
 };
 use serde_json::json;
@@ -266,13 +266,20 @@ where
 
 /// Extract the client IP address from the request.
 ///
+/// See [`client_ip`] for the lookup order.
+fn extract_client_ip<B>(req: &Request<B>) -> Option<IpAddr> {
+    client_ip(req.headers(), req.extensions())
+}
+
+/// Determine the client IP address from request headers and extensions.
+///
 /// Checks in order:
 /// 1. `X-Forwarded-For` header (first IP in the list)
 /// 2. `X-Real-IP` header
 /// 3. Connection info (if available)
-fn extract_client_ip<B>(req: &Request<B>) -> Option<IpAddr> {
+pub(crate) fn client_ip(headers: &HeaderMap, extensions: &Extensions) -> Option<IpAddr> {
     // Try X-Forwarded-For header first (common with reverse proxies)
-    if let Some(xff) = req.headers().get("x-forwarded-for") {
+    if let Some(xff) = headers.get("x-forwarded-for") {
         if let Ok(xff_str) = xff.to_str() {
             // Take the first IP in the chain (original client)
             if let Some(first_ip) = xff_str.split(',').next() {
@@ -284,7 +291,7 @@ fn extract_client_ip<B>(req: &Request<B>) -> Option<IpAddr> {
     }
 
     // Try X-Real-IP header
-    if let Some(real_ip) = req.headers().get("x-real-ip") {
+    if let Some(real_ip) = headers.get("x-real-ip") {
         if let Ok(ip_str) = real_ip.to_str() {
             if let Ok(ip) = IpAddr::from_str(ip_str.trim()) {
                 return Some(ip);
@@ -294,9 +301,7 @@ fn extract_client_ip<B>(req: &Request<B>) -> Option<IpAddr> {
 
     // Try to get from connection info extension (set by axum/hyper)
     // This is typically only available when not behind a proxy
-    if let Some(connect_info) = req
-        .extensions()
-        .get::<axum::extract::ConnectInfo<std::net::SocketAddr>>()
+    if let Some(connect_info) = extensions.get::<axum::extract::ConnectInfo<std::net::SocketAddr>>()
     {
         return Some(connect_info.0.ip());
     }
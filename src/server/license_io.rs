@@ -0,0 +1,1156 @@
+//! Bulk import and export of licenses as CSV or NDJSON.
+//!
+//! Exports contain every column of [`License`], one license per CSV row or
+//! NDJSON line, and accept the same filters and sort order as the license
+//! listing. In CSV an empty cell stands for `NULL`; in NDJSON each line is
+//! the license serialized as a JSON object.
+//!
+//! Imports take the same formats, so an export can be imported into another
+//! server as is. IDs, keys and timestamps in the file are kept (rows without
+//! an ID or key get a new one), keys are checked against the configured key
+//! format, and tiers and features are resolved the way license creation
+//! resolves them. Every row is validated before anything is written; if any
+//! row fails, nothing is imported and the report lists each failing row. A
+//! dry run only validates.
+//!
+//! # Endpoints
+//!
+//! - `GET /api/v1/licenses/export` - Export licenses as CSV or NDJSON
+//! - `POST /api/v1/licenses/import` - Import (or dry-run) a CSV or NDJSON file
+//!
+//! The same operations are available as `talos_server licenses export` and
+//! `talos_server licenses import`.
+
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::str::FromStr;
+
+use chrono::{NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use tracing::info;
+use uuid::Uuid;
+
+#[cfg(feature = "openapi")]
+use utoipa::ToSchema;
+
+use crate::config::get_config;
+use crate::errors::{LicenseError, LicenseResult};
+use crate::license_key::{generate_license_key, validate_license_key_format, LicenseKeyConfig};
+use crate::server::audit::{snapshot, AuditAction, AuditContext, AuditTarget};
+use crate::server::billing::BillingCycle;
+use crate::server::database::License;
+use crate::server::license_query::{LicenseCursor, LicenseFilter, LicenseSort};
+use crate::server::logging::{log_license_event, LicenseEvent};
+use crate::server::orgs::Organization;
+use crate::server::store::LicenseStore;
+use crate::server::validation::validate_org_id;
+use crate::server::webhooks;
+use crate::tiers::{get_all_tier_names, get_tier_features, tier_exists};
+
+#[cfg(feature = "admin-api")]
+use axum::{
+    extract::{Query, State},
+    http::header,
+    response::{IntoResponse, Response},
+    Json,
+};
+
+#[cfg(feature = "admin-api")]
+use crate::server::admin::{AdminError, ListLicensesQuery};
+#[cfg(feature = "admin-api")]
+use crate::server::handlers::AppState;
+
+/// Columns of an export, in [`License`] field order.
+pub const LICENSE_COLUMNS: [&str; 35] = [
+    "license_id",
+    "client_id",
+    "status",
+    "features",
+    "issued_at",
+    "expires_at",
+    "hardware_id",
+    "signature",
+    "last_heartbeat",
+    "org_id",
+    "org_name",
+    "license_key",
+    "tier",
+    "device_name",
+    "device_info",
+    "bound_at",
+    "last_seen_at",
+    "suspended_at",
+    "revoked_at",
+    "revoke_reason",
+    "grace_period_ends_at",
+    "suspension_message",
+    "is_blacklisted",
+    "blacklisted_at",
+    "blacklist_reason",
+    "metadata",
+    "bandwidth_used_bytes",
+    "bandwidth_limit_bytes",
+    "quota_exceeded",
+    "max_devices",
+    "max_concurrent",
+    "offline_days",
+    "billing_cycle",
+    "usage_period_start",
+    "suspended_by_org",
+];
+
+/// Maximum number of licenses in one import.
+pub const MAX_IMPORT_ROWS: usize = 10_000;
+
+/// Maximum size of an import request body.
+pub const MAX_IMPORT_BYTES: usize = 16 * 1024 * 1024;
+
+/// Licenses fetched per query while exporting.
+const EXPORT_PAGE_SIZE: u32 = 1000;
+
+const BOOL_COLUMNS: [&str; 3] = ["is_blacklisted", "quota_exceeded", "suspended_by_org"];
+
+const INTEGER_COLUMNS: [&str; 5] = [
+    "bandwidth_used_bytes",
+    "bandwidth_limit_bytes",
+    "max_devices",
+    "max_concurrent",
+    "offline_days",
+];
+
+const STATUSES: [&str; 4] = ["active", "expired", "suspended", "revoked"];
+
+/// File format of an import or export.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum LicenseFormat {
+    /// Comma-separated values with a header row
+    #[default]
+    Csv,
+    /// One JSON object per line
+    Ndjson,
+}
+
+impl LicenseFormat {
+    /// MIME type of the format.
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            LicenseFormat::Csv => "text/csv; charset=utf-8",
+            LicenseFormat::Ndjson => "application/x-ndjson",
+        }
+    }
+
+    /// Usual file extension of the format.
+    pub fn extension(&self) -> &'static str {
+        match self {
+            LicenseFormat::Csv => "csv",
+            LicenseFormat::Ndjson => "ndjson",
+        }
+    }
+
+    /// Guess the format of a file from its extension, defaulting to CSV.
+    pub fn from_path(path: &str) -> Self {
+        let lower = path.to_ascii_lowercase();
+        if lower.ends_with(".ndjson") || lower.ends_with(".jsonl") {
+            LicenseFormat::Ndjson
+        } else {
+            LicenseFormat::Csv
+        }
+    }
+}
+
+impl fmt::Display for LicenseFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.extension())
+    }
+}
+
+impl FromStr for LicenseFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "csv" => Ok(LicenseFormat::Csv),
+            "ndjson" => Ok(LicenseFormat::Ndjson),
+            _ => Err(format!("invalid format: {s} (expected csv or ndjson)")),
+        }
+    }
+}
+
+// ============================================================================
+// Export
+// ============================================================================
+
+/// Export every license matching `filter`, in `sort` order.
+pub async fn export_licenses(
+    db: &dyn LicenseStore,
+    filter: &LicenseFilter,
+    sort: LicenseSort,
+    format: LicenseFormat,
+) -> LicenseResult<String> {
+    let mut out = String::new();
+    if format == LicenseFormat::Csv {
+        write_csv_record(&mut out, LICENSE_COLUMNS.map(String::from));
+    }
+
+    let mut after: Option<LicenseCursor> = None;
+    loop {
+        let (page, _) = db
+            .list_licenses(filter, sort, after.as_ref(), EXPORT_PAGE_SIZE, 0)
+            .await?;
+
+        for license in &page {
+            match format {
+                LicenseFormat::Csv => write_csv_record(&mut out, license_cells(license)?),
+                LicenseFormat::Ndjson => {
+                    let line = serde_json::to_string(license).map_err(|e| {
+                        LicenseError::ServerError(format!("failed to serialize license: {e}"))
+                    })?;
+                    out.push_str(&line);
+                    out.push('\n');
+                }
+            }
+        }
+
+        if page.len() < EXPORT_PAGE_SIZE as usize {
+            break;
+        }
+        after = page.last().map(|last| LicenseCursor::after(last, sort));
+    }
+
+    Ok(out)
+}
+
+/// The CSV cells of a license, in [`LICENSE_COLUMNS`] order.
+fn license_cells(license: &License) -> LicenseResult<Vec<String>> {
+    let Value::Object(fields) = serde_json::to_value(license)
+        .map_err(|e| LicenseError::ServerError(format!("failed to serialize license: {e}")))?
+    else {
+        return Err(LicenseError::ServerError(
+            "license did not serialize to an object".to_string(),
+        ));
+    };
+
+    Ok(LICENSE_COLUMNS
+        .iter()
+        .map(|column| match fields.get(*column) {
+            None | Some(Value::Null) => String::new(),
+            Some(Value::String(s)) => s.clone(),
+            Some(other) => other.to_string(),
+        })
+        .collect())
+}
+
+/// Append one CSV record, quoting cells as RFC 4180 requires.
+fn write_csv_record(out: &mut String, cells: impl IntoIterator<Item = String>) {
+    for (i, cell) in cells.into_iter().enumerate() {
+        if i > 0 {
+            out.push(',');
+        }
+        if cell.contains([',', '"', '\n', '\r']) {
+            out.push('"');
+            out.push_str(&cell.replace('"', "\"\""));
+            out.push('"');
+        } else {
+            out.push_str(&cell);
+        }
+    }
+    out.push('\n');
+}
+
+// ============================================================================
+// Import
+// ============================================================================
+
+/// Outcome of an import.
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct ImportReport {
+    /// Whether this was only a validation run
+    pub dry_run: bool,
+    /// Whether the licenses were written
+    pub committed: bool,
+    /// Number of licenses in the file
+    pub total: usize,
+    /// Licenses that passed validation
+    pub valid: usize,
+    /// Licenses that failed validation
+    pub failed: usize,
+    /// Why each failing row failed
+    pub errors: Vec<ImportRowError>,
+}
+
+/// A row of an import that can't be imported.
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct ImportRowError {
+    /// Line of the file the row starts on (1-based, the CSV header is line 1)
+    pub row: usize,
+    /// The row's license ID, if it has one
+    pub license_id: Option<String>,
+    pub error: String,
+}
+
+/// One row of an import. Timestamps are parsed after deserializing so a bad
+/// one is reported against its column.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct LicenseRecord {
+    license_id: Option<String>,
+    client_id: Option<String>,
+    status: Option<String>,
+    features: Option<String>,
+    issued_at: Option<String>,
+    expires_at: Option<String>,
+    hardware_id: Option<String>,
+    signature: Option<String>,
+    last_heartbeat: Option<String>,
+    org_id: Option<String>,
+    org_name: Option<String>,
+    license_key: Option<String>,
+    tier: Option<String>,
+    device_name: Option<String>,
+    device_info: Option<String>,
+    bound_at: Option<String>,
+    last_seen_at: Option<String>,
+    suspended_at: Option<String>,
+    revoked_at: Option<String>,
+    revoke_reason: Option<String>,
+    grace_period_ends_at: Option<String>,
+    suspension_message: Option<String>,
+    is_blacklisted: Option<bool>,
+    blacklisted_at: Option<String>,
+    blacklist_reason: Option<String>,
+    metadata: Option<String>,
+    bandwidth_used_bytes: Option<i64>,
+    bandwidth_limit_bytes: Option<i64>,
+    quota_exceeded: Option<bool>,
+    max_devices: Option<i32>,
+    max_concurrent: Option<i32>,
+    offline_days: Option<i32>,
+    billing_cycle: Option<String>,
+    usage_period_start: Option<String>,
+    suspended_by_org: Option<bool>,
+}
+
+/// Import licenses from a CSV or NDJSON file.
+///
+/// Every row is validated first. Unless `dry_run` is set and only if every
+/// row is valid, the licenses are then written in one transaction, followed
+/// by the devices of bound licenses. Organizations the file references that
+/// aren't registered yet are registered along with them.
+pub async fn import_licenses(
+    db: &dyn LicenseStore,
+    audit: &AuditContext,
+    input: &str,
+    format: LicenseFormat,
+    dry_run: bool,
+) -> LicenseResult<ImportReport> {
+    let ParsedRows {
+        records,
+        mut errors,
+        rows: total,
+    } = match format {
+        LicenseFormat::Csv => parse_csv_rows(input),
+        LicenseFormat::Ndjson => parse_ndjson_rows(input),
+    };
+
+    let mut importer = Importer::new(db)?;
+    let mut licenses = Vec::with_capacity(records.len());
+
+    for (row, record) in records {
+        let license_id = record.license_id.clone();
+        match importer.license(record).await {
+            Ok(license) => licenses.push(license),
+            Err(error) => errors.push(ImportRowError {
+                row,
+                license_id,
+                error,
+            }),
+        }
+    }
+    errors.sort_by_key(|e| e.row);
+
+    let mut report = ImportReport {
+        dry_run,
+        committed: false,
+        total,
+        valid: licenses.len(),
+        failed: total - licenses.len(),
+        errors,
+    };
+
+    if dry_run || !report.errors.is_empty() || licenses.is_empty() {
+        return Ok(report);
+    }
+
+    for org in &importer.new_orgs {
+        db.insert_org(org).await?;
+        audit
+            .record(
+                db,
+                AuditAction::OrgCreated,
+                AuditTarget::Org(org.id.clone()),
+                None,
+                snapshot(org),
+            )
+            .await;
+    }
+
+    db.insert_licenses(&licenses, false).await?;
+
+    for license in &licenses {
+        if let Some(hardware_id) = &license.hardware_id {
+            db.add_license_device(
+                &license.license_id,
+                hardware_id,
+                license.device_name.as_deref(),
+                license.device_info.as_deref(),
+            )
+            .await?;
+            // Adding the device stamps a new bound_at; keep the imported one
+            db.insert_license(license.clone()).await?;
+        }
+
+        log_license_event(
+            LicenseEvent::Created,
+            &license.license_id,
+            license.license_key.as_deref(),
+        );
+        webhooks::notify(db, LicenseEvent::Created, &license.license_id, None).await;
+        audit
+            .record(
+                db,
+                AuditAction::LicenseCreated,
+                AuditTarget::License(license.license_id.clone()),
+                None,
+                snapshot(license),
+            )
+            .await;
+    }
+
+    info!(
+        "Imported {} licenses ({} new organizations)",
+        licenses.len(),
+        importer.new_orgs.len()
+    );
+    report.committed = true;
+    Ok(report)
+}
+
+/// Validates rows and turns them into licenses, remembering what earlier
+/// rows claimed.
+struct Importer<'a> {
+    db: &'a dyn LicenseStore,
+    key_config: LicenseKeyConfig,
+    tiers_configured: bool,
+    now: NaiveDateTime,
+    ids: HashSet<String>,
+    keys: HashSet<String>,
+    orgs: HashMap<String, Organization>,
+    new_orgs: Vec<Organization>,
+}
+
+impl<'a> Importer<'a> {
+    fn new(db: &'a dyn LicenseStore) -> LicenseResult<Self> {
+        Ok(Self {
+            db,
+            key_config: LicenseKeyConfig::from(&get_config()?.license),
+            tiers_configured: !get_all_tier_names().is_empty(),
+            now: Utc::now().naive_utc(),
+            ids: HashSet::new(),
+            keys: HashSet::new(),
+            orgs: HashMap::new(),
+            new_orgs: Vec::new(),
+        })
+    }
+
+    async fn license(&mut self, record: LicenseRecord) -> Result<License, String> {
+        let license_id = match record.license_id {
+            Some(id) => {
+                if self.ids.contains(&id) {
+                    return Err(format!("duplicate license_id {id} in file"));
+                }
+                if self.db.get_license(&id).await.map_err(db_error)?.is_some() {
+                    return Err(format!("license {id} already exists"));
+                }
+                id
+            }
+            None => Uuid::new_v4().to_string(),
+        };
+
+        let license_key = match record.license_key {
+            Some(key) => {
+                if !validate_license_key_format(&key, &self.key_config) {
+                    return Err(format!("invalid license key format: {key}"));
+                }
+                if self.keys.contains(&key) {
+                    return Err(format!("duplicate license_key {key} in file"));
+                }
+                if self.db.license_key_exists(&key).await.map_err(db_error)? {
+                    return Err(format!("license key {key} is already in use"));
+                }
+                key
+            }
+            None => self.new_key().await?,
+        };
+
+        let status = record.status.unwrap_or_else(|| "active".to_string());
+        if !STATUSES.contains(&status.as_str()) {
+            return Err(format!(
+                "invalid status: {status} (expected one of {})",
+                STATUSES.join(", ")
+            ));
+        }
+
+        let org = match record.org_id.as_deref() {
+            Some(org_id) => Some(self.org(org_id, record.org_name.as_deref()).await?),
+            None => None,
+        };
+
+        let tier = record
+            .tier
+            .or_else(|| org.as_ref().and_then(|o| o.default_tier.clone()));
+        if let Some(tier) = &tier {
+            if self.tiers_configured && !tier_exists(tier) {
+                return Err(format!("unknown tier: {tier}"));
+            }
+        }
+
+        let features = match record.features {
+            Some(features) => features,
+            None => {
+                let mut features = tier.as_deref().map(get_tier_features).unwrap_or_default();
+                for feature in org
+                    .as_ref()
+                    .map(Organization::default_feature_names)
+                    .unwrap_or_default()
+                {
+                    if !features.contains(&feature) {
+                        features.push(feature);
+                    }
+                }
+                serde_json::to_string(&features).map_err(|e| e.to_string())?
+            }
+        };
+
+        if let Some(metadata) = &record.metadata {
+            serde_json::from_str::<Value>(metadata)
+                .map_err(|e| format!("metadata is not valid JSON: {e}"))?;
+        }
+
+        let max_devices = record.max_devices.unwrap_or(1);
+        if max_devices < 1 {
+            return Err("max_devices must be at least 1".to_string());
+        }
+        if matches!(record.max_concurrent, Some(n) if n < 1) {
+            return Err("max_concurrent must be at least 1".to_string());
+        }
+        if matches!(record.offline_days, Some(n) if n < 0) {
+            return Err("offline_days must not be negative".to_string());
+        }
+        if let Some(cycle) = &record.billing_cycle {
+            cycle.parse::<BillingCycle>()?;
+        }
+
+        let license = License {
+            license_id,
+            client_id: record.client_id,
+            status,
+            features: Some(features),
+            issued_at: timestamp("issued_at", record.issued_at)?.unwrap_or(self.now),
+            expires_at: timestamp("expires_at", record.expires_at)?,
+            hardware_id: record.hardware_id,
+            signature: record.signature,
+            last_heartbeat: timestamp("last_heartbeat", record.last_heartbeat)?,
+            // Licenses carry the registered name of their organization
+            org_name: org.as_ref().map(|o| o.name.clone()).or(record.org_name),
+            org_id: record.org_id,
+            license_key: Some(license_key),
+            tier,
+            device_name: record.device_name,
+            device_info: record.device_info,
+            bound_at: timestamp("bound_at", record.bound_at)?,
+            last_seen_at: timestamp("last_seen_at", record.last_seen_at)?,
+            suspended_at: timestamp("suspended_at", record.suspended_at)?,
+            revoked_at: timestamp("revoked_at", record.revoked_at)?,
+            revoke_reason: record.revoke_reason,
+            grace_period_ends_at: timestamp("grace_period_ends_at", record.grace_period_ends_at)?,
+            suspension_message: record.suspension_message,
+            is_blacklisted: record.is_blacklisted,
+            blacklisted_at: timestamp("blacklisted_at", record.blacklisted_at)?,
+            blacklist_reason: record.blacklist_reason,
+            metadata: record.metadata,
+            bandwidth_used_bytes: record.bandwidth_used_bytes,
+            bandwidth_limit_bytes: record.bandwidth_limit_bytes,
+            quota_exceeded: record.quota_exceeded,
+            max_devices,
+            max_concurrent: record.max_concurrent,
+            offline_days: record.offline_days,
+            billing_cycle: record.billing_cycle,
+            usage_period_start: timestamp("usage_period_start", record.usage_period_start)?,
+            suspended_by_org: record.suspended_by_org,
+        };
+
+        // Only claim the ID and key once the whole row is known to be valid
+        self.ids.insert(license.license_id.clone());
+        if let Some(key) = &license.license_key {
+            self.keys.insert(key.clone());
+        }
+
+        Ok(license)
+    }
+
+    /// Generate a key not used in the database or earlier in the file.
+    async fn new_key(&self) -> Result<String, String> {
+        for _ in 0..10 {
+            let key = generate_license_key(&self.key_config);
+            if !self.keys.contains(&key)
+                && !self.db.license_key_exists(&key).await.map_err(db_error)?
+            {
+                return Ok(key);
+            }
+        }
+        Err("failed to generate unique license key after 10 attempts".to_string())
+    }
+
+    /// The organization a row belongs to, registering unknown ones on commit.
+    async fn org(&mut self, org_id: &str, org_name: Option<&str>) -> Result<Organization, String> {
+        let org = match self.orgs.get(org_id) {
+            Some(org) => org.clone(),
+            None => {
+                let org = match self.db.get_org(org_id).await.map_err(db_error)? {
+                    Some(org) => org,
+                    None => {
+                        validate_org_id(org_id, "org_id").map_err(|e| e.to_string())?;
+                        let org = Organization {
+                            id: org_id.to_string(),
+                            name: org_name.unwrap_or(org_id).to_string(),
+                            contact_name: None,
+                            contact_email: None,
+                            metadata: None,
+                            default_tier: None,
+                            default_features: None,
+                            status: "active".to_string(),
+                            suspended_at: None,
+                            suspension_reason: None,
+                            created_at: self.now,
+                            updated_at: self.now,
+                        };
+                        self.new_orgs.push(org.clone());
+                        org
+                    }
+                };
+                self.orgs.insert(org_id.to_string(), org.clone());
+                org
+            }
+        };
+
+        if org.is_suspended() {
+            return Err(format!("organization {org_id} is suspended"));
+        }
+        Ok(org)
+    }
+}
+
+fn db_error(e: LicenseError) -> String {
+    format!("database error: {e}")
+}
+
+/// Parse an optional timestamp column.
+fn timestamp(column: &str, value: Option<String>) -> Result<Option<NaiveDateTime>, String> {
+    value
+        .map(|v| parse_timestamp(&v).ok_or_else(|| format!("invalid {column}: {v}")))
+        .transpose()
+}
+
+/// Parse a timestamp as exports write it (`2025-01-31T12:00:00`, with
+/// optional fractional seconds), SQL-style (`2025-01-31 12:00:00`), as
+/// RFC 3339, or as a plain date (midnight).
+pub(crate) fn parse_timestamp(value: &str) -> Option<NaiveDateTime> {
+    if let Ok(dt) = chrono::DateTime::parse_from_rfc3339(value) {
+        return Some(dt.naive_utc());
+    }
+    for format in ["%Y-%m-%dT%H:%M:%S%.f", "%Y-%m-%d %H:%M:%S%.f"] {
+        if let Ok(dt) = NaiveDateTime::parse_from_str(value, format) {
+            return Some(dt);
+        }
+    }
+    chrono::NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .ok()
+        .and_then(|date| date.and_hms_opt(0, 0, 0))
+}
+
+/// Rows read from an import file.
+#[derive(Default)]
+struct ParsedRows {
+    /// Rows that parsed, with the line each starts on
+    records: Vec<(usize, LicenseRecord)>,
+    /// Rows that didn't parse, and problems with the file itself
+    errors: Vec<ImportRowError>,
+    /// Number of license rows, whether they parsed or not
+    rows: usize,
+}
+
+fn row_error(row: usize, license_id: Option<String>, error: impl Into<String>) -> ImportRowError {
+    ImportRowError {
+        row,
+        license_id,
+        error: error.into(),
+    }
+}
+
+/// Parse NDJSON, skipping blank lines.
+fn parse_ndjson_rows(input: &str) -> ParsedRows {
+    let mut parsed = ParsedRows::default();
+
+    for (i, line) in input.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        if parsed.rows == MAX_IMPORT_ROWS {
+            parsed.errors.push(too_many_rows(i + 1));
+            break;
+        }
+        parsed.rows += 1;
+
+        let value: Value = match serde_json::from_str(line) {
+            Ok(value) => value,
+            Err(e) => {
+                parsed
+                    .errors
+                    .push(row_error(i + 1, None, format!("invalid JSON: {e}")));
+                continue;
+            }
+        };
+        let license_id = value
+            .get("license_id")
+            .and_then(Value::as_str)
+            .map(str::to_string);
+        match serde_json::from_value(value) {
+            Ok(record) => parsed.records.push((i + 1, record)),
+            Err(e) => parsed
+                .errors
+                .push(row_error(i + 1, license_id, e.to_string())),
+        }
+    }
+
+    parsed
+}
+
+/// Parse CSV with a header row naming the columns present.
+fn parse_csv_rows(input: &str) -> ParsedRows {
+    let mut parsed = ParsedRows::default();
+
+    let records = match parse_csv(input) {
+        Ok(records) => records,
+        Err(e) => {
+            parsed.errors.push(e);
+            return parsed;
+        }
+    };
+    let mut records = records.into_iter();
+    let Some((header_row, header)) = records.next() else {
+        return parsed;
+    };
+
+    let mut seen = HashSet::new();
+    for column in &header {
+        if !LICENSE_COLUMNS.contains(&column.as_str()) {
+            parsed.errors.push(row_error(
+                header_row,
+                None,
+                format!("unknown column: {column}"),
+            ));
+        } else if !seen.insert(column.as_str()) {
+            parsed.errors.push(row_error(
+                header_row,
+                None,
+                format!("duplicate column: {column}"),
+            ));
+        }
+    }
+    if !parsed.errors.is_empty() {
+        return parsed;
+    }
+
+    let id_column = header.iter().position(|c| c == "license_id");
+    for (row, cells) in records {
+        if parsed.rows == MAX_IMPORT_ROWS {
+            parsed.errors.push(too_many_rows(row));
+            break;
+        }
+        parsed.rows += 1;
+
+        let license_id = id_column
+            .and_then(|i| cells.get(i))
+            .filter(|id| !id.is_empty())
+            .cloned();
+
+        if cells.len() != header.len() {
+            parsed.errors.push(row_error(
+                row,
+                license_id,
+                format!("expected {} cells, found {}", header.len(), cells.len()),
+            ));
+            continue;
+        }
+
+        match csv_record(&header, cells) {
+            Ok(record) => parsed.records.push((row, record)),
+            Err(e) => parsed.errors.push(row_error(row, license_id, e)),
+        }
+    }
+
+    parsed
+}
+
+fn too_many_rows(row: usize) -> ImportRowError {
+    row_error(
+        row,
+        None,
+        format!("an import is limited to {MAX_IMPORT_ROWS} licenses"),
+    )
+}
+
+/// Turn a CSV row into a record, typing the boolean and integer columns.
+fn csv_record(header: &[String], cells: Vec<String>) -> Result<LicenseRecord, String> {
+    let mut object = Map::new();
+
+    for (column, cell) in header.iter().zip(cells) {
+        if cell.is_empty() {
+            continue;
+        }
+        let value = if BOOL_COLUMNS.contains(&column.as_str()) {
+            match cell.to_ascii_lowercase().as_str() {
+                "true" | "1" => Value::Bool(true),
+                "false" | "0" => Value::Bool(false),
+                _ => return Err(format!("invalid {column}: {cell} (expected true or false)")),
+            }
+        } else if INTEGER_COLUMNS.contains(&column.as_str()) {
+            cell.parse::<i64>()
+                .map(Value::from)
+                .map_err(|_| format!("invalid {column}: {cell} (expected an integer)"))?
+        } else {
+            Value::String(cell)
+        };
+        object.insert(column.clone(), value);
+    }
+
+    serde_json::from_value(Value::Object(object)).map_err(|e| e.to_string())
+}
+
+/// Split RFC 4180 CSV into records, each with the line it starts on.
+///
+/// Quoted cells may contain commas, doubled quotes and line breaks. Blank
+/// lines are skipped.
+fn parse_csv(input: &str) -> Result<Vec<(usize, Vec<String>)>, ImportRowError> {
+    let input = input.strip_prefix('\u{feff}').unwrap_or(input);
+
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut cell = String::new();
+    let mut in_quotes = false;
+    let mut line = 1;
+    let mut record_line = 1;
+    let mut chars = input.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if in_quotes {
+            match c {
+                '"' if chars.peek() == Some(&'"') => {
+                    chars.next();
+                    cell.push('"');
+                }
+                '"' => in_quotes = false,
+                '\n' => {
+                    line += 1;
+                    cell.push(c);
+                }
+                _ => cell.push(c),
+            }
+            continue;
+        }
+
+        match c {
+            '"' if cell.is_empty() => in_quotes = true,
+            ',' => record.push(std::mem::take(&mut cell)),
+            '\r' if chars.peek() == Some(&'\n') => {}
+            '\n' | '\r' => {
+                record.push(std::mem::take(&mut cell));
+                if !(record.len() == 1 && record[0].is_empty()) {
+                    records.push((record_line, std::mem::take(&mut record)));
+                }
+                record.clear();
+                line += 1;
+                record_line = line;
+            }
+            _ => cell.push(c),
+        }
+    }
+
+    if in_quotes {
+        return Err(row_error(record_line, None, "unterminated quoted cell"));
+    }
+    if !cell.is_empty() || !record.is_empty() {
+        record.push(cell);
+        records.push((record_line, record));
+    }
+
+    Ok(records)
+}
+
+// ============================================================================
+// HTTP Handlers
+// ============================================================================
+
+/// Query parameters selecting an export or import format.
+#[cfg(feature = "admin-api")]
+#[derive(Debug, Deserialize)]
+pub struct LicenseFormatQuery {
+    /// `csv` (default) or `ndjson`
+    pub format: Option<String>,
+}
+
+/// Query parameters for importing licenses.
+#[cfg(feature = "admin-api")]
+#[derive(Debug, Deserialize)]
+pub struct ImportLicensesQuery {
+    /// `csv` (default) or `ndjson`
+    pub format: Option<String>,
+    /// Only validate the file
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+#[cfg(feature = "admin-api")]
+fn parse_format(format: Option<&str>) -> Result<LicenseFormat, AdminError> {
+    format
+        .map(str::parse)
+        .transpose()
+        .map(Option::unwrap_or_default)
+        .map_err(AdminError::BadRequest)
+}
+
+/// Export licenses as a CSV or NDJSON download.
+///
+/// `GET /api/v1/licenses/export?format=csv|ndjson&<list filters>&sort=&order=`
+///
+/// Takes the filters and sort order of `GET /api/v1/licenses`; paging
+/// parameters are ignored since every matching license is exported.
+#[cfg(feature = "admin-api")]
+#[cfg_attr(feature = "openapi", utoipa::path(
+    get,
+    path = "/api/v1/licenses/export",
+    tag = "admin",
+    params(
+        ("format" = Option<String>, Query, description = "csv (default) or ndjson"),
+        ("org_id" = Option<String>, Query, description = "Filter by organization ID"),
+        ("status" = Option<String>, Query, description = "Filter by status"),
+        ("tier" = Option<String>, Query, description = "Filter by tier"),
+        ("bound" = Option<bool>, Query, description = "Only bound (true) or unbound (false) licenses"),
+        ("blacklisted" = Option<bool>, Query, description = "Only blacklisted (true) or non-blacklisted (false) licenses"),
+        ("expires_before" = Option<String>, Query, description = "Only licenses expiring before this time"),
+        ("created_after" = Option<String>, Query, description = "Only licenses issued at or after this time"),
+        ("created_before" = Option<String>, Query, description = "Only licenses issued before this time"),
+        ("key_prefix" = Option<String>, Query, description = "Only licenses whose key starts with this prefix"),
+        ("metadata" = Option<String>, Query, description = "Only licenses with this metadata entry, as key:value"),
+        ("sort" = Option<String>, Query, description = "Sort column: issued_at (default), expires_at, license_id, license_key, org_id, status or tier"),
+        ("order" = Option<String>, Query, description = "Sort order: asc (default) or desc")
+    ),
+    responses(
+        (status = 200, description = "Every matching license, one per CSV row or NDJSON line", body = String),
+        (status = 400, description = "Invalid format, filter or sort"),
+        (status = 404, description = "Organization not found"),
+        (status = 500, description = "Server error"),
+    ),
+    security(("bearer_auth" = []))
+))]
+pub async fn export_licenses_handler(
+    State(state): State<AppState>,
+    Query(format_query): Query<LicenseFormatQuery>,
+    Query(query): Query<ListLicensesQuery>,
+) -> Result<Response, AdminError> {
+    let format = parse_format(format_query.format.as_deref())?;
+    let sort = query.sort()?;
+    let filter = query.filter()?;
+
+    if let Some(org_id) = &filter.org_id {
+        if state.db.get_org(org_id).await?.is_none() {
+            return Err(AdminError::NotFound(format!(
+                "organization not found: {org_id}"
+            )));
+        }
+    }
+
+    info!("Exporting licenses as {format} org_id={:?}", filter.org_id);
+    let body = export_licenses(&*state.db, &filter, sort, format).await?;
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, format.content_type().to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"licenses.{}\"", format.extension()),
+            ),
+        ],
+        body,
+    )
+        .into_response())
+}
+
+/// Import licenses from a CSV or NDJSON request body.
+///
+/// `POST /api/v1/licenses/import?format=csv|ndjson&dry_run=true`
+///
+/// Responds with a report either way; `committed` tells whether the
+/// licenses were written, which only happens when every row is valid.
+#[cfg(feature = "admin-api")]
+#[cfg_attr(feature = "openapi", utoipa::path(
+    post,
+    path = "/api/v1/licenses/import",
+    tag = "admin",
+    params(
+        ("format" = Option<String>, Query, description = "csv (default) or ndjson"),
+        ("dry_run" = Option<bool>, Query, description = "Only validate the file")
+    ),
+    request_body(content = String, description = "CSV with a header row, or one JSON license per line"),
+    responses(
+        (status = 200, description = "Validation report, with committed set if the licenses were written", body = ImportReport),
+        (status = 400, description = "Invalid format"),
+        (status = 413, description = "File too large"),
+        (status = 500, description = "Server error"),
+    ),
+    security(("bearer_auth" = []))
+))]
+pub async fn import_licenses_handler(
+    State(state): State<AppState>,
+    audit: AuditContext,
+    Query(query): Query<ImportLicensesQuery>,
+    body: String,
+) -> Result<Json<ImportReport>, AdminError> {
+    let format = parse_format(query.format.as_deref())?;
+
+    info!(
+        "Importing licenses as {format} ({} bytes, dry_run={})",
+        body.len(),
+        query.dry_run
+    );
+    let report = import_licenses(&*state.db, &audit, &body, format, query.dry_run).await?;
+
+    Ok(Json(report))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cells(record: &[&str]) -> Vec<String> {
+        record.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn csv_round_trips_quoted_cells() {
+        let mut out = String::new();
+        write_csv_record(
+            &mut out,
+            cells(&["id", "a,b", "say \"hi\"", "two\nlines", ""]),
+        );
+        write_csv_record(&mut out, cells(&["plain"]));
+        assert_eq!(
+            out,
+            "id,\"a,b\",\"say \"\"hi\"\"\",\"two\nlines\",\nplain\n"
+        );
+
+        let records = parse_csv(&out).unwrap();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].0, 1);
+        assert_eq!(
+            records[0].1,
+            cells(&["id", "a,b", "say \"hi\"", "two\nlines", ""])
+        );
+        // The second record starts after the embedded line break
+        assert_eq!(records[1], (3, cells(&["plain"])));
+    }
+
+    #[test]
+    fn csv_handles_crlf_bom_and_blank_lines() {
+        let records = parse_csv("\u{feff}a,b\r\n\r\n1,2\r\n3,4").unwrap();
+        assert_eq!(
+            records,
+            vec![
+                (1, cells(&["a", "b"])),
+                (3, cells(&["1", "2"])),
+                (4, cells(&["3", "4"])),
+            ]
+        );
+
+        let err = parse_csv("a,b\n1,\"open\n").unwrap_err();
+        assert_eq!(err.row, 2);
+    }
+
+    #[test]
+    fn columns_match_license_fields() {
+        let license: License = serde_json::from_value(serde_json::json!({
+            "license_id": "LIC-1",
+            "status": "active",
+            "issued_at": "2025-01-01T00:00:00",
+            "max_devices": 1,
+        }))
+        .unwrap();
+        let Value::Object(fields) = serde_json::to_value(&license).unwrap() else {
+            panic!("license is not an object");
+        };
+
+        let mut expected: Vec<&str> = fields.keys().map(String::as_str).collect();
+        let mut columns = LICENSE_COLUMNS.to_vec();
+        expected.sort_unstable();
+        columns.sort_unstable();
+        assert_eq!(columns, expected);
+    }
+
+    #[test]
+    fn timestamps_accept_export_and_common_formats() {
+        let parse = |s: &str| timestamp("issued_at", Some(s.to_string()));
+
+        let expected = chrono::NaiveDate::from_ymd_opt(2025, 1, 31)
+            .unwrap()
+            .and_hms_opt(12, 30, 0)
+            .unwrap();
+        assert_eq!(parse("2025-01-31T12:30:00").unwrap(), Some(expected));
+        assert_eq!(parse("2025-01-31 12:30:00").unwrap(), Some(expected));
+        assert_eq!(parse("2025-01-31T12:30:00Z").unwrap(), Some(expected));
+        assert_eq!(
+            parse("2025-01-31T12:30:00.250").unwrap(),
+            Some(expected + chrono::Duration::milliseconds(250))
+        );
+        assert_eq!(
+            parse("2025-01-31").unwrap(),
+            chrono::NaiveDate::from_ymd_opt(2025, 1, 31)
+                .unwrap()
+                .and_hms_opt(0, 0, 0)
+        );
+        assert!(parse("31/01/2025").is_err());
+        assert_eq!(timestamp("issued_at", None).unwrap(), None);
+    }
+
+    #[test]
+    fn format_parses_and_guesses_from_path() {
+        assert_eq!("csv".parse::<LicenseFormat>(), Ok(LicenseFormat::Csv));
+        assert_eq!("ndjson".parse::<LicenseFormat>(), Ok(LicenseFormat::Ndjson));
+        assert!("xml".parse::<LicenseFormat>().is_err());
+
+        assert_eq!(
+            LicenseFormat::from_path("out/licenses.CSV"),
+            LicenseFormat::Csv
+        );
+        assert_eq!(
+            LicenseFormat::from_path("licenses.jsonl"),
+            LicenseFormat::Ndjson
+        );
+        assert_eq!(LicenseFormat::from_path("licenses"), LicenseFormat::Csv);
+    }
+}
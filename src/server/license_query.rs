@@ -0,0 +1,668 @@
+//! Filtering, sorting and cursor pagination for license listings.
+//!
+//! [`LicenseStore::list_licenses`](crate::server::store::LicenseStore::list_licenses)
+//! takes a [`LicenseFilter`], a [`LicenseSort`] and an optional
+//! [`LicenseCursor`]. The SQL backends push all three into the query, so
+//! listing every license of a large deployment never loads more than one
+//! page into memory.
+//!
+//! Pagination is keyset based: results are ordered by the sort column and
+//! then by `license_id`, and a cursor holds both values for the last license
+//! of a page. The next page starts right after that position, so licenses
+//! created or deleted in between don't shift or repeat results the way
+//! offsets do. Licenses without a value in the sort column (e.g. no
+//! `expires_at`) come last in either direction.
+//!
+//! Only indexed columns can be sorted on; see [`LicenseSortField`].
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::str::FromStr;
+
+use base64::engine::general_purpose::URL_SAFE_NO_PAD as B64;
+use base64::Engine;
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use sqlx::QueryBuilder;
+
+use crate::server::database::License;
+
+/// Format of timestamp sort values inside a cursor.
+const CURSOR_TIME_FORMAT: &str = "%Y-%m-%dT%H:%M:%S%.f";
+
+/// Filters for [`LicenseStore::list_licenses`](crate::server::store::LicenseStore::list_licenses).
+/// `None` matches everything.
+#[derive(Debug, Clone, Default)]
+pub struct LicenseFilter {
+    pub org_id: Option<String>,
+    pub status: Option<String>,
+    pub tier: Option<String>,
+    /// `true` for licenses bound to a device, `false` for unbound ones
+    pub bound: Option<bool>,
+    pub blacklisted: Option<bool>,
+    /// Only licenses expiring before this time (licenses without an expiry
+    /// never match)
+    pub expires_before: Option<NaiveDateTime>,
+    /// Only licenses issued at or after this time
+    pub created_after: Option<NaiveDateTime>,
+    /// Only licenses issued before this time
+    pub created_before: Option<NaiveDateTime>,
+    /// Only licenses whose key starts with this prefix, ignoring case
+    pub key_prefix: Option<String>,
+    /// Only licenses whose metadata has this top-level key set to this
+    /// string value
+    pub metadata: Option<(String, String)>,
+}
+
+impl LicenseFilter {
+    /// Check a license against the filter, with the same rules the SQL
+    /// backends apply.
+    pub fn matches(&self, license: &License) -> bool {
+        fn eq(wanted: &Option<String>, actual: Option<&str>) -> bool {
+            wanted.as_deref().is_none_or(|w| actual == Some(w))
+        }
+
+        eq(&self.org_id, license.org_id.as_deref())
+            && eq(&self.status, Some(license.status.as_str()))
+            && eq(&self.tier, license.tier.as_deref())
+            && self.bound.is_none_or(|b| license.is_bound() == b)
+            && self
+                .blacklisted
+                .is_none_or(|b| license.is_blacklisted.unwrap_or(false) == b)
+            && self
+                .expires_before
+                .is_none_or(|t| license.expires_at.is_some_and(|e| e < t))
+            && self.created_after.is_none_or(|t| license.issued_at >= t)
+            && self.created_before.is_none_or(|t| license.issued_at < t)
+            && self.key_prefix.as_deref().is_none_or(|prefix| {
+                license
+                    .license_key
+                    .as_deref()
+                    .is_some_and(|key| key.to_lowercase().starts_with(&prefix.to_lowercase()))
+            })
+            && self.metadata.as_ref().is_none_or(|(key, value)| {
+                license
+                    .metadata
+                    .as_deref()
+                    .and_then(|m| serde_json::from_str::<serde_json::Value>(m).ok())
+                    .is_some_and(|m| m.get(key).and_then(|v| v.as_str()) == Some(value))
+            })
+    }
+}
+
+/// Parse a `key:value` metadata filter.
+///
+/// Keys are limited to letters, digits, `_` and `-`; the value is everything
+/// after the first `:`.
+pub fn parse_metadata_filter(s: &str) -> Result<(String, String), String> {
+    let invalid = || {
+        format!(
+            "invalid metadata filter: {s}. Use key:value with a key of letters, digits, '_' or '-'"
+        )
+    };
+
+    let (key, value) = s.split_once(':').ok_or_else(invalid)?;
+    if key.is_empty()
+        || !key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+    {
+        return Err(invalid());
+    }
+    Ok((key.to_string(), value.to_string()))
+}
+
+/// Columns licenses can be sorted on. Each one is indexed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LicenseSortField {
+    #[default]
+    IssuedAt,
+    ExpiresAt,
+    LicenseId,
+    LicenseKey,
+    OrgId,
+    Status,
+    Tier,
+}
+
+impl LicenseSortField {
+    pub const ALL: [LicenseSortField; 7] = [
+        LicenseSortField::IssuedAt,
+        LicenseSortField::ExpiresAt,
+        LicenseSortField::LicenseId,
+        LicenseSortField::LicenseKey,
+        LicenseSortField::OrgId,
+        LicenseSortField::Status,
+        LicenseSortField::Tier,
+    ];
+
+    /// The column name, also used as the query parameter value.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LicenseSortField::IssuedAt => "issued_at",
+            LicenseSortField::ExpiresAt => "expires_at",
+            LicenseSortField::LicenseId => "license_id",
+            LicenseSortField::LicenseKey => "license_key",
+            LicenseSortField::OrgId => "org_id",
+            LicenseSortField::Status => "status",
+            LicenseSortField::Tier => "tier",
+        }
+    }
+
+    fn is_nullable(&self) -> bool {
+        matches!(
+            self,
+            LicenseSortField::ExpiresAt
+                | LicenseSortField::LicenseKey
+                | LicenseSortField::OrgId
+                | LicenseSortField::Tier
+        )
+    }
+
+    fn value_of(&self, license: &License) -> Option<SortValue> {
+        let text = |v: &Option<String>| v.clone().map(SortValue::Text);
+        match self {
+            LicenseSortField::IssuedAt => Some(SortValue::Timestamp(license.issued_at)),
+            LicenseSortField::ExpiresAt => license.expires_at.map(SortValue::Timestamp),
+            LicenseSortField::LicenseId => Some(SortValue::Text(license.license_id.clone())),
+            LicenseSortField::LicenseKey => text(&license.license_key),
+            LicenseSortField::OrgId => text(&license.org_id),
+            LicenseSortField::Status => Some(SortValue::Text(license.status.clone())),
+            LicenseSortField::Tier => text(&license.tier),
+        }
+    }
+}
+
+impl fmt::Display for LicenseSortField {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for LicenseSortField {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|field| field.as_str() == s)
+            .ok_or_else(|| {
+                let valid: Vec<&str> = Self::ALL.iter().map(|f| f.as_str()).collect();
+                format!(
+                    "invalid sort field: {s} (expected one of {})",
+                    valid.join(", ")
+                )
+            })
+    }
+}
+
+/// Sort direction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SortOrder {
+    #[default]
+    Asc,
+    Desc,
+}
+
+impl SortOrder {
+    fn sql(&self) -> &'static str {
+        match self {
+            SortOrder::Asc => "ASC",
+            SortOrder::Desc => "DESC",
+        }
+    }
+
+    /// Comparison operator selecting the rows after a cursor.
+    fn after_op(&self) -> &'static str {
+        match self {
+            SortOrder::Asc => ">",
+            SortOrder::Desc => "<",
+        }
+    }
+}
+
+impl FromStr for SortOrder {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "asc" => Ok(SortOrder::Asc),
+            "desc" => Ok(SortOrder::Desc),
+            _ => Err(format!("invalid sort order: {s} (expected asc or desc)")),
+        }
+    }
+}
+
+/// Sort order of a license listing. Ties are broken by `license_id`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct LicenseSort {
+    pub field: LicenseSortField,
+    pub order: SortOrder,
+}
+
+impl LicenseSort {
+    /// Compare two licenses in this sort order.
+    pub fn compare(&self, a: &License, b: &License) -> Ordering {
+        self.compare_keys(
+            self.field.value_of(a).as_ref(),
+            &a.license_id,
+            self.field.value_of(b).as_ref(),
+            &b.license_id,
+        )
+    }
+
+    fn compare_keys(
+        &self,
+        a: Option<&SortValue>,
+        a_id: &str,
+        b: Option<&SortValue>,
+        b_id: &str,
+    ) -> Ordering {
+        let by_value = match (a, b) {
+            (Some(a), Some(b)) => self.directed(a.cmp(b)),
+            // Missing values come last in both directions
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => Ordering::Equal,
+        };
+        by_value.then_with(|| self.directed(a_id.cmp(b_id)))
+    }
+
+    fn directed(&self, ordering: Ordering) -> Ordering {
+        match self.order {
+            SortOrder::Asc => ordering,
+            SortOrder::Desc => ordering.reverse(),
+        }
+    }
+}
+
+/// Value of the sort column for one license.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+enum SortValue {
+    Text(String),
+    Timestamp(NaiveDateTime),
+}
+
+/// Position in a sorted license listing, just after a given license.
+///
+/// Pass [`encode`](Self::encode)d cursors to clients as an opaque string
+/// and [`decode`](Self::decode) them on the next request. A cursor only
+/// makes sense with the sort it was created for; see [`sort`](Self::sort).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LicenseCursor {
+    sort: LicenseSort,
+    value: Option<SortValue>,
+    license_id: String,
+}
+
+/// Wire format of a cursor before base64 encoding.
+#[derive(Serialize, Deserialize)]
+struct EncodedCursor {
+    sort: LicenseSortField,
+    order: SortOrder,
+    value: Option<String>,
+    id: String,
+}
+
+impl LicenseCursor {
+    /// Cursor positioned right after `license` in `sort` order.
+    pub fn after(license: &License, sort: LicenseSort) -> Self {
+        Self {
+            sort,
+            value: sort.field.value_of(license),
+            license_id: license.license_id.clone(),
+        }
+    }
+
+    /// The sort this cursor was created for.
+    pub fn sort(&self) -> LicenseSort {
+        self.sort
+    }
+
+    /// Encode the cursor as a URL-safe string.
+    pub fn encode(&self) -> String {
+        let encoded = EncodedCursor {
+            sort: self.sort.field,
+            order: self.sort.order,
+            value: self.value.as_ref().map(|v| match v {
+                SortValue::Text(s) => s.clone(),
+                SortValue::Timestamp(t) => t.format(CURSOR_TIME_FORMAT).to_string(),
+            }),
+            id: self.license_id.clone(),
+        };
+        // Serializing a struct of strings and unit enums cannot fail
+        B64.encode(serde_json::to_vec(&encoded).unwrap_or_default())
+    }
+
+    /// Decode a cursor produced by [`encode`](Self::encode).
+    ///
+    /// Returns `None` if the string is not a valid cursor.
+    pub fn decode(s: &str) -> Option<Self> {
+        let bytes = B64.decode(s).ok()?;
+        let encoded: EncodedCursor = serde_json::from_slice(&bytes).ok()?;
+
+        let value = match (encoded.sort, encoded.value) {
+            (_, None) if !encoded.sort.is_nullable() => return None,
+            (_, None) => None,
+            (LicenseSortField::IssuedAt | LicenseSortField::ExpiresAt, Some(v)) => Some(
+                SortValue::Timestamp(NaiveDateTime::parse_from_str(&v, CURSOR_TIME_FORMAT).ok()?),
+            ),
+            (_, Some(v)) => Some(SortValue::Text(v)),
+        };
+
+        Some(Self {
+            sort: LicenseSort {
+                field: encoded.sort,
+                order: encoded.order,
+            },
+            value,
+            license_id: encoded.id,
+        })
+    }
+
+    /// Whether `license` comes after this cursor in the cursor's sort order.
+    pub fn precedes(&self, license: &License) -> bool {
+        let value = self.sort.field.value_of(license);
+        self.sort.compare_keys(
+            self.value.as_ref(),
+            &self.license_id,
+            value.as_ref(),
+            &license.license_id,
+        ) == Ordering::Less
+    }
+}
+
+// ============================================================================
+// SQL
+// ============================================================================
+
+/// SQL dialect differences that matter for license listings.
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum Dialect {
+    #[cfg(feature = "sqlite")]
+    Sqlite,
+    #[cfg(feature = "postgres")]
+    Postgres,
+}
+
+/// Append `WHERE` conditions for `filter`, and for the rows after `after`
+/// when given.
+///
+/// Column names come from [`LicenseSortField`] and never from user input;
+/// every value is a bind parameter.
+pub(crate) fn push_conditions<'a, DB>(
+    qb: &mut QueryBuilder<'a, DB>,
+    dialect: Dialect,
+    filter: &LicenseFilter,
+    after: Option<&LicenseCursor>,
+) where
+    DB: sqlx::Database,
+    String: sqlx::Encode<'a, DB> + sqlx::Type<DB>,
+    NaiveDateTime: sqlx::Encode<'a, DB> + sqlx::Type<DB>,
+{
+    qb.push(" WHERE 1 = 1");
+
+    for (column, value) in [
+        ("org_id", &filter.org_id),
+        ("status", &filter.status),
+        ("tier", &filter.tier),
+    ] {
+        if let Some(value) = value {
+            qb.push(format!(" AND {column} = "))
+                .push_bind(value.clone());
+        }
+    }
+
+    match filter.bound {
+        Some(true) => qb.push(" AND hardware_id IS NOT NULL AND bound_at IS NOT NULL"),
+        Some(false) => qb.push(" AND (hardware_id IS NULL OR bound_at IS NULL)"),
+        None => qb,
+    };
+    match filter.blacklisted {
+        Some(true) => qb.push(" AND COALESCE(is_blacklisted, FALSE) = TRUE"),
+        Some(false) => qb.push(" AND COALESCE(is_blacklisted, FALSE) = FALSE"),
+        None => qb,
+    };
+
+    if let Some(t) = filter.expires_before {
+        qb.push(" AND expires_at < ").push_bind(t);
+    }
+    if let Some(t) = filter.created_after {
+        qb.push(" AND issued_at >= ").push_bind(t);
+    }
+    if let Some(t) = filter.created_before {
+        qb.push(" AND issued_at < ").push_bind(t);
+    }
+
+    if let Some(prefix) = &filter.key_prefix {
+        let pattern = format!("{}%", escape_like(prefix));
+        match dialect {
+            // LIKE ignores ASCII case in SQLite
+            #[cfg(feature = "sqlite")]
+            Dialect::Sqlite => qb.push(" AND license_key LIKE "),
+            #[cfg(feature = "postgres")]
+            Dialect::Postgres => qb.push(" AND license_key ILIKE "),
+        };
+        qb.push_bind(pattern).push(" ESCAPE '\\'");
+    }
+
+    if let Some((key, value)) = &filter.metadata {
+        match dialect {
+            #[cfg(feature = "sqlite")]
+            Dialect::Sqlite => {
+                let path = format!("$.\"{key}\"");
+                qb.push(" AND CASE WHEN json_valid(metadata) THEN json_type(metadata, ")
+                    .push_bind(path.clone())
+                    .push(") = 'text' AND json_extract(metadata, ")
+                    .push_bind(path)
+                    .push(") = ")
+                    .push_bind(value.clone())
+                    .push(" ELSE 0 END");
+            }
+            #[cfg(feature = "postgres")]
+            Dialect::Postgres => {
+                // Casting metadata that isn't JSON would fail the whole query
+                qb.push(
+                    " AND CASE WHEN pg_input_is_valid(metadata, 'jsonb') \
+                     THEN jsonb_typeof(metadata::jsonb -> ",
+                )
+                .push_bind(key.clone())
+                .push(") = 'string' AND metadata::jsonb ->> ")
+                .push_bind(key.clone())
+                .push(" = ")
+                .push_bind(value.clone())
+                .push(" ELSE false END");
+            }
+        }
+    }
+
+    if let Some(cursor) = after {
+        push_after(qb, cursor);
+    }
+}
+
+/// Append the keyset condition selecting the rows after `cursor`.
+fn push_after<'a, DB>(qb: &mut QueryBuilder<'a, DB>, cursor: &LicenseCursor)
+where
+    DB: sqlx::Database,
+    String: sqlx::Encode<'a, DB> + sqlx::Type<DB>,
+    NaiveDateTime: sqlx::Encode<'a, DB> + sqlx::Type<DB>,
+{
+    let column = cursor.sort.field.as_str();
+    let op = cursor.sort.order.after_op();
+
+    if cursor.sort.field == LicenseSortField::LicenseId {
+        qb.push(format!(" AND license_id {op} "))
+            .push_bind(cursor.license_id.clone());
+        return;
+    }
+
+    match &cursor.value {
+        Some(value) => {
+            qb.push(format!(" AND ({column} {op} "));
+            push_value(qb, value);
+            qb.push(format!(" OR ({column} = "));
+            push_value(qb, value);
+            qb.push(format!(" AND license_id {op} "))
+                .push_bind(cursor.license_id.clone())
+                .push(")");
+            if cursor.sort.field.is_nullable() {
+                // Rows without a value come after every row with one
+                qb.push(format!(" OR {column} IS NULL"));
+            }
+            qb.push(")");
+        }
+        None => {
+            qb.push(format!(" AND {column} IS NULL AND license_id {op} "))
+                .push_bind(cursor.license_id.clone());
+        }
+    }
+}
+
+fn push_value<'a, DB>(qb: &mut QueryBuilder<'a, DB>, value: &SortValue)
+where
+    DB: sqlx::Database,
+    String: sqlx::Encode<'a, DB> + sqlx::Type<DB>,
+    NaiveDateTime: sqlx::Encode<'a, DB> + sqlx::Type<DB>,
+{
+    match value {
+        SortValue::Text(s) => qb.push_bind(s.clone()),
+        SortValue::Timestamp(t) => qb.push_bind(*t),
+    };
+}
+
+/// Append the `ORDER BY` clause for `sort`.
+pub(crate) fn push_order_by<DB: sqlx::Database>(qb: &mut QueryBuilder<'_, DB>, sort: LicenseSort) {
+    let direction = sort.order.sql();
+    match sort.field {
+        LicenseSortField::LicenseId => {
+            qb.push(format!(" ORDER BY license_id {direction}"));
+        }
+        field if field.is_nullable() => {
+            qb.push(format!(
+                " ORDER BY {field} {direction} NULLS LAST, license_id {direction}"
+            ));
+        }
+        field => {
+            qb.push(format!(
+                " ORDER BY {field} {direction}, license_id {direction}"
+            ));
+        }
+    }
+}
+
+/// Escape `%`, `_` and the escape character itself for a `LIKE` pattern.
+fn escape_like(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        if matches!(c, '%' | '_' | '\\') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    fn license(id: &str, expires_at: Option<NaiveDateTime>) -> License {
+        let issued_at = NaiveDate::from_ymd_opt(2026, 1, 1)
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap();
+        License {
+            license_id: id.to_string(),
+            client_id: None,
+            status: "active".to_string(),
+            features: None,
+            issued_at,
+            expires_at,
+            hardware_id: None,
+            signature: None,
+            last_heartbeat: None,
+            org_id: None,
+            org_name: None,
+            license_key: None,
+            tier: None,
+            device_name: None,
+            device_info: None,
+            bound_at: None,
+            last_seen_at: None,
+            suspended_at: None,
+            revoked_at: None,
+            revoke_reason: None,
+            grace_period_ends_at: None,
+            suspension_message: None,
+            is_blacklisted: None,
+            blacklisted_at: None,
+            blacklist_reason: None,
+            metadata: None,
+            bandwidth_used_bytes: None,
+            bandwidth_limit_bytes: None,
+            quota_exceeded: None,
+            max_devices: 1,
+            max_concurrent: None,
+            offline_days: None,
+            billing_cycle: None,
+            usage_period_start: None,
+            suspended_by_org: None,
+        }
+    }
+
+    #[test]
+    fn cursor_round_trips() {
+        let expires = NaiveDate::from_ymd_opt(2026, 6, 1)
+            .unwrap()
+            .and_hms_micro_opt(12, 30, 0, 250)
+            .unwrap();
+        let sort = LicenseSort {
+            field: LicenseSortField::ExpiresAt,
+            order: SortOrder::Desc,
+        };
+
+        for l in [license("a", Some(expires)), license("b", None)] {
+            let cursor = LicenseCursor::after(&l, sort);
+            assert_eq!(LicenseCursor::decode(&cursor.encode()), Some(cursor));
+        }
+
+        assert_eq!(LicenseCursor::decode("not a cursor"), None);
+    }
+
+    #[test]
+    fn missing_values_sort_last_in_both_directions() {
+        let early = NaiveDate::from_ymd_opt(2026, 2, 1)
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap();
+        let late = early + chrono::Duration::days(30);
+        let mut licenses = [
+            license("never", None),
+            license("late", Some(late)),
+            license("early", Some(early)),
+        ];
+
+        for (order, expected) in [
+            (SortOrder::Asc, ["early", "late", "never"]),
+            (SortOrder::Desc, ["late", "early", "never"]),
+        ] {
+            let sort = LicenseSort {
+                field: LicenseSortField::ExpiresAt,
+                order,
+            };
+            licenses.sort_by(|a, b| sort.compare(a, b));
+            let ids: Vec<&str> = licenses.iter().map(|l| l.license_id.as_str()).collect();
+            assert_eq!(ids, expected);
+
+            // Each license comes after the cursor of the one before it
+            for pair in licenses.windows(2) {
+                assert!(LicenseCursor::after(&pair[0], sort).precedes(&pair[1]));
+                assert!(!LicenseCursor::after(&pair[1], sort).precedes(&pair[0]));
+            }
+        }
+    }
+}
@@ -53,6 +53,10 @@ pub enum LicenseEvent {
     Extended,
     /// License was blacklisted
     Blacklisted,
+    /// License passed its expiration date
+    Expired,
+    /// License usage reached its quota
+    QuotaExceeded,
     /// License heartbeat received
     Heartbeat,
     /// License usage updated
@@ -74,6 +78,8 @@ impl std::fmt::Display for LicenseEvent {
             LicenseEvent::Suspended => "suspended",
             LicenseEvent::Extended => "extended",
             LicenseEvent::Blacklisted => "blacklisted",
+            LicenseEvent::Expired => "expired",
+            LicenseEvent::QuotaExceeded => "quota_exceeded",
             LicenseEvent::Heartbeat => "heartbeat",
             LicenseEvent::UsageUpdated => "usage_updated",
         };
@@ -154,6 +160,11 @@ pub fn generate_request_id() -> String {
     Uuid::new_v4().to_string()
 }
 
+/// ID of the current request, stored in the request extensions by
+/// [`request_logging_middleware`] so handlers can refer to it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequestId(pub String);
+
 /// Logging middleware that tracks request timing and generates request IDs.
 ///
 /// This middleware:
@@ -162,8 +173,13 @@ pub fn generate_request_id() -> String {
 /// 3. Logs the request method and path
 /// 4. Measures and logs the response time
 /// 5. Adds the request ID to the response headers
-pub async fn request_logging_middleware(request: Request, next: Next) -> Response<Body> {
+///
+/// The ID is also available to handlers as a [`RequestId`] extension.
+pub async fn request_logging_middleware(mut request: Request, next: Next) -> Response<Body> {
     let request_id = generate_request_id();
+    request
+        .extensions_mut()
+        .insert(RequestId(request_id.clone()));
     let method = request.method().clone();
     let uri = request.uri().clone();
     let path = uri.path().to_string();
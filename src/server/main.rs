@@ -1,23 +1,41 @@
 use std::net::SocketAddr;
+use std::sync::Arc;
 
 use tokio::net::TcpListener;
 use tracing::{info, warn};
 
 use talos::config::init_config;
 use talos::errors::{LicenseError, LicenseResult};
-use talos::server::bootstrap::{check_bootstrap_token, execute_token_command, parse_token_command};
+use talos::server::bootstrap::{
+    check_bootstrap_token, execute_license_command, execute_migrate_command,
+    execute_signing_key_command, execute_token_command, parse_license_command,
+    parse_migrate_command, parse_signing_key_command, parse_token_command,
+};
 use talos::server::database::Database;
 use talos::server::handlers::AppState;
 use talos::server::routes::build_router;
+use talos::server::store::LicenseStore;
+use talos::server::webhooks::WebhookDispatcher;
+use talos::signing::LicenseSigner;
 
 #[cfg(feature = "jwt-auth")]
 use talos::server::auth::AuthState;
 
+#[cfg(feature = "background-jobs")]
+use talos::jobs::JobScheduler;
+
 #[tokio::main]
 async fn main() -> LicenseResult<()> {
-    // Parse CLI arguments for token commands
+    // Parse CLI arguments for token, migration and license commands
     let args: Vec<String> = std::env::args().collect();
     let token_cmd = parse_token_command(&args);
+    let migrate_cmd = parse_migrate_command(&args);
+    let license_cmd = parse_license_command(&args);
+
+    // Signing key generation needs neither config nor database
+    if parse_signing_key_command(&args) {
+        return execute_signing_key_command();
+    }
 
     // Load and validate configuration first
     let config = init_config()?;
@@ -30,13 +48,36 @@ async fn main() -> LicenseResult<()> {
     // Initialize the database from config
     let db = Database::new().await?;
 
+    // Migration commands manage the schema themselves (these run and exit)
+    if execute_migrate_command(&db, migrate_cmd).await? {
+        return Ok(());
+    }
+
+    // Bring the schema up to date before anything touches it
+    if config.database.run_migrations {
+        let applied = db.run_migrations().await?;
+        if !applied.is_empty() {
+            info!("Applied {} database migration(s)", applied.len());
+        }
+    } else {
+        info!("Automatic migrations disabled; run `talos_server migrate up` to apply them");
+    }
+
+    // Everything past this point only needs the storage interface
+    let db: Arc<dyn LicenseStore> = db;
+
     // Check for CLI token commands (these run and exit)
-    if execute_token_command(&db, token_cmd).await? {
+    if execute_token_command(&*db, token_cmd).await? {
         return Ok(()); // Command executed, exit
     }
 
+    // Check for CLI license export/import commands (these run and exit)
+    if execute_license_command(&*db, license_cmd).await? {
+        return Ok(());
+    }
+
     // Check for bootstrap token on startup
-    if let Some(raw_token) = check_bootstrap_token(&db).await? {
+    if let Some(raw_token) = check_bootstrap_token(&*db).await? {
         warn!("═══════════════════════════════════════════════════════");
         warn!("BOOTSTRAP TOKEN CREATED - SAVE THIS VALUE:");
         warn!("{}", raw_token);
@@ -45,13 +86,63 @@ async fn main() -> LicenseResult<()> {
 
     // Build auth state if jwt-auth feature is enabled
     #[cfg(feature = "jwt-auth")]
-    let auth = AuthState::from_config(&config.auth)?;
+    let auth = AuthState::from_config(&config.auth)?.with_database(db.clone());
+
+    // Load the license signing key, if configured
+    let signer = LicenseSigner::from_config(&config.signing)?.map(Arc::new);
+    match &signer {
+        Some(signer) => info!(
+            "License signing enabled, public key: {}",
+            signer.public_key_base64()
+        ),
+        None => warn!("No signing key configured; signed license documents are disabled"),
+    }
+
+    // Start background jobs (expiration, grace period, stale devices)
+    #[cfg(feature = "background-jobs")]
+    let scheduler = if config.jobs.enabled {
+        let scheduler = JobScheduler::new(db.clone(), config.jobs.clone())
+            .await
+            .map_err(|e| {
+                LicenseError::ServerError(format!("failed to create job scheduler: {e}"))
+            })?;
+        scheduler.start().await.map_err(|e| {
+            LicenseError::ConfigError(format!("failed to start job scheduler: {e}"))
+        })?;
+        Some(scheduler)
+    } else {
+        info!("Background jobs disabled");
+        None
+    };
+
+    // Deliver queued webhook notifications
+    let dispatcher = if config.webhooks.enabled {
+        Some(WebhookDispatcher::start(
+            db.clone(),
+            config.webhooks.clone(),
+        )?)
+    } else {
+        info!("Webhooks disabled");
+        None
+    };
+
+    #[cfg(feature = "rate-limiting")]
+    if config.rate_limit.enabled {
+        info!(
+            "Rate limiting enabled: validate={}/min heartbeat={}/min bind={}/min burst={}",
+            config.rate_limit.validate_rpm,
+            config.rate_limit.heartbeat_rpm,
+            config.rate_limit.bind_rpm,
+            config.rate_limit.burst_size
+        );
+    }
 
     // Build shared app state
     let state = AppState {
         db,
         #[cfg(feature = "jwt-auth")]
         auth,
+        signer,
     };
 
     // Set up the Axum router with all routes (legacy, client API, admin API, Swagger UI)
@@ -70,12 +161,61 @@ async fn main() -> LicenseResult<()> {
 
     // Serve the application with ConnectInfo for IP extraction
     // This enables IP whitelist and rate limiting middleware to determine client IPs
-    axum::serve(
+    let result = axum::serve(
         listener,
         app.into_make_service_with_connect_info::<SocketAddr>(),
     )
-    .await
-    .map_err(|e| LicenseError::ServerError(format!("server failed: {e}")))?;
+    .with_graceful_shutdown(shutdown_signal())
+    .await;
+
+    // Stop background jobs once the HTTP server has drained
+    #[cfg(feature = "background-jobs")]
+    if let Some(mut scheduler) = scheduler {
+        if let Err(e) = scheduler.shutdown().await {
+            warn!("Failed to shut down job scheduler: {}", e);
+        }
+    }
+
+    if let Some(dispatcher) = dispatcher {
+        dispatcher.shutdown().await;
+    }
+
+    result.map_err(|e| LicenseError::ServerError(format!("server failed: {e}")))?;
+
+    info!("Talos server stopped");
 
     Ok(())
 }
+
+/// Resolve when the process receives Ctrl+C or (on Unix) SIGTERM.
+async fn shutdown_signal() {
+    let ctrl_c = async {
+        if let Err(e) = tokio::signal::ctrl_c().await {
+            warn!("Failed to listen for Ctrl+C: {}", e);
+            std::future::pending::<()>().await;
+        }
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()) {
+            Ok(mut signal) => {
+                signal.recv().await;
+            }
+            Err(e) => {
+                warn!("Failed to listen for SIGTERM: {}", e);
+                std::future::pending::<()>().await;
+            }
+        }
+    };
+
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {},
+        _ = terminate => {},
+    }
+
+    info!("Shutdown signal received, stopping server");
+}
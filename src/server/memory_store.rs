@@ -0,0 +1,1182 @@
+//! In-memory license store.
+//!
+//! [`MemoryStore`] implements the storage traits in
+//! [`crate::server::store`] without a database. Everything lives in process
+//! and is lost when the store is dropped, which makes it a good fit for tests
+//! and for embedding the server where persistence is handled elsewhere.
+//!
+//! Every operation runs under one lock, so seat claims and lease checkouts
+//! are atomic.
+//!
+//! # Usage
+//!
+//! ```rust,ignore
+//! use std::sync::Arc;
+//! use talos::server::{LicenseStore, MemoryStore};
+//!
+//! let db: Arc<dyn LicenseStore> = Arc::new(MemoryStore::new());
+//! ```
+
+use std::collections::BTreeMap;
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+use axum::async_trait;
+use chrono::{Duration, NaiveDateTime, Utc};
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use crate::errors::{LicenseError, LicenseResult};
+use crate::server::audit::{AuditEntry, AuditFilter};
+use crate::server::database::{
+    lease_limit_reached, seat_limit, BindingAction, BindingHistoryFilter, LeaseCheckout, License,
+    LicenseBindingHistory, LicenseDevice, LicenseLease, PerformedBy, SeatClaim,
+};
+use crate::server::license_query::{LicenseCursor, LicenseFilter, LicenseSort};
+use crate::server::orgs::{OrgStats, Organization};
+use crate::server::store::{
+    AuditStore, LicenseStore, OrgStore, TokenStore, TrialStore, UsageStore, WebhookStore,
+};
+use crate::server::tokens::{generate_raw_token, hash_token, ApiToken};
+use crate::server::trials::TrialRegistration;
+use crate::server::usage::{RecordedUsage, UsageMeter, UsagePeriod, UsageReport};
+use crate::server::webhooks::{Webhook, WebhookDelivery};
+use crate::tiers::BANDWIDTH_METER;
+
+/// A [`LicenseStore`] that keeps everything in memory.
+#[derive(Debug, Default)]
+pub struct MemoryStore {
+    state: Mutex<State>,
+}
+
+#[derive(Debug, Default)]
+struct State {
+    orgs: BTreeMap<String, Organization>,
+    licenses: BTreeMap<String, License>,
+    devices: Vec<LicenseDevice>,
+    leases: Vec<LicenseLease>,
+    binding_history: Vec<LicenseBindingHistory>,
+    tokens: Vec<ApiToken>,
+    audit_log: Vec<AuditEntry>,
+    webhooks: Vec<Webhook>,
+    deliveries: Vec<WebhookDelivery>,
+    trials: Vec<TrialRegistration>,
+    /// Keyed on license ID and meter name
+    usage_meters: BTreeMap<(String, String), UsageMeter>,
+    usage_reports: Vec<UsageReport>,
+    usage_periods: Vec<UsagePeriod>,
+}
+
+impl MemoryStore {
+    /// Create an empty store.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn state(&self) -> MutexGuard<'_, State> {
+        // A panic while holding the lock cannot leave the maps half-updated
+        // in a way later calls would trip over, so keep serving
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl State {
+    fn license_mut(&mut self, license_id: &str) -> Option<&mut License> {
+        self.licenses.get_mut(license_id)
+    }
+
+    /// Fail like a unique index would if another license has this key.
+    fn check_key_free(&self, license: &License) -> LicenseResult<()> {
+        let Some(key) = &license.license_key else {
+            return Ok(());
+        };
+        let taken = self.licenses.values().any(|other| {
+            other.license_id != license.license_id && other.license_key.as_ref() == Some(key)
+        });
+        if taken {
+            return Err(LicenseError::ServerError(format!(
+                "database error: license key {key} is already in use"
+            )));
+        }
+        Ok(())
+    }
+
+    /// Insert or overwrite a license.
+    ///
+    /// Like the database, keeps the `usage_period_start` of an existing
+    /// license; only `close_usage_period` moves it.
+    fn put_license(&mut self, mut license: License) {
+        if let Some(existing) = self.licenses.get(&license.license_id) {
+            license.usage_period_start = existing.usage_period_start;
+        }
+        self.licenses.insert(license.license_id.clone(), license);
+    }
+
+    /// Devices holding a seat on a license, oldest binding first.
+    fn devices_of(&self, license_id: &str) -> Vec<LicenseDevice> {
+        let mut devices: Vec<LicenseDevice> = self
+            .devices
+            .iter()
+            .filter(|d| d.license_id == license_id)
+            .cloned()
+            .collect();
+        devices.sort_by(|a, b| (a.bound_at, &a.hardware_id).cmp(&(b.bound_at, &b.hardware_id)));
+        devices
+    }
+
+    /// Insert a seat and point the license's binding fields at it.
+    fn take_seat(
+        &mut self,
+        license_id: &str,
+        hardware_id: &str,
+        device_name: Option<&str>,
+        device_info: Option<&str>,
+        now: NaiveDateTime,
+    ) -> LicenseResult<bool> {
+        if !self.licenses.contains_key(license_id) {
+            return Err(LicenseError::InvalidLicense(format!(
+                "license {license_id} not found"
+            )));
+        }
+        if self
+            .devices
+            .iter()
+            .any(|d| d.license_id == license_id && d.hardware_id == hardware_id)
+        {
+            return Ok(false);
+        }
+
+        self.devices.push(LicenseDevice {
+            license_id: license_id.to_string(),
+            hardware_id: hardware_id.to_string(),
+            device_name: device_name.map(String::from),
+            device_info: device_info.map(String::from),
+            bound_at: now,
+            last_seen_at: Some(now),
+        });
+
+        if let Some(license) = self.license_mut(license_id) {
+            license.hardware_id = Some(hardware_id.to_string());
+            license.device_name = device_name.map(String::from);
+            license.device_info = device_info.map(String::from);
+            license.bound_at = Some(now);
+            license.last_seen_at = Some(now);
+        }
+
+        Ok(true)
+    }
+
+    fn release(&mut self, license_id: &str) -> bool {
+        let Some(license) = self.license_mut(license_id) else {
+            return false;
+        };
+        license.hardware_id = None;
+        license.device_name = None;
+        license.device_info = None;
+        license.bound_at = None;
+        true
+    }
+
+    /// Point a license's binding fields at its most recently bound device.
+    fn sync_binding_fields(&mut self, license_id: &str) {
+        let devices = self.devices_of(license_id);
+        let Some(device) = devices.iter().max_by_key(|d| d.bound_at) else {
+            self.release(license_id);
+            return;
+        };
+
+        if let Some(license) = self.license_mut(license_id) {
+            license.hardware_id = Some(device.hardware_id.clone());
+            license.device_name = device.device_name.clone();
+            license.device_info = device.device_info.clone();
+            license.bound_at = Some(device.bound_at);
+        }
+    }
+
+    /// Leases on a license that have not expired by `now`.
+    fn active_leases(&self, license_id: &str, now: NaiveDateTime) -> Vec<LicenseLease> {
+        let mut leases: Vec<LicenseLease> = self
+            .leases
+            .iter()
+            .filter(|l| l.license_id == license_id && l.expires_at > now)
+            .cloned()
+            .collect();
+        leases.sort_by(|a, b| {
+            (a.checked_out_at, &a.hardware_id).cmp(&(b.checked_out_at, &b.hardware_id))
+        });
+        leases
+    }
+
+    fn remove_lease(&mut self, license_id: &str, hardware_id: &str) -> bool {
+        let before = self.leases.len();
+        self.leases
+            .retain(|l| !(l.license_id == license_id && l.hardware_id == hardware_id));
+        self.leases.len() < before
+    }
+}
+
+/// One page of `items`, as `LIMIT limit OFFSET offset` would return it.
+fn page<T>(items: Vec<T>, limit: u32, offset: u32) -> Vec<T> {
+    items
+        .into_iter()
+        .skip(offset as usize)
+        .take(limit as usize)
+        .collect()
+}
+
+#[async_trait]
+impl LicenseStore for MemoryStore {
+    fn backend(&self) -> &'static str {
+        "memory"
+    }
+
+    async fn health_check(&self) -> bool {
+        true
+    }
+
+    async fn insert_license(&self, license: License) -> LicenseResult<()> {
+        let mut state = self.state();
+        state.check_key_free(&license)?;
+        state.put_license(license);
+        Ok(())
+    }
+
+    async fn insert_licenses(&self, licenses: &[License], free_seats: bool) -> LicenseResult<()> {
+        let mut state = self.state();
+        for license in licenses {
+            state.check_key_free(license)?;
+        }
+
+        for license in licenses {
+            let license_id = license.license_id.clone();
+            state.put_license(license.clone());
+            if free_seats {
+                state.devices.retain(|d| d.license_id != license_id);
+                state.leases.retain(|l| l.license_id != license_id);
+                state.release(&license_id);
+            }
+        }
+        Ok(())
+    }
+
+    async fn get_license(&self, license_id: &str) -> LicenseResult<Option<License>> {
+        Ok(self.state().licenses.get(license_id).cloned())
+    }
+
+    async fn update_last_heartbeat(
+        &self,
+        license_id: &str,
+        client_id: &str,
+    ) -> LicenseResult<bool> {
+        let mut state = self.state();
+        match state.license_mut(license_id) {
+            Some(license) if license.client_id.as_deref() == Some(client_id) => {
+                license.last_heartbeat = Some(Utc::now().naive_utc());
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
+    }
+
+    async fn get_license_by_key(&self, license_key: &str) -> LicenseResult<Option<License>> {
+        Ok(self
+            .state()
+            .licenses
+            .values()
+            .find(|l| l.license_key.as_deref() == Some(license_key))
+            .cloned())
+    }
+
+    async fn license_key_exists(&self, license_key: &str) -> LicenseResult<bool> {
+        Ok(self
+            .state()
+            .licenses
+            .values()
+            .any(|l| l.license_key.as_deref() == Some(license_key)))
+    }
+
+    async fn list_licenses_by_org(&self, org_id: &str) -> LicenseResult<Vec<License>> {
+        Ok(self
+            .state()
+            .licenses
+            .values()
+            .filter(|l| l.org_id.as_deref() == Some(org_id))
+            .cloned()
+            .collect())
+    }
+
+    async fn list_licenses(
+        &self,
+        filter: &LicenseFilter,
+        sort: LicenseSort,
+        after: Option<&LicenseCursor>,
+        limit: u32,
+        offset: u32,
+    ) -> LicenseResult<(Vec<License>, u64)> {
+        let state = self.state();
+        let mut matching: Vec<&License> = state
+            .licenses
+            .values()
+            .filter(|l| filter.matches(l))
+            .collect();
+        let total = matching.len() as u64;
+        matching.sort_by(|a, b| sort.compare(a, b));
+
+        let page = matching
+            .into_iter()
+            .filter(|l| after.is_none_or(|cursor| cursor.precedes(l)))
+            .skip(offset as usize)
+            .take(limit as usize)
+            .cloned()
+            .collect();
+        Ok((page, total))
+    }
+
+    async fn update_license_status(&self, license_id: &str, status: &str) -> LicenseResult<bool> {
+        let mut state = self.state();
+        let Some(license) = state.license_mut(license_id) else {
+            return Ok(false);
+        };
+        license.status = status.to_string();
+        Ok(true)
+    }
+
+    async fn bind_license(
+        &self,
+        license_id: &str,
+        hardware_id: &str,
+        device_name: Option<&str>,
+        device_info: Option<&str>,
+    ) -> LicenseResult<bool> {
+        let now = Utc::now().naive_utc();
+        let mut state = self.state();
+        let Some(license) = state.license_mut(license_id) else {
+            return Ok(false);
+        };
+        license.hardware_id = Some(hardware_id.to_string());
+        license.device_name = device_name.map(String::from);
+        license.device_info = device_info.map(String::from);
+        license.bound_at = Some(now);
+        license.last_seen_at = Some(now);
+        Ok(true)
+    }
+
+    async fn release_license(&self, license_id: &str) -> LicenseResult<bool> {
+        Ok(self.state().release(license_id))
+    }
+
+    async fn record_binding_history(
+        &self,
+        license_id: &str,
+        action: BindingAction,
+        hardware_id: Option<&str>,
+        device_name: Option<&str>,
+        device_info: Option<&str>,
+        performed_by: PerformedBy,
+        reason: Option<&str>,
+    ) -> LicenseResult<()> {
+        let mut state = self.state();
+        let id = state.binding_history.len() as i64 + 1;
+        state.binding_history.push(LicenseBindingHistory {
+            id,
+            license_id: license_id.to_string(),
+            action: action.as_str().to_string(),
+            hardware_id: hardware_id.map(String::from),
+            device_name: device_name.map(String::from),
+            device_info: device_info.map(String::from),
+            performed_by: Some(performed_by.as_str().to_string()),
+            reason: reason.map(String::from),
+            created_at: Utc::now().naive_utc(),
+        });
+        Ok(())
+    }
+
+    async fn list_binding_history(
+        &self,
+        filter: &BindingHistoryFilter,
+        limit: u32,
+        offset: u32,
+    ) -> LicenseResult<(Vec<LicenseBindingHistory>, u64)> {
+        let mut entries: Vec<LicenseBindingHistory> = self
+            .state()
+            .binding_history
+            .iter()
+            .filter(|h| {
+                filter
+                    .license_id
+                    .as_deref()
+                    .is_none_or(|id| h.license_id == id)
+                    && filter
+                        .hardware_id
+                        .as_deref()
+                        .is_none_or(|hw| h.hardware_id.as_deref() == Some(hw))
+                    && filter.action.is_none_or(|a| h.action == a.as_str())
+                    && filter
+                        .performed_by
+                        .is_none_or(|p| h.performed_by.as_deref() == Some(p.as_str()))
+            })
+            .cloned()
+            .collect();
+        entries.sort_by_key(|h| std::cmp::Reverse((h.created_at, h.id)));
+
+        let total = entries.len() as u64;
+        Ok((page(entries, limit, offset), total))
+    }
+
+    async fn update_last_seen(&self, license_id: &str) -> LicenseResult<bool> {
+        let mut state = self.state();
+        let Some(license) = state.license_mut(license_id) else {
+            return Ok(false);
+        };
+        license.last_seen_at = Some(Utc::now().naive_utc());
+        Ok(true)
+    }
+
+    async fn list_license_devices(&self, license_id: &str) -> LicenseResult<Vec<LicenseDevice>> {
+        Ok(self.state().devices_of(license_id))
+    }
+
+    async fn get_license_device(
+        &self,
+        license_id: &str,
+        hardware_id: &str,
+    ) -> LicenseResult<Option<LicenseDevice>> {
+        Ok(self
+            .state()
+            .devices
+            .iter()
+            .find(|d| d.license_id == license_id && d.hardware_id == hardware_id)
+            .cloned())
+    }
+
+    async fn add_license_device(
+        &self,
+        license_id: &str,
+        hardware_id: &str,
+        device_name: Option<&str>,
+        device_info: Option<&str>,
+    ) -> LicenseResult<bool> {
+        let now = Utc::now().naive_utc();
+        self.state()
+            .take_seat(license_id, hardware_id, device_name, device_info, now)
+    }
+
+    async fn claim_license_seat(
+        &self,
+        license: &License,
+        hardware_id: &str,
+        device_name: Option<&str>,
+        device_info: Option<&str>,
+    ) -> LicenseResult<SeatClaim> {
+        let license_id = license.license_id.as_str();
+        let now = Utc::now().naive_utc();
+        let mut state = self.state();
+
+        let Some(max_devices) = state.licenses.get(license_id).map(|l| l.max_devices) else {
+            return Err(LicenseError::InvalidLicense(format!(
+                "license {license_id} not found"
+            )));
+        };
+
+        let devices = state.devices_of(license_id);
+        if let Some(device) = devices.iter().find(|d| d.hardware_id == hardware_id) {
+            return Ok(SeatClaim::Existing(device.clone()));
+        }
+        if devices.len() >= seat_limit(max_devices) {
+            return Ok(SeatClaim::Full(devices));
+        }
+
+        state.take_seat(license_id, hardware_id, device_name, device_info, now)?;
+        Ok(SeatClaim::Claimed)
+    }
+
+    async fn remove_license_device(
+        &self,
+        license_id: &str,
+        hardware_id: &str,
+    ) -> LicenseResult<bool> {
+        let mut state = self.state();
+        let before = state.devices.len();
+        state
+            .devices
+            .retain(|d| !(d.license_id == license_id && d.hardware_id == hardware_id));
+        let removed = state.devices.len() < before;
+
+        if removed {
+            state.remove_lease(license_id, hardware_id);
+            state.sync_binding_fields(license_id);
+        }
+
+        Ok(removed)
+    }
+
+    async fn remove_all_license_devices(&self, license_id: &str) -> LicenseResult<u64> {
+        let mut state = self.state();
+        let before = state.devices.len();
+        state.devices.retain(|d| d.license_id != license_id);
+        let removed = (before - state.devices.len()) as u64;
+
+        state.leases.retain(|l| l.license_id != license_id);
+        state.release(license_id);
+
+        Ok(removed)
+    }
+
+    async fn touch_license_device(
+        &self,
+        license_id: &str,
+        hardware_id: &str,
+    ) -> LicenseResult<bool> {
+        let now = Utc::now().naive_utc();
+        let mut state = self.state();
+
+        let device = state
+            .devices
+            .iter_mut()
+            .find(|d| d.license_id == license_id && d.hardware_id == hardware_id);
+        let touched = match device {
+            Some(device) => {
+                device.last_seen_at = Some(now);
+                true
+            }
+            None => false,
+        };
+        if let Some(license) = state.license_mut(license_id) {
+            license.last_seen_at = Some(now);
+        }
+
+        Ok(touched)
+    }
+
+    async fn list_active_leases(
+        &self,
+        license_id: &str,
+        now: NaiveDateTime,
+    ) -> LicenseResult<Vec<LicenseLease>> {
+        Ok(self.state().active_leases(license_id, now))
+    }
+
+    async fn checkout_license_lease(
+        &self,
+        license: &License,
+        hardware_id: &str,
+        ttl: Duration,
+    ) -> LicenseResult<LeaseCheckout> {
+        let now = Utc::now().naive_utc();
+        let mut state = self.state();
+        let leases = state.active_leases(&license.license_id, now);
+
+        if let Some(lease) = leases.iter().find(|l| l.hardware_id == hardware_id) {
+            let lease = LicenseLease {
+                expires_at: now + ttl,
+                ..lease.clone()
+            };
+            state.remove_lease(&lease.license_id, hardware_id);
+            state.leases.push(lease.clone());
+            return Ok(LeaseCheckout::Granted(lease));
+        }
+
+        if lease_limit_reached(license.max_concurrent, leases.len()) {
+            return Ok(LeaseCheckout::Full(leases.len()));
+        }
+
+        let lease = LicenseLease {
+            license_id: license.license_id.clone(),
+            hardware_id: hardware_id.to_string(),
+            checked_out_at: now,
+            expires_at: now + ttl,
+        };
+        // Replaces an expired lease held by the same device
+        state.remove_lease(&lease.license_id, hardware_id);
+        state.leases.push(lease.clone());
+
+        Ok(LeaseCheckout::Granted(lease))
+    }
+
+    async fn renew_license_lease(
+        &self,
+        license_id: &str,
+        hardware_id: &str,
+        ttl: Duration,
+    ) -> LicenseResult<Option<NaiveDateTime>> {
+        let now = Utc::now().naive_utc();
+        let expires_at = now + ttl;
+
+        let mut state = self.state();
+        let lease = state.leases.iter_mut().find(|l| {
+            l.license_id == license_id && l.hardware_id == hardware_id && l.expires_at > now
+        });
+
+        Ok(lease.map(|lease| {
+            lease.expires_at = expires_at;
+            expires_at
+        }))
+    }
+
+    async fn checkin_license_lease(
+        &self,
+        license_id: &str,
+        hardware_id: &str,
+    ) -> LicenseResult<bool> {
+        Ok(self.state().remove_lease(license_id, hardware_id))
+    }
+
+    async fn delete_expired_leases(&self, now: NaiveDateTime) -> LicenseResult<u64> {
+        let mut state = self.state();
+        let before = state.leases.len();
+        state.leases.retain(|l| l.expires_at > now);
+        Ok((before - state.leases.len()) as u64)
+    }
+
+    async fn update_license_signature(
+        &self,
+        license_id: &str,
+        signature: &str,
+    ) -> LicenseResult<bool> {
+        let mut state = self.state();
+        let Some(license) = state.license_mut(license_id) else {
+            return Ok(false);
+        };
+        license.signature = Some(signature.to_string());
+        Ok(true)
+    }
+
+    async fn get_expired_grace_period_licenses(
+        &self,
+        now: NaiveDateTime,
+    ) -> LicenseResult<Vec<License>> {
+        Ok(self
+            .state()
+            .licenses
+            .values()
+            .filter(|l| l.status == "suspended" && l.grace_period_ends_at.is_some_and(|g| g < now))
+            .cloned()
+            .collect())
+    }
+
+    async fn get_expired_licenses(&self, now: NaiveDateTime) -> LicenseResult<Vec<License>> {
+        Ok(self
+            .state()
+            .licenses
+            .values()
+            .filter(|l| l.status == "active" && l.expires_at.is_some_and(|e| e < now))
+            .cloned()
+            .collect())
+    }
+
+    async fn get_stale_devices(
+        &self,
+        threshold: NaiveDateTime,
+    ) -> LicenseResult<Vec<LicenseDevice>> {
+        Ok(self
+            .state()
+            .devices
+            .iter()
+            .filter(|d| d.last_seen_at.is_some_and(|seen| seen < threshold))
+            .cloned()
+            .collect())
+    }
+}
+
+#[async_trait]
+impl OrgStore for MemoryStore {
+    async fn insert_org(&self, org: &Organization) -> LicenseResult<()> {
+        let mut state = self.state();
+        if state.orgs.contains_key(&org.id) {
+            return Err(LicenseError::ServerError(format!(
+                "database error: organization {} already exists",
+                org.id
+            )));
+        }
+        state.orgs.insert(org.id.clone(), org.clone());
+        Ok(())
+    }
+
+    async fn update_org(&self, org: &Organization) -> LicenseResult<()> {
+        let mut state = self.state();
+        let Some(stored) = state.orgs.get_mut(&org.id) else {
+            return Ok(());
+        };
+        *stored = Organization {
+            created_at: stored.created_at,
+            ..org.clone()
+        };
+
+        for license in state.licenses.values_mut() {
+            if license.org_id.as_deref() == Some(org.id.as_str()) {
+                license.org_name = Some(org.name.clone());
+            }
+        }
+        Ok(())
+    }
+
+    async fn get_org(&self, org_id: &str) -> LicenseResult<Option<Organization>> {
+        Ok(self.state().orgs.get(org_id).cloned())
+    }
+
+    async fn list_orgs(
+        &self,
+        status: Option<&str>,
+        limit: u32,
+        offset: u32,
+    ) -> LicenseResult<(Vec<Organization>, u64)> {
+        let mut orgs: Vec<Organization> = self
+            .state()
+            .orgs
+            .values()
+            .filter(|o| status.is_none_or(|s| o.status == s))
+            .cloned()
+            .collect();
+        orgs.sort_by(|a, b| (a.created_at, &a.id).cmp(&(b.created_at, &b.id)));
+
+        let total = orgs.len() as u64;
+        Ok((page(orgs, limit, offset), total))
+    }
+
+    async fn delete_org(&self, org_id: &str) -> LicenseResult<bool> {
+        Ok(self.state().orgs.remove(org_id).is_some())
+    }
+
+    async fn org_stats(&self, org_id: &str) -> LicenseResult<OrgStats> {
+        let state = self.state();
+        let mut stats = OrgStats::default();
+
+        for license in state
+            .licenses
+            .values()
+            .filter(|l| l.org_id.as_deref() == Some(org_id))
+        {
+            stats.total_licenses += 1;
+            *stats
+                .licenses_by_status
+                .entry(license.status.clone())
+                .or_default() += 1;
+            stats.bound_devices += state
+                .devices
+                .iter()
+                .filter(|d| d.license_id == license.license_id)
+                .count() as u64;
+            stats.bandwidth_used_bytes += license.bandwidth_used_bytes.unwrap_or(0);
+            if license.quota_exceeded == Some(true) {
+                stats.quota_exceeded_licenses += 1;
+            }
+        }
+
+        Ok(stats)
+    }
+}
+
+#[async_trait]
+impl TokenStore for MemoryStore {
+    async fn create_api_token(
+        &self,
+        name: &str,
+        scopes: &[&str],
+        expires_at: Option<NaiveDateTime>,
+        created_by: Option<&str>,
+    ) -> LicenseResult<(ApiToken, String)> {
+        let raw_token = generate_raw_token();
+        let token = ApiToken {
+            id: Uuid::new_v4().to_string(),
+            name: name.to_string(),
+            token_hash: hash_token(&raw_token),
+            scopes: scopes.join(" "),
+            created_at: Utc::now().naive_utc(),
+            expires_at,
+            last_used_at: None,
+            revoked_at: None,
+            created_by: created_by.map(String::from),
+        };
+
+        self.state().tokens.push(token.clone());
+
+        info!("Created API token '{}' with id={}", name, token.id);
+        Ok((token, raw_token))
+    }
+
+    async fn validate_api_token(&self, raw_token: &str) -> LicenseResult<Option<ApiToken>> {
+        let token_hash = hash_token(raw_token);
+        let mut state = self.state();
+
+        let Some(stored) = state.tokens.iter_mut().find(|t| t.token_hash == token_hash) else {
+            return Ok(None);
+        };
+        let token = stored.clone();
+        if token.is_valid() {
+            stored.last_used_at = Some(Utc::now().naive_utc());
+        }
+
+        Ok(Some(token))
+    }
+
+    async fn list_api_tokens(&self) -> LicenseResult<Vec<ApiToken>> {
+        let mut tokens = self.state().tokens.clone();
+        tokens.sort_by_key(|t| std::cmp::Reverse(t.created_at));
+        Ok(tokens)
+    }
+
+    async fn get_api_token(&self, token_id: &str) -> LicenseResult<Option<ApiToken>> {
+        Ok(self
+            .state()
+            .tokens
+            .iter()
+            .find(|t| t.id == token_id)
+            .cloned())
+    }
+
+    async fn revoke_api_token(&self, token_id: &str) -> LicenseResult<bool> {
+        let mut state = self.state();
+        let Some(token) = state
+            .tokens
+            .iter_mut()
+            .find(|t| t.id == token_id && t.revoked_at.is_none())
+        else {
+            return Ok(false);
+        };
+        token.revoked_at = Some(Utc::now().naive_utc());
+
+        warn!("Revoked API token id={}", token_id);
+        Ok(true)
+    }
+
+    async fn has_api_tokens(&self) -> LicenseResult<bool> {
+        Ok(!self.state().tokens.is_empty())
+    }
+}
+
+#[async_trait]
+impl AuditStore for MemoryStore {
+    async fn insert_audit_entry(&self, entry: &AuditEntry) -> LicenseResult<()> {
+        self.state().audit_log.push(entry.clone());
+        Ok(())
+    }
+
+    async fn list_audit_entries(
+        &self,
+        filter: &AuditFilter,
+        limit: u32,
+        offset: u32,
+    ) -> LicenseResult<(Vec<AuditEntry>, u64)> {
+        let matches = |value: &str, wanted: &Option<String>| {
+            wanted.as_deref().is_none_or(|wanted| value == wanted)
+        };
+
+        let mut entries: Vec<AuditEntry> = self
+            .state()
+            .audit_log
+            .iter()
+            .filter(|e| {
+                matches(&e.actor, &filter.actor)
+                    && matches(&e.action, &filter.action)
+                    && matches(&e.target_type, &filter.target_type)
+                    && matches(&e.target_id, &filter.target_id)
+                    && filter.since.is_none_or(|since| e.occurred_at >= since)
+                    && filter.until.is_none_or(|until| e.occurred_at <= until)
+            })
+            .cloned()
+            .collect();
+        entries.sort_by(|a, b| (b.occurred_at, &a.id).cmp(&(a.occurred_at, &b.id)));
+
+        let total = entries.len() as u64;
+        Ok((page(entries, limit, offset), total))
+    }
+}
+
+#[async_trait]
+impl WebhookStore for MemoryStore {
+    async fn insert_webhook(&self, webhook: &Webhook) -> LicenseResult<()> {
+        self.state().webhooks.push(webhook.clone());
+        Ok(())
+    }
+
+    async fn update_webhook(&self, webhook: &Webhook) -> LicenseResult<()> {
+        let mut state = self.state();
+        if let Some(stored) = state.webhooks.iter_mut().find(|w| w.id == webhook.id) {
+            stored.url = webhook.url.clone();
+            stored.events = webhook.events.clone();
+            stored.description = webhook.description.clone();
+            stored.is_active = webhook.is_active;
+            stored.updated_at = webhook.updated_at;
+        }
+        Ok(())
+    }
+
+    async fn get_webhook(&self, webhook_id: &str) -> LicenseResult<Option<Webhook>> {
+        Ok(self
+            .state()
+            .webhooks
+            .iter()
+            .find(|w| w.id == webhook_id)
+            .cloned())
+    }
+
+    async fn list_webhooks(&self) -> LicenseResult<Vec<Webhook>> {
+        let mut webhooks = self.state().webhooks.clone();
+        webhooks.sort_by(|a, b| (a.created_at, &a.id).cmp(&(b.created_at, &b.id)));
+        Ok(webhooks)
+    }
+
+    async fn delete_webhook(&self, webhook_id: &str) -> LicenseResult<bool> {
+        let mut state = self.state();
+        state.deliveries.retain(|d| d.webhook_id != webhook_id);
+        let before = state.webhooks.len();
+        state.webhooks.retain(|w| w.id != webhook_id);
+        Ok(state.webhooks.len() < before)
+    }
+
+    async fn insert_webhook_delivery(&self, delivery: &WebhookDelivery) -> LicenseResult<()> {
+        self.state().deliveries.push(delivery.clone());
+        Ok(())
+    }
+
+    async fn update_webhook_delivery(&self, delivery: &WebhookDelivery) -> LicenseResult<()> {
+        let mut state = self.state();
+        if let Some(stored) = state.deliveries.iter_mut().find(|d| d.id == delivery.id) {
+            stored.status = delivery.status.clone();
+            stored.attempts = delivery.attempts;
+            stored.next_attempt_at = delivery.next_attempt_at;
+            stored.last_attempt_at = delivery.last_attempt_at;
+            stored.response_status = delivery.response_status;
+            stored.last_error = delivery.last_error.clone();
+            stored.delivered_at = delivery.delivered_at;
+        }
+        Ok(())
+    }
+
+    async fn due_webhook_deliveries(
+        &self,
+        now: NaiveDateTime,
+        limit: u32,
+    ) -> LicenseResult<Vec<WebhookDelivery>> {
+        let mut due: Vec<WebhookDelivery> = self
+            .state()
+            .deliveries
+            .iter()
+            .filter(|d| d.status == "pending" && d.next_attempt_at.is_some_and(|at| at <= now))
+            .cloned()
+            .collect();
+        due.sort_by(|a, b| (a.next_attempt_at, &a.id).cmp(&(b.next_attempt_at, &b.id)));
+        Ok(page(due, limit, 0))
+    }
+
+    async fn list_webhook_deliveries(
+        &self,
+        webhook_id: &str,
+        status: Option<&str>,
+        limit: u32,
+        offset: u32,
+    ) -> LicenseResult<(Vec<WebhookDelivery>, u64)> {
+        let mut deliveries: Vec<WebhookDelivery> = self
+            .state()
+            .deliveries
+            .iter()
+            .filter(|d| d.webhook_id == webhook_id && status.is_none_or(|s| d.status == s))
+            .cloned()
+            .collect();
+        deliveries.sort_by(|a, b| (b.created_at, &a.id).cmp(&(a.created_at, &b.id)));
+
+        let total = deliveries.len() as u64;
+        Ok((page(deliveries, limit, offset), total))
+    }
+}
+
+#[async_trait]
+impl TrialStore for MemoryStore {
+    async fn insert_trial(
+        &self,
+        trial: &TrialRegistration,
+        license: &License,
+    ) -> LicenseResult<bool> {
+        let mut state = self.state();
+        let used = state
+            .trials
+            .iter()
+            .any(|t| t.hardware_id == trial.hardware_id && t.product == trial.product);
+        if used {
+            return Ok(false);
+        }
+
+        state.check_key_free(license)?;
+        state
+            .licenses
+            .insert(license.license_id.clone(), license.clone());
+        state.trials.push(trial.clone());
+        Ok(true)
+    }
+
+    async fn get_trial_by_license(
+        &self,
+        license_id: &str,
+    ) -> LicenseResult<Option<TrialRegistration>> {
+        Ok(self
+            .state()
+            .trials
+            .iter()
+            .find(|t| t.license_id == license_id)
+            .cloned())
+    }
+
+    async fn convert_trial(
+        &self,
+        license: &License,
+        converted_at: NaiveDateTime,
+    ) -> LicenseResult<bool> {
+        let mut state = self.state();
+        let Some(trial) = state
+            .trials
+            .iter_mut()
+            .find(|t| t.license_id == license.license_id && t.converted_at.is_none())
+        else {
+            return Ok(false);
+        };
+        trial.converted_at = Some(converted_at);
+
+        state
+            .licenses
+            .insert(license.license_id.clone(), license.clone());
+        Ok(true)
+    }
+}
+
+#[async_trait]
+impl UsageStore for MemoryStore {
+    async fn record_usage(&self, report: &UsageReport) -> LicenseResult<RecordedUsage> {
+        let mut state = self.state();
+        let key = (report.license_id.clone(), report.meter.clone());
+
+        let duplicate = state
+            .usage_reports
+            .iter()
+            .any(|r| r.license_id == report.license_id && r.report_id == report.report_id);
+        if duplicate {
+            let meter = state.usage_meters.get(&key).cloned().unwrap_or_else(|| {
+                UsageMeter::empty(&report.license_id, &report.meter, report.reported_at)
+            });
+            return Ok(RecordedUsage {
+                meter,
+                duplicate: true,
+            });
+        }
+
+        state.usage_reports.push(report.clone());
+        let meter = state.usage_meters.entry(key).or_insert_with(|| {
+            UsageMeter::empty(&report.license_id, &report.meter, report.reported_at)
+        });
+        meter.used += report.amount;
+        meter.updated_at = report.reported_at;
+        let meter = meter.clone();
+
+        // Only the usage changed; the legacy limit field keeps its value
+        if meter.meter == BANDWIDTH_METER {
+            if let Some(license) = state.license_mut(&meter.license_id) {
+                license.bandwidth_used_bytes = Some(meter.used);
+            }
+        }
+
+        Ok(RecordedUsage {
+            meter,
+            duplicate: false,
+        })
+    }
+
+    async fn set_usage_meter(&self, meter: &UsageMeter) -> LicenseResult<()> {
+        let mut state = self.state();
+        state.usage_meters.insert(
+            (meter.license_id.clone(), meter.meter.clone()),
+            meter.clone(),
+        );
+        if meter.meter == BANDWIDTH_METER {
+            if let Some(license) = state.license_mut(&meter.license_id) {
+                license.bandwidth_used_bytes = Some(meter.used);
+                license.bandwidth_limit_bytes = meter.usage_limit;
+            }
+        }
+        Ok(())
+    }
+
+    async fn list_usage_meters(&self, license_id: &str) -> LicenseResult<Vec<UsageMeter>> {
+        Ok(self
+            .state()
+            .usage_meters
+            .values()
+            .filter(|m| m.license_id == license_id)
+            .cloned()
+            .collect())
+    }
+
+    async fn set_quota_exceeded(&self, license_id: &str, exceeded: bool) -> LicenseResult<bool> {
+        let mut state = self.state();
+        let Some(license) = state.license_mut(license_id) else {
+            return Ok(false);
+        };
+        license.quota_exceeded = Some(exceeded);
+        Ok(true)
+    }
+
+    async fn close_usage_period(
+        &self,
+        license_id: &str,
+        period_end: NaiveDateTime,
+    ) -> LicenseResult<Option<Vec<UsagePeriod>>> {
+        let mut state = self.state();
+        let Some(license) = state.license_mut(license_id) else {
+            return Ok(None);
+        };
+        let period_start = license.usage_period_start.unwrap_or(license.issued_at);
+        if period_end <= period_start {
+            return Ok(None);
+        }
+        license.usage_period_start = Some(period_end);
+        // Meters are zeroed below, so nothing carries over
+        license.quota_exceeded = Some(false);
+        if license.bandwidth_used_bytes.is_some() {
+            license.bandwidth_used_bytes = Some(0);
+        }
+
+        let mut periods = Vec::new();
+        for meter in state.usage_meters.values_mut() {
+            if meter.license_id != license_id || meter.used == 0 {
+                continue;
+            }
+            periods.push(UsagePeriod {
+                license_id: meter.license_id.clone(),
+                meter: meter.meter.clone(),
+                period_start,
+                period_end,
+                used: meter.used,
+                usage_limit: meter.usage_limit,
+            });
+            meter.used = 0;
+            meter.updated_at = period_end;
+        }
+        state.usage_periods.extend(periods.iter().cloned());
+        Ok(Some(periods))
+    }
+
+    async fn list_usage_periods(&self, license_id: &str) -> LicenseResult<Vec<UsagePeriod>> {
+        let mut periods: Vec<UsagePeriod> = self
+            .state()
+            .usage_periods
+            .iter()
+            .filter(|p| p.license_id == license_id)
+            .cloned()
+            .collect();
+        periods.sort_by(|a, b| {
+            b.period_start
+                .cmp(&a.period_start)
+                .then_with(|| a.meter.cmp(&b.meter))
+        });
+        Ok(periods)
+    }
+
+    async fn list_billed_licenses(&self, tiers: &[String]) -> LicenseResult<Vec<License>> {
+        Ok(self
+            .state()
+            .licenses
+            .values()
+            .filter(|l| {
+                l.billing_cycle.is_some() || l.tier.as_ref().is_some_and(|t| tiers.contains(t))
+            })
+            .cloned()
+            .collect())
+    }
+}
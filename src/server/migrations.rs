@@ -0,0 +1,455 @@
+//! Embedded database migrations.
+//!
+//! The SQL files under `migrations/` (SQLite) and `migrations/postgres/`
+//! (PostgreSQL) are compiled into the binary, so a server can create or
+//! upgrade its schema without sqlx-cli. `talos_server` applies pending
+//! migrations on startup unless `database.run_migrations` is `false`, and
+//! `talos_server migrate status|up|down` manages them by hand (see
+//! [`crate::server::bootstrap`]).
+//!
+//! Every migration has a matching `.down.sql`, so schema changes can be
+//! rolled back one version at a time. Applied versions are tracked in the
+//! `_sqlx_migrations` table that `sqlx migrate run` also uses, so databases
+//! migrated with sqlx-cli are picked up as they are.
+//!
+//! Databases created from `scripts/sql/init_*.sql` have a schema but no
+//! migration history. When [`Database::run_migrations`] finds such a
+//! database, it records every migration whose tables, columns and indexes
+//! already exist as applied instead of running it again.
+//!
+//! # Usage
+//!
+//! ```rust,ignore
+//! let db = Database::new().await?;
+//!
+//! for migration in db.run_migrations().await? {
+//!     println!("applied {} {}", migration.version, migration.description);
+//! }
+//! ```
+
+use std::collections::HashMap;
+
+use sqlx::migrate::{AppliedMigration, Migrate, MigrateError, Migration, Migrator};
+use tracing::{error, info};
+
+use crate::errors::{LicenseError, LicenseResult};
+use crate::server::database::Database;
+
+#[cfg(feature = "sqlite")]
+static SQLITE_MIGRATOR: Migrator = sqlx::migrate!("./migrations");
+
+#[cfg(feature = "postgres")]
+static POSTGRES_MIGRATOR: Migrator = sqlx::migrate!("./migrations/postgres");
+
+/// A schema object whose presence shows that a migration has been applied.
+#[derive(Debug, Clone, Copy)]
+enum Marker {
+    Table(&'static str),
+    Column(&'static str, &'static str),
+    Index(&'static str),
+}
+
+/// What each migration adds, used to recognize schemas that were created
+/// without the migrator. Every embedded migration needs an entry; use a
+/// column for migrations that add one, since those can't be run twice.
+const MARKERS: &[(i64, Marker)] = &[
+    (20241114103315, Marker::Table("licenses")),
+    (20250103000000, Marker::Column("licenses", "license_key")),
+    (
+        20260103000000,
+        Marker::Column("licenses", "bandwidth_used_bytes"),
+    ),
+    (20260103000001, Marker::Table("api_tokens")),
+    (20260104000000, Marker::Column("licenses", "max_devices")),
+    (20260105000000, Marker::Column("licenses", "max_concurrent")),
+    (20260106000000, Marker::Table("audit_log")),
+    (20260107000000, Marker::Table("webhooks")),
+    (20260108000000, Marker::Table("organizations")),
+    (20260109000000, Marker::Index("idx_licenses_issued_at")),
+    (20260110000000, Marker::Column("licenses", "offline_days")),
+    (
+        20260111000000,
+        Marker::Index("idx_binding_history_hardware_id"),
+    ),
+    (20260112000000, Marker::Table("trial_registrations")),
+    (20260113000000, Marker::Table("usage_meters")),
+    (20260114000000, Marker::Column("licenses", "billing_cycle")),
+    (
+        20260115000000,
+        Marker::Column("licenses", "suspended_by_org"),
+    ),
+];
+
+/// A migration embedded in this build and whether the database has it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MigrationStatus {
+    /// Version from the file name, e.g. `20260107000000`
+    pub version: i64,
+    /// Description from the file name, e.g. `webhooks`
+    pub description: String,
+    /// Whether the migration has been applied
+    pub applied: bool,
+    /// Whether the applied migration differs from the embedded SQL
+    pub checksum_mismatch: bool,
+}
+
+impl Database {
+    /// The embedded migrations for this database's backend.
+    pub fn migrator(&self) -> &'static Migrator {
+        match self {
+            #[cfg(feature = "sqlite")]
+            Database::SQLite(_) => &SQLITE_MIGRATOR,
+            #[cfg(feature = "postgres")]
+            Database::Postgres(_) => &POSTGRES_MIGRATOR,
+        }
+    }
+
+    /// List every embedded migration, oldest first, with its applied state.
+    pub async fn migration_status(&self) -> LicenseResult<Vec<MigrationStatus>> {
+        let applied = match self {
+            #[cfg(feature = "sqlite")]
+            Database::SQLite(pool) => match pool.acquire().await {
+                Ok(mut conn) => applied_migrations(&mut *conn).await,
+                Err(e) => Err(e.into()),
+            },
+            #[cfg(feature = "postgres")]
+            Database::Postgres(pool) => match pool.acquire().await {
+                Ok(mut conn) => applied_migrations(&mut *conn).await,
+                Err(e) => Err(e.into()),
+            },
+        }
+        .map_err(|e| migration_error("migration_status", e))?;
+
+        Ok(statuses(self.migrator(), &applied))
+    }
+
+    /// Apply every pending migration in version order.
+    ///
+    /// Returns the migrations that were applied, which is empty when the
+    /// schema is already current. Fails without changes if an applied
+    /// migration no longer matches its embedded SQL. A schema that exists
+    /// but was never tracked is adopted first (see
+    /// [`Database::adopt_untracked_schema`]).
+    pub async fn run_migrations(&self) -> LicenseResult<Vec<MigrationStatus>> {
+        self.adopt_untracked_schema().await?;
+
+        let pending: Vec<MigrationStatus> = self
+            .migration_status()
+            .await?
+            .into_iter()
+            .filter(|m| !m.applied)
+            .collect();
+
+        match self {
+            #[cfg(feature = "sqlite")]
+            Database::SQLite(pool) => SQLITE_MIGRATOR.run(pool).await,
+            #[cfg(feature = "postgres")]
+            Database::Postgres(pool) => POSTGRES_MIGRATOR.run(pool).await,
+        }
+        .map_err(|e| migration_error("run_migrations", e))?;
+
+        for migration in &pending {
+            info!(
+                "Applied migration {} ({})",
+                migration.version, migration.description
+            );
+        }
+
+        Ok(pending
+            .into_iter()
+            .map(|m| MigrationStatus { applied: true, ..m })
+            .collect())
+    }
+
+    /// Record the migrations an untracked schema already has as applied.
+    ///
+    /// Does nothing unless the database has a `licenses` table but no
+    /// applied migrations, as with databases created from
+    /// `scripts/sql/init_*.sql`. Migrations are checked oldest first and
+    /// recorded while their changes are present. A migration that only
+    /// creates tables or indexes is run if they are missing, since it is safe
+    /// to repeat; the first one whose columns are missing ends the scan, so
+    /// it and everything after it still run. Returns the recorded versions.
+    pub async fn adopt_untracked_schema(&self) -> LicenseResult<Vec<i64>> {
+        if self.migration_status().await?.iter().any(|m| m.applied) {
+            return Ok(Vec::new());
+        }
+
+        let fail = |e: sqlx::Error| {
+            error!("adopt_untracked_schema failed: {e}");
+            LicenseError::ServerError(format!("migration error: {e}"))
+        };
+
+        let mut adopted = Vec::new();
+        match self {
+            #[cfg(feature = "sqlite")]
+            Database::SQLite(pool) => {
+                let mut tx = pool.begin().await.map_err(fail)?;
+                if !sqlite_has(&mut tx, Marker::Table("licenses"))
+                    .await
+                    .map_err(fail)?
+                {
+                    return Ok(adopted);
+                }
+
+                for migration in up_migrations(&SQLITE_MIGRATOR) {
+                    let Some(marker) = marker(migration.version) else {
+                        break;
+                    };
+                    if !sqlite_has(&mut tx, marker).await.map_err(fail)? {
+                        if matches!(marker, Marker::Column(..)) {
+                            break;
+                        }
+                        sqlx::raw_sql(&migration.sql)
+                            .execute(&mut *tx)
+                            .await
+                            .map_err(fail)?;
+                    }
+
+                    sqlx::query(
+                        "INSERT INTO _sqlx_migrations \
+                         (version, description, success, checksum, execution_time) \
+                         VALUES (?, ?, TRUE, ?, 0)",
+                    )
+                    .bind(migration.version)
+                    .bind(&*migration.description)
+                    .bind(&*migration.checksum)
+                    .execute(&mut *tx)
+                    .await
+                    .map_err(fail)?;
+                    adopted.push(migration.version);
+                }
+                tx.commit().await.map_err(fail)?;
+            }
+            #[cfg(feature = "postgres")]
+            Database::Postgres(pool) => {
+                let mut tx = pool.begin().await.map_err(fail)?;
+                if !postgres_has(&mut tx, Marker::Table("licenses"))
+                    .await
+                    .map_err(fail)?
+                {
+                    return Ok(adopted);
+                }
+
+                for migration in up_migrations(&POSTGRES_MIGRATOR) {
+                    let Some(marker) = marker(migration.version) else {
+                        break;
+                    };
+                    if !postgres_has(&mut tx, marker).await.map_err(fail)? {
+                        if matches!(marker, Marker::Column(..)) {
+                            break;
+                        }
+                        sqlx::raw_sql(&migration.sql)
+                            .execute(&mut *tx)
+                            .await
+                            .map_err(fail)?;
+                    }
+
+                    sqlx::query(
+                        "INSERT INTO _sqlx_migrations \
+                         (version, description, success, checksum, execution_time) \
+                         VALUES ($1, $2, TRUE, $3, 0)",
+                    )
+                    .bind(migration.version)
+                    .bind(&*migration.description)
+                    .bind(&*migration.checksum)
+                    .execute(&mut *tx)
+                    .await
+                    .map_err(fail)?;
+                    adopted.push(migration.version);
+                }
+                tx.commit().await.map_err(fail)?;
+            }
+        }
+
+        for version in &adopted {
+            info!("Recorded migration {version} as applied (schema already present)");
+        }
+
+        Ok(adopted)
+    }
+    /// Revert applied migrations newer than `target`, newest first.
+    ///
+    /// `target` is the version to roll back to; `0` reverts everything.
+    /// Returns the migrations that were reverted.
+    pub async fn revert_migrations(&self, target: i64) -> LicenseResult<Vec<MigrationStatus>> {
+        let mut reverted: Vec<MigrationStatus> = self
+            .migration_status()
+            .await?
+            .into_iter()
+            .filter(|m| m.applied && m.version > target)
+            .collect();
+        reverted.reverse();
+
+        match self {
+            #[cfg(feature = "sqlite")]
+            Database::SQLite(pool) => SQLITE_MIGRATOR.undo(pool, target).await,
+            #[cfg(feature = "postgres")]
+            Database::Postgres(pool) => POSTGRES_MIGRATOR.undo(pool, target).await,
+        }
+        .map_err(|e| migration_error("revert_migrations", e))?;
+
+        for migration in &reverted {
+            info!(
+                "Reverted migration {} ({})",
+                migration.version, migration.description
+            );
+        }
+
+        Ok(reverted
+            .into_iter()
+            .map(|m| MigrationStatus {
+                applied: false,
+                ..m
+            })
+            .collect())
+    }
+}
+
+/// Read the applied migrations, creating the tracking table if needed.
+async fn applied_migrations<C: Migrate>(
+    conn: &mut C,
+) -> Result<Vec<AppliedMigration>, MigrateError> {
+    conn.ensure_migrations_table().await?;
+    conn.list_applied_migrations().await
+}
+
+fn up_migrations(migrator: &Migrator) -> impl Iterator<Item = &Migration> {
+    migrator
+        .iter()
+        .filter(|m| !m.migration_type.is_down_migration())
+}
+
+#[cfg(feature = "sqlite")]
+async fn sqlite_has(conn: &mut sqlx::SqliteConnection, marker: Marker) -> sqlx::Result<bool> {
+    let count: i64 = match marker {
+        Marker::Table(table) => {
+            sqlx::query_scalar(
+                "SELECT COUNT(*) FROM sqlite_master WHERE type = 'table' AND name = ?",
+            )
+            .bind(table)
+            .fetch_one(conn)
+            .await?
+        }
+        Marker::Index(index) => {
+            sqlx::query_scalar(
+                "SELECT COUNT(*) FROM sqlite_master WHERE type = 'index' AND name = ?",
+            )
+            .bind(index)
+            .fetch_one(conn)
+            .await?
+        }
+        Marker::Column(table, column) => {
+            sqlx::query_scalar("SELECT COUNT(*) FROM pragma_table_info(?) WHERE name = ?")
+                .bind(table)
+                .bind(column)
+                .fetch_one(conn)
+                .await?
+        }
+    };
+    Ok(count > 0)
+}
+
+#[cfg(feature = "postgres")]
+async fn postgres_has(conn: &mut sqlx::PgConnection, marker: Marker) -> sqlx::Result<bool> {
+    match marker {
+        Marker::Table(name) | Marker::Index(name) => {
+            sqlx::query_scalar("SELECT to_regclass($1) IS NOT NULL")
+                .bind(name)
+                .fetch_one(conn)
+                .await
+        }
+        Marker::Column(table, column) => {
+            sqlx::query_scalar(
+                "SELECT EXISTS (SELECT 1 FROM information_schema.columns \
+                 WHERE table_schema = current_schema() \
+                 AND table_name = $1 AND column_name = $2)",
+            )
+            .bind(table)
+            .bind(column)
+            .fetch_one(conn)
+            .await
+        }
+    }
+}
+
+fn marker(version: i64) -> Option<Marker> {
+    MARKERS
+        .iter()
+        .find(|(v, _)| *v == version)
+        .map(|(_, marker)| *marker)
+}
+
+fn statuses(migrator: &Migrator, applied: &[AppliedMigration]) -> Vec<MigrationStatus> {
+    let applied: HashMap<i64, &AppliedMigration> = applied.iter().map(|m| (m.version, m)).collect();
+
+    migrator
+        .iter()
+        .filter(|m| !m.migration_type.is_down_migration())
+        .map(|m| {
+            let applied = applied.get(&m.version);
+            MigrationStatus {
+                version: m.version,
+                description: m.description.to_string(),
+                applied: applied.is_some(),
+                checksum_mismatch: applied.is_some_and(|a| a.checksum != m.checksum),
+            }
+        })
+        .collect()
+}
+
+fn migration_error(operation: &str, e: MigrateError) -> LicenseError {
+    error!("{operation} failed: {e}");
+    LicenseError::ServerError(format!("migration error: {e}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "sqlite")]
+    #[test]
+    fn every_sqlite_migration_is_reversible() {
+        let ups: Vec<i64> = SQLITE_MIGRATOR
+            .iter()
+            .filter(|m| m.migration_type.is_up_migration())
+            .map(|m| m.version)
+            .collect();
+        let downs: Vec<i64> = SQLITE_MIGRATOR
+            .iter()
+            .filter(|m| m.migration_type.is_down_migration())
+            .map(|m| m.version)
+            .collect();
+
+        assert!(!ups.is_empty());
+        assert_eq!(ups, downs);
+    }
+
+    #[cfg(feature = "sqlite")]
+    #[test]
+    fn every_sqlite_migration_has_a_marker() {
+        let versions: Vec<i64> = up_migrations(&SQLITE_MIGRATOR).map(|m| m.version).collect();
+        let marked: Vec<i64> = MARKERS.iter().map(|(v, _)| *v).collect();
+
+        assert_eq!(versions, marked);
+    }
+
+    #[cfg(feature = "postgres")]
+    #[test]
+    fn postgres_migrations_match_sqlite_versions() {
+        let versions = |migrator: &Migrator| -> Vec<(i64, String)> {
+            migrator
+                .iter()
+                .filter(|m| !m.migration_type.is_down_migration())
+                .map(|m| (m.version, m.description.to_string()))
+                .collect()
+        };
+
+        #[cfg(feature = "sqlite")]
+        assert_eq!(versions(&POSTGRES_MIGRATOR), versions(&SQLITE_MIGRATOR));
+        assert!(POSTGRES_MIGRATOR
+            .iter()
+            .all(|m| m.migration_type.is_reversible()));
+        assert!(!versions(&POSTGRES_MIGRATOR).is_empty());
+    }
+}
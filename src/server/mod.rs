@@ -4,30 +4,53 @@
 //!
 //! This module contains:
 //! - `api_error`     → Standardized API error responses
-//! - `database`      → DB abstraction over SQLite/Postgres
+//! - `audit`         → Persistent audit log of admin and token changes
+//! - `store`         → Storage traits handlers and jobs are written against
+//! - `database`      → SQLite/Postgres implementation of the storage traits
+//! - `memory_store`  → In-memory implementation of the storage traits
+//! - `license_query` → Filtering, sorting and cursor pagination for license listings
+//! - `license_io`    → CSV and NDJSON import and export of licenses
+//! - `orgs`          → Organizations that own licenses, and their admin API
+//! - `trials`        → Self-service trial licenses, one per machine
+//! - `usage`         → Named usage meters, client usage reports and quotas
+//! - `billing`       → Billing cycles that reset usage meters
+//! - `migrations`    → Embedded schema migrations (up, down, status)
 //! - `handlers`      → Axum HTTP handlers for license endpoints
-//! - `client_api`    → New client API for bind/release/validate
+//! - `client_api`    → New client API for bind/release/validate/checkout
 //! - `routes`        → Router builder (optional helper)
 //! - `server_sim`    → In-memory simulator for tests
 //! - `auth`          → JWT authentication middleware (requires `jwt-auth` feature)
 //! - `admin`         → Admin API for license CRUD (requires `admin-api` feature)
+//! - `bulk`          → Bulk license operations (requires `admin-api` feature)
 //! - `rate_limit`    → Rate limiting middleware (requires `rate-limiting` feature)
 //! - `ip_whitelist`  → IP whitelist middleware for admin API protection
 //! - `validation`    → Request validation utilities
+//! - `webhooks`      → Signed webhook notifications for license events
 
 pub mod api_error;
+pub mod audit;
+pub mod billing;
 pub mod bootstrap;
 pub mod client_api;
 pub mod database;
 pub mod handlers;
 pub mod ip_whitelist;
+pub mod license_io;
+pub mod license_query;
 pub mod logging;
+pub mod memory_store;
+pub mod migrations;
 #[cfg(feature = "openapi")]
 pub mod openapi;
+pub mod orgs;
 pub mod routes;
 pub mod server_sim;
+pub mod store;
 pub mod tokens;
+pub mod trials;
+pub mod usage;
 pub mod validation;
+pub mod webhooks;
 
 #[cfg(feature = "jwt-auth")]
 pub mod auth;
@@ -35,41 +58,70 @@ pub mod auth;
 #[cfg(feature = "admin-api")]
 pub mod admin;
 
+#[cfg(feature = "admin-api")]
+pub mod bulk;
+
 #[cfg(feature = "rate-limiting")]
 pub mod rate_limit;
 
 // Optional: convenient re-exports so callers can do `talos::server::X`
 // instead of digging into submodules.
 
+pub use billing::{configured_billing_cycle, license_billing_cycle, BillingCycle};
 pub use client_api::{
-    bind_handler, client_heartbeat_handler, release_handler, validate_feature_handler,
-    validate_handler, validate_or_bind_handler, BindRequest, BindResponse, ClientError,
-    ClientErrorCode, ClientHeartbeatRequest, ClientHeartbeatResponse, ReleaseRequest,
-    ReleaseResponse, ValidateFeatureRequest, ValidateFeatureResponse, ValidateOrBindRequest,
-    ValidateRequest, ValidateResponse,
+    bind_handler, checkin_handler, checkout_handler, client_heartbeat_handler, release_handler,
+    validate_feature_handler, validate_handler, validate_or_bind_handler, BindRequest,
+    BindResponse, BoundDevice, CheckinRequest, CheckinResponse, CheckoutRequest, CheckoutResponse,
+    ClientError, ClientErrorCode, ClientHeartbeatRequest, ClientHeartbeatResponse, ReleaseRequest,
+    ReleaseResponse, SeatUsage, ValidateFeatureRequest, ValidateFeatureResponse,
+    ValidateOrBindRequest, ValidateRequest, ValidateResponse,
 };
 pub use database::Database;
 pub use handlers::{
     activate_license_handler, deactivate_license_handler, heartbeat_handler,
     validate_license_handler, AppState,
 };
+pub use license_io::{
+    export_licenses, import_licenses, ImportReport, ImportRowError, LicenseFormat, LICENSE_COLUMNS,
+    MAX_IMPORT_ROWS,
+};
+#[cfg(feature = "admin-api")]
+pub use license_io::{
+    export_licenses_handler, import_licenses_handler, ImportLicensesQuery, LicenseFormatQuery,
+};
+pub use license_query::{LicenseCursor, LicenseFilter, LicenseSort, LicenseSortField, SortOrder};
+pub use memory_store::MemoryStore;
 pub use routes::build_router;
+#[cfg(feature = "rate-limiting")]
+pub use routes::build_router_with_rate_limit;
+pub use store::{
+    AuditStore, LicenseStore, OrgStore, TokenStore, TrialStore, UsageStore, WebhookStore,
+};
 
 #[cfg(feature = "jwt-auth")]
 pub use auth::{
     AuthError, AuthLayer, AuthMiddleware, AuthState, AuthenticatedUser, Claims, JwtValidator,
-    OptionalUser,
+    OptionalUser, RequireScopeLayer, RequireScopeMiddleware,
 };
 
 #[cfg(feature = "admin-api")]
 pub use admin::{
     admin_release_handler, batch_create_license_handler, blacklist_license_handler,
-    create_license_handler, extend_license_handler, get_license_handler, list_licenses_handler,
-    reinstate_license_handler, revoke_license_handler, update_license_handler,
-    update_usage_handler, AdminReleaseRequest, AdminReleaseResponse, BlacklistLicenseRequest,
-    BlacklistLicenseResponse, ExtendLicenseRequest, ExtendLicenseResponse, ReinstateLicenseRequest,
-    ReinstateLicenseResponse, RevokeLicenseRequest, RevokeLicenseResponse, UpdateUsageRequest,
-    UpdateUsageResponse,
+    create_license_handler, device_history_handler, extend_license_handler, get_license_handler,
+    license_history_handler, list_license_devices_handler, list_licenses_handler,
+    offline_activation_handler, reinstate_license_handler, remove_license_device_handler,
+    revoke_license_handler, update_license_handler, update_usage_handler, AdminReleaseRequest,
+    AdminReleaseResponse, BindingHistoryEntryResponse, BlacklistLicenseRequest,
+    BlacklistLicenseResponse, DeviceHistoryResponse, ExtendLicenseRequest, ExtendLicenseResponse,
+    LicenseDeviceResponse, LicenseDevicesResponse, LicenseHistoryResponse,
+    OfflineActivationRequest, ReinstateLicenseRequest, ReinstateLicenseResponse,
+    RevokeLicenseRequest, RevokeLicenseResponse, UpdateUsageRequest, UpdateUsageResponse,
+};
+
+#[cfg(feature = "admin-api")]
+pub use bulk::{
+    bulk_license_handler, BulkAction, BulkFilter, BulkItemResult, BulkItemStatus,
+    BulkLicenseRequest, BulkLicenseResponse, BulkMode, SetTierRequest,
 };
 
 #[cfg(feature = "rate-limiting")]
@@ -79,26 +131,61 @@ pub use rate_limit::{
 
 pub use validation::{
     validate_datetime, validate_feature_name, validate_hardware_id, validate_length,
-    validate_license_key, validate_not_empty, validate_optional_not_empty, validate_org_id,
-    validate_uuid, ValidationError, ValidationResult,
+    validate_license_key, validate_meter_name, validate_not_empty, validate_optional_not_empty,
+    validate_org_id, validate_uuid, ValidationError, ValidationResult,
 };
 
 pub use tokens::{
-    create_token_handler, get_token_handler, list_tokens_handler, revoke_token_handler, ApiToken,
-    CreateTokenRequest, CreateTokenResponse, ListTokensResponse, RevokeTokenResponse,
+    create_token_handler, get_token_handler, list_tokens_handler, revoke_token_handler, scopes,
+    ApiToken, CreateTokenRequest, CreateTokenResponse, ListTokensResponse, RevokeTokenResponse,
     TokenMetadata, TokenResponse,
 };
 
 pub use bootstrap::{
-    check_bootstrap_token, execute_token_command, parse_token_command, TokenCommand,
-    BOOTSTRAP_TOKEN_ENV,
+    check_bootstrap_token, execute_license_command, execute_token_command, parse_license_command,
+    parse_token_command, LicenseCommand, TokenCommand, BOOTSTRAP_TOKEN_ENV,
 };
 
 pub use api_error::{ApiError, ErrorBody, ErrorCode};
 
+#[cfg(feature = "admin-api")]
+pub use audit::{list_audit_handler, AuditEntryResponse, AuditLogResponse};
+pub use audit::{AuditAction, AuditContext, AuditEntry, AuditFilter, AuditTarget};
+
 pub use logging::{
     generate_request_id, log_license_binding_event, log_license_event, request_logging_middleware,
-    DatabaseHealth, HealthResponse, LicenseEvent, REQUEST_ID_HEADER,
+    DatabaseHealth, HealthResponse, LicenseEvent, RequestId, REQUEST_ID_HEADER,
+};
+
+#[cfg(feature = "admin-api")]
+pub use webhooks::{
+    create_webhook_handler, delete_webhook_handler, get_webhook_handler,
+    list_webhook_deliveries_handler, list_webhooks_handler, update_webhook_handler,
+    CreateWebhookRequest, CreateWebhookResponse, ListWebhooksResponse, UpdateWebhookRequest,
+    WebhookDeliveriesResponse, WebhookDeliveryResponse, WebhookResponse,
+};
+pub use webhooks::{DeliveryStatus, Webhook, WebhookDelivery, WebhookDispatcher};
+
+#[cfg(feature = "admin-api")]
+pub use orgs::{
+    create_org_handler, delete_org_handler, get_org_handler, list_orgs_handler,
+    reinstate_org_handler, suspend_org_handler, update_org_handler, CreateOrgRequest,
+    ListOrgsResponse, OrgResponse, OrgStatusResponse, SuspendOrgRequest, UpdateOrgRequest,
+};
+pub use orgs::{OrgStats, Organization};
+
+#[cfg(feature = "admin-api")]
+pub use trials::{convert_trial_handler, ConvertTrialRequest};
+pub use trials::{start_trial_handler, TrialRegistration, TrialRequest, TrialResponse};
+
+#[cfg(feature = "admin-api")]
+pub use usage::{
+    list_usage_handler, list_usage_periods_handler, set_meter_handler, SetMeterRequest,
+    UsageMetersResponse, UsagePeriodResponse, UsagePeriodsResponse,
+};
+pub use usage::{
+    report_usage_handler, MeterStatus, RecordedUsage, ReportUsageRequest, ReportUsageResponse,
+    UsageMeter, UsagePeriod, UsageReport,
 };
 
 #[cfg(feature = "openapi")]
@@ -50,6 +50,10 @@ use utoipa::OpenApi;
         crate::server::client_api::validate_or_bind_handler,
         crate::server::client_api::client_heartbeat_handler,
         crate::server::client_api::validate_feature_handler,
+        crate::server::client_api::checkout_handler,
+        crate::server::client_api::checkin_handler,
+        crate::server::trials::start_trial_handler,
+        crate::server::usage::report_usage_handler,
         // Legacy endpoints
         crate::server::handlers::activate_license_handler,
         crate::server::handlers::validate_license_handler,
@@ -70,8 +74,20 @@ use utoipa::OpenApi;
             crate::server::client_api::ClientHeartbeatResponse,
             crate::server::client_api::ValidateFeatureRequest,
             crate::server::client_api::ValidateFeatureResponse,
+            crate::server::client_api::CheckoutRequest,
+            crate::server::client_api::CheckoutResponse,
+            crate::server::client_api::CheckinRequest,
+            crate::server::client_api::CheckinResponse,
+            crate::server::trials::TrialRequest,
+            crate::server::trials::TrialResponse,
+            crate::server::usage::ReportUsageRequest,
+            crate::server::usage::ReportUsageResponse,
+            crate::server::usage::MeterStatus,
             crate::server::client_api::ClientError,
             crate::server::client_api::ClientErrorCode,
+            crate::server::client_api::SeatUsage,
+            crate::server::client_api::BoundDevice,
+            crate::signing::SignedLicense,
             // Legacy handler schemas
             crate::server::handlers::LicenseRequest,
             crate::server::handlers::LicenseResponse,
@@ -112,6 +128,8 @@ pub struct ApiDoc;
         (name = "client", description = "Client endpoints for license validation and binding"),
         (name = "admin", description = "Admin endpoints for license management (requires authentication)"),
         (name = "tokens", description = "API token management endpoints"),
+        (name = "webhooks", description = "Webhook endpoints and delivery logs"),
+        (name = "orgs", description = "Organizations that own licenses"),
         (name = "legacy", description = "Legacy endpoints for backwards compatibility")
     ),
     paths(
@@ -124,6 +142,10 @@ pub struct ApiDoc;
         crate::server::client_api::validate_or_bind_handler,
         crate::server::client_api::client_heartbeat_handler,
         crate::server::client_api::validate_feature_handler,
+        crate::server::client_api::checkout_handler,
+        crate::server::client_api::checkin_handler,
+        crate::server::trials::start_trial_handler,
+        crate::server::usage::report_usage_handler,
         // Legacy endpoints
         crate::server::handlers::activate_license_handler,
         crate::server::handlers::validate_license_handler,
@@ -139,13 +161,42 @@ pub struct ApiDoc;
         crate::server::admin::reinstate_license_handler,
         crate::server::admin::extend_license_handler,
         crate::server::admin::update_usage_handler,
+        crate::server::usage::list_usage_handler,
+        crate::server::usage::set_meter_handler,
+        crate::server::usage::list_usage_periods_handler,
         crate::server::admin::admin_release_handler,
+        crate::server::admin::list_license_devices_handler,
+        crate::server::admin::license_history_handler,
+        crate::server::admin::device_history_handler,
+        crate::server::admin::remove_license_device_handler,
         crate::server::admin::blacklist_license_handler,
+        crate::server::admin::offline_activation_handler,
+        crate::server::trials::convert_trial_handler,
+        crate::server::bulk::bulk_license_handler,
+        crate::server::license_io::export_licenses_handler,
+        crate::server::license_io::import_licenses_handler,
         // Token endpoints
         crate::server::tokens::create_token_handler,
         crate::server::tokens::list_tokens_handler,
         crate::server::tokens::get_token_handler,
         crate::server::tokens::revoke_token_handler,
+        // Audit endpoints
+        crate::server::audit::list_audit_handler,
+        // Webhook endpoints
+        crate::server::webhooks::create_webhook_handler,
+        crate::server::webhooks::list_webhooks_handler,
+        crate::server::webhooks::get_webhook_handler,
+        crate::server::webhooks::update_webhook_handler,
+        crate::server::webhooks::delete_webhook_handler,
+        crate::server::webhooks::list_webhook_deliveries_handler,
+        // Organization endpoints
+        crate::server::orgs::create_org_handler,
+        crate::server::orgs::list_orgs_handler,
+        crate::server::orgs::get_org_handler,
+        crate::server::orgs::update_org_handler,
+        crate::server::orgs::delete_org_handler,
+        crate::server::orgs::suspend_org_handler,
+        crate::server::orgs::reinstate_org_handler,
     ),
     components(
         schemas(
@@ -161,8 +212,20 @@ pub struct ApiDoc;
             crate::server::client_api::ClientHeartbeatResponse,
             crate::server::client_api::ValidateFeatureRequest,
             crate::server::client_api::ValidateFeatureResponse,
+            crate::server::client_api::CheckoutRequest,
+            crate::server::client_api::CheckoutResponse,
+            crate::server::client_api::CheckinRequest,
+            crate::server::client_api::CheckinResponse,
+            crate::server::trials::TrialRequest,
+            crate::server::trials::TrialResponse,
+            crate::server::usage::ReportUsageRequest,
+            crate::server::usage::ReportUsageResponse,
+            crate::server::usage::MeterStatus,
             crate::server::client_api::ClientError,
             crate::server::client_api::ClientErrorCode,
+            crate::server::client_api::SeatUsage,
+            crate::server::client_api::BoundDevice,
+            crate::signing::SignedLicense,
             // Legacy handler schemas
             crate::server::handlers::LicenseRequest,
             crate::server::handlers::LicenseResponse,
@@ -184,10 +247,33 @@ pub struct ApiDoc;
             crate::server::admin::ExtendLicenseResponse,
             crate::server::admin::UpdateUsageRequest,
             crate::server::admin::UpdateUsageResponse,
+            crate::server::usage::SetMeterRequest,
+            crate::server::usage::UsageMetersResponse,
+            crate::server::usage::UsagePeriodResponse,
+            crate::server::usage::UsagePeriodsResponse,
             crate::server::admin::AdminReleaseRequest,
             crate::server::admin::AdminReleaseResponse,
+            crate::server::admin::LicenseDeviceResponse,
+            crate::server::admin::LicenseDevicesResponse,
+            crate::server::admin::BindingHistoryEntryResponse,
+            crate::server::admin::LicenseHistoryResponse,
+            crate::server::admin::DeviceHistoryResponse,
             crate::server::admin::BlacklistLicenseRequest,
             crate::server::admin::BlacklistLicenseResponse,
+            crate::server::admin::OfflineActivationRequest,
+            crate::server::trials::ConvertTrialRequest,
+            crate::client::activation::ActivationRequest,
+            crate::client::activation::ActivationResponse,
+            crate::server::bulk::BulkLicenseRequest,
+            crate::server::bulk::BulkAction,
+            crate::server::bulk::SetTierRequest,
+            crate::server::bulk::BulkMode,
+            crate::server::bulk::BulkFilter,
+            crate::server::bulk::BulkItemStatus,
+            crate::server::bulk::BulkItemResult,
+            crate::server::bulk::BulkLicenseResponse,
+            crate::server::license_io::ImportReport,
+            crate::server::license_io::ImportRowError,
             // Token schemas
             crate::server::tokens::CreateTokenRequest,
             crate::server::tokens::CreateTokenResponse,
@@ -196,6 +282,25 @@ pub struct ApiDoc;
             crate::server::tokens::TokenResponse,
             crate::server::tokens::RevokeTokenResponse,
             crate::server::tokens::TokenErrorResponse,
+            // Audit schemas
+            crate::server::audit::AuditEntryResponse,
+            crate::server::audit::AuditLogResponse,
+            // Webhook schemas
+            crate::server::webhooks::CreateWebhookRequest,
+            crate::server::webhooks::CreateWebhookResponse,
+            crate::server::webhooks::UpdateWebhookRequest,
+            crate::server::webhooks::WebhookResponse,
+            crate::server::webhooks::ListWebhooksResponse,
+            crate::server::webhooks::WebhookDeliveryResponse,
+            crate::server::webhooks::WebhookDeliveriesResponse,
+            // Organization schemas
+            crate::server::orgs::CreateOrgRequest,
+            crate::server::orgs::UpdateOrgRequest,
+            crate::server::orgs::SuspendOrgRequest,
+            crate::server::orgs::OrgResponse,
+            crate::server::orgs::OrgStats,
+            crate::server::orgs::ListOrgsResponse,
+            crate::server::orgs::OrgStatusResponse,
         )
     ),
     modifiers(&SecurityAddon)
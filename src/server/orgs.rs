@@ -0,0 +1,1189 @@
+//! Organizations (customers) that own licenses.
+//!
+//! Every license with an `org_id` belongs to a row of the `organizations`
+//! table, which holds the customer's contact details and metadata plus the
+//! default tier and features given to its new licenses. Licenses keep a copy
+//! of the organization name in `org_name`; renaming the organization updates
+//! every copy.
+//!
+//! Suspending an organization suspends each of its active licenses without
+//! a grace period, marking them `suspended_by_org`, and reinstating it
+//! reactivates those licenses again. Until then they can't be reinstated
+//! one by one.
+//! Creating a license for an `org_id` that is not registered yet registers
+//! the organization, so integrations that predate this module keep working.
+//!
+//! # Endpoints
+//!
+//! - `POST /api/v1/orgs` - Register an organization
+//! - `GET /api/v1/orgs` - List organizations
+//! - `GET /api/v1/orgs/{org_id}` - Get an organization with license, device and usage totals
+//! - `PATCH /api/v1/orgs/{org_id}` - Update an organization
+//! - `DELETE /api/v1/orgs/{org_id}` - Delete an organization without licenses
+//! - `POST /api/v1/orgs/{org_id}/suspend` - Suspend an organization and its licenses
+//! - `POST /api/v1/orgs/{org_id}/reinstate` - Reinstate an organization and its licenses
+
+use std::collections::BTreeMap;
+
+use axum::async_trait;
+use chrono::NaiveDateTime;
+use serde::Serialize;
+use sqlx::FromRow;
+use tracing::error;
+
+#[cfg(feature = "openapi")]
+use utoipa::ToSchema;
+
+use crate::errors::{LicenseError, LicenseResult};
+use crate::server::database::Database;
+use crate::server::store::OrgStore;
+
+#[cfg(feature = "admin-api")]
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    Json,
+};
+#[cfg(feature = "admin-api")]
+use chrono::Utc;
+#[cfg(feature = "admin-api")]
+use serde::Deserialize;
+#[cfg(feature = "admin-api")]
+use serde_json::Value;
+#[cfg(feature = "admin-api")]
+use tracing::info;
+#[cfg(feature = "admin-api")]
+use uuid::Uuid;
+
+#[cfg(feature = "admin-api")]
+use crate::server::admin::AdminError;
+#[cfg(feature = "admin-api")]
+use crate::server::audit::{snapshot, AuditAction, AuditContext, AuditTarget};
+#[cfg(feature = "admin-api")]
+use crate::server::handlers::AppState;
+#[cfg(feature = "admin-api")]
+use crate::server::logging::{log_license_event, LicenseEvent};
+#[cfg(feature = "admin-api")]
+use crate::server::store::LicenseStore;
+#[cfg(feature = "admin-api")]
+use crate::server::validation::{validate_not_empty, validate_org_id};
+#[cfg(feature = "admin-api")]
+use crate::server::webhooks;
+
+/// A row of the `organizations` table.
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct Organization {
+    pub id: String,
+    pub name: String,
+    pub contact_name: Option<String>,
+    pub contact_email: Option<String>,
+    /// JSON object of free-form metadata
+    pub metadata: Option<String>,
+    /// Tier given to new licenses that don't name one
+    pub default_tier: Option<String>,
+    /// JSON array of features given to new licenses that don't list any
+    pub default_features: Option<String>,
+    /// `active` or `suspended`
+    pub status: String,
+    pub suspended_at: Option<NaiveDateTime>,
+    pub suspension_reason: Option<String>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+impl Organization {
+    /// Whether the organization (and with it every license) is suspended.
+    pub fn is_suspended(&self) -> bool {
+        self.status == "suspended"
+    }
+
+    /// Features given to new licenses that don't list any.
+    pub fn default_feature_names(&self) -> Vec<String> {
+        self.default_features
+            .as_deref()
+            .and_then(|f| serde_json::from_str(f).ok())
+            .unwrap_or_default()
+    }
+}
+
+/// License, device and usage totals for an organization.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct OrgStats {
+    pub total_licenses: u64,
+    /// Number of licenses per status, e.g. `{"active": 3, "revoked": 1}`
+    pub licenses_by_status: BTreeMap<String, u64>,
+    /// Devices holding a seat on any of the licenses
+    pub bound_devices: u64,
+    /// Bandwidth used across all licenses
+    pub bandwidth_used_bytes: i64,
+    /// Licenses that have exceeded their quota
+    pub quota_exceeded_licenses: u64,
+}
+
+// ============================================================================
+// Database
+// ============================================================================
+
+const ORG_COLUMNS: &str = "id, name, contact_name, contact_email, metadata, default_tier, \
+     default_features, status, suspended_at, suspension_reason, created_at, updated_at";
+
+/// Per-status license totals: status, licenses, bandwidth used, over quota.
+const STATUS_TOTALS: &str = "SELECT status, COUNT(*), \
+     CAST(COALESCE(SUM(bandwidth_used_bytes), 0) AS BIGINT), \
+     CAST(COALESCE(SUM(CASE WHEN quota_exceeded THEN 1 ELSE 0 END), 0) AS BIGINT) \
+     FROM licenses WHERE org_id = ";
+
+const BOUND_DEVICES: &str = "SELECT COUNT(*) FROM license_devices d \
+     JOIN licenses l ON l.license_id = d.license_id WHERE l.org_id = ";
+
+fn org_error(operation: &str, e: sqlx::Error) -> LicenseError {
+    error!("{operation} failed: {e}");
+    LicenseError::ServerError(format!("database error: {e}"))
+}
+
+fn stats_from_rows(rows: Vec<(String, i64, i64, i64)>, bound_devices: i64) -> OrgStats {
+    let mut stats = OrgStats {
+        bound_devices: bound_devices as u64,
+        ..OrgStats::default()
+    };
+
+    for (status, licenses, bandwidth_used, over_quota) in rows {
+        stats.total_licenses += licenses as u64;
+        stats.bandwidth_used_bytes += bandwidth_used;
+        stats.quota_exceeded_licenses += over_quota as u64;
+        stats.licenses_by_status.insert(status, licenses as u64);
+    }
+
+    stats
+}
+
+#[async_trait]
+impl OrgStore for Database {
+    async fn insert_org(&self, org: &Organization) -> LicenseResult<()> {
+        match self {
+            #[cfg(feature = "sqlite")]
+            Database::SQLite(pool) => {
+                sqlx::query(&format!(
+                    "INSERT INTO organizations ({ORG_COLUMNS}) \
+                     VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"
+                ))
+                .bind(&org.id)
+                .bind(&org.name)
+                .bind(&org.contact_name)
+                .bind(&org.contact_email)
+                .bind(&org.metadata)
+                .bind(&org.default_tier)
+                .bind(&org.default_features)
+                .bind(&org.status)
+                .bind(org.suspended_at)
+                .bind(&org.suspension_reason)
+                .bind(org.created_at)
+                .bind(org.updated_at)
+                .execute(pool)
+                .await
+                .map_err(|e| org_error("SQLite insert_org", e))?;
+            }
+            #[cfg(feature = "postgres")]
+            Database::Postgres(pool) => {
+                sqlx::query(&format!(
+                    "INSERT INTO organizations ({ORG_COLUMNS}) \
+                     VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)"
+                ))
+                .bind(&org.id)
+                .bind(&org.name)
+                .bind(&org.contact_name)
+                .bind(&org.contact_email)
+                .bind(&org.metadata)
+                .bind(&org.default_tier)
+                .bind(&org.default_features)
+                .bind(&org.status)
+                .bind(org.suspended_at)
+                .bind(&org.suspension_reason)
+                .bind(org.created_at)
+                .bind(org.updated_at)
+                .execute(pool)
+                .await
+                .map_err(|e| org_error("Postgres insert_org", e))?;
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn update_org(&self, org: &Organization) -> LicenseResult<()> {
+        match self {
+            #[cfg(feature = "sqlite")]
+            Database::SQLite(pool) => {
+                let fail = |e| org_error("SQLite update_org", e);
+                let mut tx = pool.begin().await.map_err(fail)?;
+
+                sqlx::query(
+                    "UPDATE organizations SET name = ?, contact_name = ?, contact_email = ?, \
+                     metadata = ?, default_tier = ?, default_features = ?, status = ?, \
+                     suspended_at = ?, suspension_reason = ?, updated_at = ? WHERE id = ?",
+                )
+                .bind(&org.name)
+                .bind(&org.contact_name)
+                .bind(&org.contact_email)
+                .bind(&org.metadata)
+                .bind(&org.default_tier)
+                .bind(&org.default_features)
+                .bind(&org.status)
+                .bind(org.suspended_at)
+                .bind(&org.suspension_reason)
+                .bind(org.updated_at)
+                .bind(&org.id)
+                .execute(&mut *tx)
+                .await
+                .map_err(fail)?;
+
+                sqlx::query("UPDATE licenses SET org_name = ? WHERE org_id = ?")
+                    .bind(&org.name)
+                    .bind(&org.id)
+                    .execute(&mut *tx)
+                    .await
+                    .map_err(fail)?;
+
+                tx.commit().await.map_err(fail)?;
+            }
+            #[cfg(feature = "postgres")]
+            Database::Postgres(pool) => {
+                let fail = |e| org_error("Postgres update_org", e);
+                let mut tx = pool.begin().await.map_err(fail)?;
+
+                sqlx::query(
+                    "UPDATE organizations SET name = $1, contact_name = $2, contact_email = $3, \
+                     metadata = $4, default_tier = $5, default_features = $6, status = $7, \
+                     suspended_at = $8, suspension_reason = $9, updated_at = $10 WHERE id = $11",
+                )
+                .bind(&org.name)
+                .bind(&org.contact_name)
+                .bind(&org.contact_email)
+                .bind(&org.metadata)
+                .bind(&org.default_tier)
+                .bind(&org.default_features)
+                .bind(&org.status)
+                .bind(org.suspended_at)
+                .bind(&org.suspension_reason)
+                .bind(org.updated_at)
+                .bind(&org.id)
+                .execute(&mut *tx)
+                .await
+                .map_err(fail)?;
+
+                sqlx::query("UPDATE licenses SET org_name = $1 WHERE org_id = $2")
+                    .bind(&org.name)
+                    .bind(&org.id)
+                    .execute(&mut *tx)
+                    .await
+                    .map_err(fail)?;
+
+                tx.commit().await.map_err(fail)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn get_org(&self, org_id: &str) -> LicenseResult<Option<Organization>> {
+        match self {
+            #[cfg(feature = "sqlite")]
+            Database::SQLite(pool) => sqlx::query_as::<_, Organization>(&format!(
+                "SELECT {ORG_COLUMNS} FROM organizations WHERE id = ?"
+            ))
+            .bind(org_id)
+            .fetch_optional(pool)
+            .await
+            .map_err(|e| org_error("SQLite get_org", e)),
+            #[cfg(feature = "postgres")]
+            Database::Postgres(pool) => sqlx::query_as::<_, Organization>(&format!(
+                "SELECT {ORG_COLUMNS} FROM organizations WHERE id = $1"
+            ))
+            .bind(org_id)
+            .fetch_optional(pool)
+            .await
+            .map_err(|e| org_error("Postgres get_org", e)),
+        }
+    }
+
+    async fn list_orgs(
+        &self,
+        status: Option<&str>,
+        limit: u32,
+        offset: u32,
+    ) -> LicenseResult<(Vec<Organization>, u64)> {
+        match self {
+            #[cfg(feature = "sqlite")]
+            Database::SQLite(pool) => {
+                const WHERE: &str = "(? IS NULL OR status = ?)";
+
+                let total: (i64,) =
+                    sqlx::query_as(&format!("SELECT COUNT(*) FROM organizations WHERE {WHERE}"))
+                        .bind(status)
+                        .bind(status)
+                        .fetch_one(pool)
+                        .await
+                        .map_err(|e| org_error("SQLite list_orgs", e))?;
+
+                let orgs = sqlx::query_as::<_, Organization>(&format!(
+                    "SELECT {ORG_COLUMNS} FROM organizations WHERE {WHERE} \
+                     ORDER BY created_at, id LIMIT ? OFFSET ?"
+                ))
+                .bind(status)
+                .bind(status)
+                .bind(i64::from(limit))
+                .bind(i64::from(offset))
+                .fetch_all(pool)
+                .await
+                .map_err(|e| org_error("SQLite list_orgs", e))?;
+
+                Ok((orgs, total.0 as u64))
+            }
+            #[cfg(feature = "postgres")]
+            Database::Postgres(pool) => {
+                const WHERE: &str = "($1::TEXT IS NULL OR status = $1)";
+
+                let total: (i64,) =
+                    sqlx::query_as(&format!("SELECT COUNT(*) FROM organizations WHERE {WHERE}"))
+                        .bind(status)
+                        .fetch_one(pool)
+                        .await
+                        .map_err(|e| org_error("Postgres list_orgs", e))?;
+
+                let orgs = sqlx::query_as::<_, Organization>(&format!(
+                    "SELECT {ORG_COLUMNS} FROM organizations WHERE {WHERE} \
+                     ORDER BY created_at, id LIMIT $2 OFFSET $3"
+                ))
+                .bind(status)
+                .bind(i64::from(limit))
+                .bind(i64::from(offset))
+                .fetch_all(pool)
+                .await
+                .map_err(|e| org_error("Postgres list_orgs", e))?;
+
+                Ok((orgs, total.0 as u64))
+            }
+        }
+    }
+
+    async fn delete_org(&self, org_id: &str) -> LicenseResult<bool> {
+        let result = match self {
+            #[cfg(feature = "sqlite")]
+            Database::SQLite(pool) => sqlx::query("DELETE FROM organizations WHERE id = ?")
+                .bind(org_id)
+                .execute(pool)
+                .await
+                .map_err(|e| org_error("SQLite delete_org", e))?
+                .rows_affected(),
+            #[cfg(feature = "postgres")]
+            Database::Postgres(pool) => sqlx::query("DELETE FROM organizations WHERE id = $1")
+                .bind(org_id)
+                .execute(pool)
+                .await
+                .map_err(|e| org_error("Postgres delete_org", e))?
+                .rows_affected(),
+        };
+
+        Ok(result > 0)
+    }
+
+    async fn org_stats(&self, org_id: &str) -> LicenseResult<OrgStats> {
+        match self {
+            #[cfg(feature = "sqlite")]
+            Database::SQLite(pool) => {
+                let rows: Vec<(String, i64, i64, i64)> =
+                    sqlx::query_as(&format!("{STATUS_TOTALS}? GROUP BY status"))
+                        .bind(org_id)
+                        .fetch_all(pool)
+                        .await
+                        .map_err(|e| org_error("SQLite org_stats", e))?;
+
+                let devices: (i64,) = sqlx::query_as(&format!("{BOUND_DEVICES}?"))
+                    .bind(org_id)
+                    .fetch_one(pool)
+                    .await
+                    .map_err(|e| org_error("SQLite org_stats", e))?;
+
+                Ok(stats_from_rows(rows, devices.0))
+            }
+            #[cfg(feature = "postgres")]
+            Database::Postgres(pool) => {
+                let rows: Vec<(String, i64, i64, i64)> =
+                    sqlx::query_as(&format!("{STATUS_TOTALS}$1 GROUP BY status"))
+                        .bind(org_id)
+                        .fetch_all(pool)
+                        .await
+                        .map_err(|e| org_error("Postgres org_stats", e))?;
+
+                let devices: (i64,) = sqlx::query_as(&format!("{BOUND_DEVICES}$1"))
+                    .bind(org_id)
+                    .fetch_one(pool)
+                    .await
+                    .map_err(|e| org_error("Postgres org_stats", e))?;
+
+                Ok(stats_from_rows(rows, devices.0))
+            }
+        }
+    }
+}
+
+// ============================================================================
+// License helpers
+// ============================================================================
+
+/// Find the organization a new license is created for.
+///
+/// An `org_id` that is not registered yet is registered on the fly, named
+/// `org_name` (or the ID itself). Licenses can't be created for a suspended
+/// organization.
+#[cfg(feature = "admin-api")]
+pub(crate) async fn resolve_license_org(
+    db: &dyn LicenseStore,
+    audit: &AuditContext,
+    org_id: Option<&str>,
+    org_name: Option<&str>,
+) -> Result<Option<Organization>, AdminError> {
+    let Some(org_id) = org_id else {
+        return Ok(None);
+    };
+
+    if let Some(org) = db.get_org(org_id).await? {
+        if org.is_suspended() {
+            return Err(AdminError::BadRequest(format!(
+                "organization {org_id} is suspended"
+            )));
+        }
+        return Ok(Some(org));
+    }
+
+    validate_org_id(org_id, "org_id").map_err(|e| AdminError::BadRequest(e.to_string()))?;
+
+    let now = Utc::now().naive_utc();
+    let org = Organization {
+        id: org_id.to_string(),
+        name: org_name.unwrap_or(org_id).to_string(),
+        contact_name: None,
+        contact_email: None,
+        metadata: None,
+        default_tier: None,
+        default_features: None,
+        status: "active".to_string(),
+        suspended_at: None,
+        suspension_reason: None,
+        created_at: now,
+        updated_at: now,
+    };
+
+    db.insert_org(&org).await?;
+    info!("Registered organization {} for a new license", org.id);
+
+    audit
+        .record(
+            db,
+            AuditAction::OrgCreated,
+            AuditTarget::Org(org.id.clone()),
+            None,
+            snapshot(&org),
+        )
+        .await;
+
+    Ok(Some(org))
+}
+
+// ============================================================================
+// HTTP Handlers
+// ============================================================================
+
+/// Maximum page size for `GET /api/v1/orgs`.
+#[cfg(feature = "admin-api")]
+pub const MAX_ORG_PAGE_SIZE: u32 = 500;
+
+/// Request body for registering an organization.
+#[cfg(feature = "admin-api")]
+#[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct CreateOrgRequest {
+    /// Organization ID (generated when omitted)
+    pub id: Option<String>,
+    /// Display name, copied to the organization's licenses
+    pub name: String,
+    pub contact_name: Option<String>,
+    pub contact_email: Option<String>,
+    /// Free-form metadata as a JSON object
+    pub metadata: Option<Value>,
+    /// Tier given to new licenses that don't name one
+    pub default_tier: Option<String>,
+    /// Features given to new licenses that don't list any
+    #[serde(default)]
+    pub default_features: Vec<String>,
+}
+
+/// Request body for updating an organization. Omitted fields are left unchanged.
+#[cfg(feature = "admin-api")]
+#[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct UpdateOrgRequest {
+    /// New name (also updates `org_name` on every license)
+    pub name: Option<String>,
+    pub contact_name: Option<String>,
+    pub contact_email: Option<String>,
+    pub metadata: Option<Value>,
+    pub default_tier: Option<String>,
+    pub default_features: Option<Vec<String>>,
+}
+
+/// Request body for suspending an organization.
+#[cfg(feature = "admin-api")]
+#[derive(Debug, Default, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct SuspendOrgRequest {
+    /// Reason for the suspension (kept on the organization)
+    pub reason: Option<String>,
+    /// Message shown to users of the suspended licenses
+    pub message: Option<String>,
+}
+
+/// Query parameters for listing organizations.
+#[cfg(feature = "admin-api")]
+#[derive(Debug, Deserialize)]
+pub struct ListOrgsQuery {
+    /// Filter by status (`active` or `suspended`)
+    pub status: Option<String>,
+    /// Pagination: page number (1-indexed)
+    #[serde(default = "default_page")]
+    pub page: u32,
+    /// Pagination: items per page
+    #[serde(default = "default_per_page")]
+    pub per_page: u32,
+}
+
+#[cfg(feature = "admin-api")]
+fn default_page() -> u32 {
+    1
+}
+#[cfg(feature = "admin-api")]
+fn default_per_page() -> u32 {
+    50
+}
+
+/// An organization as returned by the API.
+#[cfg(feature = "admin-api")]
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct OrgResponse {
+    pub id: String,
+    pub name: String,
+    pub contact_name: Option<String>,
+    pub contact_email: Option<String>,
+    pub metadata: Option<Value>,
+    pub default_tier: Option<String>,
+    pub default_features: Vec<String>,
+    pub status: String,
+    pub suspended_at: Option<String>,
+    pub suspension_reason: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+    /// License, device and usage totals (only on `GET /api/v1/orgs/{org_id}`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stats: Option<OrgStats>,
+}
+
+#[cfg(feature = "admin-api")]
+impl From<Organization> for OrgResponse {
+    fn from(org: Organization) -> Self {
+        Self {
+            default_features: org.default_feature_names(),
+            metadata: org
+                .metadata
+                .as_deref()
+                .and_then(|m| serde_json::from_str(m).ok()),
+            id: org.id,
+            name: org.name,
+            contact_name: org.contact_name,
+            contact_email: org.contact_email,
+            default_tier: org.default_tier,
+            status: org.status,
+            suspended_at: org.suspended_at.map(|d| d.and_utc().to_rfc3339()),
+            suspension_reason: org.suspension_reason,
+            created_at: org.created_at.and_utc().to_rfc3339(),
+            updated_at: org.updated_at.and_utc().to_rfc3339(),
+            stats: None,
+        }
+    }
+}
+
+/// Response for listing organizations.
+#[cfg(feature = "admin-api")]
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct ListOrgsResponse {
+    pub orgs: Vec<OrgResponse>,
+    pub total: u64,
+    pub page: u32,
+    pub per_page: u32,
+    pub total_pages: u64,
+}
+
+/// Response from suspending or reinstating an organization.
+#[cfg(feature = "admin-api")]
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct OrgStatusResponse {
+    pub org: OrgResponse,
+    /// IDs of the licenses whose status changed with the organization
+    pub licenses: Vec<String>,
+}
+
+#[cfg(feature = "admin-api")]
+fn validate_name(name: &str) -> Result<(), AdminError> {
+    validate_not_empty(name, "name").map_err(|e| AdminError::BadRequest(e.to_string()))
+}
+
+#[cfg(feature = "admin-api")]
+fn to_json(value: &impl Serialize) -> Result<String, AdminError> {
+    serde_json::to_string(value).map_err(|e| AdminError::BadRequest(e.to_string()))
+}
+
+#[cfg(feature = "admin-api")]
+async fn require_org(state: &AppState, org_id: &str) -> Result<Organization, AdminError> {
+    state
+        .db
+        .get_org(org_id)
+        .await?
+        .ok_or_else(|| AdminError::NotFound(format!("Organization {org_id} not found")))
+}
+
+/// Register an organization.
+///
+/// `POST /api/v1/orgs`
+#[cfg(feature = "admin-api")]
+#[cfg_attr(feature = "openapi", utoipa::path(
+    post,
+    path = "/api/v1/orgs",
+    tag = "orgs",
+    request_body = CreateOrgRequest,
+    responses(
+        (status = 201, description = "Organization created", body = OrgResponse),
+        (status = 400, description = "Invalid request or ID already taken"),
+        (status = 500, description = "Server error"),
+    ),
+    security(("bearer_auth" = []))
+))]
+pub async fn create_org_handler(
+    State(state): State<AppState>,
+    audit: AuditContext,
+    Json(payload): Json<CreateOrgRequest>,
+) -> Result<(StatusCode, Json<OrgResponse>), AdminError> {
+    validate_name(&payload.name)?;
+
+    let id = match payload.id {
+        Some(id) => {
+            validate_org_id(&id, "id").map_err(|e| AdminError::BadRequest(e.to_string()))?;
+            if state.db.get_org(&id).await?.is_some() {
+                return Err(AdminError::BadRequest(format!(
+                    "organization {id} already exists"
+                )));
+            }
+            id
+        }
+        None => Uuid::new_v4().to_string(),
+    };
+
+    let now = Utc::now().naive_utc();
+    let org = Organization {
+        id,
+        name: payload.name,
+        contact_name: payload.contact_name,
+        contact_email: payload.contact_email,
+        metadata: payload.metadata.as_ref().map(to_json).transpose()?,
+        default_tier: payload.default_tier,
+        default_features: Some(to_json(&payload.default_features)?),
+        status: "active".to_string(),
+        suspended_at: None,
+        suspension_reason: None,
+        created_at: now,
+        updated_at: now,
+    };
+
+    state.db.insert_org(&org).await?;
+    info!("Created organization {} ({})", org.id, org.name);
+
+    audit
+        .record(
+            &*state.db,
+            AuditAction::OrgCreated,
+            AuditTarget::Org(org.id.clone()),
+            None,
+            snapshot(&org),
+        )
+        .await;
+
+    Ok((StatusCode::CREATED, Json(org.into())))
+}
+
+/// List organizations, oldest first.
+///
+/// `GET /api/v1/orgs?status=&page=&per_page=`
+#[cfg(feature = "admin-api")]
+#[cfg_attr(feature = "openapi", utoipa::path(
+    get,
+    path = "/api/v1/orgs",
+    tag = "orgs",
+    params(
+        ("status" = Option<String>, Query, description = "Filter by status (active, suspended)"),
+        ("page" = Option<u32>, Query, description = "Page number (1-indexed)"),
+        ("per_page" = Option<u32>, Query, description = "Items per page (max 500)")
+    ),
+    responses(
+        (status = 200, description = "Organizations", body = ListOrgsResponse),
+        (status = 400, description = "Invalid filter"),
+        (status = 500, description = "Server error"),
+    ),
+    security(("bearer_auth" = []))
+))]
+pub async fn list_orgs_handler(
+    State(state): State<AppState>,
+    Query(query): Query<ListOrgsQuery>,
+) -> Result<Json<ListOrgsResponse>, AdminError> {
+    if query.page == 0 {
+        return Err(AdminError::BadRequest(
+            "page must be at least 1".to_string(),
+        ));
+    }
+    if query.per_page == 0 || query.per_page > MAX_ORG_PAGE_SIZE {
+        return Err(AdminError::BadRequest(format!(
+            "per_page must be between 1 and {MAX_ORG_PAGE_SIZE}"
+        )));
+    }
+    if let Some(status) = &query.status {
+        if status != "active" && status != "suspended" {
+            return Err(AdminError::BadRequest(format!("unknown status '{status}'")));
+        }
+    }
+
+    let offset = (query.page - 1).saturating_mul(query.per_page);
+    let (orgs, total) = state
+        .db
+        .list_orgs(query.status.as_deref(), query.per_page, offset)
+        .await?;
+
+    Ok(Json(ListOrgsResponse {
+        orgs: orgs.into_iter().map(Into::into).collect(),
+        total,
+        page: query.page,
+        per_page: query.per_page,
+        total_pages: total.div_ceil(u64::from(query.per_page)),
+    }))
+}
+
+/// Get an organization with its license, device and usage totals.
+///
+/// `GET /api/v1/orgs/{org_id}`
+#[cfg(feature = "admin-api")]
+#[cfg_attr(feature = "openapi", utoipa::path(
+    get,
+    path = "/api/v1/orgs/{org_id}",
+    tag = "orgs",
+    params(
+        ("org_id" = String, Path, description = "Organization ID")
+    ),
+    responses(
+        (status = 200, description = "Organization", body = OrgResponse),
+        (status = 404, description = "Organization not found"),
+    ),
+    security(("bearer_auth" = []))
+))]
+pub async fn get_org_handler(
+    State(state): State<AppState>,
+    Path(org_id): Path<String>,
+) -> Result<Json<OrgResponse>, AdminError> {
+    let org = require_org(&state, &org_id).await?;
+    let stats = state.db.org_stats(&org_id).await?;
+
+    Ok(Json(OrgResponse {
+        stats: Some(stats),
+        ..org.into()
+    }))
+}
+
+/// Update an organization's name, contact details, metadata or defaults.
+///
+/// `PATCH /api/v1/orgs/{org_id}`
+#[cfg(feature = "admin-api")]
+#[cfg_attr(feature = "openapi", utoipa::path(
+    patch,
+    path = "/api/v1/orgs/{org_id}",
+    tag = "orgs",
+    params(
+        ("org_id" = String, Path, description = "Organization ID")
+    ),
+    request_body = UpdateOrgRequest,
+    responses(
+        (status = 200, description = "Organization updated", body = OrgResponse),
+        (status = 400, description = "Invalid request"),
+        (status = 404, description = "Organization not found"),
+    ),
+    security(("bearer_auth" = []))
+))]
+pub async fn update_org_handler(
+    State(state): State<AppState>,
+    audit: AuditContext,
+    Path(org_id): Path<String>,
+    Json(payload): Json<UpdateOrgRequest>,
+) -> Result<Json<OrgResponse>, AdminError> {
+    let mut org = require_org(&state, &org_id).await?;
+    let before = snapshot(&org);
+
+    if let Some(name) = payload.name {
+        validate_name(&name)?;
+        org.name = name;
+    }
+    if let Some(contact_name) = payload.contact_name {
+        org.contact_name = Some(contact_name);
+    }
+    if let Some(contact_email) = payload.contact_email {
+        org.contact_email = Some(contact_email);
+    }
+    if let Some(metadata) = payload.metadata {
+        org.metadata = Some(to_json(&metadata)?);
+    }
+    if let Some(default_tier) = payload.default_tier {
+        org.default_tier = Some(default_tier);
+    }
+    if let Some(default_features) = payload.default_features {
+        org.default_features = Some(to_json(&default_features)?);
+    }
+    org.updated_at = Utc::now().naive_utc();
+
+    state.db.update_org(&org).await?;
+
+    audit
+        .record(
+            &*state.db,
+            AuditAction::OrgUpdated,
+            AuditTarget::Org(org_id),
+            before,
+            snapshot(&org),
+        )
+        .await;
+
+    Ok(Json(org.into()))
+}
+
+/// Delete an organization. Organizations that still have licenses can't be deleted.
+///
+/// `DELETE /api/v1/orgs/{org_id}`
+#[cfg(feature = "admin-api")]
+#[cfg_attr(feature = "openapi", utoipa::path(
+    delete,
+    path = "/api/v1/orgs/{org_id}",
+    tag = "orgs",
+    params(
+        ("org_id" = String, Path, description = "Organization ID")
+    ),
+    responses(
+        (status = 204, description = "Organization deleted"),
+        (status = 400, description = "Organization still has licenses"),
+        (status = 404, description = "Organization not found"),
+    ),
+    security(("bearer_auth" = []))
+))]
+pub async fn delete_org_handler(
+    State(state): State<AppState>,
+    audit: AuditContext,
+    Path(org_id): Path<String>,
+) -> Result<StatusCode, AdminError> {
+    let org = require_org(&state, &org_id).await?;
+
+    let licenses = state.db.list_licenses_by_org(&org_id).await?;
+    if !licenses.is_empty() {
+        return Err(AdminError::BadRequest(format!(
+            "organization {org_id} still has {} license(s)",
+            licenses.len()
+        )));
+    }
+
+    state.db.delete_org(&org_id).await?;
+    info!("Deleted organization {}", org_id);
+
+    audit
+        .record(
+            &*state.db,
+            AuditAction::OrgDeleted,
+            AuditTarget::Org(org_id),
+            snapshot(&org),
+            None,
+        )
+        .await;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Suspend an organization and every active license it owns.
+///
+/// `POST /api/v1/orgs/{org_id}/suspend`
+///
+/// Licenses are suspended without a grace period, so clients stop
+/// validating right away. Revoked, blacklisted and already suspended
+/// licenses are left as they are.
+#[cfg(feature = "admin-api")]
+#[cfg_attr(feature = "openapi", utoipa::path(
+    post,
+    path = "/api/v1/orgs/{org_id}/suspend",
+    tag = "orgs",
+    params(
+        ("org_id" = String, Path, description = "Organization ID")
+    ),
+    request_body = SuspendOrgRequest,
+    responses(
+        (status = 200, description = "Organization suspended", body = OrgStatusResponse),
+        (status = 400, description = "Organization already suspended"),
+        (status = 404, description = "Organization not found"),
+    ),
+    security(("bearer_auth" = []))
+))]
+pub async fn suspend_org_handler(
+    State(state): State<AppState>,
+    audit: AuditContext,
+    Path(org_id): Path<String>,
+    Json(payload): Json<SuspendOrgRequest>,
+) -> Result<Json<OrgStatusResponse>, AdminError> {
+    let mut org = require_org(&state, &org_id).await?;
+    if org.is_suspended() {
+        return Err(AdminError::BadRequest(format!(
+            "organization {org_id} is already suspended"
+        )));
+    }
+
+    let before = snapshot(&org);
+    let now = Utc::now().naive_utc();
+
+    org.status = "suspended".to_string();
+    org.suspended_at = Some(now);
+    org.suspension_reason = payload.reason.clone();
+    org.updated_at = now;
+    state.db.update_org(&org).await?;
+
+    let mut suspended = Vec::new();
+    for mut license in state.db.list_licenses_by_org(&org_id).await? {
+        if license.status != "active" || license.is_blacklisted == Some(true) {
+            continue;
+        }
+
+        let license_before = snapshot(&license);
+        license.status = "suspended".to_string();
+        license.suspended_at = Some(now);
+        license.grace_period_ends_at = None;
+        license.revoke_reason = payload.reason.clone();
+        license.suspension_message = payload.message.clone();
+        license.suspended_by_org = Some(true);
+        let license_after = snapshot(&license);
+
+        let license_id = license.license_id.clone();
+        state.db.insert_license(license).await?;
+
+        log_license_event(
+            LicenseEvent::Suspended,
+            &license_id,
+            Some(&format!("organization {org_id} suspended")),
+        );
+        webhooks::notify(
+            &*state.db,
+            LicenseEvent::Suspended,
+            &license_id,
+            Some(serde_json::json!({ "reason": payload.reason, "org_id": org_id })),
+        )
+        .await;
+        audit
+            .record(
+                &*state.db,
+                AuditAction::LicenseSuspended,
+                AuditTarget::License(license_id.clone()),
+                license_before,
+                license_after,
+            )
+            .await;
+
+        suspended.push(license_id);
+    }
+
+    info!(
+        "Suspended organization {} and {} license(s)",
+        org_id,
+        suspended.len()
+    );
+
+    audit
+        .record(
+            &*state.db,
+            AuditAction::OrgSuspended,
+            AuditTarget::Org(org_id),
+            before,
+            snapshot(&org),
+        )
+        .await;
+
+    Ok(Json(OrgStatusResponse {
+        org: org.into(),
+        licenses: suspended,
+    }))
+}
+
+/// Reinstate a suspended organization and the licenses suspended with it.
+///
+/// `POST /api/v1/orgs/{org_id}/reinstate`
+///
+/// Only the licenses the organization's suspension suspended are
+/// reactivated. Licenses revoked or suspended on their own, before or
+/// during the suspension, keep their status.
+#[cfg(feature = "admin-api")]
+#[cfg_attr(feature = "openapi", utoipa::path(
+    post,
+    path = "/api/v1/orgs/{org_id}/reinstate",
+    tag = "orgs",
+    params(
+        ("org_id" = String, Path, description = "Organization ID")
+    ),
+    responses(
+        (status = 200, description = "Organization reinstated", body = OrgStatusResponse),
+        (status = 400, description = "Organization is not suspended"),
+        (status = 404, description = "Organization not found"),
+    ),
+    security(("bearer_auth" = []))
+))]
+pub async fn reinstate_org_handler(
+    State(state): State<AppState>,
+    audit: AuditContext,
+    Path(org_id): Path<String>,
+) -> Result<Json<OrgStatusResponse>, AdminError> {
+    let mut org = require_org(&state, &org_id).await?;
+    if !org.is_suspended() {
+        return Err(AdminError::BadRequest(format!(
+            "organization {org_id} is not suspended"
+        )));
+    }
+
+    let before = snapshot(&org);
+
+    org.status = "active".to_string();
+    org.suspended_at = None;
+    org.suspension_reason = None;
+    org.updated_at = Utc::now().naive_utc();
+    state.db.update_org(&org).await?;
+
+    let mut reinstated = Vec::new();
+    for mut license in state.db.list_licenses_by_org(&org_id).await? {
+        if license.status != "suspended"
+            || license.suspended_by_org != Some(true)
+            || license.is_blacklisted == Some(true)
+        {
+            continue;
+        }
+
+        let license_before = snapshot(&license);
+        license.status = "active".to_string();
+        license.suspended_at = None;
+        license.revoke_reason = None;
+        license.suspension_message = None;
+        license.suspended_by_org = None;
+        let license_after = snapshot(&license);
+
+        let license_id = license.license_id.clone();
+        state.db.insert_license(license).await?;
+
+        log_license_event(
+            LicenseEvent::Reinstated,
+            &license_id,
+            Some(&format!("organization {org_id} reinstated")),
+        );
+        webhooks::notify(
+            &*state.db,
+            LicenseEvent::Reinstated,
+            &license_id,
+            Some(serde_json::json!({ "org_id": org_id })),
+        )
+        .await;
+        audit
+            .record(
+                &*state.db,
+                AuditAction::LicenseReinstated,
+                AuditTarget::License(license_id.clone()),
+                license_before,
+                license_after,
+            )
+            .await;
+
+        reinstated.push(license_id);
+    }
+
+    info!(
+        "Reinstated organization {} and {} license(s)",
+        org_id,
+        reinstated.len()
+    );
+
+    audit
+        .record(
+            &*state.db,
+            AuditAction::OrgReinstated,
+            AuditTarget::Org(org_id),
+            before,
+            snapshot(&org),
+        )
+        .await;
+
+    Ok(Json(OrgStatusResponse {
+        org: org.into(),
+        licenses: reinstated,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stats_sum_rows_per_status() {
+        let stats = stats_from_rows(
+            vec![
+                ("active".to_string(), 3, 1_000, 1),
+                ("revoked".to_string(), 1, 500, 0),
+            ],
+            4,
+        );
+
+        assert_eq!(stats.total_licenses, 4);
+        assert_eq!(stats.licenses_by_status.get("active"), Some(&3));
+        assert_eq!(stats.licenses_by_status.get("revoked"), Some(&1));
+        assert_eq!(stats.bound_devices, 4);
+        assert_eq!(stats.bandwidth_used_bytes, 1_500);
+        assert_eq!(stats.quota_exceeded_licenses, 1);
+    }
+
+    #[test]
+    fn default_features_parse_json_array() {
+        let now = chrono::Utc::now().naive_utc();
+        let mut org = Organization {
+            id: "acme".to_string(),
+            name: "Acme".to_string(),
+            contact_name: None,
+            contact_email: None,
+            metadata: None,
+            default_tier: None,
+            default_features: Some(r#"["export","api"]"#.to_string()),
+            status: "active".to_string(),
+            suspended_at: None,
+            suspension_reason: None,
+            created_at: now,
+            updated_at: now,
+        };
+        assert_eq!(org.default_feature_names(), vec!["export", "api"]);
+        assert!(!org.is_suspended());
+
+        org.default_features = None;
+        org.status = "suspended".to_string();
+        assert!(org.default_feature_names().is_empty());
+        assert!(org.is_suspended());
+    }
+}
@@ -23,18 +23,26 @@
 //!
 //! # Usage
 //!
-//! Use `SmartIpKeyExtractor` which automatically handles X-Forwarded-For headers
-//! for proxied requests, or use the built-in `PeerIpKeyExtractor` for direct connections.
-
-use axum::{body::Body, http::StatusCode, response::Response};
+//! `build_router` applies one limiter per endpoint class when
+//! `rate_limit.enabled` is set. Use `SmartIpKeyExtractor` which automatically
+//! handles X-Forwarded-For headers for proxied requests, or use the built-in
+//! `PeerIpKeyExtractor` for direct connections.
+
+use axum::{
+    body::Body,
+    http::StatusCode,
+    response::{IntoResponse, Response},
+};
 use governor::middleware::NoOpMiddleware;
 use std::sync::Arc;
 use tower_governor::governor::GovernorConfigBuilder;
+use tower_governor::GovernorError;
 
 pub use tower_governor::key_extractor::SmartIpKeyExtractor;
 pub use tower_governor::GovernorLayer;
 
 use crate::config::RateLimitConfig;
+use crate::server::api_error::{ApiError, ErrorCode};
 
 /// Rate limiter types for different endpoint categories.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -43,7 +51,7 @@ pub enum RateLimitType {
     Validate,
     /// For /heartbeat endpoint (medium limit)
     Heartbeat,
-    /// For /bind and /release endpoints (lower limit)
+    /// For /bind, /release, /checkout and /checkin endpoints (lower limit)
     Bind,
 }
 
@@ -51,7 +59,8 @@ pub enum RateLimitType {
 ///
 /// Returns a `GovernorLayer` configured with the appropriate limits from config.
 /// Uses `SmartIpKeyExtractor` which checks X-Forwarded-For, X-Real-IP headers
-/// before falling back to the peer IP address.
+/// before falling back to the peer IP address. Rejected requests get the
+/// response from [`rate_limit_error_response`].
 ///
 /// # Important
 ///
@@ -72,12 +81,14 @@ pub fn create_rate_limiter(
 
     // Convert RPM to a replenish interval
     // requests_per_minute -> one request every (60000/rpm) milliseconds
-    let interval_ms = if rpm > 0 { 60_000 / rpm } else { 60_000 };
+    let interval_ms = 60_000u32.checked_div(rpm).unwrap_or(60_000).max(1);
 
+    // A zero burst size would reject every request
     let governor_config = GovernorConfigBuilder::default()
         .per_millisecond(interval_ms.into())
-        .burst_size(config.burst_size)
+        .burst_size(config.burst_size.max(1))
         .key_extractor(SmartIpKeyExtractor)
+        .error_handler(governor_error_response)
         .finish()
         .expect("failed to build governor config");
 
@@ -105,6 +116,19 @@ pub fn rate_limit_error_response(retry_after_secs: u64) -> Response<Body> {
         .unwrap()
 }
 
+/// Map governor errors to API responses.
+fn governor_error_response(error: GovernorError) -> Response<Body> {
+    match error {
+        GovernorError::TooManyRequests { wait_time, .. } => rate_limit_error_response(wait_time),
+        GovernorError::UnableToExtractKey => ApiError::with_message(
+            ErrorCode::InternalError,
+            "unable to determine client address",
+        )
+        .into_response(),
+        GovernorError::Other { code, msg, .. } => (code, msg.unwrap_or_default()).into_response(),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,41 +1,80 @@
 use axum::{middleware, routing::get, routing::post, Router};
 
 #[cfg(feature = "admin-api")]
-use axum::routing::{delete, patch};
+use axum::{
+    extract::DefaultBodyLimit,
+    routing::{delete, patch, put, MethodRouter},
+};
 
 #[cfg(feature = "openapi")]
 use utoipa_swagger_ui::SwaggerUi;
 
 use crate::server::client_api::{
-    bind_handler, client_heartbeat_handler, release_handler, validate_feature_handler,
-    validate_handler, validate_or_bind_handler,
+    bind_handler, checkin_handler, checkout_handler, client_heartbeat_handler, release_handler,
+    validate_feature_handler, validate_handler, validate_or_bind_handler,
 };
 use crate::server::handlers::{
     activate_license_handler, deactivate_license_handler, health_handler, heartbeat_handler,
     validate_license_handler, AppState,
 };
 use crate::server::logging::request_logging_middleware;
+use crate::server::trials::start_trial_handler;
+use crate::server::usage::report_usage_handler;
 
-#[cfg(feature = "admin-api")]
+#[cfg(any(feature = "admin-api", feature = "rate-limiting"))]
 use crate::config::get_config;
+#[cfg(feature = "rate-limiting")]
+use crate::config::RateLimitConfig;
 #[cfg(feature = "admin-api")]
 use crate::server::ip_whitelist::IpWhitelistLayer;
+#[cfg(feature = "rate-limiting")]
+use crate::server::rate_limit::{create_rate_limiter, RateLimitType};
 
 #[cfg(feature = "admin-api")]
 use crate::server::admin::{
     admin_release_handler, batch_create_license_handler, blacklist_license_handler,
-    create_license_handler, extend_license_handler, get_license_handler, list_licenses_handler,
-    reinstate_license_handler, revoke_license_handler, update_license_handler,
-    update_usage_handler,
+    create_license_handler, device_history_handler, extend_license_handler, get_license_handler,
+    license_history_handler, list_license_devices_handler, list_licenses_handler,
+    offline_activation_handler, reinstate_license_handler, remove_license_device_handler,
+    revoke_license_handler, update_license_handler, update_usage_handler,
+};
+
+#[cfg(feature = "admin-api")]
+use crate::server::audit::list_audit_handler;
+
+#[cfg(feature = "admin-api")]
+use crate::server::bulk::bulk_license_handler;
+
+#[cfg(feature = "admin-api")]
+use crate::server::license_io::{
+    export_licenses_handler, import_licenses_handler, MAX_IMPORT_BYTES,
+};
+
+#[cfg(feature = "admin-api")]
+use crate::server::webhooks::{
+    create_webhook_handler, delete_webhook_handler, get_webhook_handler,
+    list_webhook_deliveries_handler, list_webhooks_handler, update_webhook_handler,
+};
+
+#[cfg(feature = "admin-api")]
+use crate::server::orgs::{
+    create_org_handler, delete_org_handler, get_org_handler, list_orgs_handler,
+    reinstate_org_handler, suspend_org_handler, update_org_handler,
 };
 
+#[cfg(feature = "admin-api")]
+use crate::server::trials::convert_trial_handler;
+
+#[cfg(feature = "admin-api")]
+use crate::server::usage::{list_usage_handler, list_usage_periods_handler, set_meter_handler};
+
 #[cfg(feature = "admin-api")]
 use crate::server::tokens::{
-    create_token_handler, get_token_handler, list_tokens_handler, revoke_token_handler,
+    create_token_handler, get_token_handler, list_tokens_handler, revoke_token_handler, scopes,
 };
 
 #[cfg(all(feature = "admin-api", feature = "jwt-auth"))]
-use crate::server::auth::AuthLayer;
+use crate::server::auth::{AuthLayer, RequireScopeLayer};
 
 /// Build the main application router for the Talos server.
 ///
@@ -57,47 +96,93 @@ use crate::server::auth::AuthLayer;
 /// - `POST /api/v1/client/validate-or-bind` - Validate or auto-bind
 /// - `POST /api/v1/client/heartbeat` - Send heartbeat
 /// - `POST /api/v1/client/validate-feature` - Validate a specific feature
+/// - `POST /api/v1/client/checkout` - Check out a floating lease
+/// - `POST /api/v1/client/checkin` - Return a floating lease to the pool
+/// - `POST /api/v1/client/trial` - Start a trial on this machine (requires `[trials] enabled`)
+/// - `POST /api/v1/client/usage` - Report usage of a meter
 ///
 /// ## Admin endpoints (requires `admin-api` feature)
 /// - `POST /api/v1/licenses` - Create a license
 /// - `POST /api/v1/licenses/batch` - Batch create licenses
+/// - `POST /api/v1/licenses/bulk` - Revoke, reinstate, extend, blacklist, release or re-tier many licenses
+/// - `GET /api/v1/licenses/export` - Export licenses as CSV or NDJSON
+/// - `POST /api/v1/licenses/import` - Import licenses from CSV or NDJSON (with dry run)
 /// - `GET /api/v1/licenses/{license_id}` - Get a license
-/// - `GET /api/v1/licenses` - List licenses (requires org_id query param)
+/// - `GET /api/v1/licenses` - List licenses (filters, sorting and cursor pagination)
 /// - `PATCH /api/v1/licenses/{license_id}` - Update a license
 /// - `POST /api/v1/licenses/{license_id}/release` - Admin force release
+/// - `GET /api/v1/licenses/{license_id}/devices` - List devices holding a seat
+/// - `DELETE /api/v1/licenses/{license_id}/devices/{hardware_id}` - Free a single seat
+/// - `GET /api/v1/licenses/{license_id}/history` - Binding history of a license
+/// - `GET /api/v1/devices/{hardware_id}/history` - Binding history of a device across all licenses
 /// - `POST /api/v1/licenses/{license_id}/revoke` - Revoke a license
 /// - `POST /api/v1/licenses/{license_id}/reinstate` - Reinstate a revoked/suspended license
 /// - `POST /api/v1/licenses/{license_id}/extend` - Extend license expiration
+/// - `GET /api/v1/licenses/{license_id}/usage` - List usage meters
 /// - `PATCH /api/v1/licenses/{license_id}/usage` - Update bandwidth/usage tracking
+/// - `PUT /api/v1/licenses/{license_id}/usage/{meter}` - Set a meter's usage or limit
+/// - `GET /api/v1/licenses/{license_id}/usage-periods` - List closed billing periods
 /// - `POST /api/v1/licenses/{license_id}/blacklist` - Permanently blacklist a license
+/// - `POST /api/v1/licenses/{license_id}/convert-trial` - Convert a trial into a paid license
+/// - `POST /api/v1/licenses/offline-activation` - Activate an air-gapped machine
 ///
 /// ## Token endpoints (requires `admin-api` feature)
 /// - `POST /api/v1/tokens` - Create a new API token
 /// - `GET /api/v1/tokens` - List all API tokens
 /// - `GET /api/v1/tokens/{token_id}` - Get a specific token
 /// - `DELETE /api/v1/tokens/{token_id}` - Revoke a token
+///
+/// ## Audit endpoints (requires `admin-api` feature)
+/// - `GET /api/v1/audit` - List audit log entries
+///
+/// ## Webhook endpoints (requires `admin-api` feature)
+/// - `POST /api/v1/webhooks` - Register a webhook
+/// - `GET /api/v1/webhooks` - List webhooks
+/// - `GET /api/v1/webhooks/{webhook_id}` - Get a webhook
+/// - `PATCH /api/v1/webhooks/{webhook_id}` - Update a webhook
+/// - `DELETE /api/v1/webhooks/{webhook_id}` - Delete a webhook
+/// - `GET /api/v1/webhooks/{webhook_id}/deliveries` - Delivery log
+///
+/// ## Organization endpoints (requires `admin-api` feature)
+/// - `POST /api/v1/orgs` - Register an organization
+/// - `GET /api/v1/orgs` - List organizations
+/// - `GET /api/v1/orgs/{org_id}` - Get an organization with its stats
+/// - `PATCH /api/v1/orgs/{org_id}` - Update an organization
+/// - `DELETE /api/v1/orgs/{org_id}` - Delete an organization without licenses
+/// - `POST /api/v1/orgs/{org_id}/suspend` - Suspend an organization and its licenses
+/// - `POST /api/v1/orgs/{org_id}/reinstate` - Reinstate an organization and its licenses
+///
+/// With the `rate-limiting` feature, client endpoints are throttled per IP
+/// according to `[rate_limit]` in the config.
 pub fn build_router(state: AppState) -> Router {
+    #[cfg(feature = "rate-limiting")]
+    let client_routes = {
+        let rate_limit = get_config()
+            .map(|c| c.rate_limit.clone())
+            .unwrap_or_default();
+        rate_limited_client_routes(&rate_limit)
+    };
+
+    #[cfg(not(feature = "rate-limiting"))]
+    let client_routes = client_routes();
+
+    assemble_router(state, client_routes)
+}
+
+/// Build the router with an explicit rate limit configuration.
+///
+/// Same as [`build_router`], but ignores `[rate_limit]` from the global config.
+#[cfg(feature = "rate-limiting")]
+pub fn build_router_with_rate_limit(state: AppState, rate_limit: &RateLimitConfig) -> Router {
+    assemble_router(state, rate_limited_client_routes(rate_limit))
+}
+
+/// Combine client routes with health, admin and docs routes.
+fn assemble_router(state: AppState, client_routes: Router<AppState>) -> Router {
     let router = Router::new()
-        // Health check endpoint (no auth required)
+        // Health check endpoint (no auth required, never rate limited)
         .route("/health", get(health_handler))
-        // Legacy client endpoints (backwards compatibility)
-        .route("/activate", post(activate_license_handler))
-        .route("/validate", post(validate_license_handler))
-        .route("/deactivate", post(deactivate_license_handler))
-        .route("/heartbeat", post(heartbeat_handler))
-        // New client API v1 endpoints
-        .route("/api/v1/client/bind", post(bind_handler))
-        .route("/api/v1/client/release", post(release_handler))
-        .route("/api/v1/client/validate", post(validate_handler))
-        .route(
-            "/api/v1/client/validate-or-bind",
-            post(validate_or_bind_handler),
-        )
-        .route("/api/v1/client/heartbeat", post(client_heartbeat_handler))
-        .route(
-            "/api/v1/client/validate-feature",
-            post(validate_feature_handler),
-        );
+        .merge(client_routes);
 
     // Add admin API routes if feature is enabled
     // When both admin-api and jwt-auth are enabled, apply auth + IP whitelist middleware
@@ -108,46 +193,9 @@ pub fn build_router(state: AppState) -> Router {
             .map(|c| IpWhitelistLayer::from_config(&c.admin.ip_whitelist))
             .unwrap_or_else(|_| IpWhitelistLayer::from_config(&[]));
 
-        // Build admin routes as a nested router with auth + IP whitelist layers
-        let admin_routes = Router::new()
-            .route("/api/v1/licenses", post(create_license_handler))
-            .route("/api/v1/licenses", get(list_licenses_handler))
-            .route("/api/v1/licenses/batch", post(batch_create_license_handler))
-            .route("/api/v1/licenses/:license_id", get(get_license_handler))
-            .route(
-                "/api/v1/licenses/:license_id",
-                patch(update_license_handler),
-            )
-            .route(
-                "/api/v1/licenses/:license_id/release",
-                post(admin_release_handler),
-            )
-            .route(
-                "/api/v1/licenses/:license_id/revoke",
-                post(revoke_license_handler),
-            )
-            .route(
-                "/api/v1/licenses/:license_id/reinstate",
-                post(reinstate_license_handler),
-            )
-            .route(
-                "/api/v1/licenses/:license_id/extend",
-                post(extend_license_handler),
-            )
-            .route(
-                "/api/v1/licenses/:license_id/usage",
-                patch(update_usage_handler),
-            )
-            .route(
-                "/api/v1/licenses/:license_id/blacklist",
-                post(blacklist_license_handler),
-            )
-            // Token management routes
-            .route("/api/v1/tokens", post(create_token_handler))
-            .route("/api/v1/tokens", get(list_tokens_handler))
-            .route("/api/v1/tokens/:token_id", get(get_token_handler))
-            .route("/api/v1/tokens/:token_id", delete(revoke_token_handler))
-            // Apply IP whitelist first (outer layer), then auth (inner layer)
+        // Apply IP whitelist first (outer layer), then auth (inner layer).
+        // Per-route scope checks run inside the auth layer.
+        let admin_routes = admin_routes()
             .layer(AuthLayer::new(state.auth.clone()))
             .layer(ip_whitelist_layer);
 
@@ -163,47 +211,7 @@ pub fn build_router(state: AppState) -> Router {
             .map(|c| IpWhitelistLayer::from_config(&c.admin.ip_whitelist))
             .unwrap_or_else(|_| IpWhitelistLayer::from_config(&[]));
 
-        let admin_routes = Router::new()
-            .route("/api/v1/licenses", post(create_license_handler))
-            .route("/api/v1/licenses", get(list_licenses_handler))
-            .route("/api/v1/licenses/batch", post(batch_create_license_handler))
-            .route("/api/v1/licenses/:license_id", get(get_license_handler))
-            .route(
-                "/api/v1/licenses/:license_id",
-                patch(update_license_handler),
-            )
-            .route(
-                "/api/v1/licenses/:license_id/release",
-                post(admin_release_handler),
-            )
-            .route(
-                "/api/v1/licenses/:license_id/revoke",
-                post(revoke_license_handler),
-            )
-            .route(
-                "/api/v1/licenses/:license_id/reinstate",
-                post(reinstate_license_handler),
-            )
-            .route(
-                "/api/v1/licenses/:license_id/extend",
-                post(extend_license_handler),
-            )
-            .route(
-                "/api/v1/licenses/:license_id/usage",
-                patch(update_usage_handler),
-            )
-            .route(
-                "/api/v1/licenses/:license_id/blacklist",
-                post(blacklist_license_handler),
-            )
-            // Token management routes
-            .route("/api/v1/tokens", post(create_token_handler))
-            .route("/api/v1/tokens", get(list_tokens_handler))
-            .route("/api/v1/tokens/:token_id", get(get_token_handler))
-            .route("/api/v1/tokens/:token_id", delete(revoke_token_handler))
-            .layer(ip_whitelist_layer);
-
-        router.merge(admin_routes)
+        router.merge(admin_routes().layer(ip_whitelist_layer))
     };
 
     // Add Swagger UI routes if openapi feature is enabled
@@ -218,3 +226,255 @@ pub fn build_router(state: AppState) -> Router {
         .layer(middleware::from_fn(request_logging_middleware))
         .with_state(state)
 }
+
+/// All client routes, without rate limiting.
+fn client_routes() -> Router<AppState> {
+    Router::new()
+        .merge(bind_routes())
+        .merge(validate_routes())
+        .merge(heartbeat_routes())
+}
+
+/// Client routes with one rate limiter per endpoint class.
+#[cfg(feature = "rate-limiting")]
+fn rate_limited_client_routes(config: &RateLimitConfig) -> Router<AppState> {
+    if !config.enabled {
+        return client_routes();
+    }
+
+    Router::new()
+        .merge(bind_routes().layer(create_rate_limiter(config, RateLimitType::Bind)))
+        .merge(validate_routes().layer(create_rate_limiter(config, RateLimitType::Validate)))
+        .merge(heartbeat_routes().layer(create_rate_limiter(config, RateLimitType::Heartbeat)))
+}
+
+/// Bind, release, checkout, checkin and trial endpoints (`RateLimitType::Bind`).
+fn bind_routes() -> Router<AppState> {
+    Router::new()
+        // Legacy client endpoints (backwards compatibility)
+        .route("/activate", post(activate_license_handler))
+        .route("/deactivate", post(deactivate_license_handler))
+        .route("/api/v1/client/bind", post(bind_handler))
+        .route("/api/v1/client/release", post(release_handler))
+        .route("/api/v1/client/checkout", post(checkout_handler))
+        .route("/api/v1/client/checkin", post(checkin_handler))
+        .route("/api/v1/client/trial", post(start_trial_handler))
+}
+
+/// Validation endpoints (`RateLimitType::Validate`).
+fn validate_routes() -> Router<AppState> {
+    Router::new()
+        .route("/validate", post(validate_license_handler))
+        .route("/api/v1/client/validate", post(validate_handler))
+        .route(
+            "/api/v1/client/validate-or-bind",
+            post(validate_or_bind_handler),
+        )
+        .route(
+            "/api/v1/client/validate-feature",
+            post(validate_feature_handler),
+        )
+}
+
+/// Heartbeat and usage report endpoints (`RateLimitType::Heartbeat`).
+fn heartbeat_routes() -> Router<AppState> {
+    Router::new()
+        .route("/heartbeat", post(heartbeat_handler))
+        .route("/api/v1/client/heartbeat", post(client_heartbeat_handler))
+        .route("/api/v1/client/usage", post(report_usage_handler))
+}
+
+/// Admin and token routes, each tagged with the scope it requires.
+///
+/// Scopes are only enforced when the `jwt-auth` feature is enabled and the
+/// router is wrapped in an `AuthLayer`.
+#[cfg(feature = "admin-api")]
+fn admin_routes() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/api/v1/licenses",
+            scoped(post(create_license_handler), scopes::LICENSES_WRITE),
+        )
+        .route(
+            "/api/v1/licenses",
+            scoped(get(list_licenses_handler), scopes::LICENSES_READ),
+        )
+        .route(
+            "/api/v1/licenses/batch",
+            scoped(post(batch_create_license_handler), scopes::LICENSES_WRITE),
+        )
+        .route(
+            "/api/v1/licenses/bulk",
+            scoped(post(bulk_license_handler), scopes::LICENSES_WRITE),
+        )
+        .route(
+            "/api/v1/licenses/export",
+            scoped(get(export_licenses_handler), scopes::LICENSES_READ),
+        )
+        .route(
+            "/api/v1/licenses/import",
+            scoped(
+                post(import_licenses_handler).layer(DefaultBodyLimit::max(MAX_IMPORT_BYTES)),
+                scopes::LICENSES_WRITE,
+            ),
+        )
+        .route(
+            "/api/v1/licenses/offline-activation",
+            scoped(post(offline_activation_handler), scopes::LICENSES_WRITE),
+        )
+        .route(
+            "/api/v1/licenses/:license_id",
+            scoped(get(get_license_handler), scopes::LICENSES_READ),
+        )
+        .route(
+            "/api/v1/licenses/:license_id",
+            scoped(patch(update_license_handler), scopes::LICENSES_WRITE),
+        )
+        .route(
+            "/api/v1/licenses/:license_id/release",
+            scoped(post(admin_release_handler), scopes::LICENSES_WRITE),
+        )
+        .route(
+            "/api/v1/licenses/:license_id/devices",
+            scoped(get(list_license_devices_handler), scopes::LICENSES_READ),
+        )
+        .route(
+            "/api/v1/licenses/:license_id/devices/:hardware_id",
+            scoped(
+                delete(remove_license_device_handler),
+                scopes::LICENSES_WRITE,
+            ),
+        )
+        .route(
+            "/api/v1/licenses/:license_id/history",
+            scoped(get(license_history_handler), scopes::LICENSES_READ),
+        )
+        .route(
+            "/api/v1/devices/:hardware_id/history",
+            scoped(get(device_history_handler), scopes::LICENSES_READ),
+        )
+        .route(
+            "/api/v1/licenses/:license_id/revoke",
+            scoped(post(revoke_license_handler), scopes::LICENSES_DELETE),
+        )
+        .route(
+            "/api/v1/licenses/:license_id/reinstate",
+            scoped(post(reinstate_license_handler), scopes::LICENSES_WRITE),
+        )
+        .route(
+            "/api/v1/licenses/:license_id/extend",
+            scoped(post(extend_license_handler), scopes::LICENSES_WRITE),
+        )
+        .route(
+            "/api/v1/licenses/:license_id/usage",
+            scoped(get(list_usage_handler), scopes::LICENSES_READ),
+        )
+        .route(
+            "/api/v1/licenses/:license_id/usage",
+            scoped(patch(update_usage_handler), scopes::LICENSES_WRITE),
+        )
+        .route(
+            "/api/v1/licenses/:license_id/usage/:meter",
+            scoped(put(set_meter_handler), scopes::LICENSES_WRITE),
+        )
+        .route(
+            "/api/v1/licenses/:license_id/usage-periods",
+            scoped(get(list_usage_periods_handler), scopes::LICENSES_READ),
+        )
+        .route(
+            "/api/v1/licenses/:license_id/blacklist",
+            scoped(post(blacklist_license_handler), scopes::LICENSES_DELETE),
+        )
+        .route(
+            "/api/v1/licenses/:license_id/convert-trial",
+            scoped(post(convert_trial_handler), scopes::LICENSES_WRITE),
+        )
+        // Token management routes
+        .route(
+            "/api/v1/tokens",
+            scoped(post(create_token_handler), scopes::TOKENS_WRITE),
+        )
+        .route(
+            "/api/v1/tokens",
+            scoped(get(list_tokens_handler), scopes::TOKENS_READ),
+        )
+        .route(
+            "/api/v1/tokens/:token_id",
+            scoped(get(get_token_handler), scopes::TOKENS_READ),
+        )
+        .route(
+            "/api/v1/tokens/:token_id",
+            scoped(delete(revoke_token_handler), scopes::TOKENS_DELETE),
+        )
+        // Audit log
+        .route(
+            "/api/v1/audit",
+            scoped(get(list_audit_handler), scopes::AUDIT_READ),
+        )
+        // Webhooks
+        .route(
+            "/api/v1/webhooks",
+            scoped(post(create_webhook_handler), scopes::WEBHOOKS_WRITE),
+        )
+        .route(
+            "/api/v1/webhooks",
+            scoped(get(list_webhooks_handler), scopes::WEBHOOKS_READ),
+        )
+        .route(
+            "/api/v1/webhooks/:webhook_id",
+            scoped(get(get_webhook_handler), scopes::WEBHOOKS_READ),
+        )
+        .route(
+            "/api/v1/webhooks/:webhook_id",
+            scoped(patch(update_webhook_handler), scopes::WEBHOOKS_WRITE),
+        )
+        .route(
+            "/api/v1/webhooks/:webhook_id",
+            scoped(delete(delete_webhook_handler), scopes::WEBHOOKS_WRITE),
+        )
+        .route(
+            "/api/v1/webhooks/:webhook_id/deliveries",
+            scoped(get(list_webhook_deliveries_handler), scopes::WEBHOOKS_READ),
+        )
+        // Organizations
+        .route(
+            "/api/v1/orgs",
+            scoped(post(create_org_handler), scopes::ORGS_WRITE),
+        )
+        .route(
+            "/api/v1/orgs",
+            scoped(get(list_orgs_handler), scopes::ORGS_READ),
+        )
+        .route(
+            "/api/v1/orgs/:org_id",
+            scoped(get(get_org_handler), scopes::ORGS_READ),
+        )
+        .route(
+            "/api/v1/orgs/:org_id",
+            scoped(patch(update_org_handler), scopes::ORGS_WRITE),
+        )
+        .route(
+            "/api/v1/orgs/:org_id",
+            scoped(delete(delete_org_handler), scopes::ORGS_WRITE),
+        )
+        .route(
+            "/api/v1/orgs/:org_id/suspend",
+            scoped(post(suspend_org_handler), scopes::ORGS_WRITE),
+        )
+        .route(
+            "/api/v1/orgs/:org_id/reinstate",
+            scoped(post(reinstate_org_handler), scopes::ORGS_WRITE),
+        )
+}
+
+/// Require `scope` for a route when JWT auth is compiled in.
+#[cfg(all(feature = "admin-api", feature = "jwt-auth"))]
+fn scoped(route: MethodRouter<AppState>, scope: &'static str) -> MethodRouter<AppState> {
+    route.layer(RequireScopeLayer::new(scope))
+}
+
+/// Without `jwt-auth` there is no authenticated user to check scopes against.
+#[cfg(all(feature = "admin-api", not(feature = "jwt-auth")))]
+fn scoped(route: MethodRouter<AppState>, _scope: &'static str) -> MethodRouter<AppState> {
+    route
+}
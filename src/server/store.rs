@@ -0,0 +1,504 @@
+//! Storage traits behind the server.
+//!
+//! Handlers, background jobs and the webhook dispatcher only talk to storage
+//! through [`LicenseStore`], so the backend is pluggable:
+//!
+//! - [`Database`](crate::server::database::Database) implements it for
+//!   SQLite and PostgreSQL
+//! - [`MemoryStore`](crate::server::memory_store::MemoryStore) keeps
+//!   everything in process, for tests and embedding
+//!
+//! `LicenseStore` builds on [`OrgStore`], [`TokenStore`], [`AuditStore`],
+//! [`WebhookStore`], [`TrialStore`] and [`UsageStore`], so a custom backend
+//! implements all seven. Methods of the supertraits can be called directly
+//! on a `dyn LicenseStore`.
+//!
+//! # Usage
+//!
+//! ```rust,ignore
+//! use std::sync::Arc;
+//! use talos::server::{AppState, LicenseStore, MemoryStore};
+//!
+//! let db: Arc<dyn LicenseStore> = Arc::new(MemoryStore::new());
+//! let state = AppState { db, /* ... */ };
+//! ```
+
+use axum::async_trait;
+use chrono::{Duration, NaiveDateTime};
+
+use crate::errors::LicenseResult;
+use crate::server::audit::{AuditEntry, AuditFilter};
+use crate::server::database::{
+    BindingAction, BindingHistoryFilter, LeaseCheckout, License, LicenseBindingHistory,
+    LicenseDevice, LicenseLease, PerformedBy, SeatClaim,
+};
+use crate::server::license_query::{LicenseCursor, LicenseFilter, LicenseSort};
+use crate::server::orgs::{OrgStats, Organization};
+use crate::server::tokens::ApiToken;
+use crate::server::trials::TrialRegistration;
+use crate::server::usage::{RecordedUsage, UsageMeter, UsagePeriod, UsageReport};
+use crate::server::webhooks::{Webhook, WebhookDelivery};
+
+/// Storage for licenses, seats and leases.
+///
+/// Implementations must make [`claim_license_seat`](Self::claim_license_seat)
+/// atomic: concurrent claims must never hand out more seats than the license
+/// allows.
+#[async_trait]
+pub trait LicenseStore:
+    OrgStore + TokenStore + AuditStore + WebhookStore + TrialStore + UsageStore + Send + Sync
+{
+    /// Short name of the backend, reported by the health endpoint
+    /// (e.g. `"sqlite"`, `"postgres"`, `"memory"`).
+    fn backend(&self) -> &'static str;
+
+    /// Check that the store is reachable.
+    ///
+    /// Returns `true` if the store is reachable, `false` otherwise.
+    async fn health_check(&self) -> bool;
+
+    /// Insert a new license or update an existing one.
+    ///
+    /// This acts like an "upsert" keyed on `license_id`:
+    /// - if the license doesn't exist, it is created
+    /// - if it exists, the fields are updated
+    async fn insert_license(&self, license: License) -> LicenseResult<()>;
+
+    /// Insert or update several licenses in one transaction.
+    ///
+    /// Either every license is written or none is. With `free_seats`, every
+    /// seat and lease of these licenses is removed as well and their binding
+    /// fields are cleared.
+    async fn insert_licenses(&self, licenses: &[License], free_seats: bool) -> LicenseResult<()>;
+
+    /// Fetch a license by its ID.
+    ///
+    /// Returns:
+    /// - `Ok(Some(License))` if found
+    /// - `Ok(None)` if not found
+    /// - `Err(LicenseError::ServerError)` on storage failure
+    async fn get_license(&self, license_id: &str) -> LicenseResult<Option<License>>;
+
+    /// Update the `last_heartbeat` timestamp for a license/client pair.
+    ///
+    /// Returns:
+    /// - `Ok(true)` if a license was updated
+    /// - `Ok(false)` if no matching license was found
+    /// - `Err(LicenseError::ServerError)` on storage failure
+    async fn update_last_heartbeat(&self, license_id: &str, client_id: &str)
+        -> LicenseResult<bool>;
+
+    /// Fetch a license by its human-readable license key.
+    ///
+    /// Returns:
+    /// - `Ok(Some(License))` if found
+    /// - `Ok(None)` if not found
+    /// - `Err(LicenseError::ServerError)` on storage failure
+    async fn get_license_by_key(&self, license_key: &str) -> LicenseResult<Option<License>>;
+
+    /// Check if a license key is already in use.
+    async fn license_key_exists(&self, license_key: &str) -> LicenseResult<bool>;
+
+    /// List licenses by organization ID.
+    async fn list_licenses_by_org(&self, org_id: &str) -> LicenseResult<Vec<License>>;
+
+    /// List licenses matching `filter` in `sort` order.
+    ///
+    /// Starts right after `after` when given (which must have been created
+    /// for `sort`), then skips `offset` matches. Returns at most `limit`
+    /// licenses and the total number of licenses matching `filter`.
+    async fn list_licenses(
+        &self,
+        filter: &LicenseFilter,
+        sort: LicenseSort,
+        after: Option<&LicenseCursor>,
+        limit: u32,
+        offset: u32,
+    ) -> LicenseResult<(Vec<License>, u64)>;
+
+    /// Update license status.
+    async fn update_license_status(&self, license_id: &str, status: &str) -> LicenseResult<bool>;
+
+    /// Point a license's binding fields at a device.
+    ///
+    /// This overwrites any existing binding unconditionally and does not take
+    /// a seat; client binds go through [`claim_license_seat`](Self::claim_license_seat).
+    async fn bind_license(
+        &self,
+        license_id: &str,
+        hardware_id: &str,
+        device_name: Option<&str>,
+        device_info: Option<&str>,
+    ) -> LicenseResult<bool>;
+
+    /// Clear a license's binding fields.
+    async fn release_license(&self, license_id: &str) -> LicenseResult<bool>;
+
+    /// Record a binding action in the binding history.
+    #[allow(clippy::too_many_arguments)]
+    async fn record_binding_history(
+        &self,
+        license_id: &str,
+        action: BindingAction,
+        hardware_id: Option<&str>,
+        device_name: Option<&str>,
+        device_info: Option<&str>,
+        performed_by: PerformedBy,
+        reason: Option<&str>,
+    ) -> LicenseResult<()>;
+
+    /// List binding history records matching `filter`, newest first.
+    ///
+    /// Returns one page of records and the total number of matches.
+    async fn list_binding_history(
+        &self,
+        filter: &BindingHistoryFilter,
+        limit: u32,
+        offset: u32,
+    ) -> LicenseResult<(Vec<LicenseBindingHistory>, u64)>;
+
+    /// Update last_seen_at timestamp for a license.
+    async fn update_last_seen(&self, license_id: &str) -> LicenseResult<bool>;
+
+    /// List the devices holding a seat on a license, oldest binding first.
+    async fn list_license_devices(&self, license_id: &str) -> LicenseResult<Vec<LicenseDevice>>;
+
+    /// Fetch the seat held by a device on a license, if any.
+    async fn get_license_device(
+        &self,
+        license_id: &str,
+        hardware_id: &str,
+    ) -> LicenseResult<Option<LicenseDevice>>;
+
+    /// Give a device a seat on a license, regardless of the seat limit.
+    ///
+    /// Also points the license's binding fields at this device. Returns
+    /// `false` if the device already held a seat.
+    async fn add_license_device(
+        &self,
+        license_id: &str,
+        hardware_id: &str,
+        device_name: Option<&str>,
+        device_info: Option<&str>,
+    ) -> LicenseResult<bool>;
+
+    /// Claim a seat on a license for a device, respecting `max_devices`.
+    ///
+    /// The seat count must be checked and the seat taken atomically. The
+    /// limit is read from the store; `license` only identifies the license.
+    /// A claimed seat also points the license's binding fields at the device.
+    async fn claim_license_seat(
+        &self,
+        license: &License,
+        hardware_id: &str,
+        device_name: Option<&str>,
+        device_info: Option<&str>,
+    ) -> LicenseResult<SeatClaim>;
+
+    /// Free the seat held by a device on a license.
+    ///
+    /// The license's binding fields move to the most recently bound remaining
+    /// device, or are cleared if no seats are left.
+    async fn remove_license_device(
+        &self,
+        license_id: &str,
+        hardware_id: &str,
+    ) -> LicenseResult<bool>;
+
+    /// Free every seat and lease on a license and clear its binding fields.
+    ///
+    /// Returns the number of seats that were freed.
+    async fn remove_all_license_devices(&self, license_id: &str) -> LicenseResult<u64>;
+
+    /// Update last_seen_at for a device's seat and for its license.
+    async fn touch_license_device(
+        &self,
+        license_id: &str,
+        hardware_id: &str,
+    ) -> LicenseResult<bool>;
+
+    /// List the leases on a license that have not expired by `now`.
+    async fn list_active_leases(
+        &self,
+        license_id: &str,
+        now: NaiveDateTime,
+    ) -> LicenseResult<Vec<LicenseLease>>;
+
+    /// Check out a floating lease for a device, respecting `max_concurrent`.
+    ///
+    /// A device that already holds an active lease has it renewed. Expired
+    /// leases do not count against the limit and are replaced on checkout.
+    async fn checkout_license_lease(
+        &self,
+        license: &License,
+        hardware_id: &str,
+        ttl: Duration,
+    ) -> LicenseResult<LeaseCheckout>;
+
+    /// Push out the expiry of a device's active lease by `ttl` from now.
+    ///
+    /// Returns the new expiry, or `None` if the device holds no active lease.
+    /// An expired lease has already returned to the pool and is not revived.
+    async fn renew_license_lease(
+        &self,
+        license_id: &str,
+        hardware_id: &str,
+        ttl: Duration,
+    ) -> LicenseResult<Option<NaiveDateTime>>;
+
+    /// Return a device's lease to the pool.
+    ///
+    /// Returns `false` if the device held no lease.
+    async fn checkin_license_lease(
+        &self,
+        license_id: &str,
+        hardware_id: &str,
+    ) -> LicenseResult<bool>;
+
+    /// Delete leases that expired before `now`.
+    ///
+    /// Expired leases already count as free; this only keeps storage small.
+    async fn delete_expired_leases(&self, now: NaiveDateTime) -> LicenseResult<u64>;
+
+    /// Store the signature of the most recently issued signed license document.
+    async fn update_license_signature(
+        &self,
+        license_id: &str,
+        signature: &str,
+    ) -> LicenseResult<bool>;
+
+    /// Get licenses with expired grace periods (suspended licenses past their grace_period_ends_at).
+    async fn get_expired_grace_period_licenses(
+        &self,
+        now: NaiveDateTime,
+    ) -> LicenseResult<Vec<License>>;
+
+    /// Get expired licenses (active licenses past their expires_at).
+    async fn get_expired_licenses(&self, now: NaiveDateTime) -> LicenseResult<Vec<License>>;
+
+    /// Get seats held by stale devices (not seen since threshold).
+    async fn get_stale_devices(
+        &self,
+        threshold: NaiveDateTime,
+    ) -> LicenseResult<Vec<LicenseDevice>>;
+}
+
+/// Storage for organizations.
+///
+/// Licenses refer to an organization through their `org_id` and keep a copy
+/// of its name in `org_name`.
+#[async_trait]
+pub trait OrgStore: Send + Sync {
+    /// Insert a new organization.
+    async fn insert_org(&self, org: &Organization) -> LicenseResult<()>;
+
+    /// Save every field of an organization except `id` and `created_at`.
+    ///
+    /// Also copies the name to the `org_name` of the organization's licenses.
+    async fn update_org(&self, org: &Organization) -> LicenseResult<()>;
+
+    /// Get an organization by ID.
+    async fn get_org(&self, org_id: &str) -> LicenseResult<Option<Organization>>;
+
+    /// List organizations, oldest first, optionally by status.
+    ///
+    /// Returns one page of organizations and the total number of matches.
+    async fn list_orgs(
+        &self,
+        status: Option<&str>,
+        limit: u32,
+        offset: u32,
+    ) -> LicenseResult<(Vec<Organization>, u64)>;
+
+    /// Delete an organization. Its licenses are left untouched.
+    ///
+    /// Returns `false` if no organization had this ID.
+    async fn delete_org(&self, org_id: &str) -> LicenseResult<bool>;
+
+    /// License, device and usage totals for an organization.
+    async fn org_stats(&self, org_id: &str) -> LicenseResult<OrgStats>;
+}
+
+/// Storage for admin API tokens.
+///
+/// Only the SHA-256 hash of a token is stored; see
+/// [`crate::server::tokens`] for the token format.
+#[async_trait]
+pub trait TokenStore: Send + Sync {
+    /// Create a new API token.
+    ///
+    /// Returns the token metadata and the raw token value (only returned once).
+    async fn create_api_token(
+        &self,
+        name: &str,
+        scopes: &[&str],
+        expires_at: Option<NaiveDateTime>,
+        created_by: Option<&str>,
+    ) -> LicenseResult<(ApiToken, String)>;
+
+    /// Look up a raw token by its hash.
+    ///
+    /// Revoked and expired tokens are returned too; callers check
+    /// [`ApiToken::is_valid`]. Updates `last_used_at` for valid tokens.
+    async fn validate_api_token(&self, raw_token: &str) -> LicenseResult<Option<ApiToken>>;
+
+    /// List all API tokens, newest first.
+    async fn list_api_tokens(&self) -> LicenseResult<Vec<ApiToken>>;
+
+    /// Get a token by ID.
+    async fn get_api_token(&self, token_id: &str) -> LicenseResult<Option<ApiToken>>;
+
+    /// Revoke a token by ID.
+    ///
+    /// Returns `false` if no unrevoked token had this ID.
+    async fn revoke_api_token(&self, token_id: &str) -> LicenseResult<bool>;
+
+    /// Check if any API tokens exist.
+    async fn has_api_tokens(&self) -> LicenseResult<bool>;
+}
+
+/// Storage for the audit log.
+#[async_trait]
+pub trait AuditStore: Send + Sync {
+    /// Insert an entry into the audit log.
+    async fn insert_audit_entry(&self, entry: &AuditEntry) -> LicenseResult<()>;
+
+    /// List audit entries matching `filter`, newest first.
+    ///
+    /// Returns one page of entries and the total number of matches.
+    async fn list_audit_entries(
+        &self,
+        filter: &AuditFilter,
+        limit: u32,
+        offset: u32,
+    ) -> LicenseResult<(Vec<AuditEntry>, u64)>;
+}
+
+/// Storage for webhooks and their delivery log.
+#[async_trait]
+pub trait WebhookStore: Send + Sync {
+    /// Insert a new webhook.
+    async fn insert_webhook(&self, webhook: &Webhook) -> LicenseResult<()>;
+
+    /// Update the URL, events, description and active flag of a webhook.
+    async fn update_webhook(&self, webhook: &Webhook) -> LicenseResult<()>;
+
+    /// Get a webhook by ID.
+    async fn get_webhook(&self, webhook_id: &str) -> LicenseResult<Option<Webhook>>;
+
+    /// List all webhooks, oldest first.
+    async fn list_webhooks(&self) -> LicenseResult<Vec<Webhook>>;
+
+    /// Delete a webhook and its delivery log.
+    ///
+    /// Returns `false` if no webhook had this ID.
+    async fn delete_webhook(&self, webhook_id: &str) -> LicenseResult<bool>;
+
+    /// Queue a delivery.
+    async fn insert_webhook_delivery(&self, delivery: &WebhookDelivery) -> LicenseResult<()>;
+
+    /// Save the outcome of a delivery attempt.
+    async fn update_webhook_delivery(&self, delivery: &WebhookDelivery) -> LicenseResult<()>;
+
+    /// Pending deliveries whose next attempt is due at `now`, oldest first.
+    async fn due_webhook_deliveries(
+        &self,
+        now: NaiveDateTime,
+        limit: u32,
+    ) -> LicenseResult<Vec<WebhookDelivery>>;
+
+    /// List deliveries for a webhook, newest first, optionally by status.
+    ///
+    /// Returns one page of deliveries and the total number of matches.
+    async fn list_webhook_deliveries(
+        &self,
+        webhook_id: &str,
+        status: Option<&str>,
+        limit: u32,
+        offset: u32,
+    ) -> LicenseResult<(Vec<WebhookDelivery>, u64)>;
+}
+
+/// Storage for the registry of self-service trials.
+///
+/// A machine gets at most one trial per product; the registry keeps the row
+/// after the trial expires or is converted so the machine can't start over.
+#[async_trait]
+pub trait TrialStore: Send + Sync {
+    /// Register a trial and insert its license, atomically.
+    ///
+    /// Returns `false`, writing nothing, if the machine already had a trial
+    /// for this product.
+    async fn insert_trial(
+        &self,
+        trial: &TrialRegistration,
+        license: &License,
+    ) -> LicenseResult<bool>;
+
+    /// Get the trial a license was issued for.
+    async fn get_trial_by_license(
+        &self,
+        license_id: &str,
+    ) -> LicenseResult<Option<TrialRegistration>>;
+
+    /// Convert a trial into a paid license, atomically.
+    ///
+    /// Marks the trial behind `license` as converted and saves the license.
+    /// Returns `false`, writing nothing, if the license was not a trial or
+    /// was already converted.
+    async fn convert_trial(
+        &self,
+        license: &License,
+        converted_at: NaiveDateTime,
+    ) -> LicenseResult<bool>;
+}
+
+/// Storage for named usage meters.
+///
+/// Each license has one counter per meter. The `bandwidth` meter is also
+/// written to the license's legacy `bandwidth_used_bytes` and
+/// `bandwidth_limit_bytes` fields.
+#[async_trait]
+pub trait UsageStore: Send + Sync {
+    /// Add a usage report to its meter, atomically.
+    ///
+    /// A report whose `report_id` was already applied to the license is not
+    /// counted again; the meter is returned as it is.
+    async fn record_usage(&self, report: &UsageReport) -> LicenseResult<RecordedUsage>;
+
+    /// Overwrite a meter's usage and per-license limit.
+    async fn set_usage_meter(&self, meter: &UsageMeter) -> LicenseResult<()>;
+
+    /// Get the meters of a license, ordered by name.
+    async fn list_usage_meters(&self, license_id: &str) -> LicenseResult<Vec<UsageMeter>>;
+
+    /// Set whether a license is over any of its quotas.
+    ///
+    /// Returns `false` if the license does not exist.
+    async fn set_quota_exceeded(&self, license_id: &str, exceeded: bool) -> LicenseResult<bool>;
+
+    /// Close the current usage period of a license, atomically.
+    ///
+    /// The usage of every meter that has any is kept as a [`UsagePeriod`]
+    /// running from the license's `usage_period_start` (or `issued_at`) to
+    /// `period_end`. That usage is then subtracted from the meters (zeroing
+    /// them unless usage is reported meanwhile), as is the billed bandwidth
+    /// from `bandwidth_used_bytes`. `quota_exceeded` is recomputed from the
+    /// remaining usage against the tier's limits and `period_end` becomes the
+    /// start of the next period.
+    ///
+    /// Returns `None`, writing nothing, if the license does not exist or
+    /// `period_end` is not after the start of its current period.
+    async fn close_usage_period(
+        &self,
+        license_id: &str,
+        period_end: NaiveDateTime,
+    ) -> LicenseResult<Option<Vec<UsagePeriod>>>;
+
+    /// Get the closed usage periods of a license, newest first.
+    async fn list_usage_periods(&self, license_id: &str) -> LicenseResult<Vec<UsagePeriod>>;
+
+    /// Get the licenses that may have a billing cycle: those that set their
+    /// own `billing_cycle`, and those in one of `tiers`.
+    async fn list_billed_licenses(&self, tiers: &[String]) -> LicenseResult<Vec<License>>;
+}
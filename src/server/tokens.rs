@@ -21,8 +21,9 @@
 //! ```
 
 use axum::{
-    extract::{Path, State},
-    http::StatusCode,
+    async_trait,
+    extract::{FromRequestParts, Path, State},
+    http::{request::Parts, StatusCode},
     response::IntoResponse,
     Json,
 };
@@ -37,8 +38,70 @@ use uuid::Uuid;
 use utoipa::ToSchema;
 
 use crate::errors::{LicenseError, LicenseResult};
+use crate::server::api_error::ApiError;
+use crate::server::audit::{snapshot, AuditAction, AuditContext, AuditTarget};
 use crate::server::database::Database;
 use crate::server::handlers::AppState;
+use crate::server::store::TokenStore;
+
+#[cfg(feature = "jwt-auth")]
+use crate::server::auth::{AuthError, AuthState, AuthenticatedUser};
+
+/// Prefix of raw API tokens, used to tell them apart from JWTs.
+pub const API_TOKEN_PREFIX: &str = "talos_";
+
+/// Scope names required by the admin API routes.
+pub mod scopes {
+    /// Read license information
+    pub const LICENSES_READ: &str = "licenses:read";
+    /// Create and modify licenses
+    pub const LICENSES_WRITE: &str = "licenses:write";
+    /// Revoke and blacklist licenses
+    pub const LICENSES_DELETE: &str = "licenses:delete";
+    /// Read API token metadata
+    pub const TOKENS_READ: &str = "tokens:read";
+    /// Create API tokens
+    pub const TOKENS_WRITE: &str = "tokens:write";
+    /// Revoke API tokens
+    pub const TOKENS_DELETE: &str = "tokens:delete";
+    /// Read the audit log
+    pub const AUDIT_READ: &str = "audit:read";
+    /// Read webhooks and their delivery logs
+    pub const WEBHOOKS_READ: &str = "webhooks:read";
+    /// Create, update and delete webhooks
+    pub const WEBHOOKS_WRITE: &str = "webhooks:write";
+    /// Read organizations and their stats
+    pub const ORGS_READ: &str = "orgs:read";
+    /// Create, update, suspend and delete organizations
+    pub const ORGS_WRITE: &str = "orgs:write";
+
+    /// Every scope a token can be granted, besides the wildcards.
+    pub const ALL: &[&str] = &[
+        LICENSES_READ,
+        LICENSES_WRITE,
+        LICENSES_DELETE,
+        TOKENS_READ,
+        TOKENS_WRITE,
+        TOKENS_DELETE,
+        AUDIT_READ,
+        WEBHOOKS_READ,
+        WEBHOOKS_WRITE,
+        ORGS_READ,
+        ORGS_WRITE,
+    ];
+
+    /// Whether a scope is one of [`ALL`], `*`, or a category wildcard such
+    /// as `licenses:*`.
+    pub fn is_known(scope: &str) -> bool {
+        if scope == "*" || ALL.contains(&scope) {
+            return true;
+        }
+        scope.strip_suffix(":*").is_some_and(|category| {
+            ALL.iter()
+                .any(|known| known.split(':').next() == Some(category))
+        })
+    }
+}
 
 /// API Token stored in the database.
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
@@ -174,23 +237,58 @@ pub struct CreateTokenRequest {
 }
 
 /// Generate a secure random token.
-fn generate_raw_token() -> String {
+pub(crate) fn generate_raw_token() -> String {
     // Generate a UUID-based token with prefix for easy identification
-    format!("talos_{}", Uuid::new_v4().to_string().replace('-', ""))
+    format!(
+        "{API_TOKEN_PREFIX}{}",
+        Uuid::new_v4().to_string().replace('-', "")
+    )
 }
 
 /// Hash a token using SHA-256.
-fn hash_token(raw_token: &str) -> String {
+pub(crate) fn hash_token(raw_token: &str) -> String {
     let mut hasher = Sha256::new();
     hasher.update(raw_token.as_bytes());
     format!("{:x}", hasher.finalize())
 }
 
 impl Database {
-    /// Create a new API token.
-    ///
-    /// Returns the token metadata and the raw token value (only returned once).
-    pub async fn create_api_token(
+    /// Update the last_used_at timestamp for a token.
+    async fn update_token_last_used(&self, token_id: &str) -> LicenseResult<()> {
+        let now = Utc::now().naive_utc();
+
+        match self {
+            #[cfg(feature = "sqlite")]
+            Database::SQLite(pool) => {
+                query("UPDATE api_tokens SET last_used_at = ? WHERE id = ?")
+                    .bind(now)
+                    .bind(token_id)
+                    .execute(pool)
+                    .await
+                    .map_err(|e| {
+                        LicenseError::ServerError(format!("update last_used failed: {e}"))
+                    })?;
+            }
+            #[cfg(feature = "postgres")]
+            Database::Postgres(pool) => {
+                query("UPDATE api_tokens SET last_used_at = $1 WHERE id = $2")
+                    .bind(now)
+                    .bind(token_id)
+                    .execute(pool)
+                    .await
+                    .map_err(|e| {
+                        LicenseError::ServerError(format!("update last_used failed: {e}"))
+                    })?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl TokenStore for Database {
+    async fn create_api_token(
         &self,
         name: &str,
         scopes: &[&str],
@@ -256,10 +354,7 @@ impl Database {
         Ok((token, raw_token))
     }
 
-    /// Validate a raw token and return the token if valid.
-    ///
-    /// Also updates `last_used_at` timestamp.
-    pub async fn validate_api_token(&self, raw_token: &str) -> LicenseResult<Option<ApiToken>> {
+    async fn validate_api_token(&self, raw_token: &str) -> LicenseResult<Option<ApiToken>> {
         let token_hash = hash_token(raw_token);
 
         let token: Option<ApiToken> = match self {
@@ -295,40 +390,7 @@ impl Database {
         Ok(token)
     }
 
-    /// Update the last_used_at timestamp for a token.
-    async fn update_token_last_used(&self, token_id: &str) -> LicenseResult<()> {
-        let now = Utc::now().naive_utc();
-
-        match self {
-            #[cfg(feature = "sqlite")]
-            Database::SQLite(pool) => {
-                query("UPDATE api_tokens SET last_used_at = ? WHERE id = ?")
-                    .bind(now)
-                    .bind(token_id)
-                    .execute(pool)
-                    .await
-                    .map_err(|e| {
-                        LicenseError::ServerError(format!("update last_used failed: {e}"))
-                    })?;
-            }
-            #[cfg(feature = "postgres")]
-            Database::Postgres(pool) => {
-                query("UPDATE api_tokens SET last_used_at = $1 WHERE id = $2")
-                    .bind(now)
-                    .bind(token_id)
-                    .execute(pool)
-                    .await
-                    .map_err(|e| {
-                        LicenseError::ServerError(format!("update last_used failed: {e}"))
-                    })?;
-            }
-        }
-
-        Ok(())
-    }
-
-    /// List all API tokens (metadata only, no hashes).
-    pub async fn list_api_tokens(&self) -> LicenseResult<Vec<ApiToken>> {
+    async fn list_api_tokens(&self) -> LicenseResult<Vec<ApiToken>> {
         match self {
             #[cfg(feature = "sqlite")]
             Database::SQLite(pool) => sqlx::query_as::<_, ApiToken>(
@@ -351,8 +413,7 @@ impl Database {
         }
     }
 
-    /// Get a token by ID.
-    pub async fn get_api_token(&self, token_id: &str) -> LicenseResult<Option<ApiToken>> {
+    async fn get_api_token(&self, token_id: &str) -> LicenseResult<Option<ApiToken>> {
         match self {
             #[cfg(feature = "sqlite")]
             Database::SQLite(pool) => sqlx::query_as::<_, ApiToken>(
@@ -377,8 +438,7 @@ impl Database {
         }
     }
 
-    /// Revoke a token by ID.
-    pub async fn revoke_api_token(&self, token_id: &str) -> LicenseResult<bool> {
+    async fn revoke_api_token(&self, token_id: &str) -> LicenseResult<bool> {
         let now = Utc::now().naive_utc();
 
         let rows_affected = match self {
@@ -411,8 +471,7 @@ impl Database {
         Ok(rows_affected > 0)
     }
 
-    /// Check if any API tokens exist in the database.
-    pub async fn has_api_tokens(&self) -> LicenseResult<bool> {
+    async fn has_api_tokens(&self) -> LicenseResult<bool> {
         match self {
             #[cfg(feature = "sqlite")]
             Database::SQLite(pool) => {
@@ -477,10 +536,75 @@ impl TokenErrorResponse {
     }
 }
 
+/// Scopes of the caller, for actions that need more than the route's scope.
+pub struct CallerScopes {
+    #[cfg(feature = "jwt-auth")]
+    enabled: bool,
+    #[cfg(feature = "jwt-auth")]
+    user: Option<AuthenticatedUser>,
+}
+
+#[async_trait]
+impl<S> FromRequestParts<S> for CallerScopes
+where
+    S: Send + Sync,
+{
+    type Rejection = std::convert::Infallible;
+
+    #[cfg_attr(not(feature = "jwt-auth"), allow(unused_variables))]
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        Ok(Self {
+            #[cfg(feature = "jwt-auth")]
+            enabled: parts
+                .extensions
+                .get::<AuthState>()
+                .is_some_and(|state| state.enabled),
+            #[cfg(feature = "jwt-auth")]
+            user: parts.extensions.get::<AuthenticatedUser>().cloned(),
+        })
+    }
+}
+
+impl CallerScopes {
+    /// Check a scope the way `RequireScopeLayer` does.
+    #[cfg_attr(not(feature = "jwt-auth"), allow(unused_variables))]
+    pub fn require(&self, scope: &str) -> Result<(), ApiError> {
+        #[cfg(feature = "jwt-auth")]
+        if self.enabled {
+            let user = self.user.as_ref().ok_or(AuthError::MissingToken)?;
+            user.require_scope(scope)?;
+        }
+        Ok(())
+    }
+
+    /// Whether the caller holds a scope. Always true when auth is disabled.
+    ///
+    /// Wildcards are matched like any other scope, so only a caller with
+    /// `*` holds `*`, and only one with `*` or `licenses:*` holds `licenses:*`.
+    pub fn holds(&self, scope: &str) -> bool {
+        self.require(scope).is_ok()
+    }
+
+    /// Subject of the authenticated caller (JWT `sub` or API token name).
+    pub fn subject(&self) -> Option<&str> {
+        #[cfg(feature = "jwt-auth")]
+        {
+            self.user.as_ref().map(|user| user.subject.as_str())
+        }
+        #[cfg(not(feature = "jwt-auth"))]
+        {
+            None
+        }
+    }
+}
+
 /// POST /api/v1/tokens - Create a new API token.
 ///
 /// Request body: `CreateTokenRequest`
 /// Response: `CreateTokenResponse` (includes raw token, only shown once)
+///
+/// Callers can only grant scopes they hold themselves, so a token with
+/// `tokens:write` alone can't mint a more powerful token.
 #[cfg_attr(feature = "openapi", utoipa::path(
     post,
     path = "/api/v1/tokens",
@@ -488,13 +612,16 @@ impl TokenErrorResponse {
     request_body = CreateTokenRequest,
     responses(
         (status = 201, description = "Token created", body = CreateTokenResponse),
-        (status = 400, description = "Invalid request", body = TokenErrorResponse),
+        (status = 400, description = "Invalid request or unknown scope", body = TokenErrorResponse),
+        (status = 403, description = "Requested a scope the caller does not hold", body = TokenErrorResponse),
         (status = 500, description = "Server error", body = TokenErrorResponse),
     ),
     security(("bearer_auth" = []))
 ))]
 pub async fn create_token_handler(
     State(state): State<AppState>,
+    audit: AuditContext,
+    caller: CallerScopes,
     Json(req): Json<CreateTokenRequest>,
 ) -> impl IntoResponse {
     // Validate request
@@ -520,6 +647,28 @@ pub async fn create_token_handler(
             .into_response();
     }
 
+    if let Some(unknown) = req.scopes.iter().find(|s| !scopes::is_known(s)) {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(serde_json::json!(TokenErrorResponse::new(
+                format!("Unknown scope: {unknown}"),
+                "INVALID_SCOPES"
+            ))),
+        )
+            .into_response();
+    }
+
+    if let Some(missing) = req.scopes.iter().find(|s| !caller.holds(s)) {
+        return (
+            StatusCode::FORBIDDEN,
+            Json(serde_json::json!(TokenErrorResponse::new(
+                format!("Cannot grant scope {missing} without holding it"),
+                "INSUFFICIENT_SCOPE"
+            ))),
+        )
+            .into_response();
+    }
+
     // Parse optional expiration
     let expires_at = match &req.expires_at {
         Some(exp_str) => match NaiveDateTime::parse_from_str(exp_str, "%Y-%m-%dT%H:%M:%SZ") {
@@ -547,12 +696,23 @@ pub async fn create_token_handler(
     // Create the token
     match state
         .db
-        .create_api_token(&req.name, &scope_refs, expires_at, None)
+        .create_api_token(&req.name, &scope_refs, expires_at, caller.subject())
         .await
     {
         Ok((token, raw_token)) => {
+            let metadata = TokenMetadata::from(token);
+            audit
+                .record(
+                    &*state.db,
+                    AuditAction::TokenCreated,
+                    AuditTarget::Token(metadata.id.clone()),
+                    None,
+                    snapshot(&metadata),
+                )
+                .await;
+
             let response = CreateTokenResponse {
-                token: TokenMetadata::from(token),
+                token: metadata,
                 raw_token,
             };
             (StatusCode::CREATED, Json(serde_json::json!(response))).into_response()
@@ -666,10 +826,36 @@ pub async fn get_token_handler(
 ))]
 pub async fn revoke_token_handler(
     State(state): State<AppState>,
+    audit: AuditContext,
     Path(token_id): Path<String>,
 ) -> impl IntoResponse {
+    let before = state
+        .db
+        .get_api_token(&token_id)
+        .await
+        .ok()
+        .flatten()
+        .map(TokenMetadata::from);
+
     match state.db.revoke_api_token(&token_id).await {
         Ok(true) => {
+            let after = state
+                .db
+                .get_api_token(&token_id)
+                .await
+                .ok()
+                .flatten()
+                .map(TokenMetadata::from);
+            audit
+                .record(
+                    &*state.db,
+                    AuditAction::TokenRevoked,
+                    AuditTarget::Token(token_id),
+                    before.as_ref().and_then(snapshot),
+                    after.as_ref().and_then(snapshot),
+                )
+                .await;
+
             let response = RevokeTokenResponse {
                 success: true,
                 message: "Token revoked successfully".to_string(),
@@ -827,4 +1013,15 @@ mod tests {
         // UUID without dashes = 32 chars, plus "talos_" = 38 chars
         assert_eq!(token.len(), 38);
     }
+
+    #[test]
+    fn known_scopes() {
+        assert!(scopes::is_known("*"));
+        assert!(scopes::is_known("licenses:read"));
+        assert!(scopes::is_known("orgs:*"));
+        assert!(!scopes::is_known("licenses:admin"));
+        assert!(!scopes::is_known("billing:*"));
+        assert!(!scopes::is_known(":*"));
+        assert!(!scopes::is_known(""));
+    }
 }
@@ -0,0 +1,678 @@
+//! Self-service trial licenses.
+//!
+//! With `[trials] enabled = true`, a client can ask for a time-limited trial
+//! license without an admin creating a key first. The trial gets the
+//! configured tier and duration and is bound to the requesting machine right
+//! away. The `trial_registrations` table remembers every machine that started
+//! a trial, so a second request from the same `hardware_id` is refused even
+//! after the first trial expired. When `products` is set, the registry is
+//! keyed on machine and product instead, allowing one trial per product.
+//!
+//! An admin can convert a trial into a paid license. Conversion updates the
+//! tier, features, expiry and organization in place, so the license key and
+//! the device binding stay the same.
+//!
+//! # Endpoints
+//!
+//! - `POST /api/v1/client/trial` - Start a trial on this machine
+//! - `POST /api/v1/licenses/{license_id}/convert-trial` - Convert a trial into a paid license
+
+use axum::async_trait;
+use axum::{extract::State, http::StatusCode, Json};
+use chrono::{Duration, NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+#[cfg(feature = "openapi")]
+use utoipa::ToSchema;
+
+use crate::config::{get_config, TrialConfig};
+use crate::errors::{LicenseError, LicenseResult};
+use crate::license_key::{generate_unique_license_key, LicenseKeyConfig};
+use crate::server::client_api::{
+    bound_details, issue_signed_license, offline_valid_until, ClientError, ClientErrorCode,
+};
+use crate::server::database::{BindingAction, Database, License, PerformedBy, SeatClaim};
+use crate::server::handlers::AppState;
+use crate::server::logging::{log_license_binding_event, log_license_event, LicenseEvent};
+use crate::server::store::TrialStore;
+use crate::server::validation::validate_not_empty;
+use crate::server::webhooks;
+use crate::signing::SignedLicense;
+use crate::tiers::get_tier_features;
+
+#[cfg(feature = "postgres")]
+use crate::server::database::pg_upsert_license;
+#[cfg(feature = "sqlite")]
+use crate::server::database::sqlite_upsert_license;
+
+#[cfg(feature = "admin-api")]
+use axum::extract::Path;
+
+#[cfg(feature = "admin-api")]
+use crate::server::admin::{
+    parse_datetime, resolve_license_defaults, resolve_max_devices, AdminError, LicenseResponse,
+};
+#[cfg(feature = "admin-api")]
+use crate::server::audit::{snapshot, AuditAction, AuditContext, AuditTarget};
+#[cfg(feature = "admin-api")]
+use crate::server::orgs::resolve_license_org;
+
+/// A row of the `trial_registrations` table.
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct TrialRegistration {
+    pub hardware_id: String,
+    /// Product the trial was started for, or `""` when trials are not
+    /// scoped per product
+    pub product: String,
+    pub license_id: String,
+    pub started_at: NaiveDateTime,
+    /// When an admin converted the trial into a paid license
+    pub converted_at: Option<NaiveDateTime>,
+}
+
+impl TrialRegistration {
+    /// Whether the trial was converted into a paid license.
+    pub fn is_converted(&self) -> bool {
+        self.converted_at.is_some()
+    }
+}
+
+// ============================================================================
+// Database
+// ============================================================================
+
+const TRIAL_COLUMNS: &str = "hardware_id, product, license_id, started_at, converted_at";
+
+fn trial_error(operation: &str, e: sqlx::Error) -> LicenseError {
+    error!("{operation} failed: {e}");
+    LicenseError::ServerError(format!("database error: {e}"))
+}
+
+#[async_trait]
+impl TrialStore for Database {
+    async fn insert_trial(
+        &self,
+        trial: &TrialRegistration,
+        license: &License,
+    ) -> LicenseResult<bool> {
+        match self {
+            #[cfg(feature = "sqlite")]
+            Database::SQLite(pool) => {
+                let fail = |e| trial_error("SQLite insert_trial", e);
+                let mut tx = pool.begin_with("BEGIN IMMEDIATE").await.map_err(fail)?;
+
+                // The registration refers to the license, so write it first
+                sqlite_upsert_license(&mut tx, license)
+                    .await
+                    .map_err(fail)?;
+
+                let inserted = sqlx::query(&format!(
+                    "INSERT INTO trial_registrations ({TRIAL_COLUMNS}) VALUES (?, ?, ?, ?, ?) \
+                     ON CONFLICT (hardware_id, product) DO NOTHING"
+                ))
+                .bind(&trial.hardware_id)
+                .bind(&trial.product)
+                .bind(&trial.license_id)
+                .bind(trial.started_at)
+                .bind(trial.converted_at)
+                .execute(&mut *tx)
+                .await
+                .map_err(fail)?
+                .rows_affected()
+                    > 0;
+
+                if inserted {
+                    tx.commit().await.map_err(fail)?;
+                } else {
+                    tx.rollback().await.map_err(fail)?;
+                }
+                Ok(inserted)
+            }
+            #[cfg(feature = "postgres")]
+            Database::Postgres(pool) => {
+                let fail = |e| trial_error("Postgres insert_trial", e);
+                let mut tx = pool.begin().await.map_err(fail)?;
+
+                pg_upsert_license(&mut tx, license).await.map_err(fail)?;
+
+                let inserted = sqlx::query(&format!(
+                    "INSERT INTO trial_registrations ({TRIAL_COLUMNS}) \
+                     VALUES ($1, $2, $3, $4, $5) \
+                     ON CONFLICT (hardware_id, product) DO NOTHING"
+                ))
+                .bind(&trial.hardware_id)
+                .bind(&trial.product)
+                .bind(&trial.license_id)
+                .bind(trial.started_at)
+                .bind(trial.converted_at)
+                .execute(&mut *tx)
+                .await
+                .map_err(fail)?
+                .rows_affected()
+                    > 0;
+
+                if inserted {
+                    tx.commit().await.map_err(fail)?;
+                } else {
+                    tx.rollback().await.map_err(fail)?;
+                }
+                Ok(inserted)
+            }
+        }
+    }
+
+    async fn get_trial_by_license(
+        &self,
+        license_id: &str,
+    ) -> LicenseResult<Option<TrialRegistration>> {
+        match self {
+            #[cfg(feature = "sqlite")]
+            Database::SQLite(pool) => sqlx::query_as::<_, TrialRegistration>(&format!(
+                "SELECT {TRIAL_COLUMNS} FROM trial_registrations WHERE license_id = ?"
+            ))
+            .bind(license_id)
+            .fetch_optional(pool)
+            .await
+            .map_err(|e| trial_error("SQLite get_trial_by_license", e)),
+            #[cfg(feature = "postgres")]
+            Database::Postgres(pool) => sqlx::query_as::<_, TrialRegistration>(&format!(
+                "SELECT {TRIAL_COLUMNS} FROM trial_registrations WHERE license_id = $1"
+            ))
+            .bind(license_id)
+            .fetch_optional(pool)
+            .await
+            .map_err(|e| trial_error("Postgres get_trial_by_license", e)),
+        }
+    }
+
+    async fn convert_trial(
+        &self,
+        license: &License,
+        converted_at: NaiveDateTime,
+    ) -> LicenseResult<bool> {
+        match self {
+            #[cfg(feature = "sqlite")]
+            Database::SQLite(pool) => {
+                let fail = |e| trial_error("SQLite convert_trial", e);
+                let mut tx = pool.begin_with("BEGIN IMMEDIATE").await.map_err(fail)?;
+
+                let converted = sqlx::query(
+                    "UPDATE trial_registrations SET converted_at = ? \
+                     WHERE license_id = ? AND converted_at IS NULL",
+                )
+                .bind(converted_at)
+                .bind(&license.license_id)
+                .execute(&mut *tx)
+                .await
+                .map_err(fail)?
+                .rows_affected()
+                    > 0;
+
+                if !converted {
+                    tx.rollback().await.map_err(fail)?;
+                    return Ok(false);
+                }
+
+                sqlite_upsert_license(&mut tx, license)
+                    .await
+                    .map_err(fail)?;
+                tx.commit().await.map_err(fail)?;
+                Ok(true)
+            }
+            #[cfg(feature = "postgres")]
+            Database::Postgres(pool) => {
+                let fail = |e| trial_error("Postgres convert_trial", e);
+                let mut tx = pool.begin().await.map_err(fail)?;
+
+                let converted = sqlx::query(
+                    "UPDATE trial_registrations SET converted_at = $1 \
+                     WHERE license_id = $2 AND converted_at IS NULL",
+                )
+                .bind(converted_at)
+                .bind(&license.license_id)
+                .execute(&mut *tx)
+                .await
+                .map_err(fail)?
+                .rows_affected()
+                    > 0;
+
+                if !converted {
+                    tx.rollback().await.map_err(fail)?;
+                    return Ok(false);
+                }
+
+                pg_upsert_license(&mut tx, license).await.map_err(fail)?;
+                tx.commit().await.map_err(fail)?;
+                Ok(true)
+            }
+        }
+    }
+}
+
+// ============================================================================
+// Request/Response Types
+// ============================================================================
+
+/// Request to start a trial on this machine.
+#[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct TrialRequest {
+    /// Hardware fingerprint (SHA-256 hash)
+    pub hardware_id: String,
+    /// Product to start a trial for; required when the server scopes trials
+    /// per product, and rejected otherwise
+    #[serde(default)]
+    pub product: Option<String>,
+    /// Optional device name for display purposes
+    #[serde(default)]
+    pub device_name: Option<String>,
+    /// Optional device info (OS, CPU, etc.)
+    #[serde(default)]
+    pub device_info: Option<String>,
+}
+
+/// Response from a successful trial request.
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct TrialResponse {
+    pub success: bool,
+    pub license_id: String,
+    /// License key of the trial, for later bind/validate calls
+    pub license_key: String,
+    pub features: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tier: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub product: Option<String>,
+    /// Signed license document for offline verification (if signing is configured)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub signed_license: Option<SignedLicense>,
+}
+
+/// Request to convert a trial into a paid license.
+#[cfg(feature = "admin-api")]
+#[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct ConvertTrialRequest {
+    /// Paid tier (defaults to the organization's default tier, then the trial's tier)
+    #[serde(default)]
+    pub tier: Option<String>,
+    /// Explicit features, merged with the tier's
+    #[serde(default)]
+    pub features: Vec<String>,
+    /// New expiration date (ISO 8601); omit for a license that never expires
+    #[serde(default)]
+    pub expires_at: Option<String>,
+    /// Organization that bought the license
+    #[serde(default)]
+    pub org_id: Option<String>,
+    /// Organization name, used when `org_id` is not registered yet
+    #[serde(default)]
+    pub org_name: Option<String>,
+    /// New seat limit (defaults to the trial's)
+    #[serde(default)]
+    pub max_devices: Option<i32>,
+}
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+/// Registry key for a trial request's product.
+///
+/// Without configured products every trial is keyed on `""`; with them the
+/// request must name one of the configured products.
+fn resolve_product(config: &TrialConfig, product: Option<&str>) -> Result<String, ClientError> {
+    match (config.products.is_empty(), product) {
+        (true, None) => Ok(String::new()),
+        (true, Some(_)) => Err(ClientError::new(
+            ClientErrorCode::InvalidRequest,
+            "Trials are not scoped per product on this server",
+        )),
+        (false, None) => Err(ClientError::new(
+            ClientErrorCode::InvalidRequest,
+            "product is required",
+        )),
+        (false, Some(product)) if config.products.iter().any(|p| p == product) => {
+            Ok(product.to_string())
+        }
+        (false, Some(product)) => Err(ClientError::new(
+            ClientErrorCode::InvalidRequest,
+            format!("Unknown product '{product}'"),
+        )),
+    }
+}
+
+fn internal_error(context: &str, e: impl std::fmt::Display) -> ClientError {
+    warn!("{context}: {e}");
+    ClientError::new(ClientErrorCode::InternalError, context)
+}
+
+// ============================================================================
+// Handlers
+// ============================================================================
+
+/// Start a trial on this machine.
+///
+/// # Behavior
+/// - Returns TRIALS_DISABLED unless `[trials] enabled = true`
+/// - Creates a license with the trial tier, expiring after `duration_days`
+/// - Binds the license to the requesting hardware
+/// - Returns TRIAL_ALREADY_USED if the machine already had a trial (for this product)
+#[cfg_attr(feature = "openapi", utoipa::path(
+    post,
+    path = "/api/v1/client/trial",
+    tag = "client",
+    request_body = TrialRequest,
+    responses(
+        (status = 201, description = "Trial license issued and bound", body = TrialResponse),
+        (status = 400, description = "Invalid request", body = ClientError),
+        (status = 403, description = "Trials are disabled", body = ClientError),
+        (status = 409, description = "This machine already used its trial", body = ClientError),
+    )
+))]
+pub async fn start_trial_handler(
+    State(state): State<AppState>,
+    Json(req): Json<TrialRequest>,
+) -> Result<(StatusCode, Json<TrialResponse>), ClientError> {
+    let config = get_config().map_err(|e| internal_error("Configuration error", e))?;
+    if !config.trials.enabled {
+        return Err(ClientError::new(
+            ClientErrorCode::TrialsDisabled,
+            "Trials are not enabled on this server",
+        ));
+    }
+
+    validate_not_empty(&req.hardware_id, "hardware_id")
+        .map_err(|e| ClientError::new(ClientErrorCode::InvalidRequest, e.to_string()))?;
+    let product = resolve_product(&config.trials, req.product.as_deref())?;
+
+    info!(
+        "Trial request for hardware_id={} product={:?}",
+        req.hardware_id, req.product
+    );
+
+    let key_config: LicenseKeyConfig = (&config.license).into();
+    let db = &state.db;
+    let license_key = generate_unique_license_key(
+        &key_config,
+        |key| async move { db.license_key_exists(&key).await },
+        10,
+    )
+    .await
+    .map_err(|e| internal_error("Failed to generate license key", e))?;
+
+    let now = Utc::now().naive_utc();
+    let tier = config.trials.tier.clone();
+    let features = tier.as_deref().map(get_tier_features).unwrap_or_default();
+    let license = License {
+        license_id: Uuid::new_v4().to_string(),
+        client_id: None,
+        status: "active".to_string(),
+        features: serde_json::to_string(&features).ok(),
+        issued_at: now,
+        expires_at: now.checked_add_signed(Duration::days(i64::from(config.trials.duration_days))),
+        hardware_id: None,
+        signature: None,
+        last_heartbeat: None,
+        org_id: None,
+        org_name: None,
+        license_key: Some(license_key.clone()),
+        tier,
+        device_name: None,
+        device_info: None,
+        bound_at: None,
+        last_seen_at: None,
+        suspended_at: None,
+        revoked_at: None,
+        revoke_reason: None,
+        grace_period_ends_at: None,
+        suspension_message: None,
+        is_blacklisted: None,
+        blacklisted_at: None,
+        blacklist_reason: None,
+        metadata: None,
+        bandwidth_used_bytes: None,
+        bandwidth_limit_bytes: None,
+        quota_exceeded: None,
+        max_devices: 1,
+        max_concurrent: None,
+        offline_days: None,
+        billing_cycle: None,
+        usage_period_start: None,
+        suspended_by_org: None,
+    };
+    let trial = TrialRegistration {
+        hardware_id: req.hardware_id.clone(),
+        product,
+        license_id: license.license_id.clone(),
+        started_at: now,
+        converted_at: None,
+    };
+
+    let registered = state
+        .db
+        .insert_trial(&trial, &license)
+        .await
+        .map_err(|e| internal_error("Failed to register trial", e))?;
+    if !registered {
+        warn!(
+            "Trial refused for hardware_id={}: trial already used",
+            req.hardware_id
+        );
+        return Err(ClientError::new(
+            ClientErrorCode::TrialAlreadyUsed,
+            "A trial has already been used on this machine",
+        ));
+    }
+
+    log_license_event(
+        LicenseEvent::Created,
+        &license.license_id,
+        Some(&license_key),
+    );
+    webhooks::notify(&*state.db, LicenseEvent::Created, &license.license_id, None).await;
+
+    // Bind the trial to the requesting machine
+    let claim = state
+        .db
+        .claim_license_seat(
+            &license,
+            &req.hardware_id,
+            req.device_name.as_deref(),
+            req.device_info.as_deref(),
+        )
+        .await
+        .map_err(|e| internal_error("Failed to bind trial license", e))?;
+
+    if matches!(claim, SeatClaim::Claimed) {
+        let _ = state
+            .db
+            .record_binding_history(
+                &license.license_id,
+                BindingAction::Bind,
+                Some(&req.hardware_id),
+                req.device_name.as_deref(),
+                req.device_info.as_deref(),
+                PerformedBy::Client,
+                Some("trial started"),
+            )
+            .await;
+
+        log_license_binding_event(
+            LicenseEvent::Bound,
+            &license_key,
+            &req.hardware_id,
+            req.device_name.as_deref(),
+        );
+        webhooks::notify(
+            &*state.db,
+            LicenseEvent::Bound,
+            &license.license_id,
+            Some(bound_details(&req.hardware_id, req.device_name.as_deref())),
+        )
+        .await;
+    }
+
+    let signed_license = issue_signed_license(
+        &state,
+        &license,
+        &req.hardware_id,
+        None,
+        offline_valid_until(&license),
+    )
+    .await;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(TrialResponse {
+            success: true,
+            license_id: license.license_id,
+            license_key,
+            features,
+            tier: license.tier,
+            expires_at: license.expires_at.map(|d| d.to_string()),
+            product: req.product,
+            signed_license,
+        }),
+    ))
+}
+
+/// Convert a trial into a paid license.
+///
+/// `POST /api/v1/licenses/{license_id}/convert-trial`
+///
+/// The license keeps its key and its device binding. An expired trial is
+/// reactivated.
+#[cfg(feature = "admin-api")]
+#[cfg_attr(feature = "openapi", utoipa::path(
+    post,
+    path = "/api/v1/licenses/{license_id}/convert-trial",
+    tag = "admin",
+    params(("license_id" = String, Path, description = "License ID")),
+    request_body = ConvertTrialRequest,
+    responses(
+        (status = 200, description = "Trial converted", body = LicenseResponse),
+        (status = 400, description = "License is not an unconverted trial"),
+        (status = 404, description = "License not found"),
+    ),
+    security(("bearer_auth" = []))
+))]
+pub async fn convert_trial_handler(
+    State(state): State<AppState>,
+    audit: AuditContext,
+    Path(license_id): Path<String>,
+    Json(payload): Json<ConvertTrialRequest>,
+) -> Result<Json<LicenseResponse>, AdminError> {
+    info!("Converting trial license_id={}", license_id);
+
+    let mut license = state
+        .db
+        .get_license(&license_id)
+        .await?
+        .ok_or_else(|| AdminError::NotFound(format!("license not found: {license_id}")))?;
+
+    match state.db.get_trial_by_license(&license_id).await? {
+        None => {
+            return Err(AdminError::BadRequest(format!(
+                "license {license_id} is not a trial"
+            )))
+        }
+        Some(trial) if trial.is_converted() => {
+            return Err(AdminError::BadRequest(format!(
+                "trial {license_id} was already converted"
+            )))
+        }
+        Some(_) => {}
+    }
+    let before = snapshot(&license);
+
+    let org = resolve_license_org(
+        &*state.db,
+        &audit,
+        payload.org_id.as_deref(),
+        payload.org_name.as_deref(),
+    )
+    .await?;
+
+    let tier = payload
+        .tier
+        .or_else(|| org.as_ref().and_then(|o| o.default_tier.clone()))
+        .or_else(|| license.tier.clone());
+    let (tier, features) = resolve_license_defaults(org.as_ref(), tier, &payload.features);
+
+    license.tier = tier;
+    license.features = serde_json::to_string(&features).ok();
+    license.expires_at = payload
+        .expires_at
+        .as_deref()
+        .map(parse_datetime)
+        .transpose()?;
+    if payload.max_devices.is_some() {
+        license.max_devices = resolve_max_devices(payload.max_devices)?;
+    }
+    if let Some(org) = org {
+        license.org_id = Some(org.id);
+        license.org_name = Some(org.name);
+    }
+    if license.status == "expired" {
+        license.status = "active".to_string();
+    }
+
+    let now = Utc::now().naive_utc();
+    if !state.db.convert_trial(&license, now).await? {
+        return Err(AdminError::BadRequest(format!(
+            "trial {license_id} was already converted"
+        )));
+    }
+
+    info!("Converted trial license_id={}", license_id);
+
+    audit
+        .record(
+            &*state.db,
+            AuditAction::TrialConverted,
+            AuditTarget::License(license_id),
+            before,
+            snapshot(&license),
+        )
+        .await;
+
+    Ok(Json(license.into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(products: &[&str]) -> TrialConfig {
+        TrialConfig {
+            products: products.iter().map(|p| p.to_string()).collect(),
+            ..TrialConfig::default()
+        }
+    }
+
+    #[test]
+    fn product_is_empty_when_trials_are_not_scoped() {
+        assert_eq!(resolve_product(&config(&[]), None).unwrap(), "");
+
+        let err = resolve_product(&config(&[]), Some("editor")).unwrap_err();
+        assert_eq!(err.error, ClientErrorCode::InvalidRequest);
+    }
+
+    #[test]
+    fn product_must_be_configured_when_trials_are_scoped() {
+        let config = config(&["editor", "viewer"]);
+        assert_eq!(resolve_product(&config, Some("viewer")).unwrap(), "viewer");
+
+        let missing = resolve_product(&config, None).unwrap_err();
+        assert_eq!(missing.error, ClientErrorCode::InvalidRequest);
+        let unknown = resolve_product(&config, Some("compiler")).unwrap_err();
+        assert_eq!(unknown.error, ClientErrorCode::InvalidRequest);
+    }
+}
@@ -0,0 +1,1321 @@
+//! Named usage meters.
+//!
+//! A license tracks any number of usage meters, named by the application:
+//! API calls, renders, seat-hours and so on. Clients report increments with
+//! `POST /api/v1/client/usage`. Every report carries a client-chosen
+//! `report_id`, and a report is only counted once per license, so clients
+//! can safely retry after a timeout. Bandwidth is the built-in `bandwidth`
+//! meter, which `PATCH /api/v1/licenses/{license_id}/usage` still updates.
+//!
+//! A meter's limit comes from the license's tier (`[tiers.<name>.meters]`)
+//! unless an admin set a per-license limit on it. A license is over quota
+//! while any of its meters has reached its limit; the tier's
+//! `quota_restricted_features` are then denied by `validate-feature`.
+//! `license.quota_exceeded` is sent to webhooks when a license goes over
+//! quota.
+//!
+//! Meters reset at the end of each period of the license's billing cycle
+//! (see [`crate::server::billing`]); the usage of closed periods is kept in
+//! `usage_periods`.
+//!
+//! # Endpoints
+//!
+//! - `POST /api/v1/client/usage` - Report usage of a meter
+//! - `GET /api/v1/licenses/{license_id}/usage` - List the meters of a license
+//! - `PUT /api/v1/licenses/{license_id}/usage/{meter}` - Set a meter's usage or limit
+//! - `GET /api/v1/licenses/{license_id}/usage-periods` - List closed billing periods
+
+use std::collections::BTreeMap;
+
+use axum::async_trait;
+use axum::{extract::State, Json};
+use chrono::{NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, QueryBuilder};
+use tracing::{error, info, warn};
+
+#[cfg(feature = "openapi")]
+use utoipa::ToSchema;
+
+use crate::errors::{LicenseError, LicenseResult};
+use crate::server::client_api::{require_seat, ClientError, ClientErrorCode};
+use crate::server::database::{Database, License};
+use crate::server::handlers::AppState;
+use crate::server::logging::{log_license_event, LicenseEvent};
+use crate::server::store::{LicenseStore, UsageStore};
+use crate::server::validation::{validate_length, validate_meter_name};
+use crate::server::webhooks;
+use crate::tiers::{get_tier_config, TierConfig, BANDWIDTH_METER};
+
+#[cfg(feature = "admin-api")]
+use axum::extract::Path;
+
+#[cfg(feature = "admin-api")]
+use crate::server::admin::AdminError;
+#[cfg(feature = "admin-api")]
+use crate::server::audit::{snapshot, AuditAction, AuditContext, AuditTarget};
+#[cfg(feature = "admin-api")]
+use crate::server::billing::configured_billing_cycle;
+
+/// A row of the `usage_meters` table.
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct UsageMeter {
+    pub license_id: String,
+    pub meter: String,
+    pub used: i64,
+    /// Per-license limit overriding the tier's (0 = unlimited)
+    pub usage_limit: Option<i64>,
+    pub updated_at: NaiveDateTime,
+}
+
+impl UsageMeter {
+    /// A meter nothing was reported to yet.
+    pub fn empty(license_id: &str, meter: &str, updated_at: NaiveDateTime) -> Self {
+        Self {
+            license_id: license_id.to_string(),
+            meter: meter.to_string(),
+            used: 0,
+            usage_limit: None,
+            updated_at,
+        }
+    }
+
+    /// The limit that applies to this meter, or `None` if it is unlimited.
+    ///
+    /// A per-license limit wins over the tier's.
+    pub fn limit(&self, tier: Option<&TierConfig>) -> Option<i64> {
+        match self.usage_limit {
+            Some(limit) if limit > 0 => Some(limit),
+            Some(_) => None,
+            None => tier
+                .and_then(|t| t.meter_limit(&self.meter))
+                .map(|limit| i64::try_from(limit).unwrap_or(i64::MAX)),
+        }
+    }
+}
+
+/// A row of the `usage_reports` table: one usage increment from a client.
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct UsageReport {
+    pub license_id: String,
+    /// Client-chosen ID; a report is counted once per license and ID
+    pub report_id: String,
+    pub meter: String,
+    pub amount: i64,
+    pub reported_at: NaiveDateTime,
+}
+
+/// Outcome of [`UsageStore::record_usage`].
+#[derive(Debug, Clone)]
+pub struct RecordedUsage {
+    /// The reported meter, after the report
+    pub meter: UsageMeter,
+    /// The report had already been counted and changed nothing
+    pub duplicate: bool,
+}
+
+/// A row of the `usage_periods` table: a meter's usage in a closed period.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, FromRow)]
+pub struct UsagePeriod {
+    pub license_id: String,
+    pub meter: String,
+    pub period_start: NaiveDateTime,
+    pub period_end: NaiveDateTime,
+    pub used: i64,
+    /// Per-license limit when the period closed (None = the tier's)
+    pub usage_limit: Option<i64>,
+}
+
+/// Usage of a meter against the limit that applies to it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct MeterStatus {
+    pub meter: String,
+    pub used: i64,
+    /// Limit from the license or its tier (None = unlimited)
+    pub limit: Option<i64>,
+    /// Usage has reached the limit
+    pub exceeded: bool,
+}
+
+impl MeterStatus {
+    /// Status of a meter under a license's tier.
+    pub fn new(meter: &UsageMeter, tier: Option<&TierConfig>) -> Self {
+        let limit = meter.limit(tier);
+        Self {
+            meter: meter.meter.clone(),
+            used: meter.used,
+            limit,
+            exceeded: limit.is_some_and(|limit| meter.used >= limit),
+        }
+    }
+}
+
+// ============================================================================
+// Database
+// ============================================================================
+
+const METER_COLUMNS: &str = "license_id, meter, used, usage_limit, updated_at";
+const PERIOD_COLUMNS: &str = "license_id, meter, period_start, period_end, used, usage_limit";
+
+fn usage_error(operation: &str, e: sqlx::Error) -> LicenseError {
+    error!("{operation} failed: {e}");
+    LicenseError::ServerError(format!("database error: {e}"))
+}
+
+#[async_trait]
+impl UsageStore for Database {
+    async fn record_usage(&self, report: &UsageReport) -> LicenseResult<RecordedUsage> {
+        match self {
+            #[cfg(feature = "sqlite")]
+            Database::SQLite(pool) => {
+                let fail = |e| usage_error("SQLite record_usage", e);
+                let mut tx = pool.begin_with("BEGIN IMMEDIATE").await.map_err(fail)?;
+
+                let applied = sqlx::query(
+                    "INSERT INTO usage_reports \
+                     (license_id, report_id, meter, amount, reported_at) \
+                     VALUES (?, ?, ?, ?, ?) \
+                     ON CONFLICT (license_id, report_id) DO NOTHING",
+                )
+                .bind(&report.license_id)
+                .bind(&report.report_id)
+                .bind(&report.meter)
+                .bind(report.amount)
+                .bind(report.reported_at)
+                .execute(&mut *tx)
+                .await
+                .map_err(fail)?
+                .rows_affected()
+                    > 0;
+
+                if applied {
+                    sqlx::query(&format!(
+                        "INSERT INTO usage_meters ({METER_COLUMNS}) VALUES (?, ?, ?, NULL, ?) \
+                         ON CONFLICT (license_id, meter) DO UPDATE SET \
+                         used = usage_meters.used + excluded.used, \
+                         updated_at = excluded.updated_at"
+                    ))
+                    .bind(&report.license_id)
+                    .bind(&report.meter)
+                    .bind(report.amount)
+                    .bind(report.reported_at)
+                    .execute(&mut *tx)
+                    .await
+                    .map_err(fail)?;
+                }
+
+                let meter = sqlx::query_as::<_, UsageMeter>(&format!(
+                    "SELECT {METER_COLUMNS} FROM usage_meters WHERE license_id = ? AND meter = ?"
+                ))
+                .bind(&report.license_id)
+                .bind(&report.meter)
+                .fetch_optional(&mut *tx)
+                .await
+                .map_err(fail)?
+                .unwrap_or_else(|| {
+                    UsageMeter::empty(&report.license_id, &report.meter, report.reported_at)
+                });
+
+                if applied && meter.meter == BANDWIDTH_METER {
+                    sqlx::query(
+                        "UPDATE licenses SET bandwidth_used_bytes = ? WHERE license_id = ?",
+                    )
+                    .bind(meter.used)
+                    .bind(&meter.license_id)
+                    .execute(&mut *tx)
+                    .await
+                    .map_err(fail)?;
+                }
+
+                tx.commit().await.map_err(fail)?;
+                Ok(RecordedUsage {
+                    meter,
+                    duplicate: !applied,
+                })
+            }
+            #[cfg(feature = "postgres")]
+            Database::Postgres(pool) => {
+                let fail = |e| usage_error("Postgres record_usage", e);
+                let mut tx = pool.begin().await.map_err(fail)?;
+
+                let applied = sqlx::query(
+                    "INSERT INTO usage_reports \
+                     (license_id, report_id, meter, amount, reported_at) \
+                     VALUES ($1, $2, $3, $4, $5) \
+                     ON CONFLICT (license_id, report_id) DO NOTHING",
+                )
+                .bind(&report.license_id)
+                .bind(&report.report_id)
+                .bind(&report.meter)
+                .bind(report.amount)
+                .bind(report.reported_at)
+                .execute(&mut *tx)
+                .await
+                .map_err(fail)?
+                .rows_affected()
+                    > 0;
+
+                if applied {
+                    sqlx::query(&format!(
+                        "INSERT INTO usage_meters ({METER_COLUMNS}) VALUES ($1, $2, $3, NULL, $4) \
+                         ON CONFLICT (license_id, meter) DO UPDATE SET \
+                         used = usage_meters.used + excluded.used, \
+                         updated_at = excluded.updated_at"
+                    ))
+                    .bind(&report.license_id)
+                    .bind(&report.meter)
+                    .bind(report.amount)
+                    .bind(report.reported_at)
+                    .execute(&mut *tx)
+                    .await
+                    .map_err(fail)?;
+                }
+
+                let meter = sqlx::query_as::<_, UsageMeter>(&format!(
+                    "SELECT {METER_COLUMNS} FROM usage_meters \
+                     WHERE license_id = $1 AND meter = $2"
+                ))
+                .bind(&report.license_id)
+                .bind(&report.meter)
+                .fetch_optional(&mut *tx)
+                .await
+                .map_err(fail)?
+                .unwrap_or_else(|| {
+                    UsageMeter::empty(&report.license_id, &report.meter, report.reported_at)
+                });
+
+                if applied && meter.meter == BANDWIDTH_METER {
+                    sqlx::query(
+                        "UPDATE licenses SET bandwidth_used_bytes = $1 WHERE license_id = $2",
+                    )
+                    .bind(meter.used)
+                    .bind(&meter.license_id)
+                    .execute(&mut *tx)
+                    .await
+                    .map_err(fail)?;
+                }
+
+                tx.commit().await.map_err(fail)?;
+                Ok(RecordedUsage {
+                    meter,
+                    duplicate: !applied,
+                })
+            }
+        }
+    }
+
+    async fn set_usage_meter(&self, meter: &UsageMeter) -> LicenseResult<()> {
+        match self {
+            #[cfg(feature = "sqlite")]
+            Database::SQLite(pool) => {
+                let fail = |e| usage_error("SQLite set_usage_meter", e);
+                let mut tx = pool.begin_with("BEGIN IMMEDIATE").await.map_err(fail)?;
+
+                sqlx::query(&format!(
+                    "INSERT INTO usage_meters ({METER_COLUMNS}) VALUES (?, ?, ?, ?, ?) \
+                     ON CONFLICT (license_id, meter) DO UPDATE SET \
+                     used = excluded.used, \
+                     usage_limit = excluded.usage_limit, \
+                     updated_at = excluded.updated_at"
+                ))
+                .bind(&meter.license_id)
+                .bind(&meter.meter)
+                .bind(meter.used)
+                .bind(meter.usage_limit)
+                .bind(meter.updated_at)
+                .execute(&mut *tx)
+                .await
+                .map_err(fail)?;
+
+                if meter.meter == BANDWIDTH_METER {
+                    sqlx::query(
+                        "UPDATE licenses SET bandwidth_used_bytes = ?, bandwidth_limit_bytes = ? \
+                         WHERE license_id = ?",
+                    )
+                    .bind(meter.used)
+                    .bind(meter.usage_limit)
+                    .bind(&meter.license_id)
+                    .execute(&mut *tx)
+                    .await
+                    .map_err(fail)?;
+                }
+
+                tx.commit().await.map_err(fail)
+            }
+            #[cfg(feature = "postgres")]
+            Database::Postgres(pool) => {
+                let fail = |e| usage_error("Postgres set_usage_meter", e);
+                let mut tx = pool.begin().await.map_err(fail)?;
+
+                sqlx::query(&format!(
+                    "INSERT INTO usage_meters ({METER_COLUMNS}) VALUES ($1, $2, $3, $4, $5) \
+                     ON CONFLICT (license_id, meter) DO UPDATE SET \
+                     used = excluded.used, \
+                     usage_limit = excluded.usage_limit, \
+                     updated_at = excluded.updated_at"
+                ))
+                .bind(&meter.license_id)
+                .bind(&meter.meter)
+                .bind(meter.used)
+                .bind(meter.usage_limit)
+                .bind(meter.updated_at)
+                .execute(&mut *tx)
+                .await
+                .map_err(fail)?;
+
+                if meter.meter == BANDWIDTH_METER {
+                    sqlx::query(
+                        "UPDATE licenses SET bandwidth_used_bytes = $1, bandwidth_limit_bytes = $2 \
+                         WHERE license_id = $3",
+                    )
+                    .bind(meter.used)
+                    .bind(meter.usage_limit)
+                    .bind(&meter.license_id)
+                    .execute(&mut *tx)
+                    .await
+                    .map_err(fail)?;
+                }
+
+                tx.commit().await.map_err(fail)
+            }
+        }
+    }
+
+    async fn list_usage_meters(&self, license_id: &str) -> LicenseResult<Vec<UsageMeter>> {
+        match self {
+            #[cfg(feature = "sqlite")]
+            Database::SQLite(pool) => sqlx::query_as::<_, UsageMeter>(&format!(
+                "SELECT {METER_COLUMNS} FROM usage_meters WHERE license_id = ? ORDER BY meter"
+            ))
+            .bind(license_id)
+            .fetch_all(pool)
+            .await
+            .map_err(|e| usage_error("SQLite list_usage_meters", e)),
+            #[cfg(feature = "postgres")]
+            Database::Postgres(pool) => sqlx::query_as::<_, UsageMeter>(&format!(
+                "SELECT {METER_COLUMNS} FROM usage_meters WHERE license_id = $1 ORDER BY meter"
+            ))
+            .bind(license_id)
+            .fetch_all(pool)
+            .await
+            .map_err(|e| usage_error("Postgres list_usage_meters", e)),
+        }
+    }
+
+    async fn set_quota_exceeded(&self, license_id: &str, exceeded: bool) -> LicenseResult<bool> {
+        let rows_affected = match self {
+            #[cfg(feature = "sqlite")]
+            Database::SQLite(pool) => {
+                sqlx::query("UPDATE licenses SET quota_exceeded = ? WHERE license_id = ?")
+                    .bind(exceeded)
+                    .bind(license_id)
+                    .execute(pool)
+                    .await
+                    .map_err(|e| usage_error("SQLite set_quota_exceeded", e))?
+                    .rows_affected()
+            }
+            #[cfg(feature = "postgres")]
+            Database::Postgres(pool) => {
+                sqlx::query("UPDATE licenses SET quota_exceeded = $1 WHERE license_id = $2")
+                    .bind(exceeded)
+                    .bind(license_id)
+                    .execute(pool)
+                    .await
+                    .map_err(|e| usage_error("Postgres set_quota_exceeded", e))?
+                    .rows_affected()
+            }
+        };
+
+        Ok(rows_affected > 0)
+    }
+
+    async fn close_usage_period(
+        &self,
+        license_id: &str,
+        period_end: NaiveDateTime,
+    ) -> LicenseResult<Option<Vec<UsagePeriod>>> {
+        match self {
+            #[cfg(feature = "sqlite")]
+            Database::SQLite(pool) => {
+                let fail = |e| usage_error("SQLite close_usage_period", e);
+                let mut tx = pool.begin_with("BEGIN IMMEDIATE").await.map_err(fail)?;
+
+                let Some((issued_at, period_start, tier)) =
+                    sqlx::query_as::<_, (NaiveDateTime, Option<NaiveDateTime>, Option<String>)>(
+                        "SELECT issued_at, usage_period_start, tier FROM licenses \
+                         WHERE license_id = ?",
+                    )
+                    .bind(license_id)
+                    .fetch_optional(&mut *tx)
+                    .await
+                    .map_err(fail)?
+                else {
+                    return Ok(None);
+                };
+                let period_start = period_start.unwrap_or(issued_at);
+                if period_end <= period_start {
+                    return Ok(None);
+                }
+
+                let meters = sqlx::query_as::<_, UsageMeter>(&format!(
+                    "SELECT {METER_COLUMNS} FROM usage_meters \
+                     WHERE license_id = ? AND used <> 0 ORDER BY meter"
+                ))
+                .bind(license_id)
+                .fetch_all(&mut *tx)
+                .await
+                .map_err(fail)?;
+
+                let periods = closed_periods(meters, period_start, period_end);
+                for period in &periods {
+                    sqlx::query(&format!(
+                        "INSERT INTO usage_periods ({PERIOD_COLUMNS}) VALUES (?, ?, ?, ?, ?, ?)"
+                    ))
+                    .bind(&period.license_id)
+                    .bind(&period.meter)
+                    .bind(period.period_start)
+                    .bind(period.period_end)
+                    .bind(period.used)
+                    .bind(period.usage_limit)
+                    .execute(&mut *tx)
+                    .await
+                    .map_err(fail)?;
+
+                    // Subtract what was billed rather than zeroing, so usage
+                    // reported since the snapshot carries over
+                    sqlx::query(
+                        "UPDATE usage_meters SET used = used - ?, updated_at = ? \
+                         WHERE license_id = ? AND meter = ?",
+                    )
+                    .bind(period.used)
+                    .bind(period_end)
+                    .bind(&period.license_id)
+                    .bind(&period.meter)
+                    .execute(&mut *tx)
+                    .await
+                    .map_err(fail)?;
+                }
+
+                // Usage carried over can already be over a limit
+                let remaining = sqlx::query_as::<_, UsageMeter>(&format!(
+                    "SELECT {METER_COLUMNS} FROM usage_meters WHERE license_id = ?"
+                ))
+                .bind(license_id)
+                .fetch_all(&mut *tx)
+                .await
+                .map_err(fail)?;
+                let exceeded = quota_exceeded(license_id, &remaining, tier.as_deref());
+
+                sqlx::query(
+                    "UPDATE licenses SET usage_period_start = ?, quota_exceeded = ?, \
+                     bandwidth_used_bytes = bandwidth_used_bytes - ? \
+                     WHERE license_id = ?",
+                )
+                .bind(period_end)
+                .bind(exceeded)
+                .bind(billed_bandwidth(&periods))
+                .bind(license_id)
+                .execute(&mut *tx)
+                .await
+                .map_err(fail)?;
+
+                tx.commit().await.map_err(fail)?;
+                Ok(Some(periods))
+            }
+            #[cfg(feature = "postgres")]
+            Database::Postgres(pool) => {
+                let fail = |e| usage_error("Postgres close_usage_period", e);
+                let mut tx = pool.begin().await.map_err(fail)?;
+
+                let Some((issued_at, period_start, tier)) =
+                    sqlx::query_as::<_, (NaiveDateTime, Option<NaiveDateTime>, Option<String>)>(
+                        "SELECT issued_at, usage_period_start, tier FROM licenses \
+                         WHERE license_id = $1 FOR UPDATE",
+                    )
+                    .bind(license_id)
+                    .fetch_optional(&mut *tx)
+                    .await
+                    .map_err(fail)?
+                else {
+                    return Ok(None);
+                };
+                let period_start = period_start.unwrap_or(issued_at);
+                if period_end <= period_start {
+                    return Ok(None);
+                }
+
+                let meters = sqlx::query_as::<_, UsageMeter>(&format!(
+                    "SELECT {METER_COLUMNS} FROM usage_meters \
+                     WHERE license_id = $1 AND used <> 0 ORDER BY meter FOR UPDATE"
+                ))
+                .bind(license_id)
+                .fetch_all(&mut *tx)
+                .await
+                .map_err(fail)?;
+
+                let periods = closed_periods(meters, period_start, period_end);
+                for period in &periods {
+                    sqlx::query(&format!(
+                        "INSERT INTO usage_periods ({PERIOD_COLUMNS}) \
+                         VALUES ($1, $2, $3, $4, $5, $6)"
+                    ))
+                    .bind(&period.license_id)
+                    .bind(&period.meter)
+                    .bind(period.period_start)
+                    .bind(period.period_end)
+                    .bind(period.used)
+                    .bind(period.usage_limit)
+                    .execute(&mut *tx)
+                    .await
+                    .map_err(fail)?;
+
+                    // Subtract what was billed rather than zeroing, so usage
+                    // reported since the snapshot carries over
+                    sqlx::query(
+                        "UPDATE usage_meters SET used = used - $1, updated_at = $2 \
+                         WHERE license_id = $3 AND meter = $4",
+                    )
+                    .bind(period.used)
+                    .bind(period_end)
+                    .bind(&period.license_id)
+                    .bind(&period.meter)
+                    .execute(&mut *tx)
+                    .await
+                    .map_err(fail)?;
+                }
+
+                // Usage carried over can already be over a limit
+                let remaining = sqlx::query_as::<_, UsageMeter>(&format!(
+                    "SELECT {METER_COLUMNS} FROM usage_meters WHERE license_id = $1"
+                ))
+                .bind(license_id)
+                .fetch_all(&mut *tx)
+                .await
+                .map_err(fail)?;
+                let exceeded = quota_exceeded(license_id, &remaining, tier.as_deref());
+
+                sqlx::query(
+                    "UPDATE licenses SET usage_period_start = $1, quota_exceeded = $2, \
+                     bandwidth_used_bytes = bandwidth_used_bytes - $3 \
+                     WHERE license_id = $4",
+                )
+                .bind(period_end)
+                .bind(exceeded)
+                .bind(billed_bandwidth(&periods))
+                .bind(license_id)
+                .execute(&mut *tx)
+                .await
+                .map_err(fail)?;
+
+                tx.commit().await.map_err(fail)?;
+                Ok(Some(periods))
+            }
+        }
+    }
+
+    async fn list_usage_periods(&self, license_id: &str) -> LicenseResult<Vec<UsagePeriod>> {
+        match self {
+            #[cfg(feature = "sqlite")]
+            Database::SQLite(pool) => sqlx::query_as::<_, UsagePeriod>(&format!(
+                "SELECT {PERIOD_COLUMNS} FROM usage_periods WHERE license_id = ? \
+                 ORDER BY period_start DESC, meter"
+            ))
+            .bind(license_id)
+            .fetch_all(pool)
+            .await
+            .map_err(|e| usage_error("SQLite list_usage_periods", e)),
+            #[cfg(feature = "postgres")]
+            Database::Postgres(pool) => sqlx::query_as::<_, UsagePeriod>(&format!(
+                "SELECT {PERIOD_COLUMNS} FROM usage_periods WHERE license_id = $1 \
+                 ORDER BY period_start DESC, meter"
+            ))
+            .bind(license_id)
+            .fetch_all(pool)
+            .await
+            .map_err(|e| usage_error("Postgres list_usage_periods", e)),
+        }
+    }
+
+    async fn list_billed_licenses(&self, tiers: &[String]) -> LicenseResult<Vec<License>> {
+        match self {
+            #[cfg(feature = "sqlite")]
+            Database::SQLite(pool) => {
+                let mut query = QueryBuilder::<sqlx::Sqlite>::new(
+                    "SELECT * FROM licenses WHERE billing_cycle IS NOT NULL",
+                );
+                push_tier_filter(&mut query, tiers);
+                query
+                    .build_query_as::<License>()
+                    .fetch_all(pool)
+                    .await
+                    .map_err(|e| usage_error("SQLite list_billed_licenses", e))
+            }
+            #[cfg(feature = "postgres")]
+            Database::Postgres(pool) => {
+                let mut query = QueryBuilder::<sqlx::Postgres>::new(
+                    "SELECT * FROM licenses WHERE billing_cycle IS NOT NULL",
+                );
+                push_tier_filter(&mut query, tiers);
+                query
+                    .build_query_as::<License>()
+                    .fetch_all(pool)
+                    .await
+                    .map_err(|e| usage_error("Postgres list_billed_licenses", e))
+            }
+        }
+    }
+}
+
+/// Usage periods for the meters of a closing period.
+/// Whether any of a license's meters is at its limit under `tier`.
+fn quota_exceeded(license_id: &str, meters: &[UsageMeter], tier: Option<&str>) -> bool {
+    meter_statuses(license_id, meters, tier_config(tier).as_ref())
+        .iter()
+        .any(|status| status.exceeded)
+}
+
+/// Bandwidth billed by a closed period, to subtract from
+/// `licenses.bandwidth_used_bytes`.
+fn billed_bandwidth(periods: &[UsagePeriod]) -> i64 {
+    periods
+        .iter()
+        .find(|period| period.meter == BANDWIDTH_METER)
+        .map_or(0, |period| period.used)
+}
+
+fn closed_periods(
+    meters: Vec<UsageMeter>,
+    period_start: NaiveDateTime,
+    period_end: NaiveDateTime,
+) -> Vec<UsagePeriod> {
+    meters
+        .into_iter()
+        .map(|meter| UsagePeriod {
+            license_id: meter.license_id,
+            meter: meter.meter,
+            period_start,
+            period_end,
+            used: meter.used,
+            usage_limit: meter.usage_limit,
+        })
+        .collect()
+}
+
+/// Append `OR tier IN (...)` for the given tiers.
+fn push_tier_filter<'a, DB>(query: &mut QueryBuilder<'a, DB>, tiers: &[String])
+where
+    DB: sqlx::Database,
+    String: sqlx::Encode<'a, DB> + sqlx::Type<DB>,
+{
+    if tiers.is_empty() {
+        return;
+    }
+    query.push(" OR tier IN (");
+    let mut separated = query.separated(", ");
+    for tier in tiers {
+        separated.push_bind(tier.clone());
+    }
+    separated.push_unseparated(")");
+}
+
+// ============================================================================
+// Request/Response Types
+// ============================================================================
+
+/// Request to report usage of a meter.
+#[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct ReportUsageRequest {
+    pub license_key: String,
+    /// Hardware fingerprint of a device holding a seat
+    pub hardware_id: String,
+    /// Meter name (e.g. "api_calls")
+    pub meter: String,
+    /// Usage to add to the meter
+    pub amount: u64,
+    /// Unique ID of this report (e.g. a UUID); retries must reuse it
+    pub report_id: String,
+}
+
+/// Response from a usage report.
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct ReportUsageResponse {
+    pub success: bool,
+    pub meter: String,
+    /// Usage of the meter, including this report
+    pub used: i64,
+    /// Limit of the meter (None = unlimited)
+    pub limit: Option<i64>,
+    /// This meter has reached its limit
+    pub exceeded: bool,
+    /// Any meter of the license has reached its limit
+    pub quota_exceeded: bool,
+    /// The report had already been counted; this retry changed nothing
+    pub duplicate: bool,
+}
+
+/// Request to set a meter's usage or per-license limit.
+#[cfg(feature = "admin-api")]
+#[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct SetMeterRequest {
+    /// New usage (keeps the current usage if omitted)
+    #[serde(default)]
+    pub used: Option<u64>,
+    /// Per-license limit overriding the tier's (0 = unlimited)
+    #[serde(default)]
+    pub limit: Option<u64>,
+    /// Remove the per-license limit so the tier's applies again
+    #[serde(default)]
+    pub clear_limit: bool,
+    /// Reset usage to 0 (takes precedence over `used`)
+    #[serde(default)]
+    pub reset: bool,
+}
+
+/// The meters of a license.
+#[cfg(feature = "admin-api")]
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct UsageMetersResponse {
+    pub license_id: String,
+    /// Any meter has reached its limit
+    pub quota_exceeded: bool,
+    /// Reported meters and the tier's configured meters, by name
+    pub meters: Vec<MeterStatus>,
+}
+
+/// A meter's usage in a closed billing period.
+#[cfg(feature = "admin-api")]
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct UsagePeriodResponse {
+    pub meter: String,
+    pub period_start: String,
+    pub period_end: String,
+    pub used: i64,
+    /// Per-license limit when the period closed (None = the tier's)
+    pub limit: Option<i64>,
+}
+
+#[cfg(feature = "admin-api")]
+impl From<UsagePeriod> for UsagePeriodResponse {
+    fn from(period: UsagePeriod) -> Self {
+        Self {
+            meter: period.meter,
+            period_start: period.period_start.to_string(),
+            period_end: period.period_end.to_string(),
+            used: period.used,
+            limit: period.usage_limit,
+        }
+    }
+}
+
+/// The billing cycle and closed usage periods of a license.
+#[cfg(feature = "admin-api")]
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct UsagePeriodsResponse {
+    pub license_id: String,
+    /// The license's or its tier's billing cycle (None = never resets)
+    pub billing_cycle: Option<String>,
+    /// Start of the current, still open period
+    pub current_period_start: String,
+    /// Closed periods, newest first
+    pub periods: Vec<UsagePeriodResponse>,
+}
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+/// Tier configuration of a license, if it has a known tier.
+pub(crate) fn license_tier(license: &License) -> Option<TierConfig> {
+    tier_config(license.tier.as_deref())
+}
+
+fn tier_config(tier: Option<&str>) -> Option<TierConfig> {
+    tier.and_then(get_tier_config).map(|tier| tier.config)
+}
+
+/// Status of every meter of a license, ordered by name.
+///
+/// Meters the tier configures but nothing was reported to yet are listed
+/// with zero usage.
+pub fn meter_statuses(
+    license_id: &str,
+    meters: &[UsageMeter],
+    tier: Option<&TierConfig>,
+) -> Vec<MeterStatus> {
+    let mut statuses: BTreeMap<&str, MeterStatus> = BTreeMap::new();
+    if let Some(tier) = tier {
+        for name in tier.meters.keys() {
+            let empty = UsageMeter::empty(license_id, name, NaiveDateTime::default());
+            statuses.insert(name, MeterStatus::new(&empty, Some(tier)));
+        }
+    }
+    for meter in meters {
+        statuses.insert(&meter.meter, MeterStatus::new(meter, tier));
+    }
+    statuses.into_values().collect()
+}
+
+/// Load the meters of a license and resolve their limits.
+pub(crate) async fn license_meter_statuses(
+    db: &dyn LicenseStore,
+    license: &License,
+) -> LicenseResult<Vec<MeterStatus>> {
+    let meters = db.list_usage_meters(&license.license_id).await?;
+    Ok(meter_statuses(
+        &license.license_id,
+        &meters,
+        license_tier(license).as_ref(),
+    ))
+}
+
+/// Bring `license.quota_exceeded` in line with its meters.
+///
+/// Logs `QuotaExceeded` and notifies webhooks with `details` only when the
+/// license goes over quota, not on every update while it stays there.
+/// Returns whether the license is over quota.
+pub(crate) async fn sync_quota_exceeded(
+    db: &dyn LicenseStore,
+    license: &License,
+    statuses: &[MeterStatus],
+    details: serde_json::Value,
+) -> LicenseResult<bool> {
+    let exceeded = statuses.iter().any(|s| s.exceeded);
+    let was_exceeded = license.quota_exceeded == Some(true);
+    if license.quota_exceeded != Some(exceeded) {
+        db.set_quota_exceeded(&license.license_id, exceeded).await?;
+    }
+
+    if exceeded && !was_exceeded {
+        log_license_event(LicenseEvent::QuotaExceeded, &license.license_id, None);
+        webhooks::notify(
+            db,
+            LicenseEvent::QuotaExceeded,
+            &license.license_id,
+            Some(details),
+        )
+        .await;
+    }
+
+    Ok(exceeded)
+}
+
+fn invalid_request(message: impl Into<String>) -> ClientError {
+    ClientError::new(ClientErrorCode::InvalidRequest, message)
+}
+
+fn internal_error(context: &str, e: impl std::fmt::Display) -> ClientError {
+    warn!("{context}: {e}");
+    ClientError::new(ClientErrorCode::InternalError, context)
+}
+
+// ============================================================================
+// Handlers
+// ============================================================================
+
+/// Report usage of a meter.
+///
+/// # Behavior
+/// - Requires the hardware to hold a seat on the license
+/// - Refuses blacklisted, revoked, expired and inactive licenses, and
+///   suspended ones past their grace period, like validate and heartbeat
+/// - Adds `amount` to the meter; a `report_id` already counted for the
+///   license is acknowledged with `duplicate: true` and not counted again
+/// - Returns the meter's usage and limit, and whether the license is over quota
+#[cfg_attr(feature = "openapi", utoipa::path(
+    post,
+    path = "/api/v1/client/usage",
+    tag = "client",
+    request_body = ReportUsageRequest,
+    responses(
+        (status = 200, description = "Usage recorded", body = ReportUsageResponse),
+        (status = 400, description = "Invalid meter, amount or report ID", body = ClientError),
+        (status = 403, description = "License blacklisted/revoked/expired/suspended/inactive or hardware mismatch", body = ClientError),
+        (status = 404, description = "License not found", body = ClientError),
+        (status = 409, description = "License not bound", body = ClientError),
+    )
+))]
+pub async fn report_usage_handler(
+    State(state): State<AppState>,
+    Json(req): Json<ReportUsageRequest>,
+) -> Result<Json<ReportUsageResponse>, ClientError> {
+    validate_meter_name(&req.meter, "meter").map_err(|e| invalid_request(e.to_string()))?;
+    validate_length(&req.report_id, 1, 128, "report_id")
+        .map_err(|e| invalid_request(e.to_string()))?;
+    let amount = i64::try_from(req.amount)
+        .ok()
+        .filter(|amount| *amount > 0)
+        .ok_or_else(|| invalid_request("amount must be a positive integer"))?;
+
+    info!(
+        "Usage report for license_key={} meter={} amount={}",
+        req.license_key, req.meter, amount
+    );
+
+    let license = state
+        .db
+        .get_license_by_key(&req.license_key)
+        .await
+        .map_err(|e| internal_error("Database error", e))?
+        .ok_or_else(|| {
+            warn!("License not found: {}", req.license_key);
+            ClientError::new(ClientErrorCode::LicenseNotFound, "License key not found")
+        })?;
+
+    if license.is_blacklisted == Some(true) {
+        return Err(ClientError::new(
+            ClientErrorCode::LicenseBlacklisted,
+            "License is blacklisted",
+        ));
+    }
+    if license.status == "revoked" {
+        return Err(ClientError::new(
+            ClientErrorCode::LicenseRevoked,
+            "License has been revoked",
+        ));
+    }
+    if license.is_expired() {
+        return Err(ClientError::new(
+            ClientErrorCode::LicenseExpired,
+            "License has expired",
+        ));
+    }
+    if license.status == "suspended" && !license.is_in_grace_period() {
+        return Err(ClientError::new(
+            ClientErrorCode::LicenseSuspended,
+            "License is suspended and grace period has ended",
+        ));
+    }
+
+    require_seat(&state, &license, &req.hardware_id).await?;
+
+    if license.status != "active" && license.status != "suspended" {
+        return Err(ClientError::new(
+            ClientErrorCode::LicenseInactive,
+            format!("License status is '{}'", license.status),
+        ));
+    }
+
+    let recorded = state
+        .db
+        .record_usage(&UsageReport {
+            license_id: license.license_id.clone(),
+            report_id: req.report_id.clone(),
+            meter: req.meter.clone(),
+            amount,
+            reported_at: Utc::now().naive_utc(),
+        })
+        .await
+        .map_err(|e| internal_error("Failed to record usage", e))?;
+
+    if recorded.duplicate {
+        info!(
+            "Usage report {} for license {} was already counted",
+            req.report_id, license.license_id
+        );
+    }
+
+    let statuses = license_meter_statuses(&*state.db, &license)
+        .await
+        .map_err(|e| internal_error("Failed to load usage meters", e))?;
+    let status = statuses
+        .iter()
+        .find(|s| s.meter == req.meter)
+        .cloned()
+        .unwrap_or_else(|| MeterStatus::new(&recorded.meter, None));
+
+    let quota_exceeded = sync_quota_exceeded(
+        &*state.db,
+        &license,
+        &statuses,
+        serde_json::json!({
+            "meter": status.meter,
+            "used": status.used,
+            "limit": status.limit,
+        }),
+    )
+    .await
+    .map_err(|e| internal_error("Failed to update quota status", e))?;
+
+    Ok(Json(ReportUsageResponse {
+        success: true,
+        meter: status.meter,
+        used: status.used,
+        limit: status.limit,
+        exceeded: status.exceeded,
+        quota_exceeded,
+        duplicate: recorded.duplicate,
+    }))
+}
+
+/// List the usage meters of a license.
+///
+/// `GET /api/v1/licenses/{license_id}/usage`
+#[cfg(feature = "admin-api")]
+#[cfg_attr(feature = "openapi", utoipa::path(
+    get,
+    path = "/api/v1/licenses/{license_id}/usage",
+    tag = "admin",
+    params(("license_id" = String, Path, description = "License ID")),
+    responses(
+        (status = 200, description = "Usage meters", body = UsageMetersResponse),
+        (status = 404, description = "License not found"),
+    ),
+    security(("bearer_auth" = []))
+))]
+pub async fn list_usage_handler(
+    State(state): State<AppState>,
+    Path(license_id): Path<String>,
+) -> Result<Json<UsageMetersResponse>, AdminError> {
+    let license = state
+        .db
+        .get_license(&license_id)
+        .await?
+        .ok_or_else(|| AdminError::NotFound(format!("License {license_id} not found")))?;
+
+    let meters = license_meter_statuses(&*state.db, &license).await?;
+    Ok(Json(UsageMetersResponse {
+        license_id,
+        quota_exceeded: meters.iter().any(|m| m.exceeded),
+        meters,
+    }))
+}
+
+/// List the closed usage periods of a license.
+///
+/// `GET /api/v1/licenses/{license_id}/usage-periods`
+///
+/// Each closed period holds the usage of every meter that had any, as it
+/// stood when the billing cycle (or a `reset_bandwidth`) reset the meters.
+#[cfg(feature = "admin-api")]
+#[cfg_attr(feature = "openapi", utoipa::path(
+    get,
+    path = "/api/v1/licenses/{license_id}/usage-periods",
+    tag = "admin",
+    params(("license_id" = String, Path, description = "License ID")),
+    responses(
+        (status = 200, description = "Usage periods", body = UsagePeriodsResponse),
+        (status = 404, description = "License not found"),
+    ),
+    security(("bearer_auth" = []))
+))]
+pub async fn list_usage_periods_handler(
+    State(state): State<AppState>,
+    Path(license_id): Path<String>,
+) -> Result<Json<UsagePeriodsResponse>, AdminError> {
+    let license = state
+        .db
+        .get_license(&license_id)
+        .await?
+        .ok_or_else(|| AdminError::NotFound(format!("License {license_id} not found")))?;
+
+    let periods = state.db.list_usage_periods(&license_id).await?;
+    Ok(Json(UsagePeriodsResponse {
+        billing_cycle: configured_billing_cycle(&license),
+        current_period_start: license
+            .usage_period_start
+            .unwrap_or(license.issued_at)
+            .to_string(),
+        periods: periods.into_iter().map(UsagePeriodResponse::from).collect(),
+        license_id,
+    }))
+}
+
+/// Set a meter's usage or per-license limit.
+///
+/// `PUT /api/v1/licenses/{license_id}/usage/{meter}`
+///
+/// Fields that are omitted keep their current value.
+#[cfg(feature = "admin-api")]
+#[cfg_attr(feature = "openapi", utoipa::path(
+    put,
+    path = "/api/v1/licenses/{license_id}/usage/{meter}",
+    tag = "admin",
+    params(
+        ("license_id" = String, Path, description = "License ID"),
+        ("meter" = String, Path, description = "Meter name"),
+    ),
+    request_body = SetMeterRequest,
+    responses(
+        (status = 200, description = "Meter updated", body = UsageMetersResponse),
+        (status = 400, description = "Invalid meter name or limit"),
+        (status = 404, description = "License not found"),
+    ),
+    security(("bearer_auth" = []))
+))]
+pub async fn set_meter_handler(
+    State(state): State<AppState>,
+    audit: AuditContext,
+    Path((license_id, meter)): Path<(String, String)>,
+    Json(payload): Json<SetMeterRequest>,
+) -> Result<Json<UsageMetersResponse>, AdminError> {
+    validate_meter_name(&meter, "meter").map_err(|e| AdminError::BadRequest(e.to_string()))?;
+    if payload.clear_limit && payload.limit.is_some() {
+        return Err(AdminError::BadRequest(
+            "limit and clear_limit cannot be combined".to_string(),
+        ));
+    }
+    let to_i64 = |value: u64, field: &str| {
+        i64::try_from(value).map_err(|_| AdminError::BadRequest(format!("{field} is too large")))
+    };
+
+    info!(
+        "Set meter {} for license_id={} used={:?} limit={:?} reset={}",
+        meter, license_id, payload.used, payload.limit, payload.reset
+    );
+
+    let license = state
+        .db
+        .get_license(&license_id)
+        .await?
+        .ok_or_else(|| AdminError::NotFound(format!("License {license_id} not found")))?;
+
+    let now = Utc::now().naive_utc();
+    let current = state
+        .db
+        .list_usage_meters(&license_id)
+        .await?
+        .into_iter()
+        .find(|m| m.meter == meter)
+        .unwrap_or_else(|| UsageMeter::empty(&license_id, &meter, now));
+
+    let mut updated = current.clone();
+    if payload.reset {
+        updated.used = 0;
+    } else if let Some(used) = payload.used {
+        updated.used = to_i64(used, "used")?;
+    }
+    if payload.clear_limit {
+        updated.usage_limit = None;
+    } else if let Some(limit) = payload.limit {
+        updated.usage_limit = Some(to_i64(limit, "limit")?);
+    }
+    updated.updated_at = now;
+    state.db.set_usage_meter(&updated).await?;
+
+    let meters = license_meter_statuses(&*state.db, &license).await?;
+    let status = MeterStatus::new(&updated, license_tier(&license).as_ref());
+    let quota_exceeded = sync_quota_exceeded(
+        &*state.db,
+        &license,
+        &meters,
+        serde_json::json!({
+            "meter": status.meter,
+            "used": status.used,
+            "limit": status.limit,
+        }),
+    )
+    .await?;
+
+    audit
+        .record(
+            &*state.db,
+            AuditAction::UsageUpdated,
+            AuditTarget::License(license_id.clone()),
+            snapshot(&current),
+            snapshot(&updated),
+        )
+        .await;
+
+    Ok(Json(UsageMetersResponse {
+        license_id,
+        quota_exceeded,
+        meters,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn meter(name: &str, used: i64, usage_limit: Option<i64>) -> UsageMeter {
+        UsageMeter {
+            usage_limit,
+            used,
+            ..UsageMeter::empty("lic-1", name, NaiveDateTime::default())
+        }
+    }
+
+    fn tier() -> TierConfig {
+        TierConfig {
+            meters: HashMap::from([("api_calls".to_string(), 100), ("renders".to_string(), 10)]),
+            ..TierConfig::default()
+        }
+    }
+
+    #[test]
+    fn carried_over_usage_can_exceed_the_quota() {
+        let meters = [meter("renders", 0, Some(5)), meter("exports", 3, Some(3))];
+        assert!(quota_exceeded("lic-1", &meters, None));
+        assert!(!quota_exceeded("lic-1", &meters[..1], None));
+        // Meters without a limit never exceed it
+        assert!(!quota_exceeded(
+            "lic-1",
+            &[meter("renders", 50, None)],
+            None
+        ));
+    }
+
+    #[test]
+    fn only_billed_bandwidth_is_subtracted() {
+        let period = |meter: &str, used| UsagePeriod {
+            license_id: "lic-1".to_string(),
+            meter: meter.to_string(),
+            period_start: NaiveDateTime::default(),
+            period_end: NaiveDateTime::default(),
+            used,
+            usage_limit: None,
+        };
+        assert_eq!(
+            billed_bandwidth(&[period("renders", 4), period(BANDWIDTH_METER, 100)]),
+            100
+        );
+        assert_eq!(billed_bandwidth(&[period("renders", 4)]), 0);
+    }
+
+    #[test]
+    fn limit_comes_from_license_then_tier() {
+        let tier = tier();
+        assert_eq!(meter("api_calls", 0, None).limit(Some(&tier)), Some(100));
+        assert_eq!(
+            meter("api_calls", 0, Some(500)).limit(Some(&tier)),
+            Some(500)
+        );
+        // A per-license limit of 0 lifts the tier's limit
+        assert_eq!(meter("api_calls", 0, Some(0)).limit(Some(&tier)), None);
+        assert_eq!(meter("api_calls", 0, None).limit(None), None);
+        assert_eq!(meter("uploads", 0, None).limit(Some(&tier)), None);
+    }
+
+    #[test]
+    fn meter_is_exceeded_once_usage_reaches_limit() {
+        let tier = tier();
+        assert!(!MeterStatus::new(&meter("renders", 9, None), Some(&tier)).exceeded);
+        assert!(MeterStatus::new(&meter("renders", 10, None), Some(&tier)).exceeded);
+        assert!(!MeterStatus::new(&meter("uploads", 1_000, None), Some(&tier)).exceeded);
+    }
+
+    #[test]
+    fn statuses_include_configured_meters_without_usage() {
+        let tier = tier();
+        let statuses = meter_statuses(
+            "lic-1",
+            &[meter("renders", 12, None), meter("uploads", 3, None)],
+            Some(&tier),
+        );
+
+        let names: Vec<&str> = statuses.iter().map(|s| s.meter.as_str()).collect();
+        assert_eq!(names, ["api_calls", "renders", "uploads"]);
+        assert_eq!(statuses[0].used, 0);
+        assert!(!statuses[0].exceeded);
+        assert!(statuses[1].exceeded);
+        assert_eq!(statuses[2].limit, None);
+    }
+}
@@ -216,6 +216,25 @@ pub fn validate_feature_name(value: &str, field_name: &str) -> ValidationResult<
     }
 }
 
+/// Validate a usage meter name.
+///
+/// Meter names follow the same rules as feature names: alphanumeric with
+/// underscores/hyphens, starting with a letter, 1-64 chars.
+pub fn validate_meter_name(value: &str, field_name: &str) -> ValidationResult<()> {
+    let meter_regex = regex::Regex::new(r"^[a-zA-Z][a-zA-Z0-9_-]{0,63}$").unwrap();
+
+    if meter_regex.is_match(value) {
+        Ok(())
+    } else {
+        Err(ValidationError {
+            field: field_name.to_string(),
+            message:
+                "invalid meter name (must start with letter, contain only alphanumeric, underscore, hyphen, max 64 chars)"
+                    .to_string(),
+        })
+    }
+}
+
 /// Validate an organization ID.
 ///
 /// Org IDs are flexible - alphanumeric with hyphens/underscores, 1-128 chars.
@@ -327,6 +346,16 @@ mod tests {
         assert!(validate_feature_name("", "feat").is_err());
     }
 
+    #[test]
+    fn test_validate_meter_name() {
+        assert!(validate_meter_name("api_calls", "meter").is_ok());
+        assert!(validate_meter_name("seat-hours", "meter").is_ok());
+        assert!(validate_meter_name("bandwidth", "meter").is_ok());
+        assert!(validate_meter_name("2fast", "meter").is_err()); // starts with number
+        assert!(validate_meter_name("api calls", "meter").is_err()); // contains space
+        assert!(validate_meter_name(&"m".repeat(65), "meter").is_err());
+    }
+
     #[test]
     fn test_validate_org_id() {
         assert!(validate_org_id("org-123", "org").is_ok());
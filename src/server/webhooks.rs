@@ -0,0 +1,1586 @@
+//! Webhook notifications for license lifecycle events.
+//!
+//! Admins register HTTP endpoints with `POST /api/v1/webhooks`, each
+//! subscribed to a set of events (`license.created`, `license.revoked`, ...
+//! or `*` for all). When `webhooks.enabled` is set, every matching event is
+//! written to the `webhook_deliveries` table and a background dispatcher
+//! POSTs it to the endpoint, retrying with exponential backoff until it
+//! succeeds or `webhooks.max_attempts` is reached. The table doubles as the
+//! delivery log (`GET /api/v1/webhooks/{id}/deliveries`).
+//!
+//! Each request carries these headers:
+//!
+//! - `X-Talos-Event` - event name, e.g. `license.revoked`
+//! - `X-Talos-Delivery` - delivery ID, stable across retries
+//! - `X-Talos-Signature` - `sha256=<hex>` HMAC-SHA256 of the body, keyed with
+//!   the webhook secret (see [`verify_signature`])
+//!
+//! Delivery is at-least-once; receivers should deduplicate on the `id` field
+//! of the payload.
+//!
+//! Webhooks can't target loopback, private, link-local or other non-public
+//! addresses (such as the `169.254.169.254` cloud metadata service) unless
+//! `webhooks.allow_private_targets` is set. URLs are checked when a webhook
+//! is saved, host names are checked again when they are resolved for each
+//! delivery, and redirects are only followed to targets that pass the same
+//! checks (see [`is_private_address`]).
+//!
+//! # Usage
+//!
+//! ```rust,ignore
+//! use talos::server::webhooks::{self, WebhookDispatcher};
+//!
+//! // Queue an event (no-op unless webhooks are enabled)
+//! webhooks::notify(&*db, LicenseEvent::Revoked, &license_id, None).await;
+//!
+//! // Deliver queued events in the background
+//! let dispatcher = WebhookDispatcher::start(db.clone(), config.webhooks.clone())?;
+//! // ...
+//! dispatcher.shutdown().await;
+//! ```
+
+use std::collections::HashMap;
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::sync::Arc;
+use std::time::Duration;
+
+use axum::async_trait;
+use chrono::{NaiveDateTime, Utc};
+use rand::Rng;
+use ring::hmac;
+use serde::Serialize;
+use serde_json::{json, Value};
+use sqlx::FromRow;
+use tokio::sync::oneshot;
+use tokio::task::JoinHandle;
+use tokio::time::MissedTickBehavior;
+use tracing::{debug, info, warn};
+use uuid::Uuid;
+
+#[cfg(feature = "openapi")]
+use utoipa::ToSchema;
+
+#[cfg(feature = "admin-api")]
+use crate::config::is_webhook_private_targets_allowed;
+use crate::config::{is_webhooks_enabled, WebhookConfig};
+use crate::errors::{LicenseError, LicenseResult};
+use crate::server::database::Database;
+use crate::server::logging::LicenseEvent;
+use crate::server::store::{LicenseStore, WebhookStore};
+
+#[cfg(feature = "admin-api")]
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    Json,
+};
+#[cfg(feature = "admin-api")]
+use serde::Deserialize;
+
+#[cfg(feature = "admin-api")]
+use crate::server::admin::AdminError;
+#[cfg(feature = "admin-api")]
+use crate::server::audit::{snapshot, AuditAction, AuditContext, AuditTarget};
+#[cfg(feature = "admin-api")]
+use crate::server::handlers::AppState;
+
+/// Header carrying the event name.
+pub const EVENT_HEADER: &str = "X-Talos-Event";
+
+/// Header carrying the delivery ID.
+pub const DELIVERY_HEADER: &str = "X-Talos-Delivery";
+
+/// Header carrying the `sha256=<hex>` payload signature.
+pub const SIGNATURE_HEADER: &str = "X-Talos-Signature";
+
+/// Subscription that matches every event.
+pub const ALL_EVENTS: &str = "*";
+
+/// License events that can be delivered to webhooks.
+pub const WEBHOOK_EVENTS: &[LicenseEvent] = &[
+    LicenseEvent::Created,
+    LicenseEvent::Bound,
+    LicenseEvent::Released,
+    LicenseEvent::Revoked,
+    LicenseEvent::Suspended,
+    LicenseEvent::Reinstated,
+    LicenseEvent::Blacklisted,
+    LicenseEvent::Expired,
+    LicenseEvent::QuotaExceeded,
+];
+
+/// Most deliveries handled per dispatcher pass.
+const DELIVERY_BATCH_SIZE: u32 = 100;
+
+/// Longest error message kept in the delivery log.
+const MAX_ERROR_LEN: usize = 500;
+
+/// Event name used in payloads and subscriptions, e.g. `license.revoked`.
+pub fn event_name(event: LicenseEvent) -> String {
+    format!("license.{event}")
+}
+
+/// Whether `name` is an event webhooks can subscribe to.
+pub fn is_known_event(name: &str) -> bool {
+    name == ALL_EVENTS || WEBHOOK_EVENTS.iter().any(|e| event_name(*e) == name)
+}
+
+/// State of a row in `webhook_deliveries`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeliveryStatus {
+    /// Waiting for its first or next attempt
+    Pending,
+    /// The endpoint answered with a 2xx status
+    Delivered,
+    /// All attempts failed, or the webhook was removed or disabled
+    Failed,
+}
+
+impl DeliveryStatus {
+    /// Name stored in the `status` column.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DeliveryStatus::Pending => "pending",
+            DeliveryStatus::Delivered => "delivered",
+            DeliveryStatus::Failed => "failed",
+        }
+    }
+}
+
+impl std::fmt::Display for DeliveryStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A row of the `webhooks` table.
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct Webhook {
+    pub id: String,
+    pub url: String,
+    /// HMAC key for payload signatures, only returned when the webhook is created
+    #[serde(skip_serializing)]
+    pub secret: String,
+    /// JSON array of subscribed event names
+    pub events: String,
+    pub description: Option<String>,
+    pub is_active: bool,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+impl Webhook {
+    /// Subscribed event names.
+    pub fn event_names(&self) -> Vec<String> {
+        serde_json::from_str(&self.events).unwrap_or_default()
+    }
+
+    /// Whether this webhook wants `event` (e.g. `license.bound`).
+    pub fn subscribes_to(&self, event: &str) -> bool {
+        self.event_names()
+            .iter()
+            .any(|name| name == ALL_EVENTS || name == event)
+    }
+}
+
+/// A row of the `webhook_deliveries` table.
+#[derive(Debug, Clone, Serialize, FromRow)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct WebhookDelivery {
+    pub id: String,
+    pub webhook_id: String,
+    /// ID of the event, shared by the deliveries of one event to several webhooks
+    pub event_id: String,
+    pub event: String,
+    /// JSON body sent to the endpoint
+    pub payload: String,
+    /// `pending`, `delivered` or `failed`
+    pub status: String,
+    pub attempts: i32,
+    /// When the next attempt is due (pending deliveries only)
+    pub next_attempt_at: Option<NaiveDateTime>,
+    pub last_attempt_at: Option<NaiveDateTime>,
+    /// HTTP status of the last attempt, if the endpoint answered
+    pub response_status: Option<i32>,
+    pub last_error: Option<String>,
+    pub created_at: NaiveDateTime,
+    pub delivered_at: Option<NaiveDateTime>,
+}
+
+/// Generate a random webhook signing secret.
+pub fn generate_secret() -> String {
+    let bytes: [u8; 32] = rand::rng().random();
+    format!("whsec_{}", hex::encode(bytes))
+}
+
+/// Compute the `X-Talos-Signature` header value for a payload.
+pub fn sign_payload(secret: &str, body: &[u8]) -> String {
+    let key = hmac::Key::new(hmac::HMAC_SHA256, secret.as_bytes());
+    let tag = hmac::sign(&key, body);
+    format!("sha256={}", hex::encode(tag.as_ref()))
+}
+
+/// Check an `X-Talos-Signature` header value against a payload.
+///
+/// The comparison runs in constant time.
+pub fn verify_signature(secret: &str, body: &[u8], signature: &str) -> bool {
+    let Some(tag) = signature
+        .strip_prefix("sha256=")
+        .and_then(|hex_tag| hex::decode(hex_tag).ok())
+    else {
+        return false;
+    };
+
+    let key = hmac::Key::new(hmac::HMAC_SHA256, secret.as_bytes());
+    hmac::verify(&key, body, &tag).is_ok()
+}
+
+/// Delay before the next attempt after `attempts` failed ones.
+///
+/// Starts at `retry_base_secs` and doubles per failure, capped at
+/// `max_retry_delay_secs`.
+pub fn retry_delay(config: &WebhookConfig, attempts: u32) -> Duration {
+    let factor = 2u64.saturating_pow(attempts.saturating_sub(1));
+    let secs = config
+        .retry_base_secs
+        .saturating_mul(factor)
+        .min(config.max_retry_delay_secs);
+    Duration::from_secs(secs)
+}
+
+/// Queue `event` for every active webhook subscribed to it.
+///
+/// Does nothing unless webhooks are enabled or when the event is not one of
+/// [`WEBHOOK_EVENTS`]. `details` is included in the payload next to a summary
+/// of the license. Failures are logged and do not affect the caller.
+pub async fn notify(
+    db: &dyn LicenseStore,
+    event: LicenseEvent,
+    license_id: &str,
+    details: Option<Value>,
+) {
+    if !is_webhooks_enabled() || !WEBHOOK_EVENTS.contains(&event) {
+        return;
+    }
+
+    if let Err(e) = enqueue_event(db, event, license_id, details).await {
+        warn!(
+            event = %event,
+            license_id = %license_id,
+            "Failed to queue webhook deliveries: {e}"
+        );
+    }
+}
+
+async fn enqueue_event(
+    db: &dyn LicenseStore,
+    event: LicenseEvent,
+    license_id: &str,
+    details: Option<Value>,
+) -> LicenseResult<()> {
+    let name = event_name(event);
+    let webhooks: Vec<Webhook> = db
+        .list_webhooks()
+        .await?
+        .into_iter()
+        .filter(|w| w.is_active && w.subscribes_to(&name))
+        .collect();
+
+    if webhooks.is_empty() {
+        return Ok(());
+    }
+
+    let license = match db.get_license(license_id).await? {
+        Some(license) => json!({
+            "license_id": license.license_id,
+            "license_key": license.license_key,
+            "org_id": license.org_id,
+            "tier": license.tier,
+            "status": license.status,
+            "expires_at": license.expires_at.map(|d| d.and_utc().to_rfc3339()),
+        }),
+        None => json!({ "license_id": license_id }),
+    };
+
+    let now = Utc::now().naive_utc();
+    let event_id = Uuid::new_v4().to_string();
+    let mut data = json!({ "license": license });
+    if let Some(details) = details {
+        data["details"] = details;
+    }
+    let payload = json!({
+        "id": event_id,
+        "event": name,
+        "occurred_at": now.and_utc().to_rfc3339(),
+        "data": data,
+    })
+    .to_string();
+
+    for webhook in webhooks {
+        let delivery = WebhookDelivery {
+            id: Uuid::new_v4().to_string(),
+            webhook_id: webhook.id,
+            event_id: event_id.clone(),
+            event: name.clone(),
+            payload: payload.clone(),
+            status: DeliveryStatus::Pending.to_string(),
+            attempts: 0,
+            next_attempt_at: Some(now),
+            last_attempt_at: None,
+            response_status: None,
+            last_error: None,
+            created_at: now,
+            delivered_at: None,
+        };
+        db.insert_webhook_delivery(&delivery).await?;
+    }
+
+    Ok(())
+}
+
+/// Whether an address is loopback, private, link-local or otherwise not
+/// reachable on the public internet.
+///
+/// Webhooks may not deliver to these addresses unless
+/// `webhooks.allow_private_targets` is set.
+pub fn is_private_address(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => is_private_v4(ip),
+        IpAddr::V6(ip) => match ip.to_ipv4_mapped() {
+            Some(ip) => is_private_v4(ip),
+            None => {
+                let first = ip.segments()[0];
+                ip.is_loopback()
+                    || ip.is_unspecified()
+                    || ip.is_multicast()
+                    // Unique local (fc00::/7) and link-local (fe80::/10)
+                    || first & 0xfe00 == 0xfc00
+                    || first & 0xffc0 == 0xfe80
+            }
+        },
+    }
+}
+
+fn is_private_v4(ip: Ipv4Addr) -> bool {
+    let [first, second, ..] = ip.octets();
+    ip.is_private()
+        || ip.is_loopback()
+        || ip.is_link_local()
+        || ip.is_unspecified()
+        || ip.is_broadcast()
+        || ip.is_multicast()
+        // "This network" (0.0.0.0/8) and carrier-grade NAT (100.64.0.0/10)
+        || first == 0
+        || (first == 100 && second & 0xc0 == 64)
+}
+
+/// Check that a webhook URL is http(s) and, unless `allow_private` is set,
+/// that its host is not `localhost` or a private address.
+///
+/// Host names are only checked by their resolved addresses at delivery time.
+pub fn check_target_url(url: &str, allow_private: bool) -> Result<(), String> {
+    let parsed = reqwest::Url::parse(url).map_err(|e| format!("invalid url: {e}"))?;
+    if !matches!(parsed.scheme(), "http" | "https") {
+        return Err("url must start with http:// or https://".to_string());
+    }
+    if allow_private {
+        return Ok(());
+    }
+
+    let Some(host) = parsed.host_str() else {
+        return Err("url must have a host".to_string());
+    };
+    // IPv6 hosts keep their brackets; IPv4 hosts are already normalized
+    let private = match host.trim_start_matches('[').trim_end_matches(']').parse() {
+        Ok(ip) => is_private_address(ip),
+        Err(_) => {
+            let host = host.trim_end_matches('.');
+            host == "localhost" || host.ends_with(".localhost")
+        }
+    };
+    if private {
+        Err("url must not point to a loopback, private or link-local address".to_string())
+    } else {
+        Ok(())
+    }
+}
+
+/// Resolves delivery hosts, dropping private addresses so a public name
+/// can't be pointed at an internal service.
+struct PublicResolver;
+
+impl reqwest::dns::Resolve for PublicResolver {
+    fn resolve(&self, name: reqwest::dns::Name) -> reqwest::dns::Resolving {
+        Box::pin(async move {
+            let host = name.as_str();
+            let addrs: Vec<SocketAddr> = tokio::net::lookup_host((host, 0))
+                .await?
+                .filter(|addr| !is_private_address(addr.ip()))
+                .collect();
+            if addrs.is_empty() {
+                return Err(format!("{host} has no public address").into());
+            }
+            let addrs: reqwest::dns::Addrs = Box::new(addrs.into_iter());
+            Ok(addrs)
+        })
+    }
+}
+
+/// Redirects followed per delivery, as with reqwest's default policy.
+const MAX_REDIRECTS: usize = 10;
+
+/// Build the HTTP client used for deliveries.
+///
+/// Unless `allow_private_targets` is set, host names that resolve only to
+/// private addresses are refused, and so are redirects to private targets.
+pub fn delivery_client(config: &WebhookConfig) -> LicenseResult<reqwest::Client> {
+    let mut builder = reqwest::Client::builder()
+        .timeout(Duration::from_secs(config.timeout_secs))
+        .user_agent(concat!("talos-webhooks/", env!("CARGO_PKG_VERSION")));
+    if !config.allow_private_targets {
+        // IP literals in a Location header never reach the resolver, so each
+        // hop is checked like the webhook's own URL
+        let redirects = reqwest::redirect::Policy::custom(|attempt| {
+            if attempt.previous().len() >= MAX_REDIRECTS {
+                attempt.error("too many redirects")
+            } else if let Err(error) = check_target_url(attempt.url().as_str(), false) {
+                attempt.error(format!("redirect refused: {error}"))
+            } else {
+                attempt.follow()
+            }
+        });
+        builder = builder
+            .dns_resolver(Arc::new(PublicResolver))
+            .redirect(redirects);
+    }
+    builder
+        .build()
+        .map_err(|e| LicenseError::ConfigError(format!("failed to build webhook client: {e}")))
+}
+
+/// Attempt every delivery that is due now.
+///
+/// Returns the number of deliveries that succeeded. `talos_server` calls this
+/// from [`WebhookDispatcher`]; call it directly to flush the queue on demand.
+pub async fn deliver_due_webhooks(
+    db: &dyn LicenseStore,
+    client: &reqwest::Client,
+    config: &WebhookConfig,
+) -> LicenseResult<u32> {
+    let now = Utc::now().naive_utc();
+    let due = db.due_webhook_deliveries(now, DELIVERY_BATCH_SIZE).await?;
+
+    let mut webhooks: HashMap<String, Option<Webhook>> = HashMap::new();
+    let mut delivered = 0;
+
+    for mut delivery in due {
+        let webhook = match webhooks.get(&delivery.webhook_id) {
+            Some(webhook) => webhook.clone(),
+            None => {
+                let webhook = db.get_webhook(&delivery.webhook_id).await?;
+                webhooks.insert(delivery.webhook_id.clone(), webhook.clone());
+                webhook
+            }
+        };
+
+        let attempted_at = Utc::now().naive_utc();
+        delivery.last_attempt_at = Some(attempted_at);
+
+        let Some(webhook) = webhook.filter(|w| w.is_active) else {
+            delivery.status = DeliveryStatus::Failed.to_string();
+            delivery.next_attempt_at = None;
+            delivery.last_error = Some("webhook was removed or disabled".to_string());
+            db.update_webhook_delivery(&delivery).await?;
+            continue;
+        };
+
+        delivery.attempts += 1;
+        let outcome = match check_target_url(&webhook.url, config.allow_private_targets) {
+            Ok(()) => send_delivery(client, &webhook, &delivery).await,
+            Err(error) => Err((None, error)),
+        };
+
+        match outcome {
+            Ok(status) => {
+                delivery.status = DeliveryStatus::Delivered.to_string();
+                delivery.response_status = Some(i32::from(status));
+                delivery.next_attempt_at = None;
+                delivery.last_error = None;
+                delivery.delivered_at = Some(attempted_at);
+                delivered += 1;
+                debug!(
+                    "Delivered {} to webhook {} (delivery {})",
+                    delivery.event, webhook.id, delivery.id
+                );
+            }
+            Err((status, error)) => {
+                delivery.response_status = status.map(i32::from);
+                delivery.last_error = Some(truncate(&error, MAX_ERROR_LEN));
+
+                let attempts = u32::try_from(delivery.attempts).unwrap_or(u32::MAX);
+                if attempts >= config.max_attempts {
+                    delivery.status = DeliveryStatus::Failed.to_string();
+                    delivery.next_attempt_at = None;
+                    warn!(
+                        "Giving up on {} for webhook {} after {} attempts: {}",
+                        delivery.event, webhook.id, attempts, error
+                    );
+                } else {
+                    let delay = chrono::Duration::from_std(retry_delay(config, attempts))
+                        .unwrap_or(chrono::Duration::MAX);
+                    delivery.next_attempt_at = Some(attempted_at + delay);
+                    debug!(
+                        "Webhook {} delivery {} failed (attempt {}): {}",
+                        webhook.id, delivery.id, attempts, error
+                    );
+                }
+            }
+        }
+
+        db.update_webhook_delivery(&delivery).await?;
+    }
+
+    Ok(delivered)
+}
+
+/// POST one delivery. Returns the status code, or the status (if any) and
+/// an error message on failure.
+async fn send_delivery(
+    client: &reqwest::Client,
+    webhook: &Webhook,
+    delivery: &WebhookDelivery,
+) -> Result<u16, (Option<u16>, String)> {
+    let body = delivery.payload.as_bytes();
+
+    let response = client
+        .post(&webhook.url)
+        .header(reqwest::header::CONTENT_TYPE, "application/json")
+        .header(EVENT_HEADER, &delivery.event)
+        .header(DELIVERY_HEADER, &delivery.id)
+        .header(SIGNATURE_HEADER, sign_payload(&webhook.secret, body))
+        .body(delivery.payload.clone())
+        .send()
+        .await
+        .map_err(|e| (None, e.to_string()))?;
+
+    let status = response.status();
+    if status.is_success() {
+        Ok(status.as_u16())
+    } else {
+        Err((
+            Some(status.as_u16()),
+            format!("endpoint responded with {status}"),
+        ))
+    }
+}
+
+fn truncate(s: &str, max: usize) -> String {
+    match s.char_indices().nth(max) {
+        Some((idx, _)) => s[..idx].to_string(),
+        None => s.to_string(),
+    }
+}
+
+/// Delivers queued webhook events in a background task.
+///
+/// Every `poll_interval_secs` it runs [`deliver_due_webhooks`]. Deliveries
+/// that are due while the dispatcher is stopped are sent once it runs again.
+#[derive(Debug)]
+pub struct WebhookDispatcher {
+    stop: Option<oneshot::Sender<()>>,
+    task: JoinHandle<()>,
+}
+
+impl WebhookDispatcher {
+    /// Start delivering webhooks from `db`.
+    pub fn start(db: Arc<dyn LicenseStore>, config: WebhookConfig) -> LicenseResult<Self> {
+        let client = delivery_client(&config)?;
+        let (stop, mut stop_rx) = oneshot::channel();
+
+        info!(
+            "Webhook dispatcher started (poll interval: {}s, max attempts: {})",
+            config.poll_interval_secs, config.max_attempts
+        );
+
+        let task = tokio::spawn(async move {
+            let mut ticker =
+                tokio::time::interval(Duration::from_secs(config.poll_interval_secs.max(1)));
+            ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+            loop {
+                tokio::select! {
+                    _ = &mut stop_rx => break,
+                    _ = ticker.tick() => {}
+                }
+
+                if let Err(e) = deliver_due_webhooks(&*db, &client, &config).await {
+                    warn!("Webhook delivery pass failed: {}", e);
+                }
+            }
+        });
+
+        Ok(Self {
+            stop: Some(stop),
+            task,
+        })
+    }
+
+    /// Stop the dispatcher, letting an in-flight pass finish.
+    pub async fn shutdown(mut self) {
+        if let Some(stop) = self.stop.take() {
+            let _ = stop.send(());
+        }
+        let _ = (&mut self.task).await;
+    }
+}
+
+impl Drop for WebhookDispatcher {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+// ============================================================================
+// Database
+// ============================================================================
+
+const WEBHOOK_COLUMNS: &str =
+    "id, url, secret, events, description, is_active, created_at, updated_at";
+
+const DELIVERY_COLUMNS: &str = "id, webhook_id, event_id, event, payload, status, attempts, \
+     next_attempt_at, last_attempt_at, response_status, last_error, created_at, delivered_at";
+
+#[async_trait]
+impl WebhookStore for Database {
+    async fn insert_webhook(&self, webhook: &Webhook) -> LicenseResult<()> {
+        match self {
+            #[cfg(feature = "sqlite")]
+            Database::SQLite(pool) => {
+                sqlx::query(&format!(
+                    "INSERT INTO webhooks ({WEBHOOK_COLUMNS}) VALUES (?, ?, ?, ?, ?, ?, ?, ?)"
+                ))
+                .bind(&webhook.id)
+                .bind(&webhook.url)
+                .bind(&webhook.secret)
+                .bind(&webhook.events)
+                .bind(&webhook.description)
+                .bind(webhook.is_active)
+                .bind(webhook.created_at)
+                .bind(webhook.updated_at)
+                .execute(pool)
+                .await
+                .map_err(|e| LicenseError::ServerError(format!("insert webhook failed: {e}")))?;
+            }
+            #[cfg(feature = "postgres")]
+            Database::Postgres(pool) => {
+                sqlx::query(&format!(
+                    "INSERT INTO webhooks ({WEBHOOK_COLUMNS}) \
+                     VALUES ($1, $2, $3, $4, $5, $6, $7, $8)"
+                ))
+                .bind(&webhook.id)
+                .bind(&webhook.url)
+                .bind(&webhook.secret)
+                .bind(&webhook.events)
+                .bind(&webhook.description)
+                .bind(webhook.is_active)
+                .bind(webhook.created_at)
+                .bind(webhook.updated_at)
+                .execute(pool)
+                .await
+                .map_err(|e| LicenseError::ServerError(format!("insert webhook failed: {e}")))?;
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn update_webhook(&self, webhook: &Webhook) -> LicenseResult<()> {
+        match self {
+            #[cfg(feature = "sqlite")]
+            Database::SQLite(pool) => {
+                sqlx::query(
+                    "UPDATE webhooks SET url = ?, events = ?, description = ?, is_active = ?, \
+                     updated_at = ? WHERE id = ?",
+                )
+                .bind(&webhook.url)
+                .bind(&webhook.events)
+                .bind(&webhook.description)
+                .bind(webhook.is_active)
+                .bind(webhook.updated_at)
+                .bind(&webhook.id)
+                .execute(pool)
+                .await
+                .map_err(|e| LicenseError::ServerError(format!("update webhook failed: {e}")))?;
+            }
+            #[cfg(feature = "postgres")]
+            Database::Postgres(pool) => {
+                sqlx::query(
+                    "UPDATE webhooks SET url = $1, events = $2, description = $3, \
+                     is_active = $4, updated_at = $5 WHERE id = $6",
+                )
+                .bind(&webhook.url)
+                .bind(&webhook.events)
+                .bind(&webhook.description)
+                .bind(webhook.is_active)
+                .bind(webhook.updated_at)
+                .bind(&webhook.id)
+                .execute(pool)
+                .await
+                .map_err(|e| LicenseError::ServerError(format!("update webhook failed: {e}")))?;
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn get_webhook(&self, webhook_id: &str) -> LicenseResult<Option<Webhook>> {
+        match self {
+            #[cfg(feature = "sqlite")]
+            Database::SQLite(pool) => sqlx::query_as::<_, Webhook>(&format!(
+                "SELECT {WEBHOOK_COLUMNS} FROM webhooks WHERE id = ?"
+            ))
+            .bind(webhook_id)
+            .fetch_optional(pool)
+            .await
+            .map_err(|e| LicenseError::ServerError(format!("get webhook failed: {e}"))),
+            #[cfg(feature = "postgres")]
+            Database::Postgres(pool) => sqlx::query_as::<_, Webhook>(&format!(
+                "SELECT {WEBHOOK_COLUMNS} FROM webhooks WHERE id = $1"
+            ))
+            .bind(webhook_id)
+            .fetch_optional(pool)
+            .await
+            .map_err(|e| LicenseError::ServerError(format!("get webhook failed: {e}"))),
+        }
+    }
+
+    async fn list_webhooks(&self) -> LicenseResult<Vec<Webhook>> {
+        let sql = format!("SELECT {WEBHOOK_COLUMNS} FROM webhooks ORDER BY created_at, id");
+
+        match self {
+            #[cfg(feature = "sqlite")]
+            Database::SQLite(pool) => sqlx::query_as::<_, Webhook>(&sql)
+                .fetch_all(pool)
+                .await
+                .map_err(|e| LicenseError::ServerError(format!("list webhooks failed: {e}"))),
+            #[cfg(feature = "postgres")]
+            Database::Postgres(pool) => sqlx::query_as::<_, Webhook>(&sql)
+                .fetch_all(pool)
+                .await
+                .map_err(|e| LicenseError::ServerError(format!("list webhooks failed: {e}"))),
+        }
+    }
+
+    async fn delete_webhook(&self, webhook_id: &str) -> LicenseResult<bool> {
+        let result = match self {
+            #[cfg(feature = "sqlite")]
+            Database::SQLite(pool) => {
+                sqlx::query("DELETE FROM webhook_deliveries WHERE webhook_id = ?")
+                    .bind(webhook_id)
+                    .execute(pool)
+                    .await
+                    .map_err(|e| {
+                        LicenseError::ServerError(format!("delete webhook deliveries failed: {e}"))
+                    })?;
+                sqlx::query("DELETE FROM webhooks WHERE id = ?")
+                    .bind(webhook_id)
+                    .execute(pool)
+                    .await
+                    .map_err(|e| LicenseError::ServerError(format!("delete webhook failed: {e}")))?
+                    .rows_affected()
+            }
+            #[cfg(feature = "postgres")]
+            Database::Postgres(pool) => {
+                sqlx::query("DELETE FROM webhook_deliveries WHERE webhook_id = $1")
+                    .bind(webhook_id)
+                    .execute(pool)
+                    .await
+                    .map_err(|e| {
+                        LicenseError::ServerError(format!("delete webhook deliveries failed: {e}"))
+                    })?;
+                sqlx::query("DELETE FROM webhooks WHERE id = $1")
+                    .bind(webhook_id)
+                    .execute(pool)
+                    .await
+                    .map_err(|e| LicenseError::ServerError(format!("delete webhook failed: {e}")))?
+                    .rows_affected()
+            }
+        };
+
+        Ok(result > 0)
+    }
+
+    async fn insert_webhook_delivery(&self, delivery: &WebhookDelivery) -> LicenseResult<()> {
+        match self {
+            #[cfg(feature = "sqlite")]
+            Database::SQLite(pool) => {
+                sqlx::query(&format!(
+                    "INSERT INTO webhook_deliveries ({DELIVERY_COLUMNS}) \
+                     VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"
+                ))
+                .bind(&delivery.id)
+                .bind(&delivery.webhook_id)
+                .bind(&delivery.event_id)
+                .bind(&delivery.event)
+                .bind(&delivery.payload)
+                .bind(&delivery.status)
+                .bind(delivery.attempts)
+                .bind(delivery.next_attempt_at)
+                .bind(delivery.last_attempt_at)
+                .bind(delivery.response_status)
+                .bind(&delivery.last_error)
+                .bind(delivery.created_at)
+                .bind(delivery.delivered_at)
+                .execute(pool)
+                .await
+                .map_err(|e| {
+                    LicenseError::ServerError(format!("insert webhook delivery failed: {e}"))
+                })?;
+            }
+            #[cfg(feature = "postgres")]
+            Database::Postgres(pool) => {
+                sqlx::query(&format!(
+                    "INSERT INTO webhook_deliveries ({DELIVERY_COLUMNS}) \
+                     VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)"
+                ))
+                .bind(&delivery.id)
+                .bind(&delivery.webhook_id)
+                .bind(&delivery.event_id)
+                .bind(&delivery.event)
+                .bind(&delivery.payload)
+                .bind(&delivery.status)
+                .bind(delivery.attempts)
+                .bind(delivery.next_attempt_at)
+                .bind(delivery.last_attempt_at)
+                .bind(delivery.response_status)
+                .bind(&delivery.last_error)
+                .bind(delivery.created_at)
+                .bind(delivery.delivered_at)
+                .execute(pool)
+                .await
+                .map_err(|e| {
+                    LicenseError::ServerError(format!("insert webhook delivery failed: {e}"))
+                })?;
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn update_webhook_delivery(&self, delivery: &WebhookDelivery) -> LicenseResult<()> {
+        match self {
+            #[cfg(feature = "sqlite")]
+            Database::SQLite(pool) => {
+                sqlx::query(
+                    "UPDATE webhook_deliveries SET status = ?, attempts = ?, \
+                     next_attempt_at = ?, last_attempt_at = ?, response_status = ?, \
+                     last_error = ?, delivered_at = ? WHERE id = ?",
+                )
+                .bind(&delivery.status)
+                .bind(delivery.attempts)
+                .bind(delivery.next_attempt_at)
+                .bind(delivery.last_attempt_at)
+                .bind(delivery.response_status)
+                .bind(&delivery.last_error)
+                .bind(delivery.delivered_at)
+                .bind(&delivery.id)
+                .execute(pool)
+                .await
+                .map_err(|e| {
+                    LicenseError::ServerError(format!("update webhook delivery failed: {e}"))
+                })?;
+            }
+            #[cfg(feature = "postgres")]
+            Database::Postgres(pool) => {
+                sqlx::query(
+                    "UPDATE webhook_deliveries SET status = $1, attempts = $2, \
+                     next_attempt_at = $3, last_attempt_at = $4, response_status = $5, \
+                     last_error = $6, delivered_at = $7 WHERE id = $8",
+                )
+                .bind(&delivery.status)
+                .bind(delivery.attempts)
+                .bind(delivery.next_attempt_at)
+                .bind(delivery.last_attempt_at)
+                .bind(delivery.response_status)
+                .bind(&delivery.last_error)
+                .bind(delivery.delivered_at)
+                .bind(&delivery.id)
+                .execute(pool)
+                .await
+                .map_err(|e| {
+                    LicenseError::ServerError(format!("update webhook delivery failed: {e}"))
+                })?;
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn due_webhook_deliveries(
+        &self,
+        now: NaiveDateTime,
+        limit: u32,
+    ) -> LicenseResult<Vec<WebhookDelivery>> {
+        match self {
+            #[cfg(feature = "sqlite")]
+            Database::SQLite(pool) => sqlx::query_as::<_, WebhookDelivery>(&format!(
+                "SELECT {DELIVERY_COLUMNS} FROM webhook_deliveries \
+                 WHERE status = 'pending' AND next_attempt_at <= ? \
+                 ORDER BY next_attempt_at, id LIMIT ?"
+            ))
+            .bind(now)
+            .bind(i64::from(limit))
+            .fetch_all(pool)
+            .await
+            .map_err(|e| LicenseError::ServerError(format!("list due deliveries failed: {e}"))),
+            #[cfg(feature = "postgres")]
+            Database::Postgres(pool) => sqlx::query_as::<_, WebhookDelivery>(&format!(
+                "SELECT {DELIVERY_COLUMNS} FROM webhook_deliveries \
+                 WHERE status = 'pending' AND next_attempt_at <= $1 \
+                 ORDER BY next_attempt_at, id LIMIT $2"
+            ))
+            .bind(now)
+            .bind(i64::from(limit))
+            .fetch_all(pool)
+            .await
+            .map_err(|e| LicenseError::ServerError(format!("list due deliveries failed: {e}"))),
+        }
+    }
+
+    async fn list_webhook_deliveries(
+        &self,
+        webhook_id: &str,
+        status: Option<&str>,
+        limit: u32,
+        offset: u32,
+    ) -> LicenseResult<(Vec<WebhookDelivery>, u64)> {
+        match self {
+            #[cfg(feature = "sqlite")]
+            Database::SQLite(pool) => {
+                const WHERE: &str = "webhook_id = ? AND (? IS NULL OR status = ?)";
+
+                let total: (i64,) = sqlx::query_as(&format!(
+                    "SELECT COUNT(*) FROM webhook_deliveries WHERE {WHERE}"
+                ))
+                .bind(webhook_id)
+                .bind(status)
+                .bind(status)
+                .fetch_one(pool)
+                .await
+                .map_err(|e| {
+                    LicenseError::ServerError(format!("count webhook deliveries failed: {e}"))
+                })?;
+
+                let deliveries = sqlx::query_as::<_, WebhookDelivery>(&format!(
+                    "SELECT {DELIVERY_COLUMNS} FROM webhook_deliveries WHERE {WHERE} \
+                     ORDER BY created_at DESC, id LIMIT ? OFFSET ?"
+                ))
+                .bind(webhook_id)
+                .bind(status)
+                .bind(status)
+                .bind(i64::from(limit))
+                .bind(i64::from(offset))
+                .fetch_all(pool)
+                .await
+                .map_err(|e| {
+                    LicenseError::ServerError(format!("list webhook deliveries failed: {e}"))
+                })?;
+
+                Ok((deliveries, total.0 as u64))
+            }
+            #[cfg(feature = "postgres")]
+            Database::Postgres(pool) => {
+                const WHERE: &str = "webhook_id = $1 AND ($2::TEXT IS NULL OR status = $2)";
+
+                let total: (i64,) = sqlx::query_as(&format!(
+                    "SELECT COUNT(*) FROM webhook_deliveries WHERE {WHERE}"
+                ))
+                .bind(webhook_id)
+                .bind(status)
+                .fetch_one(pool)
+                .await
+                .map_err(|e| {
+                    LicenseError::ServerError(format!("count webhook deliveries failed: {e}"))
+                })?;
+
+                let deliveries = sqlx::query_as::<_, WebhookDelivery>(&format!(
+                    "SELECT {DELIVERY_COLUMNS} FROM webhook_deliveries WHERE {WHERE} \
+                     ORDER BY created_at DESC, id LIMIT $3 OFFSET $4"
+                ))
+                .bind(webhook_id)
+                .bind(status)
+                .bind(i64::from(limit))
+                .bind(i64::from(offset))
+                .fetch_all(pool)
+                .await
+                .map_err(|e| {
+                    LicenseError::ServerError(format!("list webhook deliveries failed: {e}"))
+                })?;
+
+                Ok((deliveries, total.0 as u64))
+            }
+        }
+    }
+}
+
+// ============================================================================
+// HTTP Handlers
+// ============================================================================
+
+/// Maximum page size for `GET /api/v1/webhooks/{id}/deliveries`.
+#[cfg(feature = "admin-api")]
+pub const MAX_DELIVERY_PAGE_SIZE: u32 = 500;
+
+/// Request body for creating a webhook.
+#[cfg(feature = "admin-api")]
+#[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct CreateWebhookRequest {
+    /// Endpoint URL (http or https)
+    pub url: String,
+    /// Event names to deliver, e.g. `license.revoked`, or `*` for all
+    pub events: Vec<String>,
+    /// Free-form description
+    pub description: Option<String>,
+    /// Signing secret (generated when omitted)
+    pub secret: Option<String>,
+}
+
+/// Request body for updating a webhook. Omitted fields are left unchanged.
+#[cfg(feature = "admin-api")]
+#[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct UpdateWebhookRequest {
+    pub url: Option<String>,
+    pub events: Option<Vec<String>>,
+    pub description: Option<String>,
+    /// Pause (`false`) or resume (`true`) deliveries
+    pub is_active: Option<bool>,
+}
+
+/// A webhook as returned by the API. The secret is never included.
+#[cfg(feature = "admin-api")]
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct WebhookResponse {
+    pub id: String,
+    pub url: String,
+    pub events: Vec<String>,
+    pub description: Option<String>,
+    pub is_active: bool,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+#[cfg(feature = "admin-api")]
+impl From<Webhook> for WebhookResponse {
+    fn from(webhook: Webhook) -> Self {
+        Self {
+            events: webhook.event_names(),
+            id: webhook.id,
+            url: webhook.url,
+            description: webhook.description,
+            is_active: webhook.is_active,
+            created_at: webhook.created_at.and_utc().to_rfc3339(),
+            updated_at: webhook.updated_at.and_utc().to_rfc3339(),
+        }
+    }
+}
+
+/// Response from creating a webhook, including the signing secret (only shown once).
+#[cfg(feature = "admin-api")]
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct CreateWebhookResponse {
+    pub webhook: WebhookResponse,
+    pub secret: String,
+}
+
+/// Response for listing webhooks.
+#[cfg(feature = "admin-api")]
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct ListWebhooksResponse {
+    pub webhooks: Vec<WebhookResponse>,
+}
+
+/// Query parameters for listing deliveries.
+#[cfg(feature = "admin-api")]
+#[derive(Debug, Deserialize)]
+pub struct DeliveriesQuery {
+    /// Filter by status (`pending`, `delivered` or `failed`)
+    pub status: Option<String>,
+    /// Pagination: page number (1-indexed)
+    #[serde(default = "default_page")]
+    pub page: u32,
+    /// Pagination: items per page
+    #[serde(default = "default_per_page")]
+    pub per_page: u32,
+}
+
+#[cfg(feature = "admin-api")]
+fn default_page() -> u32 {
+    1
+}
+#[cfg(feature = "admin-api")]
+fn default_per_page() -> u32 {
+    50
+}
+
+/// A delivery as returned by the API, with the payload decoded as JSON.
+#[cfg(feature = "admin-api")]
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct WebhookDeliveryResponse {
+    pub id: String,
+    pub event_id: String,
+    pub event: String,
+    pub payload: Value,
+    pub status: String,
+    pub attempts: i32,
+    pub next_attempt_at: Option<String>,
+    pub last_attempt_at: Option<String>,
+    pub response_status: Option<i32>,
+    pub last_error: Option<String>,
+    pub created_at: String,
+    pub delivered_at: Option<String>,
+}
+
+#[cfg(feature = "admin-api")]
+impl From<WebhookDelivery> for WebhookDeliveryResponse {
+    fn from(delivery: WebhookDelivery) -> Self {
+        let rfc3339 = |dt: Option<NaiveDateTime>| dt.map(|d| d.and_utc().to_rfc3339());
+
+        Self {
+            payload: serde_json::from_str(&delivery.payload).unwrap_or(Value::Null),
+            id: delivery.id,
+            event_id: delivery.event_id,
+            event: delivery.event,
+            status: delivery.status,
+            attempts: delivery.attempts,
+            next_attempt_at: rfc3339(delivery.next_attempt_at),
+            last_attempt_at: rfc3339(delivery.last_attempt_at),
+            response_status: delivery.response_status,
+            last_error: delivery.last_error,
+            created_at: delivery.created_at.and_utc().to_rfc3339(),
+            delivered_at: rfc3339(delivery.delivered_at),
+        }
+    }
+}
+
+/// Response for listing deliveries.
+#[cfg(feature = "admin-api")]
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "openapi", derive(ToSchema))]
+pub struct WebhookDeliveriesResponse {
+    pub deliveries: Vec<WebhookDeliveryResponse>,
+    pub total: u64,
+    pub page: u32,
+    pub per_page: u32,
+    pub total_pages: u64,
+}
+
+#[cfg(feature = "admin-api")]
+fn validate_url(url: &str) -> Result<(), AdminError> {
+    check_target_url(url, is_webhook_private_targets_allowed()).map_err(AdminError::BadRequest)
+}
+
+#[cfg(feature = "admin-api")]
+fn validate_events(events: &[String]) -> Result<String, AdminError> {
+    if events.is_empty() {
+        return Err(AdminError::BadRequest(
+            "at least one event is required".to_string(),
+        ));
+    }
+    if let Some(unknown) = events.iter().find(|e| !is_known_event(e)) {
+        return Err(AdminError::BadRequest(format!("unknown event '{unknown}'")));
+    }
+
+    serde_json::to_string(events).map_err(|e| AdminError::BadRequest(e.to_string()))
+}
+
+#[cfg(feature = "admin-api")]
+async fn require_webhook(state: &AppState, webhook_id: &str) -> Result<Webhook, AdminError> {
+    state
+        .db
+        .get_webhook(webhook_id)
+        .await?
+        .ok_or_else(|| AdminError::NotFound(format!("Webhook {webhook_id} not found")))
+}
+
+/// Register a webhook endpoint.
+///
+/// `POST /api/v1/webhooks`
+#[cfg(feature = "admin-api")]
+#[cfg_attr(feature = "openapi", utoipa::path(
+    post,
+    path = "/api/v1/webhooks",
+    tag = "webhooks",
+    request_body = CreateWebhookRequest,
+    responses(
+        (status = 201, description = "Webhook created", body = CreateWebhookResponse),
+        (status = 400, description = "Invalid URL or event"),
+        (status = 500, description = "Server error"),
+    ),
+    security(("bearer_auth" = []))
+))]
+pub async fn create_webhook_handler(
+    State(state): State<AppState>,
+    audit: AuditContext,
+    Json(payload): Json<CreateWebhookRequest>,
+) -> Result<(StatusCode, Json<CreateWebhookResponse>), AdminError> {
+    validate_url(&payload.url)?;
+    let events = validate_events(&payload.events)?;
+
+    let secret = match payload.secret {
+        Some(secret) if secret.trim().is_empty() => {
+            return Err(AdminError::BadRequest(
+                "secret must not be empty".to_string(),
+            ));
+        }
+        Some(secret) => secret,
+        None => generate_secret(),
+    };
+
+    let now = Utc::now().naive_utc();
+    let webhook = Webhook {
+        id: Uuid::new_v4().to_string(),
+        url: payload.url,
+        secret: secret.clone(),
+        events,
+        description: payload.description,
+        is_active: true,
+        created_at: now,
+        updated_at: now,
+    };
+
+    state.db.insert_webhook(&webhook).await?;
+    info!("Created webhook {} for {}", webhook.id, webhook.url);
+
+    audit
+        .record(
+            &*state.db,
+            AuditAction::WebhookCreated,
+            AuditTarget::Webhook(webhook.id.clone()),
+            None,
+            snapshot(&webhook),
+        )
+        .await;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(CreateWebhookResponse {
+            webhook: webhook.into(),
+            secret,
+        }),
+    ))
+}
+
+/// List webhooks.
+///
+/// `GET /api/v1/webhooks`
+#[cfg(feature = "admin-api")]
+#[cfg_attr(feature = "openapi", utoipa::path(
+    get,
+    path = "/api/v1/webhooks",
+    tag = "webhooks",
+    responses(
+        (status = 200, description = "Registered webhooks", body = ListWebhooksResponse),
+        (status = 500, description = "Server error"),
+    ),
+    security(("bearer_auth" = []))
+))]
+pub async fn list_webhooks_handler(
+    State(state): State<AppState>,
+) -> Result<Json<ListWebhooksResponse>, AdminError> {
+    let webhooks = state.db.list_webhooks().await?;
+
+    Ok(Json(ListWebhooksResponse {
+        webhooks: webhooks.into_iter().map(Into::into).collect(),
+    }))
+}
+
+/// Get a webhook.
+///
+/// `GET /api/v1/webhooks/{webhook_id}`
+#[cfg(feature = "admin-api")]
+#[cfg_attr(feature = "openapi", utoipa::path(
+    get,
+    path = "/api/v1/webhooks/{webhook_id}",
+    tag = "webhooks",
+    params(
+        ("webhook_id" = String, Path, description = "Webhook ID")
+    ),
+    responses(
+        (status = 200, description = "Webhook", body = WebhookResponse),
+        (status = 404, description = "Webhook not found"),
+    ),
+    security(("bearer_auth" = []))
+))]
+pub async fn get_webhook_handler(
+    State(state): State<AppState>,
+    Path(webhook_id): Path<String>,
+) -> Result<Json<WebhookResponse>, AdminError> {
+    let webhook = require_webhook(&state, &webhook_id).await?;
+    Ok(Json(webhook.into()))
+}
+
+/// Update a webhook's URL, events, description or active flag.
+///
+/// `PATCH /api/v1/webhooks/{webhook_id}`
+#[cfg(feature = "admin-api")]
+#[cfg_attr(feature = "openapi", utoipa::path(
+    patch,
+    path = "/api/v1/webhooks/{webhook_id}",
+    tag = "webhooks",
+    params(
+        ("webhook_id" = String, Path, description = "Webhook ID")
+    ),
+    request_body = UpdateWebhookRequest,
+    responses(
+        (status = 200, description = "Webhook updated", body = WebhookResponse),
+        (status = 400, description = "Invalid URL or event"),
+        (status = 404, description = "Webhook not found"),
+    ),
+    security(("bearer_auth" = []))
+))]
+pub async fn update_webhook_handler(
+    State(state): State<AppState>,
+    audit: AuditContext,
+    Path(webhook_id): Path<String>,
+    Json(payload): Json<UpdateWebhookRequest>,
+) -> Result<Json<WebhookResponse>, AdminError> {
+    let mut webhook = require_webhook(&state, &webhook_id).await?;
+    let before = snapshot(&webhook);
+
+    if let Some(url) = payload.url {
+        validate_url(&url)?;
+        webhook.url = url;
+    }
+    if let Some(events) = payload.events {
+        webhook.events = validate_events(&events)?;
+    }
+    if let Some(description) = payload.description {
+        webhook.description = Some(description);
+    }
+    if let Some(is_active) = payload.is_active {
+        webhook.is_active = is_active;
+    }
+    webhook.updated_at = Utc::now().naive_utc();
+
+    state.db.update_webhook(&webhook).await?;
+
+    audit
+        .record(
+            &*state.db,
+            AuditAction::WebhookUpdated,
+            AuditTarget::Webhook(webhook_id),
+            before,
+            snapshot(&webhook),
+        )
+        .await;
+
+    Ok(Json(webhook.into()))
+}
+
+/// Delete a webhook and its delivery log.
+///
+/// `DELETE /api/v1/webhooks/{webhook_id}`
+#[cfg(feature = "admin-api")]
+#[cfg_attr(feature = "openapi", utoipa::path(
+    delete,
+    path = "/api/v1/webhooks/{webhook_id}",
+    tag = "webhooks",
+    params(
+        ("webhook_id" = String, Path, description = "Webhook ID")
+    ),
+    responses(
+        (status = 204, description = "Webhook deleted"),
+        (status = 404, description = "Webhook not found"),
+    ),
+    security(("bearer_auth" = []))
+))]
+pub async fn delete_webhook_handler(
+    State(state): State<AppState>,
+    audit: AuditContext,
+    Path(webhook_id): Path<String>,
+) -> Result<StatusCode, AdminError> {
+    let webhook = require_webhook(&state, &webhook_id).await?;
+
+    state.db.delete_webhook(&webhook_id).await?;
+    info!("Deleted webhook {}", webhook_id);
+
+    audit
+        .record(
+            &*state.db,
+            AuditAction::WebhookDeleted,
+            AuditTarget::Webhook(webhook_id),
+            snapshot(&webhook),
+            None,
+        )
+        .await;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// List deliveries for a webhook, newest first.
+///
+/// `GET /api/v1/webhooks/{webhook_id}/deliveries?status=&page=&per_page=`
+#[cfg(feature = "admin-api")]
+#[cfg_attr(feature = "openapi", utoipa::path(
+    get,
+    path = "/api/v1/webhooks/{webhook_id}/deliveries",
+    tag = "webhooks",
+    params(
+        ("webhook_id" = String, Path, description = "Webhook ID"),
+        ("status" = Option<String>, Query, description = "Filter by status (pending, delivered, failed)"),
+        ("page" = Option<u32>, Query, description = "Page number (1-indexed)"),
+        ("per_page" = Option<u32>, Query, description = "Items per page (max 500)")
+    ),
+    responses(
+        (status = 200, description = "Delivery log", body = WebhookDeliveriesResponse),
+        (status = 400, description = "Invalid filter"),
+        (status = 404, description = "Webhook not found"),
+    ),
+    security(("bearer_auth" = []))
+))]
+pub async fn list_webhook_deliveries_handler(
+    State(state): State<AppState>,
+    Path(webhook_id): Path<String>,
+    Query(query): Query<DeliveriesQuery>,
+) -> Result<Json<WebhookDeliveriesResponse>, AdminError> {
+    if query.page == 0 {
+        return Err(AdminError::BadRequest(
+            "page must be at least 1".to_string(),
+        ));
+    }
+    if query.per_page == 0 || query.per_page > MAX_DELIVERY_PAGE_SIZE {
+        return Err(AdminError::BadRequest(format!(
+            "per_page must be between 1 and {MAX_DELIVERY_PAGE_SIZE}"
+        )));
+    }
+    if let Some(status) = &query.status {
+        let known = [
+            DeliveryStatus::Pending,
+            DeliveryStatus::Delivered,
+            DeliveryStatus::Failed,
+        ];
+        if !known.iter().any(|s| s.as_str() == status) {
+            return Err(AdminError::BadRequest(format!("unknown status '{status}'")));
+        }
+    }
+
+    require_webhook(&state, &webhook_id).await?;
+
+    let offset = (query.page - 1).saturating_mul(query.per_page);
+    let (deliveries, total) = state
+        .db
+        .list_webhook_deliveries(&webhook_id, query.status.as_deref(), query.per_page, offset)
+        .await?;
+
+    Ok(Json(WebhookDeliveriesResponse {
+        deliveries: deliveries.into_iter().map(Into::into).collect(),
+        total,
+        page: query.page,
+        per_page: query.per_page,
+        total_pages: total.div_ceil(u64::from(query.per_page)),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn signature_round_trips() {
+        let body = br#"{"event":"license.revoked"}"#;
+        let signature = sign_payload("whsec_test", body);
+
+        assert!(signature.starts_with("sha256="));
+        assert!(verify_signature("whsec_test", body, &signature));
+        assert!(!verify_signature("whsec_other", body, &signature));
+        assert!(!verify_signature("whsec_test", b"{}", &signature));
+        assert!(!verify_signature("whsec_test", body, "sha256=zz"));
+    }
+
+    #[test]
+    fn retry_delay_doubles_up_to_the_cap() {
+        let config = WebhookConfig {
+            retry_base_secs: 30,
+            max_retry_delay_secs: 100,
+            ..WebhookConfig::default()
+        };
+
+        assert_eq!(retry_delay(&config, 1), Duration::from_secs(30));
+        assert_eq!(retry_delay(&config, 2), Duration::from_secs(60));
+        assert_eq!(retry_delay(&config, 3), Duration::from_secs(100));
+        assert_eq!(retry_delay(&config, 64), Duration::from_secs(100));
+    }
+
+    #[test]
+    fn private_targets_are_rejected_unless_allowed() {
+        for url in [
+            "http://127.0.0.1/hook",
+            "http://localhost:8080/hook",
+            "http://api.localhost/hook",
+            "http://localhost./hook",
+            "http://10.0.0.5/hook",
+            "http://172.16.0.1/hook",
+            "http://192.168.1.10/hook",
+            "http://169.254.169.254/latest/meta-data/",
+            "http://100.64.0.1/hook",
+            "http://0.0.0.0/hook",
+            "http://2130706433/hook",
+            "http://[::1]/hook",
+            "http://[fd00::1]/hook",
+            "http://[fe80::1]/hook",
+            "http://[::ffff:169.254.169.254]/hook",
+        ] {
+            assert!(check_target_url(url, false).is_err(), "{url} was allowed");
+            assert!(check_target_url(url, true).is_ok(), "{url} was rejected");
+        }
+
+        for url in [
+            "https://billing.example.com/talos",
+            "http://93.184.216.34/hook",
+            "http://[2606:4700::1111]/hook",
+        ] {
+            assert!(check_target_url(url, false).is_ok(), "{url} was rejected");
+        }
+
+        assert!(check_target_url("ftp://example.com", true).is_err());
+        assert!(check_target_url("not a url", true).is_err());
+    }
+
+    #[test]
+    fn subscriptions_match_named_and_wildcard_events() {
+        let now = Utc::now().naive_utc();
+        let mut webhook = Webhook {
+            id: "wh-1".to_string(),
+            url: "http://localhost/hook".to_string(),
+            secret: generate_secret(),
+            events: r#"["license.revoked"]"#.to_string(),
+            description: None,
+            is_active: true,
+            created_at: now,
+            updated_at: now,
+        };
+
+        assert!(webhook.subscribes_to("license.revoked"));
+        assert!(!webhook.subscribes_to("license.bound"));
+
+        webhook.events = r#"["*"]"#.to_string();
+        assert!(webhook.subscribes_to("license.bound"));
+    }
+
+    #[test]
+    fn known_events() {
+        assert_eq!(
+            event_name(LicenseEvent::QuotaExceeded),
+            "license.quota_exceeded"
+        );
+        assert!(is_known_event("license.expired"));
+        assert!(is_known_event(ALL_EVENTS));
+        assert!(!is_known_event("license.heartbeat"));
+    }
+}
@@ -0,0 +1,394 @@
+//! Ed25519-signed license documents.
+//!
+//! The server holds an Ed25519 private key and issues a [`SignedLicense`] for a
+//! bound license. The client library verifies it against a public key embedded
+//! in the application, so offline validation does not rely solely on the
+//! hardware-derived cache encryption key.
+//!
+//! The document is a Base64 JSON payload plus a Base64 signature over exactly
+//! those payload bytes, so no canonical JSON encoding is needed:
+//!
+//! ```json
+//! { "payload": "eyJ2ZXJzaW9uIjoxLC...", "signature": "3q2+7w..." }
+//! ```
+//!
+//! # Usage
+//!
+//! ```rust,ignore
+//! use talos::signing::{LicenseSigner, SignedLicensePayload};
+//!
+//! // Server side
+//! let signer = LicenseSigner::from_base64(&private_key_b64)?;
+//! let signed = signer.sign(&payload)?;
+//!
+//! // Client side (public key embedded at build time)
+//! let payload = signed.verify(&PUBLIC_KEY)?;
+//! ```
+
+use base64::engine::general_purpose::STANDARD as B64;
+use base64::Engine;
+use chrono::{DateTime, Utc};
+use ring::rand::SystemRandom;
+use ring::signature::{Ed25519KeyPair, KeyPair, UnparsedPublicKey, ED25519};
+use serde::{Deserialize, Serialize};
+
+use crate::config::SigningConfig;
+use crate::errors::{LicenseError, LicenseResult};
+
+/// Ed25519 public key size in bytes.
+pub const PUBLIC_KEY_SIZE: usize = 32;
+
+/// Current version of the signed payload format.
+pub const PAYLOAD_VERSION: u8 = 1;
+
+/// License data covered by the signature.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SignedLicensePayload {
+    /// Payload format version
+    pub version: u8,
+    /// Server-side license identifier
+    pub license_id: String,
+    /// Human-readable license key
+    pub license_key: String,
+    /// Hardware fingerprint the license is bound to
+    pub hardware_id: String,
+    /// Features enabled for this license
+    pub features: Vec<String>,
+    /// License tier name
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tier: Option<String>,
+    /// License expiration (RFC 3339, None = never)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<String>,
+    /// End of the offline grace window (RFC 3339)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub grace_period_ends_at: Option<String>,
+    /// End of the offline allowance granted when the document was issued (RFC 3339)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub offline_valid_until: Option<String>,
+    /// When the document was issued (RFC 3339)
+    pub issued_at: String,
+}
+
+impl SignedLicensePayload {
+    /// Check if the license itself has expired.
+    pub fn is_license_expired(&self) -> bool {
+        match &self.expires_at {
+            Some(expires) => match DateTime::parse_from_rfc3339(expires) {
+                Ok(exp_time) => Utc::now() >= exp_time.with_timezone(&Utc),
+                Err(_) => true, // Signed data we can't parse is treated as expired
+            },
+            None => false,
+        }
+    }
+
+    /// When offline use ends: the later of the offline allowance and the
+    /// grace window.
+    pub fn offline_deadline(&self) -> Option<DateTime<Utc>> {
+        latest_deadline([
+            self.offline_valid_until.as_deref(),
+            self.grace_period_ends_at.as_deref(),
+        ])
+    }
+
+    /// Check if the license may still be used offline.
+    ///
+    /// Returns `false` if neither an offline allowance nor a grace window
+    /// is open (online validation required).
+    pub fn is_valid_for_offline(&self) -> bool {
+        self.offline_deadline()
+            .is_some_and(|deadline| Utc::now() < deadline)
+    }
+
+    /// Check if a specific feature is enabled.
+    pub fn has_feature(&self, feature: &str) -> bool {
+        self.features.iter().any(|f| f == feature)
+    }
+}
+
+/// Latest of several RFC 3339 timestamps; unparseable ones are ignored.
+pub(crate) fn latest_deadline<'a>(
+    timestamps: impl IntoIterator<Item = Option<&'a str>>,
+) -> Option<DateTime<Utc>> {
+    timestamps
+        .into_iter()
+        .flatten()
+        .filter_map(|ts| DateTime::parse_from_rfc3339(ts).ok())
+        .map(|ts| ts.with_timezone(&Utc))
+        .max()
+}
+
+/// A license payload together with its Ed25519 signature.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct SignedLicense {
+    /// Base64-encoded JSON of [`SignedLicensePayload`]
+    pub payload: String,
+    /// Base64-encoded Ed25519 signature over the decoded payload bytes
+    pub signature: String,
+}
+
+impl SignedLicense {
+    /// Verify the signature and return the decoded payload.
+    ///
+    /// Fails if the public key is malformed, the signature does not match,
+    /// or the payload cannot be decoded.
+    pub fn verify(&self, public_key: &[u8]) -> LicenseResult<SignedLicensePayload> {
+        if public_key.len() != PUBLIC_KEY_SIZE {
+            return Err(LicenseError::SignatureError(format!(
+                "invalid public key length: expected {} bytes, got {}",
+                PUBLIC_KEY_SIZE,
+                public_key.len()
+            )));
+        }
+
+        let payload = B64
+            .decode(&self.payload)
+            .map_err(|e| LicenseError::SignatureError(format!("invalid payload encoding: {e}")))?;
+        let signature = B64.decode(&self.signature).map_err(|e| {
+            LicenseError::SignatureError(format!("invalid signature encoding: {e}"))
+        })?;
+
+        UnparsedPublicKey::new(&ED25519, public_key)
+            .verify(&payload, &signature)
+            .map_err(|_| {
+                LicenseError::SignatureError("license signature verification failed".to_string())
+            })?;
+
+        serde_json::from_slice(&payload)
+            .map_err(|e| LicenseError::SignatureError(format!("invalid payload: {e}")))
+    }
+}
+
+/// Decode a Base64 Ed25519 public key (as printed by `talos_server signing-key generate`).
+pub fn decode_public_key(encoded: &str) -> LicenseResult<Vec<u8>> {
+    let key = B64
+        .decode(encoded.trim())
+        .map_err(|e| LicenseError::SignatureError(format!("invalid public key encoding: {e}")))?;
+
+    if key.len() != PUBLIC_KEY_SIZE {
+        return Err(LicenseError::SignatureError(format!(
+            "invalid public key length: expected {} bytes, got {}",
+            PUBLIC_KEY_SIZE,
+            key.len()
+        )));
+    }
+
+    Ok(key)
+}
+
+/// Ed25519 signer for license documents.
+///
+/// Holds the server's private key. Keys are stored as PKCS#8 documents.
+pub struct LicenseSigner {
+    key_pair: Ed25519KeyPair,
+}
+
+impl LicenseSigner {
+    /// Generate a new PKCS#8-encoded Ed25519 private key.
+    ///
+    /// Caller is responsible for storing this safely.
+    pub fn generate_pkcs8() -> LicenseResult<Vec<u8>> {
+        let rng = SystemRandom::new();
+        let document = Ed25519KeyPair::generate_pkcs8(&rng)
+            .map_err(|_| LicenseError::SignatureError("failed to generate key".to_string()))?;
+        Ok(document.as_ref().to_vec())
+    }
+
+    /// Create a signer from a PKCS#8-encoded private key.
+    pub fn from_pkcs8(pkcs8: &[u8]) -> LicenseResult<Self> {
+        let key_pair = Ed25519KeyPair::from_pkcs8(pkcs8)
+            .map_err(|e| LicenseError::ConfigError(format!("invalid signing key: {e}")))?;
+        Ok(Self { key_pair })
+    }
+
+    /// Create a signer from a Base64 PKCS#8-encoded private key.
+    pub fn from_base64(encoded: &str) -> LicenseResult<Self> {
+        let pkcs8 = B64
+            .decode(encoded.trim())
+            .map_err(|e| LicenseError::ConfigError(format!("invalid signing key encoding: {e}")))?;
+        Self::from_pkcs8(&pkcs8)
+    }
+
+    /// Create a signer from configuration.
+    ///
+    /// Returns `Ok(None)` when no signing key is configured.
+    pub fn from_config(config: &SigningConfig) -> LicenseResult<Option<Self>> {
+        if !config.private_key.is_empty() {
+            // Resolve key (support env: prefix for environment variable)
+            let encoded = if let Some(env_var) = config.private_key.strip_prefix("env:") {
+                std::env::var(env_var).map_err(|_| {
+                    LicenseError::ConfigError(format!(
+                        "environment variable '{env_var}' not found for signing.private_key"
+                    ))
+                })?
+            } else {
+                config.private_key.clone()
+            };
+            return Self::from_base64(&encoded).map(Some);
+        }
+
+        if !config.private_key_path.is_empty() {
+            let encoded = std::fs::read_to_string(&config.private_key_path).map_err(|e| {
+                LicenseError::ConfigError(format!(
+                    "failed to read signing key from '{}': {e}",
+                    config.private_key_path
+                ))
+            })?;
+            return Self::from_base64(&encoded).map(Some);
+        }
+
+        Ok(None)
+    }
+
+    /// Get the raw Ed25519 public key.
+    pub fn public_key(&self) -> &[u8] {
+        self.key_pair.public_key().as_ref()
+    }
+
+    /// Get the public key as Base64, suitable for embedding in client builds.
+    pub fn public_key_base64(&self) -> String {
+        B64.encode(self.public_key())
+    }
+
+    /// Sign a license payload.
+    pub fn sign(&self, payload: &SignedLicensePayload) -> LicenseResult<SignedLicense> {
+        let bytes = serde_json::to_vec(payload)
+            .map_err(|e| LicenseError::SignatureError(format!("failed to encode payload: {e}")))?;
+        let signature = self.key_pair.sign(&bytes);
+
+        Ok(SignedLicense {
+            payload: B64.encode(&bytes),
+            signature: B64.encode(signature.as_ref()),
+        })
+    }
+}
+
+impl std::fmt::Debug for LicenseSigner {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LicenseSigner")
+            .field("public_key", &self.public_key_base64())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    fn test_signer() -> LicenseSigner {
+        let pkcs8 = LicenseSigner::generate_pkcs8().unwrap();
+        LicenseSigner::from_pkcs8(&pkcs8).unwrap()
+    }
+
+    fn test_payload() -> SignedLicensePayload {
+        SignedLicensePayload {
+            version: PAYLOAD_VERSION,
+            license_id: "lic-1".to_string(),
+            license_key: "LIC-AAAA-BBBB-CCCC".to_string(),
+            hardware_id: "hw-123".to_string(),
+            features: vec!["feature_a".to_string()],
+            tier: Some("pro".to_string()),
+            expires_at: Some((Utc::now() + Duration::days(30)).to_rfc3339()),
+            grace_period_ends_at: Some((Utc::now() + Duration::days(7)).to_rfc3339()),
+            offline_valid_until: None,
+            issued_at: Utc::now().to_rfc3339(),
+        }
+    }
+
+    #[test]
+    fn sign_and_verify_roundtrip() {
+        let signer = test_signer();
+        let payload = test_payload();
+
+        let signed = signer.sign(&payload).unwrap();
+        let verified = signed.verify(signer.public_key()).unwrap();
+
+        assert_eq!(verified, payload);
+        assert!(!verified.is_license_expired());
+        assert!(verified.is_valid_for_offline());
+    }
+
+    #[test]
+    fn tampered_payload_fails() {
+        let signer = test_signer();
+        let mut signed = signer.sign(&test_payload()).unwrap();
+
+        let mut forged = test_payload();
+        forged.features.push("feature_z".to_string());
+        signed.payload = B64.encode(serde_json::to_vec(&forged).unwrap());
+
+        assert!(signed.verify(signer.public_key()).is_err());
+    }
+
+    #[test]
+    fn wrong_public_key_fails() {
+        let signer = test_signer();
+        let other = test_signer();
+        let signed = signer.sign(&test_payload()).unwrap();
+
+        assert!(signed.verify(other.public_key()).is_err());
+    }
+
+    #[test]
+    fn invalid_public_key_length_fails() {
+        let signer = test_signer();
+        let signed = signer.sign(&test_payload()).unwrap();
+
+        assert!(signed.verify(&[0u8; 16]).is_err());
+        assert!(decode_public_key(&B64.encode([0u8; 16])).is_err());
+    }
+
+    #[test]
+    fn base64_keys_roundtrip() {
+        let pkcs8 = LicenseSigner::generate_pkcs8().unwrap();
+        let signer = LicenseSigner::from_base64(&B64.encode(&pkcs8)).unwrap();
+
+        let public_key = decode_public_key(&signer.public_key_base64()).unwrap();
+        let signed = signer.sign(&test_payload()).unwrap();
+        assert!(signed.verify(&public_key).is_ok());
+    }
+
+    #[test]
+    fn from_config_without_key_is_none() {
+        let signer = LicenseSigner::from_config(&SigningConfig::default()).unwrap();
+        assert!(signer.is_none());
+    }
+
+    #[test]
+    fn expired_grace_window_is_not_valid_offline() {
+        let mut payload = test_payload();
+        payload.grace_period_ends_at = Some((Utc::now() - Duration::hours(1)).to_rfc3339());
+        assert!(!payload.is_valid_for_offline());
+
+        payload.grace_period_ends_at = None;
+        assert!(!payload.is_valid_for_offline());
+    }
+
+    #[test]
+    fn offline_allowance_is_valid_without_grace_window() {
+        let mut payload = test_payload();
+        payload.grace_period_ends_at = None;
+        payload.offline_valid_until = Some((Utc::now() + Duration::days(3)).to_rfc3339());
+        assert!(payload.is_valid_for_offline());
+
+        payload.offline_valid_until = Some((Utc::now() - Duration::hours(1)).to_rfc3339());
+        assert!(!payload.is_valid_for_offline());
+
+        // Whichever window ends later applies
+        payload.grace_period_ends_at = Some((Utc::now() + Duration::hours(1)).to_rfc3339());
+        assert!(payload.is_valid_for_offline());
+    }
+
+    #[test]
+    fn payload_without_offline_allowance_still_parses() {
+        let mut payload = test_payload();
+        payload.offline_valid_until = None;
+        let json = serde_json::to_string(&payload).unwrap();
+        assert!(!json.contains("offline_valid_until"));
+
+        let parsed: SignedLicensePayload = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, payload);
+    }
+}
@@ -15,10 +15,17 @@
 //! [tiers.pro]
 //! features = ["feature_a", "feature_b"]
 //! bandwidth_gb = 500
+//! quota_restricted_features = ["feature_b"]  # denied while over a quota
+//! billing_cycle = "monthly"  # meters reset monthly from the issue date
+//!
+//! [tiers.pro.meters]
+//! api_calls = 100000  # per license, 0 means unlimited
+//! renders = 500
 //!
 //! [tiers.enterprise]
 //! features = ["feature_a", "feature_b", "feature_c"]
 //! bandwidth_gb = 0  # 0 means unlimited
+//! offline_days = 30 # overrides server.offline_days
 //! ```
 //!
 //! # Usage
@@ -38,12 +45,25 @@
 //! // Get bandwidth limit in bytes (None if unlimited or tier doesn't exist)
 //! let limit = get_bandwidth_limit_bytes("pro");
 //! ```
+//!
+//! # Usage meters
+//!
+//! `meters` sets a limit for each named usage meter. Meter names are up to
+//! the application; usage is reported by clients and tracked per license.
+//! Bandwidth is the built-in `bandwidth` meter (in bytes): `bandwidth_gb`
+//! is its limit unless `meters` sets one explicitly.
+//!
+//! `billing_cycle` resets the meters of the tier's licenses: `monthly`, a
+//! cron expression, or `none`. See `talos::server::billing`.
 
 use serde::Deserialize;
 use std::collections::HashMap;
 
 use crate::config::get_config;
 
+/// Name of the built-in meter that tracks bandwidth in bytes.
+pub const BANDWIDTH_METER: &str = "bandwidth";
+
 /// Configuration for a single tier.
 #[derive(Debug, Clone, Default, Deserialize)]
 #[serde(default)]
@@ -52,6 +72,15 @@ pub struct TierConfig {
     pub features: Vec<String>,
     /// Bandwidth limit in gigabytes (0 = unlimited)
     pub bandwidth_gb: u64,
+    /// Days of offline use after each successful check (None = server default)
+    pub offline_days: Option<u32>,
+    /// Limit per usage meter (0 = unlimited)
+    pub meters: HashMap<String, u64>,
+    /// Features denied while any meter of the license is over its limit
+    pub quota_restricted_features: Vec<String>,
+    /// When usage meters reset: "monthly", a cron expression or "none"
+    /// (None = never, unless the license sets a cycle)
+    pub billing_cycle: Option<String>,
 }
 
 impl TierConfig {
@@ -69,6 +98,24 @@ impl TierConfig {
             Some(self.bandwidth_gb * 1024 * 1024 * 1024)
         }
     }
+
+    /// Get the limit of a usage meter.
+    ///
+    /// Returns None if the meter is unlimited or not configured. The
+    /// `bandwidth` meter falls back to `bandwidth_gb`.
+    pub fn meter_limit(&self, meter: &str) -> Option<u64> {
+        match self.meters.get(meter) {
+            Some(0) => None,
+            Some(limit) => Some(*limit),
+            None if meter == BANDWIDTH_METER => self.bandwidth_limit_bytes(),
+            None => None,
+        }
+    }
+
+    /// Check if a feature is denied while the license is over a quota.
+    pub fn is_quota_restricted(&self, feature: &str) -> bool {
+        self.quota_restricted_features.iter().any(|f| f == feature)
+    }
 }
 
 /// A tier with its name included (for when you need the full context).
@@ -194,6 +241,7 @@ mod tests {
         let config = TierConfig {
             features: vec!["feature_a".to_string(), "feature_b".to_string()],
             bandwidth_gb: 100,
+            ..Default::default()
         };
 
         assert!(config.has_feature("feature_a"));
@@ -207,6 +255,7 @@ mod tests {
         let unlimited = TierConfig {
             features: vec![],
             bandwidth_gb: 0,
+            ..Default::default()
         };
         assert_eq!(unlimited.bandwidth_limit_bytes(), None);
 
@@ -214,6 +263,7 @@ mod tests {
         let limited = TierConfig {
             features: vec![],
             bandwidth_gb: 100, // 100 GB
+            ..Default::default()
         };
         assert_eq!(
             limited.bandwidth_limit_bytes(),
@@ -228,6 +278,7 @@ mod tests {
             config: TierConfig {
                 features: vec!["feature_a".to_string()],
                 bandwidth_gb: 50,
+                ..Default::default()
             },
         };
 
@@ -243,7 +294,41 @@ mod tests {
         let config = TierConfig::default();
         assert!(config.features.is_empty());
         assert_eq!(config.bandwidth_gb, 0);
+        assert_eq!(config.offline_days, None);
+        assert_eq!(config.billing_cycle, None);
         assert_eq!(config.bandwidth_limit_bytes(), None);
         assert!(!config.has_feature("anything"));
+        assert_eq!(config.meter_limit("api_calls"), None);
+        assert!(!config.is_quota_restricted("anything"));
+    }
+
+    #[test]
+    fn tier_config_meter_limits() {
+        let config = TierConfig {
+            bandwidth_gb: 1,
+            meters: HashMap::from([("api_calls".to_string(), 1000), ("renders".to_string(), 0)]),
+            quota_restricted_features: vec!["api".to_string()],
+            ..Default::default()
+        };
+
+        assert_eq!(config.meter_limit("api_calls"), Some(1000));
+        assert_eq!(config.meter_limit("renders"), None);
+        assert_eq!(config.meter_limit("unknown"), None);
+        assert_eq!(
+            config.meter_limit(BANDWIDTH_METER),
+            Some(1024 * 1024 * 1024)
+        );
+        assert!(config.is_quota_restricted("api"));
+        assert!(!config.is_quota_restricted("export"));
+    }
+
+    #[test]
+    fn explicit_bandwidth_meter_overrides_bandwidth_gb() {
+        let config = TierConfig {
+            bandwidth_gb: 1,
+            meters: HashMap::from([(BANDWIDTH_METER.to_string(), 500)]),
+            ..Default::default()
+        };
+        assert_eq!(config.meter_limit(BANDWIDTH_METER), Some(500));
     }
 }
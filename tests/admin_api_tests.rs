@@ -4,26 +4,35 @@
 
 #![cfg(feature = "admin-api")]
 
+use std::sync::Arc;
+
 use axum::{
     body::Body,
     http::{Request, StatusCode},
 };
 use serde_json::{json, Value};
-use talos::server::database::Database;
+use talos::server::database::{Database, License};
 use talos::server::handlers::AppState;
+use talos::server::orgs::Organization;
 use talos::server::routes::build_router;
 use tower::ServiceExt;
 
 #[cfg(feature = "jwt-auth")]
 use talos::server::auth::AuthState;
 
-/// Helper to create a test database and app state.
-async fn setup_test_app() -> AppState {
+/// Helper to create a test database with every table the admin API uses.
+async fn setup_test_db() -> Arc<Database> {
     // Use an in-memory SQLite database for testing
     std::env::set_var("TALOS_DATABASE_TYPE", "sqlite");
     std::env::set_var("TALOS_DATABASE_URL", "sqlite::memory:");
     // Disable IP whitelist for tests (tower::oneshot doesn't provide ConnectInfo)
     std::env::set_var("TALOS_ADMIN_IP_WHITELIST", "");
+    // Rate limits are keyed by client IP, which oneshot requests don't have
+    std::env::set_var("TALOS_RATE_LIMIT_ENABLED", "false");
+    // Record admin changes so the audit endpoint can be tested
+    std::env::set_var("TALOS_ADMIN_AUDIT_LOGGING", "true");
+    // Let clients start trials
+    std::env::set_var("TALOS_TRIALS_ENABLED", "true");
 
     let db = Database::new().await.expect("failed to create database");
 
@@ -62,13 +71,178 @@ async fn setup_test_app() -> AppState {
                     metadata TEXT,
                     bandwidth_used_bytes INTEGER DEFAULT 0,
                     bandwidth_limit_bytes INTEGER,
-                    quota_exceeded INTEGER DEFAULT 0
+                    quota_exceeded INTEGER DEFAULT 0,
+                    max_devices INTEGER NOT NULL DEFAULT 1,
+                    max_concurrent INTEGER,
+                    offline_days INTEGER,
+                    billing_cycle TEXT,
+                    usage_period_start TEXT,
+                    suspended_by_org INTEGER
                 )
                 "#,
             )
             .execute(pool)
             .await
             .expect("failed to create licenses table");
+
+            sqlx::query(
+                r#"
+                CREATE TABLE IF NOT EXISTS license_devices (
+                    license_id TEXT NOT NULL,
+                    hardware_id TEXT NOT NULL,
+                    device_name TEXT,
+                    device_info TEXT,
+                    bound_at TEXT NOT NULL,
+                    last_seen_at TEXT,
+                    PRIMARY KEY (license_id, hardware_id)
+                )
+                "#,
+            )
+            .execute(pool)
+            .await
+            .expect("failed to create license_devices table");
+
+            sqlx::query(
+                r#"
+                CREATE TABLE IF NOT EXISTS license_leases (
+                    license_id TEXT NOT NULL,
+                    hardware_id TEXT NOT NULL,
+                    checked_out_at TEXT NOT NULL,
+                    expires_at TEXT NOT NULL,
+                    PRIMARY KEY (license_id, hardware_id)
+                )
+                "#,
+            )
+            .execute(pool)
+            .await
+            .expect("failed to create license_leases table");
+
+            sqlx::query(
+                r#"
+                CREATE TABLE IF NOT EXISTS audit_log (
+                    id TEXT PRIMARY KEY,
+                    occurred_at TEXT NOT NULL,
+                    actor TEXT NOT NULL,
+                    actor_token_id TEXT,
+                    action TEXT NOT NULL,
+                    target_type TEXT NOT NULL,
+                    target_id TEXT NOT NULL,
+                    before_state TEXT,
+                    after_state TEXT,
+                    ip_address TEXT,
+                    request_id TEXT
+                )
+                "#,
+            )
+            .execute(pool)
+            .await
+            .expect("failed to create audit_log table");
+
+            sqlx::query(
+                r#"
+                CREATE TABLE IF NOT EXISTS organizations (
+                    id TEXT PRIMARY KEY,
+                    name TEXT NOT NULL,
+                    contact_name TEXT,
+                    contact_email TEXT,
+                    metadata TEXT,
+                    default_tier TEXT,
+                    default_features TEXT,
+                    status TEXT NOT NULL DEFAULT 'active',
+                    suspended_at TEXT,
+                    suspension_reason TEXT,
+                    created_at TEXT NOT NULL,
+                    updated_at TEXT NOT NULL
+                )
+                "#,
+            )
+            .execute(pool)
+            .await
+            .expect("failed to create organizations table");
+
+            sqlx::query(
+                r#"
+                CREATE TABLE IF NOT EXISTS license_binding_history (
+                    id INTEGER PRIMARY KEY AUTOINCREMENT,
+                    license_id TEXT NOT NULL,
+                    action TEXT NOT NULL,
+                    hardware_id TEXT,
+                    device_name TEXT,
+                    device_info TEXT,
+                    performed_by TEXT NOT NULL,
+                    reason TEXT,
+                    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+                )
+                "#,
+            )
+            .execute(pool)
+            .await
+            .expect("failed to create license_binding_history table");
+
+            sqlx::query(
+                r#"
+                CREATE TABLE IF NOT EXISTS trial_registrations (
+                    hardware_id TEXT NOT NULL,
+                    product TEXT NOT NULL DEFAULT '',
+                    license_id TEXT NOT NULL,
+                    started_at TEXT NOT NULL,
+                    converted_at TEXT,
+                    PRIMARY KEY (hardware_id, product)
+                )
+                "#,
+            )
+            .execute(pool)
+            .await
+            .expect("failed to create trial_registrations table");
+
+            sqlx::query(
+                r#"
+                CREATE TABLE IF NOT EXISTS usage_meters (
+                    license_id TEXT NOT NULL,
+                    meter TEXT NOT NULL,
+                    used INTEGER NOT NULL DEFAULT 0,
+                    usage_limit INTEGER,
+                    updated_at TEXT NOT NULL,
+                    PRIMARY KEY (license_id, meter)
+                )
+                "#,
+            )
+            .execute(pool)
+            .await
+            .expect("failed to create usage_meters table");
+
+            sqlx::query(
+                r#"
+                CREATE TABLE IF NOT EXISTS usage_reports (
+                    license_id TEXT NOT NULL,
+                    report_id TEXT NOT NULL,
+                    meter TEXT NOT NULL,
+                    amount INTEGER NOT NULL,
+                    reported_at TEXT NOT NULL,
+                    PRIMARY KEY (license_id, report_id)
+                )
+                "#,
+            )
+            .execute(pool)
+            .await
+            .expect("failed to create usage_reports table");
+
+            sqlx::query(
+                r#"
+                CREATE TABLE IF NOT EXISTS usage_periods (
+                    license_id TEXT NOT NULL,
+                    meter TEXT NOT NULL,
+                    period_start TEXT NOT NULL,
+                    period_end TEXT NOT NULL,
+                    used INTEGER NOT NULL,
+                    usage_limit INTEGER,
+                    PRIMARY KEY (license_id, meter, period_start)
+                )
+                "#,
+            )
+            .execute(pool)
+            .await
+            .expect("failed to create usage_periods table");
         }
         #[cfg(feature = "postgres")]
         Database::Postgres(_) => {
@@ -76,10 +250,35 @@ async fn setup_test_app() -> AppState {
         }
     }
 
+    db
+}
+
+/// Helper to create a test database and app state.
+async fn setup_test_app() -> AppState {
     AppState {
-        db,
+        db: setup_test_db().await,
         #[cfg(feature = "jwt-auth")]
         auth: AuthState::disabled(),
+        signer: None,
+    }
+}
+
+/// An active organization with no contact details or defaults.
+fn test_org(id: &str) -> Organization {
+    let now = chrono::Utc::now().naive_utc();
+    Organization {
+        id: id.to_string(),
+        name: id.to_string(),
+        contact_name: None,
+        contact_email: None,
+        metadata: None,
+        default_tier: None,
+        default_features: None,
+        status: "active".to_string(),
+        suspended_at: None,
+        suspension_reason: None,
+        created_at: now,
+        updated_at: now,
     }
 }
 
@@ -252,17 +451,121 @@ async fn list_licenses_by_org() {
 }
 
 #[tokio::test]
-async fn list_licenses_requires_org_id() {
+async fn list_licenses_without_org_lists_every_org() {
     let state = setup_test_app().await;
     let app = build_router(state);
 
+    for org in ["org-a", "org-b"] {
+        let (status, _) = json_request(
+            app.clone(),
+            "POST",
+            "/api/v1/licenses",
+            Some(json!({ "org_id": org })),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
+    }
+
     let (status, body) = json_request(app, "GET", "/api/v1/licenses", None).await;
 
-    assert_eq!(status, StatusCode::BAD_REQUEST);
-    assert!(body["error"]["message"]
-        .as_str()
-        .unwrap()
-        .contains("org_id"));
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["total"], 2);
+    assert!(body["next_cursor"].is_null());
+}
+
+#[tokio::test]
+async fn list_licenses_walks_filtered_pages_with_cursor() {
+    let state = setup_test_app().await;
+    let app = build_router(state);
+
+    for i in 0..5 {
+        let tier = if i % 2 == 0 { "pro" } else { "basic" };
+        let _ = json_request(
+            app.clone(),
+            "POST",
+            "/api/v1/licenses",
+            Some(json!({
+                "org_id": "org-pages",
+                "tier": tier,
+                "metadata": { "batch": format!("b{}", i % 2) }
+            })),
+        )
+        .await;
+    }
+
+    // Three pro licenses, walked two at a time, highest license ID first
+    let base = "/api/v1/licenses?tier=pro&sort=license_id&order=desc&per_page=2";
+    let mut ids = Vec::new();
+    let mut uri = base.to_string();
+    loop {
+        let (status, body) = json_request(app.clone(), "GET", &uri, None).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["total"], 3);
+        for license in body["licenses"].as_array().unwrap() {
+            assert_eq!(license["tier"], "pro");
+            ids.push(license["license_id"].as_str().unwrap().to_string());
+        }
+        match body["next_cursor"].as_str() {
+            Some(cursor) => uri = format!("{base}&cursor={cursor}"),
+            None => break,
+        }
+    }
+    assert_eq!(ids.len(), 3);
+    let mut sorted = ids.clone();
+    sorted.sort_by(|a, b| b.cmp(a));
+    assert_eq!(ids, sorted);
+
+    let (_, body) = json_request(
+        app,
+        "GET",
+        "/api/v1/licenses?metadata=batch:b1&bound=false",
+        None,
+    )
+    .await;
+    assert_eq!(body["total"], 2);
+}
+
+#[tokio::test]
+async fn list_licenses_rejects_bad_sort_and_cursor() {
+    let state = setup_test_app().await;
+    let app = build_router(state);
+
+    for _ in 0..2 {
+        let _ = json_request(
+            app.clone(),
+            "POST",
+            "/api/v1/licenses",
+            Some(json!({ "org_id": "org-sort" })),
+        )
+        .await;
+    }
+    let (_, body) = json_request(app.clone(), "GET", "/api/v1/licenses?per_page=1", None).await;
+    let cursor = body["next_cursor"].as_str().unwrap().to_string();
+
+    for uri in [
+        "/api/v1/licenses?sort=features".to_string(),
+        "/api/v1/licenses?order=sideways".to_string(),
+        "/api/v1/licenses?cursor=garbage".to_string(),
+        "/api/v1/licenses?metadata=no-separator".to_string(),
+        "/api/v1/licenses?per_page=501".to_string(),
+        // A cursor only works with the sort it came from
+        format!("/api/v1/licenses?sort=tier&cursor={cursor}"),
+        format!("/api/v1/licenses?page=2&cursor={cursor}"),
+    ] {
+        let (status, _) = json_request(app.clone(), "GET", &uri, None).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST, "{uri}");
+    }
+
+    let (status, body) = json_request(
+        app,
+        "GET",
+        &format!("/api/v1/licenses?per_page=1&cursor={cursor}"),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["licenses"].as_array().unwrap().len(), 1);
+    assert!(body["next_cursor"].is_null());
 }
 
 #[tokio::test]
@@ -1634,3 +1937,3024 @@ async fn reinstate_blacklisted_license_fails() {
         .unwrap()
         .contains("Cannot reinstate a blacklisted license"));
 }
+
+// ============================================================================
+// Signed License Tests
+// ============================================================================
+
+#[tokio::test]
+async fn bind_and_validate_return_signed_license() {
+    use std::sync::Arc;
+    use talos::signing::{LicenseSigner, SignedLicense};
+
+    let mut state = setup_test_app().await;
+    let pkcs8 = LicenseSigner::generate_pkcs8().unwrap();
+    let signer = Arc::new(LicenseSigner::from_pkcs8(&pkcs8).unwrap());
+    state.signer = Some(signer.clone());
+
+    let app = build_router(state.clone());
+    let (_, create_body) = json_request(
+        app,
+        "POST",
+        "/api/v1/licenses",
+        Some(json!({
+            "org_id": "signed-org",
+            "features": ["export", "sync"],
+            "tier": "pro"
+        })),
+    )
+    .await;
+    let license_id = create_body["license_id"].as_str().unwrap();
+    let license_key = create_body["license_key"].as_str().unwrap();
+
+    let app = build_router(state.clone());
+    let (status, bind_body) = json_request(
+        app,
+        "POST",
+        "/api/v1/client/bind",
+        Some(json!({ "license_key": license_key, "hardware_id": "signed-hw" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let signed: SignedLicense = serde_json::from_value(bind_body["signed_license"].clone())
+        .expect("bind should return a signed license");
+    let payload = signed.verify(signer.public_key()).unwrap();
+    assert_eq!(payload.license_id, license_id);
+    assert_eq!(payload.license_key, license_key);
+    assert_eq!(payload.hardware_id, "signed-hw");
+    assert_eq!(payload.features, vec!["export", "sync"]);
+    assert_eq!(payload.tier.as_deref(), Some("pro"));
+
+    let app = build_router(state.clone());
+    let (status, validate_body) = json_request(
+        app,
+        "POST",
+        "/api/v1/client/validate",
+        Some(json!({ "license_key": license_key, "hardware_id": "signed-hw" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let signed: SignedLicense =
+        serde_json::from_value(validate_body["signed_license"].clone()).unwrap();
+    assert!(signed.verify(signer.public_key()).is_ok());
+
+    // The latest signature is stored on the license record
+    let stored = state.db.get_license(license_id).await.unwrap().unwrap();
+    assert_eq!(stored.signature.as_deref(), Some(signed.signature.as_str()));
+}
+
+#[tokio::test]
+async fn no_signed_license_without_signing_key() {
+    let state = setup_test_app().await;
+
+    let app = build_router(state.clone());
+    let (_, create_body) = json_request(
+        app,
+        "POST",
+        "/api/v1/licenses",
+        Some(json!({ "org_id": "unsigned-org", "features": ["a"] })),
+    )
+    .await;
+    let license_key = create_body["license_key"].as_str().unwrap();
+
+    let app = build_router(state);
+    let (status, body) = json_request(
+        app,
+        "POST",
+        "/api/v1/client/bind",
+        Some(json!({ "license_key": license_key, "hardware_id": "hw" })),
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert!(body.get("signed_license").is_none());
+}
+
+// ============================================================================
+// Offline Activation Tests
+// ============================================================================
+
+#[tokio::test]
+async fn offline_activation_binds_and_returns_signed_response() {
+    use std::sync::Arc;
+    use talos::client::activation::{ActivationRequest, ActivationResponse};
+    use talos::signing::LicenseSigner;
+
+    let mut state = setup_test_app().await;
+    let pkcs8 = LicenseSigner::generate_pkcs8().unwrap();
+    let signer = Arc::new(LicenseSigner::from_pkcs8(&pkcs8).unwrap());
+    state.signer = Some(signer.clone());
+
+    let app = build_router(state.clone());
+    let (_, create_body) = json_request(
+        app,
+        "POST",
+        "/api/v1/licenses",
+        Some(json!({ "org_id": "airgap-org", "features": ["export"] })),
+    )
+    .await;
+    let license_id = create_body["license_id"].as_str().unwrap();
+    let license_key = create_body["license_key"].as_str().unwrap();
+
+    let request = ActivationRequest::new(
+        license_key.to_string(),
+        "airgap-hw".to_string(),
+        Some("Plant floor".to_string()),
+        None,
+    );
+
+    let app = build_router(state.clone());
+    let (status, body) = json_request(
+        app,
+        "POST",
+        "/api/v1/licenses/offline-activation",
+        Some(json!({ "request": request, "valid_for_days": 7 })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let response: ActivationResponse = serde_json::from_value(body).unwrap();
+    let payload = response.signed_license.verify(signer.public_key()).unwrap();
+    assert_eq!(payload.license_key, license_key);
+    assert_eq!(payload.hardware_id, "airgap-hw");
+    assert_eq!(payload.features, vec!["export"]);
+    // The window is an offline allowance, not a suspension grace period
+    assert!(payload.grace_period_ends_at.is_none());
+    let offline_until =
+        chrono::DateTime::parse_from_rfc3339(payload.offline_valid_until.as_deref().unwrap())
+            .unwrap();
+    let window = offline_until.with_timezone(&chrono::Utc) - chrono::Utc::now();
+    assert!(window > chrono::Duration::days(6) && window <= chrono::Duration::days(7));
+    assert!(payload.is_valid_for_offline());
+
+    let stored = state.db.get_license(license_id).await.unwrap().unwrap();
+    assert_eq!(stored.hardware_id.as_deref(), Some("airgap-hw"));
+    assert_eq!(stored.device_name.as_deref(), Some("Plant floor"));
+
+    // Re-activating the same hardware is idempotent
+    let app = build_router(state.clone());
+    let (status, _) = json_request(
+        app,
+        "POST",
+        "/api/v1/licenses/offline-activation",
+        Some(json!({ "request": request })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    // Other hardware is rejected
+    let other = ActivationRequest::new(license_key.to_string(), "other-hw".to_string(), None, None);
+    let app = build_router(state);
+    let (status, _) = json_request(
+        app,
+        "POST",
+        "/api/v1/licenses/offline-activation",
+        Some(json!({ "request": other })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn offline_activation_requires_signing_key() {
+    use talos::client::activation::ActivationRequest;
+
+    let state = setup_test_app().await;
+
+    let app = build_router(state.clone());
+    let (_, create_body) = json_request(
+        app,
+        "POST",
+        "/api/v1/licenses",
+        Some(json!({ "org_id": "airgap-org" })),
+    )
+    .await;
+    let license_key = create_body["license_key"].as_str().unwrap();
+
+    let request = ActivationRequest::new(license_key.to_string(), "hw".to_string(), None, None);
+    let app = build_router(state);
+    let (status, _) = json_request(
+        app,
+        "POST",
+        "/api/v1/licenses/offline-activation",
+        Some(json!({ "request": request })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::INTERNAL_SERVER_ERROR);
+}
+
+#[tokio::test]
+async fn offline_activation_unknown_license_returns_404() {
+    use std::sync::Arc;
+    use talos::client::activation::ActivationRequest;
+    use talos::signing::LicenseSigner;
+
+    let mut state = setup_test_app().await;
+    let pkcs8 = LicenseSigner::generate_pkcs8().unwrap();
+    state.signer = Some(Arc::new(LicenseSigner::from_pkcs8(&pkcs8).unwrap()));
+
+    let request = ActivationRequest::new("NOPE-0000".to_string(), "hw".to_string(), None, None);
+    let app = build_router(state);
+    let (status, _) = json_request(
+        app,
+        "POST",
+        "/api/v1/licenses/offline-activation",
+        Some(json!({ "request": request })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+// ============================================================================
+// Multi-seat licenses
+// ============================================================================
+
+/// Create a license with the given seat limit, returning (license_id, license_key).
+async fn create_seat_license(state: &AppState, max_devices: i32) -> (String, String) {
+    let app = build_router(state.clone());
+    let (status, body) = json_request(
+        app,
+        "POST",
+        "/api/v1/licenses",
+        Some(json!({ "org_id": "seat-org", "max_devices": max_devices })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(body["max_devices"], max_devices);
+
+    (
+        body["license_id"].as_str().unwrap().to_string(),
+        body["license_key"].as_str().unwrap().to_string(),
+    )
+}
+
+async fn client_request(
+    state: &AppState,
+    path: &str,
+    license_key: &str,
+    hardware_id: &str,
+) -> (StatusCode, Value) {
+    let app = build_router(state.clone());
+    json_request(
+        app,
+        "POST",
+        path,
+        Some(json!({
+            "license_key": license_key,
+            "hardware_id": hardware_id,
+            "device_name": format!("Device {hardware_id}")
+        })),
+    )
+    .await
+}
+
+#[tokio::test]
+async fn multi_seat_license_binds_up_to_max_devices() {
+    let state = setup_test_app().await;
+    let (_, key) = create_seat_license(&state, 2).await;
+
+    for hw in ["hw-a", "hw-b"] {
+        let (status, _) = client_request(&state, "/api/v1/client/bind", &key, hw).await;
+        assert_eq!(status, StatusCode::OK, "{hw} should get a seat");
+    }
+
+    // Rebinding a device that already holds a seat is fine
+    let (status, _) = client_request(&state, "/api/v1/client/bind", &key, "hw-a").await;
+    assert_eq!(status, StatusCode::OK);
+
+    // A third device is turned away with the current seat holders
+    let (status, body) = client_request(&state, "/api/v1/client/bind", &key, "hw-c").await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert_eq!(body["error"]["code"], "SEAT_LIMIT_REACHED");
+    assert_eq!(body["error"]["details"]["max_devices"], 2);
+    let devices = body["error"]["details"]["devices"].as_array().unwrap();
+    assert_eq!(devices.len(), 2);
+    assert_eq!(devices[0]["device_name"], "Device hw-a");
+
+    let (status, _) = client_request(&state, "/api/v1/client/validate-or-bind", &key, "hw-c").await;
+    assert_eq!(status, StatusCode::CONFLICT);
+
+    // Every seat holder can validate and heartbeat on its own
+    for hw in ["hw-a", "hw-b"] {
+        let (status, body) = client_request(&state, "/api/v1/client/validate", &key, hw).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["valid"], true);
+
+        let (status, _) = client_request(&state, "/api/v1/client/heartbeat", &key, hw).await;
+        assert_eq!(status, StatusCode::OK);
+    }
+
+    // A device without a seat cannot validate
+    let (status, body) = client_request(&state, "/api/v1/client/validate", &key, "hw-c").await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    assert_eq!(body["error"]["code"], "HARDWARE_MISMATCH");
+
+    // Releasing one seat frees it for the next device
+    let (status, _) = client_request(&state, "/api/v1/client/release", &key, "hw-a").await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, _) = client_request(&state, "/api/v1/client/validate", &key, "hw-b").await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, _) = client_request(&state, "/api/v1/client/bind", &key, "hw-c").await;
+    assert_eq!(status, StatusCode::OK);
+}
+
+#[tokio::test]
+async fn single_seat_license_reports_seat_limit_reached() {
+    let state = setup_test_app().await;
+    let (_, key) = create_seat_license(&state, 1).await;
+
+    let (status, _) = client_request(&state, "/api/v1/client/bind", &key, "hw-1").await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, body) = client_request(&state, "/api/v1/client/bind", &key, "hw-2").await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert_eq!(body["error"]["code"], "SEAT_LIMIT_REACHED");
+    assert_eq!(
+        body["error"]["details"]["devices"][0]["device_name"],
+        "Device hw-1"
+    );
+}
+
+#[tokio::test]
+async fn admin_lists_and_removes_license_devices() {
+    let state = setup_test_app().await;
+    let (license_id, key) = create_seat_license(&state, 3).await;
+
+    for hw in ["hw-x", "hw-y"] {
+        client_request(&state, "/api/v1/client/bind", &key, hw).await;
+    }
+
+    let app = build_router(state.clone());
+    let (status, body) = json_request(
+        app,
+        "GET",
+        &format!("/api/v1/licenses/{license_id}/devices"),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["max_devices"], 3);
+    assert_eq!(body["devices"].as_array().unwrap().len(), 2);
+    assert_eq!(body["devices"][0]["hardware_id"], "hw-x");
+
+    // Kick one seat
+    let app = build_router(state.clone());
+    let (status, body) = json_request(
+        app,
+        "DELETE",
+        &format!("/api/v1/licenses/{license_id}/devices/hw-x"),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["previous_hardware_id"], "hw-x");
+    assert_eq!(body["released_devices"], 1);
+
+    // The kicked device must bind again; the other keeps its seat
+    let (status, _) = client_request(&state, "/api/v1/client/validate", &key, "hw-x").await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    let (status, _) = client_request(&state, "/api/v1/client/validate", &key, "hw-y").await;
+    assert_eq!(status, StatusCode::OK);
+
+    let devices = state.db.list_license_devices(&license_id).await.unwrap();
+    assert_eq!(devices.len(), 1);
+    let license = state.db.get_license(&license_id).await.unwrap().unwrap();
+    assert_eq!(license.hardware_id.as_deref(), Some("hw-y"));
+
+    // Removing a device without a seat is a 404
+    let app = build_router(state.clone());
+    let (status, _) = json_request(
+        app,
+        "DELETE",
+        &format!("/api/v1/licenses/{license_id}/devices/hw-x"),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    // Force release frees every remaining seat
+    let app = build_router(state.clone());
+    let (status, body) = json_request(
+        app,
+        "POST",
+        &format!("/api/v1/licenses/{license_id}/release"),
+        Some(json!({})),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["released_devices"], 1);
+    assert!(state
+        .db
+        .list_license_devices(&license_id)
+        .await
+        .unwrap()
+        .is_empty());
+}
+
+#[tokio::test]
+async fn admin_reads_license_and_device_history() {
+    let state = setup_test_app().await;
+    let (first_id, first_key) = create_seat_license(&state, 1).await;
+    let (second_id, second_key) = create_seat_license(&state, 1).await;
+
+    // hw-1 moves from the first license to the second
+    client_request(&state, "/api/v1/client/bind", &first_key, "hw-1").await;
+    client_request(&state, "/api/v1/client/release", &first_key, "hw-1").await;
+    client_request(&state, "/api/v1/client/bind", &second_key, "hw-1").await;
+    let app = build_router(state.clone());
+    let (status, _) = json_request(
+        app,
+        "POST",
+        &format!("/api/v1/licenses/{second_id}/release"),
+        Some(json!({ "reason": "moved to new laptop" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    client_request(&state, "/api/v1/client/bind", &second_key, "hw-2").await;
+
+    let app = build_router(state.clone());
+    let (status, body) = json_request(
+        app,
+        "GET",
+        &format!("/api/v1/licenses/{second_id}/history"),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["license_id"], second_id);
+    assert_eq!(body["total"], 3);
+    let actions: Vec<&str> = body["entries"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|e| e["action"].as_str().unwrap())
+        .collect();
+    assert_eq!(actions, ["bind", "admin_release", "bind"]);
+    assert_eq!(body["entries"][1]["performed_by"], "admin");
+    assert_eq!(body["entries"][1]["reason"], "moved to new laptop");
+    assert!(body["entries"][0].get("license_key").is_none());
+
+    // Filter by action and performer, one entry per page
+    let app = build_router(state.clone());
+    let (status, body) = json_request(
+        app,
+        "GET",
+        &format!("/api/v1/licenses/{second_id}/history?action=bind&performed_by=client&per_page=1&page=2"),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["total"], 2);
+    assert_eq!(body["total_pages"], 2);
+    assert_eq!(body["entries"][0]["hardware_id"], "hw-1");
+
+    // Reverse lookup: every license hw-1 has been on
+    let app = build_router(state.clone());
+    let (status, body) =
+        json_request(app, "GET", "/api/v1/devices/hw-1/history?action=bind", None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["hardware_id"], "hw-1");
+    assert_eq!(body["total"], 2);
+    assert_eq!(body["entries"][0]["license_id"], second_id);
+    assert_eq!(body["entries"][0]["license_key"], second_key);
+    assert_eq!(body["entries"][0]["org_id"], "seat-org");
+    assert_eq!(body["entries"][1]["license_id"], first_id);
+
+    // A device that never bound has an empty history
+    let app = build_router(state.clone());
+    let (status, body) = json_request(app, "GET", "/api/v1/devices/hw-9/history", None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["total"], 0);
+
+    for path in [
+        format!("/api/v1/licenses/{first_id}/history?action=unbind"),
+        format!("/api/v1/licenses/{first_id}/history?performed_by=robot"),
+        format!("/api/v1/licenses/{first_id}/history?per_page=0"),
+        "/api/v1/devices/hw-1/history?page=0".to_string(),
+    ] {
+        let app = build_router(state.clone());
+        let (status, _) = json_request(app, "GET", &path, None).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST, "{path}");
+    }
+
+    let app = build_router(state);
+    let (status, _) = json_request(app, "GET", "/api/v1/licenses/missing/history", None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn max_devices_must_be_positive() {
+    let state = setup_test_app().await;
+
+    let app = build_router(state.clone());
+    let (status, _) = json_request(
+        app,
+        "POST",
+        "/api/v1/licenses",
+        Some(json!({ "org_id": "seat-org", "max_devices": 0 })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (license_id, _) = create_seat_license(&state, 1).await;
+
+    let app = build_router(state.clone());
+    let (status, body) = json_request(
+        app,
+        "PATCH",
+        &format!("/api/v1/licenses/{license_id}"),
+        Some(json!({ "max_devices": 10 })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["max_devices"], 10);
+
+    let app = build_router(state);
+    let (status, _) = json_request(
+        app,
+        "PATCH",
+        &format!("/api/v1/licenses/{license_id}"),
+        Some(json!({ "max_devices": -1 })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+// ============================================================================
+// Authentication and scope enforcement (requires `jwt-auth`)
+// ============================================================================
+
+#[cfg(feature = "jwt-auth")]
+mod auth_enforcement {
+    use super::*;
+    use talos::config::AuthConfig;
+    use talos::server::auth::JwtValidator;
+
+    const TEST_SECRET: &str = "admin-api-test-secret-key-for-testing";
+
+    fn auth_config() -> AuthConfig {
+        AuthConfig {
+            enabled: true,
+            jwt_secret: TEST_SECRET.to_string(),
+            ..Default::default()
+        }
+    }
+
+    /// Create an app state with JWT auth enabled.
+    async fn setup_auth_app() -> AppState {
+        let db = setup_test_db().await;
+
+        #[cfg(feature = "sqlite")]
+        if let Database::SQLite(pool) = &*db {
+            sqlx::query(
+                r#"
+                CREATE TABLE IF NOT EXISTS api_tokens (
+                    id TEXT PRIMARY KEY,
+                    name TEXT NOT NULL,
+                    token_hash TEXT NOT NULL UNIQUE,
+                    scopes TEXT NOT NULL,
+                    created_at TEXT NOT NULL,
+                    expires_at TEXT,
+                    last_used_at TEXT,
+                    revoked_at TEXT,
+                    created_by TEXT
+                )
+                "#,
+            )
+            .execute(pool)
+            .await
+            .expect("failed to create api_tokens table");
+        }
+
+        AppState {
+            db: db.clone(),
+            auth: AuthState::from_config(&auth_config())
+                .expect("failed to build auth state")
+                .with_database(db),
+            signer: None,
+        }
+    }
+
+    /// Sign a JWT with the test secret and the given scopes.
+    fn token_with_scopes(scopes: &[&str]) -> String {
+        JwtValidator::from_config(&auth_config())
+            .unwrap()
+            .create_token("test-admin", scopes)
+            .unwrap()
+    }
+
+    /// Make a JSON request with an optional `Authorization` header value.
+    async fn authed_request(
+        app: axum::Router,
+        method: &str,
+        uri: &str,
+        body: Option<Value>,
+        authorization: Option<&str>,
+    ) -> (StatusCode, Value) {
+        let body_bytes = body
+            .map(|v| serde_json::to_vec(&v).unwrap())
+            .unwrap_or_default();
+
+        let mut builder = Request::builder()
+            .method(method)
+            .uri(uri)
+            .header("Content-Type", "application/json");
+        if let Some(value) = authorization {
+            builder = builder.header("Authorization", value);
+        }
+
+        let response = app
+            .oneshot(builder.body(Body::from(body_bytes)).unwrap())
+            .await
+            .unwrap();
+        let status = response.status();
+
+        let body_bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body: Value = serde_json::from_slice(&body_bytes).unwrap_or(json!({}));
+
+        (status, body)
+    }
+
+    #[tokio::test]
+    async fn unauthenticated_admin_routes_return_401() {
+        let state = setup_auth_app().await;
+
+        let routes = [
+            (
+                "POST",
+                "/api/v1/licenses",
+                Some(json!({ "features": ["a"] })),
+            ),
+            ("GET", "/api/v1/licenses?org_id=org-1", None),
+            (
+                "POST",
+                "/api/v1/licenses/batch",
+                Some(json!({ "count": 1 })),
+            ),
+            ("GET", "/api/v1/licenses/some-id", None),
+            ("PATCH", "/api/v1/licenses/some-id", Some(json!({}))),
+            ("POST", "/api/v1/licenses/some-id/release", Some(json!({}))),
+            ("POST", "/api/v1/licenses/some-id/revoke", Some(json!({}))),
+            (
+                "POST",
+                "/api/v1/licenses/some-id/reinstate",
+                Some(json!({})),
+            ),
+            (
+                "POST",
+                "/api/v1/licenses/some-id/extend",
+                Some(json!({ "new_expires_at": "2030-01-01" })),
+            ),
+            ("PATCH", "/api/v1/licenses/some-id/usage", Some(json!({}))),
+            ("GET", "/api/v1/licenses/some-id/usage", None),
+            (
+                "PUT",
+                "/api/v1/licenses/some-id/usage/renders",
+                Some(json!({})),
+            ),
+            ("GET", "/api/v1/licenses/some-id/usage-periods", None),
+            (
+                "POST",
+                "/api/v1/licenses/some-id/blacklist",
+                Some(json!({ "reason": "abuse" })),
+            ),
+            (
+                "POST",
+                "/api/v1/tokens",
+                Some(json!({ "name": "t", "scopes": ["*"] })),
+            ),
+            ("GET", "/api/v1/tokens", None),
+            ("GET", "/api/v1/tokens/some-id", None),
+            ("DELETE", "/api/v1/tokens/some-id", None),
+            ("GET", "/api/v1/audit", None),
+        ];
+
+        for (method, uri, body) in routes {
+            let app = build_router(state.clone());
+            let (status, body) = authed_request(app, method, uri, body, None).await;
+            assert_eq!(status, StatusCode::UNAUTHORIZED, "{method} {uri}");
+            assert_eq!(body["error"]["code"], "MISSING_TOKEN", "{method} {uri}");
+        }
+    }
+
+    #[tokio::test]
+    async fn invalid_token_returns_401() {
+        let state = setup_auth_app().await;
+        let app = build_router(state);
+
+        let (status, body) = authed_request(
+            app,
+            "GET",
+            "/api/v1/licenses?org_id=org-1",
+            None,
+            Some("Bearer not-a-real-token"),
+        )
+        .await;
+
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        assert_eq!(body["error"]["code"], "INVALID_TOKEN");
+    }
+
+    #[tokio::test]
+    async fn token_signed_with_other_secret_returns_401() {
+        let state = setup_auth_app().await;
+        let app = build_router(state);
+
+        let foreign = JwtValidator::from_config(&AuthConfig {
+            jwt_secret: "some-other-secret".to_string(),
+            ..auth_config()
+        })
+        .unwrap()
+        .create_token("intruder", &["*"])
+        .unwrap();
+
+        let (status, _) = authed_request(
+            app,
+            "GET",
+            "/api/v1/licenses?org_id=org-1",
+            None,
+            Some(&format!("Bearer {foreign}")),
+        )
+        .await;
+
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn missing_scope_returns_403() {
+        let state = setup_auth_app().await;
+        let read_only = format!("Bearer {}", token_with_scopes(&["licenses:read"]));
+
+        // Read-only token cannot create licenses
+        let app = build_router(state.clone());
+        let (status, body) = authed_request(
+            app,
+            "POST",
+            "/api/v1/licenses",
+            Some(json!({ "features": ["a"] })),
+            Some(&read_only),
+        )
+        .await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+        assert_eq!(body["error"]["code"], "INSUFFICIENT_SCOPE");
+        assert_eq!(body["error"]["details"]["required_scope"], "licenses:write");
+
+        // ...nor revoke them
+        let app = build_router(state.clone());
+        let (status, body) = authed_request(
+            app,
+            "POST",
+            "/api/v1/licenses/some-id/revoke",
+            Some(json!({})),
+            Some(&read_only),
+        )
+        .await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+        assert_eq!(
+            body["error"]["details"]["required_scope"],
+            "licenses:delete"
+        );
+
+        // ...nor touch tokens
+        let app = build_router(state);
+        let (status, body) =
+            authed_request(app, "GET", "/api/v1/tokens", None, Some(&read_only)).await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+        assert_eq!(body["error"]["details"]["required_scope"], "tokens:read");
+    }
+
+    #[tokio::test]
+    async fn bulk_revoke_and_blacklist_require_delete_scope() {
+        let state = setup_auth_app().await;
+        let writer = format!(
+            "Bearer {}",
+            token_with_scopes(&["licenses:read", "licenses:write"])
+        );
+
+        let app = build_router(state.clone());
+        let (status, body) = authed_request(
+            app,
+            "POST",
+            "/api/v1/licenses/bulk",
+            Some(json!({ "action": "revoke", "params": {}, "license_ids": ["some-id"] })),
+            Some(&writer),
+        )
+        .await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+        assert_eq!(
+            body["error"]["details"]["required_scope"],
+            "licenses:delete"
+        );
+
+        // Other actions only need licenses:write
+        let app = build_router(state);
+        let (status, body) = authed_request(
+            app,
+            "POST",
+            "/api/v1/licenses/bulk",
+            Some(json!({
+                "action": "extend",
+                "params": { "new_expires_at": "2030-01-01" },
+                "license_ids": ["some-id"]
+            })),
+            Some(&writer),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["results"][0]["status"], "failed");
+    }
+
+    #[tokio::test]
+    async fn scoped_token_can_access_matching_routes() {
+        let state = setup_auth_app().await;
+        let writer = format!(
+            "Bearer {}",
+            token_with_scopes(&["licenses:read", "licenses:write"])
+        );
+
+        let app = build_router(state.clone());
+        let (status, create_body) = authed_request(
+            app,
+            "POST",
+            "/api/v1/licenses",
+            Some(json!({ "org_id": "auth-org", "features": ["a"] })),
+            Some(&writer),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
+
+        let license_id = create_body["license_id"].as_str().unwrap();
+        let app = build_router(state);
+        let (status, body) = authed_request(
+            app,
+            "GET",
+            &format!("/api/v1/licenses/{license_id}"),
+            None,
+            Some(&writer),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["license_id"], license_id);
+    }
+
+    #[tokio::test]
+    async fn wildcard_scopes_grant_token_management() {
+        let state = setup_auth_app().await;
+        let admin = format!(
+            "Bearer {}",
+            token_with_scopes(&["tokens:*", "licenses:read"])
+        );
+
+        let app = build_router(state.clone());
+        let (status, body) = authed_request(
+            app,
+            "POST",
+            "/api/v1/tokens",
+            Some(json!({ "name": "ci", "scopes": ["licenses:read"] })),
+            Some(&admin),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
+        // The JWT subject is recorded as the token's creator
+        assert_eq!(body["token"]["created_by"], "test-admin");
+
+        let app = build_router(state);
+        let (status, body) = authed_request(app, "GET", "/api/v1/tokens", None, Some(&admin)).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["tokens"].as_array().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn tokens_cannot_grant_scopes_the_caller_lacks() {
+        let state = setup_auth_app().await;
+        let (_, raw) = state
+            .db
+            .create_api_token("token-writer", &["tokens:write"], None, None)
+            .await
+            .unwrap();
+        let bearer = format!("Bearer {raw}");
+
+        for (scopes, expected) in [
+            (json!(["*"]), StatusCode::FORBIDDEN),
+            (json!(["tokens:*"]), StatusCode::FORBIDDEN),
+            (json!(["licenses:read"]), StatusCode::FORBIDDEN),
+            (
+                json!(["tokens:write", "billing:read"]),
+                StatusCode::BAD_REQUEST,
+            ),
+            (json!(["tokens:write"]), StatusCode::CREATED),
+        ] {
+            let app = build_router(state.clone());
+            let (status, _) = authed_request(
+                app,
+                "POST",
+                "/api/v1/tokens",
+                Some(json!({ "name": "minted", "scopes": scopes })),
+                Some(&bearer),
+            )
+            .await;
+            assert_eq!(status, expected, "{scopes}");
+        }
+
+        // Only the token with the caller's own scope was created, by the caller
+        let tokens = state.db.list_api_tokens().await.unwrap();
+        assert_eq!(tokens.len(), 2);
+        let minted = tokens.iter().find(|t| t.name == "minted").unwrap();
+        assert_eq!(minted.created_by.as_deref(), Some("token-writer"));
+    }
+
+    #[tokio::test]
+    async fn api_token_authenticates_admin_routes() {
+        let state = setup_auth_app().await;
+        let (_, raw) = state
+            .db
+            .create_api_token("ci-service", &["licenses:*"], None, None)
+            .await
+            .unwrap();
+        let bearer = format!("Bearer {raw}");
+
+        let app = build_router(state.clone());
+        let (status, _) = authed_request(
+            app,
+            "POST",
+            "/api/v1/licenses",
+            Some(json!({ "org_id": "token-org", "features": ["a"] })),
+            Some(&bearer),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
+
+        // Scopes from the token record are enforced just like JWT scopes
+        let app = build_router(state);
+        let (status, body) =
+            authed_request(app, "GET", "/api/v1/tokens", None, Some(&bearer)).await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+        assert_eq!(body["error"]["code"], "INSUFFICIENT_SCOPE");
+    }
+
+    #[tokio::test]
+    async fn api_token_updates_last_used() {
+        let state = setup_auth_app().await;
+        let (token, raw) = state
+            .db
+            .create_api_token("reader", &["licenses:read"], None, None)
+            .await
+            .unwrap();
+        assert!(token.last_used_at.is_none());
+
+        state.db.insert_org(&test_org("org-1")).await.unwrap();
+
+        let app = build_router(state.clone());
+        let (status, _) = authed_request(
+            app,
+            "GET",
+            "/api/v1/licenses?org_id=org-1",
+            None,
+            Some(&format!("Bearer {raw}")),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+
+        let stored = state.db.get_api_token(&token.id).await.unwrap().unwrap();
+        assert!(stored.last_used_at.is_some());
+    }
+
+    #[tokio::test]
+    async fn revoked_api_token_returns_401() {
+        let state = setup_auth_app().await;
+        let (token, raw) = state
+            .db
+            .create_api_token("old", &["*"], None, None)
+            .await
+            .unwrap();
+        state.db.revoke_api_token(&token.id).await.unwrap();
+
+        let app = build_router(state);
+        let (status, body) = authed_request(
+            app,
+            "GET",
+            "/api/v1/licenses?org_id=org-1",
+            None,
+            Some(&format!("Bearer {raw}")),
+        )
+        .await;
+
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        assert_eq!(body["error"]["code"], "INVALID_TOKEN");
+    }
+
+    #[tokio::test]
+    async fn expired_api_token_returns_401() {
+        let state = setup_auth_app().await;
+        let expired = chrono::Utc::now().naive_utc() - chrono::Duration::hours(1);
+        let (_, raw) = state
+            .db
+            .create_api_token("expired", &["*"], Some(expired), None)
+            .await
+            .unwrap();
+
+        let app = build_router(state);
+        let (status, body) = authed_request(
+            app,
+            "GET",
+            "/api/v1/licenses?org_id=org-1",
+            None,
+            Some(&format!("Bearer {raw}")),
+        )
+        .await;
+
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        assert_eq!(body["error"]["code"], "TOKEN_EXPIRED");
+    }
+
+    #[tokio::test]
+    async fn unknown_api_token_returns_401() {
+        let state = setup_auth_app().await;
+        let app = build_router(state);
+
+        let (status, body) = authed_request(
+            app,
+            "GET",
+            "/api/v1/licenses?org_id=org-1",
+            None,
+            Some("Bearer talos_00000000000000000000000000000000"),
+        )
+        .await;
+
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        assert_eq!(body["error"]["code"], "INVALID_TOKEN");
+    }
+
+    #[tokio::test]
+    async fn client_routes_do_not_require_auth() {
+        let state = setup_auth_app().await;
+        let app = build_router(state);
+
+        let (status, _) = authed_request(
+            app,
+            "POST",
+            "/api/v1/client/validate",
+            Some(json!({ "license_key": "LIC-NONE-NONE-NONE", "hardware_id": "hw" })),
+            None,
+        )
+        .await;
+
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn audit_log_records_api_token_actor() {
+        let state = setup_auth_app().await;
+        let (token, raw) = state
+            .db
+            .create_api_token("ci-service", &["licenses:*", "audit:read"], None, None)
+            .await
+            .unwrap();
+        let bearer = format!("Bearer {raw}");
+
+        let app = build_router(state.clone());
+        let (status, _) = authed_request(
+            app,
+            "POST",
+            "/api/v1/licenses",
+            Some(json!({ "org_id": "audit-org" })),
+            Some(&bearer),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
+
+        let app = build_router(state);
+        let (status, body) = authed_request(
+            app,
+            "GET",
+            "/api/v1/audit?action=license.created",
+            None,
+            Some(&bearer),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["total"], 1);
+        let entry = &body["entries"][0];
+        assert_eq!(entry["actor"], "ci-service");
+        assert_eq!(entry["actor_token_id"], token.id);
+        assert!(entry["request_id"].is_string());
+    }
+
+    #[tokio::test]
+    async fn audit_log_requires_audit_scope() {
+        let state = setup_auth_app().await;
+        let writer = format!("Bearer {}", token_with_scopes(&["licenses:*"]));
+
+        let app = build_router(state);
+        let (status, body) = authed_request(app, "GET", "/api/v1/audit", None, Some(&writer)).await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+        assert_eq!(body["error"]["details"]["required_scope"], "audit:read");
+    }
+
+    #[tokio::test]
+    async fn token_operations_are_audited() {
+        let state = setup_auth_app().await;
+        let admin = format!("Bearer {}", token_with_scopes(&["tokens:*", "audit:read"]));
+
+        let app = build_router(state.clone());
+        let (status, created) = authed_request(
+            app,
+            "POST",
+            "/api/v1/tokens",
+            Some(json!({ "name": "ci", "scopes": ["audit:read"] })),
+            Some(&admin),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
+        let token_id = created["token"]["id"].as_str().unwrap();
+
+        let app = build_router(state.clone());
+        let (status, _) = authed_request(
+            app,
+            "DELETE",
+            &format!("/api/v1/tokens/{token_id}"),
+            None,
+            Some(&admin),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+
+        let app = build_router(state);
+        let (status, body) = authed_request(
+            app,
+            "GET",
+            &format!("/api/v1/audit?target_type=token&target_id={token_id}"),
+            None,
+            Some(&admin),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["total"], 2);
+
+        let revoked = &body["entries"][0];
+        assert_eq!(revoked["action"], "token.revoked");
+        assert_eq!(revoked["actor"], "test-admin");
+        assert_eq!(revoked["before"]["is_active"], true);
+        assert_eq!(revoked["after"]["is_active"], false);
+        assert_eq!(body["entries"][1]["action"], "token.created");
+    }
+}
+
+// ============================================================================
+// Floating leases
+// ============================================================================
+
+#[tokio::test]
+async fn floating_license_limits_concurrent_leases() {
+    let state = setup_test_app().await;
+
+    let app = build_router(state.clone());
+    let (status, body) = json_request(
+        app,
+        "POST",
+        "/api/v1/licenses",
+        Some(json!({ "org_id": "float-org", "max_devices": 5, "max_concurrent": 1 })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(body["max_concurrent"], 1);
+    let key = body["license_key"].as_str().unwrap().to_string();
+
+    // Checkout binds the device and hands out the only lease
+    let (status, body) = client_request(&state, "/api/v1/client/checkout", &key, "hw-a").await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(body["lease_expires_at"].is_string());
+    assert!(body["lease_ttl_secs"].as_u64().unwrap() > 0);
+
+    // A second device gets a seat but no lease
+    let (status, body) = client_request(&state, "/api/v1/client/checkout", &key, "hw-b").await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert_eq!(body["error"]["code"], "LEASE_LIMIT_REACHED");
+
+    // Heartbeats renew the holder's lease only
+    let (status, body) = client_request(&state, "/api/v1/client/heartbeat", &key, "hw-a").await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(body["lease_expires_at"].is_string());
+
+    let (status, body) = client_request(&state, "/api/v1/client/heartbeat", &key, "hw-b").await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(body.get("lease_expires_at").is_none());
+
+    // Checking in frees the lease for the other device
+    let (status, _) = client_request(&state, "/api/v1/client/checkin", &key, "hw-a").await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, _) = client_request(&state, "/api/v1/client/checkout", &key, "hw-b").await;
+    assert_eq!(status, StatusCode::OK);
+}
+
+#[tokio::test]
+async fn max_concurrent_must_be_positive() {
+    let state = setup_test_app().await;
+
+    let app = build_router(state.clone());
+    let (status, _) = json_request(
+        app,
+        "POST",
+        "/api/v1/licenses",
+        Some(json!({ "org_id": "float-org", "max_concurrent": 0 })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (license_id, _) = create_seat_license(&state, 1).await;
+
+    let app = build_router(state);
+    let (status, body) = json_request(
+        app,
+        "PATCH",
+        &format!("/api/v1/licenses/{license_id}"),
+        Some(json!({ "max_concurrent": 3 })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["max_concurrent"], 3);
+}
+
+// ============================================================================
+// Offline allowance
+// ============================================================================
+
+/// Parse the `offline_valid_until` of a client response, if present.
+fn offline_valid_until(body: &Value) -> Option<chrono::DateTime<chrono::Utc>> {
+    body.get("offline_valid_until").map(|v| {
+        chrono::DateTime::parse_from_rfc3339(v.as_str().unwrap())
+            .unwrap()
+            .with_timezone(&chrono::Utc)
+    })
+}
+
+#[tokio::test]
+async fn validate_and_heartbeat_grant_offline_allowance() {
+    let state = setup_test_app().await;
+
+    let app = build_router(state.clone());
+    let (status, body) = json_request(
+        app,
+        "POST",
+        "/api/v1/licenses",
+        Some(json!({ "org_id": "offline-org", "offline_days": 3 })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(body["offline_days"], 3);
+    let license_id = body["license_id"].as_str().unwrap().to_string();
+    let key = body["license_key"].as_str().unwrap().to_string();
+
+    let (status, _) = client_request(&state, "/api/v1/client/bind", &key, "hw-a").await;
+    assert_eq!(status, StatusCode::OK);
+
+    // A healthy license gets an allowance without any grace period
+    let (status, body) = client_request(&state, "/api/v1/client/validate", &key, "hw-a").await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(body.get("grace_period_ends_at").is_none());
+    let until = offline_valid_until(&body).expect("validate grants an allowance");
+    let expected = chrono::Utc::now() + chrono::Duration::days(3);
+    assert!((expected - until).num_seconds().abs() < 60);
+
+    let (status, body) = client_request(&state, "/api/v1/client/heartbeat", &key, "hw-a").await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(offline_valid_until(&body).is_some());
+
+    // Zero days turns offline use off for this license
+    let app = build_router(state.clone());
+    let (status, body) = json_request(
+        app,
+        "PATCH",
+        &format!("/api/v1/licenses/{license_id}"),
+        Some(json!({ "offline_days": 0 })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["offline_days"], 0);
+
+    let (_, body) = client_request(&state, "/api/v1/client/validate", &key, "hw-a").await;
+    assert!(offline_valid_until(&body).is_none());
+    let (_, body) = client_request(&state, "/api/v1/client/heartbeat", &key, "hw-a").await;
+    assert!(offline_valid_until(&body).is_none());
+}
+
+#[tokio::test]
+async fn offline_allowance_never_outlasts_the_license() {
+    let state = setup_test_app().await;
+    let expires_at = chrono::Utc::now().naive_utc() + chrono::Duration::days(1);
+
+    let app = build_router(state.clone());
+    let (status, body) = json_request(
+        app,
+        "POST",
+        "/api/v1/licenses",
+        Some(json!({
+            "org_id": "offline-org",
+            "offline_days": 30,
+            "expires_at": expires_at.format("%Y-%m-%dT%H:%M:%S").to_string(),
+        })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    let key = body["license_key"].as_str().unwrap().to_string();
+
+    let (status, body) =
+        client_request(&state, "/api/v1/client/validate-or-bind", &key, "hw-a").await;
+    assert_eq!(status, StatusCode::OK);
+    let until = offline_valid_until(&body).unwrap();
+    assert!(until <= expires_at.and_utc() + chrono::Duration::seconds(1));
+    assert!(until > chrono::Utc::now() + chrono::Duration::hours(23));
+}
+
+#[tokio::test]
+async fn offline_days_must_not_be_negative() {
+    let state = setup_test_app().await;
+
+    let app = build_router(state);
+    let (status, _) = json_request(
+        app,
+        "POST",
+        "/api/v1/licenses",
+        Some(json!({ "org_id": "offline-org", "offline_days": -1 })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+// ============================================================================
+// Audit log
+// ============================================================================
+
+#[tokio::test]
+async fn admin_changes_are_recorded_in_audit_log() {
+    let state = setup_test_app().await;
+    let (license_id, _) = create_seat_license(&state, 1).await;
+
+    // Revoke from a known client IP
+    let request = Request::builder()
+        .method("POST")
+        .uri(format!("/api/v1/licenses/{license_id}/revoke"))
+        .header("Content-Type", "application/json")
+        .header("X-Forwarded-For", "203.0.113.7")
+        .body(Body::from(
+            serde_json::to_vec(&json!({ "reason": "chargeback" })).unwrap(),
+        ))
+        .unwrap();
+    let response = build_router(state.clone()).oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let request_id = response.headers()["X-Request-Id"]
+        .to_str()
+        .unwrap()
+        .to_string();
+
+    let app = build_router(state.clone());
+    let (status, _) = json_request(
+        app,
+        "POST",
+        &format!("/api/v1/licenses/{license_id}/extend"),
+        Some(json!({ "new_expires_at": "2030-01-01T00:00:00Z" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let app = build_router(state);
+    let (status, body) = json_request(
+        app,
+        "GET",
+        &format!("/api/v1/audit?target_id={license_id}"),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["total"], 3);
+
+    let actions: Vec<&str> = body["entries"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|e| e["action"].as_str().unwrap())
+        .collect();
+    assert_eq!(
+        actions,
+        vec!["license.extended", "license.revoked", "license.created"]
+    );
+
+    // Only the changed fields are stored
+    let revoked = &body["entries"][1];
+    assert_eq!(revoked["actor"], "anonymous");
+    assert_eq!(revoked["target_type"], "license");
+    assert_eq!(revoked["before"]["status"], "active");
+    assert_eq!(revoked["after"]["status"], "revoked");
+    assert_eq!(revoked["after"]["revoke_reason"], "chargeback");
+    assert!(revoked["after"].get("license_key").is_none());
+    assert_eq!(revoked["ip_address"], "203.0.113.7");
+    assert_eq!(revoked["request_id"], request_id);
+}
+
+#[tokio::test]
+async fn audit_log_filters_and_paginates() {
+    let state = setup_test_app().await;
+
+    let app = build_router(state.clone());
+    let (status, _) = json_request(
+        app,
+        "POST",
+        "/api/v1/licenses/batch",
+        Some(json!({ "count": 3, "org_id": "audit-org" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+
+    let app = build_router(state.clone());
+    let (status, body) = json_request(
+        app,
+        "GET",
+        "/api/v1/audit?action=license.created&per_page=2&page=2",
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["total"], 3);
+    assert_eq!(body["total_pages"], 2);
+    assert_eq!(body["entries"].as_array().unwrap().len(), 1);
+
+    let app = build_router(state.clone());
+    let (status, body) =
+        json_request(app, "GET", "/api/v1/audit?action=license.revoked", None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["total"], 0);
+
+    let app = build_router(state.clone());
+    let (status, _) = json_request(app, "GET", "/api/v1/audit?per_page=0", None).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let app = build_router(state);
+    let (status, _) = json_request(app, "GET", "/api/v1/audit?since=yesterday", None).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+// ============================================================================
+// Organizations
+// ============================================================================
+
+#[tokio::test]
+async fn org_defaults_apply_to_new_licenses_and_stats_count_them() {
+    let state = setup_test_app().await;
+
+    let app = build_router(state.clone());
+    let (status, org) = json_request(
+        app,
+        "POST",
+        "/api/v1/orgs",
+        Some(json!({
+            "id": "acme",
+            "name": "Acme Corp",
+            "contact_email": "ops@acme.test",
+            "metadata": { "crm_id": 42 },
+            "default_features": ["export", "api"]
+        })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(org["id"], "acme");
+    assert_eq!(org["status"], "active");
+    assert_eq!(org["metadata"]["crm_id"], 42);
+    assert!(org.get("stats").is_none());
+
+    // No features in the request: the organization's defaults apply
+    let app = build_router(state.clone());
+    let (status, license) = json_request(
+        app,
+        "POST",
+        "/api/v1/licenses",
+        Some(json!({ "org_id": "acme", "org_name": "ignored" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(license["org_name"], "Acme Corp");
+    assert_eq!(license["features"], json!(["export", "api"]));
+
+    // Explicit features win
+    let app = build_router(state.clone());
+    let (_, license) = json_request(
+        app,
+        "POST",
+        "/api/v1/licenses",
+        Some(json!({ "org_id": "acme", "features": ["reports"] })),
+    )
+    .await;
+    assert_eq!(license["features"], json!(["reports"]));
+    let license_id = license["license_id"].as_str().unwrap().to_string();
+
+    let app = build_router(state.clone());
+    let (status, _) = json_request(
+        app,
+        "POST",
+        &format!("/api/v1/licenses/{license_id}/revoke"),
+        Some(json!({})),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let app = build_router(state);
+    let (status, body) = json_request(app, "GET", "/api/v1/orgs/acme", None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["stats"]["total_licenses"], 2);
+    assert_eq!(body["stats"]["licenses_by_status"]["active"], 1);
+    assert_eq!(body["stats"]["licenses_by_status"]["revoked"], 1);
+    assert_eq!(body["stats"]["bound_devices"], 0);
+}
+
+#[tokio::test]
+async fn create_license_registers_unknown_org() {
+    let state = setup_test_app().await;
+
+    let app = build_router(state.clone());
+    let (status, _) = json_request(
+        app,
+        "POST",
+        "/api/v1/licenses/batch",
+        Some(json!({ "count": 2, "org_id": "walk-in", "org_name": "Walk-in Customer" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+
+    let app = build_router(state.clone());
+    let (status, body) = json_request(app, "GET", "/api/v1/orgs/walk-in", None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["name"], "Walk-in Customer");
+    assert_eq!(body["stats"]["total_licenses"], 2);
+
+    let app = build_router(state);
+    let (status, body) = json_request(app, "GET", "/api/v1/orgs", None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["total"], 1);
+}
+
+#[tokio::test]
+async fn renaming_org_updates_its_licenses() {
+    let state = setup_test_app().await;
+
+    let app = build_router(state.clone());
+    let (_, license) = json_request(
+        app,
+        "POST",
+        "/api/v1/licenses",
+        Some(json!({ "org_id": "rename-org", "org_name": "Old Name" })),
+    )
+    .await;
+    let license_id = license["license_id"].as_str().unwrap().to_string();
+
+    let app = build_router(state.clone());
+    let (status, body) = json_request(
+        app,
+        "PATCH",
+        "/api/v1/orgs/rename-org",
+        Some(json!({ "name": "New Name", "default_tier": "pro" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["name"], "New Name");
+    assert_eq!(body["default_tier"], "pro");
+
+    let app = build_router(state.clone());
+    let (_, body) = json_request(app, "GET", &format!("/api/v1/licenses/{license_id}"), None).await;
+    assert_eq!(body["org_name"], "New Name");
+
+    let app = build_router(state);
+    let (status, _) = json_request(
+        app,
+        "PATCH",
+        "/api/v1/orgs/rename-org",
+        Some(json!({ "name": "  " })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn list_licenses_requires_registered_org() {
+    let state = setup_test_app().await;
+    let app = build_router(state);
+
+    let (status, body) =
+        json_request(app, "GET", "/api/v1/licenses?org_id=no-such-org", None).await;
+
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(body["error"]["code"], "NOT_FOUND");
+}
+
+#[tokio::test]
+async fn suspending_org_suspends_its_licenses_until_reinstated() {
+    let state = setup_test_app().await;
+
+    let mut license_ids = Vec::new();
+    for _ in 0..2 {
+        let app = build_router(state.clone());
+        let (_, license) = json_request(
+            app,
+            "POST",
+            "/api/v1/licenses",
+            Some(json!({ "org_id": "suspend-org" })),
+        )
+        .await;
+        license_ids.push(license["license_id"].as_str().unwrap().to_string());
+    }
+
+    // A license revoked on its own stays revoked through suspend/reinstate
+    let app = build_router(state.clone());
+    let (status, _) = json_request(
+        app,
+        "POST",
+        &format!("/api/v1/licenses/{}/revoke", license_ids[1]),
+        Some(json!({})),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let app = build_router(state.clone());
+    let (status, body) = json_request(
+        app,
+        "POST",
+        "/api/v1/orgs/suspend-org/suspend",
+        Some(json!({ "reason": "unpaid invoice", "message": "Contact billing" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["org"]["status"], "suspended");
+    assert_eq!(body["org"]["suspension_reason"], "unpaid invoice");
+    assert_eq!(body["licenses"], json!([license_ids[0]]));
+
+    let suspended = state
+        .db
+        .get_license(&license_ids[0])
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(suspended.status, "suspended");
+    assert!(suspended.grace_period_ends_at.is_none());
+    assert_eq!(
+        suspended.suspension_message.as_deref(),
+        Some("Contact billing")
+    );
+
+    // A license suspended without a grace period by other means, e.g. an
+    // import, isn't the organization's to reinstate
+    state
+        .db
+        .insert_license(License {
+            license_id: "LIC-SUSPENDED-ALONE".to_string(),
+            license_key: None,
+            suspended_by_org: None,
+            ..suspended.clone()
+        })
+        .await
+        .unwrap();
+
+    // Nor can its licenses be reinstated one by one
+    let app = build_router(state.clone());
+    let (status, body) = json_request(
+        app,
+        "POST",
+        &format!("/api/v1/licenses/{}/reinstate", license_ids[0]),
+        Some(json!({})),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(
+        body["error"]["message"],
+        "organization suspend-org is suspended"
+    );
+
+    // Suspended organizations can't get new licenses or be suspended twice
+    let app = build_router(state.clone());
+    let (status, _) = json_request(
+        app,
+        "POST",
+        "/api/v1/licenses",
+        Some(json!({ "org_id": "suspend-org" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let app = build_router(state.clone());
+    let (status, _) = json_request(
+        app,
+        "POST",
+        "/api/v1/orgs/suspend-org/suspend",
+        Some(json!({})),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let app = build_router(state.clone());
+    let (status, body) =
+        json_request(app, "POST", "/api/v1/orgs/suspend-org/reinstate", None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["org"]["status"], "active");
+    assert_eq!(body["licenses"], json!([license_ids[0]]));
+
+    let reinstated = state
+        .db
+        .get_license(&license_ids[0])
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(reinstated.status, "active");
+    assert!(reinstated.suspended_at.is_none());
+    assert!(reinstated.suspended_by_org.is_none());
+    let alone = state
+        .db
+        .get_license("LIC-SUSPENDED-ALONE")
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(alone.status, "suspended");
+    let revoked = state
+        .db
+        .get_license(&license_ids[1])
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(revoked.status, "revoked");
+
+    let app = build_router(state);
+    let (status, _) = json_request(app, "POST", "/api/v1/orgs/suspend-org/reinstate", None).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn org_with_licenses_cannot_be_deleted() {
+    let state = setup_test_app().await;
+
+    let app = build_router(state.clone());
+    let _ = json_request(
+        app,
+        "POST",
+        "/api/v1/licenses",
+        Some(json!({ "org_id": "busy-org" })),
+    )
+    .await;
+
+    let app = build_router(state.clone());
+    let (status, _) = json_request(app, "DELETE", "/api/v1/orgs/busy-org", None).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let app = build_router(state.clone());
+    let (status, _) = json_request(
+        app,
+        "POST",
+        "/api/v1/orgs",
+        Some(json!({ "id": "empty-org", "name": "Empty" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+
+    let app = build_router(state.clone());
+    let (status, _) = json_request(app, "DELETE", "/api/v1/orgs/empty-org", None).await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+
+    let app = build_router(state);
+    let (status, _) = json_request(app, "GET", "/api/v1/orgs/empty-org", None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn list_orgs_filters_and_paginates() {
+    let state = setup_test_app().await;
+
+    for id in ["org-a", "org-b", "org-c"] {
+        let app = build_router(state.clone());
+        let (status, _) = json_request(
+            app,
+            "POST",
+            "/api/v1/orgs",
+            Some(json!({ "id": id, "name": id })),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
+    }
+
+    // IDs are unique
+    let app = build_router(state.clone());
+    let (status, _) = json_request(
+        app,
+        "POST",
+        "/api/v1/orgs",
+        Some(json!({ "id": "org-a", "name": "again" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let app = build_router(state.clone());
+    let (status, _) =
+        json_request(app, "POST", "/api/v1/orgs/org-b/suspend", Some(json!({}))).await;
+    assert_eq!(status, StatusCode::OK);
+
+    let app = build_router(state.clone());
+    let (status, body) = json_request(app, "GET", "/api/v1/orgs?per_page=2&page=2", None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["total"], 3);
+    assert_eq!(body["total_pages"], 2);
+    assert_eq!(body["orgs"].as_array().unwrap().len(), 1);
+
+    let app = build_router(state.clone());
+    let (_, body) = json_request(app, "GET", "/api/v1/orgs?status=suspended", None).await;
+    assert_eq!(body["total"], 1);
+    assert_eq!(body["orgs"][0]["id"], "org-b");
+
+    let app = build_router(state);
+    let (status, _) = json_request(app, "GET", "/api/v1/orgs?status=deleted", None).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+// ============================================================================
+// Bulk operations
+// ============================================================================
+
+/// Create `count` licenses for an organization, returning their IDs.
+async fn create_org_licenses(state: &AppState, org_id: &str, count: usize) -> Vec<String> {
+    let mut license_ids = Vec::new();
+    for _ in 0..count {
+        let app = build_router(state.clone());
+        let (status, body) = json_request(
+            app,
+            "POST",
+            "/api/v1/licenses",
+            Some(json!({ "org_id": org_id })),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
+        license_ids.push(body["license_id"].as_str().unwrap().to_string());
+    }
+    license_ids
+}
+
+#[tokio::test]
+async fn bulk_revoke_reports_each_license() {
+    let state = setup_test_app().await;
+    let ids = create_org_licenses(&state, "bulk-org", 3).await;
+
+    let app = build_router(state.clone());
+    let (status, _) = json_request(
+        app,
+        "POST",
+        &format!("/api/v1/licenses/{}/revoke", ids[1]),
+        Some(json!({})),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    // Duplicates are acted on once; unknown IDs are reported, not fatal
+    let app = build_router(state.clone());
+    let (status, body) = json_request(
+        app,
+        "POST",
+        "/api/v1/licenses/bulk",
+        Some(json!({
+            "action": "revoke",
+            "params": { "reason": "reseller churned" },
+            "license_ids": [ids[0], ids[1], "no-such-license", ids[2], ids[0]]
+        })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["action"], "revoke");
+    assert_eq!(body["mode"], "best_effort");
+    assert_eq!(body["total"], 4);
+    assert_eq!(body["succeeded"], 2);
+    assert_eq!(body["failed"], 2);
+
+    let results = body["results"].as_array().unwrap();
+    assert_eq!(results[0]["license_id"], ids[0].as_str());
+    assert_eq!(results[0]["status"], "ok");
+    assert!(results[0].get("error").is_none());
+    assert_eq!(results[1]["status"], "failed");
+    assert_eq!(results[1]["error"], "License is already revoked");
+    assert_eq!(results[2]["license_id"], "no-such-license");
+    assert_eq!(results[2]["status"], "failed");
+    assert_eq!(results[3]["status"], "ok");
+
+    for id in [&ids[0], &ids[2]] {
+        let app = build_router(state.clone());
+        let (_, license) = json_request(app, "GET", &format!("/api/v1/licenses/{id}"), None).await;
+        assert_eq!(license["status"], "revoked");
+    }
+
+    // Each license gets its own audit entry
+    let app = build_router(state);
+    let (_, audit) = json_request(app, "GET", "/api/v1/audit?action=license.revoked", None).await;
+    assert_eq!(audit["total"], 3);
+}
+
+#[tokio::test]
+async fn bulk_transactional_writes_nothing_unless_every_license_succeeds() {
+    let state = setup_test_app().await;
+    let ids = create_org_licenses(&state, "bulk-org", 2).await;
+
+    let app = build_router(state.clone());
+    let (status, body) = json_request(
+        app,
+        "POST",
+        "/api/v1/licenses/bulk",
+        Some(json!({
+            "action": "reinstate",
+            "params": {},
+            "license_ids": ids,
+            "mode": "transactional"
+        })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["succeeded"], 0);
+    assert_eq!(body["failed"], 2);
+    assert_eq!(body["results"][0]["error"], "License is already active");
+
+    let request = json!({
+        "action": "extend",
+        "params": { "new_expires_at": "2031-06-30" },
+        "license_ids": [ids[0], "no-such-license", ids[1]],
+        "mode": "transactional"
+    });
+    let app = build_router(state.clone());
+    let (_, body) = json_request(app, "POST", "/api/v1/licenses/bulk", Some(request)).await;
+    assert_eq!(body["succeeded"], 0);
+    assert_eq!(body["failed"], 1);
+    assert_eq!(body["skipped"], 2);
+    assert_eq!(body["results"][0]["status"], "skipped");
+    assert_eq!(body["results"][1]["status"], "failed");
+
+    let app = build_router(state.clone());
+    let (_, license) =
+        json_request(app, "GET", &format!("/api/v1/licenses/{}", ids[0]), None).await;
+    assert!(license["expires_at"].is_null());
+
+    let app = build_router(state.clone());
+    let (_, body) = json_request(
+        app,
+        "POST",
+        "/api/v1/licenses/bulk",
+        Some(json!({
+            "action": "extend",
+            "params": { "new_expires_at": "2031-06-30" },
+            "license_ids": ids,
+            "mode": "transactional"
+        })),
+    )
+    .await;
+    assert_eq!(body["succeeded"], 2);
+
+    for id in &ids {
+        let app = build_router(state.clone());
+        let (_, license) = json_request(app, "GET", &format!("/api/v1/licenses/{id}"), None).await;
+        assert!(license["expires_at"]
+            .as_str()
+            .unwrap()
+            .starts_with("2031-06-30"));
+    }
+}
+
+#[tokio::test]
+async fn bulk_release_by_filter_frees_seats_and_records_history() {
+    let db = setup_test_db().await;
+    let state = AppState {
+        db: db.clone(),
+        #[cfg(feature = "jwt-auth")]
+        auth: AuthState::disabled(),
+        signer: None,
+    };
+    let (bound_id, bound_key) = create_seat_license(&state, 2).await;
+    let (unbound_id, _) = create_seat_license(&state, 1).await;
+    let other_org = create_org_licenses(&state, "other-org", 1).await;
+
+    for hw in ["hw-a", "hw-b"] {
+        let (status, _) = client_request(&state, "/api/v1/client/bind", &bound_key, hw).await;
+        assert_eq!(status, StatusCode::OK);
+    }
+
+    let app = build_router(state.clone());
+    let (status, body) = json_request(
+        app,
+        "POST",
+        "/api/v1/licenses/bulk",
+        Some(json!({
+            "action": "release",
+            "params": { "reason": "device refresh" },
+            "filter": { "org_id": "seat-org", "bound": true }
+        })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["total"], 1);
+    assert_eq!(body["results"][0]["license_id"], bound_id.as_str());
+    assert_eq!(body["results"][0]["status"], "ok");
+
+    let app = build_router(state.clone());
+    let (_, devices) = json_request(
+        app,
+        "GET",
+        &format!("/api/v1/licenses/{bound_id}/devices"),
+        None,
+    )
+    .await;
+    assert_eq!(devices["devices"].as_array().unwrap().len(), 0);
+
+    let app = build_router(state.clone());
+    let (_, license) =
+        json_request(app, "GET", &format!("/api/v1/licenses/{bound_id}"), None).await;
+    assert!(license["hardware_id"].is_null());
+
+    let history: Vec<(String, Option<String>)> = match &*db {
+        Database::SQLite(pool) => sqlx::query_as(
+            "SELECT hardware_id, reason FROM license_binding_history \
+             WHERE license_id = ? AND action = 'admin_release' ORDER BY hardware_id",
+        )
+        .bind(&bound_id)
+        .fetch_all(pool)
+        .await
+        .unwrap(),
+        #[cfg(feature = "postgres")]
+        Database::Postgres(_) => panic!("PostgreSQL not supported in tests"),
+    };
+    assert_eq!(history.len(), 2);
+    assert_eq!(history[0].0, "hw-a");
+    assert_eq!(history[1].1.as_deref(), Some("device refresh"));
+
+    // Licenses outside the filter are untouched
+    for id in [&unbound_id, &other_org[0]] {
+        let app = build_router(state.clone());
+        let (_, license) = json_request(app, "GET", &format!("/api/v1/licenses/{id}"), None).await;
+        assert_eq!(license["status"], "active");
+    }
+}
+
+#[tokio::test]
+async fn bulk_set_tier_and_blacklist_by_filter() {
+    let state = setup_test_app().await;
+    let ids = create_org_licenses(&state, "bulk-org", 2).await;
+    let other = create_org_licenses(&state, "other-org", 1).await;
+
+    let app = build_router(state.clone());
+    let (_, body) = json_request(
+        app,
+        "POST",
+        "/api/v1/licenses/bulk",
+        Some(json!({
+            "action": "set_tier",
+            "params": { "tier": "pro", "features": ["export"] },
+            "filter": { "org_id": "bulk-org" }
+        })),
+    )
+    .await;
+    assert_eq!(body["succeeded"], 2);
+
+    let app = build_router(state.clone());
+    let (_, body) = json_request(
+        app,
+        "POST",
+        "/api/v1/licenses/bulk",
+        Some(json!({
+            "action": "blacklist",
+            "params": { "reason": "chargeback" },
+            "filter": { "tier": "pro" },
+            "mode": "transactional"
+        })),
+    )
+    .await;
+    assert_eq!(body["succeeded"], 2);
+
+    for id in &ids {
+        let app = build_router(state.clone());
+        let (_, license) = json_request(app, "GET", &format!("/api/v1/licenses/{id}"), None).await;
+        assert_eq!(license["tier"], "pro");
+        assert_eq!(license["status"], "revoked");
+    }
+
+    let app = build_router(state.clone());
+    let (_, body) = json_request(app, "GET", "/api/v1/licenses?blacklisted=true", None).await;
+    assert_eq!(body["total"], 2);
+
+    let app = build_router(state);
+    let (_, license) =
+        json_request(app, "GET", &format!("/api/v1/licenses/{}", other[0]), None).await;
+    assert!(license["tier"].is_null());
+    assert_eq!(license["status"], "active");
+}
+
+#[tokio::test]
+async fn bulk_rejects_invalid_requests() {
+    let state = setup_test_app().await;
+    let ids = create_org_licenses(&state, "bulk-org", 1).await;
+
+    let cases = [
+        // Neither IDs nor a filter
+        json!({ "action": "release", "params": {} }),
+        // Both
+        json!({
+            "action": "release",
+            "params": {},
+            "license_ids": ids,
+            "filter": { "org_id": "bulk-org" }
+        }),
+        json!({ "action": "release", "params": {}, "license_ids": [] }),
+        // A filter must narrow the selection
+        json!({ "action": "release", "params": {}, "filter": {} }),
+        json!({ "action": "blacklist", "params": { "reason": " " }, "license_ids": ids }),
+        json!({ "action": "extend", "params": { "new_expires_at": "soon" }, "license_ids": ids }),
+        json!({
+            "action": "release",
+            "params": {},
+            "filter": { "metadata": "no-separator" }
+        }),
+    ];
+    for request in cases {
+        let app = build_router(state.clone());
+        let (status, _) =
+            json_request(app, "POST", "/api/v1/licenses/bulk", Some(request.clone())).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST, "{request}");
+    }
+
+    let app = build_router(state.clone());
+    let (status, _) = json_request(
+        app,
+        "POST",
+        "/api/v1/licenses/bulk",
+        Some(json!({ "action": "delete", "params": {}, "license_ids": ids })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+
+    let app = build_router(state);
+    let (status, _) = json_request(
+        app,
+        "POST",
+        "/api/v1/licenses/bulk",
+        Some(json!({ "action": "release", "params": {}, "filter": { "org_id": "nope" } })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+// ============================================================================
+// Import / Export Tests
+// ============================================================================
+
+/// Helper to send a raw text body and read back a raw text response.
+async fn text_request(
+    app: axum::Router,
+    method: &str,
+    uri: &str,
+    body: &str,
+) -> (StatusCode, Option<String>, String) {
+    let request = Request::builder()
+        .method(method)
+        .uri(uri)
+        .header("Content-Type", "text/plain")
+        .body(Body::from(body.to_string()))
+        .unwrap();
+
+    let response = app.oneshot(request).await.unwrap();
+    let status = response.status();
+    let content_type = response
+        .headers()
+        .get("content-type")
+        .map(|v| v.to_str().unwrap().to_string());
+
+    let body_bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+
+    (
+        status,
+        content_type,
+        String::from_utf8(body_bytes.to_vec()).unwrap(),
+    )
+}
+
+#[tokio::test]
+async fn export_licenses_as_csv_and_ndjson_with_filters() {
+    let state = setup_test_app().await;
+    let ids = create_org_licenses(&state, "export-org", 3).await;
+    create_org_licenses(&state, "other-org", 2).await;
+
+    let app = build_router(state.clone());
+    let (status, _) = json_request(
+        app,
+        "POST",
+        &format!("/api/v1/licenses/{}/revoke", ids[0]),
+        Some(json!({ "reason": "churned" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let app = build_router(state.clone());
+    let (status, content_type, csv) = text_request(
+        app,
+        "GET",
+        "/api/v1/licenses/export?org_id=export-org&sort=license_id",
+        "",
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(content_type.as_deref(), Some("text/csv; charset=utf-8"));
+
+    let lines: Vec<&str> = csv.lines().collect();
+    assert_eq!(lines[0], talos::server::LICENSE_COLUMNS.join(","));
+    assert_eq!(lines.len(), 4);
+    let mut sorted = ids.clone();
+    sorted.sort();
+    for (line, id) in lines[1..].iter().zip(&sorted) {
+        assert!(line.starts_with(&format!("{id},")), "{line}");
+    }
+    let revoked = lines.iter().find(|l| l.starts_with(&ids[0])).unwrap();
+    assert!(revoked.contains(",revoked,"));
+    assert!(revoked.contains(",churned,"));
+
+    // Same filters as the listing, one JSON license per line
+    let app = build_router(state.clone());
+    let (status, content_type, ndjson) = text_request(
+        app,
+        "GET",
+        "/api/v1/licenses/export?format=ndjson&org_id=export-org&status=active",
+        "",
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(content_type.as_deref(), Some("application/x-ndjson"));
+    let licenses: Vec<Value> = ndjson
+        .lines()
+        .map(|l| serde_json::from_str(l).unwrap())
+        .collect();
+    assert_eq!(licenses.len(), 2);
+    for license in &licenses {
+        assert_eq!(license["org_id"], "export-org");
+        assert_eq!(license["status"], "active");
+        assert_eq!(license["max_devices"], 1);
+    }
+
+    for (uri, expected) in [
+        (
+            "/api/v1/licenses/export?format=xml",
+            StatusCode::BAD_REQUEST,
+        ),
+        (
+            "/api/v1/licenses/export?sort=features",
+            StatusCode::BAD_REQUEST,
+        ),
+        ("/api/v1/licenses/export?org_id=nope", StatusCode::NOT_FOUND),
+    ] {
+        let app = build_router(state.clone());
+        let (status, _, _) = text_request(app, "GET", uri, "").await;
+        assert_eq!(status, expected, "{uri}");
+    }
+}
+
+#[tokio::test]
+async fn import_round_trips_an_export() {
+    let source = setup_test_app().await;
+    let (bound_id, bound_key) = create_seat_license(&source, 2).await;
+    create_org_licenses(&source, "seat-org", 2).await;
+    let (status, _) = client_request(&source, "/api/v1/client/bind", &bound_key, "hw-a").await;
+    assert_eq!(status, StatusCode::OK);
+
+    let app = build_router(source.clone());
+    let (_, _, csv) = text_request(app, "GET", "/api/v1/licenses/export", "").await;
+    let app = build_router(source.clone());
+    let (_, original) =
+        json_request(app, "GET", &format!("/api/v1/licenses/{bound_id}"), None).await;
+
+    let target = setup_test_app().await;
+    let app = build_router(target.clone());
+    let (status, _, body) = text_request(app, "POST", "/api/v1/licenses/import", &csv).await;
+    assert_eq!(status, StatusCode::OK);
+    let report: Value = serde_json::from_str(&body).unwrap();
+    assert_eq!(report["committed"], true);
+    assert_eq!(report["total"], 3);
+    assert_eq!(report["valid"], 3);
+    assert_eq!(report["errors"], json!([]));
+
+    // Keys, timestamps and the binding survive the trip
+    let app = build_router(target.clone());
+    let (status, imported) =
+        json_request(app, "GET", &format!("/api/v1/licenses/{bound_id}"), None).await;
+    assert_eq!(status, StatusCode::OK);
+    for field in [
+        "license_key",
+        "issued_at",
+        "bound_at",
+        "hardware_id",
+        "org_id",
+        "max_devices",
+    ] {
+        assert_eq!(imported[field], original[field], "{field}");
+    }
+
+    let app = build_router(target.clone());
+    let (_, devices) = json_request(
+        app,
+        "GET",
+        &format!("/api/v1/licenses/{bound_id}/devices"),
+        None,
+    )
+    .await;
+    assert_eq!(devices["devices"].as_array().unwrap().len(), 1);
+
+    // The organization came along
+    let app = build_router(target.clone());
+    let (status, _) = json_request(app, "GET", "/api/v1/orgs/seat-org", None).await;
+    assert_eq!(status, StatusCode::OK);
+
+    // Importing the same file again clashes with every license
+    let app = build_router(target);
+    let (_, _, body) = text_request(app, "POST", "/api/v1/licenses/import", &csv).await;
+    let report: Value = serde_json::from_str(&body).unwrap();
+    assert_eq!(report["committed"], false);
+    assert_eq!(report["failed"], 3);
+}
+
+#[tokio::test]
+async fn import_dry_run_reports_row_errors_and_commit_is_all_or_nothing() {
+    let state = setup_test_app().await;
+
+    let csv = "\
+license_id,license_key,status,issued_at,org_id,max_devices,is_blacklisted,metadata
+imp-1,LIC-ABCD-EFGH-JKMN-PQRS,active,2024-03-01T09:30:00,import-org,2,false,
+imp-2,NOT-A-KEY,active,,,,,
+imp-3,LIC-ABCD-EFGH-JKMN-PQRS,active,,,,,
+imp-4,,archived,,,,,
+imp-5,,,yesterday,,,,
+imp-6,,,,,0,,
+imp-7,,,,,,maybe,
+imp-1,,,,,,,
+imp-9,,,,,,,\"{\"\"plan\"\":\"\"gold\"\"}\"
+";
+
+    let app = build_router(state.clone());
+    let (status, _, body) =
+        text_request(app, "POST", "/api/v1/licenses/import?dry_run=true", csv).await;
+    assert_eq!(status, StatusCode::OK);
+    let report: Value = serde_json::from_str(&body).unwrap();
+    assert_eq!(report["dry_run"], true);
+    assert_eq!(report["committed"], false);
+    assert_eq!(report["total"], 9);
+    assert_eq!(report["valid"], 2);
+    assert_eq!(report["failed"], 7);
+
+    let errors = report["errors"].as_array().unwrap();
+    let rows: Vec<u64> = errors.iter().map(|e| e["row"].as_u64().unwrap()).collect();
+    assert_eq!(rows, vec![3, 4, 5, 6, 7, 8, 9]);
+    assert_eq!(errors[0]["license_id"], "imp-2");
+    assert!(errors[0]["error"]
+        .as_str()
+        .unwrap()
+        .contains("invalid license key format"));
+    assert!(errors[1]["error"].as_str().unwrap().contains("duplicate"));
+    assert!(errors[2]["error"].as_str().unwrap().contains("status"));
+    assert!(errors[3]["error"].as_str().unwrap().contains("issued_at"));
+    assert!(errors[4]["error"].as_str().unwrap().contains("max_devices"));
+    assert!(errors[5]["error"]
+        .as_str()
+        .unwrap()
+        .contains("is_blacklisted"));
+    assert!(errors[6]["error"].as_str().unwrap().contains("duplicate"));
+
+    // Without dry_run the invalid rows still keep every row out
+    let app = build_router(state.clone());
+    let (status, _, body) = text_request(app, "POST", "/api/v1/licenses/import", csv).await;
+    assert_eq!(status, StatusCode::OK);
+    let report: Value = serde_json::from_str(&body).unwrap();
+    assert_eq!(report["committed"], false);
+    let app = build_router(state.clone());
+    let (status, _) = json_request(app, "GET", "/api/v1/licenses/imp-1", None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    // The valid rows alone, as NDJSON
+    let ndjson = r#"{"license_id":"imp-1","license_key":"LIC-ABCD-EFGH-JKMN-PQRS","status":"active","issued_at":"2024-03-01T09:30:00","org_id":"import-org","max_devices":2,"is_blacklisted":false}
+
+{"license_id":"imp-9","metadata":"{\"plan\":\"gold\"}"}
+"#;
+    let app = build_router(state.clone());
+    let (status, _, body) =
+        text_request(app, "POST", "/api/v1/licenses/import?format=ndjson", ndjson).await;
+    assert_eq!(status, StatusCode::OK);
+    let report: Value = serde_json::from_str(&body).unwrap();
+    assert_eq!(report["committed"], true, "{report}");
+    assert_eq!(report["total"], 2);
+
+    let app = build_router(state.clone());
+    let (_, license) = json_request(app, "GET", "/api/v1/licenses/imp-1", None).await;
+    assert_eq!(license["license_key"], "LIC-ABCD-EFGH-JKMN-PQRS");
+    assert_eq!(license["issued_at"], "2024-03-01 09:30:00");
+    assert_eq!(license["org_name"], "import-org");
+    assert_eq!(license["max_devices"], 2);
+
+    // Rows without a key get a generated one
+    let app = build_router(state.clone());
+    let (_, license) = json_request(app, "GET", "/api/v1/licenses/imp-9", None).await;
+    assert!(license["license_key"].as_str().unwrap().starts_with("LIC-"));
+    assert_eq!(license["metadata"]["plan"], "gold");
+
+    let app = build_router(state);
+    let (_, audit) = json_request(app, "GET", "/api/v1/audit?action=license.created", None).await;
+    assert_eq!(audit["total"], 2);
+}
+
+#[tokio::test]
+async fn import_rejects_bad_files() {
+    let state = setup_test_app().await;
+
+    let app = build_router(state.clone());
+    let (_, _, body) = text_request(
+        app,
+        "POST",
+        "/api/v1/licenses/import",
+        "license_id,colour\nimp-1,blue\n",
+    )
+    .await;
+    let report: Value = serde_json::from_str(&body).unwrap();
+    assert_eq!(report["committed"], false);
+    assert_eq!(report["total"], 0);
+    assert_eq!(report["errors"][0]["row"], 1);
+    assert!(report["errors"][0]["error"]
+        .as_str()
+        .unwrap()
+        .contains("colour"));
+
+    let app = build_router(state.clone());
+    let (_, _, body) = text_request(
+        app,
+        "POST",
+        "/api/v1/licenses/import?format=ndjson",
+        "{\"license_id\":\"imp-1\",\"colour\":\"blue\"}\nnot json\n",
+    )
+    .await;
+    let report: Value = serde_json::from_str(&body).unwrap();
+    assert_eq!(report["failed"], 2);
+    assert_eq!(report["errors"][0]["license_id"], "imp-1");
+    assert_eq!(report["errors"][1]["row"], 2);
+
+    let app = build_router(state.clone());
+    let (status, _, _) = text_request(app, "POST", "/api/v1/licenses/import?format=xml", "").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    // Licenses can't be imported into a suspended organization
+    state.db.insert_org(&test_org("frozen")).await.unwrap();
+    let app = build_router(state.clone());
+    let (status, _) = json_request(
+        app,
+        "POST",
+        "/api/v1/orgs/frozen/suspend",
+        Some(json!({ "reason": "unpaid" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let app = build_router(state);
+    let (_, _, body) = text_request(
+        app,
+        "POST",
+        "/api/v1/licenses/import?dry_run=true",
+        "org_id\nfrozen\n",
+    )
+    .await;
+    let report: Value = serde_json::from_str(&body).unwrap();
+    assert!(report["errors"][0]["error"]
+        .as_str()
+        .unwrap()
+        .contains("suspended"));
+}
+
+async fn start_trial(state: &AppState, hardware_id: &str) -> (StatusCode, Value) {
+    let app = build_router(state.clone());
+    json_request(
+        app,
+        "POST",
+        "/api/v1/client/trial",
+        Some(json!({ "hardware_id": hardware_id, "device_name": "Trial laptop" })),
+    )
+    .await
+}
+
+#[tokio::test]
+async fn client_starts_one_trial_per_machine() {
+    let state = setup_test_app().await;
+
+    let (status, body) = start_trial(&state, "hw-trial").await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(body["success"], true);
+    assert!(body["expires_at"].is_string());
+    let license_key = body["license_key"].as_str().unwrap().to_string();
+
+    // The trial is bound to the requesting machine
+    let (status, body) =
+        client_request(&state, "/api/v1/client/validate", &license_key, "hw-trial").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["valid"], true);
+
+    // The same machine can't start another trial
+    let (status, body) = start_trial(&state, "hw-trial").await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert_eq!(body["error"]["code"], "TRIAL_ALREADY_USED");
+
+    // Another machine can
+    let (status, _) = start_trial(&state, "hw-other").await;
+    assert_eq!(status, StatusCode::CREATED);
+
+    // Products are only accepted when trials are scoped per product
+    let app = build_router(state.clone());
+    let (status, body) = json_request(
+        app,
+        "POST",
+        "/api/v1/client/trial",
+        Some(json!({ "hardware_id": "hw-new", "product": "editor" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["error"]["code"], "INVALID_REQUEST");
+}
+
+#[tokio::test]
+async fn admin_converts_trial_without_rebinding() {
+    let state = setup_test_app().await;
+    let (status, body) = start_trial(&state, "hw-convert").await;
+    assert_eq!(status, StatusCode::CREATED);
+    let license_id = body["license_id"].as_str().unwrap().to_string();
+    let license_key = body["license_key"].as_str().unwrap().to_string();
+
+    let app = build_router(state.clone());
+    let (status, body) = json_request(
+        app,
+        "POST",
+        &format!("/api/v1/licenses/{license_id}/convert-trial"),
+        Some(json!({ "org_id": "buyer", "features": ["export"], "max_devices": 3 })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["license_key"], license_key);
+    assert_eq!(body["org_id"], "buyer");
+    assert_eq!(body["max_devices"], 3);
+    assert!(body["expires_at"].is_null());
+    assert_eq!(body["hardware_id"], "hw-convert");
+
+    // The binding survives the conversion
+    let (status, body) = client_request(
+        &state,
+        "/api/v1/client/validate",
+        &license_key,
+        "hw-convert",
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["features"], json!(["export"]));
+
+    let app = build_router(state.clone());
+    let (status, body) = json_request(
+        app,
+        "GET",
+        "/api/v1/audit?action=license.trial_converted",
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["total"], 1);
+    assert_eq!(body["entries"][0]["target_id"], license_id);
+
+    // A trial converts once, and the machine still can't start another trial
+    let app = build_router(state.clone());
+    let (status, _) = json_request(
+        app,
+        "POST",
+        &format!("/api/v1/licenses/{license_id}/convert-trial"),
+        Some(json!({})),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    let (status, _) = start_trial(&state, "hw-convert").await;
+    assert_eq!(status, StatusCode::CONFLICT);
+
+    // Only trials can be converted
+    let (paid_id, _) = create_seat_license(&state, 1).await;
+    let app = build_router(state.clone());
+    let (status, _) = json_request(
+        app,
+        "POST",
+        &format!("/api/v1/licenses/{paid_id}/convert-trial"),
+        Some(json!({})),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let app = build_router(state);
+    let (status, _) = json_request(
+        app,
+        "POST",
+        "/api/v1/licenses/missing/convert-trial",
+        Some(json!({})),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+// ============================================================================
+// Usage Meter Tests
+// ============================================================================
+
+async fn report_usage(
+    state: &AppState,
+    license_key: &str,
+    hardware_id: &str,
+    meter: &str,
+    amount: u64,
+    report_id: &str,
+) -> (StatusCode, Value) {
+    let app = build_router(state.clone());
+    json_request(
+        app,
+        "POST",
+        "/api/v1/client/usage",
+        Some(json!({
+            "license_key": license_key,
+            "hardware_id": hardware_id,
+            "meter": meter,
+            "amount": amount,
+            "report_id": report_id
+        })),
+    )
+    .await
+}
+
+#[tokio::test]
+async fn client_usage_reports_are_counted_once() {
+    let state = setup_test_app().await;
+    let (_, key) = create_seat_license(&state, 1).await;
+    let (status, _) = client_request(&state, "/api/v1/client/bind", &key, "hw-usage").await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, body) = report_usage(&state, &key, "hw-usage", "api_calls", 5, "r-1").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["meter"], "api_calls");
+    assert_eq!(body["used"], 5);
+    assert!(body["limit"].is_null());
+    assert_eq!(body["exceeded"], false);
+    assert_eq!(body["duplicate"], false);
+
+    // A retried report is acknowledged but not counted again
+    let (status, body) = report_usage(&state, &key, "hw-usage", "api_calls", 5, "r-1").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["used"], 5);
+    assert_eq!(body["duplicate"], true);
+
+    let (_, body) = report_usage(&state, &key, "hw-usage", "api_calls", 3, "r-2").await;
+    assert_eq!(body["used"], 8);
+
+    // Only devices holding a seat can report
+    let (status, body) = report_usage(&state, &key, "hw-other", "api_calls", 1, "r-3").await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    assert_eq!(body["error"]["code"], "HARDWARE_MISMATCH");
+
+    for (meter, amount, report_id) in [("api calls", 1, "r-4"), ("api_calls", 0, "r-5")] {
+        let (status, body) = report_usage(&state, &key, "hw-usage", meter, amount, report_id).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST, "{meter} {amount}");
+        assert_eq!(body["error"]["code"], "INVALID_REQUEST");
+    }
+    let (status, _) = report_usage(&state, &key, "hw-usage", "api_calls", 1, "").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (status, _) = report_usage(&state, "LIC-MISSING", "hw-usage", "api_calls", 1, "r-6").await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn client_usage_reports_are_refused_for_revoked_licenses() {
+    let state = setup_test_app().await;
+    let (license_id, key) = create_seat_license(&state, 1).await;
+    let (status, _) = client_request(&state, "/api/v1/client/bind", &key, "hw-revoked").await;
+    assert_eq!(status, StatusCode::OK);
+
+    let app = build_router(state.clone());
+    let (status, _) = json_request(
+        app,
+        "POST",
+        &format!("/api/v1/licenses/{license_id}/revoke"),
+        Some(json!({})),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, body) = report_usage(&state, &key, "hw-revoked", "api_calls", 5, "r-1").await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    assert_eq!(body["error"]["code"], "LICENSE_REVOKED");
+    let meters = state.db.list_usage_meters(&license_id).await.unwrap();
+    assert!(meters.is_empty());
+}
+
+#[tokio::test]
+async fn meter_limits_put_license_over_quota() {
+    let state = setup_test_app().await;
+    let (license_id, key) = create_seat_license(&state, 1).await;
+    let (status, _) = client_request(&state, "/api/v1/client/bind", &key, "hw-meter").await;
+    assert_eq!(status, StatusCode::OK);
+    report_usage(&state, &key, "hw-meter", "renders", 8, "r-1").await;
+
+    let app = build_router(state.clone());
+    let (status, body) = json_request(
+        app,
+        "PUT",
+        &format!("/api/v1/licenses/{license_id}/usage/renders"),
+        Some(json!({ "limit": 10 })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["quota_exceeded"], false);
+    assert_eq!(
+        body["meters"],
+        json!([{ "meter": "renders", "used": 8, "limit": 10, "exceeded": false }])
+    );
+
+    let (_, body) = report_usage(&state, &key, "hw-meter", "renders", 2, "r-2").await;
+    assert_eq!(body["used"], 10);
+    assert_eq!(body["exceeded"], true);
+    assert_eq!(body["quota_exceeded"], true);
+
+    // Other meters report the license-wide quota state too
+    let (_, body) = report_usage(&state, &key, "hw-meter", "exports", 1, "r-3").await;
+    assert_eq!(body["exceeded"], false);
+    assert_eq!(body["quota_exceeded"], true);
+
+    let app = build_router(state.clone());
+    let (status, body) = json_request(
+        app,
+        "GET",
+        &format!("/api/v1/licenses/{license_id}/usage"),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["quota_exceeded"], true);
+    assert_eq!(body["meters"].as_array().unwrap().len(), 2);
+    let stored = state.db.get_license(&license_id).await.unwrap().unwrap();
+    assert_eq!(stored.quota_exceeded, Some(true));
+
+    // Resetting the meter brings the license back under quota
+    let app = build_router(state.clone());
+    let (status, body) = json_request(
+        app,
+        "PUT",
+        &format!("/api/v1/licenses/{license_id}/usage/renders"),
+        Some(json!({ "reset": true })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["quota_exceeded"], false);
+    let stored = state.db.get_license(&license_id).await.unwrap().unwrap();
+    assert_eq!(stored.quota_exceeded, Some(false));
+
+    let app = build_router(state.clone());
+    let (status, body) = json_request(
+        app,
+        "GET",
+        "/api/v1/audit?action=license.usage_updated",
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["total"], 2);
+
+    for (uri, payload, expected) in [
+        (
+            format!("/api/v1/licenses/{license_id}/usage/renders"),
+            json!({ "limit": 5, "clear_limit": true }),
+            StatusCode::BAD_REQUEST,
+        ),
+        (
+            format!("/api/v1/licenses/{license_id}/usage/bad%20meter"),
+            json!({ "used": 1 }),
+            StatusCode::BAD_REQUEST,
+        ),
+        (
+            "/api/v1/licenses/missing/usage/renders".to_string(),
+            json!({ "used": 1 }),
+            StatusCode::NOT_FOUND,
+        ),
+    ] {
+        let app = build_router(state.clone());
+        let (status, _) = json_request(app, "PUT", &uri, Some(payload)).await;
+        assert_eq!(status, expected, "{uri}");
+    }
+}
+
+#[tokio::test]
+async fn update_usage_writes_the_bandwidth_meter() {
+    let state = setup_test_app().await;
+    let (license_id, _) = create_seat_license(&state, 1).await;
+
+    let app = build_router(state.clone());
+    let (status, _) = json_request(
+        app,
+        "PATCH",
+        &format!("/api/v1/licenses/{license_id}/usage"),
+        Some(json!({
+            "bandwidth_used_bytes": 300,
+            "bandwidth_limit_bytes": 1_000
+        })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let app = build_router(state.clone());
+    let (status, body) = json_request(
+        app,
+        "GET",
+        &format!("/api/v1/licenses/{license_id}/usage"),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(
+        body["meters"],
+        json!([{ "meter": "bandwidth", "used": 300, "limit": 1_000, "exceeded": false }])
+    );
+
+    let stored = state.db.get_license(&license_id).await.unwrap().unwrap();
+    assert_eq!(stored.bandwidth_used_bytes, Some(300));
+    assert_eq!(stored.bandwidth_limit_bytes, Some(1_000));
+}
+
+// ============================================================================
+// Billing Cycle Tests
+// ============================================================================
+
+#[tokio::test]
+async fn billing_cycles_are_validated_and_can_be_cleared() {
+    let state = setup_test_app().await;
+
+    let app = build_router(state.clone());
+    let (status, body) = json_request(
+        app,
+        "POST",
+        "/api/v1/licenses",
+        Some(json!({ "org_id": "billing-org", "billing_cycle": "0 0 1 * *" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(body["billing_cycle"], "0 0 1 * *");
+    let license_id = body["license_id"].as_str().unwrap().to_string();
+
+    let app = build_router(state.clone());
+    let (status, _) = json_request(
+        app,
+        "POST",
+        "/api/v1/licenses",
+        Some(json!({ "org_id": "billing-org", "billing_cycle": "fortnightly" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    for (cycle, expected) in [
+        ("monthly", json!("monthly")),
+        // An empty cycle falls back to the tier's
+        ("", Value::Null),
+    ] {
+        let app = build_router(state.clone());
+        let (status, body) = json_request(
+            app,
+            "PATCH",
+            &format!("/api/v1/licenses/{license_id}"),
+            Some(json!({ "billing_cycle": cycle })),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["billing_cycle"], expected);
+    }
+
+    let app = build_router(state.clone());
+    let (status, _) = json_request(
+        app,
+        "PATCH",
+        &format!("/api/v1/licenses/{license_id}"),
+        Some(json!({ "billing_cycle": "0 0 32 * *" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn reset_bandwidth_closes_the_usage_period() {
+    let state = setup_test_app().await;
+    let (license_id, key) = create_seat_license(&state, 1).await;
+    let (status, _) = client_request(&state, "/api/v1/client/bind", &key, "hw-billing").await;
+    assert_eq!(status, StatusCode::OK);
+
+    let app = build_router(state.clone());
+    json_request(
+        app,
+        "PUT",
+        &format!("/api/v1/licenses/{license_id}/usage/renders"),
+        Some(json!({ "limit": 5 })),
+    )
+    .await;
+    let (_, body) = report_usage(&state, &key, "hw-billing", "renders", 8, "r-1").await;
+    assert_eq!(body["quota_exceeded"], true);
+
+    let app = build_router(state.clone());
+    let (status, _) = json_request(
+        app,
+        "POST",
+        &format!("/api/v1/licenses/{license_id}/extend"),
+        Some(json!({ "new_expires_at": "2030-12-31", "reset_bandwidth": true })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let app = build_router(state.clone());
+    let (_, body) = json_request(
+        app,
+        "GET",
+        &format!("/api/v1/licenses/{license_id}/usage"),
+        None,
+    )
+    .await;
+    assert_eq!(body["quota_exceeded"], false);
+    assert_eq!(
+        body["meters"],
+        json!([{ "meter": "renders", "used": 0, "limit": 5, "exceeded": false }])
+    );
+
+    let app = build_router(state.clone());
+    let (status, body) = json_request(
+        app,
+        "GET",
+        &format!("/api/v1/licenses/{license_id}/usage-periods"),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(body["billing_cycle"].is_null());
+    let periods = body["periods"].as_array().unwrap();
+    assert_eq!(periods.len(), 1);
+    assert_eq!(periods[0]["meter"], "renders");
+    assert_eq!(periods[0]["used"], 8);
+    assert_eq!(periods[0]["limit"], 5);
+    assert_eq!(body["current_period_start"], periods[0]["period_end"]);
+
+    // Without reset_bandwidth the usage carries on
+    report_usage(&state, &key, "hw-billing", "renders", 1, "r-2").await;
+    let app = build_router(state.clone());
+    let (status, _) = json_request(
+        app,
+        "POST",
+        &format!("/api/v1/licenses/{license_id}/extend"),
+        Some(json!({ "new_expires_at": "2031-12-31" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(
+        state
+            .db
+            .list_usage_periods(&license_id)
+            .await
+            .unwrap()
+            .len(),
+        1
+    );
+
+    let app = build_router(state);
+    let (status, _) =
+        json_request(app, "GET", "/api/v1/licenses/missing/usage-periods", None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+// ============================================================================
+// Webhook Target Tests
+// ============================================================================
+
+#[tokio::test]
+async fn webhooks_cannot_target_private_addresses_by_default() {
+    let state = setup_test_app().await;
+
+    for url in [
+        "http://169.254.169.254/latest/meta-data/",
+        "http://127.0.0.1:8080/hook",
+        "http://localhost/hook",
+        "http://10.1.2.3/hook",
+        "http://[::1]/hook",
+    ] {
+        let app = build_router(state.clone());
+        let (status, body) = json_request(
+            app,
+            "POST",
+            "/api/v1/webhooks",
+            Some(json!({ "url": url, "events": ["license.created"] })),
+        )
+        .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST, "{url} was accepted");
+        assert!(body["error"]["message"]
+            .as_str()
+            .unwrap()
+            .contains("private"));
+    }
+}
@@ -1,16 +1,32 @@
 use std::sync::Arc;
 
-use chrono::Utc;
+use chrono::{Duration, NaiveDateTime, Utc};
 use sqlx::sqlite::SqlitePoolOptions;
 
 use talos::errors::{LicenseError, LicenseResult};
-use talos::server::database::{BindingAction, Database, License, PerformedBy};
+use talos::server::audit::{AuditEntry, AuditFilter};
+use talos::server::database::{
+    BindingAction, BindingHistoryFilter, Database, LeaseCheckout, License, PerformedBy, SeatClaim,
+};
+use talos::server::license_query::{
+    LicenseCursor, LicenseFilter, LicenseSort, LicenseSortField, SortOrder,
+};
+use talos::server::store::{AuditStore, LicenseStore, TrialStore, UsageStore};
+use talos::server::trials::TrialRegistration;
+use talos::server::usage::{UsageMeter, UsageReport};
+use talos::server::MemoryStore;
+use talos::tiers::BANDWIDTH_METER;
 
 /// Helper: create an in-memory SQLite Database with both tables.
 async fn setup_in_memory_db() -> LicenseResult<Arc<Database>> {
+    setup_db("sqlite::memory:", 1).await
+}
+
+/// Helper: create a SQLite Database at `url` with every table the tests use.
+async fn setup_db(url: &str, max_connections: u32) -> LicenseResult<Arc<Database>> {
     let pool = SqlitePoolOptions::new()
-        .max_connections(1)
-        .connect("sqlite::memory:")
+        .max_connections(max_connections)
+        .connect(url)
         .await
         .map_err(|e| LicenseError::ServerError(format!("db connect failed: {e}")))?;
 
@@ -46,7 +62,13 @@ async fn setup_in_memory_db() -> LicenseResult<Arc<Database>> {
             metadata        TEXT,
             bandwidth_used_bytes INTEGER DEFAULT 0,
             bandwidth_limit_bytes INTEGER,
-            quota_exceeded  INTEGER DEFAULT 0
+            quota_exceeded  INTEGER DEFAULT 0,
+            max_devices     INTEGER NOT NULL DEFAULT 1,
+            max_concurrent  INTEGER,
+            offline_days    INTEGER,
+            billing_cycle   TEXT,
+            usage_period_start TEXT,
+            suspended_by_org INTEGER
         );
         "#,
     )
@@ -54,6 +76,39 @@ async fn setup_in_memory_db() -> LicenseResult<Arc<Database>> {
     .await
     .map_err(|e| LicenseError::ServerError(format!("licenses table create failed: {e}")))?;
 
+    // Seat table
+    sqlx::query(
+        r#"
+        CREATE TABLE license_devices (
+            license_id      TEXT NOT NULL,
+            hardware_id     TEXT NOT NULL,
+            device_name     TEXT,
+            device_info     TEXT,
+            bound_at        TEXT NOT NULL,
+            last_seen_at    TEXT,
+            PRIMARY KEY (license_id, hardware_id)
+        );
+        "#,
+    )
+    .execute(&pool)
+    .await
+    .map_err(|e| LicenseError::ServerError(format!("devices table create failed: {e}")))?;
+
+    sqlx::query(
+        r#"
+        CREATE TABLE license_leases (
+            license_id      TEXT NOT NULL,
+            hardware_id     TEXT NOT NULL,
+            checked_out_at  TEXT NOT NULL,
+            expires_at      TEXT NOT NULL,
+            PRIMARY KEY (license_id, hardware_id)
+        );
+        "#,
+    )
+    .execute(&pool)
+    .await
+    .map_err(|e| LicenseError::ServerError(format!("leases table create failed: {e}")))?;
+
     // Binding history table
     sqlx::query(
         r#"
@@ -74,6 +129,79 @@ async fn setup_in_memory_db() -> LicenseResult<Arc<Database>> {
     .await
     .map_err(|e| LicenseError::ServerError(format!("history table create failed: {e}")))?;
 
+    // Audit log table
+    sqlx::query(
+        r#"
+        CREATE TABLE audit_log (
+            id              TEXT PRIMARY KEY,
+            occurred_at     TEXT NOT NULL,
+            actor           TEXT NOT NULL,
+            actor_token_id  TEXT,
+            action          TEXT NOT NULL,
+            target_type     TEXT NOT NULL,
+            target_id       TEXT NOT NULL,
+            before_state    TEXT,
+            after_state     TEXT,
+            ip_address      TEXT,
+            request_id      TEXT
+        );
+        "#,
+    )
+    .execute(&pool)
+    .await
+    .map_err(|e| LicenseError::ServerError(format!("audit table create failed: {e}")))?;
+
+    // Trial registry table
+    sqlx::query(
+        r#"
+        CREATE TABLE trial_registrations (
+            hardware_id     TEXT NOT NULL,
+            product         TEXT NOT NULL DEFAULT '',
+            license_id      TEXT NOT NULL,
+            started_at      TEXT NOT NULL,
+            converted_at    TEXT,
+            PRIMARY KEY (hardware_id, product)
+        );
+        "#,
+    )
+    .execute(&pool)
+    .await
+    .map_err(|e| LicenseError::ServerError(format!("trial table create failed: {e}")))?;
+
+    // Usage meter tables
+    sqlx::query(
+        r#"
+        CREATE TABLE usage_meters (
+            license_id      TEXT NOT NULL,
+            meter           TEXT NOT NULL,
+            used            INTEGER NOT NULL DEFAULT 0,
+            usage_limit     INTEGER,
+            updated_at      TEXT NOT NULL,
+            PRIMARY KEY (license_id, meter)
+        );
+        CREATE TABLE usage_reports (
+            license_id      TEXT NOT NULL,
+            report_id       TEXT NOT NULL,
+            meter           TEXT NOT NULL,
+            amount          INTEGER NOT NULL,
+            reported_at     TEXT NOT NULL,
+            PRIMARY KEY (license_id, report_id)
+        );
+        CREATE TABLE usage_periods (
+            license_id      TEXT NOT NULL,
+            meter           TEXT NOT NULL,
+            period_start    TEXT NOT NULL,
+            period_end      TEXT NOT NULL,
+            used            INTEGER NOT NULL,
+            usage_limit     INTEGER,
+            PRIMARY KEY (license_id, meter, period_start)
+        );
+        "#,
+    )
+    .execute(&pool)
+    .await
+    .map_err(|e| LicenseError::ServerError(format!("usage table create failed: {e}")))?;
+
     Ok(Arc::new(Database::SQLite(pool)))
 }
 
@@ -116,6 +244,12 @@ async fn insert_test_license(
         bandwidth_used_bytes: None,
         bandwidth_limit_bytes: None,
         quota_exceeded: None,
+        max_devices: 1,
+        max_concurrent: None,
+        offline_days: None,
+        billing_cycle: None,
+        usage_period_start: None,
+        suspended_by_org: None,
     };
 
     db.insert_license(license).await
@@ -195,6 +329,261 @@ async fn list_licenses_by_org_returns_matching() -> LicenseResult<()> {
     Ok(())
 }
 
+// =============================================================================
+// License Listing Tests
+// =============================================================================
+
+/// An active, unbound license issued at `issued_at` for the listing tests.
+fn listing_license(license_id: &str, issued_at: chrono::NaiveDateTime) -> License {
+    License {
+        license_id: license_id.to_string(),
+        client_id: None,
+        status: "active".to_string(),
+        features: None,
+        issued_at,
+        expires_at: None,
+        hardware_id: None,
+        signature: None,
+        last_heartbeat: None,
+        org_id: None,
+        org_name: None,
+        license_key: None,
+        tier: None,
+        device_name: None,
+        device_info: None,
+        bound_at: None,
+        last_seen_at: None,
+        suspended_at: None,
+        revoked_at: None,
+        revoke_reason: None,
+        grace_period_ends_at: None,
+        suspension_message: None,
+        is_blacklisted: None,
+        blacklisted_at: None,
+        blacklist_reason: None,
+        metadata: None,
+        bandwidth_used_bytes: None,
+        bandwidth_limit_bytes: None,
+        quota_exceeded: None,
+        max_devices: 1,
+        max_concurrent: None,
+        offline_days: None,
+        billing_cycle: None,
+        usage_period_start: None,
+        suspended_by_org: None,
+    }
+}
+
+/// Walk every page of a listing with a cursor, two licenses at a time.
+async fn list_all(
+    store: &dyn LicenseStore,
+    filter: &LicenseFilter,
+    sort: LicenseSort,
+) -> LicenseResult<(Vec<String>, u64)> {
+    let mut ids = Vec::new();
+    let mut after: Option<LicenseCursor> = None;
+    loop {
+        let (page, total) = store
+            .list_licenses(filter, sort, after.as_ref(), 2, 0)
+            .await?;
+        ids.extend(page.iter().map(|l| l.license_id.clone()));
+        match page.last() {
+            Some(last) if page.len() == 2 => after = Some(LicenseCursor::after(last, sort)),
+            _ => return Ok((ids, total)),
+        }
+    }
+}
+
+#[tokio::test]
+async fn list_licenses_filters_sorts_and_pages_like_memory_store() -> LicenseResult<()> {
+    let db = setup_in_memory_db().await?;
+    let memory = MemoryStore::new();
+
+    let t0 = Utc::now().naive_utc() - Duration::days(1);
+    let hours = |h| t0 + Duration::hours(h);
+    let days = |d| Some(t0 + Duration::days(d));
+    let licenses = [
+        License {
+            org_id: Some("a".into()),
+            tier: Some("pro".into()),
+            license_key: Some("ACME-0001".into()),
+            expires_at: days(10),
+            hardware_id: Some("HW-1".into()),
+            bound_at: Some(t0),
+            metadata: Some(r#"{"region":"eu"}"#.into()),
+            ..listing_license("L1", hours(0))
+        },
+        License {
+            org_id: Some("a".into()),
+            license_key: Some("ACME-0002".into()),
+            metadata: Some(r#"{"region":"us"}"#.into()),
+            ..listing_license("L2", hours(1))
+        },
+        License {
+            org_id: Some("b".into()),
+            tier: Some("basic".into()),
+            license_key: Some("GLBX-0001".into()),
+            expires_at: days(5),
+            status: "revoked".into(),
+            is_blacklisted: Some(true),
+            ..listing_license("L3", hours(2))
+        },
+        License {
+            org_id: Some("b".into()),
+            tier: Some("pro".into()),
+            // Only string values match metadata filters
+            metadata: Some(r#"{"region":5}"#.into()),
+            ..listing_license("L4", hours(2))
+        },
+        License {
+            tier: Some("pro".into()),
+            license_key: Some("acme-0003".into()),
+            expires_at: days(1),
+            status: "suspended".into(),
+            // Metadata that isn't JSON never matches, nor fails the query
+            metadata: Some("region=eu".into()),
+            ..listing_license("L5", hours(3))
+        },
+        License {
+            org_id: Some("a".into()),
+            tier: Some("basic".into()),
+            license_key: Some("ACME_X".into()),
+            expires_at: days(10),
+            ..listing_license("L6", hours(4))
+        },
+    ];
+    for license in licenses {
+        db.insert_license(license.clone()).await?;
+        memory.insert_license(license).await?;
+    }
+
+    let sort = |field, order| LicenseSort { field, order };
+    let cases: Vec<(LicenseFilter, LicenseSort, Vec<&str>)> = vec![
+        (
+            LicenseFilter::default(),
+            LicenseSort::default(),
+            vec!["L1", "L2", "L3", "L4", "L5", "L6"],
+        ),
+        (
+            LicenseFilter {
+                org_id: Some("a".into()),
+                ..Default::default()
+            },
+            sort(LicenseSortField::ExpiresAt, SortOrder::Desc),
+            vec!["L6", "L1", "L2"],
+        ),
+        (
+            LicenseFilter {
+                key_prefix: Some("acme".into()),
+                ..Default::default()
+            },
+            LicenseSort::default(),
+            vec!["L1", "L2", "L5", "L6"],
+        ),
+        (
+            // `_` is matched literally, not as a wildcard
+            LicenseFilter {
+                key_prefix: Some("ACME_".into()),
+                ..Default::default()
+            },
+            LicenseSort::default(),
+            vec!["L6"],
+        ),
+        (
+            LicenseFilter {
+                metadata: Some(("region".into(), "eu".into())),
+                ..Default::default()
+            },
+            LicenseSort::default(),
+            vec!["L1"],
+        ),
+        (
+            LicenseFilter {
+                bound: Some(false),
+                blacklisted: Some(false),
+                ..Default::default()
+            },
+            LicenseSort::default(),
+            vec!["L2", "L4", "L5", "L6"],
+        ),
+        (
+            LicenseFilter {
+                expires_before: Some(t0 + Duration::days(6)),
+                ..Default::default()
+            },
+            LicenseSort::default(),
+            vec!["L3", "L5"],
+        ),
+        (
+            LicenseFilter {
+                created_after: Some(t0 + Duration::hours(2)),
+                created_before: Some(t0 + Duration::hours(4)),
+                ..Default::default()
+            },
+            LicenseSort::default(),
+            vec!["L3", "L4", "L5"],
+        ),
+        (
+            LicenseFilter {
+                status: Some("active".into()),
+                tier: Some("pro".into()),
+                ..Default::default()
+            },
+            LicenseSort::default(),
+            vec!["L1", "L4"],
+        ),
+        (
+            LicenseFilter::default(),
+            sort(LicenseSortField::Tier, SortOrder::Asc),
+            vec!["L3", "L6", "L1", "L4", "L5", "L2"],
+        ),
+        (
+            LicenseFilter::default(),
+            sort(LicenseSortField::Tier, SortOrder::Desc),
+            vec!["L5", "L4", "L1", "L6", "L3", "L2"],
+        ),
+        (
+            LicenseFilter::default(),
+            sort(LicenseSortField::LicenseKey, SortOrder::Desc),
+            vec!["L5", "L3", "L6", "L2", "L1", "L4"],
+        ),
+        (
+            LicenseFilter::default(),
+            sort(LicenseSortField::LicenseId, SortOrder::Desc),
+            vec!["L6", "L5", "L4", "L3", "L2", "L1"],
+        ),
+    ];
+
+    for (filter, sort, expected) in cases {
+        for store in [&*db as &dyn LicenseStore, &memory] {
+            let (ids, total) = list_all(store, &filter, sort).await?;
+            assert_eq!(
+                ids,
+                expected,
+                "{} with {filter:?} {sort:?}",
+                store.backend()
+            );
+            assert_eq!(total, expected.len() as u64);
+        }
+    }
+
+    // Offsets skip matches after the cursor
+    let (page, total) = db
+        .list_licenses(
+            &LicenseFilter::default(),
+            LicenseSort::default(),
+            None,
+            2,
+            3,
+        )
+        .await?;
+    let ids: Vec<&str> = page.iter().map(|l| l.license_id.as_str()).collect();
+    assert_eq!(ids, ["L4", "L5"]);
+    assert_eq!(total, 6);
+
+    Ok(())
+}
+
 // =============================================================================
 // Status Update Tests
 // =============================================================================
@@ -295,6 +684,267 @@ async fn release_license_clears_hardware_fields() -> LicenseResult<()> {
     Ok(())
 }
 
+// =============================================================================
+// Seat Tests
+// =============================================================================
+
+#[tokio::test]
+async fn claim_license_seat_respects_max_devices() -> LicenseResult<()> {
+    let db = setup_in_memory_db().await?;
+
+    insert_test_license(&db, "LIC-SEATS", None, None).await?;
+    let mut license = db.get_license("LIC-SEATS").await?.unwrap();
+    license.max_devices = 2;
+    db.insert_license(license.clone()).await?;
+
+    assert!(matches!(
+        db.claim_license_seat(&license, "HW-1", Some("Laptop"), None)
+            .await?,
+        SeatClaim::Claimed
+    ));
+    assert!(matches!(
+        db.claim_license_seat(&license, "HW-2", None, None).await?,
+        SeatClaim::Claimed
+    ));
+    assert!(matches!(
+        db.claim_license_seat(&license, "HW-1", None, None).await?,
+        SeatClaim::Existing(_)
+    ));
+
+    match db.claim_license_seat(&license, "HW-3", None, None).await? {
+        SeatClaim::Full(devices) => {
+            let ids: Vec<_> = devices.iter().map(|d| d.hardware_id.as_str()).collect();
+            assert_eq!(ids, vec!["HW-1", "HW-2"]);
+        }
+        other => panic!("expected a full license, got {other:?}"),
+    }
+
+    // The license's binding fields follow the most recent seat
+    let stored = db.get_license("LIC-SEATS").await?.unwrap();
+    assert_eq!(stored.hardware_id.as_deref(), Some("HW-2"));
+
+    assert!(db.remove_license_device("LIC-SEATS", "HW-2").await?);
+    let stored = db.get_license("LIC-SEATS").await?.unwrap();
+    assert_eq!(stored.hardware_id.as_deref(), Some("HW-1"));
+    assert_eq!(stored.device_name.as_deref(), Some("Laptop"));
+
+    assert_eq!(db.remove_all_license_devices("LIC-SEATS").await?, 1);
+    let stored = db.get_license("LIC-SEATS").await?.unwrap();
+    assert!(!stored.is_bound());
+    assert!(db.list_license_devices("LIC-SEATS").await?.is_empty());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn insert_licenses_writes_all_or_nothing() -> LicenseResult<()> {
+    let db = setup_in_memory_db().await?;
+
+    insert_test_license(&db, "LIC-A", Some("KEY-A"), None).await?;
+    insert_test_license(&db, "LIC-B", Some("KEY-B"), None).await?;
+    let license = db.get_license("LIC-A").await?.unwrap();
+    assert!(matches!(
+        db.claim_license_seat(&license, "HW-1", None, None).await?,
+        SeatClaim::Claimed
+    ));
+
+    // The second license takes a key already in use, so neither is written
+    let mut a = db.get_license("LIC-A").await?.unwrap();
+    a.status = "revoked".to_string();
+    let mut b = db.get_license("LIC-B").await?.unwrap();
+    b.license_key = Some("KEY-A".to_string());
+    assert!(db.insert_licenses(&[a.clone(), b], true).await.is_err());
+
+    let stored = db.get_license("LIC-A").await?.unwrap();
+    assert_eq!(stored.status, "active");
+    assert!(stored.is_bound());
+    assert_eq!(db.list_license_devices("LIC-A").await?.len(), 1);
+
+    db.insert_licenses(&[a], true).await?;
+    let stored = db.get_license("LIC-A").await?.unwrap();
+    assert_eq!(stored.status, "revoked");
+    assert!(!stored.is_bound());
+    assert!(db.list_license_devices("LIC-A").await?.is_empty());
+
+    Ok(())
+}
+
+// =============================================================================
+// Floating Lease Tests
+// =============================================================================
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn concurrent_binds_claim_exactly_one_seat() -> LicenseResult<()> {
+    // A file database so every connection in the pool sees the same data
+    let path = std::env::temp_dir().join(format!("talos-bind-race-{}.db", uuid::Uuid::new_v4()));
+    let db = setup_db(&format!("sqlite://{}?mode=rwc", path.display()), 8).await?;
+
+    insert_test_license(&db, "LIC-RACE", None, None).await?;
+    let license = db.get_license("LIC-RACE").await?.unwrap();
+
+    let binds: Vec<_> = (0..16)
+        .map(|i| {
+            let db = Arc::clone(&db);
+            let license = license.clone();
+            tokio::spawn(async move {
+                db.claim_license_seat(&license, &format!("HW-{i}"), None, None)
+                    .await
+            })
+        })
+        .collect();
+
+    let mut winners = Vec::new();
+    for (i, bind) in binds.into_iter().enumerate() {
+        match bind.await.expect("bind task panicked")? {
+            SeatClaim::Claimed => winners.push(format!("HW-{i}")),
+            SeatClaim::Full(devices) => assert_eq!(devices.len(), 1),
+            SeatClaim::Existing(device) => panic!("unexpected existing seat {device:?}"),
+        }
+    }
+
+    assert_eq!(winners.len(), 1, "exactly one bind should win: {winners:?}");
+    let devices = db.list_license_devices("LIC-RACE").await?;
+    assert_eq!(devices.len(), 1);
+    assert_eq!(devices[0].hardware_id, winners[0]);
+
+    // The license binding fields agree with the seat that was taken
+    let stored = db.get_license("LIC-RACE").await?.unwrap();
+    assert_eq!(stored.hardware_id.as_deref(), Some(winners[0].as_str()));
+
+    drop(db);
+    for suffix in ["", "-wal", "-shm"] {
+        let _ = std::fs::remove_file(format!("{}{suffix}", path.display()));
+    }
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn concurrent_checkouts_respect_max_concurrent() -> LicenseResult<()> {
+    // A file database so every connection in the pool sees the same data
+    let path = std::env::temp_dir().join(format!("talos-lease-race-{}.db", uuid::Uuid::new_v4()));
+    let db = setup_db(&format!("sqlite://{}?mode=rwc", path.display()), 8).await?;
+
+    insert_test_license(&db, "LIC-LEASE-RACE", None, None).await?;
+    let mut license = db.get_license("LIC-LEASE-RACE").await?.unwrap();
+    license.max_concurrent = Some(2);
+    db.insert_license(license.clone()).await?;
+
+    let checkouts: Vec<_> = (0..16)
+        .map(|i| {
+            let db = Arc::clone(&db);
+            let license = license.clone();
+            tokio::spawn(async move {
+                db.checkout_license_lease(&license, &format!("HW-{i}"), Duration::minutes(5))
+                    .await
+            })
+        })
+        .collect();
+
+    let mut winners = Vec::new();
+    for (i, checkout) in checkouts.into_iter().enumerate() {
+        match checkout.await.expect("checkout task panicked")? {
+            LeaseCheckout::Granted(_) => winners.push(format!("HW-{i}")),
+            LeaseCheckout::Full(active) => assert_eq!(active, 2),
+        }
+    }
+
+    assert_eq!(
+        winners.len(),
+        2,
+        "exactly two checkouts should win: {winners:?}"
+    );
+    let leases = db
+        .list_active_leases("LIC-LEASE-RACE", Utc::now().naive_utc())
+        .await?;
+    assert_eq!(leases.len(), 2);
+
+    drop(db);
+    for suffix in ["", "-wal", "-shm"] {
+        let _ = std::fs::remove_file(format!("{}{suffix}", path.display()));
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn checkout_license_lease_respects_max_concurrent() -> LicenseResult<()> {
+    let db = setup_in_memory_db().await?;
+
+    insert_test_license(&db, "LIC-FLOAT", None, None).await?;
+    let mut license = db.get_license("LIC-FLOAT").await?.unwrap();
+    license.max_concurrent = Some(1);
+    db.insert_license(license.clone()).await?;
+
+    let ttl = Duration::minutes(5);
+    let first = match db.checkout_license_lease(&license, "HW-1", ttl).await? {
+        LeaseCheckout::Granted(lease) => lease,
+        other => panic!("expected a lease, got {other:?}"),
+    };
+    assert!(first.expires_at > Utc::now().naive_utc());
+
+    // The pool is empty for other devices, but the holder can renew
+    assert!(matches!(
+        db.checkout_license_lease(&license, "HW-2", ttl).await?,
+        LeaseCheckout::Full(1)
+    ));
+    assert!(matches!(
+        db.checkout_license_lease(&license, "HW-1", ttl).await?,
+        LeaseCheckout::Granted(_)
+    ));
+    assert!(db
+        .renew_license_lease("LIC-FLOAT", "HW-1", ttl)
+        .await?
+        .is_some());
+    assert!(db
+        .renew_license_lease("LIC-FLOAT", "HW-2", ttl)
+        .await?
+        .is_none());
+
+    // Checking in returns the lease to the pool
+    assert!(db.checkin_license_lease("LIC-FLOAT", "HW-1").await?);
+    assert!(matches!(
+        db.checkout_license_lease(&license, "HW-2", ttl).await?,
+        LeaseCheckout::Granted(_)
+    ));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn expired_lease_returns_to_the_pool() -> LicenseResult<()> {
+    let db = setup_in_memory_db().await?;
+
+    insert_test_license(&db, "LIC-EXPIRED-LEASE", None, None).await?;
+    let mut license = db.get_license("LIC-EXPIRED-LEASE").await?.unwrap();
+    license.max_concurrent = Some(1);
+    db.insert_license(license.clone()).await?;
+
+    // A lease whose TTL has already run out, as if heartbeats stopped
+    db.checkout_license_lease(&license, "HW-1", Duration::seconds(-1))
+        .await?;
+
+    // It cannot be renewed and no longer blocks other devices
+    assert!(db
+        .renew_license_lease("LIC-EXPIRED-LEASE", "HW-1", Duration::minutes(5))
+        .await?
+        .is_none());
+    assert!(matches!(
+        db.checkout_license_lease(&license, "HW-2", Duration::minutes(5))
+            .await?,
+        LeaseCheckout::Granted(_)
+    ));
+
+    assert_eq!(db.delete_expired_leases(Utc::now().naive_utc()).await?, 1);
+    let active = db
+        .list_active_leases("LIC-EXPIRED-LEASE", Utc::now().naive_utc())
+        .await?;
+    assert_eq!(active.len(), 1);
+    assert_eq!(active[0].hardware_id, "HW-2");
+
+    Ok(())
+}
+
 // =============================================================================
 // Binding History Tests
 // =============================================================================
@@ -329,21 +979,506 @@ async fn record_binding_history_inserts_record() -> LicenseResult<()> {
     )
     .await?;
 
-    // Verify records were inserted (query directly since we don't have a get method yet)
-    let pool = match db.as_ref() {
-        Database::SQLite(p) => p,
-        #[allow(unreachable_patterns)]
-        _ => panic!("Expected SQLite"),
+    let (entries, total) = db
+        .list_binding_history(
+            &BindingHistoryFilter {
+                license_id: Some("LIC-HISTORY".to_string()),
+                ..Default::default()
+            },
+            50,
+            0,
+        )
+        .await?;
+    assert_eq!(total, 2, "should have 2 history records");
+
+    // Newest first
+    assert_eq!(entries[0].action, "admin_release");
+    assert_eq!(entries[0].performed_by.as_deref(), Some("admin"));
+    assert_eq!(
+        entries[0].reason.as_deref(),
+        Some("User requested transfer")
+    );
+    assert_eq!(entries[1].action, "bind");
+    assert_eq!(entries[1].device_name.as_deref(), Some("Test Device"));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn list_binding_history_filters_and_pages() -> LicenseResult<()> {
+    let db = setup_in_memory_db().await?;
+
+    for (license_id, hardware_id) in [("LIC-H1", "HW-1"), ("LIC-H2", "HW-1"), ("LIC-H2", "HW-2")] {
+        db.record_binding_history(
+            license_id,
+            BindingAction::Bind,
+            Some(hardware_id),
+            None,
+            None,
+            PerformedBy::Client,
+            None,
+        )
+        .await?;
+    }
+    db.record_binding_history(
+        "LIC-H1",
+        BindingAction::SystemRelease,
+        Some("HW-1"),
+        None,
+        None,
+        PerformedBy::System,
+        Some("stale"),
+    )
+    .await?;
+
+    // Every license a machine has been on
+    let by_device = BindingHistoryFilter {
+        hardware_id: Some("HW-1".to_string()),
+        ..Default::default()
+    };
+    let (entries, total) = db.list_binding_history(&by_device, 50, 0).await?;
+    assert_eq!(total, 3);
+    assert_eq!(entries[0].action, "system_release");
+    assert!(entries.iter().any(|e| e.license_id == "LIC-H2"));
+
+    let (page, total) = db.list_binding_history(&by_device, 2, 2).await?;
+    assert_eq!(total, 3);
+    assert_eq!(page.len(), 1);
+    assert_eq!(page[0].license_id, "LIC-H1");
+    assert_eq!(page[0].action, "bind");
+
+    let (entries, total) = db
+        .list_binding_history(
+            &BindingHistoryFilter {
+                action: Some(BindingAction::Bind),
+                performed_by: Some(PerformedBy::Client),
+                ..Default::default()
+            },
+            50,
+            0,
+        )
+        .await?;
+    assert_eq!(total, 3);
+    assert!(entries.iter().all(|e| e.action == "bind"));
+
+    let (entries, total) = db
+        .list_binding_history(
+            &BindingHistoryFilter {
+                license_id: Some("LIC-H1".to_string()),
+                performed_by: Some(PerformedBy::Admin),
+                ..Default::default()
+            },
+            50,
+            0,
+        )
+        .await?;
+    assert_eq!(total, 0);
+    assert!(entries.is_empty());
+
+    Ok(())
+}
+
+// =============================================================================
+// Audit Log Tests
+// =============================================================================
+
+fn audit_entry(id: &str, minutes_ago: i64, actor: &str, action: &str) -> AuditEntry {
+    AuditEntry {
+        id: id.to_string(),
+        occurred_at: Utc::now().naive_utc() - Duration::minutes(minutes_ago),
+        actor: actor.to_string(),
+        actor_token_id: None,
+        action: action.to_string(),
+        target_type: "license".to_string(),
+        target_id: "LIC-AUDIT".to_string(),
+        before_state: None,
+        after_state: Some(r#"{"status":"active"}"#.to_string()),
+        ip_address: Some("10.0.0.1".to_string()),
+        request_id: None,
+    }
+}
+
+#[tokio::test]
+async fn list_audit_entries_filters_and_pages() -> LicenseResult<()> {
+    let db = setup_in_memory_db().await?;
+
+    db.insert_audit_entry(&audit_entry("a1", 30, "alice", "license.created"))
+        .await?;
+    db.insert_audit_entry(&audit_entry("a2", 20, "bob", "license.revoked"))
+        .await?;
+    db.insert_audit_entry(&audit_entry("a3", 10, "alice", "license.extended"))
+        .await?;
+
+    // Newest first
+    let (entries, total) = db
+        .list_audit_entries(&AuditFilter::default(), 10, 0)
+        .await?;
+    assert_eq!(total, 3);
+    let ids: Vec<&str> = entries.iter().map(|e| e.id.as_str()).collect();
+    assert_eq!(ids, vec!["a3", "a2", "a1"]);
+
+    // Filter by actor
+    let by_alice = AuditFilter {
+        actor: Some("alice".to_string()),
+        ..Default::default()
+    };
+    let (entries, total) = db.list_audit_entries(&by_alice, 10, 0).await?;
+    assert_eq!(total, 2);
+    assert!(entries.iter().all(|e| e.actor == "alice"));
+
+    // Filter by time range
+    let recent = AuditFilter {
+        since: Some(Utc::now().naive_utc() - Duration::minutes(25)),
+        ..Default::default()
     };
+    let (_, total) = db.list_audit_entries(&recent, 10, 0).await?;
+    assert_eq!(total, 2);
 
-    let count: (i64,) =
-        sqlx::query_as("SELECT COUNT(*) FROM license_binding_history WHERE license_id = ?")
-            .bind("LIC-HISTORY")
-            .fetch_one(pool)
-            .await
-            .map_err(|e| LicenseError::ServerError(format!("query failed: {e}")))?;
+    // Second page of one
+    let (entries, total) = db.list_audit_entries(&AuditFilter::default(), 1, 1).await?;
+    assert_eq!(total, 3);
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].id, "a2");
+    assert_eq!(entries[0].ip_address.as_deref(), Some("10.0.0.1"));
 
-    assert_eq!(count.0, 2, "should have 2 history records");
+    Ok(())
+}
+
+// =============================================================================
+// Trial Registry Tests
+// =============================================================================
+
+fn trial(hardware_id: &str, product: &str, license_id: &str) -> TrialRegistration {
+    TrialRegistration {
+        hardware_id: hardware_id.to_string(),
+        product: product.to_string(),
+        license_id: license_id.to_string(),
+        started_at: Utc::now().naive_utc(),
+        converted_at: None,
+    }
+}
+
+#[tokio::test]
+async fn insert_trial_allows_one_trial_per_machine_and_product() -> LicenseResult<()> {
+    let db = setup_in_memory_db().await?;
+    let now = Utc::now().naive_utc();
+
+    let first = listing_license("LIC-T1", now);
+    assert!(
+        db.insert_trial(&trial("HW-T", "", "LIC-T1"), &first)
+            .await?
+    );
+    assert!(db.get_license("LIC-T1").await?.is_some());
+
+    // A second trial for the same machine writes nothing
+    let second = listing_license("LIC-T2", now);
+    assert!(
+        !db.insert_trial(&trial("HW-T", "", "LIC-T2"), &second)
+            .await?
+    );
+    assert!(db.get_license("LIC-T2").await?.is_none());
+
+    // Another product is a separate trial
+    assert!(
+        db.insert_trial(&trial("HW-T", "viewer", "LIC-T2"), &second)
+            .await?
+    );
+
+    let registered = db.get_trial_by_license("LIC-T1").await?.unwrap();
+    assert_eq!(registered.hardware_id, "HW-T");
+    assert!(!registered.is_converted());
+    assert!(db.get_trial_by_license("LIC-NONE").await?.is_none());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn convert_trial_saves_license_once() -> LicenseResult<()> {
+    let db = setup_in_memory_db().await?;
+    let now = Utc::now().naive_utc();
+
+    let mut license = listing_license("LIC-C1", now);
+    db.insert_trial(&trial("HW-C", "", "LIC-C1"), &license)
+        .await?;
+
+    license.tier = Some("pro".to_string());
+    assert!(db.convert_trial(&license, now).await?);
+    assert!(db
+        .get_trial_by_license("LIC-C1")
+        .await?
+        .unwrap()
+        .is_converted());
+    assert_eq!(
+        db.get_license("LIC-C1").await?.unwrap().tier.as_deref(),
+        Some("pro")
+    );
+
+    // Converting again, or converting a non-trial, changes nothing
+    license.tier = Some("enterprise".to_string());
+    assert!(!db.convert_trial(&license, now).await?);
+    assert_eq!(
+        db.get_license("LIC-C1").await?.unwrap().tier.as_deref(),
+        Some("pro")
+    );
+    assert!(
+        !db.convert_trial(&listing_license("LIC-C2", now), now)
+            .await?
+    );
+    assert!(db.get_license("LIC-C2").await?.is_none());
+
+    Ok(())
+}
+
+// =============================================================================
+// Usage Meter Tests
+// =============================================================================
+
+fn usage_report(license_id: &str, report_id: &str, meter: &str, amount: i64) -> UsageReport {
+    UsageReport {
+        license_id: license_id.to_string(),
+        report_id: report_id.to_string(),
+        meter: meter.to_string(),
+        amount,
+        reported_at: Utc::now().naive_utc(),
+    }
+}
+
+#[tokio::test]
+async fn record_usage_counts_each_report_once() -> LicenseResult<()> {
+    let db = setup_in_memory_db().await?;
+    insert_test_license(&db, "LIC-U1", None, None).await?;
+
+    let first = db
+        .record_usage(&usage_report("LIC-U1", "r-1", "api_calls", 5))
+        .await?;
+    assert!(!first.duplicate);
+    assert_eq!(first.meter.used, 5);
+
+    let retried = db
+        .record_usage(&usage_report("LIC-U1", "r-1", "api_calls", 5))
+        .await?;
+    assert!(retried.duplicate);
+    assert_eq!(retried.meter.used, 5);
+
+    db.record_usage(&usage_report("LIC-U1", "r-2", "api_calls", 3))
+        .await?;
+    db.record_usage(&usage_report("LIC-U1", "r-3", "renders", 1))
+        .await?;
+
+    let meters = db.list_usage_meters("LIC-U1").await?;
+    let usage: Vec<(&str, i64)> = meters.iter().map(|m| (m.meter.as_str(), m.used)).collect();
+    assert_eq!(usage, [("api_calls", 8), ("renders", 1)]);
+    assert!(db.list_usage_meters("LIC-NONE").await?.is_empty());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn bandwidth_meter_is_mirrored_to_license() -> LicenseResult<()> {
+    let db = setup_in_memory_db().await?;
+    insert_test_license(&db, "LIC-BW", None, None).await?;
+
+    let mut meter = UsageMeter::empty("LIC-BW", BANDWIDTH_METER, Utc::now().naive_utc());
+    meter.used = 100;
+    meter.usage_limit = Some(1_000);
+    db.set_usage_meter(&meter).await?;
+    db.record_usage(&usage_report("LIC-BW", "r-1", BANDWIDTH_METER, 50))
+        .await?;
+
+    let license = db.get_license("LIC-BW").await?.unwrap();
+    assert_eq!(license.bandwidth_used_bytes, Some(150));
+    assert_eq!(license.bandwidth_limit_bytes, Some(1_000));
+
+    // Other meters leave the legacy fields alone
+    db.record_usage(&usage_report("LIC-BW", "r-2", "renders", 7))
+        .await?;
+    let license = db.get_license("LIC-BW").await?.unwrap();
+    assert_eq!(license.bandwidth_used_bytes, Some(150));
+
+    assert!(db.set_quota_exceeded("LIC-BW", true).await?);
+    assert_eq!(
+        db.get_license("LIC-BW").await?.unwrap().quota_exceeded,
+        Some(true)
+    );
+    assert!(!db.set_quota_exceeded("LIC-NONE", true).await?);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn close_usage_period_keeps_usage_and_resets_meters() -> LicenseResult<()> {
+    let db = setup_in_memory_db().await?;
+    insert_test_license(&db, "LIC-P", None, None).await?;
+    let issued_at = db.get_license("LIC-P").await?.unwrap().issued_at;
+
+    let mut bandwidth = UsageMeter::empty("LIC-P", BANDWIDTH_METER, issued_at);
+    bandwidth.used = 100;
+    bandwidth.usage_limit = Some(1_000);
+    db.set_usage_meter(&bandwidth).await?;
+    db.record_usage(&usage_report("LIC-P", "r-1", "renders", 4))
+        .await?;
+    db.set_usage_meter(&UsageMeter::empty("LIC-P", "exports", issued_at))
+        .await?;
+    db.set_quota_exceeded("LIC-P", true).await?;
+
+    // The period must end after it started
+    assert!(db.close_usage_period("LIC-P", issued_at).await?.is_none());
+    assert!(db
+        .close_usage_period("LIC-NONE", issued_at + Duration::days(1))
+        .await?
+        .is_none());
+
+    let first_end = issued_at + Duration::days(30);
+    let periods = db.close_usage_period("LIC-P", first_end).await?.unwrap();
+    // Meters without usage are not kept
+    let usage: Vec<(&str, i64, Option<i64>)> = periods
+        .iter()
+        .map(|p| (p.meter.as_str(), p.used, p.usage_limit))
+        .collect();
+    assert_eq!(
+        usage,
+        [(BANDWIDTH_METER, 100, Some(1_000)), ("renders", 4, None)]
+    );
+    assert!(periods
+        .iter()
+        .all(|p| p.period_start == issued_at && p.period_end == first_end));
+
+    let meters = db.list_usage_meters("LIC-P").await?;
+    assert!(meters.iter().all(|m| m.used == 0));
+    let license = db.get_license("LIC-P").await?.unwrap();
+    assert_eq!(license.quota_exceeded, Some(false));
+    assert_eq!(license.bandwidth_used_bytes, Some(0));
+    assert_eq!(license.usage_period_start, Some(first_end));
+
+    // Saving the license doesn't move the period back
+    db.insert_license(License {
+        usage_period_start: None,
+        ..license
+    })
+    .await?;
+    assert!(db.close_usage_period("LIC-P", first_end).await?.is_none());
+
+    // The next period starts where the last one ended
+    db.record_usage(&usage_report("LIC-P", "r-2", "renders", 2))
+        .await?;
+    let second_end = first_end + Duration::days(30);
+    db.close_usage_period("LIC-P", second_end).await?.unwrap();
+
+    let periods = db.list_usage_periods("LIC-P").await?;
+    let closed: Vec<(&str, i64, NaiveDateTime)> = periods
+        .iter()
+        .map(|p| (p.meter.as_str(), p.used, p.period_start))
+        .collect();
+    assert_eq!(
+        closed,
+        [
+            ("renders", 2, first_end),
+            (BANDWIDTH_METER, 100, issued_at),
+            ("renders", 4, issued_at),
+        ]
+    );
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn close_usage_period_keeps_concurrent_reports() -> LicenseResult<()> {
+    let path = std::env::temp_dir().join(format!("talos-usage-race-{}.db", uuid::Uuid::new_v4()));
+    let db = setup_db(&format!("sqlite://{}?mode=rwc", path.display()), 8).await?;
+    insert_test_license(&db, "LIC-PR", None, None).await?;
+    let issued_at = db.get_license("LIC-PR").await?.unwrap().issued_at;
+    let mut renders = UsageMeter::empty("LIC-PR", "renders", issued_at);
+    renders.usage_limit = Some(1);
+    db.set_usage_meter(&renders).await?;
+
+    let reports: Vec<_> = (0..40)
+        .map(|i| {
+            let db = Arc::clone(&db);
+            let meter = if i % 2 == 0 {
+                "renders"
+            } else {
+                BANDWIDTH_METER
+            };
+            tokio::spawn(async move {
+                db.record_usage(&usage_report("LIC-PR", &format!("r-{i}"), meter, 1))
+                    .await
+                    .map(|_| ())
+            })
+        })
+        .collect();
+    let closes: Vec<_> = (1..=5)
+        .map(|day| {
+            let db = Arc::clone(&db);
+            tokio::spawn(async move {
+                db.close_usage_period("LIC-PR", issued_at + Duration::days(day))
+                    .await
+                    .map(|_| ())
+            })
+        })
+        .collect();
+    for task in reports.into_iter().chain(closes) {
+        task.await.expect("task panicked")?;
+    }
+
+    // Every report is either in a closed period or still on the meter
+    let billed: i64 = db
+        .list_usage_periods("LIC-PR")
+        .await?
+        .iter()
+        .map(|p| p.used)
+        .sum();
+    let meters = db.list_usage_meters("LIC-PR").await?;
+    let current: i64 = meters.iter().map(|m| m.used).sum();
+    assert_eq!(billed + current, 40);
+
+    // Bandwidth carried over is kept, and only carried-over usage can
+    // leave the quota exceeded
+    let used = |meter: &str| {
+        meters
+            .iter()
+            .find(|m| m.meter == meter)
+            .map_or(0, |m| m.used)
+    };
+    let license = db.get_license("LIC-PR").await?.unwrap();
+    if license.bandwidth_used_bytes.is_some() {
+        assert_eq!(license.bandwidth_used_bytes, Some(used(BANDWIDTH_METER)));
+    }
+    if license.quota_exceeded == Some(true) {
+        assert!(used("renders") >= 1);
+    }
+
+    drop(db);
+    for suffix in ["", "-wal", "-shm"] {
+        let _ = std::fs::remove_file(format!("{}{suffix}", path.display()));
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn billed_licenses_have_a_cycle_or_a_billed_tier() -> LicenseResult<()> {
+    let db = setup_in_memory_db().await?;
+    for license_id in ["LIC-OWN", "LIC-TIER", "LIC-PLAIN"] {
+        insert_test_license(&db, license_id, None, None).await?;
+    }
+
+    let mut own = db.get_license("LIC-OWN").await?.unwrap();
+    own.billing_cycle = Some("monthly".to_string());
+    db.insert_license(own).await?;
+    let mut tiered = db.get_license("LIC-TIER").await?.unwrap();
+    tiered.tier = Some("metered".to_string());
+    db.insert_license(tiered).await?;
+
+    let billed = |licenses: Vec<License>| {
+        let mut ids: Vec<String> = licenses.into_iter().map(|l| l.license_id).collect();
+        ids.sort();
+        ids
+    };
+    assert_eq!(billed(db.list_billed_licenses(&[]).await?), ["LIC-OWN"]);
+    assert_eq!(
+        billed(db.list_billed_licenses(&["metered".to_string()]).await?),
+        ["LIC-OWN", "LIC-TIER"]
+    );
 
     Ok(())
 }
@@ -416,6 +1551,12 @@ async fn license_is_valid_checks_all_conditions() -> LicenseResult<()> {
         bandwidth_used_bytes: None,
         bandwidth_limit_bytes: None,
         quota_exceeded: None,
+        max_devices: 1,
+        max_concurrent: None,
+        offline_days: None,
+        billing_cycle: None,
+        usage_period_start: None,
+        suspended_by_org: None,
     };
     assert!(valid_license.is_valid(), "active license should be valid");
 
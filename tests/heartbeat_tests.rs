@@ -7,6 +7,7 @@ use sqlx::sqlite::SqlitePoolOptions;
 use talos::errors::{LicenseError, LicenseResult};
 use talos::server::database::{Database, License};
 use talos::server::handlers::{heartbeat_handler, AppState, HeartbeatRequest, HeartbeatResponse};
+use talos::server::store::LicenseStore;
 
 #[cfg(feature = "jwt-auth")]
 use talos::server::auth::AuthState;
@@ -52,7 +53,13 @@ async fn setup_in_memory_db() -> LicenseResult<Arc<Database>> {
             metadata        TEXT,
             bandwidth_used_bytes INTEGER DEFAULT 0,
             bandwidth_limit_bytes INTEGER,
-            quota_exceeded  INTEGER DEFAULT 0
+            quota_exceeded  INTEGER DEFAULT 0,
+            max_devices     INTEGER NOT NULL DEFAULT 1,
+            max_concurrent  INTEGER,
+            offline_days    INTEGER,
+            billing_cycle   TEXT,
+            usage_period_start TEXT,
+            suspended_by_org INTEGER
         );
         "#,
     )
@@ -101,6 +108,12 @@ async fn insert_active_license(
         bandwidth_used_bytes: None,
         bandwidth_limit_bytes: None,
         quota_exceeded: None,
+        max_devices: 1,
+        max_concurrent: None,
+        offline_days: None,
+        billing_cycle: None,
+        usage_period_start: None,
+        suspended_by_org: None,
     };
 
     db.insert_license(license).await?;
@@ -114,6 +127,7 @@ async fn valid_heartbeat_updates_license() -> LicenseResult<()> {
         db: db.clone(),
         #[cfg(feature = "jwt-auth")]
         auth: AuthState::disabled(),
+        signer: None,
     };
 
     let license_id = "HB-LICENSE-1";
@@ -156,6 +170,7 @@ async fn invalid_heartbeat_fails() -> LicenseResult<()> {
         db,
         #[cfg(feature = "jwt-auth")]
         auth: AuthState::disabled(),
+        signer: None,
     };
 
     // No license inserted at all
@@ -7,11 +7,11 @@ use tokio::net::TcpListener;
 
 use talos::client::License;
 use talos::hardware::get_hardware_id;
-#[cfg(feature = "admin-api")]
-use talos::server::client_api::validate_feature_handler;
 use talos::server::client_api::{
     bind_handler, client_heartbeat_handler, release_handler, validate_handler,
 };
+#[cfg(feature = "admin-api")]
+use talos::server::client_api::{checkin_handler, checkout_handler, validate_feature_handler};
 use talos::server::database::Database;
 use talos::server::handlers::{
     activate_license_handler, deactivate_license_handler, heartbeat_handler,
@@ -19,7 +19,7 @@ use talos::server::handlers::{
 };
 
 #[cfg(feature = "admin-api")]
-use talos::server::create_license_handler;
+use talos::server::{create_license_handler, revoke_license_handler};
 
 #[cfg(feature = "jwt-auth")]
 use talos::server::auth::AuthState;
@@ -65,7 +65,13 @@ async fn setup_in_memory_db() -> Arc<Database> {
             metadata        TEXT,
             bandwidth_used_bytes INTEGER DEFAULT 0,
             bandwidth_limit_bytes INTEGER,
-            quota_exceeded  INTEGER DEFAULT 0
+            quota_exceeded  INTEGER DEFAULT 0,
+            max_devices     INTEGER NOT NULL DEFAULT 1,
+            max_concurrent  INTEGER,
+            offline_days    INTEGER,
+            billing_cycle   TEXT,
+            usage_period_start TEXT,
+            suspended_by_org INTEGER
         );
         "#,
     )
@@ -73,6 +79,60 @@ async fn setup_in_memory_db() -> Arc<Database> {
     .await
     .expect("schema create failed");
 
+    sqlx::query(
+        r#"
+        CREATE TABLE license_devices (
+            license_id      TEXT NOT NULL,
+            hardware_id     TEXT NOT NULL,
+            device_name     TEXT,
+            device_info     TEXT,
+            bound_at        TEXT NOT NULL,
+            last_seen_at    TEXT,
+            PRIMARY KEY (license_id, hardware_id)
+        );
+        "#,
+    )
+    .execute(&pool)
+    .await
+    .expect("devices table create failed");
+
+    sqlx::query(
+        r#"
+        CREATE TABLE license_leases (
+            license_id      TEXT NOT NULL,
+            hardware_id     TEXT NOT NULL,
+            checked_out_at  TEXT NOT NULL,
+            expires_at      TEXT NOT NULL,
+            PRIMARY KEY (license_id, hardware_id)
+        );
+        "#,
+    )
+    .execute(&pool)
+    .await
+    .expect("leases table create failed");
+
+    sqlx::query(
+        r#"
+        CREATE TABLE organizations (
+            id                TEXT PRIMARY KEY,
+            name              TEXT NOT NULL,
+            contact_name      TEXT,
+            contact_email     TEXT,
+            metadata          TEXT,
+            default_tier      TEXT,
+            default_features  TEXT,
+            status            TEXT NOT NULL DEFAULT 'active',
+            suspended_at      TEXT,
+            suspension_reason TEXT,
+            created_at        TEXT NOT NULL,
+            updated_at        TEXT NOT NULL
+        );
+        "#,
+    )
+    .execute(&pool)
+    .await
+    .expect("organizations table create failed");
+
     Arc::new(Database::SQLite(pool))
 }
 
@@ -83,6 +143,7 @@ async fn spawn_test_server() -> String {
         db,
         #[cfg(feature = "jwt-auth")]
         auth: AuthState::disabled(),
+        signer: None,
     };
 
     let router: Router = Router::new()
@@ -122,11 +183,16 @@ async fn spawn_full_test_server() -> String {
         db,
         #[cfg(feature = "jwt-auth")]
         auth: AuthState::disabled(),
+        signer: None,
     };
 
     let router: Router = Router::new()
         // Admin API endpoints
         .route("/api/v1/licenses", post(create_license_handler))
+        .route(
+            "/api/v1/licenses/:license_id/revoke",
+            post(revoke_license_handler),
+        )
         // Client v1 API endpoints
         .route("/api/v1/client/bind", post(bind_handler))
         .route("/api/v1/client/release", post(release_handler))
@@ -136,6 +202,8 @@ async fn spawn_full_test_server() -> String {
             "/api/v1/client/validate-feature",
             post(validate_feature_handler),
         )
+        .route("/api/v1/client/checkout", post(checkout_handler))
+        .route("/api/v1/client/checkin", post(checkin_handler))
         // Legacy endpoints (for backwards compatibility)
         .route("/activate", post(activate_license_handler))
         .route("/validate", post(validate_license_handler))
@@ -334,3 +402,329 @@ async fn integration_test_v1_api_lifecycle() {
         "Validate should fail after release"
     );
 }
+
+/// Test the floating lease flow: create (admin) -> keeper checkout -> heartbeat -> checkin
+#[cfg(feature = "admin-api")]
+#[tokio::test]
+async fn integration_test_floating_lease_keeper() {
+    use serde_json::json;
+    use talos::client::{LeaseKeeper, LeaseStatus};
+
+    let server_url = spawn_full_test_server().await;
+    let client = reqwest::Client::new();
+
+    let create_body: serde_json::Value = client
+        .post(format!("{}/api/v1/licenses", server_url))
+        .json(&json!({ "org_id": "float-org", "max_concurrent": 1 }))
+        .send()
+        .await
+        .expect("create request failed")
+        .json()
+        .await
+        .expect("parse json failed");
+    let license_key = create_body["license_key"]
+        .as_str()
+        .expect("license_key missing");
+
+    let license = License::new(license_key.to_string(), server_url.clone());
+    let keeper = LeaseKeeper::start(license, Some("Render Node"), None)
+        .await
+        .expect("checkout should succeed");
+    assert!(keeper.is_held());
+    assert!(matches!(keeper.status(), LeaseStatus::Held { .. }));
+
+    // The lease is renewed by heartbeats while held
+    let mut license = keeper.checkin().await.expect("checkin should succeed");
+    let heartbeat = license.heartbeat().await.expect("heartbeat should succeed");
+    assert!(
+        heartbeat.lease_expires_at.is_none(),
+        "lease should be back in the pool after checkin"
+    );
+
+    // A fresh checkout gets the lease again
+    let lease = license
+        .checkout(Some("Render Node"), None)
+        .await
+        .expect("checkout should succeed");
+    assert!(lease.lease_ttl_secs > 0);
+    let heartbeat = license.heartbeat().await.expect("heartbeat should succeed");
+    assert!(heartbeat.lease_expires_at.is_some());
+
+    license.checkin().await.expect("checkin should succeed");
+}
+
+/// Test offline allowance: validate -> server unreachable -> fallback runs on the cache
+#[cfg(feature = "admin-api")]
+#[tokio::test]
+async fn integration_test_offline_fallback_for_active_license() {
+    use serde_json::json;
+
+    let server_url = spawn_full_test_server().await;
+    let client = reqwest::Client::new();
+
+    let create_body: serde_json::Value = client
+        .post(format!("{}/api/v1/licenses", server_url))
+        .json(&json!({ "org_id": "offline-org", "features": ["feature_a"] }))
+        .send()
+        .await
+        .expect("create request failed")
+        .json()
+        .await
+        .expect("parse json failed");
+    let license_key = create_body["license_key"]
+        .as_str()
+        .expect("license_key missing");
+
+    let mut license = License::new(license_key.to_string(), server_url.clone());
+    license
+        .bind(Some("Laptop"), None)
+        .await
+        .expect("bind should succeed");
+
+    // A healthy license has no grace period but is granted an offline allowance
+    let validation = license.validate().await.expect("validate should succeed");
+    assert!(validation.grace_period_ends_at.is_none());
+    assert!(validation.offline_valid_until.is_some());
+
+    // Point the client at a port nobody listens on
+    let unused = TcpListener::bind(SocketAddr::from(([127, 0, 0, 1], 0)))
+        .await
+        .expect("failed to bind");
+    license.server_url = format!("http://{}", unused.local_addr().unwrap());
+    drop(unused);
+
+    let offline = license
+        .validate_with_fallback()
+        .await
+        .expect("fallback should use the offline allowance");
+    assert!(offline.has_feature("feature_a"));
+    assert!(offline.offline_valid_until.is_some());
+    assert!(offline.has_warning());
+}
+
+/// Test the license guard: bind -> guard start -> revoke (admin) -> state goes Invalid
+#[cfg(feature = "admin-api")]
+#[tokio::test]
+async fn integration_test_license_guard() {
+    use std::time::Duration;
+
+    use serde_json::json;
+    use talos::client::{ClientErrorCode, GuardOptions, LicenseGuard, LicenseState};
+
+    let server_url = spawn_full_test_server().await;
+    let client = reqwest::Client::new();
+
+    let create_body: serde_json::Value = client
+        .post(format!("{}/api/v1/licenses", server_url))
+        .json(&json!({ "org_id": "guard-org" }))
+        .send()
+        .await
+        .expect("create request failed")
+        .json()
+        .await
+        .expect("parse json failed");
+    let license_key = create_body["license_key"]
+        .as_str()
+        .expect("license_key missing");
+    let license_id = create_body["license_id"]
+        .as_str()
+        .expect("license_id missing");
+
+    let mut license = License::new(license_key.to_string(), server_url.clone());
+    license
+        .bind(Some("Guarded Workstation"), None)
+        .await
+        .expect("bind should succeed");
+
+    // The server advertises its heartbeat interval
+    let validation = license.validate().await.expect("validate should succeed");
+    assert_eq!(validation.heartbeat_interval_secs, Some(60));
+
+    let options = GuardOptions {
+        heartbeat_interval: Some(Duration::from_millis(50)),
+        revalidate_interval: Duration::from_millis(150),
+        ..GuardOptions::default()
+    };
+    let guard = LicenseGuard::start(license, options)
+        .await
+        .expect("guard should start");
+    assert!(guard.is_valid());
+    assert_eq!(guard.state(), LicenseState::Valid);
+
+    // Revoking the license is picked up on the next revalidation
+    let mut state = guard.subscribe();
+    let revoke = client
+        .post(format!(
+            "{}/api/v1/licenses/{}/revoke",
+            server_url, license_id
+        ))
+        .json(&json!({ "reason": "chargeback" }))
+        .send()
+        .await
+        .expect("revoke request failed");
+    assert!(revoke.status().is_success());
+
+    tokio::time::timeout(Duration::from_secs(5), state.changed())
+        .await
+        .expect("state should change after revocation")
+        .expect("guard task should be running");
+    assert!(matches!(
+        guard.state(),
+        LicenseState::Invalid {
+            code: ClientErrorCode::LicenseRevoked,
+            ..
+        }
+    ));
+    assert!(!guard.is_valid());
+
+    let license = guard.stop().await.expect("stop should succeed");
+    assert!(license.is_bound());
+}
+
+/// Test the license guard: a server answering 500 INTERNAL_ERROR puts the
+/// license in grace instead of invalidating it
+#[tokio::test]
+async fn integration_test_license_guard_survives_server_errors() {
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::time::Duration;
+
+    use axum::http::StatusCode;
+    use axum::Json;
+    use serde_json::json;
+    use talos::client::{GuardOptions, LicenseGuard, LicenseState};
+
+    let failing = Arc::new(AtomicBool::new(false));
+    let respond = {
+        let failing = Arc::clone(&failing);
+        move || {
+            let failing = Arc::clone(&failing);
+            async move {
+                if failing.load(Ordering::SeqCst) {
+                    return Err((
+                        StatusCode::INTERNAL_SERVER_ERROR,
+                        Json(json!({
+                            "error": { "code": "INTERNAL_ERROR", "message": "database error" }
+                        })),
+                    ));
+                }
+                let offline_until = chrono::Utc::now() + chrono::Duration::days(7);
+                Ok(Json(json!({
+                    "valid": true,
+                    "features": ["feature_a"],
+                    "offline_valid_until": offline_until.to_rfc3339(),
+                })))
+            }
+        }
+    };
+    let router: Router = Router::new()
+        .route("/api/v1/client/validate", post(respond.clone()))
+        .route("/api/v1/client/heartbeat", post(respond));
+
+    let listener = TcpListener::bind(SocketAddr::from(([127, 0, 0, 1], 0)))
+        .await
+        .expect("failed to bind");
+    let server_url = format!("http://{}", listener.local_addr().unwrap());
+    tokio::spawn(async move {
+        axum::serve(listener, router.into_make_service())
+            .await
+            .expect("server failed");
+    });
+
+    let mut license = License::new("LIC-GUARD-500".to_string(), server_url);
+    license.hardware_id = get_hardware_id();
+
+    let options = GuardOptions {
+        heartbeat_interval: Some(Duration::from_millis(50)),
+        revalidate_interval: Duration::from_millis(150),
+        ..GuardOptions::default()
+    };
+    let guard = LicenseGuard::start(license, options.clone())
+        .await
+        .expect("guard should start");
+    assert_eq!(guard.state(), LicenseState::Valid);
+
+    // The server starts failing: the cache keeps the license usable
+    let mut state = guard.subscribe();
+    failing.store(true, Ordering::SeqCst);
+    tokio::time::timeout(Duration::from_secs(5), state.changed())
+        .await
+        .expect("state should change when the server fails")
+        .expect("guard task should be running");
+    assert!(matches!(guard.state(), LicenseState::Grace { .. }));
+    assert!(guard.is_valid());
+
+    // Starting while the server fails also falls back to the cache
+    let license = guard.stop().await.expect("stop should succeed");
+    let guard = LicenseGuard::start(license, options)
+        .await
+        .expect("guard should start on the cache");
+    assert!(matches!(guard.state(), LicenseState::Grace { .. }));
+
+    // Once the server recovers the license is valid again
+    let mut state = guard.subscribe();
+    failing.store(false, Ordering::SeqCst);
+    tokio::time::timeout(Duration::from_secs(5), state.changed())
+        .await
+        .expect("state should change when the server recovers")
+        .expect("guard task should be running");
+    assert_eq!(guard.state(), LicenseState::Valid);
+}
+
+/// Test client retries: a server failing with 503 twice is retried until it answers
+#[tokio::test]
+async fn integration_test_client_retries_server_errors() {
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use std::time::Duration;
+
+    use axum::http::StatusCode;
+    use axum::Json;
+    use serde_json::json;
+    use talos::client::{ClientOptions, RetryPolicy};
+
+    let attempts = Arc::new(AtomicU32::new(0));
+    let counter = Arc::clone(&attempts);
+    let router: Router = Router::new().route(
+        "/api/v1/client/validate",
+        post(move || {
+            let counter = Arc::clone(&counter);
+            async move {
+                if counter.fetch_add(1, Ordering::SeqCst) < 2 {
+                    return Err(StatusCode::SERVICE_UNAVAILABLE);
+                }
+                Ok(Json(json!({ "valid": true, "features": ["feature_a"] })))
+            }
+        }),
+    );
+
+    let listener = TcpListener::bind(SocketAddr::from(([127, 0, 0, 1], 0)))
+        .await
+        .expect("failed to bind");
+    let server_url = format!("http://{}", listener.local_addr().unwrap());
+    tokio::spawn(async move {
+        axum::serve(listener, router.into_make_service())
+            .await
+            .expect("server failed");
+    });
+
+    let retry = RetryPolicy {
+        max_retries: 2,
+        initial_backoff: Duration::from_millis(10),
+        max_backoff: Duration::from_millis(50),
+    };
+    let mut license = License::new("LIC-RETRY".to_string(), server_url)
+        .with_options(&ClientOptions::new().with_retry(retry))
+        .expect("options should build");
+    license.hardware_id = get_hardware_id();
+
+    let validation = license.validate().await.expect("validate should succeed");
+    assert!(validation.has_feature("feature_a"));
+    assert_eq!(attempts.load(Ordering::SeqCst), 3);
+
+    // Without retries the first 503 is returned as is
+    attempts.store(0, Ordering::SeqCst);
+    license
+        .set_options(&ClientOptions::new())
+        .expect("options should build");
+    assert!(license.validate().await.is_err());
+    assert_eq!(attempts.load(Ordering::SeqCst), 1);
+}
@@ -7,9 +7,12 @@
 use chrono::{Duration, Utc};
 use std::sync::Arc;
 use talos::jobs::{
-    run_grace_period_check, run_license_expiration_check, run_stale_device_cleanup, JobConfig,
+    run_grace_period_check, run_license_expiration_check, run_stale_device_cleanup,
+    run_usage_reset, JobConfig, JobScheduler,
 };
 use talos::server::database::Database;
+use talos::server::store::{LicenseStore, UsageStore};
+use talos::server::UsageMeter;
 
 /// Helper to create a test database.
 async fn setup_test_db() -> Arc<Database> {
@@ -53,7 +56,13 @@ async fn setup_test_db() -> Arc<Database> {
                     metadata TEXT,
                     bandwidth_used_bytes INTEGER DEFAULT 0,
                     bandwidth_limit_bytes INTEGER,
-                    quota_exceeded INTEGER DEFAULT 0
+                    quota_exceeded INTEGER DEFAULT 0,
+                    max_devices INTEGER NOT NULL DEFAULT 1,
+                    max_concurrent INTEGER,
+                    offline_days INTEGER,
+                    billing_cycle TEXT,
+                    usage_period_start TEXT,
+                    suspended_by_org INTEGER
                 )
                 "#,
             )
@@ -61,6 +70,38 @@ async fn setup_test_db() -> Arc<Database> {
             .await
             .expect("failed to create licenses table");
 
+            sqlx::query(
+                r#"
+                CREATE TABLE IF NOT EXISTS license_devices (
+                    license_id TEXT NOT NULL,
+                    hardware_id TEXT NOT NULL,
+                    device_name TEXT,
+                    device_info TEXT,
+                    bound_at TEXT NOT NULL,
+                    last_seen_at TEXT,
+                    PRIMARY KEY (license_id, hardware_id)
+                )
+                "#,
+            )
+            .execute(pool)
+            .await
+            .expect("failed to create license_devices table");
+
+            sqlx::query(
+                r#"
+                CREATE TABLE IF NOT EXISTS license_leases (
+                    license_id TEXT NOT NULL,
+                    hardware_id TEXT NOT NULL,
+                    checked_out_at TEXT NOT NULL,
+                    expires_at TEXT NOT NULL,
+                    PRIMARY KEY (license_id, hardware_id)
+                )
+                "#,
+            )
+            .execute(pool)
+            .await
+            .expect("failed to create license_leases table");
+
             sqlx::query(
                 r#"
                 CREATE TABLE IF NOT EXISTS license_binding_history (
@@ -72,7 +113,7 @@ async fn setup_test_db() -> Arc<Database> {
                     device_info TEXT,
                     performed_by TEXT NOT NULL,
                     reason TEXT,
-                    timestamp TEXT NOT NULL,
+                    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
                     FOREIGN KEY (license_id) REFERENCES licenses(license_id)
                 )
                 "#,
@@ -80,6 +121,39 @@ async fn setup_test_db() -> Arc<Database> {
             .execute(pool)
             .await
             .expect("failed to create license_binding_history table");
+
+            sqlx::query(
+                r#"
+                CREATE TABLE IF NOT EXISTS usage_meters (
+                    license_id TEXT NOT NULL,
+                    meter TEXT NOT NULL,
+                    used INTEGER NOT NULL DEFAULT 0,
+                    usage_limit INTEGER,
+                    updated_at TEXT NOT NULL,
+                    PRIMARY KEY (license_id, meter)
+                )
+                "#,
+            )
+            .execute(pool)
+            .await
+            .expect("failed to create usage_meters table");
+
+            sqlx::query(
+                r#"
+                CREATE TABLE IF NOT EXISTS usage_periods (
+                    license_id TEXT NOT NULL,
+                    meter TEXT NOT NULL,
+                    period_start TEXT NOT NULL,
+                    period_end TEXT NOT NULL,
+                    used INTEGER NOT NULL,
+                    usage_limit INTEGER,
+                    PRIMARY KEY (license_id, meter, period_start)
+                )
+                "#,
+            )
+            .execute(pool)
+            .await
+            .expect("failed to create usage_periods table");
         }
         #[cfg(feature = "postgres")]
         Database::Postgres(_) => {
@@ -138,11 +212,50 @@ async fn create_test_license(
         bandwidth_used_bytes: None,
         bandwidth_limit_bytes: None,
         quota_exceeded: None,
+        max_devices: 1,
+        max_concurrent: None,
+        offline_days: None,
+        billing_cycle: None,
+        usage_period_start: None,
+        suspended_by_org: None,
     };
 
     db.insert_license(license)
         .await
         .expect("failed to insert license");
+
+    if let Some(hardware_id) = hardware_id {
+        add_test_device(db, license_id, hardware_id, last_seen_at).await;
+    }
+}
+
+/// Helper to give a device a seat with a specific last_seen_at.
+async fn add_test_device(
+    db: &Database,
+    license_id: &str,
+    hardware_id: &str,
+    last_seen_at: Option<chrono::NaiveDateTime>,
+) {
+    match db {
+        #[cfg(feature = "sqlite")]
+        Database::SQLite(pool) => {
+            sqlx::query(
+                "INSERT INTO license_devices (license_id, hardware_id, device_name, bound_at, last_seen_at) \
+                 VALUES (?, ?, 'Test Device', ?, ?)",
+            )
+            .bind(license_id)
+            .bind(hardware_id)
+            .bind(Utc::now().naive_utc())
+            .bind(last_seen_at)
+            .execute(pool)
+            .await
+            .expect("failed to insert device");
+        }
+        #[cfg(feature = "postgres")]
+        Database::Postgres(_) => {
+            panic!("PostgreSQL not supported in tests");
+        }
+    }
 }
 
 // ============================================================================
@@ -158,7 +271,7 @@ async fn grace_period_check_revokes_expired_suspended_licenses() {
 
     // Create a suspended license with expired grace period
     create_test_license(
-        &*db,
+        &db,
         "grace-expired-1",
         "suspended",
         None,
@@ -170,7 +283,7 @@ async fn grace_period_check_revokes_expired_suspended_licenses() {
 
     // Create a suspended license with future grace period
     create_test_license(
-        &*db,
+        &db,
         "grace-future-1",
         "suspended",
         None,
@@ -181,7 +294,7 @@ async fn grace_period_check_revokes_expired_suspended_licenses() {
     .await;
 
     // Create an active license (should not be affected)
-    create_test_license(&*db, "active-1", "active", None, None, None, None).await;
+    create_test_license(&db, "active-1", "active", None, None, None, None).await;
 
     // Run the grace period check
     let count = run_grace_period_check(&*db).await.expect("job failed");
@@ -211,7 +324,7 @@ async fn grace_period_check_handles_no_expired_licenses() {
 
     // Create a suspended license with future grace period
     create_test_license(
-        &*db,
+        &db,
         "grace-future-2",
         "suspended",
         None,
@@ -241,7 +354,7 @@ async fn license_expiration_check_expires_old_licenses() {
 
     // Create an active license that has expired
     create_test_license(
-        &*db,
+        &db,
         "expired-1",
         "active",
         Some(past), // Expired yesterday
@@ -253,7 +366,7 @@ async fn license_expiration_check_expires_old_licenses() {
 
     // Create an active license with future expiration
     create_test_license(
-        &*db,
+        &db,
         "future-1",
         "active",
         Some(now + Duration::days(30)), // Expires in 30 days
@@ -264,7 +377,7 @@ async fn license_expiration_check_expires_old_licenses() {
     .await;
 
     // Create an active license with no expiration
-    create_test_license(&*db, "no-expiry-1", "active", None, None, None, None).await;
+    create_test_license(&db, "no-expiry-1", "active", None, None, None, None).await;
 
     // Run the expiration check
     let count = run_license_expiration_check(&*db)
@@ -295,7 +408,7 @@ async fn license_expiration_check_ignores_already_expired() {
 
     // Create an already expired license (status = 'expired')
     create_test_license(
-        &*db,
+        &db,
         "already-expired-1",
         "expired", // Already has expired status
         Some(past),
@@ -328,7 +441,7 @@ async fn stale_device_cleanup_releases_old_devices() {
 
     // Create a license with stale device (last seen 100 days ago)
     create_test_license(
-        &*db,
+        &db,
         "stale-device-1",
         "active",
         None,
@@ -340,7 +453,7 @@ async fn stale_device_cleanup_releases_old_devices() {
 
     // Create a license with recent device (last seen 10 days ago)
     create_test_license(
-        &*db,
+        &db,
         "recent-device-1",
         "active",
         None,
@@ -351,7 +464,7 @@ async fn stale_device_cleanup_releases_old_devices() {
     .await;
 
     // Create a license with no device
-    create_test_license(&*db, "no-device-1", "active", None, None, None, None).await;
+    create_test_license(&db, "no-device-1", "active", None, None, None, None).await;
 
     // Run stale device cleanup with 90 day threshold
     let count = run_stale_device_cleanup(&*db, 90)
@@ -371,6 +484,44 @@ async fn stale_device_cleanup_releases_old_devices() {
     assert!(license.hardware_id.is_some());
 }
 
+#[tokio::test]
+async fn stale_device_cleanup_frees_only_stale_seats() {
+    let db = setup_test_db().await;
+
+    let now = Utc::now().naive_utc();
+
+    // A multi-seat license with one stale and one recent device
+    create_test_license(
+        &db,
+        "multi-seat-1",
+        "active",
+        None,
+        None,
+        Some("hw-seat-recent"),
+        Some(now - Duration::days(5)),
+    )
+    .await;
+    add_test_device(
+        &db,
+        "multi-seat-1",
+        "hw-seat-stale",
+        Some(now - Duration::days(120)),
+    )
+    .await;
+
+    let count = run_stale_device_cleanup(&*db, 90)
+        .await
+        .expect("job failed");
+    assert_eq!(count, 1);
+
+    let devices = db.list_license_devices("multi-seat-1").await.unwrap();
+    assert_eq!(devices.len(), 1);
+    assert_eq!(devices[0].hardware_id, "hw-seat-recent");
+
+    let license = db.get_license("multi-seat-1").await.unwrap().unwrap();
+    assert_eq!(license.hardware_id.as_deref(), Some("hw-seat-recent"));
+}
+
 #[tokio::test]
 async fn stale_device_cleanup_handles_no_stale_devices() {
     let db = setup_test_db().await;
@@ -380,7 +531,7 @@ async fn stale_device_cleanup_handles_no_stale_devices() {
 
     // Create a license with recent device
     create_test_license(
-        &*db,
+        &db,
         "recent-device-2",
         "active",
         None,
@@ -399,6 +550,89 @@ async fn stale_device_cleanup_handles_no_stale_devices() {
     assert_eq!(count, 0);
 }
 
+// ============================================================================
+// Usage Reset Tests
+// ============================================================================
+
+/// Helper to backdate a license and give it a billing cycle and some usage.
+async fn create_billed_license(
+    db: &Database,
+    license_id: &str,
+    billing_cycle: Option<&str>,
+    issued_days_ago: i64,
+    used: i64,
+) -> chrono::NaiveDateTime {
+    create_test_license(db, license_id, "active", None, None, None, None).await;
+
+    let mut license = db.get_license(license_id).await.unwrap().unwrap();
+    license.issued_at = Utc::now().naive_utc() - Duration::days(issued_days_ago);
+    license.billing_cycle = billing_cycle.map(str::to_string);
+    db.insert_license(license.clone()).await.unwrap();
+
+    db.set_usage_meter(&UsageMeter {
+        used,
+        ..UsageMeter::empty(license_id, "api_calls", Utc::now().naive_utc())
+    })
+    .await
+    .unwrap();
+    db.set_quota_exceeded(license_id, true).await.unwrap();
+
+    license.issued_at
+}
+
+#[tokio::test]
+async fn usage_reset_closes_finished_billing_periods() {
+    let db = setup_test_db().await;
+
+    let issued_at = create_billed_license(&db, "billed-1", Some("monthly"), 45, 42).await;
+
+    let count = run_usage_reset(&*db).await.expect("usage reset failed");
+    assert_eq!(count, 1);
+
+    // The usage up to the boundary is kept
+    let periods = db.list_usage_periods("billed-1").await.unwrap();
+    assert_eq!(periods.len(), 1);
+    assert_eq!(periods[0].meter, "api_calls");
+    assert_eq!(periods[0].used, 42);
+    assert_eq!(periods[0].period_start, issued_at);
+    assert_eq!(
+        periods[0].period_end,
+        issued_at
+            .checked_add_months(chrono::Months::new(1))
+            .unwrap()
+    );
+
+    // The meters start over and the license is back under quota
+    let meters = db.list_usage_meters("billed-1").await.unwrap();
+    assert_eq!(meters[0].used, 0);
+    let license = db.get_license("billed-1").await.unwrap().unwrap();
+    assert_eq!(license.quota_exceeded, Some(false));
+    assert_eq!(license.usage_period_start, Some(periods[0].period_end));
+
+    // The next period is still running
+    assert_eq!(run_usage_reset(&*db).await.unwrap(), 0);
+    assert_eq!(db.list_usage_periods("billed-1").await.unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn usage_reset_ignores_licenses_without_finished_periods() {
+    let db = setup_test_db().await;
+
+    create_billed_license(&db, "unbilled", None, 45, 10).await;
+    create_billed_license(&db, "fresh", Some("monthly"), 5, 10).await;
+    create_billed_license(&db, "opted-out", Some("none"), 45, 10).await;
+    create_billed_license(&db, "broken", Some("not a cycle"), 45, 10).await;
+
+    let count = run_usage_reset(&*db).await.expect("usage reset failed");
+    assert_eq!(count, 0);
+
+    for license_id in ["unbilled", "fresh", "opted-out", "broken"] {
+        assert!(db.list_usage_periods(license_id).await.unwrap().is_empty());
+        let meters = db.list_usage_meters(license_id).await.unwrap();
+        assert_eq!(meters[0].used, 10, "{license_id} was reset");
+    }
+}
+
 // ============================================================================
 // JobConfig Tests
 // ============================================================================
@@ -412,4 +646,57 @@ fn job_config_has_sensible_defaults() {
     assert!(!config.grace_period_cron.is_empty());
     assert!(!config.license_expiration_cron.is_empty());
     assert!(!config.stale_device_cron.is_empty());
+    assert!(config.usage_reset_enabled);
+    assert!(!config.usage_reset_cron.is_empty());
+}
+
+#[test]
+fn job_config_is_enabled_by_default() {
+    assert!(JobConfig::default().enabled);
+}
+
+// ============================================================================
+// JobScheduler Tests
+// ============================================================================
+
+#[tokio::test]
+async fn scheduler_starts_and_shuts_down_with_shared_db() {
+    let db = setup_test_db().await;
+
+    let config = JobConfig {
+        stale_device_cleanup_enabled: true,
+        ..JobConfig::default()
+    };
+    let mut scheduler = JobScheduler::new(db.clone(), config)
+        .await
+        .expect("failed to create scheduler");
+
+    scheduler.start().await.expect("failed to start scheduler");
+
+    // Jobs share the server's database
+    create_test_license(&db, "sched-1", "active", None, None, None, None).await;
+    assert_eq!(
+        scheduler.run_license_expiration_check_now().await.unwrap(),
+        0
+    );
+
+    scheduler
+        .shutdown()
+        .await
+        .expect("failed to shut down scheduler");
+}
+
+#[tokio::test]
+async fn scheduler_rejects_invalid_cron() {
+    let db = setup_test_db().await;
+
+    let config = JobConfig {
+        grace_period_cron: "not a cron".to_string(),
+        ..JobConfig::default()
+    };
+    let scheduler = JobScheduler::new(db, config)
+        .await
+        .expect("failed to create scheduler");
+
+    assert!(scheduler.start().await.is_err());
 }
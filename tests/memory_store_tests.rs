@@ -0,0 +1,716 @@
+//! Tests for the in-memory license store.
+
+use std::sync::Arc;
+
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+};
+use chrono::{Duration, NaiveDateTime, Utc};
+use serde_json::{json, Value};
+use tower::ServiceExt;
+
+use talos::errors::LicenseResult;
+use talos::server::audit::{AuditEntry, AuditFilter};
+use talos::server::database::{
+    BindingAction, BindingHistoryFilter, LeaseCheckout, License, PerformedBy, SeatClaim,
+};
+use talos::server::handlers::AppState;
+use talos::server::orgs::Organization;
+use talos::server::routes::build_router;
+use talos::server::store::{
+    AuditStore, LicenseStore, OrgStore, TokenStore, TrialStore, UsageStore, WebhookStore,
+};
+use talos::server::trials::TrialRegistration;
+use talos::server::usage::{UsageMeter, UsageReport};
+use talos::server::webhooks::{Webhook, WebhookDelivery};
+use talos::server::MemoryStore;
+use talos::tiers::BANDWIDTH_METER;
+
+#[cfg(feature = "jwt-auth")]
+use talos::server::auth::AuthState;
+
+fn license(license_id: &str, license_key: Option<&str>) -> License {
+    License {
+        license_id: license_id.to_string(),
+        client_id: None,
+        status: "active".to_string(),
+        features: Some("export,api".to_string()),
+        issued_at: Utc::now().naive_utc(),
+        expires_at: None,
+        hardware_id: None,
+        signature: None,
+        last_heartbeat: None,
+        org_id: None,
+        org_name: None,
+        license_key: license_key.map(String::from),
+        tier: None,
+        device_name: None,
+        device_info: None,
+        bound_at: None,
+        last_seen_at: None,
+        suspended_at: None,
+        revoked_at: None,
+        revoke_reason: None,
+        grace_period_ends_at: None,
+        suspension_message: None,
+        is_blacklisted: None,
+        blacklisted_at: None,
+        blacklist_reason: None,
+        metadata: None,
+        bandwidth_used_bytes: None,
+        bandwidth_limit_bytes: None,
+        quota_exceeded: None,
+        max_devices: 1,
+        max_concurrent: None,
+        offline_days: None,
+        billing_cycle: None,
+        usage_period_start: None,
+        suspended_by_org: None,
+    }
+}
+
+fn audit_entry(id: &str, actor: &str, occurred_at: NaiveDateTime) -> AuditEntry {
+    AuditEntry {
+        id: id.to_string(),
+        occurred_at,
+        actor: actor.to_string(),
+        actor_token_id: None,
+        action: "license.created".to_string(),
+        target_type: "license".to_string(),
+        target_id: "LIC-1".to_string(),
+        before_state: None,
+        after_state: None,
+        ip_address: None,
+        request_id: None,
+    }
+}
+
+fn delivery(id: &str, webhook_id: &str, next_attempt_at: NaiveDateTime) -> WebhookDelivery {
+    WebhookDelivery {
+        id: id.to_string(),
+        webhook_id: webhook_id.to_string(),
+        event_id: "evt-1".to_string(),
+        event: "license.created".to_string(),
+        payload: "{}".to_string(),
+        status: "pending".to_string(),
+        attempts: 0,
+        next_attempt_at: Some(next_attempt_at),
+        last_attempt_at: None,
+        response_status: None,
+        last_error: None,
+        created_at: next_attempt_at,
+        delivered_at: None,
+    }
+}
+
+#[tokio::test]
+async fn licenses_round_trip_and_keys_stay_unique() -> LicenseResult<()> {
+    let store = MemoryStore::new();
+    store
+        .insert_license(license("LIC-1", Some("LIC-AAAA-BBBB-CCCC")))
+        .await?;
+
+    let stored = store.get_license("LIC-1").await?.expect("license");
+    assert_eq!(stored.features.as_deref(), Some("export,api"));
+    assert!(store.license_key_exists("LIC-AAAA-BBBB-CCCC").await?);
+    assert_eq!(
+        store
+            .get_license_by_key("LIC-AAAA-BBBB-CCCC")
+            .await?
+            .map(|l| l.license_id),
+        Some("LIC-1".to_string())
+    );
+
+    // Upserting the same license keeps its key; another license cannot take it
+    store
+        .insert_license(license("LIC-1", Some("LIC-AAAA-BBBB-CCCC")))
+        .await?;
+    assert!(store
+        .insert_license(license("LIC-2", Some("LIC-AAAA-BBBB-CCCC")))
+        .await
+        .is_err());
+
+    assert!(store.update_license_status("LIC-1", "suspended").await?);
+    assert!(!store.update_license_status("missing", "suspended").await?);
+    assert_eq!(
+        store.get_license("LIC-1").await?.unwrap().status,
+        "suspended"
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn seat_claims_respect_max_devices() -> LicenseResult<()> {
+    let store = MemoryStore::new();
+    let mut lic = license("LIC-1", None);
+    lic.max_devices = 2;
+    store.insert_license(lic.clone()).await?;
+
+    assert!(matches!(
+        store
+            .claim_license_seat(&lic, "HW-1", Some("Laptop"), None)
+            .await?,
+        SeatClaim::Claimed
+    ));
+    assert!(matches!(
+        store.claim_license_seat(&lic, "HW-2", None, None).await?,
+        SeatClaim::Claimed
+    ));
+    assert!(matches!(
+        store.claim_license_seat(&lic, "HW-1", None, None).await?,
+        SeatClaim::Existing(device) if device.device_name.as_deref() == Some("Laptop")
+    ));
+    match store.claim_license_seat(&lic, "HW-3", None, None).await? {
+        SeatClaim::Full(devices) => assert_eq!(devices.len(), 2),
+        other => panic!("expected a full license, got {other:?}"),
+    }
+
+    // Freeing the newest seat moves the binding back to the remaining device
+    assert!(store.remove_license_device("LIC-1", "HW-2").await?);
+    let stored = store.get_license("LIC-1").await?.unwrap();
+    assert_eq!(stored.hardware_id.as_deref(), Some("HW-1"));
+
+    assert_eq!(store.remove_all_license_devices("LIC-1").await?, 1);
+    let stored = store.get_license("LIC-1").await?.unwrap();
+    assert!(!stored.is_bound());
+    assert!(store.list_license_devices("LIC-1").await?.is_empty());
+
+    assert!(store
+        .claim_license_seat(&license("missing", None), "HW-1", None, None)
+        .await
+        .is_err());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn insert_licenses_writes_all_or_nothing() -> LicenseResult<()> {
+    let store = MemoryStore::new();
+    let a = license("LIC-A", Some("KEY-A"));
+    store.insert_license(a.clone()).await?;
+    store
+        .insert_license(license("LIC-B", Some("KEY-B")))
+        .await?;
+    store.claim_license_seat(&a, "HW-1", None, None).await?;
+
+    let mut revoked = a.clone();
+    revoked.status = "revoked".to_string();
+    let taken = license("LIC-B", Some("KEY-A"));
+    assert!(store
+        .insert_licenses(&[revoked.clone(), taken], true)
+        .await
+        .is_err());
+    assert_eq!(store.get_license("LIC-A").await?.unwrap().status, "active");
+    assert_eq!(store.list_license_devices("LIC-A").await?.len(), 1);
+
+    store.insert_licenses(&[revoked], true).await?;
+    let stored = store.get_license("LIC-A").await?.unwrap();
+    assert_eq!(stored.status, "revoked");
+    assert!(!stored.is_bound());
+    assert!(store.list_license_devices("LIC-A").await?.is_empty());
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn concurrent_claims_take_exactly_one_seat() -> LicenseResult<()> {
+    let store = Arc::new(MemoryStore::new());
+    let lic = license("LIC-1", None);
+    store.insert_license(lic.clone()).await?;
+
+    let claims = (0..16).map(|i| {
+        let store = Arc::clone(&store);
+        let lic = lic.clone();
+        tokio::spawn(async move {
+            store
+                .claim_license_seat(&lic, &format!("HW-{i}"), None, None)
+                .await
+        })
+    });
+
+    let mut claimed = 0;
+    for claim in claims {
+        if matches!(claim.await.unwrap()?, SeatClaim::Claimed) {
+            claimed += 1;
+        }
+    }
+
+    assert_eq!(claimed, 1);
+    assert_eq!(store.list_license_devices("LIC-1").await?.len(), 1);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn leases_are_pooled_and_expire() -> LicenseResult<()> {
+    let store = MemoryStore::new();
+    let mut lic = license("LIC-1", None);
+    lic.max_concurrent = Some(1);
+    store.insert_license(lic.clone()).await?;
+
+    let ttl = Duration::minutes(5);
+    let LeaseCheckout::Granted(lease) = store.checkout_license_lease(&lic, "HW-1", ttl).await?
+    else {
+        panic!("expected a lease");
+    };
+    assert!(matches!(
+        store.checkout_license_lease(&lic, "HW-2", ttl).await?,
+        LeaseCheckout::Full(1)
+    ));
+
+    // Checking out again renews the lease held by the same device
+    let LeaseCheckout::Granted(renewed) = store.checkout_license_lease(&lic, "HW-1", ttl).await?
+    else {
+        panic!("expected a renewed lease");
+    };
+    assert_eq!(renewed.checked_out_at, lease.checked_out_at);
+    assert!(store
+        .renew_license_lease("LIC-1", "HW-1", ttl)
+        .await?
+        .is_some());
+
+    assert!(store.checkin_license_lease("LIC-1", "HW-1").await?);
+    assert!(!store.checkin_license_lease("LIC-1", "HW-1").await?);
+
+    // An expired lease frees its slot and is not revived by renewal
+    store
+        .checkout_license_lease(&lic, "HW-2", Duration::zero())
+        .await?;
+    assert!(store
+        .renew_license_lease("LIC-1", "HW-2", ttl)
+        .await?
+        .is_none());
+    assert!(matches!(
+        store.checkout_license_lease(&lic, "HW-3", ttl).await?,
+        LeaseCheckout::Granted(_)
+    ));
+
+    assert_eq!(
+        store.delete_expired_leases(Utc::now().naive_utc()).await?,
+        1
+    );
+    assert_eq!(
+        store
+            .list_active_leases("LIC-1", Utc::now().naive_utc())
+            .await?
+            .len(),
+        1
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn api_tokens_validate_until_revoked() -> LicenseResult<()> {
+    let store = MemoryStore::new();
+    assert!(!store.has_api_tokens().await?);
+
+    let (token, raw) = store
+        .create_api_token("ci", &["licenses:read"], None, Some("admin"))
+        .await?;
+    assert!(raw.starts_with("talos_"));
+    assert!(store.has_api_tokens().await?);
+
+    let validated = store.validate_api_token(&raw).await?.expect("token");
+    assert_eq!(validated.id, token.id);
+    assert!(validated.has_scope("licenses:read"));
+    assert!(store.validate_api_token("talos_unknown").await?.is_none());
+    assert!(store
+        .get_api_token(&token.id)
+        .await?
+        .unwrap()
+        .last_used_at
+        .is_some());
+
+    assert!(store.revoke_api_token(&token.id).await?);
+    assert!(!store.revoke_api_token(&token.id).await?);
+    assert!(!store.validate_api_token(&raw).await?.unwrap().is_valid());
+    assert_eq!(store.list_api_tokens().await?.len(), 1);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn audit_entries_are_filtered_and_paged_newest_first() -> LicenseResult<()> {
+    let store = MemoryStore::new();
+    let now = Utc::now().naive_utc();
+    store
+        .insert_audit_entry(&audit_entry("a", "alice", now - Duration::minutes(2)))
+        .await?;
+    store
+        .insert_audit_entry(&audit_entry("b", "bob", now - Duration::minutes(1)))
+        .await?;
+    store
+        .insert_audit_entry(&audit_entry("c", "alice", now))
+        .await?;
+
+    let (entries, total) = store
+        .list_audit_entries(&AuditFilter::default(), 2, 0)
+        .await?;
+    assert_eq!(total, 3);
+    assert_eq!(
+        entries.iter().map(|e| e.id.as_str()).collect::<Vec<_>>(),
+        ["c", "b"]
+    );
+
+    let filter = AuditFilter {
+        actor: Some("alice".to_string()),
+        until: Some(now - Duration::minutes(1)),
+        ..Default::default()
+    };
+    let (entries, total) = store.list_audit_entries(&filter, 10, 0).await?;
+    assert_eq!(total, 1);
+    assert_eq!(entries[0].id, "a");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn binding_history_is_filtered_and_paged_newest_first() -> LicenseResult<()> {
+    let store = MemoryStore::new();
+    for (license_id, hardware_id, action, performed_by) in [
+        ("lic-1", "hw-1", BindingAction::Bind, PerformedBy::Client),
+        ("lic-2", "hw-1", BindingAction::Bind, PerformedBy::Admin),
+        ("lic-1", "hw-1", BindingAction::Release, PerformedBy::Client),
+        ("lic-2", "hw-2", BindingAction::Bind, PerformedBy::Client),
+    ] {
+        store
+            .record_binding_history(
+                license_id,
+                action,
+                Some(hardware_id),
+                None,
+                None,
+                performed_by,
+                None,
+            )
+            .await?;
+    }
+
+    let filter = BindingHistoryFilter {
+        hardware_id: Some("hw-1".to_string()),
+        ..Default::default()
+    };
+    let (entries, total) = store.list_binding_history(&filter, 2, 0).await?;
+    assert_eq!(total, 3);
+    assert_eq!(entries.iter().map(|e| e.id).collect::<Vec<_>>(), [3, 2]);
+
+    let filter = BindingHistoryFilter {
+        license_id: Some("lic-2".to_string()),
+        action: Some(BindingAction::Bind),
+        performed_by: Some(PerformedBy::Client),
+        ..Default::default()
+    };
+    let (entries, total) = store.list_binding_history(&filter, 10, 0).await?;
+    assert_eq!(total, 1);
+    assert_eq!(entries[0].hardware_id.as_deref(), Some("hw-2"));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn trials_are_registered_once_per_machine_and_convert_once() -> LicenseResult<()> {
+    let store = MemoryStore::new();
+    let trial = |license_id: &str| TrialRegistration {
+        hardware_id: "hw-trial".to_string(),
+        product: String::new(),
+        license_id: license_id.to_string(),
+        started_at: Utc::now().naive_utc(),
+        converted_at: None,
+    };
+
+    let first = license("trial-1", Some("KEY-T1"));
+    assert!(store.insert_trial(&trial("trial-1"), &first).await?);
+    assert!(
+        !store
+            .insert_trial(&trial("trial-2"), &license("trial-2", Some("KEY-T2")))
+            .await?
+    );
+    assert!(store.get_license("trial-2").await?.is_none());
+
+    let paid = License {
+        tier: Some("pro".to_string()),
+        ..first
+    };
+    let now = Utc::now().naive_utc();
+    assert!(store.convert_trial(&paid, now).await?);
+    assert!(!store.convert_trial(&paid, now).await?);
+    assert!(store
+        .get_trial_by_license("trial-1")
+        .await?
+        .is_some_and(|t| t.is_converted()));
+    assert_eq!(
+        store.get_license("trial-1").await?.unwrap().tier.as_deref(),
+        Some("pro")
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn usage_reports_count_once_and_mirror_bandwidth() -> LicenseResult<()> {
+    let store = MemoryStore::new();
+    store.insert_license(license("LIC-U", None)).await?;
+    let report = |report_id: &str, meter: &str, amount: i64| UsageReport {
+        license_id: "LIC-U".to_string(),
+        report_id: report_id.to_string(),
+        meter: meter.to_string(),
+        amount,
+        reported_at: Utc::now().naive_utc(),
+    };
+
+    assert!(
+        !store
+            .record_usage(&report("r-1", "renders", 2))
+            .await?
+            .duplicate
+    );
+    let retried = store.record_usage(&report("r-1", "renders", 2)).await?;
+    assert!(retried.duplicate);
+    assert_eq!(retried.meter.used, 2);
+
+    store
+        .record_usage(&report("r-2", BANDWIDTH_METER, 300))
+        .await?;
+    let mut bandwidth = UsageMeter::empty("LIC-U", BANDWIDTH_METER, Utc::now().naive_utc());
+    bandwidth.used = 400;
+    bandwidth.usage_limit = Some(500);
+    store.set_usage_meter(&bandwidth).await?;
+
+    let meters = store.list_usage_meters("LIC-U").await?;
+    let usage: Vec<(&str, i64)> = meters.iter().map(|m| (m.meter.as_str(), m.used)).collect();
+    assert_eq!(usage, [(BANDWIDTH_METER, 400), ("renders", 2)]);
+
+    let stored = store.get_license("LIC-U").await?.unwrap();
+    assert_eq!(stored.bandwidth_used_bytes, Some(400));
+    assert_eq!(stored.bandwidth_limit_bytes, Some(500));
+
+    assert!(store.set_quota_exceeded("LIC-U", true).await?);
+    assert_eq!(
+        store.get_license("LIC-U").await?.unwrap().quota_exceeded,
+        Some(true)
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn closing_a_usage_period_resets_meters_once() -> LicenseResult<()> {
+    let store = MemoryStore::new();
+    let mut billed = license("LIC-P", None);
+    billed.billing_cycle = Some("monthly".to_string());
+    let issued_at = billed.issued_at;
+    store.insert_license(billed).await?;
+    store.insert_license(license("LIC-PLAIN", None)).await?;
+
+    let mut renders = UsageMeter::empty("LIC-P", "renders", issued_at);
+    renders.used = 7;
+    store.set_usage_meter(&renders).await?;
+    store.set_quota_exceeded("LIC-P", true).await?;
+
+    let period_end = issued_at + Duration::days(30);
+    let periods = store
+        .close_usage_period("LIC-P", period_end)
+        .await?
+        .unwrap();
+    assert_eq!(periods.len(), 1);
+    assert_eq!(periods[0].used, 7);
+    assert_eq!(periods[0].period_start, issued_at);
+    assert!(store
+        .close_usage_period("LIC-P", period_end)
+        .await?
+        .is_none());
+
+    assert_eq!(store.list_usage_meters("LIC-P").await?[0].used, 0);
+    assert_eq!(store.list_usage_periods("LIC-P").await?, periods);
+
+    let billed: Vec<String> = store
+        .list_billed_licenses(&[])
+        .await?
+        .into_iter()
+        .map(|l| l.license_id)
+        .collect();
+    assert_eq!(billed, ["LIC-P"]);
+
+    // Saving the license keeps the current period
+    let stored = store.get_license("LIC-P").await?.unwrap();
+    assert_eq!(stored.quota_exceeded, Some(false));
+    assert_eq!(stored.usage_period_start, Some(period_end));
+    store.insert_license(license("LIC-P", None)).await?;
+    assert_eq!(
+        store
+            .get_license("LIC-P")
+            .await?
+            .unwrap()
+            .usage_period_start,
+        Some(period_end)
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn webhook_deliveries_are_queued_and_deleted_with_their_webhook() -> LicenseResult<()> {
+    let store = MemoryStore::new();
+    let now = Utc::now().naive_utc();
+    let webhook = Webhook {
+        id: "wh-1".to_string(),
+        url: "https://example.com/hook".to_string(),
+        secret: "whsec_test".to_string(),
+        events: r#"["*"]"#.to_string(),
+        description: None,
+        is_active: true,
+        created_at: now,
+        updated_at: now,
+    };
+    store.insert_webhook(&webhook).await?;
+
+    store
+        .insert_webhook_delivery(&delivery("d-1", "wh-1", now - Duration::seconds(1)))
+        .await?;
+    store
+        .insert_webhook_delivery(&delivery("d-2", "wh-1", now + Duration::hours(1)))
+        .await?;
+
+    let due = store.due_webhook_deliveries(now, 10).await?;
+    assert_eq!(due.len(), 1);
+    let mut sent = due[0].clone();
+    sent.status = "delivered".to_string();
+    sent.attempts = 1;
+    sent.delivered_at = Some(now);
+    store.update_webhook_delivery(&sent).await?;
+    assert!(store.due_webhook_deliveries(now, 10).await?.is_empty());
+
+    let (delivered, total) = store
+        .list_webhook_deliveries("wh-1", Some("delivered"), 10, 0)
+        .await?;
+    assert_eq!(total, 1);
+    assert_eq!(delivered[0].attempts, 1);
+
+    assert!(store.delete_webhook("wh-1").await?);
+    assert!(!store.delete_webhook("wh-1").await?);
+    assert!(store.get_webhook("wh-1").await?.is_none());
+    assert_eq!(
+        store.list_webhook_deliveries("wh-1", None, 10, 0).await?.1,
+        0
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn org_renames_reach_licenses_and_stats_add_up() -> LicenseResult<()> {
+    let store = MemoryStore::new();
+    let now = Utc::now().naive_utc();
+    let mut org = Organization {
+        id: "acme".to_string(),
+        name: "Acme".to_string(),
+        contact_name: None,
+        contact_email: None,
+        metadata: None,
+        default_tier: None,
+        default_features: None,
+        status: "active".to_string(),
+        suspended_at: None,
+        suspension_reason: None,
+        created_at: now,
+        updated_at: now,
+    };
+    store.insert_org(&org).await?;
+    assert!(store.insert_org(&org).await.is_err());
+
+    for (id, status) in [
+        ("LIC-1", "active"),
+        ("LIC-2", "active"),
+        ("LIC-3", "revoked"),
+    ] {
+        let mut l = license(id, None);
+        l.org_id = Some("acme".to_string());
+        l.org_name = Some("Acme".to_string());
+        l.status = status.to_string();
+        l.bandwidth_used_bytes = Some(100);
+        store.insert_license(l).await?;
+    }
+    let lic = store.get_license("LIC-1").await?.unwrap();
+    store.claim_license_seat(&lic, "HW-1", None, None).await?;
+
+    org.name = "Acme Corp".to_string();
+    store.update_org(&org).await?;
+    assert_eq!(
+        store
+            .get_license("LIC-2")
+            .await?
+            .unwrap()
+            .org_name
+            .as_deref(),
+        Some("Acme Corp")
+    );
+
+    let stats = store.org_stats("acme").await?;
+    assert_eq!(stats.total_licenses, 3);
+    assert_eq!(stats.licenses_by_status.get("active"), Some(&2));
+    assert_eq!(stats.licenses_by_status.get("revoked"), Some(&1));
+    assert_eq!(stats.bound_devices, 1);
+    assert_eq!(stats.bandwidth_used_bytes, 300);
+
+    assert_eq!(store.list_orgs(Some("suspended"), 10, 0).await?.1, 0);
+    assert!(store.delete_org("acme").await?);
+    assert!(store.get_org("acme").await?.is_none());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn router_serves_client_binds_from_memory_store() -> LicenseResult<()> {
+    // Rate limits are keyed by client IP, which oneshot requests don't have
+    std::env::set_var("TALOS_RATE_LIMIT_ENABLED", "false");
+    std::env::set_var("TALOS_ADMIN_IP_WHITELIST", "");
+
+    let store = Arc::new(MemoryStore::new());
+    store
+        .insert_license(license("LIC-1", Some("LIC-AAAA-BBBB-CCCC")))
+        .await?;
+
+    let state = AppState {
+        db: store.clone(),
+        #[cfg(feature = "jwt-auth")]
+        auth: AuthState::disabled(),
+        signer: None,
+    };
+    let app = build_router(state);
+
+    let request = Request::builder()
+        .method("POST")
+        .uri("/api/v1/client/bind")
+        .header("Content-Type", "application/json")
+        .body(Body::from(
+            json!({
+                "license_key": "LIC-AAAA-BBBB-CCCC",
+                "hardware_id": "a".repeat(64),
+                "device_name": "Laptop",
+            })
+            .to_string(),
+        ))
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let stored = store.get_license("LIC-1").await?.unwrap();
+    assert_eq!(stored.hardware_id, Some("a".repeat(64)));
+
+    let request = Request::builder()
+        .uri("/health")
+        .body(Body::empty())
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let body: Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(body["database"]["db_type"], "memory");
+    assert_eq!(body["database"]["connected"], true);
+
+    Ok(())
+}
@@ -0,0 +1,332 @@
+//! Tests for the embedded schema migrations.
+
+#![cfg(feature = "sqlite")]
+
+use sqlx::sqlite::SqlitePoolOptions;
+use sqlx::SqlitePool;
+
+use talos::errors::{LicenseError, LicenseResult};
+use talos::server::database::{Database, SeatClaim};
+use talos::server::store::{LicenseStore, OrgStore, UsageStore};
+
+/// The schema every deployment started from, before any other migration.
+const LEGACY_INIT_SCHEMA: &str = include_str!("../migrations/20241114103315_init.up.sql");
+
+/// The standalone setup script, which creates the full schema untracked.
+const SETUP_SCRIPT: &str = include_str!("../scripts/sql/init_sqlite.sql");
+
+async fn memory_pool() -> LicenseResult<SqlitePool> {
+    SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await
+        .map_err(|e| LicenseError::ServerError(format!("db connect failed: {e}")))
+}
+
+async fn table_exists(pool: &SqlitePool, table: &str) -> bool {
+    sqlx::query("SELECT name FROM sqlite_master WHERE type = 'table' AND name = ?")
+        .bind(table)
+        .fetch_optional(pool)
+        .await
+        .unwrap()
+        .is_some()
+}
+
+async fn index_exists(pool: &SqlitePool, index: &str) -> bool {
+    sqlx::query("SELECT name FROM sqlite_master WHERE type = 'index' AND name = ?")
+        .bind(index)
+        .fetch_optional(pool)
+        .await
+        .unwrap()
+        .is_some()
+}
+
+async fn column_exists(pool: &SqlitePool, table: &str, column: &str) -> bool {
+    sqlx::query("SELECT name FROM pragma_table_info(?) WHERE name = ?")
+        .bind(table)
+        .bind(column)
+        .fetch_optional(pool)
+        .await
+        .unwrap()
+        .is_some()
+}
+
+#[tokio::test]
+async fn fresh_database_migrates_to_current_schema() -> LicenseResult<()> {
+    let pool = memory_pool().await?;
+    let db = Database::SQLite(pool.clone());
+
+    let status = db.migration_status().await?;
+    assert!(!status.is_empty());
+    assert!(status.iter().all(|m| !m.applied));
+
+    let applied = db.run_migrations().await?;
+    assert_eq!(applied.len(), status.len());
+    assert!(db.migration_status().await?.iter().all(|m| m.applied));
+
+    for table in [
+        "licenses",
+        "license_binding_history",
+        "api_tokens",
+        "license_devices",
+        "license_leases",
+        "audit_log",
+        "webhooks",
+        "webhook_deliveries",
+        "organizations",
+    ] {
+        assert!(table_exists(&pool, table).await, "missing table {table}");
+    }
+
+    // Running again is a no-op
+    assert!(db.run_migrations().await?.is_empty());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn down_reverts_latest_migration_and_up_reapplies_it() -> LicenseResult<()> {
+    let pool = memory_pool().await?;
+    let db = Database::SQLite(pool.clone());
+    db.run_migrations().await?;
+
+    let status = db.migration_status().await?;
+    let latest = status.last().unwrap().clone();
+    let previous = status[status.len() - 2].version;
+
+    let reverted = db.revert_migrations(previous).await?;
+    assert_eq!(reverted.len(), 1);
+    assert_eq!(reverted[0].version, latest.version);
+    assert!(!reverted[0].applied);
+
+    assert!(!column_exists(&pool, "licenses", "suspended_by_org").await);
+    assert!(column_exists(&pool, "licenses", "usage_period_start").await);
+
+    let reapplied = db.run_migrations().await?;
+    assert_eq!(reapplied.len(), 1);
+    assert_eq!(reapplied[0].version, latest.version);
+    assert!(column_exists(&pool, "licenses", "suspended_by_org").await);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn every_migration_can_be_reverted() -> LicenseResult<()> {
+    let pool = memory_pool().await?;
+    let db = Database::SQLite(pool.clone());
+    db.run_migrations().await?;
+
+    let reverted = db.revert_migrations(0).await?;
+    assert_eq!(reverted.len(), db.migration_status().await?.len());
+    assert!(db.migration_status().await?.iter().all(|m| !m.applied));
+    assert!(!table_exists(&pool, "licenses").await);
+
+    // A full round trip leaves a usable schema
+    db.run_migrations().await?;
+    assert!(table_exists(&pool, "licenses").await);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn upgrades_legacy_init_schema_and_keeps_licenses() -> LicenseResult<()> {
+    let pool = memory_pool().await?;
+
+    // A database created by hand from the original init migration, never
+    // tracked by the migrator
+    sqlx::raw_sql(LEGACY_INIT_SCHEMA)
+        .execute(&pool)
+        .await
+        .map_err(|e| LicenseError::ServerError(format!("legacy schema failed: {e}")))?;
+    sqlx::query(
+        "INSERT INTO licenses (license_id, client_id, status, features, issued_at, hardware_id) \
+         VALUES ('LIC-LEGACY', 'client-1', 'active', 'export,api', '2024-11-14 10:00:00', NULL)",
+    )
+    .execute(&pool)
+    .await
+    .map_err(|e| LicenseError::ServerError(format!("legacy insert failed: {e}")))?;
+
+    let db = Database::SQLite(pool.clone());
+    db.run_migrations().await?;
+
+    assert!(column_exists(&pool, "licenses", "license_key").await);
+    assert!(column_exists(&pool, "licenses", "max_devices").await);
+    assert!(column_exists(&pool, "licenses", "max_concurrent").await);
+    assert!(column_exists(&pool, "licenses", "offline_days").await);
+    assert!(column_exists(&pool, "licenses", "billing_cycle").await);
+
+    let license = db.get_license("LIC-LEGACY").await?.expect("legacy license");
+    assert_eq!(license.client_id.as_deref(), Some("client-1"));
+    assert_eq!(license.status, "active");
+    assert_eq!(license.features.as_deref(), Some("export,api"));
+    assert_eq!(license.max_devices, 1);
+
+    // The upgraded license works with the current seat model
+    assert!(matches!(
+        db.claim_license_seat(&license, "HW-1", Some("Laptop"), None)
+            .await?,
+        SeatClaim::Claimed
+    ));
+    let stored = db.get_license("LIC-LEGACY").await?.unwrap();
+    assert_eq!(stored.hardware_id.as_deref(), Some("HW-1"));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn adopts_schema_created_by_setup_script() -> LicenseResult<()> {
+    let pool = memory_pool().await?;
+    sqlx::raw_sql(SETUP_SCRIPT)
+        .execute(&pool)
+        .await
+        .map_err(|e| LicenseError::ServerError(format!("setup script failed: {e}")))?;
+    sqlx::query(
+        "INSERT INTO licenses (license_id, status, issued_at, license_key, org_id) \
+         VALUES ('LIC-SCRIPT', 'active', '2026-01-01 00:00:00', 'KEY-SCRIPT', 'acme')",
+    )
+    .execute(&pool)
+    .await
+    .map_err(|e| LicenseError::ServerError(format!("insert failed: {e}")))?;
+
+    // Earlier versions of the script left out organizations and the listing
+    // index
+    sqlx::raw_sql("DROP TABLE organizations; DROP INDEX idx_licenses_issued_at;")
+        .execute(&pool)
+        .await
+        .map_err(|e| LicenseError::ServerError(format!("drop failed: {e}")))?;
+
+    let db = Database::SQLite(pool.clone());
+    assert!(db.migration_status().await?.iter().all(|m| !m.applied));
+
+    // Nothing runs again; every migration is recorded as applied
+    assert!(db.run_migrations().await?.is_empty());
+    let status = db.migration_status().await?;
+    assert!(status.iter().all(|m| m.applied && !m.checksum_mismatch));
+    assert!(db.run_migrations().await?.is_empty());
+    assert!(index_exists(&pool, "idx_licenses_issued_at").await);
+    assert!(db.get_org("acme").await?.is_some());
+
+    let license = db.get_license("LIC-SCRIPT").await?.expect("script license");
+    assert_eq!(license.license_key.as_deref(), Some("KEY-SCRIPT"));
+
+    // The adopted history can be rolled back like a migrated one
+    let previous = status[status.len() - 2].version;
+    assert_eq!(db.revert_migrations(previous).await?.len(), 1);
+    assert!(!column_exists(&pool, "licenses", "suspended_by_org").await);
+    assert_eq!(db.run_migrations().await?.len(), 1);
+    assert!(column_exists(&pool, "licenses", "suspended_by_org").await);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn adopts_only_the_migrations_an_older_schema_has() -> LicenseResult<()> {
+    let pool = memory_pool().await?;
+    let db = Database::SQLite(pool.clone());
+
+    // An untracked schema from before usage meters
+    db.run_migrations().await?;
+    db.revert_migrations(20260112000000).await?;
+    sqlx::query("DELETE FROM _sqlx_migrations")
+        .execute(&pool)
+        .await
+        .map_err(|e| LicenseError::ServerError(format!("delete failed: {e}")))?;
+
+    // Usage meters only adds tables, so it runs during adoption; later
+    // migrations add columns and are left to the migrator
+    let adopted = db.adopt_untracked_schema().await?;
+    assert_eq!(adopted.last(), Some(&20260113000000));
+    assert!(table_exists(&pool, "usage_meters").await);
+
+    let applied = db.run_migrations().await?;
+    let versions: Vec<i64> = applied.iter().map(|m| m.version).collect();
+    assert_eq!(versions, [20260114000000, 20260115000000]);
+    assert!(column_exists(&pool, "licenses", "billing_cycle").await);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn registers_organizations_referenced_by_licenses() -> LicenseResult<()> {
+    let pool = memory_pool().await?;
+    let db = Database::SQLite(pool.clone());
+    db.run_migrations().await?;
+
+    // Roll back to just before organizations existed, then add licenses the
+    // way older versions did: free-text org_id/org_name on each one
+    db.revert_migrations(20260107000000).await?;
+    for (id, org_id, org_name) in [
+        ("LIC-1", Some("acme"), Some("Acme")),
+        ("LIC-2", Some("acme"), None),
+        ("LIC-3", Some("globex"), None),
+        ("LIC-4", None, None),
+    ] {
+        sqlx::query(
+            "INSERT INTO licenses (license_id, status, issued_at, org_id, org_name) \
+             VALUES (?, 'active', '2025-01-01 00:00:00', ?, ?)",
+        )
+        .bind(id)
+        .bind(org_id)
+        .bind(org_name)
+        .execute(&pool)
+        .await
+        .map_err(|e| LicenseError::ServerError(format!("insert failed: {e}")))?;
+    }
+
+    db.run_migrations().await?;
+
+    let (orgs, total) = db.list_orgs(None, 10, 0).await?;
+    assert_eq!(total, 2);
+    let acme = orgs.iter().find(|o| o.id == "acme").expect("acme");
+    assert_eq!(acme.name, "Acme");
+    assert_eq!(acme.status, "active");
+    let globex = orgs.iter().find(|o| o.id == "globex").expect("globex");
+    assert_eq!(globex.name, "globex");
+
+    assert_eq!(db.org_stats("acme").await?.total_licenses, 2);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn moves_bandwidth_usage_into_meters() -> LicenseResult<()> {
+    let pool = memory_pool().await?;
+    let db = Database::SQLite(pool.clone());
+    db.run_migrations().await?;
+
+    // Roll back to before usage meters, when bandwidth lived on the license
+    db.revert_migrations(20260112000000).await?;
+    for (id, used, limit) in [
+        ("LIC-1", Some(500), Some(1_000)),
+        ("LIC-2", Some(0), None),
+        ("LIC-3", None, Some(0)),
+    ] {
+        sqlx::query(
+            "INSERT INTO licenses \
+             (license_id, status, issued_at, bandwidth_used_bytes, bandwidth_limit_bytes) \
+             VALUES (?, 'active', '2025-01-01 00:00:00', ?, ?)",
+        )
+        .bind(id)
+        .bind(used)
+        .bind(limit)
+        .execute(&pool)
+        .await
+        .map_err(|e| LicenseError::ServerError(format!("insert failed: {e}")))?;
+    }
+
+    db.run_migrations().await?;
+
+    let meters = db.list_usage_meters("LIC-1").await?;
+    assert_eq!(meters.len(), 1);
+    assert_eq!(meters[0].meter, "bandwidth");
+    assert_eq!(meters[0].used, 500);
+    assert_eq!(meters[0].usage_limit, Some(1_000));
+
+    // Licenses without usage or limit get no meter
+    assert!(db.list_usage_meters("LIC-2").await?.is_empty());
+    let meters = db.list_usage_meters("LIC-3").await?;
+    assert_eq!(meters.len(), 1);
+    assert_eq!(meters[0].usage_limit, Some(0));
+
+    Ok(())
+}
@@ -0,0 +1,237 @@
+//! Integration tests for rate limiting on the client endpoints.
+//!
+//! These tests require the `rate-limiting` feature to be enabled.
+
+#![cfg(feature = "rate-limiting")]
+
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use reqwest::StatusCode;
+use serde_json::{json, Value};
+use sqlx::sqlite::SqlitePoolOptions;
+use tokio::net::TcpListener;
+
+use talos::config::RateLimitConfig;
+use talos::server::database::Database;
+use talos::server::handlers::AppState;
+use talos::server::routes::build_router_with_rate_limit;
+
+#[cfg(feature = "jwt-auth")]
+use talos::server::auth::AuthState;
+
+/// Helper: create an in-memory SQLite `Database` with the `licenses` table.
+async fn setup_in_memory_db() -> Arc<Database> {
+    let pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await
+        .expect("db connect failed");
+
+    sqlx::query(
+        r#"
+        CREATE TABLE licenses (
+            license_id      TEXT PRIMARY KEY,
+            client_id       TEXT,
+            status          TEXT NOT NULL,
+            features        TEXT,
+            issued_at       TEXT NOT NULL,
+            expires_at      TEXT,
+            hardware_id     TEXT,
+            signature       TEXT,
+            last_heartbeat  TEXT,
+            org_id          TEXT,
+            org_name        TEXT,
+            license_key     TEXT UNIQUE,
+            tier            TEXT,
+            device_name     TEXT,
+            device_info     TEXT,
+            bound_at        TEXT,
+            last_seen_at    TEXT,
+            suspended_at    TEXT,
+            revoked_at      TEXT,
+            revoke_reason   TEXT,
+            grace_period_ends_at TEXT,
+            suspension_message TEXT,
+            is_blacklisted  INTEGER DEFAULT 0,
+            blacklisted_at  TEXT,
+            blacklist_reason TEXT,
+            metadata        TEXT,
+            bandwidth_used_bytes INTEGER DEFAULT 0,
+            bandwidth_limit_bytes INTEGER,
+            quota_exceeded  INTEGER DEFAULT 0,
+            max_devices     INTEGER NOT NULL DEFAULT 1,
+            max_concurrent  INTEGER,
+            offline_days    INTEGER,
+            billing_cycle   TEXT,
+            usage_period_start TEXT,
+            suspended_by_org INTEGER
+        );
+        "#,
+    )
+    .execute(&pool)
+    .await
+    .expect("schema create failed");
+
+    Arc::new(Database::SQLite(pool))
+}
+
+/// Spin up a server with the full router and the given rate limits.
+///
+/// Served with `ConnectInfo` like the real binary, so limits are keyed by peer IP.
+async fn spawn_rate_limited_server(rate_limit: RateLimitConfig) -> String {
+    // Keep the admin IP whitelist out of the way
+    std::env::set_var("TALOS_ADMIN_IP_WHITELIST", "");
+
+    let state = AppState {
+        db: setup_in_memory_db().await,
+        #[cfg(feature = "jwt-auth")]
+        auth: AuthState::disabled(),
+        signer: None,
+    };
+    let router = build_router_with_rate_limit(state, &rate_limit);
+
+    let listener = TcpListener::bind(SocketAddr::from(([127, 0, 0, 1], 0)))
+        .await
+        .expect("failed to bind");
+    let addr = listener.local_addr().unwrap();
+
+    tokio::spawn(async move {
+        axum::serve(
+            listener,
+            router.into_make_service_with_connect_info::<SocketAddr>(),
+        )
+        .await
+        .expect("server failed");
+    });
+
+    format!("http://{}", addr)
+}
+
+/// Tight limits: two requests per class, then one per minute.
+fn strict_config() -> RateLimitConfig {
+    RateLimitConfig {
+        enabled: true,
+        validate_rpm: 1,
+        heartbeat_rpm: 1,
+        bind_rpm: 1,
+        burst_size: 2,
+    }
+}
+
+async fn post(client: &reqwest::Client, url: &str) -> reqwest::Response {
+    client
+        .post(url)
+        .json(&json!({ "license_key": "LIC-UNKNOWN", "hardware_id": "hw-1" }))
+        .send()
+        .await
+        .expect("request failed")
+}
+
+#[tokio::test]
+async fn bind_over_limit_returns_429_with_retry_after() {
+    let base = spawn_rate_limited_server(strict_config()).await;
+    let client = reqwest::Client::new();
+    let url = format!("{base}/api/v1/client/bind");
+
+    for _ in 0..2 {
+        let res = post(&client, &url).await;
+        assert_ne!(res.status(), StatusCode::TOO_MANY_REQUESTS);
+    }
+
+    let res = post(&client, &url).await;
+    assert_eq!(res.status(), StatusCode::TOO_MANY_REQUESTS);
+
+    let retry_after: u64 = res
+        .headers()
+        .get("Retry-After")
+        .expect("missing Retry-After")
+        .to_str()
+        .unwrap()
+        .parse()
+        .unwrap();
+    assert!(retry_after >= 1);
+
+    let body: Value = res.json().await.unwrap();
+    assert_eq!(body["error"], "Too many requests");
+    assert_eq!(body["retry_after_seconds"], retry_after);
+}
+
+#[tokio::test]
+async fn bind_and_release_share_a_limit() {
+    let base = spawn_rate_limited_server(strict_config()).await;
+    let client = reqwest::Client::new();
+
+    post(&client, &format!("{base}/api/v1/client/bind")).await;
+    post(&client, &format!("{base}/api/v1/client/release")).await;
+
+    let res = post(&client, &format!("{base}/activate")).await;
+    assert_eq!(res.status(), StatusCode::TOO_MANY_REQUESTS);
+}
+
+#[tokio::test]
+async fn endpoint_classes_are_limited_independently() {
+    let base = spawn_rate_limited_server(strict_config()).await;
+    let client = reqwest::Client::new();
+
+    for _ in 0..3 {
+        post(&client, &format!("{base}/api/v1/client/bind")).await;
+    }
+    assert_eq!(
+        post(&client, &format!("{base}/api/v1/client/bind"))
+            .await
+            .status(),
+        StatusCode::TOO_MANY_REQUESTS
+    );
+
+    let res = post(&client, &format!("{base}/api/v1/client/validate")).await;
+    assert_eq!(res.status(), StatusCode::NOT_FOUND);
+
+    let res = post(&client, &format!("{base}/api/v1/client/heartbeat")).await;
+    assert_ne!(res.status(), StatusCode::TOO_MANY_REQUESTS);
+}
+
+#[tokio::test]
+async fn validate_and_heartbeat_over_limit_return_429() {
+    let base = spawn_rate_limited_server(strict_config()).await;
+    let client = reqwest::Client::new();
+
+    for path in ["/api/v1/client/validate", "/api/v1/client/heartbeat"] {
+        let url = format!("{base}{path}");
+        for _ in 0..2 {
+            post(&client, &url).await;
+        }
+        assert_eq!(
+            post(&client, &url).await.status(),
+            StatusCode::TOO_MANY_REQUESTS,
+            "{path} should be rate limited"
+        );
+    }
+}
+
+#[tokio::test]
+async fn health_is_never_limited() {
+    let base = spawn_rate_limited_server(strict_config()).await;
+    let client = reqwest::Client::new();
+
+    for _ in 0..5 {
+        let res = client.get(format!("{base}/health")).send().await.unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+    }
+}
+
+#[tokio::test]
+async fn disabled_rate_limit_allows_all_requests() {
+    let base = spawn_rate_limited_server(RateLimitConfig {
+        enabled: false,
+        ..strict_config()
+    })
+    .await;
+    let client = reqwest::Client::new();
+    let url = format!("{base}/api/v1/client/bind");
+
+    for _ in 0..5 {
+        let res = post(&client, &url).await;
+        assert_eq!(res.status(), StatusCode::NOT_FOUND);
+    }
+}
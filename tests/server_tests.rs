@@ -57,7 +57,13 @@ async fn setup_in_memory_db() -> LicenseResult<Arc<Database>> {
             metadata        TEXT,
             bandwidth_used_bytes INTEGER DEFAULT 0,
             bandwidth_limit_bytes INTEGER,
-            quota_exceeded  INTEGER DEFAULT 0
+            quota_exceeded  INTEGER DEFAULT 0,
+            max_devices     INTEGER NOT NULL DEFAULT 1,
+            max_concurrent  INTEGER,
+            offline_days    INTEGER,
+            billing_cycle   TEXT,
+            usage_period_start TEXT,
+            suspended_by_org INTEGER
         );
         "#,
     )
@@ -76,6 +82,7 @@ async fn activate_and_validate_round_trip() -> LicenseResult<()> {
         db,
         #[cfg(feature = "jwt-auth")]
         auth: AuthState::disabled(),
+        signer: None,
     };
 
     let license_id = "TEST-LICENSE-1".to_string();
@@ -122,6 +129,7 @@ async fn deactivate_makes_license_invalid() -> LicenseResult<()> {
         db,
         #[cfg(feature = "jwt-auth")]
         auth: AuthState::disabled(),
+        signer: None,
     };
 
     let license_id = "TEST-LICENSE-2".to_string();
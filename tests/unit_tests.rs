@@ -66,7 +66,13 @@ async fn setup_in_memory_db() -> Arc<Database> {
             metadata        TEXT,
             bandwidth_used_bytes INTEGER DEFAULT 0,
             bandwidth_limit_bytes INTEGER,
-            quota_exceeded  INTEGER DEFAULT 0
+            quota_exceeded  INTEGER DEFAULT 0,
+            max_devices     INTEGER NOT NULL DEFAULT 1,
+            max_concurrent  INTEGER,
+            offline_days    INTEGER,
+            billing_cycle   TEXT,
+            usage_period_start TEXT,
+            suspended_by_org INTEGER
         );
         "#,
     )
@@ -74,6 +80,38 @@ async fn setup_in_memory_db() -> Arc<Database> {
     .await
     .expect("schema create failed");
 
+    sqlx::query(
+        r#"
+        CREATE TABLE license_devices (
+            license_id      TEXT NOT NULL,
+            hardware_id     TEXT NOT NULL,
+            device_name     TEXT,
+            device_info     TEXT,
+            bound_at        TEXT NOT NULL,
+            last_seen_at    TEXT,
+            PRIMARY KEY (license_id, hardware_id)
+        );
+        "#,
+    )
+    .execute(&pool)
+    .await
+    .expect("devices table create failed");
+
+    sqlx::query(
+        r#"
+        CREATE TABLE license_leases (
+            license_id      TEXT NOT NULL,
+            hardware_id     TEXT NOT NULL,
+            checked_out_at  TEXT NOT NULL,
+            expires_at      TEXT NOT NULL,
+            PRIMARY KEY (license_id, hardware_id)
+        );
+        "#,
+    )
+    .execute(&pool)
+    .await
+    .expect("leases table create failed");
+
     Arc::new(Database::SQLite(pool))
 }
 
@@ -84,6 +122,7 @@ async fn spawn_test_server() -> String {
         db,
         #[cfg(feature = "jwt-auth")]
         auth: AuthState::disabled(),
+        signer: None,
     };
 
     let router: Router = Router::new()
@@ -0,0 +1,835 @@
+//! Integration tests for webhook notifications.
+//!
+//! These tests require the `admin-api` feature to be enabled. Deliveries are
+//! sent to a local HTTP receiver that records every request.
+
+#![cfg(feature = "admin-api")]
+
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+
+use axum::{
+    body::{Body, Bytes},
+    extract::State,
+    http::{HeaderMap, Request, StatusCode},
+    routing::post,
+    Router,
+};
+use serde_json::{json, Value};
+use talos::config::WebhookConfig;
+use talos::server::database::Database;
+use talos::server::handlers::AppState;
+use talos::server::routes::build_router;
+use talos::server::webhooks::{
+    deliver_due_webhooks, delivery_client, verify_signature, DELIVERY_HEADER, EVENT_HEADER,
+    SIGNATURE_HEADER,
+};
+use tokio::net::TcpListener;
+use tower::ServiceExt;
+
+#[cfg(feature = "jwt-auth")]
+use talos::server::auth::AuthState;
+
+const SECRET: &str = "whsec_test_secret";
+
+/// Helper to create a test database and app state with webhooks enabled.
+async fn setup_test_app() -> AppState {
+    std::env::set_var("TALOS_DATABASE_TYPE", "sqlite");
+    std::env::set_var("TALOS_DATABASE_URL", "sqlite::memory:");
+    // Disable IP whitelist for tests (tower::oneshot doesn't provide ConnectInfo)
+    std::env::set_var("TALOS_ADMIN_IP_WHITELIST", "");
+    // Rate limits are keyed by client IP, which oneshot requests don't have
+    std::env::set_var("TALOS_RATE_LIMIT_ENABLED", "false");
+    std::env::set_var("TALOS_WEBHOOKS_ENABLED", "true");
+    // Deliveries go to a receiver on 127.0.0.1
+    std::env::set_var("TALOS_WEBHOOKS_ALLOW_PRIVATE_TARGETS", "true");
+
+    let db = Database::new().await.expect("failed to create database");
+
+    // Run migrations (create tables)
+    match &*db {
+        #[cfg(feature = "sqlite")]
+        Database::SQLite(pool) => {
+            sqlx::query(
+                r#"
+                CREATE TABLE IF NOT EXISTS licenses (
+                    license_id TEXT PRIMARY KEY,
+                    client_id TEXT,
+                    status TEXT NOT NULL DEFAULT 'active',
+                    features TEXT,
+                    issued_at TEXT NOT NULL,
+                    expires_at TEXT,
+                    hardware_id TEXT,
+                    signature TEXT,
+                    last_heartbeat TEXT,
+                    org_id TEXT,
+                    org_name TEXT,
+                    license_key TEXT UNIQUE,
+                    tier TEXT,
+                    device_name TEXT,
+                    device_info TEXT,
+                    bound_at TEXT,
+                    last_seen_at TEXT,
+                    suspended_at TEXT,
+                    revoked_at TEXT,
+                    revoke_reason TEXT,
+                    grace_period_ends_at TEXT,
+                    suspension_message TEXT,
+                    is_blacklisted INTEGER,
+                    blacklisted_at TEXT,
+                    blacklist_reason TEXT,
+                    metadata TEXT,
+                    bandwidth_used_bytes INTEGER DEFAULT 0,
+                    bandwidth_limit_bytes INTEGER,
+                    quota_exceeded INTEGER DEFAULT 0,
+                    max_devices INTEGER NOT NULL DEFAULT 1,
+                    max_concurrent INTEGER,
+                    offline_days INTEGER,
+                    billing_cycle TEXT,
+                    usage_period_start TEXT,
+                    suspended_by_org INTEGER
+                )
+                "#,
+            )
+            .execute(pool)
+            .await
+            .expect("failed to create licenses table");
+
+            sqlx::query(
+                r#"
+                CREATE TABLE IF NOT EXISTS license_devices (
+                    license_id TEXT NOT NULL,
+                    hardware_id TEXT NOT NULL,
+                    device_name TEXT,
+                    device_info TEXT,
+                    bound_at TEXT NOT NULL,
+                    last_seen_at TEXT,
+                    PRIMARY KEY (license_id, hardware_id)
+                )
+                "#,
+            )
+            .execute(pool)
+            .await
+            .expect("failed to create license_devices table");
+
+            sqlx::query(
+                r#"
+                CREATE TABLE IF NOT EXISTS license_leases (
+                    license_id TEXT NOT NULL,
+                    hardware_id TEXT NOT NULL,
+                    checked_out_at TEXT NOT NULL,
+                    expires_at TEXT NOT NULL,
+                    PRIMARY KEY (license_id, hardware_id)
+                )
+                "#,
+            )
+            .execute(pool)
+            .await
+            .expect("failed to create license_leases table");
+
+            sqlx::query(
+                r#"
+                CREATE TABLE IF NOT EXISTS webhooks (
+                    id TEXT PRIMARY KEY,
+                    url TEXT NOT NULL,
+                    secret TEXT NOT NULL,
+                    events TEXT NOT NULL,
+                    description TEXT,
+                    is_active INTEGER NOT NULL DEFAULT 1,
+                    created_at TEXT NOT NULL,
+                    updated_at TEXT NOT NULL
+                )
+                "#,
+            )
+            .execute(pool)
+            .await
+            .expect("failed to create webhooks table");
+
+            sqlx::query(
+                r#"
+                CREATE TABLE IF NOT EXISTS webhook_deliveries (
+                    id TEXT PRIMARY KEY,
+                    webhook_id TEXT NOT NULL,
+                    event_id TEXT NOT NULL,
+                    event TEXT NOT NULL,
+                    payload TEXT NOT NULL,
+                    status TEXT NOT NULL DEFAULT 'pending',
+                    attempts INTEGER NOT NULL DEFAULT 0,
+                    next_attempt_at TEXT,
+                    last_attempt_at TEXT,
+                    response_status INTEGER,
+                    last_error TEXT,
+                    created_at TEXT NOT NULL,
+                    delivered_at TEXT
+                )
+                "#,
+            )
+            .execute(pool)
+            .await
+            .expect("failed to create webhook_deliveries table");
+
+            sqlx::query(
+                r#"
+                CREATE TABLE IF NOT EXISTS organizations (
+                    id TEXT PRIMARY KEY,
+                    name TEXT NOT NULL,
+                    contact_name TEXT,
+                    contact_email TEXT,
+                    metadata TEXT,
+                    default_tier TEXT,
+                    default_features TEXT,
+                    status TEXT NOT NULL DEFAULT 'active',
+                    suspended_at TEXT,
+                    suspension_reason TEXT,
+                    created_at TEXT NOT NULL,
+                    updated_at TEXT NOT NULL
+                )
+                "#,
+            )
+            .execute(pool)
+            .await
+            .expect("failed to create organizations table");
+
+            sqlx::query(
+                r#"
+                CREATE TABLE IF NOT EXISTS usage_meters (
+                    license_id TEXT NOT NULL,
+                    meter TEXT NOT NULL,
+                    used INTEGER NOT NULL DEFAULT 0,
+                    usage_limit INTEGER,
+                    updated_at TEXT NOT NULL,
+                    PRIMARY KEY (license_id, meter)
+                )
+                "#,
+            )
+            .execute(pool)
+            .await
+            .expect("failed to create usage_meters table");
+
+            sqlx::query(
+                r#"
+                CREATE TABLE IF NOT EXISTS usage_reports (
+                    license_id TEXT NOT NULL,
+                    report_id TEXT NOT NULL,
+                    meter TEXT NOT NULL,
+                    amount INTEGER NOT NULL,
+                    reported_at TEXT NOT NULL,
+                    PRIMARY KEY (license_id, report_id)
+                )
+                "#,
+            )
+            .execute(pool)
+            .await
+            .expect("failed to create usage_reports table");
+
+            sqlx::query(
+                r#"
+                CREATE TABLE IF NOT EXISTS usage_periods (
+                    license_id TEXT NOT NULL,
+                    meter TEXT NOT NULL,
+                    period_start TEXT NOT NULL,
+                    period_end TEXT NOT NULL,
+                    used INTEGER NOT NULL,
+                    usage_limit INTEGER,
+                    PRIMARY KEY (license_id, meter, period_start)
+                )
+                "#,
+            )
+            .execute(pool)
+            .await
+            .expect("failed to create usage_periods table");
+        }
+        #[cfg(feature = "postgres")]
+        Database::Postgres(_) => {
+            panic!("PostgreSQL not supported in tests");
+        }
+    }
+
+    AppState {
+        db,
+        #[cfg(feature = "jwt-auth")]
+        auth: AuthState::disabled(),
+        signer: None,
+    }
+}
+
+/// Helper to make a JSON request to the app.
+async fn json_request(
+    app: axum::Router,
+    method: &str,
+    uri: &str,
+    body: Option<Value>,
+) -> (StatusCode, Value) {
+    let body_bytes = body
+        .map(|v| serde_json::to_vec(&v).unwrap())
+        .unwrap_or_default();
+
+    let request = Request::builder()
+        .method(method)
+        .uri(uri)
+        .header("Content-Type", "application/json")
+        .body(Body::from(body_bytes))
+        .unwrap();
+
+    let response = app.oneshot(request).await.unwrap();
+    let status = response.status();
+
+    let body_bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let body: Value = serde_json::from_slice(&body_bytes).unwrap_or(json!({}));
+
+    (status, body)
+}
+
+/// A request received by the stand-in endpoint.
+#[derive(Clone)]
+struct Received {
+    headers: HeaderMap,
+    body: Bytes,
+}
+
+impl Received {
+    fn header(&self, name: &str) -> &str {
+        self.headers.get(name).unwrap().to_str().unwrap()
+    }
+
+    fn json(&self) -> Value {
+        serde_json::from_slice(&self.body).unwrap()
+    }
+}
+
+#[derive(Clone, Default)]
+struct Receiver {
+    received: Arc<Mutex<Vec<Received>>>,
+    /// Status codes to answer with, in order; 200 once exhausted
+    responses: Arc<Mutex<VecDeque<StatusCode>>>,
+}
+
+impl Receiver {
+    fn received(&self) -> Vec<Received> {
+        self.received.lock().unwrap().clone()
+    }
+
+    fn respond_with(&self, statuses: &[StatusCode]) {
+        self.responses.lock().unwrap().extend(statuses);
+    }
+}
+
+async fn receive(State(receiver): State<Receiver>, headers: HeaderMap, body: Bytes) -> StatusCode {
+    receiver
+        .received
+        .lock()
+        .unwrap()
+        .push(Received { headers, body });
+    receiver
+        .responses
+        .lock()
+        .unwrap()
+        .pop_front()
+        .unwrap_or(StatusCode::OK)
+}
+
+/// Start a local HTTP endpoint and return its URL.
+async fn start_receiver() -> (String, Receiver) {
+    let receiver = Receiver::default();
+    let app = Router::new()
+        .route("/hook", post(receive))
+        .with_state(receiver.clone());
+
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move {
+        axum::serve(listener, app).await.unwrap();
+    });
+
+    (format!("http://{addr}/hook"), receiver)
+}
+
+/// Start a local endpoint that answers every POST with a `307` to `location`.
+async fn start_redirector(location: String) -> String {
+    let app = Router::new().route(
+        "/hook",
+        post(move || async move {
+            (
+                StatusCode::TEMPORARY_REDIRECT,
+                [(axum::http::header::LOCATION, location)],
+            )
+        }),
+    );
+
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move {
+        axum::serve(listener, app).await.unwrap();
+    });
+
+    format!("http://{addr}/hook")
+}
+
+/// Retry immediately so tests don't have to wait for backoff.
+fn test_config() -> WebhookConfig {
+    WebhookConfig {
+        enabled: true,
+        max_attempts: 3,
+        retry_base_secs: 0,
+        allow_private_targets: true,
+        ..WebhookConfig::default()
+    }
+}
+
+async fn deliver(state: &AppState, config: &WebhookConfig) -> u32 {
+    let client = delivery_client(config).unwrap();
+    deliver_due_webhooks(&*state.db, &client, config)
+        .await
+        .unwrap()
+}
+
+async fn create_webhook(state: &AppState, url: &str, events: Value) -> String {
+    let (status, body) = json_request(
+        build_router(state.clone()),
+        "POST",
+        "/api/v1/webhooks",
+        Some(json!({ "url": url, "events": events, "secret": SECRET })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    body["webhook"]["id"].as_str().unwrap().to_string()
+}
+
+async fn create_license(state: &AppState) -> (String, String) {
+    let (status, body) = json_request(
+        build_router(state.clone()),
+        "POST",
+        "/api/v1/licenses",
+        Some(json!({ "org_id": "org-1" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    (
+        body["license_id"].as_str().unwrap().to_string(),
+        body["license_key"].as_str().unwrap().to_string(),
+    )
+}
+
+async fn deliveries(state: &AppState, webhook_id: &str) -> Value {
+    let (status, body) = json_request(
+        build_router(state.clone()),
+        "GET",
+        &format!("/api/v1/webhooks/{webhook_id}/deliveries"),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    body
+}
+
+// ============================================================================
+// Webhook management
+// ============================================================================
+
+#[tokio::test]
+async fn webhook_crud_round_trip() {
+    let state = setup_test_app().await;
+
+    let (status, body) = json_request(
+        build_router(state.clone()),
+        "POST",
+        "/api/v1/webhooks",
+        Some(json!({
+            "url": "https://billing.example.com/talos",
+            "events": ["license.created", "license.revoked"],
+            "description": "Billing"
+        })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert!(body["secret"].as_str().unwrap().starts_with("whsec_"));
+    assert_eq!(
+        body["webhook"]["events"],
+        json!(["license.created", "license.revoked"])
+    );
+    assert_eq!(body["webhook"]["is_active"], true);
+    let id = body["webhook"]["id"].as_str().unwrap().to_string();
+
+    let (status, body) =
+        json_request(build_router(state.clone()), "GET", "/api/v1/webhooks", None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["webhooks"].as_array().unwrap().len(), 1);
+    assert!(body["webhooks"][0].get("secret").is_none());
+
+    let (status, body) = json_request(
+        build_router(state.clone()),
+        "PATCH",
+        &format!("/api/v1/webhooks/{id}"),
+        Some(json!({ "events": ["*"], "is_active": false })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["events"], json!(["*"]));
+    assert_eq!(body["is_active"], false);
+    assert_eq!(body["description"], "Billing");
+
+    let (status, _) = json_request(
+        build_router(state.clone()),
+        "DELETE",
+        &format!("/api/v1/webhooks/{id}"),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+
+    let (status, _) = json_request(
+        build_router(state.clone()),
+        "GET",
+        &format!("/api/v1/webhooks/{id}"),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn create_webhook_validates_url_and_events() {
+    let state = setup_test_app().await;
+
+    for payload in [
+        json!({ "url": "ftp://example.com", "events": ["license.created"] }),
+        json!({ "url": "https://example.com", "events": [] }),
+        json!({ "url": "https://example.com", "events": ["license.heartbeat"] }),
+    ] {
+        let (status, _) = json_request(
+            build_router(state.clone()),
+            "POST",
+            "/api/v1/webhooks",
+            Some(payload),
+        )
+        .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+}
+
+// ============================================================================
+// Delivery
+// ============================================================================
+
+#[tokio::test]
+async fn revoke_delivers_signed_payload() {
+    let state = setup_test_app().await;
+    let (url, receiver) = start_receiver().await;
+    let webhook_id = create_webhook(&state, &url, json!(["license.revoked"])).await;
+    let (license_id, license_key) = create_license(&state).await;
+
+    let (status, _) = json_request(
+        build_router(state.clone()),
+        "POST",
+        &format!("/api/v1/licenses/{license_id}/revoke"),
+        Some(json!({ "reason": "chargeback" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    assert_eq!(deliver(&state, &test_config()).await, 1);
+
+    // Only the subscribed event was sent, not license.created
+    let received = receiver.received();
+    assert_eq!(received.len(), 1);
+    let request = &received[0];
+    assert_eq!(request.header(EVENT_HEADER), "license.revoked");
+    assert!(verify_signature(
+        SECRET,
+        &request.body,
+        request.header(SIGNATURE_HEADER)
+    ));
+
+    let payload = request.json();
+    assert_eq!(payload["event"], "license.revoked");
+    assert_eq!(payload["data"]["license"]["license_id"], license_id);
+    assert_eq!(payload["data"]["license"]["license_key"], license_key);
+    assert_eq!(payload["data"]["license"]["status"], "revoked");
+    assert_eq!(payload["data"]["details"]["reason"], "chargeback");
+
+    let log = deliveries(&state, &webhook_id).await;
+    assert_eq!(log["total"], 1);
+    let delivery = &log["deliveries"][0];
+    assert_eq!(delivery["id"], request.header(DELIVERY_HEADER));
+    assert_eq!(delivery["status"], "delivered");
+    assert_eq!(delivery["attempts"], 1);
+    assert_eq!(delivery["response_status"], 200);
+    assert_eq!(delivery["payload"], payload);
+
+    // Nothing left to send
+    assert_eq!(deliver(&state, &test_config()).await, 0);
+    assert_eq!(receiver.received().len(), 1);
+}
+
+#[tokio::test]
+async fn client_bind_and_release_are_delivered() {
+    let state = setup_test_app().await;
+    let (url, receiver) = start_receiver().await;
+    create_webhook(&state, &url, json!(["*"])).await;
+    let (_, license_key) = create_license(&state).await;
+
+    let device = json!({
+        "license_key": license_key,
+        "hardware_id": "hw-1",
+        "device_name": "Workstation"
+    });
+    let (status, _) = json_request(
+        build_router(state.clone()),
+        "POST",
+        "/api/v1/client/bind",
+        Some(device.clone()),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, _) = json_request(
+        build_router(state.clone()),
+        "POST",
+        "/api/v1/client/release",
+        Some(device),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    assert_eq!(deliver(&state, &test_config()).await, 3);
+
+    let mut events: Vec<Value> = receiver.received().iter().map(Received::json).collect();
+    events.sort_by_key(|e| e["occurred_at"].as_str().unwrap().to_string());
+    let names: Vec<&str> = events
+        .iter()
+        .map(|e| e["event"].as_str().unwrap())
+        .collect();
+    assert!(names.contains(&"license.created"));
+    assert!(names.contains(&"license.bound"));
+    assert!(names.contains(&"license.released"));
+
+    let bound = events
+        .iter()
+        .find(|e| e["event"] == "license.bound")
+        .unwrap();
+    assert_eq!(bound["data"]["details"]["hardware_id"], "hw-1");
+    assert_eq!(bound["data"]["details"]["device_name"], "Workstation");
+}
+
+#[tokio::test]
+async fn quota_exceeded_is_delivered_once() {
+    let state = setup_test_app().await;
+    let (url, receiver) = start_receiver().await;
+    create_webhook(&state, &url, json!(["license.quota_exceeded"])).await;
+    let (license_id, _) = create_license(&state).await;
+
+    for used in [50, 150, 200] {
+        let (status, _) = json_request(
+            build_router(state.clone()),
+            "PATCH",
+            &format!("/api/v1/licenses/{license_id}/usage"),
+            Some(json!({ "bandwidth_used_bytes": used, "bandwidth_limit_bytes": 100 })),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+    }
+
+    assert_eq!(deliver(&state, &test_config()).await, 1);
+    let payload = receiver.received()[0].json();
+    assert_eq!(payload["event"], "license.quota_exceeded");
+    assert_eq!(payload["data"]["details"]["bandwidth_used_bytes"], 150);
+}
+
+#[tokio::test]
+async fn inactive_webhooks_receive_nothing() {
+    let state = setup_test_app().await;
+    let (url, receiver) = start_receiver().await;
+    let webhook_id = create_webhook(&state, &url, json!(["*"])).await;
+
+    let (status, _) = json_request(
+        build_router(state.clone()),
+        "PATCH",
+        &format!("/api/v1/webhooks/{webhook_id}"),
+        Some(json!({ "is_active": false })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    create_license(&state).await;
+
+    assert_eq!(deliver(&state, &test_config()).await, 0);
+    assert!(receiver.received().is_empty());
+    assert_eq!(deliveries(&state, &webhook_id).await["total"], 0);
+}
+
+// ============================================================================
+// Retries
+// ============================================================================
+
+#[tokio::test]
+async fn failed_delivery_is_retried() {
+    let state = setup_test_app().await;
+    let (url, receiver) = start_receiver().await;
+    receiver.respond_with(&[StatusCode::INTERNAL_SERVER_ERROR]);
+    let webhook_id = create_webhook(&state, &url, json!(["license.created"])).await;
+    create_license(&state).await;
+
+    assert_eq!(deliver(&state, &test_config()).await, 0);
+    let log = deliveries(&state, &webhook_id).await;
+    let delivery = &log["deliveries"][0];
+    assert_eq!(delivery["status"], "pending");
+    assert_eq!(delivery["attempts"], 1);
+    assert_eq!(delivery["response_status"], 500);
+    assert!(delivery["last_error"].as_str().unwrap().contains("500"));
+
+    assert_eq!(deliver(&state, &test_config()).await, 1);
+    let log = deliveries(&state, &webhook_id).await;
+    assert_eq!(log["deliveries"][0]["status"], "delivered");
+    assert_eq!(log["deliveries"][0]["attempts"], 2);
+
+    // Both attempts carried the same delivery and event IDs
+    let received = receiver.received();
+    assert_eq!(received.len(), 2);
+    assert_eq!(
+        received[0].header(DELIVERY_HEADER),
+        received[1].header(DELIVERY_HEADER)
+    );
+    assert_eq!(received[0].json()["id"], received[1].json()["id"]);
+}
+
+#[tokio::test]
+async fn private_targets_are_not_delivered_by_default() {
+    let state = setup_test_app().await;
+    let (url, receiver) = start_receiver().await;
+    let webhook_id = create_webhook(&state, &url, json!(["license.created"])).await;
+    create_license(&state).await;
+
+    let config = WebhookConfig {
+        allow_private_targets: false,
+        ..test_config()
+    };
+    assert_eq!(deliver(&state, &config).await, 0);
+    assert!(receiver.received().is_empty());
+
+    let log = deliveries(&state, &webhook_id).await;
+    let delivery = &log["deliveries"][0];
+    assert_eq!(delivery["status"], "pending");
+    assert!(delivery["last_error"].as_str().unwrap().contains("private"));
+
+    // Host names that resolve only to private addresses are refused too
+    let by_name = url.replace("127.0.0.1", "localhost");
+    let client = delivery_client(&config).unwrap();
+    let error = client.post(&by_name).send().await.unwrap_err();
+    assert!(format!("{error:?}").contains("no public address"));
+    assert!(receiver.received().is_empty());
+}
+
+#[tokio::test]
+async fn redirects_to_private_targets_are_refused() {
+    let (url, receiver) = start_receiver().await;
+    // The redirector stands in for a public endpoint; its own address is
+    // never checked by the client, only the hops it redirects to
+    let redirector = start_redirector(url).await;
+    let metadata = start_redirector("http://169.254.169.254/latest/meta-data/".into()).await;
+
+    let config = WebhookConfig {
+        allow_private_targets: false,
+        ..test_config()
+    };
+    let client = delivery_client(&config).unwrap();
+    for target in [&redirector, &metadata] {
+        let error = client.post(target).send().await.unwrap_err();
+        assert!(error.is_redirect(), "{error:?}");
+        assert!(format!("{error:?}").contains("redirect refused"));
+    }
+    assert!(receiver.received().is_empty());
+
+    // With private targets allowed the redirect is followed
+    let client = delivery_client(&test_config()).unwrap();
+    let response = client.post(&redirector).send().await.unwrap();
+    assert!(response.status().is_success());
+    assert_eq!(receiver.received().len(), 1);
+}
+
+#[tokio::test]
+async fn retries_wait_for_backoff() {
+    let state = setup_test_app().await;
+    let (url, receiver) = start_receiver().await;
+    receiver.respond_with(&[StatusCode::SERVICE_UNAVAILABLE]);
+    let webhook_id = create_webhook(&state, &url, json!(["license.created"])).await;
+    create_license(&state).await;
+
+    let config = WebhookConfig {
+        retry_base_secs: 60,
+        ..test_config()
+    };
+
+    assert_eq!(deliver(&state, &config).await, 0);
+    assert_eq!(deliver(&state, &config).await, 0);
+
+    // The second pass found nothing due yet
+    assert_eq!(receiver.received().len(), 1);
+    let log = deliveries(&state, &webhook_id).await;
+    assert_eq!(log["deliveries"][0]["status"], "pending");
+    assert_eq!(log["deliveries"][0]["attempts"], 1);
+    assert!(log["deliveries"][0]["next_attempt_at"].is_string());
+}
+
+#[tokio::test]
+async fn delivery_fails_after_max_attempts() {
+    let state = setup_test_app().await;
+    let (url, receiver) = start_receiver().await;
+    receiver.respond_with(&[StatusCode::INTERNAL_SERVER_ERROR; 5]);
+    let webhook_id = create_webhook(&state, &url, json!(["license.created"])).await;
+    create_license(&state).await;
+
+    let config = test_config();
+    for _ in 0..5 {
+        deliver(&state, &config).await;
+    }
+
+    assert_eq!(receiver.received().len(), config.max_attempts as usize);
+    let log = deliveries(&state, &webhook_id).await;
+    assert_eq!(log["deliveries"][0]["status"], "failed");
+    assert_eq!(log["deliveries"][0]["attempts"], config.max_attempts);
+    assert!(log["deliveries"][0]["next_attempt_at"].is_null());
+
+    let (status, body) = json_request(
+        build_router(state.clone()),
+        "GET",
+        &format!("/api/v1/webhooks/{webhook_id}/deliveries?status=failed"),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["total"], 1);
+}
+
+// ============================================================================
+// Background jobs
+// ============================================================================
+
+#[cfg(feature = "background-jobs")]
+#[tokio::test]
+async fn expiration_job_delivers_expired_event() {
+    use talos::jobs::run_license_expiration_check;
+
+    let state = setup_test_app().await;
+    let (url, receiver) = start_receiver().await;
+    create_webhook(&state, &url, json!(["license.expired"])).await;
+
+    let (status, body) = json_request(
+        build_router(state.clone()),
+        "POST",
+        "/api/v1/licenses",
+        Some(json!({ "expires_at": "2020-01-01T00:00:00" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    let license_id = body["license_id"].as_str().unwrap().to_string();
+
+    assert_eq!(run_license_expiration_check(&*state.db).await.unwrap(), 1);
+    assert_eq!(deliver(&state, &test_config()).await, 1);
+
+    let payload = receiver.received()[0].json();
+    assert_eq!(payload["event"], "license.expired");
+    assert_eq!(payload["data"]["license"]["license_id"], license_id);
+    assert_eq!(payload["data"]["license"]["status"], "expired");
+}